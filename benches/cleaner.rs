@@ -0,0 +1,45 @@
+// * Content cleaner benchmark on a ~2MB page corpus
+// * `clean` parses each page once; `parse_twice` reproduces the former cost of re-parsing
+// * the serialized main content as a fragment, so the two should differ by about one parse.
+
+mod common;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use scraper::{Html, Selector};
+use titan_flow::refinery::ContentCleaner;
+
+// * Each page is padded to roughly this size
+const PAGE_BYTES: usize = 2 * 1024 * 1024;
+
+fn bench_cleaner(c: &mut Criterion) {
+    let cleaner = ContentCleaner::new();
+    let area = Selector::parse("article").unwrap();
+    let body = Selector::parse("body").unwrap();
+    let mut group = c.benchmark_group("cleaner_2mb");
+    group.sample_size(10);
+
+    for (name, page) in [
+        ("article", common::long_article_page(PAGE_BYTES)),
+        ("div_soup", common::div_soup_page(PAGE_BYTES)),
+    ] {
+        group.throughput(Throughput::Bytes(page.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("parse_document", name), &page, |b, page| {
+            b.iter(|| Html::parse_document(black_box(page)))
+        });
+        group.bench_with_input(BenchmarkId::new("parse_twice", name), &page, |b, page| {
+            b.iter(|| {
+                let document = Html::parse_document(black_box(page));
+                let main = document.select(&area).next().or_else(|| document.select(&body).next());
+                main.map(|main| Html::parse_fragment(&main.html()))
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("clean", name), &page, |b, page| {
+            b.iter(|| cleaner.clean(black_box(page)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_cleaner);
+criterion_main!(benches);
@@ -0,0 +1,109 @@
+// * Shared benchmark fixtures
+// * Pages and texts are generated rather than checked in, so sizes can be tuned per bench.
+
+#![allow(dead_code)]
+
+/// A news-style article page (~20KB): navigation, article body, a data table, related links, footer
+pub fn news_page() -> String {
+    let mut html = String::from(
+        r#"<html lang="en"><head><title>City council approves transit plan - Example News</title>
+        <meta name="description" content="The council voted to fund three new tram lines.">
+        <meta property="article:published_time" content="2024-03-12T09:30:00Z">
+        <script type="application/ld+json">{"@type": "NewsArticle", "headline": "City council approves transit plan", "author": {"name": "Jane Reporter"}}</script>
+        </head><body>
+        <header><nav><ul><li><a href="/">Home</a></li><li><a href="/politics">Politics</a></li><li><a href="/business">Business</a></li></ul></nav></header>
+        <article><h1>City council approves transit plan</h1><p class="byline">By Jane Reporter</p>"#,
+    );
+    for section in 0..12 {
+        html.push_str(&format!("<h2>Part {section}</h2>"));
+        for paragraph in 0..4 {
+            html.push_str(&format!(
+                "<p>Council members debated the proposal for {paragraph} hours on day {section}, weighing the \
+                 cost of the new lines against projected ridership. Contact press@example.gov or call \
+                 +1 555 0100 for the full minutes, published on 2024-03-{:02}.</p>",
+                section + 1
+            ));
+        }
+    }
+    html.push_str("<table><caption>Budget by line</caption><tr><th>Line</th><th>Cost (M)</th><th>Riders/day</th></tr>");
+    for line in 1..=8 {
+        html.push_str(&format!("<tr><td>Line {line}</td><td>{}</td><td>{}</td></tr>", line * 120, line * 4_500));
+    }
+    html.push_str("</table></article><aside><h3>Related</h3><ul>");
+    for related in 0..10 {
+        html.push_str(&format!("<li><a href=\"/politics/story-{related}\">Related story {related}</a></li>"));
+    }
+    html.push_str("</ul></aside><footer><p>Copyright 2024 Example News. All rights reserved.</p></footer></body></html>");
+    html
+}
+
+/// A long article page padded to about `target_bytes`
+pub fn long_article_page(target_bytes: usize) -> String {
+    let mut html = String::from(
+        r#"<html lang="en"><head><title>Benchmark</title></head><body>
+        <nav><ul><li><a href="/">Home</a></li><li><a href="/news">News</a></li></ul></nav>
+        <article><h1>A very long article</h1>"#,
+    );
+    let mut section = 0;
+    while html.len() < target_bytes {
+        section += 1;
+        html.push_str(&format!("<h2>Section {section}</h2>"));
+        for paragraph in 0..10 {
+            html.push_str(&format!(
+                "<p>Paragraph {paragraph} of section {section} explains the findings in detail, \
+                 with <a href=\"/ref/{section}/{paragraph}\">a reference</a> and <em>some emphasis</em> \
+                 so the text carries realistic inline markup.</p>"
+            ));
+        }
+        html.push_str("<table><tr><th>Metric</th><th>Value</th></tr><tr><td>Rows</td><td>42</td></tr></table>");
+    }
+    html.push_str("</article><footer><p>Copyright 2024. All rights reserved.</p></footer></body></html>");
+    html
+}
+
+/// A page padded to about `target_bytes` without a semantic main area (the cleaner falls back to <body>)
+pub fn div_soup_page(target_bytes: usize) -> String {
+    let mut html = String::from(r#"<html><body><div class="wrapper">"#);
+    let mut block = 0;
+    while html.len() < target_bytes {
+        block += 1;
+        html.push_str(&format!(
+            "<div class=\"row\"><div class=\"cell\"><span>Item {block}</span>\
+             <p>Listing {block} describes a product with enough words to count as a paragraph.</p>\
+             <ul><li>Feature one of item {block} is described here</li><li>Feature two</li></ul></div></div>"
+        ));
+    }
+    html.push_str("</div></body></html>");
+    html
+}
+
+/// Plain prose of `words` words; each `seed` yields a different pseudo-random text
+pub fn prose(words: usize, seed: usize) -> String {
+    const VOCABULARY: &[&str] = &[
+        "the", "council", "transit", "budget", "line", "riders", "vote", "plan", "city", "cost", "tram",
+        "station", "route", "funding", "proposal", "members", "debate", "public", "service", "network",
+        "schedule", "capacity", "growth", "district", "project", "review", "report", "impact", "of", "and",
+        "weekly", "delays", "bridge", "harbor", "tickets", "fares", "mayor", "residents", "survey", "data",
+    ];
+    // * Linear congruential generator: deterministic, no dependency
+    let mut state = (seed as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+    (0..words)
+        .map(|_| {
+            state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            VOCABULARY[(state >> 33) as usize % VOCABULARY.len()]
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Links as (url, anchor text) pairs, mixing content, navigation and low-value URLs
+pub fn links(count: usize) -> Vec<(String, String)> {
+    (0..count)
+        .map(|i| match i % 4 {
+            0 => (format!("https://example.com/articles/2024/transit-plan-{i}"), "Read the full transit report".to_string()),
+            1 => (format!("https://example.com/category/politics?page={i}"), "Next page".to_string()),
+            2 => (format!("https://example.com/login?redirect=/story-{i}"), "Log in".to_string()),
+            _ => (format!("https://cdn.example.com/assets/image-{i}.jpg"), String::new()),
+        })
+        .collect()
+}
@@ -0,0 +1,112 @@
+// * Hot path benchmarks: refinery, dedup (MinHash + LSH), chunking, link scoring and the link queue
+// * Baselines for optimization work; run with `cargo bench --bench hot_paths`.
+
+mod common;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use titan_flow::persistence::{LSHIndex, LinkScorer, MinHashSignature, MinHashStrategy, PriorityLinkQueue};
+use titan_flow::refinery::{chunk_text, Refinery, SlidingWindowChunker};
+
+// * Documents already in the index when a new one is checked
+const INDEXED_DOCUMENTS: usize = 1_000;
+
+fn bench_refinery(c: &mut Criterion) {
+    let refinery = Refinery::new();
+    let mut group = c.benchmark_group("refinery_process");
+    group.sample_size(20);
+
+    for (name, page) in [
+        ("news_20kb", common::news_page()),
+        ("article_500kb", common::long_article_page(500 * 1024)),
+    ] {
+        group.throughput(Throughput::Bytes(page.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &page, |b, page| {
+            b.iter(|| refinery.process(black_box(page)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_minhash(c: &mut Criterion) {
+    let mut group = c.benchmark_group("minhash_from_text");
+
+    for words in [200, 2_000, 20_000] {
+        let text = common::prose(words, 1);
+        group.throughput(Throughput::Elements(words as u64));
+        for strategy in [MinHashStrategy::Serial, MinHashStrategy::Blocked, MinHashStrategy::Parallel] {
+            let id = BenchmarkId::new(format!("{strategy:?}").to_lowercase(), words);
+            group.bench_with_input(id, &text, |b, text| {
+                b.iter(|| MinHashSignature::from_text_with(black_box(text), "doc".to_string(), strategy))
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bench_lsh_index(c: &mut Criterion) {
+    let mut index = LSHIndex::new();
+    for i in 0..INDEXED_DOCUMENTS {
+        index.index_document(&common::prose(300, i), &format!("doc-{i}"));
+    }
+    let unique = common::prose(300, INDEXED_DOCUMENTS + 1);
+    let duplicate = common::prose(300, 7);
+
+    let mut group = c.benchmark_group("lsh_index_document");
+    for (name, text) in [("unique", &unique), ("duplicate", &duplicate)] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let result = index.index_document(black_box(text), "candidate");
+                // * Keep the index at a fixed size across iterations (a no-op for duplicates)
+                index.remove_document("candidate");
+                result
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_chunking(c: &mut Criterion) {
+    let chunker = SlidingWindowChunker::new();
+    let mut group = c.benchmark_group("chunking");
+
+    for words in [1_000, 10_000] {
+        let text = common::prose(words, 3);
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_with_input(BenchmarkId::new("sliding_window", words), &text, |b, text| {
+            b.iter(|| chunker.chunk(black_box(text)))
+        });
+        group.bench_with_input(BenchmarkId::new("chunk_text", words), &text, |b, text| {
+            b.iter(|| chunk_text(black_box(text)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_link_scoring(c: &mut Criterion) {
+    let scorer = LinkScorer::new();
+    let links = common::links(1_000);
+    let mut group = c.benchmark_group("link_scoring");
+    group.throughput(Throughput::Elements(links.len() as u64));
+
+    group.bench_function("score_batch_1000", |b| b.iter(|| scorer.score_batch(black_box(&links))));
+    group.bench_function("queue_push_pop_1000", |b| {
+        b.iter(|| {
+            let mut queue = PriorityLinkQueue::new(links.len());
+            queue.push_many(links.iter().map(|(url, text)| (url.as_str(), text.as_str())), "https://example.com/", 0);
+            while let Some(link) = queue.pop() {
+                black_box(link);
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_refinery,
+    bench_minhash,
+    bench_lsh_index,
+    bench_chunking,
+    bench_link_scoring
+);
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+// * Build script: compiles proto/titan.proto for the `grpc` feature
+// * protoc comes from protoc-bin-vendored so no system install is needed
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto/titan.proto");
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc is available");
+        std::env::set_var("PROTOC", protoc);
+        tonic_build::configure()
+            .build_client(true)
+            .compile(&["proto/titan.proto"], &["proto"])
+            .expect("proto/titan.proto compiles");
+    }
+}
@@ -1,13 +1,1021 @@
-// use titan_flow::config; // (Reserved for future use)
+// * [NFR-01] Titan-Flow CLI
+// * Subcommands: crawl, extract, enrich, metrics-server
+
+use clap::{Args, Parser, Subcommand};
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use titan_flow::config::{self, ConfigFormat, ConfigWatcher, TitanConfig};
+use titan_flow::ops::{start_metrics_server_with_stats, AlertManager, Coordinator};
+use titan_flow::network::cache::RedisValidatorStore;
+use titan_flow::orchestrator::{
+    seed_source, CheckpointStore, DirectoryCheckpointStore, Orchestrator, SeedFormat, SeedLoader,
+};
+use titan_flow::persistence::{
+    load_sample, load_samples, read_jsonl_records, reprocess_records, warc_files, AuditReport, AuditSampler,
+    ChangeEvent, ChangeTracker, ChangeTrackerConfig, ContentHashMode, EnrichmentFilter, EnrichmentPipelineBuilder,
+    InMemoryRecordStore, JsonlRecordSink, LanceRecordStore, MultimodalRecord, RecordFilter, RecordProvider,
+    RecordUpdater, SnapshotStore, WarcReader, WarcWriter, WebhookSink, WorkerStats,
+};
+use titan_flow::refinery::{GoldenHarness, RedactionPolicy, Refinery};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+#[cfg(feature = "otel")]
+use titan_flow::ops::otel;
+#[cfg(not(feature = "otel"))]
+use tracing_subscriber::layer::Identity;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+// * Output file names written by `crawl`
+const RECORDS_FILE: &str = "records.jsonl";
+const REPORT_FILE: &str = "report.json";
+const CHECKPOINT_DIR: &str = "checkpoint";
+const VERSIONS_FILE: &str = "page_versions.json";
+const CHANGES_FILE: &str = "changes.jsonl";
+
+// * Enrichment worker poll interval for one-shot CLI runs
+const ENRICH_POLL_INTERVAL_MS: u64 = 50;
+
+type CliResult = Result<(), Box<dyn Error>>;
+
+#[derive(Debug, Parser)]
+#[command(name = "titan-flow", version, about = "Titan-Flow crawler and extraction pipeline")]
+struct Cli {
+    /// TOML or YAML config file (TITAN_* environment variables override it)
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// Tracing filter (e.g. "info", "titan_flow=debug"); overrides telemetry.log_level
+    #[arg(long, global = true)]
+    log_level: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Crawl from a seed list and write records to an output directory
+    Crawl(CrawlArgs),
+    /// Run the refinery over a local HTML file and print the JSON result
+    Extract(ExtractArgs),
+    /// Compute embeddings and sentiment for records in a store
+    Enrich(EnrichArgs),
+    /// Re-run the refinery over archived HTML and update the stored records in place
+    Reprocess(ReprocessArgs),
+    /// Print the raw HTML a record was extracted from (its `html_snapshot` hash)
+    Snapshot(SnapshotArgs),
+    /// Diff refinery output over saved pages against their golden JSON files
+    Golden(GoldenArgs),
+    /// Summarize the pages sampled for quality audits, or print one sample
+    Audit(AuditArgs),
+    /// Serve Prometheus metrics until interrupted
+    MetricsServer(MetricsServerArgs),
+    /// Serve the gRPC API (crawl submission, record lookup, similarity search)
+    #[cfg(feature = "grpc")]
+    Grpc(GrpcArgs),
+    /// Serve on-demand extraction (POST /extract) until interrupted
+    #[cfg(feature = "http-api")]
+    ExtractServer(ExtractServerArgs),
+}
+
+#[derive(Debug, Args)]
+struct CrawlArgs {
+    /// Seed list: a file with one URL per line ('#' starts a comment), a .csv file with a
+    /// "url" column, an http(s) URL to download the list from, or "-" for stdin
+    #[arg(long, required_unless_present = "resume")]
+    seeds: Option<String>,
+    /// Seed list layout, "lines" or "csv" (default: csv for .csv paths, lines otherwise)
+    #[arg(long, value_parser = parse_seed_format)]
+    seed_format: Option<SeedFormat>,
+    /// Skip seeds already stored in <out>/records.jsonl by an earlier crawl
+    #[arg(long)]
+    skip_crawled: bool,
+    /// Output directory for records.jsonl and report.json
+    #[arg(long, default_value = "./data")]
+    out: PathBuf,
+    /// Maximum number of pages to fetch
+    #[arg(long)]
+    max_pages: Option<usize>,
+    /// Maximum link depth from the seeds
+    #[arg(long)]
+    max_depth: Option<usize>,
+    /// Maximum number of pages processed concurrently
+    #[arg(long)]
+    concurrency: Option<usize>,
+    /// Redis URL for shared rate limiting and circuit breaker state
+    #[arg(long)]
+    redis_url: Option<String>,
+    /// Follow links to hosts other than the seeds'
+    #[arg(long)]
+    allow_offsite: bool,
+    /// Ignore robots.txt
+    #[arg(long)]
+    no_robots: bool,
+    /// Never launch the headless browser
+    #[arg(long)]
+    no_slow_path: bool,
+    /// Skip AI enrichment of crawled records
+    #[arg(long)]
+    no_enrich: bool,
+    /// Redact PII from persisted text
+    #[arg(long)]
+    redact: bool,
+    /// Directory to write crawl checkpoints to (default: <out>/checkpoint)
+    #[arg(long)]
+    checkpoint_dir: Option<PathBuf>,
+    /// Continue from the checkpoint instead of starting from the seeds
+    #[arg(long)]
+    resume: bool,
+    /// Revalidate pages with ETag/Last-Modified kept in Redis; unchanged pages are skipped
+    #[arg(long)]
+    http_cache: bool,
+    /// Compare pages with <out>/page_versions.json and append changes to <out>/changes.jsonl
+    #[arg(long)]
+    track_changes: bool,
+    /// With --track-changes, ignore timestamps, counters and related-article blocks when comparing
+    #[arg(long, requires = "track_changes")]
+    structural_hash: bool,
+    /// Archive fetched and rendered pages to this WARC file (gzip per record if it ends in .gz)
+    #[arg(long)]
+    warc: Option<PathBuf>,
+    /// Also store records in this LanceDB table (lancedb://<database>/<table>) for `enrich`
+    #[arg(long)]
+    lancedb: Option<String>,
+    /// Serve /metrics and the live /events stream on this port while the crawl runs
+    #[arg(long)]
+    metrics_port: Option<u16>,
+    /// Serve the REST control plane on this address while the crawl runs
+    #[cfg(feature = "http-api")]
+    #[arg(long)]
+    api_addr: Option<std::net::SocketAddr>,
+}
+
+#[derive(Debug, Args)]
+struct ExtractArgs {
+    /// HTML file to process
+    file: PathBuf,
+    /// Pretty-print the JSON output
+    #[arg(long)]
+    pretty: bool,
+    /// Redact PII from the extracted text and chunks
+    #[arg(long)]
+    redact: bool,
+}
+
+#[derive(Debug, Args)]
+struct EnrichArgs {
+    /// Record store: a records.jsonl path, jsonl://<path> or lancedb://<database>/<table>
+    #[arg(long)]
+    store: String,
+    /// Records processed per worker batch (default: worker.batch_size)
+    #[arg(long)]
+    batch_size: Option<usize>,
+    /// Give up on records still unenriched after this many seconds
+    #[arg(long, default_value_t = 60)]
+    timeout_secs: u64,
+}
+
+#[derive(Debug, Args)]
+struct ReprocessArgs {
+    /// WARC archive (.warc or .warc.gz) or a directory of them, as written by `crawl --warc`
+    #[arg(long)]
+    input: PathBuf,
+    /// Record store to update: a records.jsonl path, jsonl://<path> or lancedb://<database>/<table>
+    #[arg(long, default_value = "./data/records.jsonl")]
+    store: String,
+}
+
+#[derive(Debug, Args)]
+struct SnapshotArgs {
+    /// Snapshot hash from a record's html_snapshot field
+    hash: String,
+    /// Snapshot directory (default: snapshots.dir)
+    #[arg(long)]
+    dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct AuditArgs {
+    /// Sample ID to print; without one every sample is listed, worst quality first
+    id: Option<String>,
+    /// Audit directory (default: audit.dir)
+    #[arg(long)]
+    dir: Option<PathBuf>,
+    /// Print the sample's raw HTML instead of its refinery result
+    #[arg(long, requires = "id")]
+    html: bool,
+    /// Quality score below which samples count as low quality
+    #[arg(long, default_value_t = 0.4)]
+    min_quality: f32,
+}
+
+#[derive(Debug, Args)]
+struct GoldenArgs {
+    /// Directory of .html fixtures (per-site subdirectories are refined with that domain's settings)
+    #[arg(long)]
+    fixtures: PathBuf,
+    /// Rewrite the golden files that changed or are missing instead of failing
+    #[arg(long)]
+    update: bool,
+    /// Field to leave out of the diff, e.g. "stats.quality_score" (repeatable)
+    #[arg(long)]
+    ignore: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+struct MetricsServerArgs {
+    /// Port to bind the /metrics endpoint on (default: telemetry.metrics_port)
+    #[arg(long)]
+    port: Option<u16>,
+}
+
+#[cfg(feature = "grpc")]
+#[derive(Debug, Args)]
+struct GrpcArgs {
+    /// Address to bind the gRPC server on
+    #[arg(long, default_value = "0.0.0.0:50051")]
+    addr: std::net::SocketAddr,
+}
+
+#[cfg(feature = "http-api")]
+#[derive(Debug, Args)]
+struct ExtractServerArgs {
+    /// Address to bind the extraction endpoint on
+    #[arg(long, default_value = "0.0.0.0:8081")]
+    addr: std::net::SocketAddr,
+}
 
 #[tokio::main]
-async fn main() {
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let config = match load_config(cli.config.as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
     // Initialize Telemetry [NFR-01]
-    tracing_subscriber::fmt()
-        .with_env_filter("titan_flow=debug,info")
+    // * Logs go to stderr so command output on stdout stays machine-readable
+    let log_level = cli.log_level.as_deref().unwrap_or(&config.telemetry.log_level);
+    let logs = tracing_subscriber::fmt::layer()
         .with_target(false)
-        .json()
+        .with_writer(std::io::stderr);
+    let logs = if config.telemetry.json_logs {
+        logs.json().boxed()
+    } else {
+        logs.boxed()
+    };
+    // * Per-URL spans go to the OTLP collector when one is configured
+    #[cfg(feature = "otel")]
+    let traces = match config.telemetry.otlp_endpoint.as_deref().map(otel::otlp_layer) {
+        Some(Ok(layer)) => Some(layer),
+        Some(Err(e)) => {
+            eprintln!("error: Failed to start the OTLP exporter: {}", e);
+            return ExitCode::FAILURE;
+        }
+        None => None,
+    };
+    #[cfg(not(feature = "otel"))]
+    let traces: Option<Identity> = None;
+    tracing_subscriber::registry()
+        .with(logs)
+        .with(traces)
+        .with(EnvFilter::new(log_level))
         .init();
+    #[cfg(not(feature = "otel"))]
+    if config.telemetry.otlp_endpoint.is_some() {
+        tracing::warn!("telemetry.otlp_endpoint is set but this build lacks the `otel` feature");
+    }
+
+    let result = match cli.command {
+        Command::Crawl(args) => crawl(args, &config, cli.config.as_deref()).await,
+        Command::Extract(args) => extract(args, &config),
+        Command::Enrich(args) => enrich(args, &config).await,
+        Command::Reprocess(args) => reprocess(args, &config).await,
+        Command::Snapshot(args) => snapshot(args, &config),
+        Command::Golden(args) => golden(args, &config),
+        Command::Audit(args) => audit(args, &config),
+        Command::MetricsServer(args) => metrics_server(args, &config).await,
+        #[cfg(feature = "grpc")]
+        Command::Grpc(args) => grpc(args, &config).await,
+        #[cfg(feature = "http-api")]
+        Command::ExtractServer(args) => extract_server(args).await,
+    };
+
+    #[cfg(feature = "otel")]
+    if config.telemetry.otlp_endpoint.is_some() {
+        let _ = tokio::task::spawn_blocking(otel::shutdown_tracing).await;
+    }
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Loads the config file, or applies environment overrides to the defaults
+fn load_config(path: Option<&Path>) -> Result<TitanConfig, config::ConfigError> {
+    match path {
+        Some(path) => config::load(path),
+        None => config::parse("", ConfigFormat::Toml, std::env::vars()),
+    }
+}
+
+async fn crawl(args: CrawlArgs, titan: &TitanConfig, config_path: Option<&Path>) -> CliResult {
+    let checkpoints = Arc::new(DirectoryCheckpointStore::new(
+        args.checkpoint_dir.clone().unwrap_or_else(|| args.out.join(CHECKPOINT_DIR)),
+    ));
+    let checkpoint = if args.resume {
+        let checkpoint = checkpoints.load().await?.ok_or_else(|| {
+            format!("No checkpoint found at {}", checkpoints.path().display())
+        })?;
+        Some(checkpoint)
+    } else {
+        None
+    };
+    // * Seeds are read up front so a bad list fails before anything starts
+    let seeds = match (&checkpoint, &args.seeds) {
+        (None, Some(spec)) => {
+            let mut loader = SeedLoader::new().with_boxed_source(seed_source(spec, args.seed_format));
+            let records_path = args.out.join(RECORDS_FILE);
+            if args.skip_crawled && records_path.exists() {
+                let crawled = InMemoryRecordStore::new();
+                for record in read_records(&records_path)? {
+                    crawled.add(record);
+                }
+                loader = loader.with_crawled(Arc::new(crawled));
+            }
+            let batch = loader.load().await?;
+            if !batch.rejected.is_empty() {
+                eprintln!("Skipped {} invalid seeds", batch.rejected.len());
+            }
+            batch.urls()
+        }
+        // * clap guarantees --seeds when not resuming
+        _ => Vec::new(),
+    };
+
+    let mut config = titan.crawl_config();
+    if args.allow_offsite {
+        config.same_domain_only = false;
+    }
+    if args.no_robots {
+        config.respect_robots_txt = false;
+    }
+    if args.no_slow_path {
+        config.enable_slow_path = false;
+    }
+    if args.redis_url.is_some() {
+        config.redis_url = args.redis_url;
+    }
+    if let Some(max_pages) = args.max_pages {
+        config.max_pages = max_pages;
+    }
+    if let Some(max_depth) = args.max_depth {
+        config.max_depth = max_depth;
+    }
+    if let Some(concurrency) = args.concurrency {
+        config.concurrency = concurrency;
+    }
+    if args.no_enrich {
+        config.enable_enrichment = false;
+    }
+    if args.redact {
+        config.refinery.redaction = Some(RedactionPolicy::default());
+    }
+    // * crawl-summary.json / .md land next to report.json unless crawl.report.dir says otherwise
+    if config.report.dir.is_none() {
+        config.report.dir = Some(args.out.clone());
+    }
+
+    // * Rate limits and scorer weights can be tuned by editing the file mid-crawl
+    let watcher = config_path.map(ConfigWatcher::start).transpose()?;
+    // * Ctrl+C / SIGTERM stops dispatching; whatever was persisted is still written out
+    let coordinator = Arc::new(Coordinator::new());
+    let signals = coordinator.listen_for_signals();
+    let metrics = match args.metrics_port {
+        Some(port) => Some(start_metrics_server_with_stats(port, stats_interval(titan)).await),
+        None => None,
+    };
+    // * Domains raising the SEV-3 ban rate alert are slowed to the maximum crawl delay
+    let alerts = Arc::new(AlertManager::with_config(titan.alerts.clone()));
+    let mut orchestrator = Orchestrator::new()
+        .with_shutdown(coordinator.signal())
+        .with_checkpoint_store(checkpoints)
+        .with_alert_manager(Arc::clone(&alerts));
+    if args.http_cache {
+        let redis_url = config
+            .redis_url
+            .as_deref()
+            .ok_or("--http-cache needs --redis-url or crawl.redis_url")?;
+        orchestrator =
+            orchestrator.with_http_cache(Arc::new(RedisValidatorStore::connect(redis_url).await?));
+    }
+    // * Persisted records are pushed to webhook.url as they are stored
+    let webhook = match &titan.webhook.url {
+        Some(_) => Some(Arc::new(WebhookSink::new(titan.webhook.clone())?)),
+        None => None,
+    };
+    if let Some(webhook) = &webhook {
+        orchestrator = orchestrator.with_result_sink(webhook.clone());
+    }
+    // * Raw HTML of persisted pages is kept under snapshots.dir for debugging extraction
+    if titan.snapshots.dir.is_some() {
+        orchestrator = orchestrator.with_snapshot_store(Arc::new(SnapshotStore::open(titan.snapshots.clone())?));
+    }
+    // * A random share of processed pages is copied under audit.dir for `titan-flow audit`
+    if titan.audit.dir.is_some() {
+        orchestrator = orchestrator.with_audit_sampler(Arc::new(AuditSampler::open(titan.audit.clone())?));
+    }
+    if let Some(path) = &args.warc {
+        let gzip = path.extension().is_some_and(|ext| ext == "gz");
+        orchestrator = orchestrator.with_warc_archive(Arc::new(WarcWriter::create(path, gzip)?));
+    }
+    let mut change_log = None;
+    let tracker = if args.track_changes {
+        let hash_mode = if args.structural_hash {
+            ContentHashMode::Structural
+        } else {
+            ContentHashMode::Full
+        };
+        let tracker = Arc::new(ChangeTracker::with_config(ChangeTrackerConfig {
+            hash_mode,
+            ..Default::default()
+        }));
+        let versions_path = args.out.join(VERSIONS_FILE);
+        if versions_path.exists() {
+            tracker.restore(&serde_json::from_str(&fs::read_to_string(&versions_path)?)?);
+        }
+        fs::create_dir_all(&args.out)?;
+        change_log = Some(spawn_change_log(tracker.subscribe(), args.out.join(CHANGES_FILE)));
+        orchestrator = orchestrator.with_change_tracker(Arc::clone(&tracker));
+        Some(tracker)
+    } else {
+        None
+    };
+    if let Some(uri) = &args.lancedb {
+        let RecordStoreUri::Lance { uri, table } = parse_store(uri)? else {
+            return Err(format!("--lancedb expects lancedb://<database>/<table>, got '{}'", uri).into());
+        };
+        orchestrator = orchestrator.with_result_sink(Arc::new(LanceRecordStore::open(&uri, &table).await?));
+    }
+    // * Seeds, domain pauses and progress are exposed over HTTP for the crawl's lifetime
+    #[cfg(feature = "http-api")]
+    let api = match args.api_addr {
+        Some(addr) => {
+            let control = Arc::new(titan_flow::orchestrator::CrawlControl::new());
+            orchestrator = orchestrator.with_control(Arc::clone(&control));
+            let extractor = Arc::new(titan_flow::orchestrator::Extractor::new()?);
+            let router = titan_flow::server::router(control, orchestrator.store().clone())
+                .merge(titan_flow::server::extract_router(extractor))
+                .merge(titan_flow::server::domain_stats_router(alerts.domain_stats()));
+            let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+            let server = tokio::spawn(titan_flow::server::http::serve(router, addr, async move {
+                stopped.await.ok();
+            }));
+            Some((stop, server))
+        }
+        None => None,
+    };
+    let mut alert_binding = None;
+    if let Some(watcher) = &watcher {
+        orchestrator = orchestrator.with_config_watcher(watcher);
+        alert_binding = Some(watcher.bind_alert_manager(alerts));
+    }
+    // * Records are appended to records.jsonl as they are stored; a resumed crawl adds to
+    // * the records written by the earlier run
+    let resuming = checkpoint.is_some();
+    fs::create_dir_all(&args.out)?;
+    let record_log = Arc::new(JsonlRecordSink::create(args.out.join(RECORDS_FILE), resuming)?);
+    orchestrator = orchestrator.with_result_sink(record_log.clone());
+    let report = match checkpoint {
+        Some(checkpoint) => orchestrator.resume(checkpoint, config).await?,
+        None => orchestrator.run(seeds, config).await?,
+    };
+    signals.abort();
+    if let Some(binding) = alert_binding {
+        binding.abort();
+    }
+    if let Some(metrics) = metrics {
+        metrics.shutdown();
+    }
+    #[cfg(feature = "http-api")]
+    if let Some((stop, server)) = api {
+        stop.send(()).ok();
+        server.await??;
+    }
+    if let Some(webhook) = webhook {
+        webhook.close().await;
+        let stats = webhook.stats();
+        if stats.failed + stats.dropped > 0 {
+            eprintln!(
+                "Webhook: {} delivered, {} failed, {} dropped",
+                stats.delivered, stats.failed, stats.dropped
+            );
+        }
+    }
+
+    // * Enrichment that finished during the run supersedes the crawled copies already logged
+    if report.records_enriched > 0 {
+        for record in orchestrator.store().records().iter().filter(|r| !r.needs_enrichment()) {
+            record_log.append(record)?;
+        }
+    }
+    fs::write(args.out.join(REPORT_FILE), serde_json::to_string_pretty(&report)?)?;
+
+    if let Some(tracker) = tracker {
+        fs::write(args.out.join(VERSIONS_FILE), serde_json::to_string(&tracker.snapshot())?)?;
+        // * The event channel closes once the orchestrator and tracker are gone
+        drop(orchestrator);
+        drop(tracker);
+    }
+    if let Some(change_log) = change_log {
+        change_log.await??;
+    }
+
+    println!("{}", serde_json::to_string(&report)?);
+    Ok(())
+}
+
+fn extract(args: ExtractArgs, titan: &TitanConfig) -> CliResult {
+    let html = fs::read_to_string(&args.file)?;
+
+    let mut config = titan.refinery.clone();
+    if args.redact {
+        config.redaction = Some(RedactionPolicy::default());
+    }
+    let result = Refinery::with_config(config).process(&html);
+
+    if args.pretty {
+        println!("{}", result.to_json_pretty());
+    } else {
+        println!("{}", result.to_json());
+    }
+    Ok(())
+}
+
+async fn enrich(args: EnrichArgs, titan: &TitanConfig) -> CliResult {
+    let (records, stats) = match parse_store(&args.store)? {
+        RecordStoreUri::Jsonl(path) => {
+            let store = Arc::new(InMemoryRecordStore::new());
+            for record in read_records(&path)? {
+                store.add(record);
+            }
+            let stats = run_enrichment(Arc::clone(&store), &args, titan).await;
+            write_records(&path, &store.records())?;
+            (store.count(), stats)
+        }
+        // * LanceDB rows are updated in place as the worker enriches them
+        RecordStoreUri::Lance { uri, table } => {
+            let store = LanceRecordStore::open(&uri, &table).await?;
+            let stats = run_enrichment(store.clone(), &args, titan).await;
+            (store.count().await?, stats)
+        }
+    };
+
+    println!(
+        "{}",
+        serde_json::json!({
+            "records": records,
+            "enriched": stats.processed_count,
+            "errors": stats.error_count,
+        })
+    );
+    Ok(())
+}
+
+// * Runs the enrichment worker until nothing is pending or --timeout-secs passes
+async fn run_enrichment<S>(store: S, args: &EnrichArgs, titan: &TitanConfig) -> WorkerStats
+where
+    S: RecordProvider + RecordUpdater + Clone + 'static,
+{
+    let worker = EnrichmentPipelineBuilder::new()
+        .batch_size(args.batch_size.unwrap_or(titan.worker.batch_size).max(1))
+        .poll_interval_ms(ENRICH_POLL_INTERVAL_MS)
+        .max_retries(titan.worker.max_retries)
+        .with_embeddings(titan.worker.compute_embeddings)
+        .with_sentiment(titan.worker.compute_sentiment)
+        .build();
+    let handle = worker.start(store.clone(), store.clone()).await;
+
+    // * Records that fail enrichment (e.g. empty text) stay pending until the timeout
+    let deadline = Instant::now() + Duration::from_secs(args.timeout_secs);
+    while Instant::now() < deadline {
+        match store.fetch_unenriched(EnrichmentFilter::new(1)).await {
+            Ok(batch) if !batch.is_empty() => {
+                tokio::time::sleep(Duration::from_millis(ENRICH_POLL_INTERVAL_MS)).await;
+            }
+            _ => break,
+        }
+    }
+    handle.shutdown().await;
+    worker.stats()
+}
+
+// * Refinery settings come from --config, so a new cleaner/chunker setup is applied to old pages
+async fn reprocess(args: ReprocessArgs, titan: &TitanConfig) -> CliResult {
+    let mut pages = Vec::new();
+    for archive in warc_files(&args.input)? {
+        for page in WarcReader::open(&archive)?.pages() {
+            pages.push(page.map_err(|e| format!("{}: {}", archive.display(), e))?);
+        }
+    }
+    let refinery = Refinery::with_config(titan.refinery.clone());
+
+    let report = match parse_store(&args.store)? {
+        RecordStoreUri::Jsonl(path) => {
+            let mut records = read_records(&path)?;
+            let report = reprocess_records(&mut records, pages, &refinery);
+            write_records(&path, &records)?;
+            report
+        }
+        // * Soft-deleted rows are revised too, matching the records.jsonl store
+        RecordStoreUri::Lance { uri, table } => {
+            let store = LanceRecordStore::open(&uri, &table).await?;
+            let mut records = store
+                .fetch_records(RecordFilter::new().include_deleted(true), 0, usize::MAX)
+                .await?;
+            let report = reprocess_records(&mut records, pages, &refinery);
+            store.upsert(&records).await?;
+            report
+        }
+    };
+    println!("{}", serde_json::to_string(&report)?);
+    Ok(())
+}
+
+fn snapshot(args: SnapshotArgs, titan: &TitanConfig) -> CliResult {
+    let mut config = titan.snapshots.clone();
+    if args.dir.is_some() {
+        config.dir = args.dir;
+    }
+    if config.dir.is_none() {
+        return Err("No snapshot directory: pass --dir or set snapshots.dir".into());
+    }
+    let store = SnapshotStore::open(config)?;
+    let html = store
+        .get(&args.hash)?
+        .ok_or_else(|| format!("No snapshot {} (never stored or evicted)", args.hash))?;
+    print!("{}", html);
+    Ok(())
+}
+
+fn audit(args: AuditArgs, titan: &TitanConfig) -> CliResult {
+    let dir = args
+        .dir
+        .or_else(|| titan.audit.dir.clone())
+        .ok_or("No audit directory: pass --dir or set audit.dir")?;
+    match args.id {
+        Some(id) => {
+            let sample = load_sample(&dir, &id)?;
+            if args.html {
+                print!("{}", sample.html);
+            } else {
+                println!("{}", serde_json::to_string_pretty(&sample.result)?);
+            }
+        }
+        None => print!("{}", AuditReport::new(&load_samples(&dir)?, args.min_quality).render()),
+    }
+    Ok(())
+}
+
+// * Refinery settings come from --config, so proposed heuristics are diffed against the goldens
+fn golden(args: GoldenArgs, titan: &TitanConfig) -> CliResult {
+    let mut harness = GoldenHarness::new(&args.fixtures).with_refinery(Refinery::with_config(titan.refinery.clone()));
+    for path in args.ignore {
+        harness = harness.with_ignored(path);
+    }
+
+    if args.update {
+        let written = harness.update()?;
+        println!("Updated {} golden files", written);
+        return Ok(());
+    }
+    let report = harness.run()?;
+    print!("{}", report.render());
+    if !report.is_clean() {
+        return Err(format!(
+            "{} fixtures differ from their goldens; review the diffs and rerun with --update to accept them",
+            report.failures().count()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Interval between automatic throughput and success-rate publications
+fn stats_interval(titan: &TitanConfig) -> Duration {
+    Duration::from_secs(titan.telemetry.stats_interval_secs)
+}
+
+async fn metrics_server(args: MetricsServerArgs, titan: &TitanConfig) -> CliResult {
+    let port = args.port.unwrap_or(titan.telemetry.metrics_port);
+    let coordinator = Arc::new(Coordinator::new());
+    coordinator.register_metrics_server(start_metrics_server_with_stats(port, stats_interval(titan)).await);
+    coordinator.listen_for_signals();
+    tracing::info!(port, "Serving metrics - press Ctrl+C to stop");
+
+    coordinator.signal().triggered().await;
+    coordinator.shutdown().await;
+    Ok(())
+}
+
+#[cfg(feature = "grpc")]
+async fn grpc(args: GrpcArgs, titan: &TitanConfig) -> CliResult {
+    use titan_flow::server::{serve, TitanFlowService};
+
+    let coordinator = Arc::new(Coordinator::new());
+    coordinator.listen_for_signals();
+    // * Ctrl+C stops the server and drains running crawls
+    let orchestrator = Orchestrator::new().with_shutdown(coordinator.signal());
+    let service = TitanFlowService::new(orchestrator, titan.crawl_config());
+
+    let signal = coordinator.signal();
+    serve(service, args.addr, async move { signal.triggered().await }).await?;
+    coordinator.shutdown().await;
+    Ok(())
+}
+
+#[cfg(feature = "http-api")]
+async fn extract_server(args: ExtractServerArgs) -> CliResult {
+    use titan_flow::orchestrator::Extractor;
+    use titan_flow::server::{extract_router, http::serve};
+
+    let coordinator = Arc::new(Coordinator::new());
+    coordinator.listen_for_signals();
+    let extractor = Arc::new(Extractor::new()?);
+    let signal = coordinator.signal();
+    serve(extract_router(Arc::clone(&extractor)), args.addr, async move {
+        signal.triggered().await
+    })
+    .await?;
+    extractor.shutdown().await;
+    Ok(())
+}
+
+/// Record store named by a `--store` URI
+#[derive(Debug, PartialEq)]
+enum RecordStoreUri {
+    /// A records.jsonl file
+    Jsonl(PathBuf),
+    /// A table in a LanceDB database directory
+    Lance { uri: String, table: String },
+}
+
+fn parse_seed_format(format: &str) -> Result<SeedFormat, String> {
+    match format {
+        "lines" => Ok(SeedFormat::Lines),
+        "csv" => Ok(SeedFormat::Csv),
+        _ => Err(format!("Expected \"lines\" or \"csv\", got '{}'", format)),
+    }
+}
+
+/// Parses `<path>`, `jsonl://<path>` or `lancedb://<database>/<table>`
+fn parse_store(uri: &str) -> Result<RecordStoreUri, String> {
+    if let Some(path) = uri.strip_prefix("jsonl://") {
+        return Ok(RecordStoreUri::Jsonl(PathBuf::from(path)));
+    }
+    if let Some(rest) = uri.strip_prefix("lancedb://") {
+        return match rest.trim_end_matches('/').rsplit_once('/') {
+            Some((database, table)) if !database.is_empty() && !table.is_empty() => Ok(RecordStoreUri::Lance {
+                uri: database.to_string(),
+                table: table.to_string(),
+            }),
+            _ => Err(format!("Expected lancedb://<database>/<table>, got '{}'", uri)),
+        };
+    }
+    if let Some((scheme, _)) = uri.split_once("://") {
+        return Err(format!("Unsupported store scheme '{}'", scheme));
+    }
+    Ok(RecordStoreUri::Jsonl(PathBuf::from(uri)))
+}
+
+/// Reads a records.jsonl log; the last line written for a record id wins
+fn read_records(path: &Path) -> Result<Vec<MultimodalRecord>, Box<dyn Error>> {
+    Ok(read_jsonl_records(path)?)
+}
+
+// * Appends every change event to a JSON-lines file until the tracker is dropped
+fn spawn_change_log(
+    mut events: broadcast::Receiver<ChangeEvent>,
+    path: PathBuf,
+) -> JoinHandle<io::Result<()>> {
+    tokio::spawn(async move {
+        let file = fs::OpenOptions::new().create(true).append(true).open(&path)?;
+        let mut writer = BufWriter::new(file);
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    serde_json::to_writer(&mut writer, &event)?;
+                    writer.write_all(b"\n")?;
+                }
+                Err(RecvError::Lagged(missed)) => {
+                    tracing::warn!(missed, "Change log fell behind; events dropped");
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+        writer.flush()
+    })
+}
+
+/// Writes records as newline-delimited JSON, replacing the file
+fn write_records(path: &Path, records: &[MultimodalRecord]) -> Result<(), Box<dyn Error>> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    for record in records {
+        serde_json::to_writer(&mut writer, record)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_crawl_subcommand() {
+        let cli = Cli::try_parse_from([
+            "titan-flow", "crawl", "--seeds", "seeds.txt", "--out", "./out", "--max-pages", "5",
+            "--no-enrich",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Command::Crawl(args) => {
+                assert_eq!(args.seeds.as_deref(), Some("seeds.txt"));
+                assert!(!args.skip_crawled);
+                assert!(!args.resume);
+                assert_eq!(args.out, PathBuf::from("./out"));
+                assert_eq!(args.max_pages, Some(5));
+                assert!(args.no_enrich);
+                assert!(!args.no_robots);
+                assert!(!args.http_cache);
+                assert!(!args.track_changes);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_crawl_resume() {
+        let cli = Cli::try_parse_from([
+            "titan-flow", "crawl", "--resume", "--checkpoint-dir", "./ckpt", "--http-cache",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Command::Crawl(args) => {
+                assert!(args.resume);
+                assert_eq!(args.seeds, None);
+                assert_eq!(args.checkpoint_dir, Some(PathBuf::from("./ckpt")));
+                assert!(args.http_cache);
+                assert!(!args.track_changes);
+                assert!(!args.structural_hash);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        // * Without --resume the seed list is mandatory
+        assert!(Cli::try_parse_from(["titan-flow", "crawl"]).is_err());
+        // * Structural hashing only applies to change tracking
+        assert!(Cli::try_parse_from(["titan-flow", "crawl", "--seeds", "s.txt", "--structural-hash"]).is_err());
+        let cli = Cli::try_parse_from([
+            "titan-flow", "crawl", "--seeds", "s.txt", "--track-changes", "--structural-hash",
+        ])
+        .unwrap();
+        assert!(matches!(cli.command, Command::Crawl(CrawlArgs { structural_hash: true, .. })));
+    }
+
+    #[test]
+    fn test_parse_other_subcommands() {
+        let cli = Cli::try_parse_from(["titan-flow", "extract", "page.html", "--pretty"]).unwrap();
+        assert!(matches!(cli.command, Command::Extract(ExtractArgs { pretty: true, .. })));
+
+        let cli = Cli::try_parse_from(["titan-flow", "metrics-server"]).unwrap();
+        assert!(matches!(cli.command, Command::MetricsServer(MetricsServerArgs { port: None })));
+
+        let cli = Cli::try_parse_from([
+            "titan-flow", "metrics-server", "--port", "9100", "--config", "titan.toml",
+        ])
+        .unwrap();
+        assert_eq!(cli.config, Some(PathBuf::from("titan.toml")));
+        assert!(matches!(cli.command, Command::MetricsServer(MetricsServerArgs { port: Some(9100) })));
+
+        assert!(Cli::try_parse_from(["titan-flow", "enrich"]).is_err());
+
+        let cli = Cli::try_parse_from([
+            "titan-flow", "reprocess", "--input", "./warc", "--config", "new_refinery.toml",
+        ])
+        .unwrap();
+        assert_eq!(cli.config, Some(PathBuf::from("new_refinery.toml")));
+        match cli.command {
+            Command::Reprocess(args) => {
+                assert_eq!(args.input, PathBuf::from("./warc"));
+                assert_eq!(args.store, "./data/records.jsonl");
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+        assert!(Cli::try_parse_from(["titan-flow", "reprocess"]).is_err());
+
+        let cli = Cli::try_parse_from([
+            "titan-flow", "golden", "--fixtures", "tests/fixtures/golden", "--update", "--ignore", "stats.quality_score",
+        ])
+        .unwrap();
+        match cli.command {
+            Command::Golden(args) => {
+                assert!(args.update);
+                assert_eq!(args.ignore, vec!["stats.quality_score"]);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        let cli = Cli::try_parse_from(["titan-flow", "snapshot", "00ff00ff00ff00ff", "--dir", "./snaps"]).unwrap();
+        match cli.command {
+            Command::Snapshot(args) => {
+                assert_eq!(args.hash, "00ff00ff00ff00ff");
+                assert_eq!(args.dir, Some(PathBuf::from("./snaps")));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        let cli = Cli::try_parse_from(["titan-flow", "audit", "--dir", "./audit"]).unwrap();
+        match cli.command {
+            Command::Audit(args) => {
+                assert_eq!(args.id, None);
+                assert_eq!(args.dir, Some(PathBuf::from("./audit")));
+                assert_eq!(args.min_quality, 0.4);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+        // * --html prints one sample, so it needs an ID
+        assert!(Cli::try_parse_from(["titan-flow", "audit", "--html"]).is_err());
+    }
+
+    #[test]
+    fn test_seed_sources() {
+        assert_eq!(seed_source("-", Some(SeedFormat::Csv)).name(), "stdin");
+        assert_eq!(
+            seed_source("https://lists.example.com/seeds.txt", None).name(),
+            "https://lists.example.com/seeds.txt"
+        );
+        assert_eq!(seed_source("seeds.csv", None).name(), "seeds.csv");
+
+        let cli = Cli::try_parse_from(["titan-flow", "crawl", "--seeds", "-", "--seed-format", "csv"]).unwrap();
+        assert!(matches!(cli.command, Command::Crawl(args) if args.seed_format == Some(SeedFormat::Csv)));
+        assert!(Cli::try_parse_from(["titan-flow", "crawl", "--seeds", "-", "--seed-format", "tsv"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_store() {
+        let jsonl = Ok(RecordStoreUri::Jsonl(PathBuf::from("data/r.jsonl")));
+        assert_eq!(parse_store("jsonl://data/r.jsonl"), jsonl);
+        assert_eq!(parse_store("data/r.jsonl"), jsonl);
+        assert_eq!(
+            parse_store("lancedb://data/lance/records"),
+            Ok(RecordStoreUri::Lance {
+                uri: "data/lance".to_string(),
+                table: "records".to_string(),
+            })
+        );
+        assert!(parse_store("lancedb://records").is_err());
+        assert!(parse_store("s3://bucket/key").is_err());
+    }
+
+    #[test]
+    fn test_records_roundtrip() {
+        let path = std::env::temp_dir().join(format!("titan-flow-cli-{}.jsonl", std::process::id()));
+        let records = vec![
+            MultimodalRecord::new("https://a.com".into(), 1, "first".into()),
+            MultimodalRecord::new("https://b.com".into(), 2, "second".into()),
+        ];
+
+        write_records(&path, &records).unwrap();
+        let loaded = read_records(&path).unwrap();
+        let _ = fs::remove_file(&path);
 
-    tracing::info!("Titan-Flow Engineering Orchestrator Initialized");
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[1].url, "https://b.com");
+        assert_eq!(loaded[0].id, records[0].id);
+    }
 }
@@ -0,0 +1,876 @@
+// * [EDD-7] Configuration Loader
+// * Deserializes a full TitanConfig from TOML or YAML, applies TITAN_* environment
+// * overrides, and reports errors against the dotted key that caused them
+
+use crate::engine::rate_limiter::CrawlDelayConfig;
+use crate::ops::{AlertConfig, TelemetryConfig};
+use crate::engine::url_filter::{UrlFilter, UrlFilterError};
+use crate::orchestrator::{CapacityPolicy, CrawlConfig, PolicyEnforcer, PolicyError};
+use crate::persistence::{AuditConfig, DomainAuthority, ExpiryPolicy, ScorerConfig, SnapshotConfig, WebhookConfig, WorkerConfig};
+use crate::refinery::{JsonRuleSet, RefineryConfig, SiteRuleSet, SiteRulesFile};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use tracing_subscriber::EnvFilter;
+
+/// Prefix for environment variable overrides (e.g. TITAN_CRAWL__MAX_PAGES=50)
+pub const ENV_PREFIX: &str = "TITAN_";
+
+// * Separates nested keys in override variable names
+const ENV_SEPARATOR: &str = "__";
+
+// * Top-level sections; other TITAN_* variables are not treated as overrides
+const SECTIONS: [&str; 10] = [
+    "crawl", "refinery", "scorer", "rate_limiter", "worker", "alerts", "telemetry", "webhook", "snapshots", "audit",
+];
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Failed to read config file {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Unsupported config format '{0}' (expected .toml, .yaml or .yml)")]
+    UnsupportedFormat(String),
+
+    #[error("Invalid {format} config: {message}")]
+    Parse { format: ConfigFormat, message: String },
+
+    #[error("Invalid value for `{key}`: {message}")]
+    Invalid { key: String, message: String },
+}
+
+/// Supported configuration file formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Detects the format from a file extension
+    pub fn from_path(path: &Path) -> Result<Self, ConfigError> {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_lowercase)
+            .unwrap_or_default();
+
+        match ext.as_str() {
+            "toml" => Ok(ConfigFormat::Toml),
+            "yaml" | "yml" => Ok(ConfigFormat::Yaml),
+            _ => Err(ConfigError::UnsupportedFormat(path.display().to_string())),
+        }
+    }
+}
+
+impl fmt::Display for ConfigFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigFormat::Toml => write!(f, "TOML"),
+            ConfigFormat::Yaml => write!(f, "YAML"),
+        }
+    }
+}
+
+/// Complete Titan-Flow configuration, one section per component
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TitanConfig {
+    /// Crawl budget, scope and routing
+    pub crawl: CrawlConfig,
+    /// Refinery pipeline (cleaner, chunker, extractor, tables, redaction)
+    pub refinery: RefineryConfig,
+    /// Link scorer used to prioritize the frontier
+    pub scorer: ScorerConfig,
+    /// Default crawl delay for domains without robots.txt
+    pub rate_limiter: CrawlDelayConfig,
+    /// AI enrichment worker
+    pub worker: WorkerConfig,
+    /// SEV-1/SEV-3 alert thresholds
+    pub alerts: AlertConfig,
+    /// Logging and metrics
+    pub telemetry: TelemetryConfig,
+    /// Signed record delivery to an HTTP endpoint (disabled without a URL)
+    pub webhook: WebhookConfig,
+    /// Raw HTML snapshots referenced from records (disabled without a directory)
+    pub snapshots: SnapshotConfig,
+    /// Random sample of processed pages kept for quality audits (disabled without a directory)
+    pub audit: AuditConfig,
+}
+
+impl TitanConfig {
+    /// Returns the crawl section with the component sections filled in
+    pub fn crawl_config(&self) -> CrawlConfig {
+        CrawlConfig {
+            refinery: self.refinery.clone(),
+            link_scorer: self.scorer.clone(),
+            crawl_delay: self.rate_limiter.clone(),
+            worker: self.worker.clone(),
+            ..self.crawl.clone()
+        }
+    }
+
+    /// Checks values that deserialize cleanly but would break at runtime
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let crawl = &self.crawl;
+        ensure(crawl.max_pages > 0, "crawl.max_pages", "must be at least 1")?;
+        ensure(crawl.concurrency > 0, "crawl.concurrency", "must be at least 1")?;
+        ensure(crawl.frontier_capacity > 0, "crawl.frontier_capacity", "must be at least 1")?;
+        if let Err(PolicyError::InvalidPattern { list, source }) = PolicyEnforcer::new(crawl.policy.clone()) {
+            return Err(invalid(&format!("crawl.policy.{}", list), source.to_string()));
+        }
+        ensure(
+            crawl.policy.preferred_languages.iter().all(|tag| !tag.trim().is_empty()),
+            "crawl.policy.preferred_languages",
+            "language tags must not be empty",
+        )?;
+        match UrlFilter::new(&crawl.url_filter) {
+            Ok(_) => {}
+            Err(e @ UrlFilterError::InvalidPattern { .. }) => {
+                return Err(invalid("crawl.url_filter", e.to_string()));
+            }
+            Err(e) => return Err(invalid("crawl.url_filter.public_suffix_list", e.to_string())),
+        }
+        ensure(
+            crawl.frontier_policy != CapacityPolicy::SpillToDisk || crawl.spill.dir.is_some(),
+            "crawl.frontier_policy",
+            "spill_to_disk needs crawl.spill.dir",
+        )?;
+        ensure(
+            crawl.spill.memory_budget_bytes > 0,
+            "crawl.spill.memory_budget_bytes",
+            "must be at least 1",
+        )?;
+
+        let memory = &crawl.memory;
+        ensure(
+            memory.high_watermark_percent > 0.0 && memory.high_watermark_percent <= 100.0,
+            "crawl.memory.high_watermark_percent",
+            "must be between 0 and 100",
+        )?;
+        ensure(
+            memory.low_watermark_percent < memory.high_watermark_percent,
+            "crawl.memory.low_watermark_percent",
+            "must be below crawl.memory.high_watermark_percent",
+        )?;
+        ensure(
+            memory.sample_interval_ms > 0,
+            "crawl.memory.sample_interval_ms",
+            "must be at least 1",
+        )?;
+
+        ensure(crawl.dns.max_entries > 0, "crawl.dns.max_entries", "must be at least 1")?;
+        ensure(crawl.readiness.timeout_ms > 0, "crawl.readiness.timeout_ms", "must be at least 1")?;
+        ensure(
+            crawl.readiness.poll_interval_ms > 0,
+            "crawl.readiness.poll_interval_ms",
+            "must be at least 1",
+        )?;
+        ensure(
+            crawl.browser_limits.page_timeout_ms > 0,
+            "crawl.browser_limits.page_timeout_ms",
+            "must be at least 1",
+        )?;
+        ensure(
+            crawl.browser_limits.watchdog_interval_ms > 0,
+            "crawl.browser_limits.watchdog_interval_ms",
+            "must be at least 1",
+        )?;
+        ensure(
+            crawl.preflight.max_content_length > 0,
+            "crawl.preflight.max_content_length",
+            "must be at least 1",
+        )?;
+        ensure(
+            (0.0..=1.0).contains(&crawl.retry.jitter),
+            "crawl.retry.jitter",
+            "must be between 0 and 1",
+        )?;
+        ensure(crawl.gc.interval_secs > 0, "crawl.gc.interval_secs", "must be at least 1")?;
+        ensure(crawl.clusters.interval_secs > 0, "crawl.clusters.interval_secs", "must be at least 1")?;
+        ensure(crawl.clusters.min_size >= 2, "crawl.clusters.min_size", "must be at least 2")?;
+        ensure(
+            !crawl.clusters.enabled || crawl.clusters.path.is_some(),
+            "crawl.clusters.path",
+            "is required when crawl.clusters.enabled is set",
+        )?;
+        ensure(crawl.expiry.interval_secs > 0, "crawl.expiry.interval_secs", "must be at least 1")?;
+        if let Err(e) = ExpiryPolicy::new(&crawl.expiry.rules) {
+            return Err(invalid("crawl.expiry.rules", e.to_string()));
+        }
+
+        let politeness = &crawl.politeness;
+        ensure(
+            politeness.backoff_factor >= 1.0,
+            "crawl.politeness.backoff_factor",
+            "must be at least 1",
+        )?;
+        ensure(
+            politeness.max_backoff >= 1.0,
+            "crawl.politeness.max_backoff",
+            "must be at least 1",
+        )?;
+        ensure(
+            politeness.recovery_factor > 0.0 && politeness.recovery_factor <= 1.0,
+            "crawl.politeness.recovery_factor",
+            "must be greater than 0 and at most 1",
+        )?;
+        ensure(
+            politeness.success_streak > 0,
+            "crawl.politeness.success_streak",
+            "must be at least 1",
+        )?;
+
+        let chunker = &self.refinery.chunker;
+        ensure(chunker.window_size > 0, "refinery.chunker.window_size", "must be at least 1")?;
+        ensure(
+            chunker.overlap < chunker.window_size,
+            "refinery.chunker.overlap",
+            "must be smaller than refinery.chunker.window_size",
+        )?;
+        if let Err(e) = SiteRuleSet::new(&self.refinery.site_rules) {
+            return Err(invalid("refinery.site_rules", e.to_string()));
+        }
+        if let Some(path) = &self.refinery.site_rules_file {
+            if let Err(e) = SiteRulesFile::load(path).and_then(|file| SiteRuleSet::new(&file.site)) {
+                return Err(invalid("refinery.site_rules_file", e.to_string()));
+            }
+        }
+        if let Err(e) = JsonRuleSet::new(&self.refinery.json_rules) {
+            return Err(invalid("refinery.json_rules", e.to_string()));
+        }
+        let templates = &self.refinery.templates;
+        ensure(templates.min_pages >= 2, "refinery.templates.min_pages", "must be at least 2")?;
+        ensure(
+            templates.min_share > 0.0 && templates.min_share <= 1.0,
+            "refinery.templates.min_share",
+            "must be greater than 0 and at most 1",
+        )?;
+        ensure(
+            templates.max_blocks_per_domain > 0,
+            "refinery.templates.max_blocks_per_domain",
+            "must be at least 1",
+        )?;
+
+        ensure(
+            (0.0..=10.0).contains(&self.scorer.base_score),
+            "scorer.base_score",
+            "must be between 0 and 10",
+        )?;
+        if let Some(path) = &self.scorer.authority_file {
+            if let Err(e) = DomainAuthority::from_file(path) {
+                return Err(invalid("scorer.authority_file", e.to_string()));
+            }
+        }
+        ensure(self.scorer.topic.weight >= 0.0, "scorer.topic.weight", "must not be negative")?;
+        ensure(
+            self.scorer.learning.learning_rate > 0.0,
+            "scorer.learning.learning_rate",
+            "must be greater than 0",
+        )?;
+        ensure(
+            self.scorer.learning.max_adjustment >= 0.0,
+            "scorer.learning.max_adjustment",
+            "must not be negative",
+        )?;
+
+        ensure(
+            self.rate_limiter.slow_path_delay_ms >= self.rate_limiter.standard_delay_ms,
+            "rate_limiter.slow_path_delay_ms",
+            "must not be smaller than rate_limiter.standard_delay_ms",
+        )?;
+
+        ensure(self.worker.batch_size > 0, "worker.batch_size", "must be at least 1")?;
+        // ! A zero poll interval panics inside tokio::time::interval
+        ensure(self.worker.poll_interval_ms > 0, "worker.poll_interval_ms", "must be at least 1")?;
+
+        let alerts = &self.alerts;
+        ensure(
+            (0.0..=1.0).contains(&alerts.sev1_success_rate_threshold),
+            "alerts.sev1_success_rate_threshold",
+            "must be between 0 and 1",
+        )?;
+        ensure(
+            (0.0..=1.0).contains(&alerts.sev3_ban_rate_threshold),
+            "alerts.sev3_ban_rate_threshold",
+            "must be between 0 and 1",
+        )?;
+
+        ensure(
+            self.telemetry.stats_interval_secs > 0,
+            "telemetry.stats_interval_secs",
+            "must be at least 1",
+        )?;
+        if let Err(e) = EnvFilter::try_new(&self.telemetry.log_level) {
+            return Err(invalid("telemetry.log_level", e.to_string()));
+        }
+        if let Some(endpoint) = &self.telemetry.otlp_endpoint {
+            ensure(
+                endpoint.starts_with("http://") || endpoint.starts_with("https://"),
+                "telemetry.otlp_endpoint",
+                "must start with http:// or https://",
+            )?;
+        }
+
+        let webhook = &self.webhook;
+        if let Some(url) = &webhook.url {
+            ensure(
+                url.starts_with("http://") || url.starts_with("https://"),
+                "webhook.url",
+                "must start with http:// or https://",
+            )?;
+            ensure(!webhook.secret.is_empty(), "webhook.secret", "must be set when webhook.url is")?;
+        }
+        ensure(webhook.queue_capacity > 0, "webhook.queue_capacity", "must be at least 1")?;
+
+        let snapshots = &self.snapshots;
+        ensure(snapshots.max_bytes > 0, "snapshots.max_bytes", "must be at least 1")?;
+        ensure(
+            (1..=22).contains(&snapshots.compression_level),
+            "snapshots.compression_level",
+            "must be between 1 and 22",
+        )?;
+        ensure(
+            self.audit.sample_percent > 0.0 && self.audit.sample_percent <= 100.0,
+            "audit.sample_percent",
+            "must be greater than 0 and at most 100",
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Loads a config file, applying TITAN_* overrides from the process environment
+///
+/// # Example
+/// ```ignore
+/// let config = titan_flow::config::load("titan.toml")?;
+/// let report = orchestrator.run(seeds, config.crawl_config()).await?;
+/// ```
+pub fn load(path: impl AsRef<Path>) -> Result<TitanConfig, ConfigError> {
+    load_with_env(path, std::env::vars())
+}
+
+/// Loads a config file, applying overrides from the given variables
+pub fn load_with_env<I>(path: impl AsRef<Path>, env: I) -> Result<TitanConfig, ConfigError>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    let path = path.as_ref();
+    let format = ConfigFormat::from_path(path)?;
+    let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    parse(&contents, format, env)
+}
+
+/// Parses config text, applies overrides and validates the result
+///
+/// Missing sections and keys fall back to their defaults. Unknown keys are
+/// rejected so typos do not silently keep the default value.
+pub fn parse<I>(contents: &str, format: ConfigFormat, env: I) -> Result<TitanConfig, ConfigError>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    let parsed = match format {
+        ConfigFormat::Toml => toml::from_str::<Value>(contents).map_err(|e| e.to_string()),
+        ConfigFormat::Yaml => serde_yaml::from_str::<Value>(contents).map_err(|e| e.to_string()),
+    };
+    let mut tree = match parsed {
+        Ok(Value::Object(map)) => Value::Object(map),
+        // * An empty YAML document is null
+        Ok(Value::Null) => Value::Object(Map::new()),
+        Ok(_) => {
+            return Err(ConfigError::Parse {
+                format,
+                message: "top level must be a table of sections".to_string(),
+            })
+        }
+        Err(message) => return Err(ConfigError::Parse { format, message }),
+    };
+
+    let overrides = apply_env_overrides(&mut tree, env);
+
+    let config = match serde_json::from_value::<TitanConfig>(tree.clone()) {
+        Ok(config) => config,
+        Err(e) => {
+            let (key, message) = locate_error::<TitanConfig>(&tree)
+                .unwrap_or_else(|| (String::new(), e.to_string()));
+            return Err(annotate(invalid(&key, message), &overrides));
+        }
+    };
+
+    config.validate().map_err(|e| annotate(e, &overrides))?;
+    Ok(config)
+}
+
+/// Applies TITAN_SECTION__KEY=value overrides, returning (dotted key, variable) pairs
+fn apply_env_overrides<I>(tree: &mut Value, env: I) -> Vec<(String, String)>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    let defaults = serde_json::to_value(TitanConfig::default()).unwrap_or(Value::Null);
+    let mut applied = Vec::new();
+
+    let mut vars: Vec<(String, String)> = env
+        .into_iter()
+        .filter(|(name, _)| name.starts_with(ENV_PREFIX))
+        .collect();
+    // * Deterministic order when two variables target overlapping keys
+    vars.sort();
+
+    for (name, raw) in vars {
+        let path: Vec<String> = name[ENV_PREFIX.len()..]
+            .split(ENV_SEPARATOR)
+            .map(str::to_lowercase)
+            .collect();
+        if path.len() < 2 || path.iter().any(|p| p.is_empty()) || !SECTIONS.contains(&path[0].as_str()) {
+            continue;
+        }
+
+        // * String fields take the raw value; others accept JSON (numbers, bools, arrays)
+        let default = path.iter().try_fold(&defaults, |node, key| node.get(key));
+        let value = match default {
+            Some(Value::String(_)) => Value::String(raw),
+            _ => serde_json::from_str(&raw).unwrap_or(Value::String(raw)),
+        };
+
+        set_path(tree, &path, value);
+        applied.push((path.join("."), name));
+    }
+
+    applied
+}
+
+/// Sets a nested value, creating intermediate tables as needed
+fn set_path(tree: &mut Value, path: &[String], value: Value) {
+    let mut node = tree;
+    for key in &path[..path.len() - 1] {
+        if !node.is_object() {
+            *node = Value::Object(Map::new());
+        }
+        node = node
+            .as_object_mut()
+            .map(|map| map.entry(key.clone()).or_insert(Value::Null))
+            .expect("node was just made an object");
+    }
+    if !node.is_object() {
+        *node = Value::Object(Map::new());
+    }
+    if let Some(map) = node.as_object_mut() {
+        map.insert(path[path.len() - 1].clone(), value);
+    }
+}
+
+/// Finds the dotted key responsible for a deserialization failure
+///
+/// Every section uses `#[serde(default)]`, so a tree holding a single key is
+/// valid on its own; each key is tried in isolation and the search descends
+/// into the first failing table.
+fn locate_error<T: DeserializeOwned>(tree: &Value) -> Option<(String, String)> {
+    let mut path = Vec::new();
+    locate_in::<T>(tree, &mut path)
+}
+
+fn locate_in<T: DeserializeOwned>(node: &Value, path: &mut Vec<String>) -> Option<(String, String)> {
+    let map = node.as_object()?;
+
+    for (key, value) in map {
+        path.push(key.clone());
+
+        if let Err(e) = serde_json::from_value::<T>(nest(path, value.clone())) {
+            // * An unknown table fails even when empty; report it rather than its children
+            let table_rejected =
+                value.is_object() && serde_json::from_value::<T>(nest(path, Value::Object(Map::new()))).is_err();
+            if !table_rejected {
+                if let Some(found) = locate_in::<T>(value, path) {
+                    return Some(found);
+                }
+            }
+            return Some((path.join("."), e.to_string()));
+        }
+        path.pop();
+    }
+
+    None
+}
+
+/// Wraps a value in one single-key table per path segment
+fn nest(path: &[String], value: Value) -> Value {
+    path.iter().rev().fold(value, |inner, segment| {
+        let mut wrapper = Map::new();
+        wrapper.insert(segment.clone(), inner);
+        Value::Object(wrapper)
+    })
+}
+
+/// Appends the overriding variable name when the offending key came from the environment
+fn annotate(error: ConfigError, overrides: &[(String, String)]) -> ConfigError {
+    let ConfigError::Invalid { key, message } = error else {
+        return error;
+    };
+
+    let source = overrides
+        .iter()
+        .rev()
+        .find(|(path, _)| *path == key || key.starts_with(&format!("{}.", path)));
+
+    match source {
+        Some((_, var)) => ConfigError::Invalid {
+            key,
+            message: format!("{} (set by {})", message, var),
+        },
+        None => ConfigError::Invalid { key, message },
+    }
+}
+
+fn ensure(condition: bool, key: &str, message: &str) -> Result<(), ConfigError> {
+    if condition {
+        Ok(())
+    } else {
+        Err(invalid(key, message))
+    }
+}
+
+fn invalid(key: &str, message: impl Into<String>) -> ConfigError {
+    ConfigError::Invalid {
+        key: key.to_string(),
+        message: message.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_env() -> Vec<(String, String)> {
+        Vec::new()
+    }
+
+    fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn invalid_key(result: Result<TitanConfig, ConfigError>) -> (String, String) {
+        match result {
+            Err(ConfigError::Invalid { key, message }) => (key, message),
+            other => panic!("expected Invalid error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_toml() {
+        let toml = r#"
+            [crawl]
+            max_pages = 25
+            same_domain_only = false
+
+            [refinery.chunker]
+            window_size = 512
+            overlap = 64
+
+            [scorer]
+            high_value_paths = ["/guides/"]
+
+            [rate_limiter]
+            standard_delay_ms = 500
+            slow_path_delay_ms = 2000
+
+            [worker]
+            batch_size = 4
+
+            [alerts]
+            sev3_ban_rate_threshold = 0.75
+
+            [telemetry]
+            log_level = "debug"
+        "#;
+        let config = parse(toml, ConfigFormat::Toml, no_env()).unwrap();
+
+        assert_eq!(config.crawl.max_pages, 25);
+        assert!(!config.crawl.same_domain_only);
+        assert_eq!(config.crawl.max_depth, CrawlConfig::default().max_depth);
+        assert_eq!(config.refinery.chunker.window_size, 512);
+        assert_eq!(config.scorer.high_value_paths, vec!["/guides/"]);
+        assert_eq!(config.worker.batch_size, 4);
+        assert!((config.alerts.sev3_ban_rate_threshold - 0.75).abs() < f64::EPSILON);
+        assert_eq!(config.telemetry.log_level, "debug");
+
+        let crawl = config.crawl_config();
+        assert_eq!(crawl.crawl_delay.standard_delay_ms, 500);
+        assert_eq!(crawl.refinery.chunker.overlap, 64);
+        assert_eq!(crawl.worker.batch_size, 4);
+    }
+
+    #[test]
+    fn test_parse_yaml() {
+        let yaml = "
+crawl:
+  max_depth: 1
+  tier1_proxies: [\"http://proxy:8080\"]
+refinery:
+  extract_tables: false
+telemetry:
+  metrics_port: 9100
+";
+        let config = parse(yaml, ConfigFormat::Yaml, no_env()).unwrap();
+
+        assert_eq!(config.crawl.max_depth, 1);
+        assert_eq!(config.crawl.tier1_proxies, vec!["http://proxy:8080"]);
+        assert!(!config.refinery.extract_tables);
+        assert_eq!(config.telemetry.metrics_port, 9100);
+
+        // * Empty documents fall back to defaults
+        assert!(parse("", ConfigFormat::Yaml, no_env()).is_ok());
+        assert!(parse("", ConfigFormat::Toml, no_env()).is_ok());
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let vars = env(&[
+            ("TITAN_CRAWL__MAX_PAGES", "7"),
+            ("TITAN_CRAWL__REDIS_URL", "redis://localhost:6379"),
+            ("TITAN_REFINERY__CHUNKER__WINDOW_SIZE", "300"),
+            ("TITAN_TELEMETRY__LOG_LEVEL", "true"),
+            // * Not a config section, must be ignored
+            ("TITAN_HOME", "/opt/titan"),
+            ("PATH", "/usr/bin"),
+        ]);
+        let config = parse("[crawl]\nmax_pages = 50\n", ConfigFormat::Toml, vars).unwrap();
+
+        assert_eq!(config.crawl.max_pages, 7);
+        assert_eq!(config.crawl.redis_url.as_deref(), Some("redis://localhost:6379"));
+        assert_eq!(config.refinery.chunker.window_size, 300);
+        // * String fields keep the raw text even when it looks like JSON
+        assert_eq!(config.telemetry.log_level, "true");
+    }
+
+    #[test]
+    fn test_unknown_key_points_at_path() {
+        let (key, message) = invalid_key(parse(
+            "[refinery.chunker]\nwindow_sise = 10\n",
+            ConfigFormat::Toml,
+            no_env(),
+        ));
+        assert_eq!(key, "refinery.chunker.window_sise");
+        assert!(message.contains("unknown field"));
+
+        let (key, _) = invalid_key(parse("[crawler]\nmax_pages = 1\n", ConfigFormat::Toml, no_env()));
+        assert_eq!(key, "crawler");
+    }
+
+    #[test]
+    fn test_type_error_points_at_path() {
+        let (key, message) = invalid_key(parse(
+            "worker:\n  batch_size: 4\n  poll_interval_ms: soon\n",
+            ConfigFormat::Yaml,
+            no_env(),
+        ));
+        assert_eq!(key, "worker.poll_interval_ms");
+        assert!(message.contains("invalid type"));
+    }
+
+    #[test]
+    fn test_env_errors_name_the_variable() {
+        let vars = env(&[("TITAN_CRAWL__MAX_DEPTH", "deep")]);
+        let (key, message) = invalid_key(parse("", ConfigFormat::Toml, vars));
+        assert_eq!(key, "crawl.max_depth");
+        assert!(message.contains("TITAN_CRAWL__MAX_DEPTH"));
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let (key, _) = invalid_key(parse(
+            "[refinery.chunker]\nwindow_size = 100\noverlap = 100\n",
+            ConfigFormat::Toml,
+            no_env(),
+        ));
+        assert_eq!(key, "refinery.chunker.overlap");
+
+        let (key, _) = invalid_key(parse(
+            "[rate_limiter]\nstandard_delay_ms = 5000\nslow_path_delay_ms = 10\n",
+            ConfigFormat::Toml,
+            no_env(),
+        ));
+        assert_eq!(key, "rate_limiter.slow_path_delay_ms");
+
+        let (key, _) = invalid_key(parse(
+            "[crawl.policy]\ndenied_url_patterns = [\"(unclosed\"]\n",
+            ConfigFormat::Toml,
+            no_env(),
+        ));
+        assert_eq!(key, "crawl.policy.denied_url_patterns");
+
+        let (key, _) = invalid_key(parse(
+            "[crawl.policy]\npreferred_languages = [\"en\", \" \"]\n",
+            ConfigFormat::Toml,
+            no_env(),
+        ));
+        assert_eq!(key, "crawl.policy.preferred_languages");
+
+        let (key, _) = invalid_key(parse("[crawl.clusters]\nenabled = true\n", ConfigFormat::Toml, no_env()));
+        assert_eq!(key, "crawl.clusters.path");
+
+        let (key, message) = invalid_key(parse(
+            "[crawl.url_filter]\nallow = [\"re:(\"]\n",
+            ConfigFormat::Toml,
+            no_env(),
+        ));
+        assert_eq!(key, "crawl.url_filter");
+        assert!(message.contains("re:("));
+
+        let (key, _) = invalid_key(parse(
+            "[crawl.politeness]\nrecovery_factor = 1.5\n",
+            ConfigFormat::Toml,
+            no_env(),
+        ));
+        assert_eq!(key, "crawl.politeness.recovery_factor");
+
+        let (key, _) = invalid_key(parse(
+            "[crawl.memory]\nhigh_watermark_percent = 80\nlow_watermark_percent = 85\n",
+            ConfigFormat::Toml,
+            no_env(),
+        ));
+        assert_eq!(key, "crawl.memory.low_watermark_percent");
+
+        let (key, _) = invalid_key(parse(
+            "[scorer]\nauthority_file = \"/nonexistent/authority.txt\"\n",
+            ConfigFormat::Toml,
+            no_env(),
+        ));
+        assert_eq!(key, "scorer.authority_file");
+
+        let (key, _) = invalid_key(parse(
+            "[[crawl.expiry.rules]]\nname = \"news\"\nurl_pattern = \"(news\"\nmax_age_days = 90\n",
+            ConfigFormat::Toml,
+            no_env(),
+        ));
+        assert_eq!(key, "crawl.expiry.rules");
+
+        let (key, _) = invalid_key(parse("[crawl.dns]\nmax_entries = 0\n", ConfigFormat::Toml, no_env()));
+        assert_eq!(key, "crawl.dns.max_entries");
+
+        let (key, _) = invalid_key(parse(
+            "[crawl.preflight]\nenabled = true\nmax_content_length = 0\n",
+            ConfigFormat::Toml,
+            no_env(),
+        ));
+        assert_eq!(key, "crawl.preflight.max_content_length");
+
+        let (key, _) = invalid_key(parse("[crawl.readiness]\npoll_interval_ms = 0\n", ConfigFormat::Toml, no_env()));
+        assert_eq!(key, "crawl.readiness.poll_interval_ms");
+
+        let (key, _) = invalid_key(parse(
+            "[crawl.browser_limits]\nwatchdog_interval_ms = 0\n",
+            ConfigFormat::Toml,
+            no_env(),
+        ));
+        assert_eq!(key, "crawl.browser_limits.watchdog_interval_ms");
+
+        let (key, _) = invalid_key(parse("[crawl.retry]\njitter = 1.5\n", ConfigFormat::Toml, no_env()));
+        assert_eq!(key, "crawl.retry.jitter");
+
+        let (key, _) = invalid_key(parse("[snapshots]\ncompression_level = 30\n", ConfigFormat::Toml, no_env()));
+        assert_eq!(key, "snapshots.compression_level");
+
+        let (key, _) = invalid_key(parse("[audit]\nsample_percent = 0\n", ConfigFormat::Toml, no_env()));
+        assert_eq!(key, "audit.sample_percent");
+
+        let (key, _) = invalid_key(parse(
+            "[[refinery.site_rules]]\ndomain = \"example.com\"\ntitle = \"h1[\"\n",
+            ConfigFormat::Toml,
+            no_env(),
+        ));
+        assert_eq!(key, "refinery.site_rules");
+
+        let (key, _) = invalid_key(parse(
+            "[refinery]\nsite_rules_file = \"/nonexistent/sites.toml\"\n",
+            ConfigFormat::Toml,
+            no_env(),
+        ));
+        assert_eq!(key, "refinery.site_rules_file");
+
+        let (key, _) = invalid_key(parse(
+            "[[refinery.json_rules]]\ndomain = \"api.example.com\"\nbody = \"content\"\n",
+            ConfigFormat::Toml,
+            no_env(),
+        ));
+        assert_eq!(key, "refinery.json_rules");
+
+        let (key, _) = invalid_key(parse("[refinery.templates]\nmin_share = 1.5\n", ConfigFormat::Toml, no_env()));
+        assert_eq!(key, "refinery.templates.min_share");
+
+        let (key, _) = invalid_key(parse(
+            "[webhook]\nurl = \"https://hooks.example.com/titan\"\n",
+            ConfigFormat::Toml,
+            no_env(),
+        ));
+        assert_eq!(key, "webhook.secret");
+        let config = parse(
+            "[webhook]\nurl = \"https://hooks.example.com/titan\"\n",
+            ConfigFormat::Toml,
+            env(&[("TITAN_WEBHOOK__SECRET", "s3cret")]),
+        )
+        .unwrap();
+        assert_eq!(config.webhook.secret, "s3cret");
+
+        let (key, _) = invalid_key(parse(
+            "[telemetry]\notlp_endpoint = \"localhost:4317\"\n",
+            ConfigFormat::Toml,
+            no_env(),
+        ));
+        assert_eq!(key, "telemetry.otlp_endpoint");
+
+        let (key, message) = invalid_key(parse(
+            "",
+            ConfigFormat::Toml,
+            env(&[("TITAN_WORKER__POLL_INTERVAL_MS", "0")]),
+        ));
+        assert_eq!(key, "worker.poll_interval_ms");
+        assert!(message.contains("TITAN_WORKER__POLL_INTERVAL_MS"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            parse("[crawl\n", ConfigFormat::Toml, no_env()),
+            Err(ConfigError::Parse { format: ConfigFormat::Toml, .. })
+        ));
+        assert!(matches!(
+            parse("- just\n- a list\n", ConfigFormat::Yaml, no_env()),
+            Err(ConfigError::Parse { format: ConfigFormat::Yaml, .. })
+        ));
+    }
+
+    #[test]
+    fn test_load_from_file() {
+        let path = std::env::temp_dir().join(format!("titan-flow-config-{}.yml", std::process::id()));
+        std::fs::write(&path, "crawl:\n  concurrency: 2\n").unwrap();
+        let config = load_with_env(&path, no_env());
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(config.unwrap().crawl.concurrency, 2);
+
+        assert!(matches!(
+            ConfigFormat::from_path(Path::new("titan.json")),
+            Err(ConfigError::UnsupportedFormat(_))
+        ));
+        assert!(matches!(
+            load_with_env("/nonexistent/titan.toml", no_env()),
+            Err(ConfigError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn test_default_config_is_valid() {
+        assert!(TitanConfig::default().validate().is_ok());
+    }
+}
@@ -1 +1,6 @@
 pub mod constants;
+pub mod loader;
+pub mod watcher;
+
+pub use loader::{load, load_with_env, parse, ConfigError, ConfigFormat, TitanConfig, ENV_PREFIX};
+pub use watcher::{ConfigWatcher, ReloadOutcome, WatchError};
@@ -0,0 +1,365 @@
+// * [EDD-7] Configuration Hot Reload
+// * Watches the config file and publishes safe-to-change sections (rate limits,
+// * scorer weights, alert thresholds) on watch channels so long crawls can be tuned live
+
+use super::loader::{load, ConfigError, TitanConfig};
+use crate::engine::rate_limiter::CrawlDelayConfig;
+use crate::ops::{AlertConfig, AlertManager};
+use crate::persistence::ScorerConfig;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+// * Editors often write a file in several steps; wait for the burst to settle
+const RELOAD_DEBOUNCE_MS: u64 = 200;
+
+// * Sections published on watch channels
+const RELOADABLE_SECTIONS: [&str; 3] = ["rate_limiter", "scorer", "alerts"];
+
+#[derive(Debug, Error)]
+pub enum WatchError {
+    #[error("Initial config load failed: {0}")]
+    Config(#[from] ConfigError),
+
+    #[error("File watcher setup failed: {0}")]
+    Notify(#[from] notify::Error),
+}
+
+/// Sections that changed in a reload
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReloadOutcome {
+    /// Sections published to subscribers
+    pub applied: Vec<&'static str>,
+    /// Sections that changed but only take effect after a restart
+    pub restart_required: Vec<&'static str>,
+}
+
+impl ReloadOutcome {
+    /// Returns true if nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty() && self.restart_required.is_empty()
+    }
+}
+
+/// State shared between the watcher handle and its reload task
+struct Shared {
+    path: PathBuf,
+    current: Mutex<TitanConfig>,
+    rate_limiter: watch::Sender<CrawlDelayConfig>,
+    scorer: watch::Sender<ScorerConfig>,
+    alerts: watch::Sender<AlertConfig>,
+}
+
+impl Shared {
+    fn reload(&self) -> Result<ReloadOutcome, ConfigError> {
+        let next = load(&self.path)?;
+        let mut current = self.current.lock().unwrap();
+        let mut outcome = ReloadOutcome::default();
+
+        for section in ["crawl", "refinery", "worker", "telemetry", "snapshots", "webhook", "audit"] {
+            if section_of(&current, section) != section_of(&next, section) {
+                outcome.restart_required.push(section);
+            }
+        }
+
+        // * Publish only what changed so subscribers are not woken needlessly
+        for section in RELOADABLE_SECTIONS {
+            if section_of(&current, section) == section_of(&next, section) {
+                continue;
+            }
+            match section {
+                "rate_limiter" => {
+                    self.rate_limiter.send_replace(next.rate_limiter.clone());
+                }
+                "scorer" => {
+                    self.scorer.send_replace(next.scorer.clone());
+                }
+                _ => {
+                    self.alerts.send_replace(next.alerts.clone());
+                }
+            }
+            outcome.applied.push(section);
+        }
+
+        *current = next;
+        Ok(outcome)
+    }
+}
+
+/// Watches a config file and publishes live-tunable settings
+///
+/// # Example
+/// ```ignore
+/// let watcher = ConfigWatcher::start("titan.toml")?;
+/// let orchestrator = Orchestrator::new().with_config_watcher(&watcher);
+/// let report = orchestrator.run(seeds, watcher.config().crawl_config()).await?;
+/// ```
+pub struct ConfigWatcher {
+    shared: Arc<Shared>,
+    task: JoinHandle<()>,
+    _watcher: RecommendedWatcher,
+}
+
+impl std::fmt::Debug for ConfigWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigWatcher")
+            .field("path", &self.shared.path)
+            .finish()
+    }
+}
+
+impl ConfigWatcher {
+    /// Loads the config file and starts watching it for changes
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub fn start(path: impl AsRef<Path>) -> Result<Self, WatchError> {
+        let path = path.as_ref().to_path_buf();
+        let initial = load(&path)?;
+
+        let shared = Arc::new(Shared {
+            rate_limiter: watch::Sender::new(initial.rate_limiter.clone()),
+            scorer: watch::Sender::new(initial.scorer.clone()),
+            alerts: watch::Sender::new(initial.alerts.clone()),
+            current: Mutex::new(initial),
+            path,
+        });
+
+        // * Watch the parent directory: atomic saves replace the file's inode
+        let (tx, rx) = mpsc::unbounded_channel();
+        let file_name = shared.path.file_name().map(|n| n.to_os_string());
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            let Ok(event) = event else {
+                return;
+            };
+            let relevant = matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            ) && event
+                .paths
+                .iter()
+                .any(|p| p.file_name().map(|n| n.to_os_string()) == file_name);
+            if relevant {
+                let _ = tx.send(());
+            }
+        })?;
+        let dir = match shared.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+        let task = tokio::spawn(reload_loop(Arc::clone(&shared), rx));
+        info!(path = %shared.path.display(), "Watching config for changes");
+
+        Ok(Self {
+            shared,
+            task,
+            _watcher: watcher,
+        })
+    }
+
+    /// Re-reads the file immediately, publishing any changed sections
+    ///
+    /// On error the previous settings stay in effect.
+    pub fn reload(&self) -> Result<ReloadOutcome, ConfigError> {
+        self.shared.reload()
+    }
+
+    /// Returns the most recently loaded configuration
+    pub fn config(&self) -> TitanConfig {
+        self.shared.current.lock().unwrap().clone()
+    }
+
+    /// Returns the path being watched
+    pub fn path(&self) -> &Path {
+        &self.shared.path
+    }
+
+    /// Subscribes to default crawl delay changes
+    pub fn subscribe_rate_limits(&self) -> watch::Receiver<CrawlDelayConfig> {
+        self.shared.rate_limiter.subscribe()
+    }
+
+    /// Subscribes to link scorer weight changes
+    pub fn subscribe_scorer(&self) -> watch::Receiver<ScorerConfig> {
+        self.shared.scorer.subscribe()
+    }
+
+    /// Subscribes to alert threshold changes
+    pub fn subscribe_alerts(&self) -> watch::Receiver<AlertConfig> {
+        self.shared.alerts.subscribe()
+    }
+
+    /// Keeps an alert manager's thresholds in sync with the file
+    pub fn bind_alert_manager(&self, manager: Arc<AlertManager>) -> JoinHandle<()> {
+        let mut rx = self.subscribe_alerts();
+        tokio::spawn(async move {
+            while rx.changed().await.is_ok() {
+                let config = rx.borrow_and_update().clone();
+                manager.set_config(config);
+            }
+        })
+    }
+}
+
+impl Drop for ConfigWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn reload_loop(shared: Arc<Shared>, mut events: mpsc::UnboundedReceiver<()>) {
+    while events.recv().await.is_some() {
+        tokio::time::sleep(Duration::from_millis(RELOAD_DEBOUNCE_MS)).await;
+        while events.try_recv().is_ok() {}
+
+        match shared.reload() {
+            Ok(outcome) if outcome.is_empty() => {}
+            Ok(outcome) => {
+                if !outcome.applied.is_empty() {
+                    info!(sections = ?outcome.applied, "Config reloaded");
+                }
+                if !outcome.restart_required.is_empty() {
+                    warn!(
+                        sections = ?outcome.restart_required,
+                        "Config sections changed that only apply after a restart"
+                    );
+                }
+            }
+            Err(e) => warn!(error = %e, "Config reload rejected; keeping previous settings"),
+        }
+    }
+}
+
+fn section_of(config: &TitanConfig, section: &str) -> serde_json::Value {
+    fn value<T: Serialize>(section: &T) -> serde_json::Value {
+        serde_json::to_value(section).unwrap_or(serde_json::Value::Null)
+    }
+
+    match section {
+        "crawl" => value(&config.crawl),
+        "refinery" => value(&config.refinery),
+        "scorer" => value(&config.scorer),
+        "rate_limiter" => value(&config.rate_limiter),
+        "worker" => value(&config.worker),
+        "alerts" => value(&config.alerts),
+        "telemetry" => value(&config.telemetry),
+        "snapshots" => value(&config.snapshots),
+        "webhook" => value(&config.webhook),
+        "audit" => value(&config.audit),
+        _ => serde_json::Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TempConfig(PathBuf);
+
+    impl TempConfig {
+        fn new(name: &str, contents: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("titan-flow-watch-{}-{}", name, std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            let path = dir.join("titan.toml");
+            std::fs::write(&path, contents).unwrap();
+            Self(path)
+        }
+
+        fn write(&self, contents: &str) {
+            std::fs::write(&self.0, contents).unwrap();
+        }
+    }
+
+    impl Drop for TempConfig {
+        fn drop(&mut self) {
+            if let Some(dir) = self.0.parent() {
+                let _ = std::fs::remove_dir_all(dir);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reload_publishes_changed_sections() {
+        let file = TempConfig::new("publish", "[rate_limiter]\nstandard_delay_ms = 1000\nslow_path_delay_ms = 5000\n");
+        let watcher = ConfigWatcher::start(&file.0).unwrap();
+        let mut rates = watcher.subscribe_rate_limits();
+        let scorer = watcher.subscribe_scorer();
+
+        file.write(
+            "[rate_limiter]\nstandard_delay_ms = 250\nslow_path_delay_ms = 5000\n\n[crawl]\nmax_pages = 5\n",
+        );
+        let outcome = watcher.reload().unwrap();
+
+        assert_eq!(outcome.applied, vec!["rate_limiter"]);
+        assert_eq!(outcome.restart_required, vec!["crawl"]);
+        assert!(rates.has_changed().unwrap());
+        assert_eq!(rates.borrow_and_update().standard_delay_ms, 250);
+        assert!(!scorer.has_changed().unwrap());
+        assert_eq!(watcher.config().crawl.max_pages, 5);
+
+        // * Reloading an unchanged file is a no-op
+        assert!(watcher.reload().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_output_sections_require_restart() {
+        let file = TempConfig::new("outputs", "[audit]\nsample_percent = 1.0\n");
+        let watcher = ConfigWatcher::start(&file.0).unwrap();
+
+        file.write(
+            "[audit]\nsample_percent = 5.0\n\n[snapshots]\ndir = \"./snapshots\"\n\n[webhook]\nmax_retries = 1\n",
+        );
+        let outcome = watcher.reload().unwrap();
+
+        assert!(outcome.applied.is_empty());
+        assert_eq!(outcome.restart_required, vec!["snapshots", "webhook", "audit"]);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_reload_keeps_previous_settings() {
+        let file = TempConfig::new("invalid", "[scorer]\nbase_score = 4.0\n");
+        let watcher = ConfigWatcher::start(&file.0).unwrap();
+        let scorer = watcher.subscribe_scorer();
+
+        file.write("[scorer]\nbase_score = 40.0\n");
+        let err = watcher.reload().unwrap_err();
+
+        assert!(err.to_string().contains("scorer.base_score"));
+        assert!(!scorer.has_changed().unwrap());
+        assert!((watcher.config().scorer.base_score - 4.0).abs() < f32::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_file_change_is_picked_up() {
+        let file = TempConfig::new("notify", "[alerts]\nsev3_ban_rate_threshold = 0.9\n");
+        let watcher = ConfigWatcher::start(&file.0).unwrap();
+        let manager = Arc::new(AlertManager::new());
+        let _binding = watcher.bind_alert_manager(Arc::clone(&manager));
+        let mut alerts = watcher.subscribe_alerts();
+
+        file.write("[alerts]\nsev3_ban_rate_threshold = 0.6\n");
+        tokio::time::timeout(Duration::from_secs(5), alerts.changed())
+            .await
+            .expect("config change not observed")
+            .unwrap();
+
+        assert!((alerts.borrow().sev3_ban_rate_threshold - 0.6).abs() < f64::EPSILON);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!((manager.config().sev3_ban_rate_threshold - 0.6).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_start_rejects_invalid_config() {
+        let file = TempConfig::new("start", "[crawl]\nmax_pages = 0\n");
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        assert!(matches!(ConfigWatcher::start(&file.0), Err(WatchError::Config(_))));
+    }
+}
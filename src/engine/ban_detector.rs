@@ -0,0 +1,438 @@
+// * [FR-03] Ban Detector
+// * Classifies fetch responses as clean, soft-banned (challenge/CAPTCHA/WAF page) or
+// * hard-banned using status codes, WAF vendor markers, challenge heuristics and body size
+
+use crate::ops;
+use regex::Regex;
+use reqwest::header::HeaderMap;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{LazyLock, Mutex};
+
+// * Challenge and block pages are small; weak signals only count below this size
+const DEFAULT_CHALLENGE_MAX_BYTES: usize = 32 * 1024;
+
+// * A page this much smaller than the domain's typical page is suspicious
+const DEFAULT_SIZE_ANOMALY_RATIO: f64 = 0.1;
+
+// * Clean pages needed before size anomalies are trusted
+const DEFAULT_MIN_SIZE_SAMPLES: u32 = 5;
+
+// * Weight of the newest sample in the running page size average
+const SIZE_EMA_ALPHA: f64 = 0.2;
+
+static CHALLENGE_TITLE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)<title[^>]*>\s*(just a moment|attention required|security check|access denied|are you a robot|verify you are human|captcha|pardon our interruption|request blocked)")
+        .unwrap()
+});
+
+// * Vendor-specific challenge markers: conclusive at any status and body size
+const VENDOR_MARKERS: &[(WafVendor, &str)] = &[
+    (WafVendor::Cloudflare, "cf-browser-verification"),
+    (WafVendor::Cloudflare, "cf_chl_opt"),
+    (WafVendor::Akamai, "errors.edgesuite.net"),
+    (WafVendor::Akamai, "Reference&#32;&#35;"),
+    (WafVendor::PerimeterX, "px-captcha"),
+    (WafVendor::PerimeterX, "captcha.px-cdn.net"),
+    (WafVendor::DataDome, "captcha-delivery"),
+    (WafVendor::DataDome, "geo.captcha-delivery.com"),
+    (WafVendor::Imperva, "Incapsula incident ID"),
+];
+
+// * Sensor scripts protected sites also embed in normal pages; only error or small pages count
+const SENSOR_MARKERS: &[(WafVendor, &str)] = &[
+    (WafVendor::Cloudflare, "challenge-platform"),
+    (WafVendor::Cloudflare, "cf-turnstile"),
+    (WafVendor::PerimeterX, "_pxAppId"),
+    (WafVendor::Imperva, "_Incapsula_Resource"),
+];
+
+// * Generic CAPTCHA widgets also appear on normal forms; only small pages count
+const CAPTCHA_MARKERS: &[&str] = &[
+    "g-recaptcha",
+    "recaptcha/api.js",
+    "h-captcha",
+    "hcaptcha.com/1/api.js",
+    "Press & Hold",
+    "unusual traffic from your computer",
+];
+
+/// Bot-protection vendor that produced a block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WafVendor {
+    Cloudflare,
+    Akamai,
+    PerimeterX,
+    DataDome,
+    Imperva,
+}
+
+/// Why a response was classified as a ban
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BanSignal {
+    pub status: u16,
+    pub vendor: Option<WafVendor>,
+    pub reason: String,
+}
+
+impl fmt::Display for BanSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.vendor {
+            Some(vendor) => write!(f, "{} ({:?}, HTTP {})", self.reason, vendor, self.status),
+            None => write!(f, "{} (HTTP {})", self.reason, self.status),
+        }
+    }
+}
+
+/// Classification of a single response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BanVerdict {
+    /// No ban detected (the status may still be an ordinary error)
+    Clean,
+    /// A challenge or CAPTCHA page a real browser can usually pass
+    SoftBan(BanSignal),
+    /// The request was refused outright (403 without a challenge, 429)
+    HardBan(BanSignal),
+}
+
+impl BanVerdict {
+    /// Returns true for soft and hard bans
+    pub fn is_ban(&self) -> bool {
+        !matches!(self, BanVerdict::Clean)
+    }
+}
+
+/// Thresholds for the heuristic checks
+#[derive(Debug, Clone)]
+pub struct BanDetectorConfig {
+    /// Bodies larger than this are never flagged by weak signals
+    pub challenge_max_bytes: usize,
+    /// Fraction of the domain's typical page size below which a page is anomalous
+    pub size_anomaly_ratio: f64,
+    /// Clean pages seen on a domain before size anomalies are checked
+    pub min_size_samples: u32,
+}
+
+impl Default for BanDetectorConfig {
+    fn default() -> Self {
+        Self {
+            challenge_max_bytes: DEFAULT_CHALLENGE_MAX_BYTES,
+            size_anomaly_ratio: DEFAULT_SIZE_ANOMALY_RATIO,
+            min_size_samples: DEFAULT_MIN_SIZE_SAMPLES,
+        }
+    }
+}
+
+/// Running page size average for a domain
+#[derive(Debug, Clone, Copy, Default)]
+struct SizeStats {
+    mean: f64,
+    samples: u32,
+}
+
+/// Response classifier shared by all fast path clients
+///
+/// # Example
+/// ```ignore
+/// let detector = BanDetector::new();
+/// match detector.observe("example.com", status, &headers, &body) {
+///     BanVerdict::Clean => { /* parse */ }
+///     BanVerdict::SoftBan(signal) => { /* escalate to the slow path */ }
+///     BanVerdict::HardBan(signal) => { /* back off */ }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct BanDetector {
+    config: BanDetectorConfig,
+    // * Domain -> typical clean page size
+    sizes: Mutex<HashMap<String, SizeStats>>,
+}
+
+impl BanDetector {
+    /// Creates a detector with default thresholds
+    pub fn new() -> Self {
+        Self::with_config(BanDetectorConfig::default())
+    }
+
+    /// Creates a detector with custom thresholds
+    pub fn with_config(config: BanDetectorConfig) -> Self {
+        Self {
+            config,
+            sizes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Classifies a response without side effects
+    ///
+    /// # Checks (in order):
+    /// 1. 429 is a hard ban
+    /// 2. Vendor challenge headers (`cf-mitigated: challenge`) and body markers are soft bans
+    /// 3. Challenge titles and CAPTCHA widgets on small pages are soft bans
+    /// 4. Any other 403 is a hard ban
+    /// 5. A small 200 far below the domain's typical size is a soft ban
+    pub fn classify(&self, domain: &str, status: u16, headers: &HeaderMap, body: &str) -> BanVerdict {
+        let vendor = vendor_from_headers(headers);
+        let signal = |vendor: Option<WafVendor>, reason: &str| BanSignal {
+            status,
+            vendor,
+            reason: reason.to_string(),
+        };
+
+        if status == 429 {
+            return BanVerdict::HardBan(signal(vendor, "Rate limited"));
+        }
+
+        if header_is(headers, "cf-mitigated", "challenge") {
+            return BanVerdict::SoftBan(signal(Some(WafVendor::Cloudflare), "Challenge header"));
+        }
+        let small = body.len() <= self.config.challenge_max_bytes;
+        let mut markers = VENDOR_MARKERS
+            .iter()
+            .chain(SENSOR_MARKERS.iter().filter(|_| small || !(200..300).contains(&status)));
+        if let Some((marker_vendor, marker)) = markers.find(|(_, m)| body.contains(m)) {
+            return BanVerdict::SoftBan(signal(
+                Some(*marker_vendor),
+                &format!("Body marker `{}`", marker),
+            ));
+        }
+
+        if small {
+            if let Some(title) = CHALLENGE_TITLE_REGEX.captures(body).and_then(|c| c.get(1)) {
+                return BanVerdict::SoftBan(signal(
+                    vendor,
+                    &format!("Challenge title `{}`", title.as_str()),
+                ));
+            }
+            if let Some(marker) = CAPTCHA_MARKERS.iter().find(|m| body.contains(*m)) {
+                return BanVerdict::SoftBan(signal(vendor, &format!("CAPTCHA marker `{}`", marker)));
+            }
+        }
+
+        if status == 403 {
+            return BanVerdict::HardBan(signal(vendor, "Forbidden"));
+        }
+
+        if (200..300).contains(&status) && small {
+            if let Some(stats) = self.sizes.lock().unwrap().get(domain) {
+                let threshold = stats.mean * self.config.size_anomaly_ratio;
+                if stats.samples >= self.config.min_size_samples && (body.len() as f64) < threshold {
+                    return BanVerdict::SoftBan(signal(
+                        vendor,
+                        &format!(
+                            "Body size anomaly ({} bytes, typical {:.0})",
+                            body.len(),
+                            stats.mean
+                        ),
+                    ));
+                }
+            }
+        }
+
+        BanVerdict::Clean
+    }
+
+    /// Classifies a response, records ban metrics and learns clean page sizes
+    pub fn observe(&self, domain: &str, status: u16, headers: &HeaderMap, body: &str) -> BanVerdict {
+        let verdict = self.classify(domain, status, headers, body);
+        match &verdict {
+            BanVerdict::SoftBan(signal) => {
+                tracing::warn!(domain = %domain, signal = %signal, "Soft ban detected");
+                ops::record_soft_ban();
+            }
+            BanVerdict::HardBan(signal) => {
+                tracing::warn!(domain = %domain, signal = %signal, "Hard ban detected");
+                ops::record_hard_ban();
+            }
+            BanVerdict::Clean if (200..300).contains(&status) => {
+                self.record_size(domain, body.len());
+            }
+            BanVerdict::Clean => {}
+        }
+        verdict
+    }
+
+    /// Returns the typical clean page size seen for a domain
+    pub fn typical_size(&self, domain: &str) -> Option<f64> {
+        self.sizes.lock().unwrap().get(domain).map(|s| s.mean)
+    }
+
+    fn record_size(&self, domain: &str, len: usize) {
+        let mut sizes = self.sizes.lock().unwrap();
+        let stats = sizes.entry(domain.to_string()).or_default();
+        stats.mean = if stats.samples == 0 {
+            len as f64
+        } else {
+            SIZE_EMA_ALPHA * len as f64 + (1.0 - SIZE_EMA_ALPHA) * stats.mean
+        };
+        stats.samples = stats.samples.saturating_add(1);
+    }
+}
+
+/// Identifies the bot-protection vendor fronting a response, if any
+fn vendor_from_headers(headers: &HeaderMap) -> Option<WafVendor> {
+    let has = |name: &str| headers.contains_key(name);
+    let cookies: Vec<&str> = headers
+        .get_all("set-cookie")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .collect();
+    let cookie = |prefix: &str| cookies.iter().any(|c| c.starts_with(prefix));
+
+    if has("cf-ray") || has("cf-mitigated") || header_is(headers, "server", "cloudflare") {
+        Some(WafVendor::Cloudflare)
+    } else if has("x-datadome") || cookie("datadome=") {
+        Some(WafVendor::DataDome)
+    } else if cookie("_px") || has("x-px-block") {
+        Some(WafVendor::PerimeterX)
+    } else if has("x-iinfo") || cookie("incap_ses") || cookie("visid_incap") {
+        Some(WafVendor::Imperva)
+    } else if header_is(headers, "server", "akamaighost") || has("akamai-grn") {
+        Some(WafVendor::Akamai)
+    } else {
+        None
+    }
+}
+
+fn header_is(headers: &HeaderMap, name: &str, value: &str) -> bool {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn page(bytes: usize) -> String {
+        format!("<html><head><title>News</title></head><body>{}</body></html>", "a".repeat(bytes))
+    }
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.append(*name, HeaderValue::from_static(value));
+        }
+        map
+    }
+
+    #[test]
+    fn test_clean_page() {
+        let detector = BanDetector::new();
+        let verdict = detector.classify("example.com", 200, &HeaderMap::new(), &page(2_000));
+        assert_eq!(verdict, BanVerdict::Clean);
+    }
+
+    #[test]
+    fn test_status_codes() {
+        let detector = BanDetector::new();
+        let empty = HeaderMap::new();
+
+        assert!(matches!(
+            detector.classify("example.com", 429, &empty, ""),
+            BanVerdict::HardBan(BanSignal { status: 429, .. })
+        ));
+        assert!(matches!(
+            detector.classify("example.com", 403, &headers(&[("server", "AkamaiGHost")]), "Denied"),
+            BanVerdict::HardBan(BanSignal { vendor: Some(WafVendor::Akamai), .. })
+        ));
+        // * Ordinary errors are not bans
+        assert_eq!(detector.classify("example.com", 404, &empty, "Not found"), BanVerdict::Clean);
+    }
+
+    #[test]
+    fn test_cloudflare_challenge() {
+        let detector = BanDetector::new();
+        let body = r#"<html><head><title>Just a moment...</title></head>
+            <body><script src="/cdn-cgi/challenge-platform/h/b/orchestrate/jsch/v1"></script></body></html>"#;
+
+        let verdict = detector.classify("example.com", 403, &headers(&[("cf-ray", "8a1b")]), body);
+        assert!(matches!(
+            verdict,
+            BanVerdict::SoftBan(BanSignal { vendor: Some(WafVendor::Cloudflare), .. })
+        ));
+
+        let verdict = detector.classify(
+            "example.com",
+            403,
+            &headers(&[("cf-mitigated", "challenge")]),
+            "",
+        );
+        assert!(matches!(verdict, BanVerdict::SoftBan(_)));
+    }
+
+    #[test]
+    fn test_vendor_markers() {
+        let detector = BanDetector::new();
+        let cases = [
+            (r#"<div id="px-captcha"></div>"#, WafVendor::PerimeterX),
+            (r#"<script src="https://geo.captcha-delivery.com/captcha/"></script>"#, WafVendor::DataDome),
+            ("Request unsuccessful. Incapsula incident ID: 123", WafVendor::Imperva),
+        ];
+
+        for (body, vendor) in cases {
+            match detector.classify("example.com", 200, &HeaderMap::new(), body) {
+                BanVerdict::SoftBan(signal) => assert_eq!(signal.vendor, Some(vendor)),
+                other => panic!("expected soft ban for {:?}, got {:?}", vendor, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_sensor_scripts_on_large_clean_pages_are_not_bans() {
+        let detector = BanDetector::new();
+        let empty = HeaderMap::new();
+
+        for marker in ["_pxAppId", "_Incapsula_Resource", "challenge-platform", "cf-turnstile"] {
+            let large = format!("{}<script>{}</script>", page(DEFAULT_CHALLENGE_MAX_BYTES), marker);
+            let small = format!("<html><body><script>{}</script></body></html>", marker);
+
+            assert_eq!(detector.classify("example.com", 200, &empty, &large), BanVerdict::Clean);
+            assert!(matches!(detector.classify("example.com", 200, &empty, &small), BanVerdict::SoftBan(_)));
+            assert!(matches!(detector.classify("example.com", 503, &empty, &large), BanVerdict::SoftBan(_)));
+        }
+    }
+
+    #[test]
+    fn test_captcha_widget_only_counts_on_small_pages() {
+        let detector = BanDetector::new();
+        let small = r#"<html><body><div class="g-recaptcha"></div></body></html>"#;
+        let large = format!("{}<div class=\"g-recaptcha\"></div>", page(DEFAULT_CHALLENGE_MAX_BYTES));
+
+        assert!(matches!(
+            detector.classify("example.com", 200, &HeaderMap::new(), small),
+            BanVerdict::SoftBan(_)
+        ));
+        assert_eq!(
+            detector.classify("example.com", 200, &HeaderMap::new(), &large),
+            BanVerdict::Clean
+        );
+    }
+
+    #[test]
+    fn test_size_anomaly() {
+        let detector = BanDetector::new();
+        let empty = HeaderMap::new();
+        for _ in 0..DEFAULT_MIN_SIZE_SAMPLES {
+            assert_eq!(detector.observe("example.com", 200, &empty, &page(20_000)), BanVerdict::Clean);
+        }
+        assert!(detector.typical_size("example.com").unwrap() > 20_000.0);
+
+        let verdict = detector.observe("example.com", 200, &empty, &page(600));
+        assert!(matches!(verdict, BanVerdict::SoftBan(ref s) if s.reason.contains("size anomaly")));
+
+        // * Other domains have no history yet
+        assert_eq!(detector.observe("other.com", 200, &empty, &page(600)), BanVerdict::Clean);
+    }
+
+    #[test]
+    fn test_signal_display() {
+        let signal = BanSignal {
+            status: 403,
+            vendor: Some(WafVendor::Cloudflare),
+            reason: "Challenge header".to_string(),
+        };
+        assert_eq!(signal.to_string(), "Challenge header (Cloudflare, HTTP 403)");
+    }
+}
@@ -0,0 +1,187 @@
+// * [FR-03] Headless Browser Limits
+// * Long-lived Chromium processes slowly leak memory and eventually stall the slow path. Each
+// * render is watched (wall time, JS heap, main-thread CPU) and the browser is relaunched after
+// * a number of pages, after a maximum age, or as soon as a page breaches a watchdog limit.
+
+use crate::config::constants::PAGE_TIMEOUT_MS;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use thiserror::Error;
+
+/// A watchdog limit a page went over
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum PageLimit {
+    #[error("render took longer than {0}ms")]
+    Time(u64),
+
+    #[error("JS heap reached {0} bytes")]
+    Memory(u64),
+
+    #[error("main thread used {0}ms of CPU")]
+    Cpu(u64),
+}
+
+impl PageLimit {
+    /// Metric label
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PageLimit::Time(_) => "time",
+            PageLimit::Memory(_) => "memory",
+            PageLimit::Cpu(_) => "cpu",
+        }
+    }
+}
+
+/// Resource use of one page, sampled from the CDP Performance domain
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PageUsage {
+    /// `JSHeapUsedSize`
+    pub js_heap_bytes: u64,
+    /// `TaskDuration`: time the renderer main thread spent running tasks
+    pub cpu_ms: u64,
+}
+
+impl PageUsage {
+    /// Reads the metrics reported by `Performance.getMetrics`, ignoring unknown names
+    pub fn from_metrics<'a>(metrics: impl IntoIterator<Item = (&'a str, f64)>) -> Self {
+        let mut usage = Self::default();
+        for (name, value) in metrics {
+            match name {
+                "JSHeapUsedSize" => usage.js_heap_bytes = value.max(0.0) as u64,
+                "TaskDuration" => usage.cpu_ms = (value.max(0.0) * 1000.0) as u64,
+                _ => {}
+            }
+        }
+        usage
+    }
+}
+
+/// Per-page watchdogs and browser recycling policy for the slow path
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BrowserLimits {
+    /// Pages rendered before the browser is relaunched (0 never recycles on count)
+    pub max_pages_per_browser: u32,
+    /// Seconds a browser runs before it is relaunched (0 never recycles on age)
+    pub max_browser_age_secs: u64,
+    /// Longest a render may take, navigation through extraction
+    pub page_timeout_ms: u64,
+    /// JS heap a page may use in bytes (0 disables the check)
+    pub max_page_memory_bytes: u64,
+    /// Main-thread CPU time a page may use in milliseconds (0 disables the check)
+    pub max_page_cpu_ms: u64,
+    /// Milliseconds between watchdog samples
+    pub watchdog_interval_ms: u64,
+}
+
+impl Default for BrowserLimits {
+    fn default() -> Self {
+        Self {
+            max_pages_per_browser: 100,
+            max_browser_age_secs: 30 * 60,
+            // * Leaves the readiness timeout room to extract a page that never settled
+            page_timeout_ms: PAGE_TIMEOUT_MS + 30_000,
+            max_page_memory_bytes: 512 * 1024 * 1024,
+            max_page_cpu_ms: 30_000,
+            watchdog_interval_ms: 1_000,
+        }
+    }
+}
+
+impl BrowserLimits {
+    /// Checks a page's usage after `elapsed`; returns the first limit exceeded
+    pub fn check(&self, usage: &PageUsage, elapsed: Duration) -> Result<(), PageLimit> {
+        if elapsed >= Duration::from_millis(self.page_timeout_ms) {
+            return Err(PageLimit::Time(self.page_timeout_ms));
+        }
+        if self.max_page_memory_bytes > 0 && usage.js_heap_bytes > self.max_page_memory_bytes {
+            return Err(PageLimit::Memory(usage.js_heap_bytes));
+        }
+        if self.max_page_cpu_ms > 0 && usage.cpu_ms > self.max_page_cpu_ms {
+            return Err(PageLimit::Cpu(usage.cpu_ms));
+        }
+        Ok(())
+    }
+
+    /// Returns why a browser that rendered `pages` pages over `age` should be relaunched
+    pub fn recycle_reason(&self, pages: u32, age: Duration) -> Option<&'static str> {
+        if self.max_pages_per_browser > 0 && pages >= self.max_pages_per_browser {
+            Some("max_pages")
+        } else if self.max_browser_age_secs > 0 && age >= Duration::from_secs(self.max_browser_age_secs) {
+            Some("max_age")
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_is_read_from_performance_metrics() {
+        let usage = PageUsage::from_metrics([
+            ("Nodes", 1200.0),
+            ("JSHeapUsedSize", 48_000_000.0),
+            ("TaskDuration", 1.25),
+        ]);
+        assert_eq!(
+            usage,
+            PageUsage {
+                js_heap_bytes: 48_000_000,
+                cpu_ms: 1_250,
+            }
+        );
+    }
+
+    #[test]
+    fn test_watchdog_limits() {
+        let limits = BrowserLimits {
+            page_timeout_ms: 5_000,
+            max_page_memory_bytes: 100,
+            max_page_cpu_ms: 10,
+            ..Default::default()
+        };
+        let light = PageUsage { js_heap_bytes: 50, cpu_ms: 5 };
+
+        assert_eq!(limits.check(&light, Duration::from_secs(1)), Ok(()));
+        assert_eq!(limits.check(&light, Duration::from_secs(5)), Err(PageLimit::Time(5_000)));
+        assert_eq!(
+            limits.check(&PageUsage { js_heap_bytes: 101, cpu_ms: 50 }, Duration::ZERO),
+            Err(PageLimit::Memory(101))
+        );
+        assert_eq!(
+            limits.check(&PageUsage { js_heap_bytes: 0, cpu_ms: 11 }, Duration::ZERO),
+            Err(PageLimit::Cpu(11))
+        );
+
+        let unchecked = BrowserLimits {
+            max_page_memory_bytes: 0,
+            max_page_cpu_ms: 0,
+            ..Default::default()
+        };
+        let heavy = PageUsage { js_heap_bytes: u64::MAX, cpu_ms: u64::MAX };
+        assert_eq!(unchecked.check(&heavy, Duration::ZERO), Ok(()));
+    }
+
+    #[test]
+    fn test_recycle_reason() {
+        let limits = BrowserLimits {
+            max_pages_per_browser: 10,
+            max_browser_age_secs: 60,
+            ..Default::default()
+        };
+
+        assert_eq!(limits.recycle_reason(9, Duration::from_secs(59)), None);
+        assert_eq!(limits.recycle_reason(10, Duration::ZERO), Some("max_pages"));
+        assert_eq!(limits.recycle_reason(0, Duration::from_secs(60)), Some("max_age"));
+
+        let never = BrowserLimits {
+            max_pages_per_browser: 0,
+            max_browser_age_secs: 0,
+            ..Default::default()
+        };
+        assert_eq!(never.recycle_reason(u32::MAX, Duration::MAX), None);
+    }
+}
@@ -84,7 +84,7 @@ impl CircuitBreaker {
         if let Some(mut redis) = self.redis.clone() {
             // * Check if domain requires full browser
             let requires_full_browser: bool = redis
-                .get(&Self::config_key(domain))
+                .get(Self::config_key(domain))
                 .await
                 .unwrap_or(false);
 
@@ -98,7 +98,7 @@ impl CircuitBreaker {
 
             // * Get current failure count
             let failure_count: u32 = redis
-                .get(&Self::failure_key(domain))
+                .get(Self::failure_key(domain))
                 .await
                 .unwrap_or(0);
 
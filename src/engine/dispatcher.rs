@@ -1,12 +1,26 @@
 // * [NFR-02] Memory Adaptive Dispatcher
 // * Monitors system RAM usage and throttles crawling when memory pressure is detected
+// * [FR-01] Host-Sharded Worker Pool
+// * URLs are sharded to worker lanes by host hash so a host's politeness state stays in one
+// * lane; idle lanes steal work for hosts no other lane is fetching
 
-use std::sync::Arc;
-use std::time::Duration;
+use crate::ops;
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use sysinfo::System;
-use tokio::sync::RwLock;
+use thiserror::Error;
+use tokio::sync::{Notify, RwLock};
+use tokio::task::JoinHandle;
 use tokio::time::interval;
 use tracing::{debug, info, warn};
+use xxhash_rust::xxh64::xxh64;
 
 // * Memory pressure thresholds (percentage)
 const PRESSURE_THRESHOLD_HIGH: f64 = 90.0;
@@ -99,6 +113,354 @@ impl Default for MemoryMonitor {
     }
 }
 
+// * How long an idle worker waits before looking for work to steal again
+const STEAL_INTERVAL_MS: u64 = 50;
+// * How long a worker waits before re-checking memory pressure
+const PRESSURE_BACKOFF_MS: u64 = 100;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum DispatchError {
+    #[error("Dispatcher is shut down")]
+    Closed,
+
+    #[error("Dispatch queue is full ({0} jobs)")]
+    QueueFull(usize),
+}
+
+/// Worker pool settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DispatcherConfig {
+    /// Worker lanes; each host is sharded to one lane
+    pub lanes: usize,
+    /// Jobs a lane runs at once
+    pub max_in_flight_per_lane: usize,
+    /// Jobs waiting across all lanes before `submit` is refused
+    pub max_queued: usize,
+    /// Let idle lanes take work queued on busy ones
+    pub work_stealing: bool,
+}
+
+impl Default for DispatcherConfig {
+    fn default() -> Self {
+        Self {
+            lanes: 8,
+            max_in_flight_per_lane: 4,
+            max_queued: 10_000,
+            work_stealing: true,
+        }
+    }
+}
+
+/// Point-in-time counters for one lane
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LaneStats {
+    pub queued: usize,
+    pub in_flight: usize,
+    pub processed: usize,
+    /// Jobs this lane took from other lanes
+    pub stolen: usize,
+    /// Jobs whose handler panicked (also counted as processed)
+    pub panicked: usize,
+}
+
+type Handler<J> = Arc<dyn Fn(J) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
+
+struct Queued<J> {
+    host: String,
+    job: J,
+    enqueued: Instant,
+}
+
+struct Lane<J> {
+    queue: Mutex<VecDeque<Queued<J>>>,
+    notify: Notify,
+    queued: AtomicUsize,
+    in_flight: AtomicUsize,
+    processed: AtomicUsize,
+    stolen: AtomicUsize,
+    panicked: AtomicUsize,
+}
+
+struct Shared<J> {
+    config: DispatcherConfig,
+    lanes: Vec<Lane<J>>,
+    // * Host -> (lane running it, jobs in flight); a host never runs on two lanes at once
+    leases: Mutex<HashMap<String, (usize, usize)>>,
+    queued: AtomicUsize,
+    closed: AtomicBool,
+    pressure: Option<Arc<RwLock<bool>>>,
+}
+
+/// Host-sharded worker pool with work stealing
+///
+/// Each lane runs up to `max_in_flight_per_lane` jobs. A lane only starts a job
+/// when no other lane is running the job's host, so per-host state (politeness,
+/// rate limits, connection pools) is only ever touched from one lane at a time.
+///
+/// The `Orchestrator` submits every page it pops from the frontier here, keyed
+/// by the page's host.
+///
+/// # Example
+/// ```ignore
+/// let dispatcher = Dispatcher::spawn(DispatcherConfig::default(), |url: String| async move {
+///     fetch(&url).await;
+/// });
+/// dispatcher.submit("example.com", "https://example.com/".to_string())?;
+/// dispatcher.shutdown().await;
+/// ```
+pub struct Dispatcher<J: Send + 'static> {
+    shared: Arc<Shared<J>>,
+    workers: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl<J: Send + 'static> Dispatcher<J> {
+    /// Starts the workers; `handler` runs every submitted job
+    pub fn spawn<F, Fut>(config: DispatcherConfig, handler: F) -> Self
+    where
+        F: Fn(J) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        Self::start(config, None, handler)
+    }
+
+    /// Starts the workers, pausing new jobs while the monitor reports memory pressure
+    pub fn spawn_with_pressure<F, Fut>(config: DispatcherConfig, monitor: &MemoryMonitor, handler: F) -> Self
+    where
+        F: Fn(J) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        Self::start(config, Some(monitor.pressure_handle()), handler)
+    }
+
+    fn start<F, Fut>(mut config: DispatcherConfig, pressure: Option<Arc<RwLock<bool>>>, handler: F) -> Self
+    where
+        F: Fn(J) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        config.lanes = config.lanes.max(1);
+        config.max_in_flight_per_lane = config.max_in_flight_per_lane.max(1);
+        let lanes = (0..config.lanes)
+            .map(|_| Lane {
+                queue: Mutex::new(VecDeque::new()),
+                notify: Notify::new(),
+                queued: AtomicUsize::new(0),
+                in_flight: AtomicUsize::new(0),
+                processed: AtomicUsize::new(0),
+                stolen: AtomicUsize::new(0),
+                panicked: AtomicUsize::new(0),
+            })
+            .collect();
+        let per_lane = config.max_in_flight_per_lane;
+        let lane_count = config.lanes;
+        let shared = Arc::new(Shared {
+            config,
+            lanes,
+            leases: Mutex::new(HashMap::new()),
+            queued: AtomicUsize::new(0),
+            closed: AtomicBool::new(false),
+            pressure,
+        });
+        let handler: Handler<J> = Arc::new(move |job| Box::pin(handler(job)));
+
+        let workers = (0..lane_count)
+            .flat_map(|lane| (0..per_lane).map(move |_| lane))
+            .map(|lane| tokio::spawn(run_worker(Arc::clone(&shared), lane, Arc::clone(&handler))))
+            .collect();
+        Self {
+            shared,
+            workers: Mutex::new(workers),
+        }
+    }
+
+    /// Returns the lane a host is sharded to
+    pub fn lane_for(&self, host: &str) -> usize {
+        lane_for(host, self.shared.lanes.len())
+    }
+
+    /// Queues a job on its host's lane and returns the lane
+    pub fn submit(&self, host: &str, job: J) -> Result<usize, DispatchError> {
+        let shared = &self.shared;
+        if shared.closed.load(Ordering::Acquire) {
+            return Err(DispatchError::Closed);
+        }
+        let max_queued = shared.config.max_queued;
+        if shared
+            .queued
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| (n < max_queued).then_some(n + 1))
+            .is_err()
+        {
+            return Err(DispatchError::QueueFull(max_queued));
+        }
+
+        let host = host.to_ascii_lowercase();
+        let index = lane_for(&host, shared.lanes.len());
+        let lane = &shared.lanes[index];
+        {
+            let mut queue = lane.queue.lock().unwrap();
+            queue.push_back(Queued {
+                host,
+                job,
+                enqueued: Instant::now(),
+            });
+            lane.queued.fetch_add(1, Ordering::AcqRel);
+        }
+        lane.notify.notify_one();
+        Ok(index)
+    }
+
+    /// Returns per-lane counters, indexed by lane
+    pub fn stats(&self) -> Vec<LaneStats> {
+        self.shared
+            .lanes
+            .iter()
+            .map(|lane| LaneStats {
+                queued: lane.queued.load(Ordering::Acquire),
+                in_flight: lane.in_flight.load(Ordering::Acquire),
+                processed: lane.processed.load(Ordering::Acquire),
+                stolen: lane.stolen.load(Ordering::Acquire),
+                panicked: lane.panicked.load(Ordering::Acquire),
+            })
+            .collect()
+    }
+
+    /// Returns the jobs waiting across all lanes
+    pub fn queued(&self) -> usize {
+        self.shared.queued.load(Ordering::Acquire)
+    }
+
+    /// Refuses new jobs, runs everything already queued, then stops the workers
+    pub async fn shutdown(&self) {
+        self.shared.closed.store(true, Ordering::Release);
+        for lane in &self.shared.lanes {
+            lane.notify.notify_waiters();
+        }
+        let workers = std::mem::take(&mut *self.workers.lock().unwrap());
+        for worker in workers {
+            let _ = worker.await;
+        }
+    }
+
+    /// Refuses new jobs and stops the workers now, cancelling running jobs and dropping queued ones
+    pub fn abort(&self) {
+        self.shared.closed.store(true, Ordering::Release);
+        for worker in self.workers.lock().unwrap().drain(..) {
+            worker.abort();
+        }
+    }
+}
+
+impl<J: Send + 'static> Drop for Dispatcher<J> {
+    fn drop(&mut self) {
+        if let Ok(workers) = self.workers.get_mut() {
+            for worker in workers.drain(..) {
+                worker.abort();
+            }
+        }
+    }
+}
+
+fn lane_for(host: &str, lanes: usize) -> usize {
+    (xxh64(host.to_ascii_lowercase().as_bytes(), 0) % lanes.max(1) as u64) as usize
+}
+
+impl<J> Shared<J> {
+    // * Takes the lane's oldest runnable job, or steals the newest runnable job of the longest
+    // * other queue; returns the job and whether it was stolen
+    fn take(&self, index: usize) -> Option<(Queued<J>, bool)> {
+        if let Some(job) = self.take_from(index, index, false) {
+            return Some((job, false));
+        }
+        if !self.config.work_stealing {
+            return None;
+        }
+        let victim = (0..self.lanes.len())
+            .filter(|&other| other != index)
+            .max_by_key(|&other| self.lanes[other].queued.load(Ordering::Acquire))
+            .filter(|&other| self.lanes[other].queued.load(Ordering::Acquire) > 0)?;
+        self.take_from(victim, index, true).map(|job| (job, true))
+    }
+
+    // * Removes a job from `from` whose host is free or already leased to `to`, leasing it to `to`
+    fn take_from(&self, from: usize, to: usize, newest: bool) -> Option<Queued<J>> {
+        let mut queue = self.lanes[from].queue.lock().unwrap();
+        let mut leases = self.leases.lock().unwrap();
+        let runnable = |job: &Queued<J>| leases.get(&job.host).is_none_or(|&(lane, _)| lane == to);
+        let position = if newest {
+            queue.iter().rposition(runnable)
+        } else {
+            queue.iter().position(runnable)
+        }?;
+        let job = queue.remove(position)?;
+        leases.entry(job.host.clone()).or_insert((to, 0)).1 += 1;
+        self.lanes[from].queued.fetch_sub(1, Ordering::AcqRel);
+        self.queued.fetch_sub(1, Ordering::AcqRel);
+        Some(job)
+    }
+
+    fn release(&self, host: &str) {
+        let mut leases = self.leases.lock().unwrap();
+        if let Some((_, count)) = leases.get_mut(host) {
+            *count -= 1;
+            if *count == 0 {
+                leases.remove(host);
+            }
+        }
+    }
+
+    async fn under_pressure(&self) -> bool {
+        match &self.pressure {
+            Some(pressure) => *pressure.read().await,
+            None => false,
+        }
+    }
+}
+
+async fn run_worker<J: Send + 'static>(shared: Arc<Shared<J>>, index: usize, handler: Handler<J>) {
+    let lane = &shared.lanes[index];
+    let label = index.to_string();
+    let capacity = shared.config.max_in_flight_per_lane as f64;
+    loop {
+        let closed = shared.closed.load(Ordering::Acquire);
+        if closed && shared.queued.load(Ordering::Acquire) == 0 {
+            break;
+        }
+        // * Queued work stays queued under memory pressure; shutdown still drains it
+        if !closed && shared.under_pressure().await {
+            tokio::time::sleep(Duration::from_millis(PRESSURE_BACKOFF_MS)).await;
+            continue;
+        }
+
+        let Some((queued, stolen)) = shared.take(index) else {
+            tokio::select! {
+                _ = lane.notify.notified() => {}
+                _ = tokio::time::sleep(Duration::from_millis(STEAL_INTERVAL_MS)) => {}
+            }
+            continue;
+        };
+
+        ops::record_dispatch_wait(if stolen { "stolen" } else { "own" }, queued.enqueued.elapsed().as_secs_f64());
+        if stolen {
+            lane.stolen.fetch_add(1, Ordering::AcqRel);
+        }
+        let in_flight = lane.in_flight.fetch_add(1, Ordering::AcqRel) + 1;
+        ops::set_lane_utilization(&label, in_flight as f64 / capacity);
+
+        // * A panicking job must not take the worker down with it: the host's lease and the
+        // * lane's in-flight count are released on every path, or the host never runs again
+        let job = queued.job;
+        if AssertUnwindSafe(async { handler(job).await }).catch_unwind().await.is_err() {
+            warn!(host = %queued.host, lane = index, "Dispatched job panicked");
+            lane.panicked.fetch_add(1, Ordering::AcqRel);
+        }
+
+        shared.release(&queued.host);
+        let in_flight = lane.in_flight.fetch_sub(1, Ordering::AcqRel) - 1;
+        lane.processed.fetch_add(1, Ordering::AcqRel);
+        ops::set_lane_utilization(&label, in_flight as f64 / capacity);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,7 +477,7 @@ mod tests {
         let monitor = MemoryMonitor::new();
         let usage = monitor.get_ram_usage_percent().await;
         // * RAM usage should be between 0 and 100
-        assert!(usage >= 0.0 && usage <= 100.0);
+        assert!((0.0..=100.0).contains(&usage));
     }
 
     #[tokio::test]
@@ -129,4 +491,169 @@ mod tests {
         // * Should reflect in monitor
         assert!(monitor.is_under_pressure().await);
     }
+
+    // * Returns `count` distinct hosts sharded to `lane`
+    fn hosts_on_lane(lane: usize, lanes: usize, count: usize) -> Vec<String> {
+        (0..)
+            .map(|i| format!("host-{}.example.com", i))
+            .filter(|host| lane_for(host, lanes) == lane)
+            .take(count)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_all_jobs_run_on_their_host_lane() {
+        let done = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&done);
+        let dispatcher = Dispatcher::spawn(DispatcherConfig::default(), move |_: u32| {
+            let counter = Arc::clone(&counter);
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        for i in 0..40u32 {
+            let host = format!("site{}.example.com", i % 5);
+            assert_eq!(dispatcher.submit(&host, i), Ok(dispatcher.lane_for(&host)));
+        }
+        assert_eq!(dispatcher.lane_for("Site1.Example.com"), dispatcher.lane_for("site1.example.com"));
+        dispatcher.shutdown().await;
+
+        assert_eq!(done.load(Ordering::SeqCst), 40);
+        assert_eq!(dispatcher.stats().iter().map(|s| s.processed).sum::<usize>(), 40);
+        assert_eq!(dispatcher.submit("example.com", 0), Err(DispatchError::Closed));
+    }
+
+    #[tokio::test]
+    async fn test_idle_lane_steals_without_sharing_hosts() {
+        let config = DispatcherConfig {
+            lanes: 2,
+            max_in_flight_per_lane: 1,
+            ..Default::default()
+        };
+        let hosts = hosts_on_lane(0, 2, 2);
+        // * Host -> jobs running now, and the most ever seen at once
+        let running: Arc<Mutex<HashMap<String, usize>>> = Arc::default();
+        let peak = Arc::new(AtomicUsize::new(0));
+        let (running_ref, peak_ref) = (Arc::clone(&running), Arc::clone(&peak));
+        let dispatcher = Dispatcher::spawn(config, move |host: String| {
+            let (running, peak) = (Arc::clone(&running_ref), Arc::clone(&peak_ref));
+            async move {
+                let now = {
+                    let mut running = running.lock().unwrap();
+                    let count = running.entry(host.clone()).or_default();
+                    *count += 1;
+                    *count
+                };
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                *running.lock().unwrap().get_mut(&host).unwrap() -= 1;
+            }
+        });
+
+        for _ in 0..4 {
+            for host in &hosts {
+                dispatcher.submit(host, host.clone()).unwrap();
+            }
+        }
+        dispatcher.shutdown().await;
+
+        let stats = dispatcher.stats();
+        assert_eq!(stats[0].processed + stats[1].processed, 8);
+        assert!(stats[1].stolen > 0);
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_panicking_job_releases_its_host() {
+        let done = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&done);
+        let config = DispatcherConfig {
+            lanes: 2,
+            max_in_flight_per_lane: 1,
+            ..Default::default()
+        };
+        let dispatcher = Dispatcher::spawn(config, move |job: u32| {
+            let counter = Arc::clone(&counter);
+            async move {
+                if job == 0 {
+                    panic!("job 0 failed");
+                }
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        for job in 0..4u32 {
+            dispatcher.submit("example.com", job).unwrap();
+        }
+        // * Shutdown only returns once the host's later jobs ran after the panic
+        tokio::time::timeout(Duration::from_secs(5), dispatcher.shutdown())
+            .await
+            .expect("shutdown hung after a panicking job");
+
+        assert_eq!(done.load(Ordering::SeqCst), 3);
+        let stats = dispatcher.stats();
+        assert_eq!(stats.iter().map(|s| s.panicked).sum::<usize>(), 1);
+        assert_eq!(stats.iter().map(|s| s.processed).sum::<usize>(), 4);
+        assert!(stats.iter().all(|s| s.in_flight == 0));
+    }
+
+    #[tokio::test]
+    async fn test_queue_is_bounded() {
+        let gate = Arc::new(Notify::new());
+        let waiter = Arc::clone(&gate);
+        let dispatcher = Dispatcher::spawn(
+            DispatcherConfig {
+                lanes: 1,
+                max_in_flight_per_lane: 1,
+                max_queued: 2,
+                work_stealing: false,
+            },
+            move |_: u32| {
+                let gate = Arc::clone(&waiter);
+                async move { gate.notified().await }
+            },
+        );
+
+        // * The first job is taken by the worker and blocks it
+        dispatcher.submit("example.com", 1).unwrap();
+        while dispatcher.queued() > 0 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        dispatcher.submit("example.com", 2).unwrap();
+        dispatcher.submit("example.com", 3).unwrap();
+        assert_eq!(dispatcher.submit("example.com", 4), Err(DispatchError::QueueFull(2)));
+        assert_eq!(dispatcher.stats()[0].in_flight, 1);
+
+        for _ in 0..3 {
+            gate.notify_one();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        dispatcher.shutdown().await;
+        assert_eq!(dispatcher.stats()[0].processed, 3);
+    }
+
+    #[tokio::test]
+    async fn test_abort_cancels_running_jobs() {
+        let done = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&done);
+        let dispatcher = Dispatcher::spawn(DispatcherConfig::default(), move |_: u32| {
+            let counter = Arc::clone(&counter);
+            async move {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        dispatcher.submit("example.com", 1).unwrap();
+        while dispatcher.queued() > 0 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        dispatcher.abort();
+        // * Nothing is left to drain, so shutdown returns at once
+        tokio::time::timeout(Duration::from_secs(1), dispatcher.shutdown()).await.unwrap();
+
+        assert_eq!(done.load(Ordering::SeqCst), 0);
+        assert_eq!(dispatcher.submit("example.com", 2), Err(DispatchError::Closed));
+    }
 }
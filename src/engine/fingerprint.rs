@@ -1,8 +1,13 @@
 // * [FR-03.b] [EDD-3.2] Smart Caching via Head Fingerprinting
 // * Computes content fingerprints to detect duplicate/unchanged pages
+// * [EDD-1.2] Browser Fingerprints
+// * Coherent browser profiles (UA, client hints, Accept-*, header order) rotated per domain
+// * Profiles also supply the in-browser values (viewport, timezone, WebGL) for slow path stealth
 
 use regex::Regex;
-use std::sync::LazyLock;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex};
 use xxhash_rust::xxh64::xxh64;
 
 // * Precompiled regex patterns for metadata extraction
@@ -81,6 +86,421 @@ pub fn has_content_changed(new_fingerprint: u64, cached_fingerprint: u64) -> boo
     new_fingerprint != cached_fingerprint
 }
 
+// * Navigation Accept headers per engine
+const CHROMIUM_ACCEPT: &str = "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8,application/signed-exchange;v=b3;q=0.7";
+const GECKO_ACCEPT: &str = "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,*/*;q=0.8";
+const WEBKIT_ACCEPT: &str = "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8";
+
+// * Encodings advertised per engine; network::decompress handles all of them.
+// * Safari does not send zstd, so advertising it there would stand out.
+const ACCEPT_ENCODING: &str = "gzip, deflate, br, zstd";
+const WEBKIT_ACCEPT_ENCODING: &str = "gzip, deflate, br";
+
+/// Browser engine family a profile imitates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BrowserFamily {
+    Chrome,
+    Edge,
+    Firefox,
+    Safari,
+}
+
+/// Operating system reported by a profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Platform {
+    Windows,
+    MacOs,
+    Linux,
+}
+
+// * Chrome version used when a profile has to be presented by Chromium
+const FALLBACK_CHROME_VERSION: u32 = 122;
+
+impl Platform {
+    fn ua_token(&self, family: BrowserFamily) -> &'static str {
+        match (self, family) {
+            (Platform::Windows, _) => "Windows NT 10.0; Win64; x64",
+            (Platform::MacOs, BrowserFamily::Firefox) => "Macintosh; Intel Mac OS X 10.15",
+            (Platform::MacOs, _) => "Macintosh; Intel Mac OS X 10_15_7",
+            (Platform::Linux, _) => "X11; Linux x86_64",
+        }
+    }
+
+    fn client_hint(&self) -> &'static str {
+        match self {
+            Platform::Windows => r#""Windows""#,
+            Platform::MacOs => r#""macOS""#,
+            Platform::Linux => r#""Linux""#,
+        }
+    }
+
+    // * navigator.platform as Chromium reports it
+    fn navigator_platform(&self) -> &'static str {
+        match self {
+            Platform::Windows => "Win32",
+            Platform::MacOs => "MacIntel",
+            Platform::Linux => "Linux x86_64",
+        }
+    }
+
+    // * Common desktop window sizes per platform
+    fn viewports(&self) -> &'static [(u32, u32)] {
+        match self {
+            Platform::MacOs => &[(1440, 900), (1512, 982), (1728, 1117), (1280, 800)],
+            _ => &[(1920, 1080), (1366, 768), (1536, 864), (1440, 900), (1280, 720)],
+        }
+    }
+
+    fn core_counts(&self) -> &'static [u32] {
+        match self {
+            Platform::MacOs => &[8, 10, 12],
+            _ => &[4, 8, 12, 16],
+        }
+    }
+
+    // * (UNMASKED_VENDOR_WEBGL, UNMASKED_RENDERER_WEBGL) pairs Chrome reports through ANGLE
+    fn webgl(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Platform::Windows => &[
+                ("Google Inc. (NVIDIA)", "ANGLE (NVIDIA, NVIDIA GeForce GTX 1660 SUPER Direct3D11 vs_5_0 ps_5_0, D3D11)"),
+                ("Google Inc. (Intel)", "ANGLE (Intel, Intel(R) UHD Graphics 620 Direct3D11 vs_5_0 ps_5_0, D3D11)"),
+                ("Google Inc. (AMD)", "ANGLE (AMD, AMD Radeon RX 580 Series Direct3D11 vs_5_0 ps_5_0, D3D11)"),
+            ],
+            Platform::MacOs => &[
+                ("Google Inc. (Apple)", "ANGLE (Apple, Apple M1, OpenGL 4.1)"),
+                ("Google Inc. (Intel Inc.)", "ANGLE (Intel Inc., Intel(R) Iris(TM) Plus Graphics 655, OpenGL 4.1)"),
+            ],
+            Platform::Linux => &[
+                ("Google Inc. (Intel)", "ANGLE (Intel, Mesa Intel(R) UHD Graphics 620 (KBL GT2), OpenGL 4.6)"),
+                ("Google Inc. (NVIDIA Corporation)", "ANGLE (NVIDIA Corporation, NVIDIA GeForce GTX 1080/PCIe/SSE2, OpenGL 4.5.0)"),
+            ],
+        }
+    }
+}
+
+// * IANA timezone for a locale's most populous region (UTC when unknown)
+fn locale_timezone(locale: &str) -> &'static str {
+    match locale.to_ascii_lowercase().as_str() {
+        "en-us" => "America/New_York",
+        "en-gb" => "Europe/London",
+        "en-ca" | "fr-ca" => "America/Toronto",
+        "en-au" => "Australia/Sydney",
+        "de" | "de-de" => "Europe/Berlin",
+        "fr" | "fr-fr" => "Europe/Paris",
+        "es" | "es-es" => "Europe/Madrid",
+        "ja" | "ja-jp" => "Asia/Tokyo",
+        _ => "UTC",
+    }
+}
+
+/// In-browser values the slow path presents for a profile
+///
+/// Injected into every rendered page so that the JavaScript-visible environment
+/// (navigator, screen, timezone, WebGL) agrees with the request headers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StealthProfile {
+    pub user_agent: String,
+    pub accept_language: String,
+    /// `navigator.languages`, most preferred first
+    pub languages: Vec<String>,
+    /// Primary locale (e.g. "en-GB")
+    pub locale: String,
+    /// `navigator.platform` (e.g. "Win32")
+    pub platform: &'static str,
+    /// Brand and version reported through User-Agent Client Hints
+    pub brand: &'static str,
+    pub major_version: u32,
+    /// `sec-ch-ua-platform` value without quotes (e.g. "Windows")
+    pub client_hint_platform: &'static str,
+    /// Window size in CSS pixels
+    pub viewport: (u32, u32),
+    /// IANA timezone matching the locale
+    pub timezone: &'static str,
+    pub hardware_concurrency: u32,
+    pub webgl_vendor: &'static str,
+    pub webgl_renderer: &'static str,
+}
+
+/// A self-consistent set of browser request headers
+///
+/// Every header a real browser of this family, version, platform and locale
+/// sends on a top-level navigation, in that browser's order. Chromium
+/// browsers send `sec-ch-ua` client hints; Firefox and Safari never do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrowserProfile {
+    pub family: BrowserFamily,
+    pub platform: Platform,
+    pub major_version: u32,
+    pub user_agent: String,
+    pub accept_language: String,
+    headers: Vec<(&'static str, String)>,
+}
+
+impl BrowserProfile {
+    /// Builds a profile for a browser, platform and primary locale (e.g. "en-GB")
+    ///
+    /// Safari is only shipped on macOS; other platforms fall back to macOS.
+    pub fn new(family: BrowserFamily, platform: Platform, major_version: u32, locale: &str) -> Self {
+        let platform = match family {
+            BrowserFamily::Safari => Platform::MacOs,
+            _ => platform,
+        };
+        let language = locale.split('-').next().unwrap_or(locale);
+        let os = platform.ua_token(family);
+
+        let (user_agent, accept_language) = match family {
+            BrowserFamily::Chrome => (
+                format!("Mozilla/5.0 ({}) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/{}.0.0.0 Safari/537.36", os, major_version),
+                format!("{},{};q=0.9", locale, language),
+            ),
+            BrowserFamily::Edge => (
+                format!("Mozilla/5.0 ({}) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/{v}.0.0.0 Safari/537.36 Edg/{v}.0.0.0", os, v = major_version),
+                format!("{},{};q=0.9", locale, language),
+            ),
+            BrowserFamily::Firefox => (
+                format!("Mozilla/5.0 ({}; rv:{v}.0) Gecko/20100101 Firefox/{v}.0", os, v = major_version),
+                format!("{},{};q=0.5", locale, language),
+            ),
+            BrowserFamily::Safari => (
+                format!("Mozilla/5.0 ({}) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/{}.0 Safari/605.1.15", os, major_version),
+                format!("{},{};q=0.9", locale, language),
+            ),
+        };
+
+        let headers = match family {
+            BrowserFamily::Chrome | BrowserFamily::Edge => {
+                let brand = if family == BrowserFamily::Edge { "Microsoft Edge" } else { "Google Chrome" };
+                vec![
+                    ("sec-ch-ua", format!(r#""Not_A Brand";v="8", "Chromium";v="{v}", "{}";v="{v}""#, brand, v = major_version)),
+                    ("sec-ch-ua-mobile", "?0".to_string()),
+                    ("sec-ch-ua-platform", platform.client_hint().to_string()),
+                    ("upgrade-insecure-requests", "1".to_string()),
+                    ("user-agent", user_agent.clone()),
+                    ("accept", CHROMIUM_ACCEPT.to_string()),
+                    ("sec-fetch-site", "none".to_string()),
+                    ("sec-fetch-mode", "navigate".to_string()),
+                    ("sec-fetch-user", "?1".to_string()),
+                    ("sec-fetch-dest", "document".to_string()),
+                    ("accept-encoding", ACCEPT_ENCODING.to_string()),
+                    ("accept-language", accept_language.clone()),
+                ]
+            }
+            BrowserFamily::Firefox => vec![
+                ("user-agent", user_agent.clone()),
+                ("accept", GECKO_ACCEPT.to_string()),
+                ("accept-language", accept_language.clone()),
+                ("accept-encoding", ACCEPT_ENCODING.to_string()),
+                ("upgrade-insecure-requests", "1".to_string()),
+                ("sec-fetch-dest", "document".to_string()),
+                ("sec-fetch-mode", "navigate".to_string()),
+                ("sec-fetch-site", "none".to_string()),
+                ("sec-fetch-user", "?1".to_string()),
+            ],
+            BrowserFamily::Safari => vec![
+                ("sec-fetch-dest", "document".to_string()),
+                ("user-agent", user_agent.clone()),
+                ("accept", WEBKIT_ACCEPT.to_string()),
+                ("sec-fetch-site", "none".to_string()),
+                ("sec-fetch-mode", "navigate".to_string()),
+                ("accept-language", accept_language.clone()),
+                ("accept-encoding", WEBKIT_ACCEPT_ENCODING.to_string()),
+            ],
+        };
+
+        Self {
+            family,
+            platform,
+            major_version,
+            user_agent,
+            accept_language,
+            headers,
+        }
+    }
+
+    /// Returns the built-in catalog of current desktop browsers
+    pub fn catalog() -> Vec<BrowserProfile> {
+        use BrowserFamily::*;
+        use Platform::*;
+
+        let browsers = [
+            (Chrome, Windows, 122),
+            (Chrome, MacOs, 122),
+            (Chrome, Linux, 121),
+            (Edge, Windows, 122),
+            (Firefox, Windows, 123),
+            (Firefox, MacOs, 123),
+            (Firefox, Linux, 122),
+            (Safari, MacOs, 17),
+        ];
+
+        browsers
+            .iter()
+            .flat_map(|&(family, platform, version)| {
+                ["en-US", "en-GB"]
+                    .into_iter()
+                    .map(move |locale| BrowserProfile::new(family, platform, version, locale))
+            })
+            .collect()
+    }
+
+    /// Returns the in-browser values the slow path presents for this profile
+    ///
+    /// Headless Chromium cannot pass as Gecko or WebKit, so Firefox and Safari
+    /// profiles are presented as Chrome on the same platform and locale. Viewport,
+    /// core count and GPU are picked from common values, stably per profile.
+    pub fn stealth(&self) -> StealthProfile {
+        let locale = self.accept_language.split(',').next().unwrap_or("en-US").to_string();
+        let (brand, major_version, user_agent, accept_language) = match self.family {
+            BrowserFamily::Chrome => ("Google Chrome", self.major_version, self.user_agent.clone(), self.accept_language.clone()),
+            BrowserFamily::Edge => ("Microsoft Edge", self.major_version, self.user_agent.clone(), self.accept_language.clone()),
+            BrowserFamily::Firefox | BrowserFamily::Safari => {
+                let chrome = BrowserProfile::new(BrowserFamily::Chrome, self.platform, FALLBACK_CHROME_VERSION, &locale);
+                ("Google Chrome", chrome.major_version, chrome.user_agent, chrome.accept_language)
+            }
+        };
+
+        let language = locale.split('-').next().unwrap_or(&locale).to_string();
+        let mut languages = vec![locale.clone()];
+        if language != locale {
+            languages.push(language);
+        }
+
+        let seed = xxh64(format!("{}|{}", self.user_agent, self.accept_language).as_bytes(), 0);
+        let pick = |len: usize, salt: u64| ((seed >> (salt * 16)) % len as u64) as usize;
+        let viewports = self.platform.viewports();
+        let cores = self.platform.core_counts();
+        let gpus = self.platform.webgl();
+        let (webgl_vendor, webgl_renderer) = gpus[pick(gpus.len(), 2)];
+
+        StealthProfile {
+            user_agent,
+            accept_language,
+            languages,
+            timezone: locale_timezone(&locale),
+            locale,
+            platform: self.platform.navigator_platform(),
+            brand,
+            major_version,
+            client_hint_platform: self.platform.client_hint().trim_matches('"'),
+            viewport: viewports[pick(viewports.len(), 0)],
+            hardware_concurrency: cores[pick(cores.len(), 1)],
+            webgl_vendor,
+            webgl_renderer,
+        }
+    }
+
+    /// Returns the request headers in the order the browser sends them
+    pub fn headers(&self) -> &[(&'static str, String)] {
+        &self.headers
+    }
+
+    /// Returns the headers as an insertion-ordered `HeaderMap`
+    pub fn to_header_map(&self) -> HeaderMap {
+        let mut map = HeaderMap::with_capacity(self.headers.len());
+        for (name, value) in &self.headers {
+            // * Every value is built from ASCII literals, so conversion cannot fail
+            if let Ok(value) = HeaderValue::from_str(value) {
+                map.insert(HeaderName::from_static(name), value);
+            }
+        }
+        map
+    }
+}
+
+impl Default for BrowserProfile {
+    fn default() -> Self {
+        Self::new(BrowserFamily::Chrome, Platform::Windows, FALLBACK_CHROME_VERSION, "en-US")
+    }
+}
+
+/// Assigns each domain a stable browser profile and rotates it on ban signals
+///
+/// A domain keeps its profile between requests (real visitors don't change
+/// browsers mid-session); the initial pick is derived from the domain name
+/// so different domains see different browsers.
+///
+/// # Example
+/// ```ignore
+/// let rotator = FingerprintRotator::new();
+/// let profile = rotator.profile_for("example.com");
+/// // ... soft ban detected ...
+/// let next = rotator.rotate("example.com");
+/// ```
+#[derive(Debug)]
+pub struct FingerprintRotator {
+    profiles: Vec<Arc<BrowserProfile>>,
+    // * Domain -> index into `profiles`
+    assignments: Mutex<HashMap<String, usize>>,
+}
+
+impl FingerprintRotator {
+    /// Creates a rotator over the built-in catalog
+    pub fn new() -> Self {
+        Self::with_profiles(BrowserProfile::catalog())
+    }
+
+    /// Creates a rotator over custom profiles (the default profile if empty)
+    pub fn with_profiles(profiles: Vec<BrowserProfile>) -> Self {
+        let mut profiles: Vec<Arc<BrowserProfile>> = profiles.into_iter().map(Arc::new).collect();
+        if profiles.is_empty() {
+            profiles.push(Arc::new(BrowserProfile::default()));
+        }
+        Self {
+            profiles,
+            assignments: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the domain's current profile
+    pub fn profile_for(&self, domain: &str) -> Arc<BrowserProfile> {
+        let mut assignments = self.assignments.lock().unwrap();
+        let len = self.profiles.len();
+        let index = *assignments
+            .entry(domain.to_string())
+            .or_insert_with(|| (xxh64(domain.as_bytes(), 0) % len as u64) as usize);
+        Arc::clone(&self.profiles[index])
+    }
+
+    /// Moves the domain to a different browser family where possible and returns it
+    pub fn rotate(&self, domain: &str) -> Arc<BrowserProfile> {
+        let current = self.profile_for(domain);
+        let len = self.profiles.len();
+        let mut assignments = self.assignments.lock().unwrap();
+        let index = assignments.get(domain).copied().unwrap_or(0);
+
+        // * Prefer the next profile from another family: a new locale alone rarely clears a ban
+        let next = (1..len)
+            .map(|step| (index + step) % len)
+            .find(|&i| self.profiles[i].family != current.family)
+            .unwrap_or((index + 1) % len);
+        assignments.insert(domain.to_string(), next);
+
+        tracing::info!(
+            domain = %domain,
+            from = ?current.family,
+            to = ?self.profiles[next].family,
+            "Rotated browser fingerprint"
+        );
+        Arc::clone(&self.profiles[next])
+    }
+
+    /// Returns the number of profiles available
+    pub fn len(&self) -> usize {
+        self.profiles.len()
+    }
+
+    /// Returns true if only the fallback profile is available
+    pub fn is_empty(&self) -> bool {
+        self.profiles.is_empty()
+    }
+}
+
+impl Default for FingerprintRotator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,4 +551,94 @@ mod tests {
         assert!(has_content_changed(123, 456));
         assert!(!has_content_changed(123, 123));
     }
+
+    #[test]
+    fn test_chromium_profiles_send_matching_client_hints() {
+        let edge = BrowserProfile::new(BrowserFamily::Edge, Platform::Windows, 122, "en-GB");
+        let headers: HashMap<_, _> = edge.headers().iter().cloned().collect();
+
+        assert!(edge.user_agent.contains("Edg/122"));
+        assert!(headers["sec-ch-ua"].contains(r#""Microsoft Edge";v="122""#));
+        assert_eq!(headers["sec-ch-ua-platform"], r#""Windows""#);
+        assert_eq!(headers["accept-language"], "en-GB,en;q=0.9");
+        // * Chrome sends client hints before the User-Agent
+        assert_eq!(edge.headers()[0].0, "sec-ch-ua");
+    }
+
+    #[test]
+    fn test_firefox_and_safari_omit_client_hints() {
+        let firefox = BrowserProfile::new(BrowserFamily::Firefox, Platform::Linux, 123, "en-US");
+        assert!(firefox.user_agent.contains("X11; Linux x86_64; rv:123.0"));
+        assert_eq!(firefox.accept_language, "en-US,en;q=0.5");
+        assert!(firefox.headers().iter().all(|(name, _)| !name.starts_with("sec-ch-ua")));
+
+        // * Safari only exists on macOS
+        let safari = BrowserProfile::new(BrowserFamily::Safari, Platform::Windows, 17, "en-US");
+        assert_eq!(safari.platform, Platform::MacOs);
+        assert!(safari.user_agent.contains("Version/17.0 Safari"));
+        assert!(safari.headers().iter().all(|(name, _)| !name.starts_with("sec-ch-ua")));
+    }
+
+    #[test]
+    fn test_stealth_values_match_the_profile() {
+        let edge = BrowserProfile::new(BrowserFamily::Edge, Platform::Windows, 122, "en-GB").stealth();
+        assert!(edge.user_agent.contains("Edg/122"));
+        assert_eq!(edge.brand, "Microsoft Edge");
+        assert_eq!(edge.languages, vec!["en-GB", "en"]);
+        assert_eq!(edge.timezone, "Europe/London");
+        assert_eq!((edge.platform, edge.client_hint_platform), ("Win32", "Windows"));
+        assert!(edge.webgl_renderer.contains("Direct3D11"));
+
+        // * Stable for a profile, so a domain sees the same machine on every render
+        let chrome = BrowserProfile::new(BrowserFamily::Chrome, Platform::MacOs, 122, "en-US");
+        assert_eq!(chrome.stealth(), chrome.stealth());
+        assert!(Platform::MacOs.viewports().contains(&chrome.stealth().viewport));
+    }
+
+    #[test]
+    fn test_non_chromium_profiles_render_as_chrome() {
+        let firefox = BrowserProfile::new(BrowserFamily::Firefox, Platform::Linux, 123, "en-US").stealth();
+        assert!(firefox.user_agent.contains("X11; Linux x86_64) AppleWebKit/537.36"));
+        assert!(firefox.user_agent.contains("Chrome/122"));
+        assert_eq!(firefox.accept_language, "en-US,en;q=0.9");
+        assert_eq!(firefox.platform, "Linux x86_64");
+        assert_eq!(firefox.timezone, "America/New_York");
+
+        let safari = BrowserProfile::new(BrowserFamily::Safari, Platform::MacOs, 17, "fr-FR").stealth();
+        assert_eq!(safari.brand, "Google Chrome");
+        assert_eq!(safari.platform, "MacIntel");
+        assert_eq!(safari.timezone, "Europe/Paris");
+    }
+
+    #[test]
+    fn test_header_map_preserves_order() {
+        let profile = BrowserProfile::default();
+        let map = profile.to_header_map();
+        let names: Vec<&str> = map.keys().map(|k| k.as_str()).collect();
+        let expected: Vec<&str> = profile.headers().iter().map(|(n, _)| *n).collect();
+        assert_eq!(names, expected);
+    }
+
+    #[test]
+    fn test_rotator_is_stable_per_domain() {
+        let rotator = FingerprintRotator::new();
+        assert_eq!(rotator.profile_for("example.com"), rotator.profile_for("example.com"));
+        assert_eq!(rotator.len(), BrowserProfile::catalog().len());
+    }
+
+    #[test]
+    fn test_rotate_switches_family() {
+        let rotator = FingerprintRotator::new();
+        let before = rotator.profile_for("example.com");
+        let after = rotator.rotate("example.com");
+
+        assert_ne!(before.family, after.family);
+        assert_eq!(rotator.profile_for("example.com"), after);
+    }
+
+    #[test]
+    fn test_rotator_with_single_profile() {
+        let rotator = FingerprintRotator::with_profiles(Vec::new());
+        assert_eq!(*rotator.rotate("example.com"), BrowserProfile::default());
+    }
 }
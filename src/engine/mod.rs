@@ -4,4 +4,9 @@ pub mod rate_limiter;
 pub mod fingerprint;
 pub mod density;
 pub mod slow_path;
+pub mod readiness;
+pub mod browser_limits;
 pub mod circuit_breaker;
+pub mod url_filter;
+pub mod ban_detector;
+pub mod politeness;
@@ -68,3 +68,21 @@ pub fn normalize_url(href: &str, base_url: &str) -> Option<String> {
     // * Return normalized string
     Some(url.to_string())
 }
+
+// * Normalizes a page's declared canonical URL for canonical-level dedup.
+// * Returns None when the canonical should not be trusted:
+// * 1. It does not resolve, or points at another host.
+// * 2. It is the site root while the page is not (a common template mistake that would
+// *    otherwise mark every page of the site as a duplicate of the homepage).
+pub fn canonical_key(page_url: &str, canonical: &str) -> Option<String> {
+    let page = Url::parse(page_url).ok()?;
+    let key = normalize_url(canonical.trim(), page_url)?;
+    let target = Url::parse(&key).ok()?;
+    if !matches!(target.scheme(), "http" | "https") || target.host_str() != page.host_str() {
+        return None;
+    }
+    if target.path() == "/" && target.query().is_none() && page.path() != "/" {
+        return None;
+    }
+    Some(key)
+}
@@ -0,0 +1,279 @@
+// * [FR-03] Adaptive Politeness
+// * Per-domain crawl delay controller: widens the delay after ban signals, tightens it
+// * back after sustained success, and pins it at the ceiling while a domain's ban rate
+// * is above the SEV-3 alert threshold
+
+use crate::ops::AlertManager;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+// * Delay multiplier applied per ban
+const DEFAULT_BACKOFF_FACTOR: f64 = 2.0;
+
+// * Largest multiplier of a domain's base delay
+const DEFAULT_MAX_BACKOFF: f64 = 32.0;
+
+// * Multiplier applied to the backoff after each success streak
+const DEFAULT_RECOVERY_FACTOR: f64 = 0.5;
+
+// * Consecutive successes needed before the delay is tightened
+const DEFAULT_SUCCESS_STREAK: u32 = 20;
+
+/// Tuning for the adaptive politeness controller
+///
+/// # Example
+/// ```toml
+/// [crawl.politeness]
+/// backoff_factor = 2.0
+/// max_backoff = 16.0
+/// success_streak = 50
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PolitenessConfig {
+    /// Disables adaptive backoff entirely
+    pub enabled: bool,
+    /// Multiplier applied to a domain's delay after each ban (at least 1)
+    pub backoff_factor: f64,
+    /// Ceiling for the multiplier; also applied while the SEV-3 ban rate alert holds
+    pub max_backoff: f64,
+    /// Multiplier applied to the backoff after a success streak (between 0 and 1)
+    pub recovery_factor: f64,
+    /// Consecutive successful fetches before the delay is tightened
+    pub success_streak: u32,
+}
+
+impl Default for PolitenessConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            backoff_factor: DEFAULT_BACKOFF_FACTOR,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            recovery_factor: DEFAULT_RECOVERY_FACTOR,
+            success_streak: DEFAULT_SUCCESS_STREAK,
+        }
+    }
+}
+
+/// Result of a fetch as seen by the politeness controller
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FetchSignal {
+    /// The page was fetched
+    Success,
+    /// The fetch failed for reasons other than a ban (timeouts, 5xx, ...)
+    Failure,
+    /// The domain served a challenge, 403 or 429
+    Ban,
+}
+
+/// Backoff state for a single domain
+#[derive(Debug, Clone, Copy)]
+struct DomainPoliteness {
+    backoff: f64,
+    successes: u32,
+}
+
+impl Default for DomainPoliteness {
+    fn default() -> Self {
+        Self {
+            backoff: 1.0,
+            successes: 0,
+        }
+    }
+}
+
+/// Per-domain adaptive crawl delay controller
+///
+/// The controller only decides the multiplier; callers apply it with
+/// `RateLimitManager::set_backoff` whenever `record` returns a new value.
+///
+/// # Example
+/// ```ignore
+/// let politeness = PolitenessController::new(PolitenessConfig::default())
+///     .with_alert_manager(alerts);
+/// if let Some(backoff) = politeness.record("example.com", FetchSignal::Ban) {
+///     rate_limiter.set_backoff("example.com", backoff).await;
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct PolitenessController {
+    config: PolitenessConfig,
+    alerts: Option<Arc<AlertManager>>,
+    domains: Mutex<HashMap<String, DomainPoliteness>>,
+}
+
+impl PolitenessController {
+    /// Creates a controller with the given tuning
+    pub fn new(config: PolitenessConfig) -> Self {
+        Self {
+            config,
+            alerts: None,
+            domains: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Feeds every fetch into the alert manager's per-domain ban rate
+    ///
+    /// While the SEV-3 ban rate alert holds for a domain, its backoff stays at `max_backoff`.
+    pub fn with_alert_manager(mut self, alerts: Arc<AlertManager>) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
+    /// Returns the controller's tuning
+    pub fn config(&self) -> &PolitenessConfig {
+        &self.config
+    }
+
+    /// Returns the current delay multiplier for a domain
+    pub fn backoff_for(&self, domain: &str) -> f64 {
+        self.domains
+            .lock()
+            .unwrap()
+            .get(domain)
+            .map_or(1.0, |state| state.backoff)
+    }
+
+    /// Records a fetch result; returns the new multiplier when it changed
+    ///
+    /// # Rules:
+    /// 1. A ban multiplies the backoff by `backoff_factor` and resets the success streak
+    /// 2. Other failures reset the success streak
+    /// 3. `success_streak` consecutive successes multiply it by `recovery_factor`
+    /// 4. While the domain's ban rate alert holds, the backoff is `max_backoff`
+    pub fn record(&self, domain: &str, signal: FetchSignal) -> Option<f64> {
+        // * The alert manager tracks ban rates even when adaptive backoff is disabled
+        let ban_rate_high = self.alerts.as_ref().is_some_and(|alerts| {
+            alerts.record_domain_event(
+                domain,
+                signal == FetchSignal::Success,
+                signal == FetchSignal::Ban,
+            )
+        });
+        if !self.config.enabled {
+            return None;
+        }
+
+        let max_backoff = self.config.max_backoff.max(1.0);
+        let mut domains = self.domains.lock().unwrap();
+        let state = domains.entry(domain.to_string()).or_default();
+        let previous = state.backoff;
+
+        match signal {
+            FetchSignal::Ban => {
+                state.successes = 0;
+                state.backoff = (state.backoff * self.config.backoff_factor).min(max_backoff);
+            }
+            FetchSignal::Failure => state.successes = 0,
+            FetchSignal::Success => {
+                state.successes += 1;
+                if state.successes >= self.config.success_streak && state.backoff > 1.0 {
+                    state.successes = 0;
+                    state.backoff = (state.backoff * self.config.recovery_factor).max(1.0);
+                }
+            }
+        }
+
+        if ban_rate_high {
+            state.backoff = max_backoff;
+        }
+
+        let backoff = state.backoff;
+        if backoff == 1.0 {
+            // * Recovered domains need no state
+            domains.remove(domain);
+        }
+        (backoff != previous).then_some(backoff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::AlertConfig;
+
+    fn controller() -> PolitenessController {
+        PolitenessController::new(PolitenessConfig {
+            success_streak: 3,
+            max_backoff: 8.0,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_ban_widens_delay() {
+        let politeness = controller();
+        assert_eq!(politeness.record("example.com", FetchSignal::Ban), Some(2.0));
+        assert_eq!(politeness.record("example.com", FetchSignal::Ban), Some(4.0));
+        assert_eq!(politeness.record("example.com", FetchSignal::Ban), Some(8.0));
+
+        // * Capped at the ceiling
+        assert_eq!(politeness.record("example.com", FetchSignal::Ban), None);
+        assert!((politeness.backoff_for("example.com") - 8.0).abs() < f64::EPSILON);
+        assert!((politeness.backoff_for("other.com") - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_sustained_success_tightens_delay() {
+        let politeness = controller();
+        politeness.record("example.com", FetchSignal::Ban);
+        politeness.record("example.com", FetchSignal::Ban);
+
+        assert_eq!(politeness.record("example.com", FetchSignal::Success), None);
+        assert_eq!(politeness.record("example.com", FetchSignal::Success), None);
+        assert_eq!(politeness.record("example.com", FetchSignal::Success), Some(2.0));
+
+        // * A failure breaks the streak
+        politeness.record("example.com", FetchSignal::Success);
+        politeness.record("example.com", FetchSignal::Failure);
+        politeness.record("example.com", FetchSignal::Success);
+        assert_eq!(politeness.record("example.com", FetchSignal::Success), None);
+        assert_eq!(politeness.record("example.com", FetchSignal::Success), Some(1.0));
+
+        // * Fully recovered domains never go below the base delay
+        for _ in 0..3 {
+            assert_eq!(politeness.record("example.com", FetchSignal::Success), None);
+        }
+    }
+
+    #[test]
+    fn test_ban_rate_alert_pins_backoff() {
+        let alerts = Arc::new(AlertManager::with_config(AlertConfig {
+            sev3_ban_rate_threshold: 0.5,
+            ..Default::default()
+        }));
+        let politeness = PolitenessController::new(PolitenessConfig {
+            backoff_factor: 1.0,
+            max_backoff: 16.0,
+            success_streak: 1,
+            ..Default::default()
+        })
+        .with_alert_manager(Arc::clone(&alerts));
+
+        for _ in 0..9 {
+            assert_eq!(politeness.record("example.com", FetchSignal::Ban), None);
+        }
+        // * The tenth event completes the alert's sample size
+        assert_eq!(politeness.record("example.com", FetchSignal::Ban), Some(16.0));
+
+        // * Successes cannot tighten the delay while the ban rate stays above the threshold
+        assert_eq!(politeness.record("example.com", FetchSignal::Success), None);
+        assert!((politeness.backoff_for("example.com") - 16.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_disabled_controller_still_feeds_alerts() {
+        let alerts = Arc::new(AlertManager::new());
+        let politeness = PolitenessController::new(PolitenessConfig {
+            enabled: false,
+            ..Default::default()
+        })
+        .with_alert_manager(Arc::clone(&alerts));
+
+        for _ in 0..10 {
+            assert_eq!(politeness.record("example.com", FetchSignal::Ban), None);
+        }
+        assert_eq!(alerts.get_stats().active_alert_count, 1);
+    }
+}
@@ -2,21 +2,23 @@
 // * Handles per-domain rate limiting, robots.txt compliance, and blacklist management
 
 use governor::{Quota, RateLimiter as GovernorLimiter};
-use nonzero_ext::nonzero;
 use redis::aio::ConnectionManager;
 use redis::AsyncCommands;
 use robotstxt::DefaultMatcher;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::num::NonZeroU32;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use tokio::sync::RwLock;
-use tracing::{debug, warn};
+use tracing::{debug, info, warn};
 use xxhash_rust::xxh64::xxh64;
 
 // * TTL constants for Redis keys
+// * 429 backoff when the response has no Retry-After; longer Retry-After values are capped at a day
 const BACKOFF_429_TTL_SECS: u64 = 3600;
+const MAX_RETRY_AFTER_SECS: u64 = 86400;
 const BLACKLIST_TTL_SECS: u64 = 86400;
 const DEFAULT_CRAWL_DELAY_MS: u64 = 1000;
 const SLOW_PATH_MULTIPLIER: u64 = 2;
@@ -25,6 +27,47 @@ const SLOW_PATH_MULTIPLIER: u64 = 2;
 const RATELIMIT_PREFIX: &str = "ratelimit";
 const BLACKLIST_PREFIX: &str = "blacklist";
 
+// * Requests a domain's shared bucket can burst before the crawl delay applies
+const BUCKET_CAPACITY: u64 = 1;
+// * Longer waits (e.g. a 429 backoff) fail with RateLimitExceeded instead of parking the worker
+const MAX_BUCKET_WAIT_MS: u64 = 30_000;
+
+// * Shared token bucket, refilled at one token per crawl delay
+// * KEYS[1] = bucket key, ARGV[1] = refill interval (ms), ARGV[2] = capacity
+// * State is "tokens:last_refill_ms" so record_429 can park the same key with SETEX "backoff".
+// * Uses the Redis clock so replicas with skewed clocks share one timeline.
+// * Returns 0 when a token was taken, otherwise the milliseconds to wait.
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local interval = tonumber(ARGV[1])
+local capacity = tonumber(ARGV[2])
+local state = redis.call('GET', KEYS[1])
+if state == 'backoff' then
+    local ttl = redis.call('PTTL', KEYS[1])
+    if ttl < 0 then
+        return interval
+    end
+    return ttl
+end
+
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+local tokens = capacity
+if state then
+    local stored, last = string.match(state, '^([^:]+):(%d+)$')
+    if stored then
+        tokens = math.min(capacity, tonumber(stored) + math.max(0, now - tonumber(last)) / interval)
+    end
+end
+
+local ttl = interval * capacity
+if tokens >= 1 then
+    redis.call('SET', KEYS[1], (tokens - 1) .. ':' .. now, 'PX', ttl)
+    return 0
+end
+redis.call('SET', KEYS[1], tokens .. ':' .. now, 'PX', ttl)
+return math.ceil((1 - tokens) * interval)
+"#;
+
 #[derive(Debug, Error)]
 pub enum RateLimitError {
     #[error("Redis connection error: {0}")]
@@ -33,15 +76,16 @@ pub enum RateLimitError {
     #[error("Domain is blacklisted until TTL expires")]
     DomainBlacklisted,
 
-    #[error("Rate limit exceeded, retry after delay")]
-    RateLimitExceeded,
+    #[error("Rate limit exceeded, retry after {0:?}")]
+    RateLimitExceeded(Duration),
 
     #[error("Failed to parse robots.txt: {0}")]
     RobotstxtParseError(String),
 }
 
 // * Represents the crawl delay configuration for a domain
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct CrawlDelayConfig {
     pub standard_delay_ms: u64,
     pub slow_path_delay_ms: u64,
@@ -134,6 +178,10 @@ pub struct DomainRateLimiter {
     domain: String,
     domain_hash: u64,
     config: CrawlDelayConfig,
+    // * Delay before adaptive backoff was applied
+    base: CrawlDelayConfig,
+    backoff: f64,
+    inherits_default: bool,
     local_limiter: GovernorLimiter<
         governor::state::NotKeyed,
         governor::state::InMemoryState,
@@ -143,22 +191,30 @@ pub struct DomainRateLimiter {
 
 impl DomainRateLimiter {
     pub fn new(domain: &str, config: CrawlDelayConfig) -> Self {
-        let domain_hash = compute_domain_hash(domain);
+        Self::with_backoff(domain, config, 1.0)
+    }
 
-        // * Create local governor rate limiter based on crawl delay
-        let requests_per_second = if config.standard_delay_ms > 0 {
-            std::cmp::max(1, 1000 / config.standard_delay_ms as u32)
-        } else {
-            1
+    // * Creates a limiter whose delays are the base delays multiplied by `backoff`
+    pub fn with_backoff(domain: &str, base: CrawlDelayConfig, backoff: f64) -> Self {
+        let domain_hash = compute_domain_hash(domain);
+        let backoff = backoff.max(1.0);
+        let config = CrawlDelayConfig {
+            standard_delay_ms: (base.standard_delay_ms as f64 * backoff) as u64,
+            slow_path_delay_ms: (base.slow_path_delay_ms as f64 * backoff) as u64,
         };
 
-        let quota = Quota::per_second(NonZeroU32::new(requests_per_second).unwrap_or(nonzero!(1u32)));
+        // * One request per crawl delay (a zero delay still allows only one per millisecond)
+        let period = Duration::from_millis(config.standard_delay_ms.max(1));
+        let quota = Quota::with_period(period).expect("period is never zero");
         let local_limiter = GovernorLimiter::direct(quota);
 
         Self {
             domain: domain.to_string(),
             domain_hash,
             config,
+            base,
+            backoff,
+            inherits_default: false,
             local_limiter,
         }
     }
@@ -187,16 +243,36 @@ impl DomainRateLimiter {
         &self.config
     }
 
+    // * Returns the delay before adaptive backoff
+    pub fn base_config(&self) -> &CrawlDelayConfig {
+        &self.base
+    }
+
+    // * Returns the adaptive backoff multiplier (1.0 when none applies)
+    pub fn backoff(&self) -> f64 {
+        self.backoff
+    }
+
     pub fn domain(&self) -> &str {
         &self.domain
     }
 }
 
 // * RateLimitManager coordinates rate limiting across multiple domains with Redis backend
+// * With Redis, every replica draws from the same per-domain token bucket; without it (or while
+// * Redis is unreachable) only the in-process limiter applies
 pub struct RateLimitManager {
     redis: Option<ConnectionManager>,
+    bucket_script: redis::Script,
+    // * Set while Redis calls fail, so the fallback is logged once per outage
+    redis_degraded: AtomicBool,
     domain_limiters: Arc<RwLock<HashMap<String, Arc<DomainRateLimiter>>>>,
     robots_parser: RobotstxtParser,
+    default_delay: std::sync::RwLock<CrawlDelayConfig>,
+    // * Domain -> adaptive delay multiplier, kept across limiter re-creation
+    backoffs: std::sync::RwLock<HashMap<String, f64>>,
+    // * Domain -> end of its 429/Retry-After backoff, as known to this process
+    backoff_deadlines: std::sync::RwLock<HashMap<String, Instant>>,
 }
 
 impl RateLimitManager {
@@ -211,22 +287,50 @@ impl RateLimitManager {
 
         Ok(Self {
             redis,
+            bucket_script: redis::Script::new(TOKEN_BUCKET_SCRIPT),
+            redis_degraded: AtomicBool::new(false),
             domain_limiters: Arc::new(RwLock::new(HashMap::new())),
             robots_parser: RobotstxtParser::new(user_agent),
+            default_delay: std::sync::RwLock::new(CrawlDelayConfig::default()),
+            backoffs: std::sync::RwLock::new(HashMap::new()),
+            backoff_deadlines: std::sync::RwLock::new(HashMap::new()),
         })
     }
 
+    // * Overrides the delay applied to domains registered without robots.txt
+    pub fn with_default_delay(mut self, config: CrawlDelayConfig) -> Self {
+        *self.default_delay.get_mut().unwrap() = config;
+        self
+    }
+
+    // * Returns the delay applied to domains registered without robots.txt
+    pub fn default_delay(&self) -> CrawlDelayConfig {
+        self.default_delay.read().unwrap().clone()
+    }
+
+    // * Replaces the default delay at runtime
+    // * Domains that inherited the old default are dropped and re-created on next use;
+    // * domains governed by robots.txt Crawl-Delay keep their limiter
+    pub async fn set_default_delay(&self, config: CrawlDelayConfig) {
+        *self.default_delay.write().unwrap() = config;
+
+        let mut limiters = self.domain_limiters.write().await;
+        limiters.retain(|_, limiter| !limiter.inherits_default);
+    }
+
     // * Registers a domain with its robots.txt content
     pub async fn register_domain(
         &self,
         domain: &str,
         robots_txt: Option<&str>,
     ) -> Arc<DomainRateLimiter> {
-        let config = robots_txt
-            .map(|txt| self.robots_parser.parse_crawl_delay(txt))
-            .unwrap_or_default();
-
-        let limiter = Arc::new(DomainRateLimiter::new(domain, config));
+        let base = match robots_txt {
+            Some(txt) => self.robots_parser.parse_crawl_delay(txt),
+            None => self.default_delay(),
+        };
+        let mut limiter = DomainRateLimiter::with_backoff(domain, base, self.backoff_for(domain));
+        limiter.inherits_default = robots_txt.is_none();
+        let limiter = Arc::new(limiter);
 
         let mut limiters = self.domain_limiters.write().await;
         limiters.insert(domain.to_string(), Arc::clone(&limiter));
@@ -253,6 +357,39 @@ impl RateLimitManager {
         self.register_domain(domain, None).await
     }
 
+    // * Returns the adaptive delay multiplier for a domain (1.0 when none applies)
+    pub fn backoff_for(&self, domain: &str) -> f64 {
+        self.backoffs.read().unwrap().get(domain).copied().unwrap_or(1.0)
+    }
+
+    // * Multiplies a domain's crawl delay by `backoff` (1.0 restores the base delay)
+    // * The registered limiter is rebuilt immediately; unregistered domains pick it up on first use
+    pub async fn set_backoff(&self, domain: &str, backoff: f64) {
+        let backoff = backoff.max(1.0);
+        {
+            let mut backoffs = self.backoffs.write().unwrap();
+            if backoff > 1.0 {
+                backoffs.insert(domain.to_string(), backoff);
+            } else {
+                backoffs.remove(domain);
+            }
+        }
+
+        let mut limiters = self.domain_limiters.write().await;
+        if let Some(current) = limiters.get(domain) {
+            let mut limiter =
+                DomainRateLimiter::with_backoff(domain, current.base_config().clone(), backoff);
+            limiter.inherits_default = current.inherits_default;
+            debug!(
+                "Domain '{}' backoff x{:.2}: delay {}ms",
+                domain,
+                backoff,
+                limiter.get_config().standard_delay_ms
+            );
+            limiters.insert(domain.to_string(), Arc::new(limiter));
+        }
+    }
+
     // * Checks if a domain is blacklisted
     pub async fn is_blacklisted(&self, domain: &str) -> Result<bool, RateLimitError> {
         if let Some(mut redis) = self.redis.clone() {
@@ -263,21 +400,50 @@ impl RateLimitManager {
         Ok(false)
     }
 
-    // * Records an HTTP 429 response - sets 1 hour backoff
-    pub async fn record_429(&self, domain: &str) -> Result<(), RateLimitError> {
+    // * Records an HTTP 429 (or 503 with Retry-After) response
+    // * Backs off for the server's Retry-After, or 1 hour without one; replicas share it through Redis
+    pub async fn record_429(&self, domain: &str, retry_after: Option<Duration>) -> Result<(), RateLimitError> {
+        let backoff = retry_after
+            .unwrap_or(Duration::from_secs(BACKOFF_429_TTL_SECS))
+            .min(Duration::from_secs(MAX_RETRY_AFTER_SECS));
+        if backoff.is_zero() {
+            return Ok(());
+        }
+        self.extend_backoff(domain, backoff);
+
         if let Some(mut redis) = self.redis.clone() {
             let key = format!("{}:{}:bucket", RATELIMIT_PREFIX, compute_domain_hash(domain));
             redis
-                .set_ex::<_, _, ()>(&key, "backoff", BACKOFF_429_TTL_SECS)
+                .pset_ex::<_, _, ()>(&key, "backoff", backoff.as_millis() as u64)
                 .await?;
-            warn!(
-                "Domain '{}' received 429 - backoff for {} seconds",
-                domain, BACKOFF_429_TTL_SECS
-            );
         }
+        warn!(
+            "Domain '{}' throttled - backoff for {} seconds",
+            domain,
+            backoff.as_secs()
+        );
         Ok(())
     }
 
+    // * Returns when a domain's 429/Retry-After backoff ends, or None when it is not backing off
+    // * Read locally (no Redis round-trip), so the dispatcher can hold the domain's URLs until then
+    pub fn backoff_deadline(&self, domain: &str) -> Option<Instant> {
+        let deadline = self.backoff_deadlines.read().unwrap().get(domain).copied()?;
+        if deadline > Instant::now() {
+            return Some(deadline);
+        }
+        self.backoff_deadlines.write().unwrap().remove(domain);
+        None
+    }
+
+    // * Pushes a domain's backoff deadline out to at least `backoff` from now
+    fn extend_backoff(&self, domain: &str, backoff: Duration) {
+        let deadline = Instant::now() + backoff;
+        let mut deadlines = self.backoff_deadlines.write().unwrap();
+        let current = deadlines.entry(domain.to_string()).or_insert(deadline);
+        *current = (*current).max(deadline);
+    }
+
     // * Records a Tier 2 proxy failure - blacklists domain for 24 hours
     pub async fn record_tier2_failure(&self, domain: &str) -> Result<(), RateLimitError> {
         if let Some(mut redis) = self.redis.clone() {
@@ -299,17 +465,31 @@ impl RateLimitManager {
     }
 
     // * Acquires permission to make a request (checks blacklist and rate limit)
+    // * Redis failures fall back to the local limiter instead of failing the request
     pub async fn acquire(&self, domain: &str, is_slow_path: bool) -> Result<(), RateLimitError> {
         // * Check blacklist first
-        if self.is_blacklisted(domain).await? {
-            return Err(RateLimitError::DomainBlacklisted);
+        match self.is_blacklisted(domain).await {
+            Ok(true) => return Err(RateLimitError::DomainBlacklisted),
+            Ok(false) => self.mark_redis_healthy(),
+            Err(e) => self.mark_redis_degraded(&e),
         }
 
         let limiter = self.get_limiter(domain).await;
-
-        // * Wait for local rate limit
         limiter.wait_local().await;
 
+        // * Checked after the local wait so a 429 recorded meanwhile is honoured. Short backoffs
+        // * are waited out; long ones are left for the dispatcher to schedule around
+        if let Some(deadline) = self.backoff_deadline(domain) {
+            let wait = deadline.saturating_duration_since(Instant::now());
+            if wait > Duration::from_millis(MAX_BUCKET_WAIT_MS) {
+                return Err(RateLimitError::RateLimitExceeded(wait));
+            }
+            tokio::time::sleep(wait).await;
+        }
+
+        // * Then wait for the bucket shared with other replicas
+        self.acquire_shared(&limiter).await?;
+
         // * Apply additional delay for slow path
         if is_slow_path {
             let extra_delay = limiter.get_config().slow_path_delay_ms
@@ -321,6 +501,57 @@ impl RateLimitManager {
 
         Ok(())
     }
+
+    // * Takes a token from the domain's Redis bucket, sleeping until one is available
+    async fn acquire_shared(&self, limiter: &DomainRateLimiter) -> Result<(), RateLimitError> {
+        let Some(mut redis) = self.redis.clone() else {
+            return Ok(());
+        };
+        let interval_ms = limiter.get_config().standard_delay_ms.max(1);
+        loop {
+            let wait_ms: Result<u64, _> = self
+                .bucket_script
+                .key(limiter.redis_key())
+                .arg(interval_ms)
+                .arg(BUCKET_CAPACITY)
+                .invoke_async(&mut redis)
+                .await;
+            match wait_ms {
+                Ok(0) => {
+                    self.mark_redis_healthy();
+                    return Ok(());
+                }
+                Ok(wait_ms) if wait_ms > MAX_BUCKET_WAIT_MS => {
+                    // * Usually another replica's 429 backoff; remember it so dispatch holds the domain
+                    debug!("Domain '{}' bucket needs {}ms; giving up", limiter.domain(), wait_ms);
+                    self.extend_backoff(limiter.domain(), Duration::from_millis(wait_ms));
+                    return Err(RateLimitError::RateLimitExceeded(Duration::from_millis(wait_ms)));
+                }
+                Ok(wait_ms) => tokio::time::sleep(Duration::from_millis(wait_ms)).await,
+                Err(e) => {
+                    self.mark_redis_degraded(&e);
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    // * Returns true while Redis is unreachable and only local limits apply
+    pub fn is_degraded(&self) -> bool {
+        self.redis_degraded.load(Ordering::Relaxed)
+    }
+
+    fn mark_redis_degraded(&self, error: &dyn std::fmt::Display) {
+        if !self.redis_degraded.swap(true, Ordering::Relaxed) {
+            warn!("Redis rate limiting unavailable ({}); falling back to local limits", error);
+        }
+    }
+
+    fn mark_redis_healthy(&self) {
+        if self.redis.is_some() && self.redis_degraded.swap(false, Ordering::Relaxed) {
+            info!("Redis rate limiting restored");
+        }
+    }
 }
 
 #[cfg(test)]
@@ -391,6 +622,85 @@ Allow: /public/
 
         // * Should be able to acquire
         assert!(manager.acquire("example.com", false).await.is_ok());
+        assert!(!manager.is_degraded());
+    }
+
+    #[tokio::test]
+    async fn test_register_domain_uses_default_delay() {
+        let manager = RateLimitManager::new(None, "TestBot/1.0")
+            .await
+            .unwrap()
+            .with_default_delay(CrawlDelayConfig {
+                standard_delay_ms: 250,
+                slow_path_delay_ms: 500,
+            });
+
+        let limiter = manager.register_domain("example.com", None).await;
+        assert_eq!(limiter.get_config().standard_delay_ms, 250);
+        assert_eq!(limiter.get_config().slow_path_delay_ms, 500);
+    }
+
+    #[tokio::test]
+    async fn test_set_default_delay_refreshes_inherited_limiters() {
+        let manager = RateLimitManager::new(None, "TestBot/1.0").await.unwrap();
+        manager.get_limiter("plain.com").await;
+        manager
+            .register_domain("polite.com", Some("User-agent: *\nCrawl-delay: 3"))
+            .await;
+
+        manager
+            .set_default_delay(CrawlDelayConfig {
+                standard_delay_ms: 200,
+                slow_path_delay_ms: 400,
+            })
+            .await;
+
+        assert_eq!(manager.default_delay().standard_delay_ms, 200);
+        assert_eq!(manager.get_limiter("plain.com").await.get_config().standard_delay_ms, 200);
+        assert_eq!(manager.get_limiter("polite.com").await.get_config().standard_delay_ms, 3000);
+    }
+
+    #[tokio::test]
+    async fn test_set_backoff_scales_delay() {
+        let manager = RateLimitManager::new(None, "TestBot/1.0").await.unwrap();
+        manager
+            .register_domain("polite.com", Some("User-agent: *\nCrawl-delay: 2"))
+            .await;
+
+        manager.set_backoff("polite.com", 4.0).await;
+        let limiter = manager.get_limiter("polite.com").await;
+        assert_eq!(limiter.get_config().standard_delay_ms, 8000);
+        assert_eq!(limiter.base_config().standard_delay_ms, 2000);
+
+        // * Backoff survives re-registration and applies to domains registered later
+        manager.set_backoff("later.com", 2.0).await;
+        let later = manager.register_domain("later.com", None).await;
+        assert_eq!(later.get_config().standard_delay_ms, DEFAULT_CRAWL_DELAY_MS * 2);
+
+        manager.set_backoff("polite.com", 1.0).await;
+        assert_eq!(manager.get_limiter("polite.com").await.get_config().standard_delay_ms, 2000);
+        assert!((manager.backoff_for("polite.com") - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_record_429_honours_retry_after() {
+        let manager = RateLimitManager::new(None, "TestBot/1.0").await.unwrap();
+
+        manager.record_429("busy.com", Some(Duration::from_secs(120))).await.unwrap();
+        let wait = manager.backoff_deadline("busy.com").unwrap() - Instant::now();
+        assert!(wait > Duration::from_secs(110) && wait <= Duration::from_secs(120));
+        assert!(matches!(
+            manager.acquire("busy.com", false).await,
+            Err(RateLimitError::RateLimitExceeded(wait)) if wait > Duration::from_secs(100)
+        ));
+
+        // * No Retry-After falls back to the fixed backoff; a zero wait sets none
+        manager.record_429("plain.com", None).await.unwrap();
+        let wait = manager.backoff_deadline("plain.com").unwrap() - Instant::now();
+        assert!(wait > Duration::from_secs(BACKOFF_429_TTL_SECS - 10));
+        manager.record_429("quick.com", Some(Duration::ZERO)).await.unwrap();
+        assert!(manager.backoff_deadline("quick.com").is_none());
+        assert!(manager.acquire("quick.com", false).await.is_ok());
     }
 
     #[tokio::test]
@@ -0,0 +1,232 @@
+// * [FR-03] Render Readiness
+// * Decides when a rendered page is ready for extraction. A probe script is polled in the page
+// * until the configured condition holds, so finished pages are extracted at once and slow
+// * SPAs are given time, instead of one fixed wait for every site.
+
+use crate::config::constants::PAGE_TIMEOUT_MS;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+fn default_idle_ms() -> u64 {
+    500
+}
+
+/// Condition a rendered page must meet before its HTML is extracted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum WaitUntil {
+    /// The load event has fired (all subresources loaded)
+    Load,
+    /// The HTML is parsed; images, styles and late scripts may still be loading
+    DomContentLoaded,
+    /// The load event has fired and no new resources were fetched for `idle_ms`
+    NetworkIdle {
+        #[serde(default = "default_idle_ms")]
+        idle_ms: u64,
+    },
+    /// An element matching the CSS selector exists
+    Selector { selector: String },
+    /// A JavaScript expression evaluates truthy (exceptions count as not ready)
+    Predicate { script: String },
+}
+
+impl Default for WaitUntil {
+    fn default() -> Self {
+        WaitUntil::NetworkIdle { idle_ms: default_idle_ms() }
+    }
+}
+
+impl WaitUntil {
+    /// Metric label
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WaitUntil::Load => "load",
+            WaitUntil::DomContentLoaded => "dom_content_loaded",
+            WaitUntil::NetworkIdle { .. } => "network_idle",
+            WaitUntil::Selector { .. } => "selector",
+            WaitUntil::Predicate { .. } => "predicate",
+        }
+    }
+
+    /// Expression polled in the page; false while the blank tab has not navigated yet
+    pub fn probe_script(&self) -> String {
+        let condition = match self {
+            WaitUntil::Load => "document.readyState === 'complete'".to_string(),
+            WaitUntil::DomContentLoaded => "document.readyState !== 'loading'".to_string(),
+            // * Reports the resource count once loaded; idleness is judged across polls
+            WaitUntil::NetworkIdle { .. } => {
+                "(document.readyState === 'complete' ? performance.getEntriesByType('resource').length : false)"
+                    .to_string()
+            }
+            WaitUntil::Selector { selector } => {
+                let selector = serde_json::to_string(selector).unwrap_or_else(|_| "\"\"".to_string());
+                format!("(() => {{ try {{ return document.querySelector({}) !== null; }} catch (e) {{ return false; }} }})()", selector)
+            }
+            WaitUntil::Predicate { script } => {
+                format!("(() => {{ try {{ return Boolean({}); }} catch (e) {{ return false; }} }})()", script)
+            }
+        };
+        format!("location.href !== 'about:blank' && {}", condition)
+    }
+}
+
+/// Readiness override for one site
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SiteReadiness {
+    /// Host the override applies to, including its subdomains
+    pub host: String,
+    pub wait_until: WaitUntil,
+    /// Replaces the default timeout for this site
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+/// When the slow path extracts a rendered page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReadinessConfig {
+    /// Condition for sites without an override
+    pub wait_until: WaitUntil,
+    /// Longest wait for the condition, including navigation
+    pub timeout_ms: u64,
+    /// Milliseconds between probes
+    pub poll_interval_ms: u64,
+    /// Extract whatever rendered when the condition times out (false fails the render)
+    pub extract_on_timeout: bool,
+    /// Per-site conditions; the most specific host wins
+    pub sites: Vec<SiteReadiness>,
+}
+
+impl Default for ReadinessConfig {
+    fn default() -> Self {
+        Self {
+            wait_until: WaitUntil::default(),
+            timeout_ms: PAGE_TIMEOUT_MS,
+            poll_interval_ms: 100,
+            extract_on_timeout: true,
+            sites: Vec::new(),
+        }
+    }
+}
+
+impl ReadinessConfig {
+    /// Returns the condition and timeout for a host
+    pub fn for_host(&self, host: &str) -> (&WaitUntil, Duration) {
+        let host = host.to_ascii_lowercase();
+        let site = self
+            .sites
+            .iter()
+            .filter(|site| {
+                let site_host = site.host.to_ascii_lowercase();
+                host == site_host || host.ends_with(&format!(".{}", site_host))
+            })
+            .max_by_key(|site| site.host.len());
+        match site {
+            Some(site) => (
+                &site.wait_until,
+                Duration::from_millis(site.timeout_ms.unwrap_or(self.timeout_ms)),
+            ),
+            None => (&self.wait_until, Duration::from_millis(self.timeout_ms)),
+        }
+    }
+}
+
+/// Judges successive probe results for one render
+#[derive(Debug)]
+pub struct ReadinessCheck {
+    wait_until: WaitUntil,
+    // * Resource count last seen and since when it has not changed (network idle only)
+    quiet_since: Option<(u64, Instant)>,
+}
+
+impl ReadinessCheck {
+    pub fn new(wait_until: WaitUntil) -> Self {
+        Self {
+            wait_until,
+            quiet_since: None,
+        }
+    }
+
+    /// Feeds one probe result; returns true once the page is ready
+    pub fn observe(&mut self, value: &serde_json::Value, now: Instant) -> bool {
+        let WaitUntil::NetworkIdle { idle_ms } = self.wait_until else {
+            return value.as_bool().unwrap_or(false);
+        };
+        let Some(count) = value.as_u64() else {
+            self.quiet_since = None;
+            return false;
+        };
+        match self.quiet_since {
+            Some((last, since)) if last == count => now.duration_since(since) >= Duration::from_millis(idle_ms),
+            _ => {
+                self.quiet_since = Some((count, now));
+                idle_ms == 0
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_network_idle_waits_for_quiet_period() {
+        let mut check = ReadinessCheck::new(WaitUntil::NetworkIdle { idle_ms: 500 });
+        let start = Instant::now();
+
+        assert!(!check.observe(&json!(false), start));
+        assert!(!check.observe(&json!(4), start));
+        assert!(!check.observe(&json!(4), start + Duration::from_millis(300)));
+        // * A new request restarts the quiet period
+        assert!(!check.observe(&json!(5), start + Duration::from_millis(400)));
+        assert!(!check.observe(&json!(5), start + Duration::from_millis(800)));
+        assert!(check.observe(&json!(5), start + Duration::from_millis(900)));
+
+        let mut selector = ReadinessCheck::new(WaitUntil::Selector { selector: "#app".into() });
+        assert!(!selector.observe(&json!(false), start));
+        assert!(selector.observe(&json!(true), start));
+    }
+
+    #[test]
+    fn test_site_overrides_pick_most_specific_host() {
+        let config = ReadinessConfig {
+            sites: vec![
+                SiteReadiness {
+                    host: "example.com".into(),
+                    wait_until: WaitUntil::DomContentLoaded,
+                    timeout_ms: None,
+                },
+                SiteReadiness {
+                    host: "app.example.com".into(),
+                    wait_until: WaitUntil::Selector { selector: "#root > div".into() },
+                    timeout_ms: Some(5_000),
+                },
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.for_host("App.Example.com"),
+            (&WaitUntil::Selector { selector: "#root > div".into() }, Duration::from_millis(5_000))
+        );
+        assert_eq!(
+            config.for_host("www.example.com"),
+            (&WaitUntil::DomContentLoaded, Duration::from_millis(PAGE_TIMEOUT_MS))
+        );
+        assert_eq!(config.for_host("example.org").0, &WaitUntil::default());
+    }
+
+    #[test]
+    fn test_probe_scripts_escape_selectors_and_guard_blank_tabs() {
+        let probe = WaitUntil::Selector { selector: "a[href=\"/x\"]".into() }.probe_script();
+        assert!(probe.starts_with("location.href !== 'about:blank' && "));
+        assert!(probe.contains(r#"document.querySelector("a[href=\"/x\"]")"#));
+
+        let config: ReadinessConfig =
+            toml::from_str("wait_until = { strategy = \"predicate\", script = \"window.__ready\" }\n").unwrap();
+        assert!(config.wait_until.probe_script().contains("Boolean(window.__ready)"));
+    }
+}
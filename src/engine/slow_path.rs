@@ -2,16 +2,31 @@
 // * Uses ChromiumOxide for JavaScript-heavy pages that require full rendering
 
 use chromiumoxide::browser::{Browser, BrowserConfig};
+use chromiumoxide::cdp::browser_protocol::emulation::{
+    SetDeviceMetricsOverrideParams, SetLocaleOverrideParams, SetTimezoneOverrideParams, SetUserAgentOverrideParams,
+    UserAgentBrandVersion, UserAgentMetadata,
+};
+use chromiumoxide::cdp::browser_protocol::performance::{EnableParams as PerformanceEnableParams, GetMetricsParams};
+use chromiumoxide::cdp::browser_protocol::network::{
+    EnableParams, EventLoadingFinished, EventResponseReceived, GetResponseBodyParams, RequestId, ResourceType,
+};
+use chromiumoxide::listeners::EventStream;
 use chromiumoxide::page::Page;
-use futures::StreamExt;
-use std::time::Duration;
+use futures::{FutureExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 use thiserror::Error;
-use tracing::info;
+use tracing::{debug, info, warn};
 
-use crate::config::constants::PAGE_TIMEOUT_MS;
+use crate::engine::browser_limits::{BrowserLimits, PageLimit, PageUsage};
+use crate::engine::fingerprint::{BrowserProfile, StealthProfile};
+use crate::engine::readiness::{ReadinessCheck, ReadinessConfig};
+use crate::ops;
 
 // * [APP-A.1] Stealth payload to mask WebDriver detection
-const STEALTH_PAYLOAD: &str = r#"
+// * `__TITAN_*__` placeholders are replaced with JSON values from the domain's `StealthProfile`
+const STEALTH_TEMPLATE: &str = r#"
 (() => {
     // * Mask navigator.webdriver
     Object.defineProperty(navigator, 'webdriver', {
@@ -35,18 +50,39 @@ const STEALTH_PAYLOAD: &str = r#"
         configurable: true
     });
 
-    // * Mask languages
+    // * Languages and platform from the profile
     Object.defineProperty(navigator, 'languages', {
-        get: () => ['en-US', 'en'],
+        get: () => __TITAN_LANGUAGES__,
+        configurable: true
+    });
+    Object.defineProperty(navigator, 'platform', {
+        get: () => __TITAN_PLATFORM__,
         configurable: true
     });
 
-    // * Set hardwareConcurrency to 4 (common value)
     Object.defineProperty(navigator, 'hardwareConcurrency', {
-        get: () => 4,
+        get: () => __TITAN_HARDWARE_CONCURRENCY__,
         configurable: true
     });
 
+    // * Screen matches the emulated window
+    const [width, height] = __TITAN_VIEWPORT__;
+    for (const [key, value] of Object.entries({ width, height, availWidth: width, availHeight: height - 40 })) {
+        Object.defineProperty(screen, key, { get: () => value, configurable: true });
+    }
+
+    // * WebGL vendor and renderer (UNMASKED_VENDOR_WEBGL / UNMASKED_RENDERER_WEBGL)
+    const maskWebGl = (proto) => {
+        const getParameter = proto.getParameter;
+        proto.getParameter = function (parameter) {
+            if (parameter === 37445) return __TITAN_WEBGL_VENDOR__;
+            if (parameter === 37446) return __TITAN_WEBGL_RENDERER__;
+            return getParameter.call(this, parameter);
+        };
+    };
+    maskWebGl(WebGLRenderingContext.prototype);
+    if (window.WebGL2RenderingContext) maskWebGl(WebGL2RenderingContext.prototype);
+
     // * Mask permissions query
     const originalQuery = window.navigator.permissions.query;
     window.navigator.permissions.query = (parameters) => (
@@ -62,33 +98,59 @@ const STEALTH_PAYLOAD: &str = r#"
 })();
 "#;
 
-// * Console capture script to collect JS errors and logs
+// * Fills the stealth template with a profile's values
+fn stealth_payload(profile: &StealthProfile) -> String {
+    let json = |value: serde_json::Value| value.to_string();
+    STEALTH_TEMPLATE
+        .replace("__TITAN_LANGUAGES__", &json(serde_json::json!(profile.languages)))
+        .replace("__TITAN_PLATFORM__", &json(serde_json::json!(profile.platform)))
+        .replace("__TITAN_HARDWARE_CONCURRENCY__", &profile.hardware_concurrency.to_string())
+        .replace("__TITAN_VIEWPORT__", &json(serde_json::json!([profile.viewport.0, profile.viewport.1])))
+        .replace("__TITAN_WEBGL_VENDOR__", &json(serde_json::json!(profile.webgl_vendor)))
+        .replace("__TITAN_WEBGL_RENDERER__", &json(serde_json::json!(profile.webgl_renderer)))
+}
+
+// * Console capture script to collect JS errors, rejected promises, CSP violations and logs
 const CONSOLE_CAPTURE_JS: &str = r#"
 (() => {
     window.__titanConsoleLogs = [];
     const originalConsole = { ...console };
+    const push = (type, message, source) => window.__titanConsoleLogs.push({
+        type,
+        timestamp: Date.now(),
+        message: String(message),
+        source: source || null
+    });
 
     ['log', 'warn', 'error', 'info', 'debug'].forEach(method => {
         console[method] = (...args) => {
-            window.__titanConsoleLogs.push({
-                type: method,
-                timestamp: Date.now(),
-                message: args.map(a => typeof a === 'object' ? JSON.stringify(a) : String(a)).join(' ')
-            });
+            push(method, args.map(a => typeof a === 'object' ? JSON.stringify(a) : String(a)).join(' '));
             originalConsole[method](...args);
         };
     });
 
     window.addEventListener('error', (event) => {
-        window.__titanConsoleLogs.push({
-            type: 'uncaught_error',
-            timestamp: Date.now(),
-            message: `${event.message} at ${event.filename}:${event.lineno}`
-        });
+        // * Failed resource loads also dispatch 'error' but carry no message
+        if (!event.message) return;
+        push('uncaught_error', event.message, event.filename ? `${event.filename}:${event.lineno}:${event.colno}` : null);
+    });
+
+    window.addEventListener('unhandledrejection', (event) => {
+        const reason = event.reason;
+        push('unhandled_rejection', reason && reason.message ? reason.message : reason);
+    });
+
+    document.addEventListener('securitypolicyviolation', (event) => {
+        push('csp_violation', `${event.violatedDirective} blocked ${event.blockedURI || 'inline'}`,
+            event.sourceFile ? `${event.sourceFile}:${event.lineNumber}` : null);
     });
 })();
 "#;
 
+// * Diagnostics kept per page, and the longest message stored for each
+const MAX_DIAGNOSTICS: usize = 50;
+const MAX_DIAGNOSTIC_MESSAGE_CHARS: usize = 500;
+
 // * Resource types to block for performance
 const BLOCKED_EXTENSIONS: &[&str] = &[".png", ".jpg", ".jpeg", ".gif", ".webp", ".woff", ".woff2", ".ttf", ".mp4", ".webm", ".css"];
 
@@ -111,6 +173,9 @@ pub enum SlowPathError {
 
     #[error("Browser crashed")]
     BrowserCrash,
+
+    #[error("Page exceeded a resource limit: {0}")]
+    ResourceLimit(PageLimit),
 }
 
 // * Result of slow path rendering
@@ -119,6 +184,39 @@ pub struct SlowPathResult {
     pub html: String,
     pub console_logs: Vec<ConsoleLogEntry>,
     pub final_url: String,
+    // * JSON answers to the page's XHR/fetch calls (empty unless capture is enabled)
+    pub captured_responses: Vec<CapturedResponse>,
+    // * Errors and warnings picked out of `console_logs`
+    pub diagnostics: Vec<PageDiagnostic>,
+}
+
+// * A JSON API response the page loaded while rendering
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CapturedResponse {
+    pub url: String,
+    pub status: u16,
+    pub mime_type: String,
+    pub body: String,
+}
+
+// * Which XHR/fetch responses are recorded during a render
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseCaptureConfig {
+    pub enabled: bool,
+    // * Responses kept per page, in the order they finished loading
+    pub max_responses: usize,
+    // * Larger bodies are dropped rather than cut, since partial JSON is unusable
+    pub max_body_bytes: usize,
+}
+
+impl Default for ResponseCaptureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_responses: 50,
+            max_body_bytes: 1024 * 1024,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -126,12 +224,93 @@ pub struct ConsoleLogEntry {
     pub log_type: String,
     pub timestamp: u64,
     pub message: String,
+    // * Script position or blocked resource, for errors and CSP violations
+    pub source: Option<String>,
+}
+
+/// Kind of problem a page reported while it rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    /// An exception no script caught
+    UncaughtError,
+    /// A rejected promise without a handler
+    UnhandledRejection,
+    /// A Content Security Policy violation
+    CspViolation,
+    /// `console.error` output
+    ConsoleError,
+    /// `console.warn` output
+    ConsoleWarning,
+}
+
+impl DiagnosticKind {
+    /// Metric label
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticKind::UncaughtError => "uncaught_error",
+            DiagnosticKind::UnhandledRejection => "unhandled_rejection",
+            DiagnosticKind::CspViolation => "csp_violation",
+            DiagnosticKind::ConsoleError => "console_error",
+            DiagnosticKind::ConsoleWarning => "console_warning",
+        }
+    }
+
+    // * Informational console output (log, info, debug) is not a diagnostic
+    fn from_log_type(log_type: &str) -> Option<Self> {
+        match log_type {
+            "uncaught_error" => Some(DiagnosticKind::UncaughtError),
+            "unhandled_rejection" => Some(DiagnosticKind::UnhandledRejection),
+            "csp_violation" => Some(DiagnosticKind::CspViolation),
+            "error" => Some(DiagnosticKind::ConsoleError),
+            "warn" => Some(DiagnosticKind::ConsoleWarning),
+            _ => None,
+        }
+    }
+}
+
+/// Error or warning a page raised while it rendered
+///
+/// Stored with the page's record so domains where rendering breaks pages can be found.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageDiagnostic {
+    pub kind: DiagnosticKind,
+    pub message: String,
+    /// Script position (`file:line:column`) or the blocked resource
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+/// Picks the errors and warnings out of a render's console output
+///
+/// Keeps the first `MAX_DIAGNOSTICS`, with messages cut to `MAX_DIAGNOSTIC_MESSAGE_CHARS`.
+pub fn diagnostics_from_console(logs: &[ConsoleLogEntry]) -> Vec<PageDiagnostic> {
+    logs.iter()
+        .filter_map(|entry| {
+            let kind = DiagnosticKind::from_log_type(&entry.log_type)?;
+            Some(PageDiagnostic {
+                kind,
+                message: entry.message.chars().take(MAX_DIAGNOSTIC_MESSAGE_CHARS).collect(),
+                source: entry.source.clone(),
+                timestamp: entry.timestamp,
+            })
+        })
+        .take(MAX_DIAGNOSTICS)
+        .collect()
 }
 
 // * SlowPathRenderer manages headless browser instances
 pub struct SlowPathRenderer {
     browser: Option<Browser>,
     handler: Option<tokio::task::JoinHandle<()>>,
+    capture: ResponseCaptureConfig,
+    readiness: ReadinessConfig,
+    limits: BrowserLimits,
+    // * Pages opened by the current browser and when it was launched
+    pages_rendered: u32,
+    launched_at: Option<Instant>,
 }
 
 impl SlowPathRenderer {
@@ -140,11 +319,41 @@ impl SlowPathRenderer {
         Self {
             browser: None,
             handler: None,
+            capture: ResponseCaptureConfig::default(),
+            readiness: ReadinessConfig::default(),
+            limits: BrowserLimits::default(),
+            pages_rendered: 0,
+            launched_at: None,
         }
     }
 
-    // * Launches the browser if not already running
+    // * Sets the per-page watchdogs and when the browser is relaunched
+    pub fn with_limits(mut self, limits: BrowserLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    // * Sets when rendered pages are extracted (wait strategy, timeout, per-site overrides)
+    pub fn with_readiness(mut self, readiness: ReadinessConfig) -> Self {
+        self.readiness = readiness;
+        self
+    }
+
+    // * Records JSON responses to the page's XHR/fetch calls in `captured_responses`
+    pub fn with_response_capture(mut self, capture: ResponseCaptureConfig) -> Self {
+        self.capture = capture;
+        self
+    }
+
+    // * Launches the browser if not already running, relaunching it once it is due for recycling
     pub async fn ensure_browser(&mut self) -> Result<&Browser, SlowPathError> {
+        let age = self.launched_at.map(|at| at.elapsed()).unwrap_or_default();
+        if let Some(reason) = self.limits.recycle_reason(self.pages_rendered, age) {
+            if self.browser.is_some() {
+                self.recycle(reason).await;
+            }
+        }
+
         if self.browser.is_none() {
             let config = BrowserConfig::builder()
                 .with_head()
@@ -170,43 +379,107 @@ impl SlowPathRenderer {
 
             self.browser = Some(browser);
             self.handler = Some(handle);
+            self.pages_rendered = 0;
+            self.launched_at = Some(Instant::now());
             info!("SlowPathRenderer browser launched");
         }
 
         Ok(self.browser.as_ref().unwrap())
     }
 
-    // * Renders a page and returns the final HTML
+    // * Renders a page with the default browser profile and returns the final HTML
     pub async fn render(&mut self, url: &str) -> Result<SlowPathResult, SlowPathError> {
+        self.render_with_profile(url, &BrowserProfile::default()).await
+    }
+
+    // * Renders a page presenting the given profile (user agent, locale, viewport, WebGL)
+    pub async fn render_with_profile(
+        &mut self,
+        url: &str,
+        profile: &BrowserProfile,
+    ) -> Result<SlowPathResult, SlowPathError> {
         let browser = self.ensure_browser().await?;
 
         let page = browser
             .new_page("about:blank")
             .await
             .map_err(|e| SlowPathError::Navigation(e.to_string()))?;
+        self.pages_rendered += 1;
+
+        let result = tokio::select! {
+            result = self.render_page(&page, url, profile) => result,
+            limit = self.watchdog(&page) => Err(SlowPathError::ResourceLimit(limit)),
+        };
 
-        // * Inject stealth script before navigation
-        page.evaluate(STEALTH_PAYLOAD)
+        match &result {
+            // * The page may be wedged; relaunching the browser reclaims it without waiting on it
+            Err(SlowPathError::ResourceLimit(limit)) => {
+                warn!(url = %url, limit = %limit, "Slow path page exceeded a resource limit");
+                ops::record_page_limit(limit.as_str());
+                drop(page);
+                self.recycle("page_limit").await;
+            }
+            _ => {
+                let _ = page.close().await;
+            }
+        }
+        result
+    }
+
+    // * Closes the browser so the next render launches a fresh one
+    async fn recycle(&mut self, reason: &'static str) {
+        info!(reason, pages = self.pages_rendered, "Recycling slow path browser");
+        ops::record_browser_recycle(reason);
+        self.shutdown().await;
+    }
+
+    // * Samples the page's resource use until a limit is exceeded
+    async fn watchdog(&self, page: &Page) -> PageLimit {
+        let started = Instant::now();
+        let interval = Duration::from_millis(self.limits.watchdog_interval_ms.max(1));
+        let _ = page.execute(PerformanceEnableParams::default()).await;
+        loop {
+            tokio::time::sleep(interval).await;
+            // * A stalled renderer may never answer; the wall-clock limit still applies
+            let usage = match tokio::time::timeout(interval, page.execute(GetMetricsParams::default())).await {
+                Ok(Ok(response)) => {
+                    PageUsage::from_metrics(response.result.metrics.iter().map(|m| (m.name.as_str(), m.value)))
+                }
+                _ => PageUsage::default(),
+            };
+            if let Err(limit) = self.limits.check(&usage, started.elapsed()) {
+                return limit;
+            }
+        }
+    }
+
+    // * Emulates the profile, navigates and extracts the rendered page
+    async fn render_page(
+        &self,
+        page: &Page,
+        url: &str,
+        profile: &BrowserProfile,
+    ) -> Result<SlowPathResult, SlowPathError> {
+        // * Emulate the profile and inject the stealth script into every document before navigation
+        let stealth = profile.stealth();
+        Self::apply_profile(page, &stealth).await?;
+        page.evaluate_on_new_document(stealth_payload(&stealth))
             .await
             .map_err(|e| SlowPathError::ScriptInjection(e.to_string()))?;
 
-        // * Inject console capture
-        page.evaluate(CONSOLE_CAPTURE_JS)
+        // * Console capture has to be in place before the page's own scripts run
+        page.evaluate_on_new_document(CONSOLE_CAPTURE_JS)
             .await
             .map_err(|e| SlowPathError::ScriptInjection(e.to_string()))?;
 
-        // * Navigate with timeout
-        let timeout = Duration::from_millis(PAGE_TIMEOUT_MS);
-        let navigate_result = tokio::time::timeout(timeout, page.goto(url)).await;
-
-        match navigate_result {
-            Ok(Ok(_)) => {}
-            Ok(Err(e)) => return Err(SlowPathError::Navigation(e.to_string())),
-            Err(_) => return Err(SlowPathError::Timeout(PAGE_TIMEOUT_MS)),
-        }
+        // * Network events are buffered by the listeners until the page has settled
+        let listeners = if self.capture.enabled {
+            Some(Self::listen_for_responses(page).await?)
+        } else {
+            None
+        };
 
-        // * Wait for page to settle
-        tokio::time::sleep(Duration::from_millis(500)).await;
+        self.navigate_until_ready(page, url).await?;
 
         // * Get final URL after redirects
         let final_url = page
@@ -222,18 +495,180 @@ impl SlowPathRenderer {
             .map_err(|e| SlowPathError::ContentExtraction(e.to_string()))?;
 
         // * Extract console logs
-        let console_logs = self.extract_console_logs(&page).await;
+        let console_logs = self.extract_console_logs(page).await;
+        let diagnostics = diagnostics_from_console(&console_logs);
+        for diagnostic in &diagnostics {
+            ops::record_page_diagnostic(diagnostic.kind.as_str());
+        }
 
-        // * Close the page
-        let _ = page.close().await;
+        let captured_responses = match listeners {
+            Some(listeners) => self.collect_responses(page, listeners).await,
+            None => Vec::new(),
+        };
 
         Ok(SlowPathResult {
             html,
             console_logs,
             final_url,
+            captured_responses,
+            diagnostics,
         })
     }
 
+    // * Overrides the user agent, client hints, locale, timezone and window size for a page
+    async fn apply_profile(page: &Page, stealth: &StealthProfile) -> Result<(), SlowPathError> {
+        let version = stealth.major_version.to_string();
+        let brands = vec![
+            UserAgentBrandVersion::new("Not_A Brand", "8"),
+            UserAgentBrandVersion::new("Chromium", version.clone()),
+            UserAgentBrandVersion::new(stealth.brand, version),
+        ];
+        let metadata = UserAgentMetadata {
+            brands: Some(brands),
+            full_version_list: None,
+            platform: stealth.client_hint_platform.to_string(),
+            platform_version: String::new(),
+            architecture: "x86".to_string(),
+            model: String::new(),
+            mobile: false,
+            bitness: Some("64".to_string()),
+            wow64: Some(false),
+        };
+        let emulation = async {
+            page.execute(SetUserAgentOverrideParams {
+                user_agent: stealth.user_agent.clone(),
+                accept_language: Some(stealth.accept_language.clone()),
+                platform: Some(stealth.platform.to_string()),
+                user_agent_metadata: Some(metadata),
+            })
+            .await?;
+            page.execute(SetLocaleOverrideParams {
+                locale: Some(stealth.locale.replace('-', "_")),
+            })
+            .await?;
+            page.execute(SetTimezoneOverrideParams::new(stealth.timezone)).await?;
+            let (width, height) = stealth.viewport;
+            page.execute(SetDeviceMetricsOverrideParams::new(width, height, 1.0, false))
+                .await?;
+            Ok::<_, chromiumoxide::error::CdpError>(())
+        };
+        emulation
+            .await
+            .map_err(|e| SlowPathError::ScriptInjection(e.to_string()))
+    }
+
+    // * Navigates and polls the readiness probe until the page is ready or the timeout passes
+    async fn navigate_until_ready(&self, page: &Page, url: &str) -> Result<(), SlowPathError> {
+        let host = url::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_default();
+        let (wait_until, timeout) = self.readiness.for_host(&host);
+        let probe = wait_until.probe_script();
+        let mut check = ReadinessCheck::new(wait_until.clone());
+        let poll = Duration::from_millis(self.readiness.poll_interval_ms.max(1));
+        let started = Instant::now();
+
+        // * goto resolves on the load event; earlier strategies are probed while it runs
+        let navigation = page.goto(url);
+        tokio::pin!(navigation);
+        let mut navigated = false;
+        let ready = loop {
+            tokio::select! {
+                result = &mut navigation, if !navigated => {
+                    if let Err(e) = result {
+                        return Err(SlowPathError::Navigation(e.to_string()));
+                    }
+                    navigated = true;
+                }
+                _ = tokio::time::sleep(poll) => {}
+            }
+            if let Ok(result) = page.evaluate(probe.as_str()).await {
+                if check.observe(result.value().unwrap_or(&serde_json::Value::Null), Instant::now()) {
+                    break true;
+                }
+            }
+            if started.elapsed() >= timeout {
+                break false;
+            }
+        };
+
+        let waited = started.elapsed().as_secs_f64();
+        ops::record_render_wait(wait_until.as_str(), if ready { "ready" } else { "timeout" }, waited);
+        if !ready {
+            // * A tab that never left about:blank has nothing to extract
+            let blank = page.url().await.ok().flatten().is_none_or(|u| u == "about:blank");
+            if blank || !self.readiness.extract_on_timeout {
+                return Err(SlowPathError::Timeout(timeout.as_millis() as u64));
+            }
+            debug!(url = %url, strategy = wait_until.as_str(), "Readiness timed out; extracting as rendered");
+        }
+        Ok(())
+    }
+
+    // * Enables the CDP Network domain and subscribes to response events
+    async fn listen_for_responses(
+        page: &Page,
+    ) -> Result<(EventStream<EventResponseReceived>, EventStream<EventLoadingFinished>), SlowPathError> {
+        page.execute(EnableParams::default())
+            .await
+            .map_err(|e| SlowPathError::ScriptInjection(e.to_string()))?;
+        let received = page
+            .event_listener::<EventResponseReceived>()
+            .await
+            .map_err(|e| SlowPathError::ScriptInjection(e.to_string()))?;
+        let finished = page
+            .event_listener::<EventLoadingFinished>()
+            .await
+            .map_err(|e| SlowPathError::ScriptInjection(e.to_string()))?;
+        Ok((received, finished))
+    }
+
+    // * Reads the bodies of finished XHR/fetch JSON responses
+    async fn collect_responses(
+        &self,
+        page: &Page,
+        (mut received, mut finished): (EventStream<EventResponseReceived>, EventStream<EventLoadingFinished>),
+    ) -> Vec<CapturedResponse> {
+        let mut candidates: HashMap<RequestId, CapturedResponse> = HashMap::new();
+        while let Some(Some(event)) = received.next().now_or_never() {
+            if is_capturable(&event.r#type, &event.response.mime_type) {
+                let response = CapturedResponse {
+                    url: event.response.url.clone(),
+                    status: u16::try_from(event.response.status).unwrap_or_default(),
+                    mime_type: event.response.mime_type.clone(),
+                    body: String::new(),
+                };
+                candidates.insert(event.request_id.clone(), response);
+            }
+        }
+
+        let mut captured = Vec::new();
+        let mut done = HashSet::new();
+        while let Some(Some(event)) = finished.next().now_or_never() {
+            if captured.len() >= self.capture.max_responses {
+                break;
+            }
+            let Some(response) = candidates.get(&event.request_id) else {
+                continue;
+            };
+            if !done.insert(event.request_id.clone()) {
+                continue;
+            }
+            match page.execute(GetResponseBodyParams::new(event.request_id.clone())).await {
+                Ok(body) if !body.result.base64_encoded && body.result.body.len() <= self.capture.max_body_bytes => {
+                    captured.push(CapturedResponse {
+                        body: body.result.body.clone(),
+                        ..response.clone()
+                    });
+                }
+                Ok(_) => debug!(url = %response.url, "Skipped binary or oversized API response"),
+                Err(e) => debug!(url = %response.url, error = %e, "API response body unavailable"),
+            }
+        }
+        captured
+    }
+
     // * Extracts captured console logs from the page
     async fn extract_console_logs(&self, page: &Page) -> Vec<ConsoleLogEntry> {
         let result = page
@@ -242,7 +677,7 @@ impl SlowPathRenderer {
 
         match result {
             Ok(value) => {
-                if let Some(json_str) = value.into_value::<String>().ok() {
+                if let Ok(json_str) = value.into_value::<String>() {
                     serde_json::from_str::<Vec<serde_json::Value>>(&json_str)
                         .unwrap_or_default()
                         .into_iter()
@@ -251,6 +686,7 @@ impl SlowPathRenderer {
                                 log_type: v.get("type")?.as_str()?.to_string(),
                                 timestamp: v.get("timestamp")?.as_u64()?,
                                 message: v.get("message")?.as_str()?.to_string(),
+                                source: v.get("source").and_then(|s| s.as_str()).map(str::to_string),
                             })
                         })
                         .collect()
@@ -262,6 +698,11 @@ impl SlowPathRenderer {
         }
     }
 
+    // * Returns true while a browser process is running
+    pub fn is_running(&self) -> bool {
+        self.browser.is_some()
+    }
+
     // * Closes the browser gracefully
     pub async fn shutdown(&mut self) {
         // * FIXED: Changed to `mut browser` as close() requires mutable borrow
@@ -271,6 +712,8 @@ impl SlowPathRenderer {
         if let Some(handler) = self.handler.take() {
             handler.abort();
         }
+        self.pages_rendered = 0;
+        self.launched_at = None;
         info!("SlowPathRenderer shutdown complete");
     }
 }
@@ -290,6 +733,13 @@ impl Drop for SlowPathRenderer {
     }
 }
 
+// * True for XHR/fetch responses carrying JSON (application/json or a +json type)
+fn is_capturable(resource_type: &ResourceType, mime_type: &str) -> bool {
+    let mime_type = mime_type.to_ascii_lowercase();
+    matches!(resource_type, ResourceType::Xhr | ResourceType::Fetch)
+        && (mime_type == "application/json" || mime_type.ends_with("+json") || mime_type == "text/json")
+}
+
 // * Checks if a URL should be blocked based on extension
 pub fn should_block_resource(url: &str) -> bool {
     let lower_url = url.to_lowercase();
@@ -312,12 +762,55 @@ mod tests {
         assert!(!should_block_resource("https://example.com/api/data"));
     }
 
+    #[test]
+    fn test_only_json_api_responses_are_captured() {
+        assert!(is_capturable(&ResourceType::Xhr, "application/json"));
+        assert!(is_capturable(&ResourceType::Fetch, "application/ld+json"));
+        assert!(is_capturable(&ResourceType::Fetch, "Application/JSON"));
+        assert!(!is_capturable(&ResourceType::Fetch, "text/html"));
+        assert!(!is_capturable(&ResourceType::Script, "application/json"));
+        assert!(!is_capturable(&ResourceType::Document, "application/json"));
+    }
+
     #[test]
     fn test_stealth_payload_contains_required_masks() {
-        assert!(STEALTH_PAYLOAD.contains("webdriver"));
-        assert!(STEALTH_PAYLOAD.contains("plugins"));
-        assert!(STEALTH_PAYLOAD.contains("languages"));
-        assert!(STEALTH_PAYLOAD.contains("hardwareConcurrency"));
+        let stealth = BrowserProfile::default().stealth();
+        let payload = stealth_payload(&stealth);
+        assert!(payload.contains("webdriver"));
+        assert!(payload.contains("plugins"));
+        assert!(payload.contains("get: () => [\"en-US\",\"en\"]"));
+        assert!(payload.contains(&format!("get: () => {},", stealth.hardware_concurrency)));
+        assert!(payload.contains(&serde_json::to_string(stealth.webgl_renderer).unwrap()));
+        assert!(!payload.contains("__TITAN_"));
+    }
+
+    #[test]
+    fn test_diagnostics_keep_errors_and_warnings() {
+        let entry = |log_type: &str, message: &str| ConsoleLogEntry {
+            log_type: log_type.to_string(),
+            timestamp: 1_700_000_000_000,
+            message: message.to_string(),
+            source: None,
+        };
+        let mut logs = vec![
+            entry("log", "app booted"),
+            entry("csp_violation", "script-src blocked https://cdn.example.com/a.js"),
+            entry("warn", "deprecated API"),
+            entry("uncaught_error", &"x".repeat(2_000)),
+        ];
+        logs[3].source = Some("https://example.com/app.js:10:4".to_string());
+
+        let diagnostics = diagnostics_from_console(&logs);
+        let kinds: Vec<DiagnosticKind> = diagnostics.iter().map(|d| d.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![DiagnosticKind::CspViolation, DiagnosticKind::ConsoleWarning, DiagnosticKind::UncaughtError]
+        );
+        assert_eq!(diagnostics[2].message.len(), MAX_DIAGNOSTIC_MESSAGE_CHARS);
+        assert_eq!(diagnostics[2].source.as_deref(), Some("https://example.com/app.js:10:4"));
+
+        let noisy = vec![entry("error", "boom"); MAX_DIAGNOSTICS + 10];
+        assert_eq!(diagnostics_from_console(&noisy).len(), MAX_DIAGNOSTICS);
     }
 
     #[test]
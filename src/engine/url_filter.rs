@@ -0,0 +1,456 @@
+// * [EDD-4] URL Filter
+// * Declarative allow/deny rules (globs, regexes, domain suffixes) and public-suffix-aware
+// * same-site checks, so crawl scope is configuration rather than custom code
+
+use publicsuffix::{List, Psl};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use thiserror::Error;
+use url::Url;
+
+// * Prefix marking a pattern as a regular expression instead of a glob
+const REGEX_PREFIX: &str = "re:";
+
+static BUILTIN_SUFFIXES: LazyLock<List> = LazyLock::new(|| {
+    include_str!("public_suffix.dat")
+        .parse()
+        .expect("built-in public suffix list is valid")
+});
+
+#[derive(Debug, Error)]
+pub enum UrlFilterError {
+    #[error("Invalid URL pattern `{pattern}`: {source}")]
+    InvalidPattern {
+        pattern: String,
+        #[source]
+        source: regex::Error,
+    },
+
+    #[error("Failed to read public suffix list {path}: {source}")]
+    SuffixListIo {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Invalid public suffix list {path}: {message}")]
+    SuffixListParse { path: PathBuf, message: String },
+}
+
+/// Why a URL was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterRejection {
+    /// Not an http(s) URL with a host
+    InvalidUrl,
+    /// Host is in, or under, a denied domain
+    DeniedDomain,
+    /// Matched a deny pattern
+    DeniedPattern,
+    /// Host is outside every allowed domain
+    DomainNotAllowed,
+    /// Matched none of the allow patterns
+    PatternNotAllowed,
+    /// Registrable domain differs from every seed's
+    OffSite,
+}
+
+/// Serializable URL filter rules
+///
+/// Patterns are globs unless prefixed with `re:`. A glob containing `://` is
+/// matched against the whole URL; one containing `/` against
+/// `host/path?query`; any other glob against the host alone. `*` and `?`
+/// stop at `/`, `**` crosses it, and a trailing `/**` also matches the bare
+/// prefix.
+///
+/// # Example
+/// ```toml
+/// [crawl.url_filter]
+/// allow = ["*.example.com/news/**"]
+/// deny = ["re:[?&]sessionid="]
+/// denied_domains = ["ads.example.com"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UrlFilterConfig {
+    /// URLs must match at least one of these (when non-empty)
+    pub allow: Vec<String>,
+    /// URLs matching any of these are rejected
+    pub deny: Vec<String>,
+    /// Hosts must equal or end in one of these domains (when non-empty)
+    pub allowed_domains: Vec<String>,
+    /// Hosts equal to or under these domains are rejected
+    pub denied_domains: Vec<String>,
+    /// Only accept URLs on the same registrable domain (site) as the seeds
+    pub same_site: bool,
+    /// Full public suffix list file (the built-in list covers common suffixes)
+    pub public_suffix_list: Option<PathBuf>,
+}
+
+/// A compiled URL pattern and the part of the URL it applies to
+#[derive(Debug, Clone)]
+struct UrlPattern {
+    regex: Regex,
+    target: PatternTarget,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PatternTarget {
+    Url,
+    HostAndPath,
+    Host,
+}
+
+impl UrlPattern {
+    fn compile(pattern: &str) -> Result<Self, UrlFilterError> {
+        let invalid = |source| UrlFilterError::InvalidPattern {
+            pattern: pattern.to_string(),
+            source,
+        };
+
+        if let Some(regex) = pattern.strip_prefix(REGEX_PREFIX) {
+            return Ok(Self {
+                regex: Regex::new(regex).map_err(invalid)?,
+                target: PatternTarget::Url,
+            });
+        }
+
+        let target = if pattern.contains("://") {
+            PatternTarget::Url
+        } else if pattern.contains('/') {
+            PatternTarget::HostAndPath
+        } else {
+            PatternTarget::Host
+        };
+        Ok(Self {
+            regex: Regex::new(&glob_to_regex(pattern)).map_err(invalid)?,
+            target,
+        })
+    }
+
+    fn matches(&self, url: &Url, raw: &str) -> bool {
+        let host = url.host_str().unwrap_or_default();
+        match self.target {
+            PatternTarget::Url => self.regex.is_match(raw),
+            PatternTarget::Host => self.regex.is_match(host),
+            PatternTarget::HostAndPath => {
+                let mut target = format!("{}{}", host, url.path());
+                if let Some(query) = url.query() {
+                    target.push('?');
+                    target.push_str(query);
+                }
+                self.regex.is_match(&target)
+            }
+        }
+    }
+}
+
+/// Compiled URL filter
+///
+/// # Example
+/// ```ignore
+/// let filter = UrlFilter::new(&UrlFilterConfig {
+///     allow: vec!["*.example.com/news/**".to_string()],
+///     ..Default::default()
+/// })?;
+/// assert!(filter.allows("https://www.example.com/news/today"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct UrlFilter {
+    allow: Vec<UrlPattern>,
+    deny: Vec<UrlPattern>,
+    allowed_domains: Vec<String>,
+    denied_domains: Vec<String>,
+    same_site: bool,
+    sites: HashSet<String>,
+    suffixes: Option<List>,
+}
+
+impl UrlFilter {
+    /// Compiles the patterns and loads the public suffix list, if configured
+    pub fn new(config: &UrlFilterConfig) -> Result<Self, UrlFilterError> {
+        let compile = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|p| UrlPattern::compile(p))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let suffixes = match &config.public_suffix_list {
+            Some(path) => {
+                let contents = std::fs::read_to_string(path).map_err(|source| {
+                    UrlFilterError::SuffixListIo {
+                        path: path.clone(),
+                        source,
+                    }
+                })?;
+                let list = contents.parse::<List>().map_err(|e| UrlFilterError::SuffixListParse {
+                    path: path.clone(),
+                    message: e.to_string(),
+                })?;
+                Some(list)
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            allow: compile(&config.allow)?,
+            deny: compile(&config.deny)?,
+            allowed_domains: normalize_domains(&config.allowed_domains),
+            denied_domains: normalize_domains(&config.denied_domains),
+            same_site: config.same_site,
+            sites: HashSet::new(),
+            suffixes,
+        })
+    }
+
+    /// Records the sites (registrable domains) of the seed URLs for same-site checks
+    pub fn with_sites<I, S>(mut self, urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for url in urls {
+            if let Some(site) = Url::parse(url.as_ref())
+                .ok()
+                .and_then(|u| u.host_str().map(|h| self.site_of(h)))
+            {
+                self.sites.insert(site);
+            }
+        }
+        self
+    }
+
+    /// Returns true if the URL passes every rule
+    pub fn allows(&self, url: &str) -> bool {
+        self.check(url).is_ok()
+    }
+
+    /// Applies the rules in order: domains, deny patterns, allow lists, then site
+    pub fn check(&self, url: &str) -> Result<(), FilterRejection> {
+        let parsed = Url::parse(url).map_err(|_| FilterRejection::InvalidUrl)?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(FilterRejection::InvalidUrl);
+        }
+        let host = parsed
+            .host_str()
+            .map(|h| h.to_lowercase())
+            .ok_or(FilterRejection::InvalidUrl)?;
+
+        if self.denied_domains.iter().any(|d| domain_matches(&host, d)) {
+            return Err(FilterRejection::DeniedDomain);
+        }
+        if self.deny.iter().any(|p| p.matches(&parsed, url)) {
+            return Err(FilterRejection::DeniedPattern);
+        }
+        if !self.allowed_domains.is_empty()
+            && !self.allowed_domains.iter().any(|d| domain_matches(&host, d))
+        {
+            return Err(FilterRejection::DomainNotAllowed);
+        }
+        if !self.allow.is_empty() && !self.allow.iter().any(|p| p.matches(&parsed, url)) {
+            return Err(FilterRejection::PatternNotAllowed);
+        }
+        // * With no recorded seeds there is no site to compare against
+        if self.same_site && !self.sites.is_empty() && !self.sites.contains(&self.site_of(&host)) {
+            return Err(FilterRejection::OffSite);
+        }
+        Ok(())
+    }
+
+    /// Returns true if both URLs belong to the same registrable domain
+    pub fn same_site(&self, a: &str, b: &str) -> bool {
+        let site = |url: &str| {
+            Url::parse(url)
+                .ok()
+                .and_then(|u| u.host_str().map(|h| self.site_of(&h.to_lowercase())))
+        };
+        matches!((site(a), site(b)), (Some(a), Some(b)) if a == b)
+    }
+
+    /// Returns the registrable domain ("eTLD+1") of a host
+    ///
+    /// IP addresses and bare public suffixes are returned unchanged.
+    pub fn site_of(&self, host: &str) -> String {
+        let host = host.trim_end_matches('.').to_lowercase();
+        if host.trim_matches(|c| c == '[' || c == ']').parse::<IpAddr>().is_ok() {
+            return host;
+        }
+
+        let list = self.suffixes.as_ref().unwrap_or(&BUILTIN_SUFFIXES);
+        list.domain(host.as_bytes())
+            .and_then(|d| std::str::from_utf8(d.as_bytes()).ok().map(str::to_string))
+            .unwrap_or(host)
+    }
+}
+
+/// Translates a glob into an anchored regex
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    let mut chars = glob.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+
+    // * `news/**` should also match `news` itself
+    if let Some(prefix) = regex.strip_suffix("/.*") {
+        regex = format!("{}(/.*)?", prefix);
+    }
+    regex.push('$');
+    regex
+}
+
+fn normalize_domains(domains: &[String]) -> Vec<String> {
+    domains
+        .iter()
+        .map(|d| d.trim().trim_start_matches("*.").trim_matches('.').to_lowercase())
+        .filter(|d| !d.is_empty())
+        .collect()
+}
+
+/// Returns true if `host` is `domain` or one of its subdomains
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(config: UrlFilterConfig) -> UrlFilter {
+        UrlFilter::new(&config).unwrap()
+    }
+
+    #[test]
+    fn test_glob_allow() {
+        let filter = filter(UrlFilterConfig {
+            allow: vec!["*.example.com/news/**".to_string()],
+            ..Default::default()
+        });
+
+        assert!(filter.allows("https://www.example.com/news/2024/story"));
+        assert!(filter.allows("https://www.example.com/news"));
+        assert!(filter.allows("https://www.example.com/news/item?id=4"));
+        assert_eq!(
+            filter.check("https://www.example.com/sports/"),
+            Err(FilterRejection::PatternNotAllowed)
+        );
+        assert_eq!(
+            filter.check("https://example.org/news/"),
+            Err(FilterRejection::PatternNotAllowed)
+        );
+    }
+
+    #[test]
+    fn test_glob_targets() {
+        let filter = filter(UrlFilterConfig {
+            deny: vec![
+                "ads.*".to_string(),
+                "https://example.com/private/*".to_string(),
+                "*/calendar/**".to_string(),
+            ],
+            ..Default::default()
+        });
+
+        assert_eq!(filter.check("https://ads.example.com/"), Err(FilterRejection::DeniedPattern));
+        assert_eq!(
+            filter.check("https://example.com/private/page"),
+            Err(FilterRejection::DeniedPattern)
+        );
+        assert_eq!(
+            filter.check("https://example.com/calendar/2024/05"),
+            Err(FilterRejection::DeniedPattern)
+        );
+        // * A single `*` does not cross `/`
+        assert!(filter.allows("https://example.com/private/a/b"));
+    }
+
+    #[test]
+    fn test_regex_patterns() {
+        let filter = filter(UrlFilterConfig {
+            deny: vec!["re:[?&](sort|sessionid)=".to_string()],
+            ..Default::default()
+        });
+
+        assert!(!filter.allows("https://example.com/shop?page=2&sort=price"));
+        assert!(filter.allows("https://example.com/shop?page=2"));
+    }
+
+    #[test]
+    fn test_domain_lists() {
+        let filter = filter(UrlFilterConfig {
+            allowed_domains: vec!["example.com".to_string()],
+            denied_domains: vec!["*.ads.example.com".to_string()],
+            ..Default::default()
+        });
+
+        assert!(filter.allows("https://example.com/"));
+        assert!(filter.allows("https://docs.example.com/"));
+        assert_eq!(filter.check("https://badexample.com/"), Err(FilterRejection::DomainNotAllowed));
+        assert_eq!(
+            filter.check("https://eu.ads.example.com/"),
+            Err(FilterRejection::DeniedDomain)
+        );
+        assert_eq!(filter.check("mailto:someone@example.com"), Err(FilterRejection::InvalidUrl));
+    }
+
+    #[test]
+    fn test_same_site() {
+        let filter = filter(UrlFilterConfig {
+            same_site: true,
+            ..Default::default()
+        })
+        .with_sites(["https://www.bbc.co.uk/news", "https://alice.github.io/"]);
+
+        assert!(filter.allows("https://sport.bbc.co.uk/football"));
+        assert_eq!(filter.check("https://itv.co.uk/"), Err(FilterRejection::OffSite));
+        // * github.io is a public suffix: each user page is its own site
+        assert!(filter.allows("https://alice.github.io/blog"));
+        assert!(!filter.allows("https://bob.github.io/"));
+
+        assert!(filter.same_site("https://a.example.com/", "https://b.example.com/x"));
+        assert!(!filter.same_site("https://example.com/", "https://example.co.uk/"));
+    }
+
+    #[test]
+    fn test_site_of() {
+        let filter = filter(UrlFilterConfig::default());
+        assert_eq!(filter.site_of("www.example.com"), "example.com");
+        assert_eq!(filter.site_of("a.b.example.co.uk"), "example.co.uk");
+        assert_eq!(filter.site_of("127.0.0.1"), "127.0.0.1");
+        assert_eq!(filter.site_of("co.uk"), "co.uk");
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        let result = UrlFilter::new(&UrlFilterConfig {
+            allow: vec!["re:(".to_string()],
+            ..Default::default()
+        });
+        assert!(matches!(result, Err(UrlFilterError::InvalidPattern { .. })));
+    }
+
+    #[test]
+    fn test_missing_suffix_list() {
+        let result = UrlFilter::new(&UrlFilterConfig {
+            public_suffix_list: Some(PathBuf::from("/nonexistent/public_suffix_list.dat")),
+            ..Default::default()
+        });
+        assert!(matches!(result, Err(UrlFilterError::SuffixListIo { .. })));
+    }
+}
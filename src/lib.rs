@@ -4,3 +4,6 @@ pub mod engine;
 pub mod refinery;
 pub mod persistence;
 pub mod ops;
+pub mod orchestrator;
+#[cfg(any(feature = "grpc", feature = "http-api"))]
+pub mod server;
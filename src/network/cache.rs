@@ -0,0 +1,295 @@
+// * [FR-03] HTTP Cache Validators
+// * Remembers ETag/Last-Modified per URL so recrawls can issue conditional requests;
+// * a 304 means the page is unchanged and never reaches the Refinery again
+
+use crate::network::redirect::RedirectChain;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use reqwest::header::{HeaderMap, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+use thiserror::Error;
+use xxhash_rust::xxh64::xxh64;
+
+// * Redis key prefix for cache validators
+const CACHE_PREFIX: &str = "httpcache";
+
+// * Validators older than this are dropped by Redis (30 days)
+const DEFAULT_TTL_SECS: u64 = 30 * 24 * 3600;
+
+/// Boxed future returned by validator stores
+pub type CacheFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+#[derive(Debug, Error)]
+pub enum CacheError {
+    #[error("HTTP cache Redis operation failed: {0}")]
+    Redis(#[from] redis::RedisError),
+
+    #[error("Failed to (de)serialize cache entry: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// Validators a server returned for a URL
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    /// Reads `ETag` and `Last-Modified`; returns None when the response has neither
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let read = |name| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let validators = Self {
+            etag: read(ETAG),
+            last_modified: read(LAST_MODIFIED),
+        };
+        (!validators.is_empty()).then_some(validators)
+    }
+
+    /// Returns true when there is nothing to revalidate with
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+
+    /// Returns the `If-None-Match` / `If-Modified-Since` headers for a conditional request
+    pub fn conditional_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(value) = self.etag.as_deref().and_then(|v| HeaderValue::from_str(v).ok()) {
+            headers.insert(IF_NONE_MATCH, value);
+        }
+        if let Some(value) = self
+            .last_modified
+            .as_deref()
+            .and_then(|v| HeaderValue::from_str(v).ok())
+        {
+            headers.insert(IF_MODIFIED_SINCE, value);
+        }
+        headers
+    }
+}
+
+/// Result of a (possibly conditional) fast path fetch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConditionalFetch {
+    /// The server sent a body, with the validators to store for the next crawl
+    Modified {
+        body: String,
+        validators: Option<CacheValidators>,
+        /// Charset the body was transcoded from (None when the fetcher does not say)
+        encoding: Option<String>,
+        /// True when the body was cut at the fetcher's response size limit
+        truncated: bool,
+        /// Redirects followed to reach the body (None when served from the requested URL)
+        redirect: Option<RedirectChain>,
+    },
+    /// HTTP 304: the stored copy is still current
+    NotModified,
+}
+
+impl ConditionalFetch {
+    /// Returns the body of a modified response
+    pub fn into_body(self) -> Option<String> {
+        match self {
+            ConditionalFetch::Modified { body, .. } => Some(body),
+            ConditionalFetch::NotModified => None,
+        }
+    }
+}
+
+/// Persists cache validators between crawls
+pub trait ValidatorStore: Send + Sync {
+    /// Returns the validators stored for a URL
+    fn get<'a>(&'a self, url: &'a str) -> CacheFuture<'a, Result<Option<CacheValidators>, CacheError>>;
+
+    /// Stores the validators for a URL, replacing earlier ones
+    fn put<'a>(
+        &'a self,
+        url: &'a str,
+        validators: &'a CacheValidators,
+    ) -> CacheFuture<'a, Result<(), CacheError>>;
+}
+
+/// Keeps validators in process memory (single-run recrawls and tests)
+#[derive(Debug, Default)]
+pub struct InMemoryValidatorStore {
+    entries: Mutex<HashMap<String, CacheValidators>>,
+}
+
+impl InMemoryValidatorStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of URLs with stored validators
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ValidatorStore for InMemoryValidatorStore {
+    fn get<'a>(&'a self, url: &'a str) -> CacheFuture<'a, Result<Option<CacheValidators>, CacheError>> {
+        let validators = self.entries.lock().unwrap().get(url).cloned();
+        Box::pin(async move { Ok(validators) })
+    }
+
+    fn put<'a>(
+        &'a self,
+        url: &'a str,
+        validators: &'a CacheValidators,
+    ) -> CacheFuture<'a, Result<(), CacheError>> {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(url.to_string(), validators.clone());
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Redis value: the URL is kept to rule out hash collisions
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    url: String,
+    #[serde(flatten)]
+    validators: CacheValidators,
+}
+
+/// Stores validators in Redis so every worker and later crawls share them
+#[derive(Clone)]
+pub struct RedisValidatorStore {
+    redis: ConnectionManager,
+    ttl_secs: u64,
+}
+
+impl std::fmt::Debug for RedisValidatorStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisValidatorStore")
+            .field("ttl_secs", &self.ttl_secs)
+            .finish()
+    }
+}
+
+impl RedisValidatorStore {
+    /// Connects to Redis; entries live at `httpcache:<url hash>` for 30 days
+    pub async fn connect(redis_url: &str) -> Result<Self, CacheError> {
+        let client = redis::Client::open(redis_url)?;
+        Ok(Self {
+            redis: ConnectionManager::new(client).await?,
+            ttl_secs: DEFAULT_TTL_SECS,
+        })
+    }
+
+    /// Overrides how long validators are kept
+    pub fn with_ttl_secs(mut self, ttl_secs: u64) -> Self {
+        self.ttl_secs = ttl_secs.max(1);
+        self
+    }
+}
+
+impl ValidatorStore for RedisValidatorStore {
+    fn get<'a>(&'a self, url: &'a str) -> CacheFuture<'a, Result<Option<CacheValidators>, CacheError>> {
+        Box::pin(async move {
+            let mut redis = self.redis.clone();
+            let value: Option<String> = redis.get(cache_key(url)).await?;
+            let Some(value) = value else {
+                return Ok(None);
+            };
+            let entry: CacheEntry = serde_json::from_str(&value)?;
+            Ok((entry.url == url).then_some(entry.validators))
+        })
+    }
+
+    fn put<'a>(
+        &'a self,
+        url: &'a str,
+        validators: &'a CacheValidators,
+    ) -> CacheFuture<'a, Result<(), CacheError>> {
+        Box::pin(async move {
+            let value = serde_json::to_string(&CacheEntry {
+                url: url.to_string(),
+                validators: validators.clone(),
+            })?;
+            let mut redis = self.redis.clone();
+            redis
+                .set_ex::<_, _, ()>(cache_key(url), value, self.ttl_secs)
+                .await?;
+            Ok(())
+        })
+    }
+}
+
+// * Returns the Redis key for a URL's validators
+fn cache_key(url: &str) -> String {
+    format!("{}:{:016x}", CACHE_PREFIX, xxh64(url.as_bytes(), 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validators_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(CacheValidators::from_headers(&headers), None);
+
+        headers.insert(ETAG, HeaderValue::from_static("W/\"abc123\""));
+        headers.insert(
+            LAST_MODIFIED,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        let validators = CacheValidators::from_headers(&headers).unwrap();
+        assert_eq!(validators.etag.as_deref(), Some("W/\"abc123\""));
+
+        let conditional = validators.conditional_headers();
+        assert_eq!(conditional.get(IF_NONE_MATCH).unwrap(), "W/\"abc123\"");
+        assert_eq!(
+            conditional.get(IF_MODIFIED_SINCE).unwrap(),
+            "Wed, 21 Oct 2015 07:28:00 GMT"
+        );
+    }
+
+    #[test]
+    fn test_conditional_headers_skip_missing_validators() {
+        let validators = CacheValidators {
+            etag: None,
+            last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+        };
+        let headers = validators.conditional_headers();
+        assert!(headers.get(IF_NONE_MATCH).is_none());
+        assert!(headers.get(IF_MODIFIED_SINCE).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store() {
+        let store = InMemoryValidatorStore::new();
+        let validators = CacheValidators {
+            etag: Some("\"v1\"".to_string()),
+            last_modified: None,
+        };
+
+        assert_eq!(store.get("https://example.com/").await.unwrap(), None);
+        store.put("https://example.com/", &validators).await.unwrap();
+        assert_eq!(store.get("https://example.com/").await.unwrap(), Some(validators));
+        assert_eq!(store.len(), 1);
+    }
+
+    #[test]
+    fn test_cache_key_is_stable() {
+        assert_eq!(cache_key("https://example.com/"), cache_key("https://example.com/"));
+        assert_ne!(cache_key("https://example.com/"), cache_key("https://example.com/a"));
+        assert!(cache_key("https://example.com/").starts_with("httpcache:"));
+    }
+}
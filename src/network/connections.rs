@@ -0,0 +1,141 @@
+// * [FR-03] Connection Pool Statistics
+// * reqwest does not say whether a request rode a pooled connection, but hyper resolves the
+// * host once per new connection. Counting resolutions per host tells new connections from
+// * reused ones; the fast path reports each request's protocol and time to headers with it.
+
+use crate::network::dns::DnsCache;
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+/// Connection pool settings for fast path clients
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PoolConfig {
+    /// Idle connections kept per host (None leaves reqwest's unbounded default)
+    pub max_idle_per_host: Option<usize>,
+    /// Seconds an idle connection is kept before it is closed
+    pub idle_timeout_secs: u64,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_idle_per_host: None,
+            idle_timeout_secs: 90,
+        }
+    }
+}
+
+/// Resolver that counts the connections a client opens per host
+///
+/// Wraps the shared DNS cache when there is one, otherwise asks the system resolver.
+/// Cloning is cheap and clones share the counts.
+#[derive(Clone, Default)]
+pub struct ConnectionTracker {
+    dns: Option<Arc<DnsCache>>,
+    // * Host -> connections opened (resolutions requested by the connector)
+    opened: Arc<Mutex<HashMap<String, u64>>>,
+}
+
+impl ConnectionTracker {
+    pub fn new(dns: Option<Arc<DnsCache>>) -> Self {
+        Self {
+            dns,
+            opened: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Returns the connections opened to a host so far
+    ///
+    /// IP literals skip resolution and are never counted.
+    pub fn opened(&self, host: &str) -> u64 {
+        self.opened
+            .lock()
+            .unwrap()
+            .get(&host.to_ascii_lowercase())
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+impl Resolve for ConnectionTracker {
+    fn resolve(&self, name: Name) -> Resolving {
+        *self
+            .opened
+            .lock()
+            .unwrap()
+            .entry(name.as_str().to_ascii_lowercase())
+            .or_default() += 1;
+
+        if let Some(dns) = &self.dns {
+            return dns.resolve(name);
+        }
+        Box::pin(async move {
+            // * Port 0 is replaced by the connector with the URL's port
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+impl std::fmt::Debug for ConnectionTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnectionTracker")
+            .field("dns", &self.dns)
+            .field("hosts", &self.opened.lock().unwrap().len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    // * Answers every request on the first `connections` accepted connections with a keep-alive 200
+    fn serve(connections: usize) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(connections) {
+                let mut stream = stream.unwrap();
+                std::thread::spawn(move || {
+                    let mut buf = [0u8; 4096];
+                    while let Ok(n) = stream.read(&mut buf) {
+                        if n == 0 {
+                            break;
+                        }
+                        let reply = "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok";
+                        if stream.write_all(reply.as_bytes()).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        port
+    }
+
+    #[tokio::test]
+    async fn test_pooled_requests_open_one_connection() {
+        let port = serve(1);
+        let tracker = ConnectionTracker::new(None);
+        let client = reqwest::Client::builder()
+            .dns_resolver(Arc::new(tracker.clone()))
+            .build()
+            .unwrap();
+
+        for _ in 0..3 {
+            let resp = client.get(format!("http://localhost:{port}/")).send().await.unwrap();
+            assert_eq!(resp.text().await.unwrap(), "ok");
+        }
+        assert_eq!(tracker.opened("LOCALHOST"), 1);
+        assert_eq!(tracker.opened("example.com"), 0);
+    }
+}
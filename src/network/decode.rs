@@ -0,0 +1,142 @@
+// * [FR-01] Response Body Decoding
+// * Transcodes response bytes to UTF-8 before they reach the Refinery.
+// * Charset precedence follows the HTML spec: BOM, then Content-Type, then <meta> prescan.
+
+use encoding_rs::{Encoding, UTF_8, WINDOWS_1252};
+use regex::bytes::Regex;
+use std::sync::LazyLock;
+
+// * How far into the body to look for a <meta> charset (the spec prescans 1024 bytes;
+// * real pages often put it after long inline scripts or comments)
+const META_PRESCAN_BYTES: usize = 4096;
+
+// * Matches both <meta charset="x"> and <meta http-equiv="Content-Type" content="...; charset=x">
+static META_CHARSET: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i-u)<meta\s[^>]*?charset\s*=\s*["']?\s*([a-z0-9_:.\-]+)"#).unwrap()
+});
+
+/// Where the charset of a body came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharsetSource {
+    /// Byte order mark at the start of the body
+    Bom,
+    /// `charset` parameter of the Content-Type header
+    Header,
+    /// `<meta charset>` or `<meta http-equiv="Content-Type">` in the document
+    Meta,
+    /// Nothing declared: UTF-8 if the bytes are valid UTF-8, windows-1252 otherwise
+    Default,
+}
+
+/// A response body transcoded to UTF-8
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedBody {
+    pub text: String,
+    /// Canonical name of the source encoding (e.g. "windows-1251", "Shift_JIS")
+    pub encoding: &'static str,
+    pub source: CharsetSource,
+    /// True when malformed sequences were replaced with U+FFFD
+    pub had_errors: bool,
+}
+
+/// Decodes a response body to UTF-8
+///
+/// `content_type` is the raw Content-Type header value, if any.
+pub fn decode_body(bytes: &[u8], content_type: Option<&str>) -> DecodedBody {
+    let (encoding, source) = detect_encoding(bytes, content_type);
+    let (text, had_errors) = encoding.decode_with_bom_removal(bytes);
+    DecodedBody {
+        text: text.into_owned(),
+        encoding: encoding.name(),
+        source,
+        had_errors,
+    }
+}
+
+/// Picks the encoding of a body, and where it was declared
+pub fn detect_encoding(bytes: &[u8], content_type: Option<&str>) -> (&'static Encoding, CharsetSource) {
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        return (encoding, CharsetSource::Bom);
+    }
+    if let Some(encoding) = content_type.and_then(header_charset) {
+        return (encoding, CharsetSource::Header);
+    }
+    if let Some(encoding) = meta_charset(bytes) {
+        return (encoding, CharsetSource::Meta);
+    }
+
+    let fallback = if std::str::from_utf8(bytes).is_ok() { UTF_8 } else { WINDOWS_1252 };
+    (fallback, CharsetSource::Default)
+}
+
+/// Reads the `charset` parameter of a Content-Type value
+fn header_charset(content_type: &str) -> Option<&'static Encoding> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        if !name.trim().eq_ignore_ascii_case("charset") {
+            return None;
+        }
+        Encoding::for_label(value.trim().trim_matches(['"', '\'']).as_bytes())
+    })
+}
+
+/// Finds a charset declared in a `<meta>` tag near the start of the document
+fn meta_charset(bytes: &[u8]) -> Option<&'static Encoding> {
+    let head = &bytes[..bytes.len().min(META_PRESCAN_BYTES)];
+    let label = META_CHARSET.captures(head)?.get(1)?.as_bytes();
+    // * A document that decodes its own <meta> as ASCII cannot be UTF-16 (the spec maps it to UTF-8)
+    Encoding::for_label(label).map(Encoding::output_encoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use encoding_rs::{SHIFT_JIS, WINDOWS_1251};
+
+    #[test]
+    fn test_header_charset() {
+        let (bytes, _, _) = WINDOWS_1251.encode("<p>Привет, мир</p>");
+        let decoded = decode_body(&bytes, Some("text/html; charset=\"Windows-1251\""));
+        assert_eq!(decoded.text, "<p>Привет, мир</p>");
+        assert_eq!(decoded.encoding, "windows-1251");
+        assert_eq!(decoded.source, CharsetSource::Header);
+        assert!(!decoded.had_errors);
+    }
+
+    #[test]
+    fn test_meta_charset() {
+        let html = r#"<html><head><meta http-equiv="Content-Type" content="text/html; charset=Shift_JIS"></head><body>東京の天気</body></html>"#;
+        let (bytes, _, _) = SHIFT_JIS.encode(html);
+        let decoded = decode_body(&bytes, Some("text/html"));
+        assert_eq!(decoded.text, html);
+        assert_eq!(decoded.encoding, "Shift_JIS");
+        assert_eq!(decoded.source, CharsetSource::Meta);
+
+        let (bytes, _, _) = WINDOWS_1251.encode("<meta charset=koi8-r><p>x</p>");
+        assert_eq!(detect_encoding(&bytes, None).0.name(), "KOI8-R");
+    }
+
+    #[test]
+    fn test_bom_wins_over_declarations() {
+        let mut bytes = vec![0xEF, 0xBB, 0xBF];
+        bytes.extend_from_slice("<meta charset=\"windows-1252\">café".as_bytes());
+        let decoded = decode_body(&bytes, Some("text/html; charset=iso-8859-1"));
+        assert_eq!(decoded.source, CharsetSource::Bom);
+        assert_eq!(decoded.encoding, "UTF-8");
+        assert!(decoded.text.ends_with("café"));
+    }
+
+    #[test]
+    fn test_undeclared_fallback() {
+        let decoded = decode_body("naïve".as_bytes(), None);
+        assert_eq!((decoded.encoding, decoded.source), ("UTF-8", CharsetSource::Default));
+
+        // * 0xE9 alone is invalid UTF-8; as windows-1252 it is "é"
+        let decoded = decode_body(b"caf\xe9", None);
+        assert_eq!(decoded.encoding, "windows-1252");
+        assert_eq!(decoded.text, "café");
+
+        // * UTF-16 in <meta> is read as UTF-8
+        assert_eq!(meta_charset(b"<meta charset=utf-16>"), Some(UTF_8));
+    }
+}
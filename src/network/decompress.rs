@@ -0,0 +1,205 @@
+// * [FR-01] Response Body Decompression
+// * Undoes the Content-Encoding of fast path responses (gzip, deflate, br, zstd).
+// * Output is capped so a small compressed body cannot expand into gigabytes of memory.
+
+use std::io::{self, Read};
+use thiserror::Error;
+
+/// Default cap on a decompressed response body (32 MiB)
+pub const DEFAULT_MAX_DECOMPRESSED_BYTES: usize = 32 * 1024 * 1024;
+
+// * zlib streams start with a CMF byte whose low nibble is 8 (deflate)
+const ZLIB_METHOD_DEFLATE: u8 = 0x08;
+
+/// A single `Content-Encoding` coding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentCoding {
+    Identity,
+    Gzip,
+    Deflate,
+    Brotli,
+    Zstd,
+}
+
+impl ContentCoding {
+    /// Parses a coding token (case-insensitive, including legacy aliases)
+    pub fn from_token(token: &str) -> Option<Self> {
+        match token.trim().to_ascii_lowercase().as_str() {
+            "" | "identity" | "none" => Some(ContentCoding::Identity),
+            "gzip" | "x-gzip" => Some(ContentCoding::Gzip),
+            "deflate" => Some(ContentCoding::Deflate),
+            "br" => Some(ContentCoding::Brotli),
+            "zstd" => Some(ContentCoding::Zstd),
+            _ => None,
+        }
+    }
+
+    /// Canonical token, used as a telemetry label
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentCoding::Identity => "identity",
+            ContentCoding::Gzip => "gzip",
+            ContentCoding::Deflate => "deflate",
+            ContentCoding::Brotli => "br",
+            ContentCoding::Zstd => "zstd",
+        }
+    }
+}
+
+/// Errors raised while undoing a Content-Encoding
+#[derive(Error, Debug)]
+pub enum DecompressError {
+    #[error("Unsupported content encoding: {0}")]
+    Unsupported(String),
+
+    #[error("Decompressed body exceeds {0} bytes")]
+    TooLarge(usize),
+
+    #[error("Corrupt {coding} body: {source}")]
+    Corrupt {
+        coding: &'static str,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// Parses a Content-Encoding header into the codings applied, in order
+pub fn parse_content_encoding(header: Option<&str>) -> Result<Vec<ContentCoding>, DecompressError> {
+    let Some(header) = header else {
+        return Ok(Vec::new());
+    };
+    header
+        .split(',')
+        .map(|token| {
+            ContentCoding::from_token(token)
+                .ok_or_else(|| DecompressError::Unsupported(token.trim().to_string()))
+        })
+        .filter(|coding| !matches!(coding, Ok(ContentCoding::Identity)))
+        .collect()
+}
+
+/// Decompresses a body according to its Content-Encoding header
+///
+/// Codings are undone in reverse order of application. Fails with `TooLarge` once the
+/// output of any stage would exceed `max_bytes`.
+pub fn decompress_body(
+    bytes: &[u8],
+    content_encoding: Option<&str>,
+    max_bytes: usize,
+) -> Result<Vec<u8>, DecompressError> {
+    let codings = parse_content_encoding(content_encoding)?;
+    let mut body = bytes.to_vec();
+    for coding in codings.into_iter().rev() {
+        body = decode(coding, &body, max_bytes)?;
+    }
+    if body.len() > max_bytes {
+        return Err(DecompressError::TooLarge(max_bytes));
+    }
+    Ok(body)
+}
+
+/// Undoes a single coding
+fn decode(coding: ContentCoding, bytes: &[u8], max_bytes: usize) -> Result<Vec<u8>, DecompressError> {
+    match coding {
+        ContentCoding::Identity => Ok(bytes.to_vec()),
+        ContentCoding::Gzip => read_capped(flate2::read::MultiGzDecoder::new(bytes), coding, max_bytes),
+        // * "deflate" should be zlib-wrapped, but some servers send raw deflate streams
+        ContentCoding::Deflate if bytes.first().is_some_and(|b| b & 0x0F == ZLIB_METHOD_DEFLATE) => {
+            read_capped(flate2::read::ZlibDecoder::new(bytes), coding, max_bytes)
+        }
+        ContentCoding::Deflate => read_capped(flate2::read::DeflateDecoder::new(bytes), coding, max_bytes),
+        ContentCoding::Brotli => read_capped(brotli::Decompressor::new(bytes, 4096), coding, max_bytes),
+        ContentCoding::Zstd => {
+            let decoder = zstd::stream::read::Decoder::new(bytes).map_err(|source| corrupt(coding, source))?;
+            read_capped(decoder, coding, max_bytes)
+        }
+    }
+}
+
+/// Reads a decoder to the end, stopping one byte past the cap
+fn read_capped<R: Read>(reader: R, coding: ContentCoding, max_bytes: usize) -> Result<Vec<u8>, DecompressError> {
+    let mut out = Vec::new();
+    reader
+        .take(max_bytes as u64 + 1)
+        .read_to_end(&mut out)
+        .map_err(|source| corrupt(coding, source))?;
+    if out.len() > max_bytes {
+        return Err(DecompressError::TooLarge(max_bytes));
+    }
+    Ok(out)
+}
+
+fn corrupt(coding: ContentCoding, source: io::Error) -> DecompressError {
+    DecompressError::Corrupt { coding: coding.as_str(), source }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::{DeflateEncoder, GzEncoder, ZlibEncoder};
+    use flate2::Compression;
+    use std::io::Write;
+
+    const BODY: &[u8] = b"<html><body><p>Compressed content, repeated. Compressed content, repeated.</p></body></html>";
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_all_codings_round_trip() {
+        let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+        zlib.write_all(BODY).unwrap();
+        let mut raw = DeflateEncoder::new(Vec::new(), Compression::default());
+        raw.write_all(BODY).unwrap();
+        let mut br = Vec::new();
+        brotli::BrotliCompress(&mut &BODY[..], &mut br, &Default::default()).unwrap();
+        let zst = zstd::encode_all(BODY, 3).unwrap();
+
+        let cases = [
+            ("gzip", gzip(BODY)),
+            ("X-GZIP", gzip(BODY)),
+            ("deflate", zlib.finish().unwrap()),
+            ("deflate", raw.finish().unwrap()),
+            ("br", br),
+            ("zstd", zst),
+            ("identity", BODY.to_vec()),
+        ];
+        for (coding, bytes) in cases {
+            let body = decompress_body(&bytes, Some(coding), DEFAULT_MAX_DECOMPRESSED_BYTES).unwrap();
+            assert_eq!(body, BODY, "coding {}", coding);
+        }
+        assert_eq!(decompress_body(BODY, None, 1024).unwrap(), BODY);
+    }
+
+    #[test]
+    fn test_stacked_codings_are_undone_in_reverse() {
+        let bytes = zstd::encode_all(&gzip(BODY)[..], 3).unwrap();
+        let body = decompress_body(&bytes, Some("gzip, zstd"), 1024).unwrap();
+        assert_eq!(body, BODY);
+    }
+
+    #[test]
+    fn test_decompression_bomb_is_rejected() {
+        let bomb = gzip(&vec![0u8; 10 * 1024 * 1024]);
+        assert!(bomb.len() < 64 * 1024);
+        assert!(matches!(
+            decompress_body(&bomb, Some("gzip"), 1024 * 1024),
+            Err(DecompressError::TooLarge(limit)) if limit == 1024 * 1024
+        ));
+    }
+
+    #[test]
+    fn test_bad_input() {
+        assert!(matches!(
+            decompress_body(BODY, Some("gzip"), 1024),
+            Err(DecompressError::Corrupt { coding: "gzip", .. })
+        ));
+        assert!(matches!(
+            decompress_body(BODY, Some("compress"), 1024),
+            Err(DecompressError::Unsupported(coding)) if coding == "compress"
+        ));
+    }
+}
@@ -0,0 +1,339 @@
+// * [FR-03] DNS Resolver Cache
+// * Every fetch builds a fresh reqwest client, so without a shared cache each request pays a
+// * getaddrinfo call. Answers are cached for `ttl_secs`, failures for `negative_ttl_secs`, and
+// * consecutive failures are counted per host so the crawler can feed them to the circuit breaker.
+
+use crate::ops;
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+/// Boxed future returned by host lookups
+pub type LookupFuture = Pin<Box<dyn Future<Output = io::Result<Vec<IpAddr>>> + Send>>;
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+#[error("DNS lookup for {host} failed: {message}")]
+pub struct DnsError {
+    pub host: String,
+    pub message: String,
+}
+
+/// DNS cache settings
+///
+/// The system resolver does not report record TTLs, so answers are kept for `ttl_secs`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DnsConfig {
+    /// Cache lookups (false resolves every request through the system resolver)
+    pub enabled: bool,
+    /// Seconds a successful answer is reused
+    pub ttl_secs: u64,
+    /// Seconds a failed lookup is answered from the cache without asking again
+    pub negative_ttl_secs: u64,
+    /// Hosts kept; the entries closest to expiry are dropped first
+    pub max_entries: usize,
+}
+
+impl Default for DnsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_secs: 300,
+            negative_ttl_secs: 30,
+            max_entries: 10_000,
+        }
+    }
+}
+
+/// Resolves a host name to addresses
+pub trait HostLookup: Send + Sync {
+    fn lookup(&self, host: String) -> LookupFuture;
+}
+
+/// Lookup through the operating system resolver (getaddrinfo)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemLookup;
+
+impl HostLookup for SystemLookup {
+    fn lookup(&self, host: String) -> LookupFuture {
+        Box::pin(async move {
+            let addrs = tokio::net::lookup_host((host.as_str(), 0)).await?;
+            Ok(addrs.map(|addr| addr.ip()).collect())
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+enum CachedAnswer {
+    Resolved(Arc<[IpAddr]>),
+    Failed(DnsError),
+}
+
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    answer: CachedAnswer,
+    expires: Instant,
+}
+
+/// Caching resolver shared by every HTTP client of a fetcher
+///
+/// Cloning is cheap and clones share the cache. Plugs into reqwest via `ClientBuilder::dns_resolver`.
+///
+/// # Example
+/// ```ignore
+/// let dns = Arc::new(DnsCache::new(DnsConfig::default()));
+/// let client = reqwest::Client::builder().dns_resolver(Arc::clone(&dns)).build()?;
+/// ```
+#[derive(Clone)]
+pub struct DnsCache {
+    config: DnsConfig,
+    lookup: Arc<dyn HostLookup>,
+    entries: Arc<Mutex<HashMap<String, CacheEntry>>>,
+    // * Host -> consecutive failed lookups, cleared by the next success
+    failures: Arc<Mutex<HashMap<String, u32>>>,
+}
+
+impl DnsCache {
+    pub fn new(config: DnsConfig) -> Self {
+        Self {
+            config,
+            lookup: Arc::new(SystemLookup),
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            failures: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Replaces the system resolver (e.g. with a fake in tests)
+    pub fn with_lookup(mut self, lookup: Arc<dyn HostLookup>) -> Self {
+        self.lookup = lookup;
+        self
+    }
+
+    pub fn config(&self) -> &DnsConfig {
+        &self.config
+    }
+
+    /// Resolves a host, answering from the cache while the entry is fresh
+    pub async fn resolve_host(&self, host: &str) -> Result<Arc<[IpAddr]>, DnsError> {
+        let host = host.to_ascii_lowercase();
+        if let Some(answer) = self.cached(&host) {
+            return match answer {
+                CachedAnswer::Resolved(addrs) => {
+                    ops::record_dns_lookup("hit");
+                    Ok(addrs)
+                }
+                CachedAnswer::Failed(error) => {
+                    ops::record_dns_lookup("negative_hit");
+                    Err(error)
+                }
+            };
+        }
+
+        let result = match self.lookup.lookup(host.clone()).await {
+            Ok(addrs) if !addrs.is_empty() => Ok(Arc::<[IpAddr]>::from(addrs)),
+            Ok(_) => Err("no addresses".to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        match result {
+            Ok(addrs) => {
+                ops::record_dns_lookup("resolved");
+                self.failures.lock().unwrap().remove(&host);
+                self.store(&host, CachedAnswer::Resolved(Arc::clone(&addrs)), self.config.ttl_secs);
+                Ok(addrs)
+            }
+            Err(message) => {
+                ops::record_dns_lookup("failed");
+                *self.failures.lock().unwrap().entry(host.clone()).or_default() += 1;
+                let error = DnsError { host: host.clone(), message };
+                self.store(&host, CachedAnswer::Failed(error.clone()), self.config.negative_ttl_secs);
+                Err(error)
+            }
+        }
+    }
+
+    /// Returns the consecutive failed lookups for a host (0 after a success)
+    pub fn failure_count(&self, host: &str) -> u32 {
+        self.failures
+            .lock()
+            .unwrap()
+            .get(&host.to_ascii_lowercase())
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns the number of cached hosts, including expired entries not yet replaced
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn cached(&self, host: &str) -> Option<CachedAnswer> {
+        if !self.config.enabled {
+            return None;
+        }
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(host)
+            .filter(|entry| entry.expires > Instant::now())
+            .map(|entry| entry.answer.clone())
+    }
+
+    fn store(&self, host: &str, answer: CachedAnswer, ttl_secs: u64) {
+        if !self.config.enabled || ttl_secs == 0 || self.config.max_entries == 0 {
+            return;
+        }
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= self.config.max_entries && !entries.contains_key(host) {
+            entries.retain(|_, entry| entry.expires > now);
+            if entries.len() >= self.config.max_entries {
+                let soonest = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.expires)
+                    .map(|(host, _)| host.clone());
+                if let Some(soonest) = soonest {
+                    entries.remove(&soonest);
+                }
+            }
+        }
+        entries.insert(
+            host.to_string(),
+            CacheEntry {
+                answer,
+                expires: now + Duration::from_secs(ttl_secs),
+            },
+        );
+    }
+}
+
+impl Resolve for DnsCache {
+    fn resolve(&self, name: Name) -> Resolving {
+        let cache = self.clone();
+        Box::pin(async move {
+            let addrs = cache.resolve_host(name.as_str()).await?;
+            // * Port 0 is replaced by the connector with the URL's port
+            let addrs: Vec<SocketAddr> = addrs.iter().map(|ip| SocketAddr::new(*ip, 0)).collect();
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+impl std::fmt::Debug for DnsCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DnsCache")
+            .field("config", &self.config)
+            .field("entries", &self.len())
+            .finish()
+    }
+}
+
+/// Returns true when an error (or one of its sources) is a failed `DnsCache` lookup
+pub fn is_dns_failure(error: &(dyn std::error::Error + 'static)) -> bool {
+    let mut current = Some(error);
+    while let Some(error) = current {
+        if error.is::<DnsError>() {
+            return true;
+        }
+        current = error.source();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // * Resolves hosts starting with "ok" to 127.0.0.1 and fails the rest, counting calls
+    #[derive(Default)]
+    struct FakeLookup {
+        calls: AtomicUsize,
+    }
+
+    impl HostLookup for FakeLookup {
+        fn lookup(&self, host: String) -> LookupFuture {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move {
+                if host.starts_with("ok") {
+                    Ok(vec![IpAddr::from([127, 0, 0, 1])])
+                } else {
+                    Err(io::Error::new(io::ErrorKind::NotFound, "no such host"))
+                }
+            })
+        }
+    }
+
+    fn cache(config: DnsConfig) -> (DnsCache, Arc<FakeLookup>) {
+        let lookup = Arc::new(FakeLookup::default());
+        (DnsCache::new(config).with_lookup(lookup.clone()), lookup)
+    }
+
+    #[tokio::test]
+    async fn test_answers_and_failures_are_cached() {
+        let (dns, lookup) = cache(DnsConfig::default());
+
+        assert_eq!(dns.resolve_host("ok.example.com").await.unwrap().len(), 1);
+        assert!(dns.resolve_host("OK.example.com").await.is_ok());
+        assert_eq!(lookup.calls.load(Ordering::SeqCst), 1);
+
+        let error = dns.resolve_host("missing.example.com").await.unwrap_err();
+        assert_eq!(error.host, "missing.example.com");
+        assert!(dns.resolve_host("missing.example.com").await.is_err());
+        assert_eq!(lookup.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(dns.failure_count("missing.example.com"), 1);
+        assert_eq!(dns.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_expired_failures_are_retried_and_counted() {
+        let (dns, lookup) = cache(DnsConfig {
+            negative_ttl_secs: 0,
+            ..Default::default()
+        });
+
+        for _ in 0..3 {
+            assert!(dns.resolve_host("missing.example.com").await.is_err());
+        }
+        assert_eq!(lookup.calls.load(Ordering::SeqCst), 3);
+        assert_eq!(dns.failure_count("missing.example.com"), 3);
+        assert_eq!(dns.failure_count("ok.example.com"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_full_cache_drops_entries_nearest_expiry() {
+        let (dns, _) = cache(DnsConfig {
+            max_entries: 2,
+            ..Default::default()
+        });
+        dns.resolve_host("gone.example.com").await.unwrap_err();
+        dns.resolve_host("ok-a.example.com").await.unwrap();
+        dns.resolve_host("ok-b.example.com").await.unwrap();
+
+        // * The negative entry expires first, so it made room
+        assert_eq!(dns.len(), 2);
+        assert!(dns.cached("gone.example.com").is_none());
+        assert!(dns.cached("ok-a.example.com").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_reqwest_errors_expose_dns_failures() {
+        let (dns, _) = cache(DnsConfig::default());
+        let client = reqwest::Client::builder().dns_resolver(Arc::new(dns)).build().unwrap();
+
+        let error = client.get("http://missing.example.com/").send().await.unwrap_err();
+        assert!(is_dns_failure(&error));
+        assert!(!is_dns_failure(&io::Error::other("refused")));
+    }
+}
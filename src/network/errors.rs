@@ -1,3 +1,8 @@
+use crate::network::decompress::DecompressError;
+use crate::network::dns::is_dns_failure;
+use crate::network::preflight::PreflightSkip;
+use crate::network::redirect::RedirectError;
+use std::time::Duration;
 use thiserror::Error;
 
 // * Unified Error type for the Network Layer.
@@ -12,9 +17,53 @@ pub enum NetworkError {
     #[error("HTTP {0} Forbidden/Blocked")]
     HardBan(u16),
 
+    // * 429, or 503 with Retry-After; `retry_after` is the server's requested wait
+    #[error("HTTP {status} Throttled (retry after {retry_after:?})")]
+    Throttled {
+        status: u16,
+        retry_after: Option<Duration>,
+    },
+
     #[error("Empty response body (< {0} bytes)")]
     EmptyResponse(usize),
     
     #[error("Invalid URL")]
     InvalidUrl,
+
+    #[error("Decompression failed: {0}")]
+    Decompression(#[from] DecompressError),
+
+    #[error("Redirect rejected: {0}")]
+    Redirect(#[from] RedirectError),
+
+    #[error("Skipped by HEAD preflight: {0}")]
+    Preflight(#[from] PreflightSkip),
+}
+
+impl NetworkError {
+    /// Returns true when the host could not be resolved by the `DnsCache`
+    pub fn is_dns_failure(&self) -> bool {
+        matches!(self, NetworkError::Reqwest(e) if is_dns_failure(e))
+    }
+
+    /// Short label for grouping failures in reports (e.g. `http_403`, `timeout`)
+    pub fn kind(&self) -> String {
+        match self {
+            NetworkError::Reqwest(e) if e.is_timeout() => "timeout".to_string(),
+            NetworkError::Reqwest(e) if is_dns_failure(e) => "dns".to_string(),
+            NetworkError::Reqwest(e) if e.is_connect() => "connect".to_string(),
+            NetworkError::Reqwest(e) => match e.status() {
+                Some(status) => format!("http_{}", status.as_u16()),
+                None => "request".to_string(),
+            },
+            NetworkError::SoftBan(_) => "soft_ban".to_string(),
+            NetworkError::HardBan(status) | NetworkError::Throttled { status, .. } => format!("http_{}", status),
+            NetworkError::EmptyResponse(_) => "empty_response".to_string(),
+            NetworkError::InvalidUrl => "invalid_url".to_string(),
+            NetworkError::Decompression(DecompressError::TooLarge(_)) => "body_too_large".to_string(),
+            NetworkError::Decompression(_) => "decompression".to_string(),
+            NetworkError::Redirect(e) => format!("redirect_{}", e.as_str()),
+            NetworkError::Preflight(skip) => format!("preflight_{}", skip.as_str()),
+        }
+    }
 }
\ No newline at end of file
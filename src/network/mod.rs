@@ -2,3 +2,11 @@ pub mod identity;
 pub mod errors;
 pub mod client;
 pub mod proxy;
+pub mod cache;
+pub mod decode;
+pub mod decompress;
+pub mod dns;
+pub mod connections;
+pub mod redirect;
+pub mod preflight;
+pub mod retry;
@@ -0,0 +1,158 @@
+// * [FR-03] HEAD Preflight
+// * URLs that may not be pages (downloads, media, unknown extensions) are checked with a HEAD
+// * request first, so videos, archives and oversized files are skipped without downloading them.
+
+use reqwest::header::{HeaderMap, CONTENT_LENGTH, CONTENT_TYPE};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Default size above which a URL is skipped (10 MiB)
+pub const DEFAULT_PREFLIGHT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Why a preflight kept a URL from being fetched
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum PreflightSkip {
+    #[error("Content-Type {0} is skipped")]
+    ContentType(String),
+
+    #[error("Content-Length {0} exceeds the preflight limit")]
+    TooLarge(u64),
+}
+
+impl PreflightSkip {
+    /// Metric and report label
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PreflightSkip::ContentType(_) => "content_type",
+            PreflightSkip::TooLarge(_) => "too_large",
+        }
+    }
+}
+
+/// HEAD preflight settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PreflightConfig {
+    /// Send a HEAD request before fetching URLs that may not be pages
+    pub enabled: bool,
+    /// Path extensions fetched without a preflight; paths without an extension never need one
+    pub page_extensions: Vec<String>,
+    /// Content-Type prefixes skipped (e.g. "video/" or "application/zip")
+    pub skip_content_types: Vec<String>,
+    /// Largest Content-Length fetched
+    pub max_content_length: u64,
+}
+
+impl Default for PreflightConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            page_extensions: ["html", "htm", "xhtml", "php", "asp", "aspx", "jsp", "cfm", "shtml"]
+                .map(String::from)
+                .to_vec(),
+            skip_content_types: [
+                "video/",
+                "audio/",
+                "image/",
+                "font/",
+                "application/zip",
+                "application/x-zip-compressed",
+                "application/gzip",
+                "application/x-tar",
+                "application/x-7z-compressed",
+                "application/x-rar-compressed",
+                "application/octet-stream",
+            ]
+            .map(String::from)
+            .to_vec(),
+            max_content_length: DEFAULT_PREFLIGHT_MAX_BYTES,
+        }
+    }
+}
+
+impl PreflightConfig {
+    /// Returns true when the URL should be checked with HEAD before it is fetched
+    pub fn applies_to(&self, url: &str) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let Ok(url) = Url::parse(url) else {
+            return false;
+        };
+        let file = url.path().rsplit('/').next().unwrap_or_default();
+        match file.rsplit_once('.') {
+            Some((_, extension)) if !extension.is_empty() => !self
+                .page_extensions
+                .iter()
+                .any(|page| page.eq_ignore_ascii_case(extension)),
+            _ => false,
+        }
+    }
+
+    /// Checks HEAD response headers; missing headers never cause a skip
+    pub fn check(&self, headers: &HeaderMap) -> Result<(), PreflightSkip> {
+        if let Some(content_type) = headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok()) {
+            let media_type = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+            if self
+                .skip_content_types
+                .iter()
+                .any(|skipped| media_type.starts_with(&skipped.to_ascii_lowercase()))
+            {
+                return Err(PreflightSkip::ContentType(media_type));
+            }
+        }
+        let length = headers
+            .get(CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<u64>().ok());
+        match length {
+            Some(length) if length > self.max_content_length => Err(PreflightSkip::TooLarge(length)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(content_type: &str, length: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_str(content_type).unwrap());
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_str(length).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_only_unknown_extensions_are_preflighted() {
+        let config = PreflightConfig {
+            enabled: true,
+            ..Default::default()
+        };
+
+        assert!(config.applies_to("https://example.com/files/report.pdf"));
+        assert!(config.applies_to("https://example.com/media/clip.MP4?dl=1"));
+        assert!(!config.applies_to("https://example.com/docs/guide"));
+        assert!(!config.applies_to("https://example.com/index.HTML"));
+        assert!(!config.applies_to("https://example.com/v1.2/"));
+        assert!(!PreflightConfig::default().applies_to("https://example.com/clip.mp4"));
+    }
+
+    #[test]
+    fn test_check_skips_media_and_large_bodies() {
+        let config = PreflightConfig::default();
+
+        assert_eq!(config.check(&headers("text/html; charset=utf-8", "2048")), Ok(()));
+        assert_eq!(
+            config.check(&headers("Video/MP4", "100")),
+            Err(PreflightSkip::ContentType("video/mp4".to_string()))
+        );
+        assert_eq!(
+            config.check(&headers("application/pdf", "20971520")),
+            Err(PreflightSkip::TooLarge(20_971_520))
+        );
+        assert_eq!(config.check(&HeaderMap::new()), Ok(()));
+    }
+}
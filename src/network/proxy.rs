@@ -1,6 +1,16 @@
+use crate::engine::ban_detector::BanDetector;
+use crate::engine::fingerprint::BrowserProfile;
+use crate::network::cache::{CacheValidators, ConditionalFetch};
 use crate::network::client::FastClient;
+use crate::network::client::DEFAULT_MAX_RESPONSE_BYTES;
+use crate::network::connections::PoolConfig;
+use crate::network::decompress::DEFAULT_MAX_DECOMPRESSED_BYTES;
+use crate::network::dns::DnsCache;
+use crate::network::preflight::PreflightConfig;
+use crate::network::redirect::RedirectConfig;
 use crate::network::errors::NetworkError;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use tokio::sync::RwLock;
 
 // * The Ladder Levels
@@ -17,8 +27,22 @@ pub struct ProxyManager {
     tier2_proxies: Vec<String>,
     t1_idx: Arc<RwLock<usize>>,
     t2_idx: Arc<RwLock<usize>>,
+    // * Shared so per-domain page size history survives across tiers
+    detector: Arc<BanDetector>,
+    max_decompressed_bytes: usize,
+    max_response_bytes: usize,
+    // * Shared by every tier's client
+    dns: Option<Arc<DnsCache>>,
+    pool: PoolConfig,
+    redirect: RedirectConfig,
+    preflight: PreflightConfig,
+    // * One client per proxy and browser profile, so their connection pools are reused
+    clients: Mutex<HashMap<ClientKey, Arc<FastClient>>>,
 }
 
+// * Proxy URL, User-Agent and Accept-Language of a cached client
+type ClientKey = (Option<String>, String, String);
+
 impl ProxyManager {
     pub fn new(tier1: Vec<String>, tier2: Vec<String>) -> Self {
         Self {
@@ -26,12 +50,78 @@ impl ProxyManager {
             tier2_proxies: tier2,
             t1_idx: Arc::new(RwLock::new(0)),
             t2_idx: Arc::new(RwLock::new(0)),
+            detector: Arc::new(BanDetector::new()),
+            max_decompressed_bytes: DEFAULT_MAX_DECOMPRESSED_BYTES,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            dns: None,
+            pool: PoolConfig::default(),
+            redirect: RedirectConfig::default(),
+            preflight: PreflightConfig::default(),
+            clients: Mutex::new(HashMap::new()),
         }
     }
 
+    // * Replaces the ban detector used to classify every tier's responses.
+    pub fn with_detector(mut self, detector: Arc<BanDetector>) -> Self {
+        self.detector = detector;
+        self
+    }
+
+    // * Caps the decompressed body size on every tier.
+    pub fn with_max_decompressed_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_decompressed_bytes = max_bytes;
+        self
+    }
+
+    // * Cuts bodies past this size on every tier.
+    pub fn with_max_response_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_response_bytes = max_bytes;
+        self
+    }
+
+    // * Resolves hosts on every tier through a shared DNS cache.
+    pub fn with_dns_cache(mut self, dns: Arc<DnsCache>) -> Self {
+        self.dns = Some(dns);
+        self
+    }
+
+    // * Applies connection pool settings to every tier's clients.
+    pub fn with_pool(mut self, pool: PoolConfig) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    // * Applies the redirect policy on every tier.
+    pub fn with_redirect_policy(mut self, redirect: RedirectConfig) -> Self {
+        self.redirect = redirect;
+        self
+    }
+
+    // * Applies HEAD preflight checks on every tier.
+    pub fn with_preflight(mut self, preflight: PreflightConfig) -> Self {
+        self.preflight = preflight;
+        self
+    }
+
     pub async fn fetch_with_escalation(&self, url: &str) -> Result<String, NetworkError> {
+        self.fetch_with_profile(url, &BrowserProfile::default()).await
+    }
+
+    // * Runs the escalation ladder presenting the given browser profile on every tier.
+    pub async fn fetch_with_profile(&self, url: &str, profile: &BrowserProfile) -> Result<String, NetworkError> {
+        let fetched = self.fetch_conditional(url, profile, None).await?;
+        fetched.into_body().ok_or(NetworkError::EmptyResponse(0))
+    }
+
+    // * Runs the escalation ladder, revalidating with cached ETag/Last-Modified on every tier.
+    pub async fn fetch_conditional(
+        &self,
+        url: &str,
+        profile: &BrowserProfile,
+        validators: Option<&CacheValidators>,
+    ) -> Result<ConditionalFetch, NetworkError> {
         // --- TIER 0 ---
-        match self.execute_tier(ProxyTier::Tier0Direct, url).await {
+        match self.execute_tier(ProxyTier::Tier0Direct, url, profile, validators).await {
             Ok(body) => return Ok(body),
             Err(e) => {
                 if !self.should_escalate(&e) { return Err(e); }
@@ -40,7 +130,7 @@ impl ProxyManager {
         }
 
         // --- TIER 1 ---
-        match self.execute_tier(ProxyTier::Tier1Datacenter, url).await {
+        match self.execute_tier(ProxyTier::Tier1Datacenter, url, profile, validators).await {
             Ok(body) => return Ok(body),
             Err(e) => {
                 if !self.should_escalate(&e) { return Err(e); }
@@ -49,24 +139,56 @@ impl ProxyManager {
         }
 
         // --- TIER 2 ---
-        self.execute_tier(ProxyTier::Tier2Residential, url).await
+        self.execute_tier(ProxyTier::Tier2Residential, url, profile, validators).await
     }
 
-    async fn execute_tier(&self, tier: ProxyTier, url: &str) -> Result<String, NetworkError> {
+    async fn execute_tier(
+        &self,
+        tier: ProxyTier,
+        url: &str,
+        profile: &BrowserProfile,
+        validators: Option<&CacheValidators>,
+    ) -> Result<ConditionalFetch, NetworkError> {
         let proxy_url = match tier {
             ProxyTier::Tier0Direct => None,
             ProxyTier::Tier1Datacenter => self.get_next_proxy(1).await,
             ProxyTier::Tier2Residential => self.get_next_proxy(2).await,
         };
 
-        // * Build Client
-        let client = FastClient::new(proxy_url.as_deref())?;
-        
         // * TEST HOOK
         #[cfg(test)]
-        let client = self.inject_simulation(client, tier, url);
+        if let Some(code) = Self::simulated_failure(tier, url) {
+            let client = self.build_client(proxy_url.as_deref(), profile)?.with_simulation_mode(code);
+            return client.fetch_conditional(url, validators).await;
+        }
 
-        client.fetch(url).await
+        let client = self.client_for(proxy_url, profile)?;
+        client.fetch_conditional(url, validators).await
+    }
+
+    // * Returns the cached client for a proxy and profile, building it on first use.
+    fn client_for(&self, proxy_url: Option<String>, profile: &BrowserProfile) -> Result<Arc<FastClient>, NetworkError> {
+        let key = (proxy_url, profile.user_agent.clone(), profile.accept_language.clone());
+        if let Some(client) = self.clients.lock().unwrap().get(&key) {
+            return Ok(Arc::clone(client));
+        }
+        let client = Arc::new(self.build_client(key.0.as_deref(), profile)?);
+        Ok(Arc::clone(self.clients.lock().unwrap().entry(key).or_insert(client)))
+    }
+
+    fn build_client(&self, proxy_url: Option<&str>, profile: &BrowserProfile) -> Result<FastClient, NetworkError> {
+        let client = FastClient::with_options(proxy_url, profile.clone(), self.dns.clone(), &self.pool)?;
+        Ok(client
+            .with_detector(self.detector.clone())
+            .with_max_decompressed_bytes(self.max_decompressed_bytes)
+            .with_max_response_bytes(self.max_response_bytes)
+            .with_redirect_policy(self.redirect.clone())
+            .with_preflight(self.preflight.clone()))
+    }
+
+    // * Returns the number of clients (and connection pools) built so far.
+    pub fn client_count(&self) -> usize {
+        self.clients.lock().unwrap().len()
     }
 
     async fn get_next_proxy(&self, tier: u8) -> Option<String> {
@@ -90,24 +212,46 @@ impl ProxyManager {
     }
 
     fn should_escalate(&self, err: &NetworkError) -> bool {
-        match err {
-            NetworkError::SoftBan(_) => true,
-            NetworkError::HardBan(_) => true,
-            NetworkError::EmptyResponse(_) => true, 
-            _ => false,
-        }
+        matches!(
+            err,
+            NetworkError::SoftBan(_)
+                | NetworkError::HardBan(_)
+                | NetworkError::Throttled { .. }
+                | NetworkError::EmptyResponse(_)
+        )
     }
 
     #[cfg(test)]
-    fn inject_simulation(&self, client: FastClient, tier: ProxyTier, url: &str) -> FastClient {
-        if url == "https://simulate.fail" {
-             match tier {
-                ProxyTier::Tier0Direct => return client.with_simulation_mode(403),
-                ProxyTier::Tier1Datacenter => return client.with_simulation_mode(200),
-                _ => client,
-            }
-        } else {
-            client
+    fn simulated_failure(tier: ProxyTier, url: &str) -> Option<u16> {
+        if url != "https://simulate.fail" {
+            return None;
+        }
+        match tier {
+            ProxyTier::Tier0Direct => Some(403),
+            ProxyTier::Tier1Datacenter => Some(200),
+            _ => None,
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clients_are_reused_per_proxy_and_profile() {
+        let pm = ProxyManager::new(vec!["http://127.0.0.1:3128".into()], vec![]);
+        let chrome = BrowserProfile::default();
+        let other = BrowserProfile::catalog()
+            .into_iter()
+            .find(|profile| profile.user_agent != chrome.user_agent)
+            .unwrap();
+
+        let first = pm.client_for(None, &chrome).unwrap();
+        let again = pm.client_for(None, &chrome).unwrap();
+        assert!(Arc::ptr_eq(&first, &again));
+
+        pm.client_for(None, &other).unwrap();
+        pm.client_for(Some("http://127.0.0.1:3128".into()), &chrome).unwrap();
+        assert_eq!(pm.client_count(), 3);
+    }
+}
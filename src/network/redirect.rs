@@ -0,0 +1,261 @@
+// * [FR-03] Redirect Policy
+// * The fast path follows redirects itself rather than leaving them to reqwest, so every hop
+// * is checked against the policy (hop limit, loops, cross-domain rules) and the chain is kept
+// * for canonical URL resolution. `<meta http-equiv="refresh">` redirects are followed too.
+
+use reqwest::Url;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+use thiserror::Error;
+
+static META_REFRESH_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("meta[http-equiv][content]").unwrap());
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum RedirectError {
+    #[error("More than {0} redirects")]
+    TooManyHops(usize),
+
+    #[error("Redirect loop through {0}")]
+    Loop(String),
+
+    #[error("Redirect to {0} is not allowed")]
+    Disallowed(String),
+
+    #[error("Invalid redirect target: {0}")]
+    InvalidLocation(String),
+}
+
+impl RedirectError {
+    /// Metric and report label
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RedirectError::TooManyHops(_) => "too_many_hops",
+            RedirectError::Loop(_) => "loop",
+            RedirectError::Disallowed(_) => "disallowed",
+            RedirectError::InvalidLocation(_) => "invalid_location",
+        }
+    }
+}
+
+/// Redirect following rules for the fast path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RedirectConfig {
+    /// Redirects followed before the fetch fails (0 fails on the first redirect)
+    pub max_hops: usize,
+    /// Follow redirects to other hosts; `www.` prefixes do not make a host different
+    pub cross_domain: bool,
+    /// Hosts (and their subdomains) followed even when `cross_domain` is off
+    pub allow_domains: Vec<String>,
+    /// Hosts (and their subdomains) never followed
+    pub deny_domains: Vec<String>,
+    /// Follow `<meta http-equiv="refresh">` redirects on fetched pages
+    pub follow_meta_refresh: bool,
+    /// Longest meta refresh delay followed; slower refreshes are left as page content
+    pub meta_refresh_max_delay_secs: u64,
+}
+
+impl Default for RedirectConfig {
+    fn default() -> Self {
+        Self {
+            max_hops: 10,
+            cross_domain: true,
+            allow_domains: Vec::new(),
+            deny_domains: Vec::new(),
+            follow_meta_refresh: true,
+            meta_refresh_max_delay_secs: 5,
+        }
+    }
+}
+
+/// A URL that redirected elsewhere
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedirectHop {
+    pub url: String,
+    /// HTTP status of the redirect (None for a meta refresh)
+    pub status: Option<u16>,
+}
+
+/// Redirects followed by one fetch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedirectChain {
+    /// Hops in order, starting with the requested URL
+    pub hops: Vec<RedirectHop>,
+    /// URL the body was finally served from
+    pub final_url: String,
+}
+
+impl RedirectChain {
+    /// Returns the URLs that redirected, starting with the requested URL
+    pub fn urls(&self) -> Vec<String> {
+        self.hops.iter().map(|hop| hop.url.clone()).collect()
+    }
+}
+
+impl RedirectConfig {
+    /// Checks whether `target` may be followed after the hops in `chain`
+    ///
+    /// `chain` already holds the hop redirecting to `target`. A URL may come back once, as
+    /// cookie-setting bounces do; coming back a second time is a loop.
+    pub fn check(&self, chain: &[RedirectHop], target: &Url) -> Result<(), RedirectError> {
+        if !matches!(target.scheme(), "http" | "https") {
+            return Err(RedirectError::InvalidLocation(target.to_string()));
+        }
+        if chain.len() > self.max_hops {
+            return Err(RedirectError::TooManyHops(self.max_hops));
+        }
+        if chain.iter().filter(|hop| hop.url == target.as_str()).count() > 1 {
+            return Err(RedirectError::Loop(target.to_string()));
+        }
+
+        let host = target.host_str().unwrap_or_default().to_ascii_lowercase();
+        if matches_domain(&self.deny_domains, &host) {
+            return Err(RedirectError::Disallowed(target.to_string()));
+        }
+        let origin = chain
+            .first()
+            .and_then(|hop| Url::parse(&hop.url).ok())
+            .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+            .unwrap_or_default();
+        let same_site = strip_www(&host) == strip_www(&origin);
+        if !same_site && !self.cross_domain && !matches_domain(&self.allow_domains, &host) {
+            return Err(RedirectError::Disallowed(target.to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Returns the target of a `<meta http-equiv="refresh">` firing within `max_delay_secs`
+///
+/// Refreshes without a URL reload the same page and are ignored.
+pub fn meta_refresh_target(html: &str, base: &Url, max_delay_secs: u64) -> Option<Url> {
+    // * Most pages have no refresh; skip parsing them
+    if !html.as_bytes().windows(7).any(|w| w.eq_ignore_ascii_case(b"refresh")) {
+        return None;
+    }
+    let document = Html::parse_document(html);
+    let content = document
+        .select(&META_REFRESH_SELECTOR)
+        .find(|meta| {
+            meta.value()
+                .attr("http-equiv")
+                .is_some_and(|v| v.trim().eq_ignore_ascii_case("refresh"))
+        })?
+        .value()
+        .attr("content")?;
+
+    // * "<delay>; url=<target>", with "," also seen as the separator and quotes around the URL
+    let (delay, rest) = content.split_once([';', ',']).unwrap_or((content, ""));
+    let delay: f64 = delay.trim().parse().ok()?;
+    if !(0.0..=max_delay_secs as f64).contains(&delay) {
+        return None;
+    }
+    let rest = rest.trim();
+    let target = match rest.get(..3) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("url") => rest[3..].trim_start().strip_prefix('=')?,
+        _ => rest,
+    };
+    let target = target.trim().trim_matches(|c| c == '\'' || c == '"').trim();
+    if target.is_empty() {
+        return None;
+    }
+    base.join(target).ok()
+}
+
+fn strip_www(host: &str) -> &str {
+    host.strip_prefix("www.").unwrap_or(host)
+}
+
+fn matches_domain(domains: &[String], host: &str) -> bool {
+    domains.iter().any(|domain| {
+        let domain = domain.to_ascii_lowercase();
+        host == domain || host.ends_with(&format!(".{}", domain))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hop(url: &str) -> RedirectHop {
+        RedirectHop {
+            url: url.to_string(),
+            status: Some(301),
+        }
+    }
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_hop_limit_and_loops() {
+        let config = RedirectConfig {
+            max_hops: 2,
+            ..Default::default()
+        };
+        let a = "https://example.com/a";
+        let b = "https://example.com/b";
+
+        assert!(config.check(&[hop(a)], &url(b)).is_ok());
+        // * One bounce back is allowed (cookie checks), a second return is a loop
+        assert!(config.check(&[hop(a), hop(b)], &url(a)).is_ok());
+        assert_eq!(
+            config.check(&[hop(a), hop(b), hop(a)], &url(a)),
+            Err(RedirectError::TooManyHops(2))
+        );
+        let looping = RedirectConfig::default();
+        assert_eq!(
+            looping.check(&[hop(a), hop(b), hop(a), hop(b)], &url(a)),
+            Err(RedirectError::Loop(a.to_string()))
+        );
+        assert!(matches!(
+            config.check(&[hop(a)], &url("ftp://example.com/")),
+            Err(RedirectError::InvalidLocation(_))
+        ));
+    }
+
+    #[test]
+    fn test_cross_domain_rules() {
+        let config = RedirectConfig {
+            cross_domain: false,
+            allow_domains: vec!["cdn.net".to_string()],
+            deny_domains: vec!["tracker.io".to_string()],
+            ..Default::default()
+        };
+        let chain = [hop("https://example.com/")];
+
+        assert!(config.check(&chain, &url("https://www.example.com/")).is_ok());
+        assert!(config.check(&chain, &url("https://static.cdn.net/page")).is_ok());
+        assert!(matches!(
+            config.check(&chain, &url("https://other.org/")),
+            Err(RedirectError::Disallowed(_))
+        ));
+        let open = RedirectConfig::default();
+        assert!(open.check(&chain, &url("https://other.org/")).is_ok());
+        assert!(matches!(
+            config.check(&chain, &url("https://t.tracker.io/")),
+            Err(RedirectError::Disallowed(_))
+        ));
+    }
+
+    #[test]
+    fn test_meta_refresh_target() {
+        let base = url("https://example.com/old/page");
+        let page = |content: &str| format!(r#"<html><head><meta http-equiv="Refresh" content="{}"></head></html>"#, content);
+
+        assert_eq!(
+            meta_refresh_target(&page("0; url=/new"), &base, 5),
+            Some(url("https://example.com/new"))
+        );
+        assert_eq!(
+            meta_refresh_target(&page("2;URL='https://other.org/x'"), &base, 5),
+            Some(url("https://other.org/x"))
+        );
+        assert_eq!(meta_refresh_target(&page("30; url=/later"), &base, 5), None);
+        assert_eq!(meta_refresh_target(&page("10"), &base, 60), None);
+        assert_eq!(meta_refresh_target("<p>No refresh here</p>", &base, 5), None);
+    }
+}
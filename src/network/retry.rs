@@ -0,0 +1,258 @@
+// * [FR-03] Fetch Retry Policy
+// * Transient fast path failures (timeouts, dropped connections, 5xx) are retried with
+// * jittered exponential backoff. A failed URL goes back to the frontier once its delay has
+// * passed, so no worker sits out the backoff; every URL has a retry budget.
+
+use super::errors::NetworkError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use xxhash_rust::xxh64::xxh64;
+
+/// Kind of transient failure a retry rule applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RetryCategory {
+    /// The request or the body read timed out
+    Timeout,
+    /// The connection could not be opened or was reset mid-request
+    Connection,
+    /// The server answered 5xx (503 with Retry-After is throttling, waited out by the rate limiter)
+    ServerError,
+}
+
+impl RetryCategory {
+    /// Returns the category of a retryable error; bans, throttling, DNS failures and
+    /// client errors are not retried
+    pub fn classify(err: &NetworkError) -> Option<Self> {
+        let NetworkError::Reqwest(e) = err else {
+            return None;
+        };
+        if e.is_timeout() {
+            return Some(RetryCategory::Timeout);
+        }
+        if err.is_dns_failure() {
+            return None;
+        }
+        match e.status() {
+            Some(status) if status.is_server_error() => Some(RetryCategory::ServerError),
+            Some(_) => None,
+            None if e.is_connect() || e.is_request() || e.is_body() => Some(RetryCategory::Connection),
+            None => None,
+        }
+    }
+
+    /// Metric label
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RetryCategory::Timeout => "timeout",
+            RetryCategory::Connection => "connection",
+            RetryCategory::ServerError => "server_error",
+        }
+    }
+}
+
+/// Retries for one category of failure
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetryRule {
+    /// Retries of this kind per URL (0 disables them)
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled on each further retry
+    pub base_delay_ms: u64,
+}
+
+impl Default for RetryRule {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            base_delay_ms: 1_000,
+        }
+    }
+}
+
+/// Retry policy for transient fast path failures
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetryConfig {
+    pub enabled: bool,
+    /// Retries per URL across all categories
+    pub max_retries_per_url: u32,
+    pub timeout: RetryRule,
+    pub connection: RetryRule,
+    pub server_error: RetryRule,
+    /// Longest delay before a retry
+    pub max_delay_ms: u64,
+    /// Fraction of each delay randomized away (0 waits the full delay, 1 anywhere up to it)
+    pub jitter: f64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_retries_per_url: 3,
+            timeout: RetryRule::default(),
+            connection: RetryRule::default(),
+            server_error: RetryRule {
+                max_retries: 3,
+                base_delay_ms: 5_000,
+            },
+            max_delay_ms: 60_000,
+            jitter: 0.5,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Returns the rule for a category
+    pub fn rule(&self, category: RetryCategory) -> &RetryRule {
+        match category {
+            RetryCategory::Timeout => &self.timeout,
+            RetryCategory::Connection => &self.connection,
+            RetryCategory::ServerError => &self.server_error,
+        }
+    }
+
+    /// Returns the delay before retry number `retry` (starting at 0) of `url`
+    ///
+    /// The jitter is derived from the URL and retry number, so hosts retried together
+    /// spread out without a random number generator.
+    pub fn delay(&self, category: RetryCategory, retry: u32, url: &str) -> Duration {
+        let full = self
+            .rule(category)
+            .base_delay_ms
+            .saturating_mul(1u64 << retry.min(32))
+            .min(self.max_delay_ms);
+        let seed = format!("{}#{}", url, retry);
+        let unit = (xxh64(seed.as_bytes(), 0) >> 11) as f64 / (1u64 << 53) as f64;
+        let jitter = self.jitter.clamp(0.0, 1.0) * unit;
+        Duration::from_millis((full as f64 * (1.0 - jitter)) as u64)
+    }
+}
+
+#[derive(Debug, Default)]
+struct UrlRetries {
+    total: u32,
+    by_category: HashMap<RetryCategory, u32>,
+}
+
+/// Retries used per URL during one crawl
+#[derive(Debug)]
+pub struct RetryTracker {
+    config: RetryConfig,
+    urls: HashMap<String, UrlRetries>,
+}
+
+impl RetryTracker {
+    pub fn new(config: RetryConfig) -> Self {
+        Self {
+            config,
+            urls: HashMap::new(),
+        }
+    }
+
+    /// Charges a retry of `url` and returns its delay, or None once a budget is spent
+    pub fn schedule(&mut self, url: &str, category: RetryCategory) -> Option<Duration> {
+        if !self.config.enabled {
+            return None;
+        }
+        let retries = self.urls.entry(url.to_string()).or_default();
+        let used = retries.by_category.entry(category).or_default();
+        if retries.total >= self.config.max_retries_per_url || *used >= self.config.rule(category).max_retries {
+            return None;
+        }
+        let delay = self.config.delay(category, *used, url);
+        *used += 1;
+        retries.total += 1;
+        Some(delay)
+    }
+
+    /// Forgets a URL that was fetched or given up on
+    pub fn forget(&mut self, url: &str) {
+        self.urls.remove(url);
+    }
+
+    /// Returns how many retries `url` has used
+    pub fn retries(&self, url: &str) -> u32 {
+        self.urls.get(url).map_or(0, |retries| retries.total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_backoff_doubles_with_bounded_jitter() {
+        let config = RetryConfig {
+            jitter: 0.0,
+            max_delay_ms: 3_000,
+            ..Default::default()
+        };
+        let delays: Vec<u64> = (0..4)
+            .map(|retry| config.delay(RetryCategory::Timeout, retry, "https://example.com/").as_millis() as u64)
+            .collect();
+        assert_eq!(delays, vec![1_000, 2_000, 3_000, 3_000]);
+
+        let jittered = RetryConfig::default();
+        let delay = jittered.delay(RetryCategory::ServerError, 1, "https://example.com/a");
+        assert!(delay >= Duration::from_millis(5_000) && delay <= Duration::from_millis(10_000));
+        assert_eq!(delay, jittered.delay(RetryCategory::ServerError, 1, "https://example.com/a"));
+        assert_ne!(delay, jittered.delay(RetryCategory::ServerError, 1, "https://example.com/b"));
+    }
+
+    #[test]
+    fn test_tracker_enforces_category_and_url_budgets() {
+        let mut tracker = RetryTracker::new(RetryConfig {
+            max_retries_per_url: 3,
+            ..Default::default()
+        });
+        let url = "https://example.com/flaky";
+
+        assert!(tracker.schedule(url, RetryCategory::Timeout).is_some());
+        assert!(tracker.schedule(url, RetryCategory::Timeout).is_some());
+        // * Timeouts are spent, the URL budget is not
+        assert_eq!(tracker.schedule(url, RetryCategory::Timeout), None);
+        assert!(tracker.schedule(url, RetryCategory::ServerError).is_some());
+        assert_eq!(tracker.schedule(url, RetryCategory::ServerError), None);
+        assert_eq!(tracker.retries(url), 3);
+
+        tracker.forget(url);
+        assert_eq!(tracker.retries(url), 0);
+        let mut disabled = RetryTracker::new(RetryConfig {
+            enabled: false,
+            ..Default::default()
+        });
+        assert_eq!(disabled.schedule(url, RetryCategory::Connection), None);
+    }
+
+    #[tokio::test]
+    async fn test_classify_transient_errors() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            for (stream, status) in listener.incoming().zip(["502 Bad Gateway", "404 Not Found"]) {
+                let mut stream = stream.unwrap();
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf);
+                let reply = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+                stream.write_all(reply.as_bytes()).unwrap();
+            }
+        });
+        let status_error = |resp: reqwest::Response| NetworkError::Reqwest(resp.error_for_status().unwrap_err());
+        let url = format!("http://127.0.0.1:{}/", port);
+
+        let bad_gateway = status_error(reqwest::get(&url).await.unwrap());
+        assert_eq!(RetryCategory::classify(&bad_gateway), Some(RetryCategory::ServerError));
+        let not_found = status_error(reqwest::get(&url).await.unwrap());
+        assert_eq!(RetryCategory::classify(&not_found), None);
+
+        // * The listener is dropped at once, so the port refuses connections
+        let closed = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let refused = NetworkError::Reqwest(reqwest::get(format!("http://127.0.0.1:{}/", closed)).await.unwrap_err());
+        assert_eq!(RetryCategory::classify(&refused), Some(RetryCategory::Connection));
+        assert_eq!(RetryCategory::classify(&NetworkError::HardBan(403)), None);
+    }
+}
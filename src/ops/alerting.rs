@@ -1,9 +1,14 @@
 // * [Sec 5] Alerting - SEV-1 and SEV-3 Alert Conditions
 // * Defines alerting rules and triggers for operational monitoring
 
+use super::domain_stats::DomainStatsRegistry;
+use super::events::{publish_event, CrawlEvent};
+use super::telemetry::{set_global_error_rate, set_global_success_rate};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::{Duration, Instant};
 
 // * Alert thresholds from specification
@@ -11,6 +16,9 @@ const SEV1_SUCCESS_RATE_THRESHOLD: f64 = 0.50; // * Global Success Rate < 50%
 const SEV1_WINDOW_SECONDS: u64 = 300;          // * 5 minute window
 const SEV3_BAN_RATE_THRESHOLD: f64 = 0.90;     // * Single Domain Ban Rate > 90%
 
+// * Width of each bucket in the rolling success-rate window
+const BUCKET_SECONDS: u64 = 10;
+
 /// Alert severity levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AlertSeverity {
@@ -48,6 +56,8 @@ pub enum AlertType {
     CircuitBreakerOpen,
     /// Rate limit exceeded
     RateLimitExceeded,
+    /// Daily enrichment spend reached its budget
+    EnrichmentBudgetExhausted,
     /// Custom alert
     Custom(String),
 }
@@ -60,6 +70,7 @@ impl std::fmt::Display for AlertType {
             AlertType::MemoryPressure => write!(f, "MEMORY_PRESSURE"),
             AlertType::CircuitBreakerOpen => write!(f, "CIRCUIT_BREAKER_OPEN"),
             AlertType::RateLimitExceeded => write!(f, "RATE_LIMIT_EXCEEDED"),
+            AlertType::EnrichmentBudgetExhausted => write!(f, "ENRICHMENT_BUDGET_EXHAUSTED"),
             AlertType::Custom(name) => write!(f, "CUSTOM_{}", name.to_uppercase()),
         }
     }
@@ -74,6 +85,8 @@ pub struct Alert {
     pub context: HashMap<String, String>,
     pub timestamp: Instant,
     pub id: u64,
+    /// Number of alerts this notification stands for (above 1 for grouped notifications)
+    pub group_count: usize,
 }
 
 impl Alert {
@@ -87,9 +100,22 @@ impl Alert {
             context: HashMap::new(),
             timestamp: Instant::now(),
             id: COUNTER.fetch_add(1, Ordering::Relaxed),
+            group_count: 1,
         }
     }
 
+    /// Identity used for cooldowns and resolution
+    ///
+    /// Per-domain alerts (those with a `domain` context) are tracked separately per domain.
+    pub fn fingerprint(&self) -> String {
+        fingerprint(&self.alert_type, self.context.get("domain").map(String::as_str))
+    }
+
+    /// Alerts sharing a group key within the grouping window are notified together
+    pub fn group_key(&self) -> String {
+        self.alert_type.to_string()
+    }
+
     /// Adds context to the alert
     pub fn with_context(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.context.insert(key.into(), value.into());
@@ -150,6 +176,11 @@ impl Alert {
 pub trait AlertHandler: Send + Sync {
     /// Handles an alert
     fn handle(&self, alert: &Alert);
+
+    /// Handles the recovery of a previously fired alert
+    fn resolved(&self, alert: &Alert, active_for: Duration) {
+        let _ = (alert, active_for);
+    }
 }
 
 /// Default logging handler
@@ -160,36 +191,76 @@ impl AlertHandler for LoggingHandler {
     fn handle(&self, alert: &Alert) {
         alert.log();
     }
+
+    fn resolved(&self, alert: &Alert, active_for: Duration) {
+        tracing::info!(
+            alert_id = alert.id,
+            severity = %alert.severity,
+            alert_type = %alert.alert_type,
+            active_secs = active_for.as_secs_f64(),
+            "RESOLVED: {}", alert.message
+        );
+    }
+}
+
+/// Builds the fingerprint of an alert type, optionally scoped to a domain
+fn fingerprint(alert_type: &AlertType, domain: Option<&str>) -> String {
+    match domain {
+        Some(domain) => format!("{}:{}", alert_type, domain),
+        None => alert_type.to_string(),
+    }
+}
+
+/// Alerts held back while their group's window is open
+#[derive(Debug)]
+struct AlertGroup {
+    opened: Instant,
+    held: Vec<Alert>,
+}
+
+/// An alert that has fired and not yet resolved
+#[derive(Debug, Clone)]
+struct ActiveAlert {
+    /// Latest firing (re-fired after each cooldown while the condition persists)
+    alert: Alert,
+    /// First firing; alert durations are measured from here
+    since: Instant,
 }
 
 /// Alert manager for monitoring and triggering alerts
 pub struct AlertManager {
     handlers: Vec<Arc<dyn AlertHandler>>,
-    active_alerts: RwLock<HashMap<AlertType, Alert>>,
+    // * Keyed by fingerprint
+    active_alerts: RwLock<HashMap<String, ActiveAlert>>,
+    // * Keyed by group key
+    groups: Mutex<HashMap<String, AlertGroup>>,
     stats: AlertStats,
-    config: AlertConfig,
+    config: RwLock<AlertConfig>,
 }
 
 impl std::fmt::Debug for AlertManager {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("AlertManager")
             .field("handler_count", &self.handlers.len())
-            .field("config", &self.config)
+            .field("config", &self.config())
             .finish()
     }
 }
 
 /// Configuration for the alert manager
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct AlertConfig {
     /// Threshold for SEV-1 global success rate alert
     pub sev1_success_rate_threshold: f64,
-    /// Window for SEV-1 rate calculation in seconds
+    /// Rolling window for SEV-1 rate calculation in seconds (tracked in 10-second buckets)
     pub sev1_window_seconds: u64,
     /// Threshold for SEV-3 domain ban rate alert
     pub sev3_ban_rate_threshold: f64,
     /// Cooldown period before re-firing same alert
     pub alert_cooldown_seconds: u64,
+    /// Window in which alerts sharing a group key are notified as one (0 disables grouping)
+    pub group_window_seconds: u64,
 }
 
 impl Default for AlertConfig {
@@ -199,6 +270,7 @@ impl Default for AlertConfig {
             sev1_window_seconds: SEV1_WINDOW_SECONDS,
             sev3_ban_rate_threshold: SEV3_BAN_RATE_THRESHOLD,
             alert_cooldown_seconds: 300, // * 5 minute cooldown
+            group_window_seconds: 60,
         }
     }
 }
@@ -206,29 +278,115 @@ impl Default for AlertConfig {
 /// Statistics tracked by the alert manager
 #[derive(Debug)]
 pub struct AlertStats {
-    success_count: AtomicU64,
-    failure_count: AtomicU64,
-    window_start: RwLock<Instant>,
-    domain_stats: RwLock<HashMap<String, DomainStats>>,
+    requests: Mutex<RollingWindow>,
+    domains: Arc<DomainStatsRegistry>,
+    resolved_count: AtomicU64,
+    total_active_ms: AtomicU64,
+    max_active_ms: AtomicU64,
 }
 
 impl Default for AlertStats {
     fn default() -> Self {
         Self {
-            success_count: AtomicU64::new(0),
-            failure_count: AtomicU64::new(0),
-            window_start: RwLock::new(Instant::now()),
-            domain_stats: RwLock::new(HashMap::new()),
+            requests: Mutex::new(RollingWindow::new(Instant::now())),
+            domains: Arc::new(DomainStatsRegistry::new()),
+            resolved_count: AtomicU64::new(0),
+            total_active_ms: AtomicU64::new(0),
+            max_active_ms: AtomicU64::new(0),
         }
     }
 }
 
-/// Per-domain statistics
-#[derive(Debug, Clone, Default)]
-struct DomainStats {
-    success_count: u64,
-    failure_count: u64,
-    ban_count: u64,
+/// Request outcomes in one time bucket
+#[derive(Debug, Clone, Copy, Default)]
+struct Bucket {
+    /// Index of the bucket's time slot since the window was created
+    slot: u64,
+    success: u64,
+    failure: u64,
+}
+
+/// Success and failure counts over a rolling window
+///
+/// Outcomes land in a ring of `BUCKET_SECONDS` buckets. Buckets that fall out of the
+/// window are overwritten as the ring wraps, so the rate moves smoothly instead of
+/// resetting at window boundaries.
+#[derive(Debug)]
+struct RollingWindow {
+    origin: Instant,
+    buckets: Vec<Bucket>,
+}
+
+impl RollingWindow {
+    fn new(origin: Instant) -> Self {
+        Self {
+            origin,
+            buckets: Vec::new(),
+        }
+    }
+
+    /// Records one outcome at `now`
+    fn record(&mut self, now: Instant, window: Duration, success: bool) {
+        let slot = self.slot(now);
+        self.fit(window, slot);
+
+        let len = self.buckets.len() as u64;
+        let bucket = &mut self.buckets[(slot % len) as usize];
+        if bucket.slot != slot {
+            *bucket = Bucket {
+                slot,
+                ..Default::default()
+            };
+        }
+        if success {
+            bucket.success += 1;
+        } else {
+            bucket.failure += 1;
+        }
+    }
+
+    /// Returns the (success, failure) counts of the window ending at `now`
+    fn totals(&self, now: Instant) -> (u64, u64) {
+        let slot = self.slot(now);
+        let len = self.buckets.len() as u64;
+        self.buckets
+            .iter()
+            .filter(|bucket| slot.saturating_sub(bucket.slot) < len)
+            .fold((0, 0), |(success, failure), bucket| {
+                (success + bucket.success, failure + bucket.failure)
+            })
+    }
+
+    fn slot(&self, now: Instant) -> u64 {
+        now.saturating_duration_since(self.origin).as_secs() / BUCKET_SECONDS
+    }
+
+    /// Resizes the ring when the configured window changes, keeping buckets still inside it
+    fn fit(&mut self, window: Duration, slot: u64) {
+        let len = window.as_secs().div_ceil(BUCKET_SECONDS).max(1) as usize;
+        if self.buckets.len() == len {
+            return;
+        }
+
+        let mut buckets = vec![Bucket::default(); len];
+        for bucket in self.buckets.drain(..) {
+            let counted = bucket.success + bucket.failure > 0;
+            if counted && slot.saturating_sub(bucket.slot) < len as u64 {
+                buckets[(bucket.slot % len as u64) as usize] = bucket;
+            }
+        }
+        self.buckets = buckets;
+    }
+}
+
+/// Share of successes, 1.0 when nothing was recorded
+fn success_rate(success: u64, failure: u64) -> f64 {
+    let total = success + failure;
+    if total > 0 {
+        success as f64 / total as f64
+    } else {
+        1.0
+    }
 }
 
 impl AlertManager {
@@ -242,11 +400,9 @@ impl AlertManager {
         Self {
             handlers: vec![Arc::new(LoggingHandler)],
             active_alerts: RwLock::new(HashMap::new()),
-            stats: AlertStats {
-                window_start: RwLock::new(Instant::now()),
-                ..Default::default()
-            },
-            config,
+            groups: Mutex::new(HashMap::new()),
+            stats: AlertStats::default(),
+            config: RwLock::new(config),
         }
     }
 
@@ -257,58 +413,68 @@ impl AlertManager {
 
     /// Records a successful request
     pub fn record_success(&self) {
-        self.stats.success_count.fetch_add(1, Ordering::Relaxed);
-        self.check_and_rotate_window();
+        self.record_outcome(true);
+        self.check_sev1_recovery();
+        self.flush_alert_groups();
     }
 
     /// Records a failed request
     pub fn record_failure(&self) {
-        self.stats.failure_count.fetch_add(1, Ordering::Relaxed);
-        self.check_and_rotate_window();
+        self.record_outcome(false);
         self.check_sev1_alert();
+        self.flush_alert_groups();
     }
 
     /// Records a domain-specific event
-    pub fn record_domain_event(&self, domain: &str, success: bool, is_ban: bool) {
-        let mut domain_stats = self.stats.domain_stats.write().unwrap();
-        let stats = domain_stats.entry(domain.to_string()).or_default();
-
-        if success {
-            stats.success_count += 1;
-        } else {
-            stats.failure_count += 1;
-        }
-
-        if is_ban {
-            stats.ban_count += 1;
-        }
+    ///
+    /// Returns true while the domain's ban rate is above the SEV-3 threshold, so callers
+    /// can slow down even when the alert itself is suppressed by the cooldown. Once the
+    /// rate falls back to the threshold, the domain's ban rate alert is resolved.
+    pub fn record_domain_event(&self, domain: &str, success: bool, is_ban: bool) -> bool {
+        self.flush_alert_groups();
+        let stats = self.stats.domains.record_outcome(domain, success, is_ban);
 
         // * Check for SEV-3 alert
-        let total = stats.success_count + stats.failure_count;
-        if total >= 10 {
+        if stats.fetch_count() >= 10 {
             // * Minimum sample size
-            let ban_rate = stats.ban_count as f64 / total as f64;
-            if ban_rate > self.config.sev3_ban_rate_threshold {
-                drop(domain_stats); // * Release lock before firing alert
+            let ban_rate = stats.ban_rate();
+            if ban_rate > self.config().sev3_ban_rate_threshold {
                 self.fire_domain_ban_rate_alert(domain, ban_rate);
+                return true;
             }
+            self.resolve_domain_alert(&AlertType::DomainBanRateHigh, domain);
         }
+        false
     }
 
-    /// Checks and rotates the statistics window
-    fn check_and_rotate_window(&self) {
-        let mut window_start = self.stats.window_start.write().unwrap();
-        if window_start.elapsed() > Duration::from_secs(self.config.sev1_window_seconds) {
-            self.stats.success_count.store(0, Ordering::Relaxed);
-            self.stats.failure_count.store(0, Ordering::Relaxed);
-            *window_start = Instant::now();
-        }
+    /// Returns the per-domain statistics fed by `record_domain_event`
+    pub fn domain_stats(&self) -> Arc<DomainStatsRegistry> {
+        Arc::clone(&self.stats.domains)
+    }
+
+    /// Adds an outcome to the rolling window and publishes the resulting rates
+    fn record_outcome(&self, success: bool) {
+        let window = Duration::from_secs(self.config().sev1_window_seconds);
+        let now = Instant::now();
+        let (success, failure) = {
+            let mut requests = self.stats.requests.lock().unwrap();
+            requests.record(now, window, success);
+            requests.totals(now)
+        };
+
+        let rate = success_rate(success, failure);
+        set_global_success_rate(rate);
+        set_global_error_rate(1.0 - rate);
+    }
+
+    /// Returns the (success, failure) counts of the current rolling window
+    fn window_totals(&self) -> (u64, u64) {
+        self.stats.requests.lock().unwrap().totals(Instant::now())
     }
 
     /// Checks and fires SEV-1 alert if needed
     fn check_sev1_alert(&self) {
-        let success = self.stats.success_count.load(Ordering::Relaxed);
-        let failure = self.stats.failure_count.load(Ordering::Relaxed);
+        let (success, failure) = self.window_totals();
         let total = success + failure;
 
         if total < 10 {
@@ -316,11 +482,29 @@ impl AlertManager {
         }
 
         let success_rate = success as f64 / total as f64;
-        if success_rate < self.config.sev1_success_rate_threshold {
+        if success_rate < self.config().sev1_success_rate_threshold {
             self.fire_sev1_alert(success_rate);
         }
     }
 
+    /// Resolves the SEV-1 alert once the success rate is back at the threshold
+    fn check_sev1_recovery(&self) {
+        if !self.is_active(&AlertType::GlobalSuccessRateLow) {
+            return;
+        }
+
+        let (success, failure) = self.window_totals();
+        let total = success + failure;
+
+        if total < 10 {
+            return; // * Not enough data in the window
+        }
+
+        if success as f64 / total as f64 >= self.config().sev1_success_rate_threshold {
+            self.resolve_alert(&AlertType::GlobalSuccessRateLow);
+        }
+    }
+
     /// Fires a SEV-1 global success rate alert
     fn fire_sev1_alert(&self, success_rate: f64) {
         let alert = Alert::new(
@@ -329,13 +513,13 @@ impl AlertManager {
             format!(
                 "Global success rate ({:.1}%) below threshold ({:.1}%)",
                 success_rate * 100.0,
-                self.config.sev1_success_rate_threshold * 100.0
+                self.config().sev1_success_rate_threshold * 100.0
             ),
         )
         .with_context("success_rate", format!("{:.4}", success_rate))
         .with_context(
             "threshold",
-            format!("{:.4}", self.config.sev1_success_rate_threshold),
+            format!("{:.4}", self.config().sev1_success_rate_threshold),
         );
 
         self.fire_alert(alert);
@@ -350,42 +534,121 @@ impl AlertManager {
                 "Domain {} ban rate ({:.1}%) above threshold ({:.1}%)",
                 domain,
                 ban_rate * 100.0,
-                self.config.sev3_ban_rate_threshold * 100.0
+                self.config().sev3_ban_rate_threshold * 100.0
             ),
         )
         .with_context("domain", domain.to_string())
         .with_context("ban_rate", format!("{:.4}", ban_rate))
         .with_context(
             "threshold",
-            format!("{:.4}", self.config.sev3_ban_rate_threshold),
+            format!("{:.4}", self.config().sev3_ban_rate_threshold),
         );
 
         self.fire_alert(alert);
     }
 
     /// Fires a custom alert
+    ///
+    /// Handlers are notified of the first alert in a group right away; further alerts
+    /// with the same group key inside the grouping window are held and notified as one
+    /// grouped alert once the window closes.
     pub fn fire_alert(&self, alert: Alert) {
+        let fingerprint = alert.fingerprint();
+
         // * Check cooldown
         {
             let active = self.active_alerts.read().unwrap();
-            if let Some(existing) = active.get(&alert.alert_type) {
-                if existing.timestamp.elapsed()
-                    < Duration::from_secs(self.config.alert_cooldown_seconds)
+            if let Some(existing) = active.get(&fingerprint) {
+                if existing.alert.timestamp.elapsed()
+                    < Duration::from_secs(self.config().alert_cooldown_seconds)
                 {
                     return; // * Still in cooldown
                 }
             }
         }
 
-        // * Store as active alert
+        // * Store as active alert, keeping the first firing time while it stays unresolved
         {
             let mut active = self.active_alerts.write().unwrap();
-            active.insert(alert.alert_type.clone(), alert.clone());
+            let since = active
+                .get(&fingerprint)
+                .map(|existing| existing.since)
+                .unwrap_or(alert.timestamp);
+            active.insert(
+                fingerprint,
+                ActiveAlert {
+                    alert: alert.clone(),
+                    since,
+                },
+            );
         }
 
-        // * Dispatch to handlers
-        for handler in &self.handlers {
-            handler.handle(&alert);
+        publish_event(CrawlEvent::AlertFired {
+            severity: alert.severity.to_string(),
+            alert_type: alert.alert_type.to_string(),
+            message: alert.message.clone(),
+            context: alert.context.clone(),
+        });
+
+        // * Dispatch to handlers, closing expired groups first
+        let mut notifications = self.take_expired_groups();
+        notifications.extend(self.admit_to_group(alert));
+        self.dispatch(&notifications);
+    }
+
+    /// Notifies handlers of groups whose window has closed
+    pub fn flush_alert_groups(&self) {
+        let notifications = self.take_expired_groups();
+        self.dispatch(&notifications);
+    }
+
+    /// Returns the alert if it opens a new group, or holds it in the open one
+    fn admit_to_group(&self, alert: Alert) -> Option<Alert> {
+        if self.config().group_window_seconds == 0 {
+            return Some(alert);
+        }
+
+        let mut groups = self.groups.lock().unwrap();
+        match groups.entry(alert.group_key()) {
+            Entry::Occupied(mut group) => {
+                group.get_mut().held.push(alert);
+                None
+            }
+            Entry::Vacant(slot) => {
+                slot.insert(AlertGroup {
+                    opened: Instant::now(),
+                    held: Vec::new(),
+                });
+                Some(alert)
+            }
+        }
+    }
+
+    /// Closes groups whose window has elapsed, returning one notification per group
+    fn take_expired_groups(&self) -> Vec<Alert> {
+        let window = Duration::from_secs(self.config().group_window_seconds);
+        let mut groups = self.groups.lock().unwrap();
+        if groups.is_empty() {
+            return Vec::new();
+        }
+
+        let expired: Vec<String> = groups
+            .iter()
+            .filter(|(_, group)| group.opened.elapsed() >= window)
+            .map(|(key, _)| key.clone())
+            .collect();
+        expired
+            .iter()
+            .filter_map(|key| groups.remove(key))
+            .filter_map(|group| grouped_alert(group.held, window))
+            .collect()
+    }
+
+    fn dispatch(&self, notifications: &[Alert]) {
+        for alert in notifications {
+            for handler in &self.handlers {
+                handler.handle(alert);
+            }
         }
     }
 
@@ -413,34 +676,110 @@ impl AlertManager {
         self.fire_alert(alert);
     }
 
+    /// Fires an alert when enrichment pauses for the day on its spend cap
+    pub fn fire_enrichment_budget_alert(&self, spent_usd: f64, budget_usd: f64) {
+        let alert = Alert::new(
+            AlertSeverity::Sev3,
+            AlertType::EnrichmentBudgetExhausted,
+            format!("Enrichment paused: spent ${:.2} of the ${:.2} daily budget", spent_usd, budget_usd),
+        )
+        .with_context("spent_usd", format!("{:.4}", spent_usd))
+        .with_context("budget_usd", format!("{:.2}", budget_usd));
+
+        self.fire_alert(alert);
+    }
+
+    /// Resolves the circuit breaker alert once the domain's breaker has closed again
+    pub fn resolve_circuit_breaker_alert(&self, domain: &str) -> Option<Duration> {
+        self.resolve_domain_alert(&AlertType::CircuitBreakerOpen, domain)
+    }
+
+    /// Resolves an active alert that is not scoped to a domain, notifying handlers and subscribers
+    ///
+    /// Returns how long the alert was active, or `None` if it was not active.
+    pub fn resolve_alert(&self, alert_type: &AlertType) -> Option<Duration> {
+        self.resolve_fingerprint(&fingerprint(alert_type, None))
+    }
+
+    /// Resolves the alert of this type fired for `domain`
+    pub fn resolve_domain_alert(&self, alert_type: &AlertType, domain: &str) -> Option<Duration> {
+        self.resolve_fingerprint(&fingerprint(alert_type, Some(domain)))
+    }
+
+    /// Returns true while an alert of this type is active for any domain
+    pub fn is_active(&self, alert_type: &AlertType) -> bool {
+        self.active_alerts
+            .read()
+            .unwrap()
+            .values()
+            .any(|active| active.alert.alert_type == *alert_type)
+    }
+
+    fn resolve_fingerprint(&self, fingerprint: &str) -> Option<Duration> {
+        let resolved = self.active_alerts.write().unwrap().remove(fingerprint)?;
+        Some(self.finish_alert(resolved))
+    }
+
+    /// Records the duration of a removed alert and dispatches the resolution
+    fn finish_alert(&self, resolved: ActiveAlert) -> Duration {
+        let active_for = resolved.since.elapsed();
+        let active_ms = active_for.as_millis() as u64;
+        self.stats.resolved_count.fetch_add(1, Ordering::Relaxed);
+        self.stats.total_active_ms.fetch_add(active_ms, Ordering::Relaxed);
+        self.stats.max_active_ms.fetch_max(active_ms, Ordering::Relaxed);
+
+        let alert = resolved.alert;
+        publish_event(CrawlEvent::AlertResolved {
+            severity: alert.severity.to_string(),
+            alert_type: alert.alert_type.to_string(),
+            message: alert.message.clone(),
+            context: alert.context.clone(),
+            active_ms,
+        });
+
+        for handler in &self.handlers {
+            handler.resolved(&alert, active_for);
+        }
+        active_for
+    }
+
     /// Returns current statistics
     pub fn get_stats(&self) -> AlertManagerStats {
-        let success = self.stats.success_count.load(Ordering::Relaxed);
-        let failure = self.stats.failure_count.load(Ordering::Relaxed);
-        let total = success + failure;
+        let (success, failure) = self.window_totals();
         let active = self.active_alerts.read().unwrap();
+        let resolved = self.stats.resolved_count.load(Ordering::Relaxed);
+        let total_active_ms = self.stats.total_active_ms.load(Ordering::Relaxed);
 
         AlertManagerStats {
             success_count: success,
             failure_count: failure,
-            success_rate: if total > 0 {
-                success as f64 / total as f64
-            } else {
-                1.0
-            },
+            success_rate: success_rate(success, failure),
             active_alert_count: active.len(),
+            resolved_alert_count: resolved,
+            mean_alert_duration: Duration::from_millis(
+                total_active_ms.checked_div(resolved).unwrap_or(0),
+            ),
+            max_alert_duration: Duration::from_millis(
+                self.stats.max_active_ms.load(Ordering::Relaxed),
+            ),
         }
     }
 
-    /// Clears all active alerts
+    /// Clears all active alerts and held groups without resolving or notifying them
     pub fn clear_alerts(&self) {
         let mut active = self.active_alerts.write().unwrap();
         active.clear();
+        self.groups.lock().unwrap().clear();
     }
 
-    /// Returns the configuration
-    pub fn config(&self) -> &AlertConfig {
-        &self.config
+    /// Returns a snapshot of the current configuration
+    pub fn config(&self) -> AlertConfig {
+        self.config.read().unwrap().clone()
+    }
+
+    /// Replaces the thresholds used for subsequent checks
+    pub fn set_config(&self, config: AlertConfig) {
+        *self.config.write().unwrap() = config;
     }
 }
 
@@ -453,10 +792,47 @@ impl Default for AlertManager {
 /// Statistics from the alert manager
 #[derive(Debug, Clone)]
 pub struct AlertManagerStats {
+    /// Successes within the rolling SEV-1 window
     pub success_count: u64,
+    /// Failures within the rolling SEV-1 window
     pub failure_count: u64,
     pub success_rate: f64,
     pub active_alert_count: usize,
+    /// Alerts resolved since the manager was created
+    pub resolved_alert_count: u64,
+    /// Mean time from first firing to resolution
+    pub mean_alert_duration: Duration,
+    /// Longest time from first firing to resolution
+    pub max_alert_duration: Duration,
+}
+
+/// Folds the alerts held during a grouping window into one notification
+fn grouped_alert(mut held: Vec<Alert>, window: Duration) -> Option<Alert> {
+    if held.len() <= 1 {
+        return held.pop();
+    }
+
+    let first = &held[0];
+    let domains: Vec<&str> = held
+        .iter()
+        .filter_map(|alert| alert.context.get("domain").map(String::as_str))
+        .collect();
+    let mut grouped = Alert::new(
+        first.severity,
+        first.alert_type.clone(),
+        format!(
+            "{} more {} alerts within {}s",
+            held.len(),
+            first.alert_type,
+            window.as_secs()
+        ),
+    )
+    .with_context("count", held.len().to_string());
+    if !domains.is_empty() {
+        grouped = grouped.with_context("domains", domains.join(","));
+    }
+    grouped.group_count = held.len();
+    Some(grouped)
 }
 
 /// Convenience function to create a SEV-1 alert
@@ -586,7 +962,24 @@ mod tests {
         manager.record_domain_event("example.com", false, false);
         manager.record_domain_event("example.com", false, true);
 
-        // * Domain stats should be tracked
+        let stats = manager.domain_stats().get("example.com").unwrap();
+        assert_eq!(stats.success_count, 1);
+        assert_eq!(stats.failure_count, 2);
+        assert_eq!(stats.ban_count, 1);
+    }
+
+    #[test]
+    fn test_domain_event_reports_ban_rate_breach() {
+        let manager = AlertManager::new();
+
+        // * Below the minimum sample size nothing is reported
+        for _ in 0..9 {
+            assert!(!manager.record_domain_event("example.com", false, true));
+        }
+        assert!(manager.record_domain_event("example.com", false, true));
+        // * Still reported while the alert is in cooldown
+        assert!(manager.record_domain_event("example.com", false, true));
+        assert!(!manager.record_domain_event("other.com", true, false));
     }
 
     #[test]
@@ -619,6 +1012,7 @@ mod tests {
             sev3_ban_rate_threshold: 0.8,
             sev1_window_seconds: 60,
             alert_cooldown_seconds: 10,
+            group_window_seconds: 30,
         };
 
         let manager = AlertManager::with_config(config);
@@ -626,6 +1020,25 @@ mod tests {
         assert!((manager.config().sev3_ban_rate_threshold - 0.8).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_set_config_applies_to_new_events() {
+        let manager = AlertManager::new();
+        for _ in 0..10 {
+            manager.record_domain_event("example.com", false, true);
+        }
+        manager.clear_alerts();
+
+        // * A 100% ban rate no longer exceeds an impossible threshold
+        manager.set_config(AlertConfig {
+            sev3_ban_rate_threshold: 1.0,
+            ..Default::default()
+        });
+        manager.record_domain_event("example.com", false, true);
+
+        assert_eq!(manager.get_stats().active_alert_count, 0);
+        assert!((manager.config().sev3_ban_rate_threshold - 1.0).abs() < f64::EPSILON);
+    }
+
     #[test]
     fn test_memory_pressure_alert() {
         let config = AlertConfig {
@@ -639,6 +1052,216 @@ mod tests {
         assert!(stats.active_alert_count > 0);
     }
 
+    #[derive(Default)]
+    struct RecordingHandler {
+        handled: Mutex<Vec<Alert>>,
+        resolved: Mutex<Vec<(AlertType, Duration)>>,
+    }
+
+    impl AlertHandler for RecordingHandler {
+        fn handle(&self, alert: &Alert) {
+            self.handled.lock().unwrap().push(alert.clone());
+        }
+
+        fn resolved(&self, alert: &Alert, active_for: Duration) {
+            self.resolved
+                .lock()
+                .unwrap()
+                .push((alert.alert_type.clone(), active_for));
+        }
+    }
+
+    #[test]
+    fn test_sev1_alert_resolves_on_recovery() {
+        let handler = Arc::new(RecordingHandler::default());
+        let mut manager = AlertManager::new();
+        manager.add_handler(handler.clone());
+
+        for _ in 0..10 {
+            manager.record_failure();
+        }
+        assert!(manager.is_active(&AlertType::GlobalSuccessRateLow));
+
+        // * 9/19 is still below 50%
+        for _ in 0..9 {
+            manager.record_success();
+        }
+        assert!(manager.is_active(&AlertType::GlobalSuccessRateLow));
+
+        manager.record_success();
+        assert!(!manager.is_active(&AlertType::GlobalSuccessRateLow));
+
+        let resolved = handler.resolved.lock().unwrap();
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].0, AlertType::GlobalSuccessRateLow);
+        let stats = manager.get_stats();
+        assert_eq!(stats.resolved_alert_count, 1);
+        assert_eq!(stats.active_alert_count, 0);
+    }
+
+    #[test]
+    fn test_domain_alert_resolves_for_same_domain_only() {
+        let manager = AlertManager::new();
+        for _ in 0..10 {
+            manager.record_domain_event("example.com", false, true);
+        }
+        assert!(manager.is_active(&AlertType::DomainBanRateHigh));
+
+        // * Another domain recovering leaves the alert alone
+        for _ in 0..10 {
+            manager.record_domain_event("other.com", true, false);
+        }
+        assert!(manager.is_active(&AlertType::DomainBanRateHigh));
+
+        // * 10 bans out of 12 requests is ~83%, under the 90% threshold
+        manager.record_domain_event("example.com", true, false);
+        manager.record_domain_event("example.com", true, false);
+        assert!(!manager.is_active(&AlertType::DomainBanRateHigh));
+        assert_eq!(manager.get_stats().resolved_alert_count, 1);
+    }
+
+    #[test]
+    fn test_circuit_breaker_alert_resolution() {
+        let manager = AlertManager::new();
+        manager.fire_circuit_breaker_alert("slow.example.com");
+
+        assert_eq!(manager.resolve_circuit_breaker_alert("fast.example.com"), None);
+        assert!(manager.resolve_circuit_breaker_alert("slow.example.com").is_some());
+        assert_eq!(manager.resolve_circuit_breaker_alert("slow.example.com"), None);
+        assert_eq!(manager.get_stats().active_alert_count, 0);
+    }
+
+    #[test]
+    fn test_alert_duration_stats() {
+        let config = AlertConfig {
+            alert_cooldown_seconds: 0,
+            ..Default::default()
+        };
+        let manager = AlertManager::with_config(config);
+        assert_eq!(manager.get_stats().mean_alert_duration, Duration::ZERO);
+
+        manager.fire_memory_pressure_alert(95.0);
+        std::thread::sleep(Duration::from_millis(20));
+        // * Re-firing keeps the original start time
+        manager.fire_memory_pressure_alert(97.0);
+        let first = manager.resolve_alert(&AlertType::MemoryPressure).unwrap();
+        assert!(first >= Duration::from_millis(20));
+
+        manager.fire_memory_pressure_alert(95.0);
+        let second = manager.resolve_alert(&AlertType::MemoryPressure).unwrap();
+
+        let stats = manager.get_stats();
+        assert_eq!(stats.resolved_alert_count, 2);
+        assert_eq!(stats.max_alert_duration.as_millis(), first.as_millis());
+        assert!(stats.mean_alert_duration <= stats.max_alert_duration);
+        assert!(stats.mean_alert_duration.as_millis() >= (first.as_millis() + second.as_millis()) / 2);
+        assert_eq!(manager.resolve_alert(&AlertType::MemoryPressure), None);
+    }
+
+    #[test]
+    fn test_rolling_window_drops_old_buckets() {
+        let start = Instant::now();
+        let window = Duration::from_secs(30);
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut requests = RollingWindow::new(start);
+
+        requests.record(at(0), window, false);
+        requests.record(at(10), window, true);
+        requests.record(at(25), window, true);
+        assert_eq!(requests.totals(at(25)), (2, 1));
+
+        // * The first bucket ages out on its own; nothing resets abruptly
+        assert_eq!(requests.totals(at(30)), (2, 0));
+        assert_eq!(requests.totals(at(45)), (1, 0));
+        assert_eq!(requests.totals(at(60)), (0, 0));
+
+        // * A reused slot starts from zero
+        requests.record(at(61), window, false);
+        assert_eq!(requests.totals(at(61)), (0, 1));
+    }
+
+    #[test]
+    fn test_rolling_window_resize_keeps_recent_buckets() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut requests = RollingWindow::new(start);
+
+        requests.record(at(0), Duration::from_secs(60), false);
+        requests.record(at(50), Duration::from_secs(60), true);
+        assert_eq!(requests.totals(at(50)), (1, 1));
+
+        // * Shrinking to 20s keeps only the bucket that is still inside the window
+        requests.record(at(55), Duration::from_secs(20), true);
+        assert_eq!(requests.buckets.len(), 2);
+        assert_eq!(requests.totals(at(55)), (2, 0));
+    }
+
+    #[test]
+    fn test_fingerprint_scopes_domain_alerts() {
+        let a = Alert::new(AlertSeverity::Sev3, AlertType::DomainBanRateHigh, "a")
+            .with_context("domain", "a.com");
+        let b = Alert::new(AlertSeverity::Sev3, AlertType::DomainBanRateHigh, "b")
+            .with_context("domain", "b.com");
+        assert_eq!(a.fingerprint(), "DOMAIN_BAN_RATE_HIGH:a.com");
+        assert_ne!(a.fingerprint(), b.fingerprint());
+        assert_eq!(a.group_key(), b.group_key());
+        assert_eq!(sev3_alert("x").fingerprint(), "CUSTOM_WARNING");
+
+        // * One domain's cooldown does not suppress another domain's alert
+        let manager = AlertManager::new();
+        manager.fire_alert(a);
+        manager.fire_alert(b);
+        assert_eq!(manager.get_stats().active_alert_count, 2);
+    }
+
+    #[test]
+    fn test_domain_alerts_grouped_into_one_notification() {
+        let handler = Arc::new(RecordingHandler::default());
+        let mut manager = AlertManager::new();
+        manager.add_handler(handler.clone());
+
+        for domain in ["a.com", "b.com", "c.com", "d.com"] {
+            manager.fire_domain_ban_rate_alert(domain, 0.95);
+        }
+        assert_eq!(manager.get_stats().active_alert_count, 4);
+        // * Only the alert that opened the group was notified
+        assert_eq!(handler.handled.lock().unwrap().len(), 1);
+
+        manager.flush_alert_groups();
+        assert_eq!(handler.handled.lock().unwrap().len(), 1);
+
+        // * Shrinking the window closes the open group
+        manager.set_config(AlertConfig {
+            group_window_seconds: 0,
+            ..Default::default()
+        });
+        manager.flush_alert_groups();
+
+        let handled = handler.handled.lock().unwrap();
+        assert_eq!(handled.len(), 2);
+        let grouped = &handled[1];
+        assert_eq!(grouped.group_count, 3);
+        assert_eq!(grouped.alert_type, AlertType::DomainBanRateHigh);
+        assert_eq!(grouped.context.get("domains").unwrap(), "b.com,c.com,d.com");
+    }
+
+    #[test]
+    fn test_grouping_disabled() {
+        let handler = Arc::new(RecordingHandler::default());
+        let mut manager = AlertManager::with_config(AlertConfig {
+            group_window_seconds: 0,
+            ..Default::default()
+        });
+        manager.add_handler(handler.clone());
+
+        for domain in ["a.com", "b.com", "c.com"] {
+            manager.fire_circuit_breaker_alert(domain);
+        }
+        let handled = handler.handled.lock().unwrap();
+        assert_eq!(handled.len(), 3);
+        assert!(handled.iter().all(|alert| alert.group_count == 1));
+    }
+
     #[test]
     fn test_circuit_breaker_alert() {
         let config = AlertConfig {
@@ -0,0 +1,300 @@
+// * Per-Domain Statistics
+// * Fetch outcomes, bans, latencies and last-crawl times aggregated per host, so
+// * dashboards and the control plane can rank domains without scraping alerts
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Statistics of a single domain
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DomainStats {
+    pub domain: String,
+    pub success_count: u64,
+    pub failure_count: u64,
+    /// Failures classified as bans (also counted in `failure_count`)
+    pub ban_count: u64,
+    /// Fetches whose latency was measured
+    pub latency_samples: u64,
+    pub mean_latency_ms: Option<u64>,
+    pub max_latency_ms: Option<u64>,
+    /// Unix time of the most recent fetch, in milliseconds
+    pub last_crawled_ms: Option<u64>,
+}
+
+impl DomainStats {
+    /// Returns the number of recorded fetch outcomes
+    pub fn fetch_count(&self) -> u64 {
+        self.success_count + self.failure_count
+    }
+
+    /// Returns the share of fetches that were bans (0.0 without fetches)
+    pub fn ban_rate(&self) -> f64 {
+        match self.fetch_count() {
+            0 => 0.0,
+            total => self.ban_count as f64 / total as f64,
+        }
+    }
+}
+
+/// Ordering of query results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DomainSort {
+    /// Alphabetical by domain
+    #[default]
+    Domain,
+    /// Most fetches first
+    Fetches,
+    /// Most bans first
+    Bans,
+    /// Highest ban rate first
+    BanRate,
+    /// Highest mean latency first
+    Latency,
+    /// Most recently crawled first
+    LastCrawled,
+}
+
+/// Filter, order and size of a domain statistics query
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DomainStatsQuery {
+    pub sort: DomainSort,
+    /// Maximum number of domains returned (None returns all)
+    pub limit: Option<usize>,
+    /// Skips domains with fewer recorded fetches
+    pub min_fetches: u64,
+}
+
+/// Accumulated counters of one domain
+#[derive(Debug, Clone, Default)]
+struct DomainEntry {
+    success_count: u64,
+    failure_count: u64,
+    ban_count: u64,
+    latency_samples: u64,
+    total_latency_ms: u64,
+    max_latency_ms: u64,
+    last_crawled_ms: Option<u64>,
+}
+
+impl DomainEntry {
+    fn snapshot(&self, domain: &str) -> DomainStats {
+        let measured = self.latency_samples > 0;
+        DomainStats {
+            domain: domain.to_string(),
+            success_count: self.success_count,
+            failure_count: self.failure_count,
+            ban_count: self.ban_count,
+            latency_samples: self.latency_samples,
+            mean_latency_ms: self.total_latency_ms.checked_div(self.latency_samples),
+            max_latency_ms: measured.then_some(self.max_latency_ms),
+            last_crawled_ms: self.last_crawled_ms,
+        }
+    }
+}
+
+/// Process-wide per-domain statistics
+///
+/// The alert manager records every domain fetch outcome here; share the registry via
+/// `AlertManager::domain_stats` to read it.
+///
+/// # Example
+/// ```ignore
+/// let registry = alerts.domain_stats();
+/// registry.record_latency("example.com", Duration::from_millis(120));
+/// let slowest = registry.query(&DomainStatsQuery {
+///     sort: DomainSort::Latency,
+///     limit: Some(10),
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Debug, Default)]
+pub struct DomainStatsRegistry {
+    domains: RwLock<HashMap<String, DomainEntry>>,
+}
+
+impl DomainStatsRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a fetch outcome and returns the domain's updated statistics
+    pub fn record_outcome(&self, domain: &str, success: bool, is_ban: bool) -> DomainStats {
+        let domain = domain.to_lowercase();
+        let mut domains = self.domains.write().unwrap();
+        let entry = domains.entry(domain.clone()).or_default();
+
+        if success {
+            entry.success_count += 1;
+        } else {
+            entry.failure_count += 1;
+        }
+        if is_ban {
+            entry.ban_count += 1;
+        }
+        entry.last_crawled_ms = Some(now_ms());
+        entry.snapshot(&domain)
+    }
+
+    /// Records the latency of one fetch
+    pub fn record_latency(&self, domain: &str, latency: Duration) {
+        let latency_ms = latency.as_millis() as u64;
+        let mut domains = self.domains.write().unwrap();
+        let entry = domains.entry(domain.to_lowercase()).or_default();
+        entry.latency_samples += 1;
+        entry.total_latency_ms += latency_ms;
+        entry.max_latency_ms = entry.max_latency_ms.max(latency_ms);
+    }
+
+    /// Returns one domain's statistics
+    pub fn get(&self, domain: &str) -> Option<DomainStats> {
+        let domain = domain.to_lowercase();
+        self.domains
+            .read()
+            .unwrap()
+            .get(&domain)
+            .map(|entry| entry.snapshot(&domain))
+    }
+
+    /// Returns the statistics of the domains matching a query, in its order
+    pub fn query(&self, query: &DomainStatsQuery) -> Vec<DomainStats> {
+        let mut stats: Vec<DomainStats> = self
+            .domains
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(domain, entry)| entry.snapshot(domain))
+            .filter(|stats| stats.fetch_count() >= query.min_fetches)
+            .collect();
+
+        // * Ties fall back to alphabetical order so results are stable
+        stats.sort_by(|a, b| a.domain.cmp(&b.domain));
+        match query.sort {
+            DomainSort::Domain => {}
+            DomainSort::Fetches => stats.sort_by_key(|s| Reverse(s.fetch_count())),
+            DomainSort::Bans => stats.sort_by_key(|s| Reverse(s.ban_count)),
+            DomainSort::BanRate => stats.sort_by(|a, b| b.ban_rate().total_cmp(&a.ban_rate())),
+            DomainSort::Latency => stats.sort_by_key(|s| Reverse(s.mean_latency_ms)),
+            DomainSort::LastCrawled => stats.sort_by_key(|s| Reverse(s.last_crawled_ms)),
+        }
+
+        if let Some(limit) = query.limit {
+            stats.truncate(limit);
+        }
+        stats
+    }
+
+    /// Returns the number of tracked domains
+    pub fn len(&self) -> usize {
+        self.domains.read().unwrap().len()
+    }
+
+    /// Returns true if no domain has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.domains.read().unwrap().is_empty()
+    }
+
+    /// Forgets every domain
+    pub fn clear(&self) {
+        self.domains.write().unwrap().clear();
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_outcomes_and_latency() {
+        let registry = DomainStatsRegistry::new();
+        registry.record_outcome("Example.com", true, false);
+        registry.record_outcome("example.com", false, true);
+        registry.record_latency("example.com", Duration::from_millis(100));
+        registry.record_latency("example.com", Duration::from_millis(300));
+
+        let stats = registry.get("EXAMPLE.COM").unwrap();
+        assert_eq!(stats.domain, "example.com");
+        assert_eq!(stats.fetch_count(), 2);
+        assert_eq!(stats.ban_count, 1);
+        assert!((stats.ban_rate() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(stats.mean_latency_ms, Some(200));
+        assert_eq!(stats.max_latency_ms, Some(300));
+        assert!(stats.last_crawled_ms.is_some());
+        assert!(registry.get("other.com").is_none());
+    }
+
+    #[test]
+    fn test_unmeasured_latency_is_none() {
+        let registry = DomainStatsRegistry::new();
+        let stats = registry.record_outcome("example.com", true, false);
+        assert_eq!(stats.mean_latency_ms, None);
+        assert_eq!(stats.max_latency_ms, None);
+    }
+
+    #[test]
+    fn test_query_sorts_filters_and_limits() {
+        let registry = DomainStatsRegistry::new();
+        for _ in 0..3 {
+            registry.record_outcome("busy.com", true, false);
+        }
+        registry.record_outcome("banned.com", false, true);
+        registry.record_outcome("banned.com", true, false);
+        registry.record_outcome("quiet.com", true, false);
+        registry.record_latency("quiet.com", Duration::from_millis(900));
+
+        let domains = |query: DomainStatsQuery| -> Vec<String> {
+            registry.query(&query).into_iter().map(|s| s.domain).collect()
+        };
+        assert_eq!(domains(DomainStatsQuery::default()), ["banned.com", "busy.com", "quiet.com"]);
+        assert_eq!(
+            domains(DomainStatsQuery {
+                sort: DomainSort::Fetches,
+                ..Default::default()
+            }),
+            ["busy.com", "banned.com", "quiet.com"]
+        );
+        assert_eq!(
+            domains(DomainStatsQuery {
+                sort: DomainSort::BanRate,
+                limit: Some(1),
+                ..Default::default()
+            }),
+            ["banned.com"]
+        );
+        assert_eq!(
+            domains(DomainStatsQuery {
+                sort: DomainSort::Latency,
+                min_fetches: 1,
+                limit: Some(1),
+            }),
+            ["quiet.com"]
+        );
+        assert_eq!(
+            domains(DomainStatsQuery {
+                min_fetches: 2,
+                ..Default::default()
+            }),
+            ["banned.com", "busy.com"]
+        );
+    }
+
+    #[test]
+    fn test_query_deserializes_from_params() {
+        let query: DomainStatsQuery =
+            serde_json::from_str(r#"{"sort": "ban_rate", "limit": 5}"#).unwrap();
+        assert_eq!(query.sort, DomainSort::BanRate);
+        assert_eq!(query.limit, Some(5));
+        assert_eq!(query.min_fetches, 0);
+    }
+}
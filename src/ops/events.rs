@@ -0,0 +1,214 @@
+// * Live Crawl Events
+// * Process-wide event bus streamed as Server-Sent Events from the ops server's /events
+// * endpoint, so dashboards can follow a crawl without polling Prometheus
+
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+// * Events buffered per subscriber; slower subscribers skip ahead
+const EVENT_BUFFER: usize = 1024;
+
+// * SSE comment interval that keeps idle connections (and proxies) alive
+const KEEP_ALIVE_SECS: u64 = 15;
+
+lazy_static! {
+    static ref EVENTS: broadcast::Sender<EventEnvelope> = broadcast::channel(EVENT_BUFFER).0;
+}
+
+static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Something that happened during a crawl
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CrawlEvent {
+    CrawlStarted {
+        seeds: usize,
+        queued: usize,
+        max_pages: usize,
+        max_depth: usize,
+    },
+    PageFetched {
+        url: String,
+        domain: String,
+        bytes: u64,
+        slow_path: bool,
+    },
+    RecordPersisted {
+        url: String,
+        record_id: String,
+    },
+    AlertFired {
+        severity: String,
+        alert_type: String,
+        message: String,
+        context: HashMap<String, String>,
+    },
+    AlertResolved {
+        severity: String,
+        alert_type: String,
+        message: String,
+        context: HashMap<String, String>,
+        /// Time from first firing to resolution
+        active_ms: u64,
+    },
+    CrawlFinished {
+        pages_fetched: usize,
+        pages_persisted: usize,
+        fetch_errors: usize,
+        interrupted: bool,
+    },
+}
+
+impl CrawlEvent {
+    /// Event name used as the SSE `event:` field
+    pub fn name(&self) -> &'static str {
+        match self {
+            CrawlEvent::CrawlStarted { .. } => "crawl_started",
+            CrawlEvent::PageFetched { .. } => "page_fetched",
+            CrawlEvent::RecordPersisted { .. } => "record_persisted",
+            CrawlEvent::AlertFired { .. } => "alert_fired",
+            CrawlEvent::AlertResolved { .. } => "alert_resolved",
+            CrawlEvent::CrawlFinished { .. } => "crawl_finished",
+        }
+    }
+}
+
+/// A published event with its sequence number and wall-clock time
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EventEnvelope {
+    /// Increases by one per published event (gaps mean a subscriber fell behind)
+    pub seq: u64,
+    pub timestamp_ms: u64,
+    #[serde(flatten)]
+    pub event: CrawlEvent,
+}
+
+impl EventEnvelope {
+    /// Formats the event as one SSE message
+    pub fn to_sse(&self) -> String {
+        let data = serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string());
+        format!("id: {}\nevent: {}\ndata: {}\n\n", self.seq, self.event.name(), data)
+    }
+}
+
+/// Publishes an event to every live subscriber (dropped when nobody listens)
+pub fn publish_event(event: CrawlEvent) {
+    if EVENTS.receiver_count() == 0 {
+        return;
+    }
+    let envelope = EventEnvelope {
+        seq: SEQUENCE.fetch_add(1, Ordering::Relaxed),
+        timestamp_ms: now_ms(),
+        event,
+    };
+    let _ = EVENTS.send(envelope);
+}
+
+/// Subscribes to events published from now on
+pub fn subscribe_events() -> broadcast::Receiver<EventEnvelope> {
+    EVENTS.subscribe()
+}
+
+/// Builds the streaming `text/event-stream` response served at /events
+pub(crate) fn sse_response() -> hyper::Response<hyper::Body> {
+    let mut events = subscribe_events();
+    let (mut sender, body) = hyper::Body::channel();
+
+    tokio::spawn(async move {
+        let mut keep_alive = tokio::time::interval(Duration::from_secs(KEEP_ALIVE_SECS));
+        loop {
+            let chunk = tokio::select! {
+                received = events.recv() => match received {
+                    Ok(envelope) => envelope.to_sse(),
+                    Err(RecvError::Lagged(skipped)) => format!(": skipped {} events\n\n", skipped),
+                    Err(RecvError::Closed) => break,
+                },
+                _ = keep_alive.tick() => ": keep-alive\n\n".to_string(),
+            };
+            // * The client went away
+            if sender.send_data(chunk.into()).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    hyper::Response::builder()
+        .status(200)
+        .header("Content-Type", "text/event-stream")
+        .header("Cache-Control", "no-cache")
+        .header("Access-Control-Allow-Origin", "*")
+        .body(body)
+        .unwrap()
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::body::HttpBody;
+
+    fn persisted(url: &str) -> CrawlEvent {
+        CrawlEvent::RecordPersisted {
+            url: url.to_string(),
+            record_id: "rec-1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_sse_format() {
+        let envelope = EventEnvelope {
+            seq: 7,
+            timestamp_ms: 1_700_000_000_000,
+            event: persisted("https://example.com/"),
+        };
+        assert_eq!(
+            envelope.to_sse(),
+            "id: 7\nevent: record_persisted\ndata: {\"seq\":7,\"timestamp_ms\":1700000000000,\
+             \"type\":\"record_persisted\",\"url\":\"https://example.com/\",\"record_id\":\"rec-1\"}\n\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_events() {
+        let mut events = subscribe_events();
+        publish_event(persisted("https://events-bus.example/"));
+
+        // * Other tests publish to the same bus
+        loop {
+            let envelope = events.recv().await.unwrap();
+            if envelope.event == persisted("https://events-bus.example/") {
+                assert!(envelope.timestamp_ms > 0);
+                break;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sse_response_streams_events() {
+        let response = sse_response();
+        assert_eq!(response.headers()["Content-Type"], "text/event-stream");
+        let mut body = response.into_body();
+
+        publish_event(persisted("https://events-sse.example/"));
+        let mut streamed = String::new();
+        while !streamed.contains("events-sse.example") {
+            let chunk = tokio::time::timeout(Duration::from_secs(5), body.data())
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap();
+            streamed.push_str(std::str::from_utf8(&chunk).unwrap());
+        }
+        assert!(streamed.contains("event: record_persisted\n"));
+    }
+}
@@ -3,6 +3,11 @@
 // * This module provides metrics, logging, and alerting infrastructure
 
 pub mod alerting;
+pub mod domain_stats;
+pub mod events;
+pub mod otel;
+pub mod resource_monitor;
+pub mod shutdown;
 pub mod telemetry;
 
 // * Re-exports for convenient access
@@ -10,14 +15,28 @@ pub use alerting::{
     sev1_alert, sev3_alert, Alert, AlertConfig, AlertHandler, AlertManager, AlertManagerStats,
     AlertSeverity, AlertType, LoggingHandler,
 };
+pub use domain_stats::{DomainSort, DomainStats, DomainStatsQuery, DomainStatsRegistry};
+pub use events::{publish_event, subscribe_events, CrawlEvent, EventEnvelope};
+pub use resource_monitor::{MemorySample, ResourceMonitor, ResourceMonitorConfig};
+pub use shutdown::{
+    wait_for_termination, Coordinator, ShutdownConfig, ShutdownReport, ShutdownSignal,
+};
 pub use telemetry::{
     decrement_active_crawlers, get_metrics_string, increment_active_crawlers, init_tracing,
     init_tracing_pretty, init_tracing_with_level, record_bytes_downloaded, record_bytes_uploaded,
-    record_fast_path_duration, record_hard_ban, record_page_processed, record_request_failure,
-    record_request_success, record_slow_path_duration, record_soft_ban, set_active_crawlers,
+    record_dedup_duplicate, record_dedup_evictions, record_dns_lookup, record_enrichment_batch_duration, record_enrichment_summary,
+    record_enrichment_throttle, record_gc_run, record_records_expired,
+    record_content_change, record_fast_path_duration, record_hard_ban, record_http_response, record_page_processed, record_policy_skip,
+    record_preflight, record_render_wait, record_browser_recycle, record_page_limit, record_page_diagnostic, record_dispatch_wait, record_fetch_retry, record_snapshot_eviction, record_site_rule_match, record_llm_extraction,
+    record_redirect, record_refinery_document, record_refinery_stage_duration, record_robots_blocked, record_request_failure, record_response_body_bytes,
+    record_response_encoding,
+    record_request_success, record_slow_path_duration, record_soft_ban, record_truncated_page,
+    record_webhook_delivery,
+    set_active_crawlers, set_dedup_index_size, set_enrichment_spend,
     set_domain_ban_rate, set_global_error_rate, set_global_success_rate, set_memory_usage_percent,
-    set_queue_depth, set_throughput_mbps, start_metrics_server, start_metrics_server_default,
-    MetricsServerHandle, StatsCollector,
+    set_queue_depth, set_lane_utilization, set_throughput_mbps, start_metrics_server, start_metrics_server_default,
+    start_metrics_server_with_stats, global_stats, start_stats_publisher, IntervalStats,
+    MetricsServerHandle, StatsCollector, StatsSnapshot, TelemetryConfig,
 };
 
 #[cfg(test)]
@@ -0,0 +1,144 @@
+// * [NFR-01] Distributed Tracing
+// * Per-URL spans (page -> fetch / render / refinery / dedup / persist, then enrich) are
+// * plain `tracing` spans; the `otel` feature exports them over OTLP as one trace per URL
+
+use tracing::Span;
+
+#[cfg(feature = "otel")]
+use lazy_static::lazy_static;
+#[cfg(feature = "otel")]
+use std::collections::HashMap;
+#[cfg(feature = "otel")]
+use std::sync::Mutex;
+
+/// `service.name` reported with exported spans
+pub const SERVICE_NAME: &str = "titan-flow";
+
+// * Page contexts awaiting enrichment; beyond this, enrich spans start their own trace
+#[cfg(feature = "otel")]
+const MAX_PENDING_CONTEXTS: usize = 10_000;
+
+#[cfg(feature = "otel")]
+lazy_static! {
+    // * Record ID -> trace context of the page span that persisted it
+    static ref PENDING: Mutex<HashMap<String, opentelemetry::Context>> = Mutex::new(HashMap::new());
+}
+
+/// Builds the tracing layer that exports spans to an OTLP gRPC collector
+///
+/// # Example
+/// ```ignore
+/// tracing_subscriber::registry()
+///     .with(otlp_layer("http://localhost:4317")?)
+///     .init();
+/// // ... on exit
+/// shutdown_tracing();
+/// ```
+#[cfg(feature = "otel")]
+pub fn otlp_layer<S>(
+    endpoint: &str,
+) -> Result<
+    tracing_opentelemetry::OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>,
+    opentelemetry::trace::TraceError,
+>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    use opentelemetry_otlp::WithExportConfig;
+
+    let resource = opentelemetry_sdk::Resource::new(vec![opentelemetry::KeyValue::new(
+        "service.name",
+        SERVICE_NAME,
+    )]);
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(endpoint))
+        .with_trace_config(opentelemetry_sdk::trace::config().with_resource(resource))
+        .install_batch(opentelemetry_sdk::runtime::Tokio)?;
+    Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Flushes buffered spans and stops the exporter
+///
+/// Blocks until the export completes, so call it from a blocking context
+/// (e.g. `tokio::task::spawn_blocking`).
+#[cfg(feature = "otel")]
+pub fn shutdown_tracing() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
+/// Remembers a page span so the record's later enrichment joins the same trace
+///
+/// A no-op unless the `otel` feature is enabled.
+pub fn hand_off_span(record_id: &str, span: &Span) {
+    #[cfg(feature = "otel")]
+    {
+        use opentelemetry::trace::TraceContextExt;
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let context = span.context();
+        // * Without an exporter layer there is no trace to continue
+        if !context.span().span_context().is_valid() {
+            return;
+        }
+        let mut pending = PENDING.lock().unwrap();
+        if pending.len() < MAX_PENDING_CONTEXTS {
+            pending.insert(record_id.to_string(), context);
+        }
+    }
+    #[cfg(not(feature = "otel"))]
+    let _ = (record_id, span);
+}
+
+/// Parents `span` under the page span handed off for `record_id`, if any
+///
+/// A no-op unless the `otel` feature is enabled.
+pub fn adopt_span(record_id: &str, span: &Span) {
+    #[cfg(feature = "otel")]
+    {
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        if let Some(context) = PENDING.lock().unwrap().remove(record_id) {
+            span.set_parent(context);
+        }
+    }
+    #[cfg(not(feature = "otel"))]
+    let _ = (record_id, span);
+}
+
+#[cfg(all(test, feature = "otel"))]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::{TraceContextExt, TracerProvider as _};
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_enrich_span_joins_page_trace() {
+        let provider = opentelemetry_sdk::trace::TracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let page = tracing::info_span!("page", url = "https://example.com/");
+            hand_off_span("record-1", &page);
+            let page_trace = page.context().span().span_context().trace_id();
+
+            let enrich = tracing::info_span!("enrich");
+            adopt_span("record-1", &enrich);
+            assert_eq!(enrich.context().span().span_context().trace_id(), page_trace);
+
+            // * A context is handed over once
+            let again = tracing::info_span!("enrich");
+            adopt_span("record-1", &again);
+            assert_ne!(again.context().span().span_context().trace_id(), page_trace);
+        });
+    }
+
+    #[test]
+    fn test_hand_off_without_exporter_is_ignored() {
+        let span = tracing::info_span!("page");
+        hand_off_span("record-untraced", &span);
+        assert!(!PENDING.lock().unwrap().contains_key("record-untraced"));
+    }
+}
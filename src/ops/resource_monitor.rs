@@ -0,0 +1,281 @@
+// * [NFR-02] Resource Monitor - Memory Watchdog and Backpressure
+// * Samples process RSS and system memory, publishes titan_memory_usage_percent, raises
+// * MemoryPressure alerts and signals the dispatcher to stop admitting work
+
+use super::alerting::{AlertManager, AlertType};
+use super::telemetry::set_memory_usage_percent;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use sysinfo::{Pid, System};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+// * Pressure enters above the high watermark and clears below the low one (hysteresis)
+const DEFAULT_HIGH_WATERMARK_PERCENT: f64 = 90.0;
+const DEFAULT_LOW_WATERMARK_PERCENT: f64 = 85.0;
+const DEFAULT_SAMPLE_INTERVAL_MS: u64 = 1_000;
+
+/// Memory watchdog thresholds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResourceMonitorConfig {
+    /// Usage percentage above which backpressure is applied
+    pub high_watermark_percent: f64,
+    /// Usage percentage below which backpressure is released
+    pub low_watermark_percent: f64,
+    /// Time between memory samples
+    pub sample_interval_ms: u64,
+    /// Process RSS budget; usage is measured against it instead of system RAM when set
+    pub memory_limit_bytes: Option<u64>,
+}
+
+impl Default for ResourceMonitorConfig {
+    fn default() -> Self {
+        Self {
+            high_watermark_percent: DEFAULT_HIGH_WATERMARK_PERCENT,
+            low_watermark_percent: DEFAULT_LOW_WATERMARK_PERCENT,
+            sample_interval_ms: DEFAULT_SAMPLE_INTERVAL_MS,
+            memory_limit_bytes: None,
+        }
+    }
+}
+
+/// One memory reading
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemorySample {
+    /// Resident set size of this process
+    pub rss_bytes: u64,
+    pub system_used_bytes: u64,
+    pub system_total_bytes: u64,
+    /// Usage against the configured budget (or system RAM), 0-100
+    pub usage_percent: f64,
+}
+
+/// Watches memory usage and exposes a backpressure signal
+///
+/// # Example
+/// ```ignore
+/// let monitor = Arc::new(ResourceMonitor::new().with_alert_manager(alerts));
+/// let handle = Arc::clone(&monitor).spawn();
+/// if !monitor.is_under_pressure() {
+///     // * admit more work
+/// }
+/// handle.abort();
+/// ```
+pub struct ResourceMonitor {
+    config: ResourceMonitorConfig,
+    alerts: Option<Arc<AlertManager>>,
+    system: Mutex<System>,
+    pid: Option<Pid>,
+    pressure: watch::Sender<bool>,
+}
+
+impl std::fmt::Debug for ResourceMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourceMonitor")
+            .field("config", &self.config)
+            .field("under_pressure", &self.is_under_pressure())
+            .finish()
+    }
+}
+
+impl ResourceMonitor {
+    /// Creates a monitor with default watermarks
+    pub fn new() -> Self {
+        Self::with_config(ResourceMonitorConfig::default())
+    }
+
+    /// Creates a monitor with custom watermarks
+    pub fn with_config(config: ResourceMonitorConfig) -> Self {
+        Self {
+            config,
+            alerts: None,
+            system: Mutex::new(System::new()),
+            pid: sysinfo::get_current_pid().ok(),
+            pressure: watch::Sender::new(false),
+        }
+    }
+
+    /// Fires (and resolves) `MemoryPressure` alerts through the given manager
+    pub fn with_alert_manager(mut self, alerts: Arc<AlertManager>) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
+    /// Returns the monitor's thresholds
+    pub fn config(&self) -> &ResourceMonitorConfig {
+        &self.config
+    }
+
+    /// Returns true while backpressure is applied
+    pub fn is_under_pressure(&self) -> bool {
+        *self.pressure.borrow()
+    }
+
+    /// Subscribes to backpressure changes
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.pressure.subscribe()
+    }
+
+    /// Reads current process and system memory
+    pub fn sample(&self) -> MemorySample {
+        let mut system = self.system.lock().unwrap();
+        system.refresh_memory();
+        let rss_bytes = self
+            .pid
+            .filter(|pid| system.refresh_process(*pid))
+            .and_then(|pid| system.process(pid))
+            .map_or(0, |process| process.memory());
+        let system_used_bytes = system.used_memory();
+        let system_total_bytes = system.total_memory();
+
+        let (used, budget) = match self.config.memory_limit_bytes {
+            Some(limit) => (rss_bytes, limit),
+            None => (system_used_bytes, system_total_bytes),
+        };
+        let usage_percent = if budget > 0 {
+            (used as f64 / budget as f64 * 100.0).min(100.0)
+        } else {
+            0.0
+        };
+
+        MemorySample {
+            rss_bytes,
+            system_used_bytes,
+            system_total_bytes,
+            usage_percent,
+        }
+    }
+
+    /// Applies one usage reading; returns the new pressure state when it changed
+    pub fn observe(&self, usage_percent: f64) -> Option<bool> {
+        set_memory_usage_percent(usage_percent);
+
+        let under_pressure = self.is_under_pressure();
+        if !under_pressure && usage_percent > self.config.high_watermark_percent {
+            warn!(
+                usage_percent = format!("{:.1}", usage_percent),
+                threshold = self.config.high_watermark_percent,
+                "Memory pressure activated; pausing dispatch"
+            );
+            self.pressure.send_replace(true);
+            if let Some(alerts) = &self.alerts {
+                alerts.fire_memory_pressure_alert(usage_percent);
+            }
+            Some(true)
+        } else if under_pressure && usage_percent < self.config.low_watermark_percent {
+            info!(
+                usage_percent = format!("{:.1}", usage_percent),
+                threshold = self.config.low_watermark_percent,
+                "Memory pressure released; resuming dispatch"
+            );
+            self.pressure.send_replace(false);
+            if let Some(alerts) = &self.alerts {
+                alerts.resolve_alert(&AlertType::MemoryPressure);
+            }
+            Some(false)
+        } else {
+            debug!(
+                usage_percent = format!("{:.1}", usage_percent),
+                under_pressure, "Memory status"
+            );
+            None
+        }
+    }
+
+    /// Spawns the sampling loop
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let period = Duration::from_millis(self.config.sample_interval_ms.max(1));
+            let mut tick = tokio::time::interval(period);
+            loop {
+                tick.tick().await;
+                let monitor = Arc::clone(&self);
+                // * sysinfo reads /proc synchronously
+                let Ok(sample) = tokio::task::spawn_blocking(move || monitor.sample()).await else {
+                    continue;
+                };
+                self.observe(sample.usage_percent);
+            }
+        })
+    }
+}
+
+impl Default for ResourceMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_reports_valid_usage() {
+        let sample = ResourceMonitor::new().sample();
+        assert!((0.0..=100.0).contains(&sample.usage_percent));
+        assert!(sample.rss_bytes > 0);
+        assert!(sample.system_total_bytes >= sample.system_used_bytes);
+    }
+
+    #[test]
+    fn test_memory_limit_measures_rss() {
+        let monitor = ResourceMonitor::with_config(ResourceMonitorConfig {
+            memory_limit_bytes: Some(1),
+            ..Default::default()
+        });
+        assert!((monitor.sample().usage_percent - 100.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_hysteresis() {
+        let monitor = ResourceMonitor::new();
+        let mut pressure = monitor.subscribe();
+
+        assert_eq!(monitor.observe(89.0), None);
+        assert_eq!(monitor.observe(91.0), Some(true));
+        assert!(monitor.is_under_pressure());
+        assert!(pressure.has_changed().unwrap());
+        assert!(*pressure.borrow_and_update());
+
+        // * Between the watermarks the state holds
+        assert_eq!(monitor.observe(87.0), None);
+        assert!(monitor.is_under_pressure());
+
+        assert_eq!(monitor.observe(80.0), Some(false));
+        assert!(!monitor.is_under_pressure());
+    }
+
+    #[test]
+    fn test_pressure_alert_fires_and_resolves() {
+        let alerts = Arc::new(AlertManager::new());
+        let monitor = ResourceMonitor::new().with_alert_manager(Arc::clone(&alerts));
+
+        monitor.observe(95.0);
+        assert!(alerts.is_active(&AlertType::MemoryPressure));
+
+        monitor.observe(50.0);
+        assert!(!alerts.is_active(&AlertType::MemoryPressure));
+        assert_eq!(alerts.get_stats().resolved_alert_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_spawned_monitor_applies_budget() {
+        let monitor = Arc::new(ResourceMonitor::with_config(ResourceMonitorConfig {
+            memory_limit_bytes: Some(1),
+            sample_interval_ms: 10,
+            ..Default::default()
+        }));
+        let mut pressure = monitor.subscribe();
+        let handle = Arc::clone(&monitor).spawn();
+
+        tokio::time::timeout(Duration::from_secs(5), pressure.wait_for(|p| *p))
+            .await
+            .unwrap()
+            .unwrap();
+        handle.abort();
+    }
+}
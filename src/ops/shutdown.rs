@@ -0,0 +1,329 @@
+// * [NFR-02] Graceful Shutdown Coordinator
+// * Ties SIGINT/SIGTERM to the orchestrator, flush hooks, AI workers and the metrics server
+// * so an interrupted crawl stops dispatching, drains, persists and reports cleanly
+
+use super::telemetry::MetricsServerHandle;
+use crate::persistence::WorkerHandle;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+// * Maximum time each flush hook or worker gets before it is abandoned
+const DEFAULT_STEP_TIMEOUT_MS: u64 = 10_000;
+
+/// Boxed flush hook future
+type FlushFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+
+/// Deferred flush action run during shutdown
+type FlushHook = Box<dyn FnOnce() -> FlushFuture + Send>;
+
+/// Configuration for the shutdown coordinator
+#[derive(Debug, Clone)]
+pub struct ShutdownConfig {
+    /// Maximum time each flush hook or worker shutdown may take
+    pub step_timeout_ms: u64,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            step_timeout_ms: DEFAULT_STEP_TIMEOUT_MS,
+        }
+    }
+}
+
+/// Cloneable view of the shutdown flag, handed to long-running loops
+#[derive(Debug, Clone)]
+pub struct ShutdownSignal {
+    rx: watch::Receiver<bool>,
+}
+
+impl ShutdownSignal {
+    /// Returns true once shutdown has been requested
+    pub fn is_triggered(&self) -> bool {
+        *self.rx.borrow()
+    }
+
+    /// Resolves when shutdown is requested (never, if the coordinator is dropped first)
+    pub async fn triggered(&self) {
+        let mut rx = self.rx.clone();
+        if rx.wait_for(|triggered| *triggered).await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+/// What the coordinator did while shutting down
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// Flush hooks that completed
+    pub flushed: Vec<String>,
+    /// Workers and servers that were stopped
+    pub stopped: Vec<String>,
+    /// Steps that failed, with their error
+    pub failed: Vec<(String, String)>,
+    /// Steps abandoned after the step timeout
+    pub timed_out: Vec<String>,
+}
+
+impl ShutdownReport {
+    /// Returns true if every step completed
+    pub fn is_clean(&self) -> bool {
+        self.failed.is_empty() && self.timed_out.is_empty()
+    }
+}
+
+/// Coordinates an orderly shutdown across the pipeline
+///
+/// # Example
+/// ```ignore
+/// let coordinator = Arc::new(Coordinator::new());
+/// coordinator.listen_for_signals();
+///
+/// let orchestrator = Orchestrator::new().with_shutdown(coordinator.signal());
+/// let report = orchestrator.run(seeds, config).await?; // * Returns early on Ctrl+C
+///
+/// let shutdown = coordinator.shutdown().await;
+/// ```
+pub struct Coordinator {
+    config: ShutdownConfig,
+    trigger: watch::Sender<bool>,
+    hooks: Mutex<Vec<(String, FlushHook)>>,
+    workers: Mutex<Vec<(String, WorkerHandle)>>,
+    metrics_servers: Mutex<Vec<MetricsServerHandle>>,
+}
+
+impl std::fmt::Debug for Coordinator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Coordinator")
+            .field("config", &self.config)
+            .field("triggered", &self.is_triggered())
+            .finish()
+    }
+}
+
+impl Coordinator {
+    /// Creates a coordinator with default configuration
+    pub fn new() -> Self {
+        Self::with_config(ShutdownConfig::default())
+    }
+
+    /// Creates a coordinator with custom configuration
+    pub fn with_config(config: ShutdownConfig) -> Self {
+        Self {
+            config,
+            trigger: watch::Sender::new(false),
+            hooks: Mutex::new(Vec::new()),
+            workers: Mutex::new(Vec::new()),
+            metrics_servers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns a signal that observes this coordinator
+    pub fn signal(&self) -> ShutdownSignal {
+        ShutdownSignal {
+            rx: self.trigger.subscribe(),
+        }
+    }
+
+    /// Requests shutdown without running the teardown steps
+    pub fn trigger(&self) {
+        if !self.trigger.send_replace(true) {
+            info!("Shutdown requested");
+        }
+    }
+
+    /// Returns true once shutdown has been requested
+    pub fn is_triggered(&self) -> bool {
+        *self.trigger.borrow()
+    }
+
+    /// Triggers shutdown on SIGINT (Ctrl+C) or SIGTERM
+    pub fn listen_for_signals(self: &Arc<Self>) -> JoinHandle<()> {
+        let coordinator = Arc::clone(self);
+        tokio::spawn(async move {
+            wait_for_termination().await;
+            coordinator.trigger();
+        })
+    }
+
+    /// Registers a flush step (e.g. dedup snapshot, buffered writes), run in order
+    pub fn register_flush<F, Fut>(&self, name: impl Into<String>, flush: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        let hook: FlushHook = Box::new(move || Box::pin(flush()));
+        self.hooks.lock().unwrap().push((name.into(), hook));
+    }
+
+    /// Registers an AI enrichment worker to stop after the flush steps
+    pub fn register_worker(&self, name: impl Into<String>, handle: WorkerHandle) {
+        self.workers.lock().unwrap().push((name.into(), handle));
+    }
+
+    /// Registers a metrics server to stop last, so final metrics stay scrapeable
+    pub fn register_metrics_server(&self, handle: MetricsServerHandle) {
+        self.metrics_servers.lock().unwrap().push(handle);
+    }
+
+    /// Triggers shutdown and runs flush hooks, then stops workers and metrics servers
+    ///
+    /// Each step is bounded by `step_timeout_ms`. Steps only run once; calling
+    /// this again returns an empty report.
+    pub async fn shutdown(&self) -> ShutdownReport {
+        self.trigger();
+
+        let timeout = Duration::from_millis(self.config.step_timeout_ms);
+        let mut report = ShutdownReport::default();
+
+        let hooks = std::mem::take(&mut *self.hooks.lock().unwrap());
+        for (name, hook) in hooks {
+            match tokio::time::timeout(timeout, hook()).await {
+                Ok(Ok(())) => report.flushed.push(name),
+                Ok(Err(e)) => {
+                    warn!(step = %name, error = %e, "Shutdown flush failed");
+                    report.failed.push((name, e));
+                }
+                Err(_) => {
+                    warn!(step = %name, "Shutdown flush timed out");
+                    report.timed_out.push(name);
+                }
+            }
+        }
+
+        let workers = std::mem::take(&mut *self.workers.lock().unwrap());
+        for (name, handle) in workers {
+            match tokio::time::timeout(timeout, handle.shutdown()).await {
+                Ok(()) => report.stopped.push(name),
+                Err(_) => {
+                    warn!(worker = %name, "Worker did not stop in time");
+                    report.timed_out.push(name);
+                }
+            }
+        }
+
+        let servers = std::mem::take(&mut *self.metrics_servers.lock().unwrap());
+        for handle in servers {
+            handle.shutdown();
+            report.stopped.push("metrics-server".to_string());
+        }
+
+        info!(
+            flushed = report.flushed.len(),
+            stopped = report.stopped.len(),
+            failed = report.failed.len(),
+            timed_out = report.timed_out.len(),
+            "Shutdown complete"
+        );
+        report
+    }
+}
+
+impl Default for Coordinator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Resolves on the first SIGINT or SIGTERM
+pub async fn wait_for_termination() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = sigterm.recv() => {}
+                }
+            }
+            Err(e) => {
+                warn!(error = %e, "SIGTERM handler unavailable; listening for Ctrl+C only");
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::{AIEnrichmentWorker, InMemoryRecordStore, WorkerConfig};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[tokio::test]
+    async fn test_signal_observes_trigger() {
+        let coordinator = Coordinator::new();
+        let signal = coordinator.signal();
+        assert!(!signal.is_triggered());
+
+        let waiter = tokio::spawn({
+            let signal = signal.clone();
+            async move { signal.triggered().await }
+        });
+        coordinator.trigger();
+
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(signal.is_triggered());
+        assert!(coordinator.is_triggered());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_runs_steps_in_order() {
+        let coordinator = Coordinator::new();
+        let flushed = Arc::new(AtomicBool::new(false));
+
+        let flag = Arc::clone(&flushed);
+        coordinator.register_flush("dedup-snapshot", move || async move {
+            flag.store(true, Ordering::SeqCst);
+            Ok(())
+        });
+        coordinator.register_flush("records", || async { Err("disk full".to_string()) });
+
+        let store = Arc::new(InMemoryRecordStore::new());
+        let worker = AIEnrichmentWorker::with_config(WorkerConfig {
+            poll_interval_ms: 10,
+            ..Default::default()
+        });
+        let handle = worker.start(Arc::clone(&store), Arc::clone(&store)).await;
+        coordinator.register_worker("enrichment", handle);
+
+        let report = coordinator.shutdown().await;
+
+        assert!(flushed.load(Ordering::SeqCst));
+        assert_eq!(report.flushed, vec!["dedup-snapshot"]);
+        assert_eq!(report.failed, vec![("records".to_string(), "disk full".to_string())]);
+        assert_eq!(report.stopped, vec!["enrichment"]);
+        assert!(!report.is_clean());
+        assert!(coordinator.signal().is_triggered());
+
+        // * Steps are consumed by the first shutdown
+        assert_eq!(coordinator.shutdown().await, ShutdownReport::default());
+    }
+
+    #[tokio::test]
+    async fn test_slow_flush_times_out() {
+        let coordinator = Coordinator::with_config(ShutdownConfig { step_timeout_ms: 20 });
+        coordinator.register_flush("stuck", || async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(())
+        });
+
+        let report = coordinator.shutdown().await;
+        assert_eq!(report.timed_out, vec!["stuck"]);
+    }
+}
@@ -3,18 +3,28 @@
 
 use lazy_static::lazy_static;
 use prometheus::{
-    register_counter_vec, register_gauge, register_gauge_vec, register_histogram_vec,
-    CounterVec, Encoder, Gauge, GaugeVec, HistogramVec, TextEncoder,
+    register_counter, register_counter_vec, register_gauge, register_gauge_vec, register_histogram,
+    register_histogram_vec, Counter, CounterVec, Encoder, Gauge, GaugeVec, Histogram, HistogramVec,
+    TextEncoder,
 };
+use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
 use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
 // * Default metrics server port
 const DEFAULT_METRICS_PORT: u16 = 9000;
 
+// * Default tracing filter directive
+const DEFAULT_LOG_LEVEL: &str = "titan_flow=info,warn";
+
+// * Default interval between automatic throughput and success-rate publications
+const DEFAULT_STATS_INTERVAL_SECS: u64 = 10;
+
 lazy_static! {
     // * Active crawler count
     pub static ref CRAWLERS_ACTIVE: Gauge = register_gauge!(
@@ -82,12 +92,293 @@ lazy_static! {
         &["direction"]
     ).unwrap();
 
+    // * URLs kept out by the crawl policy
+    pub static ref POLICY_SKIPS_TOTAL: CounterVec = register_counter_vec!(
+        "titan_policy_skips_total",
+        "Total URLs skipped by the crawl policy by reason",
+        &["reason"]
+    ).unwrap();
+
+    // * Content changes detected between crawls
+    pub static ref CONTENT_CHANGES_TOTAL: CounterVec = register_counter_vec!(
+        "titan_content_changes_total",
+        "Total crawled pages by change classification",
+        &["kind"]
+    ).unwrap();
+
+    // * Response charsets decoded by the fast path
+    pub static ref RESPONSE_ENCODINGS_TOTAL: CounterVec = register_counter_vec!(
+        "titan_response_encodings_total",
+        "Total fast path responses by decoded character encoding",
+        &["encoding"]
+    ).unwrap();
+
+    // * Fast path body sizes before and after Content-Encoding is undone
+    pub static ref RESPONSE_BODY_BYTES_TOTAL: CounterVec = register_counter_vec!(
+        "titan_response_body_bytes_total",
+        "Total fast path response body bytes by content encoding and form",
+        &["content_encoding", "form"]
+    ).unwrap();
+
+    // * Webhook deliveries
+    pub static ref WEBHOOK_DELIVERIES_TOTAL: CounterVec = register_counter_vec!(
+        "titan_webhook_deliveries_total",
+        "Total records pushed to the webhook sink by result",
+        &["result"]
+    ).unwrap();
+
+    // * Refinery stage duration (clean, metadata, tables, entities, redaction, chunking)
+    pub static ref REFINERY_STAGE_DURATION_SECONDS: HistogramVec = register_histogram_vec!(
+        "titan_refinery_stage_duration_seconds",
+        "Refinery stage duration in seconds",
+        &["stage"],
+        vec![0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0]
+    ).unwrap();
+
+    // * Refined documents by content quality score bucket
+    pub static ref REFINERY_DOCUMENTS_TOTAL: CounterVec = register_counter_vec!(
+        "titan_refinery_documents_total",
+        "Total documents refined by quality score bucket",
+        &["quality"]
+    ).unwrap();
+
+    // * Pages cut short by a size or time limit
+    pub static ref TRUNCATED_PAGES_TOTAL: CounterVec = register_counter_vec!(
+        "titan_truncated_pages_total",
+        "Total pages truncated by a response size, DOM size or parse time limit",
+        &["reason"]
+    ).unwrap();
+
+    // * Documents in the near-duplicate (LSH) index
+    pub static ref DEDUP_DOCUMENTS_INDEXED: Gauge = register_gauge!(
+        "titan_dedup_documents_indexed",
+        "Documents held in the LSH near-duplicate index"
+    ).unwrap();
+
+    // * Estimated LSH index size
+    pub static ref DEDUP_INDEX_MEMORY_BYTES: Gauge = register_gauge!(
+        "titan_dedup_index_memory_bytes",
+        "Estimated memory held by the LSH index in bytes"
+    ).unwrap();
+
+    // * Duplicates caught by level
+    pub static ref DEDUP_DUPLICATES_TOTAL: CounterVec = register_counter_vec!(
+        "titan_dedup_duplicates_total",
+        "Total duplicate documents by dedup level",
+        &["level"]
+    ).unwrap();
+
+    // * LSH index evictions by reason
+    pub static ref DEDUP_EVICTIONS_TOTAL: CounterVec = register_counter_vec!(
+        "titan_dedup_evictions_total",
+        "Total documents evicted from the LSH index by reason",
+        &["reason"]
+    ).unwrap();
+
+    // * Enrichment batch latency
+    pub static ref ENRICHMENT_BATCH_DURATION_SECONDS: Histogram = register_histogram!(
+        "titan_enrichment_batch_duration_seconds",
+        "Enrichment batch duration in seconds",
+        vec![0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0]
+    ).unwrap();
+
+    // * Summaries generated by the enrichment worker
+    pub static ref ENRICHMENT_SUMMARIES_TOTAL: CounterVec = register_counter_vec!(
+        "titan_enrichment_summaries_total",
+        "Total summaries attempted by outcome",
+        &["outcome"]
+    ).unwrap();
+
+    pub static ref ENRICHMENT_SUMMARY_DURATION_SECONDS: Histogram = register_histogram!(
+        "titan_enrichment_summary_duration_seconds",
+        "Summary generation duration in seconds",
+        vec![0.001, 0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
+    ).unwrap();
+
+    // * Enrichment provider spend for the current UTC day
+    pub static ref ENRICHMENT_SPEND_USD: Gauge = register_gauge!(
+        "titan_enrichment_spend_usd",
+        "Estimated enrichment provider spend today in USD"
+    ).unwrap();
+
+    // * Enrichment calls delayed by a per-minute limit
+    pub static ref ENRICHMENT_THROTTLED_TOTAL: CounterVec = register_counter_vec!(
+        "titan_enrichment_throttled_total",
+        "Total enrichment provider calls delayed by rate limit",
+        &["limit"]
+    ).unwrap();
+
+    // * Soft-delete garbage collection
+    pub static ref GC_RECLAIMED_RECORDS_TOTAL: Counter = register_counter!(
+        "titan_gc_reclaimed_records_total",
+        "Total soft-deleted records permanently removed by garbage collection"
+    ).unwrap();
+
+    pub static ref GC_RUN_DURATION_SECONDS: Histogram = register_histogram!(
+        "titan_gc_run_duration_seconds",
+        "Garbage collection run duration in seconds",
+        vec![0.001, 0.01, 0.1, 0.5, 1.0, 5.0, 15.0, 60.0, 300.0]
+    ).unwrap();
+
+    // * TTL-based record expiry
+    pub static ref RECORDS_EXPIRED_TOTAL: CounterVec = register_counter_vec!(
+        "titan_records_expired_total",
+        "Total records soft-deleted by an expiry rule",
+        &["rule"]
+    ).unwrap();
+
+    // * DNS cache outcomes
+    pub static ref DNS_LOOKUPS_TOTAL: CounterVec = register_counter_vec!(
+        "titan_dns_lookups_total",
+        "Total DNS resolutions by result (hit, negative_hit, resolved, failed)",
+        &["result"]
+    ).unwrap();
+
+    // * Fast path time to response headers; new connections include DNS, TCP and TLS setup,
+    // * so the gap to reused connections is the handshake cost and the counts give the reuse rate
+    pub static ref HTTP_TIME_TO_HEADERS_SECONDS: HistogramVec = register_histogram_vec!(
+        "titan_http_time_to_headers_seconds",
+        "Fast path time to response headers by domain, protocol and connection (new or reused)",
+        &["domain", "protocol", "connection"],
+        vec![0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
+    ).unwrap();
+
+    // * Fast path redirect handling
+    pub static ref REDIRECTS_TOTAL: CounterVec = register_counter_vec!(
+        "titan_redirects_total",
+        "Total redirects by result (followed, meta_refresh, too_many_hops, loop, disallowed, invalid_location)",
+        &["result"]
+    ).unwrap();
+
+    // * HEAD preflight outcomes
+    pub static ref PREFLIGHT_TOTAL: CounterVec = register_counter_vec!(
+        "titan_preflight_total",
+        "Total HEAD preflights by result (passed, inconclusive, content_type, too_large)",
+        &["result"]
+    ).unwrap();
+
+    // * Declared size of responses a preflight kept from being downloaded
+    pub static ref PREFLIGHT_AVOIDED_BYTES_TOTAL: Counter = register_counter!(
+        "titan_preflight_avoided_bytes_total",
+        "Total Content-Length of fetches skipped after a HEAD preflight"
+    ).unwrap();
+
+    // * Time the slow path waited for a page to become ready
+    pub static ref RENDER_WAIT_SECONDS: HistogramVec = register_histogram_vec!(
+        "titan_render_wait_seconds",
+        "Slow path wait for page readiness by strategy and result (ready, timeout)",
+        &["strategy", "result"],
+        vec![0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 30.0, 60.0]
+    ).unwrap();
+
+    // * Slow path browser relaunches
+    pub static ref BROWSER_RECYCLES_TOTAL: CounterVec = register_counter_vec!(
+        "titan_browser_recycles_total",
+        "Slow path browser relaunches by reason (max_pages, max_age, page_limit)",
+        &["reason"]
+    ).unwrap();
+
+    // * Renders stopped by a watchdog
+    pub static ref PAGE_LIMIT_EXCEEDED_TOTAL: CounterVec = register_counter_vec!(
+        "titan_page_limit_exceeded_total",
+        "Slow path renders stopped by a watchdog, by limit (time, memory, cpu)",
+        &["limit"]
+    ).unwrap();
+
+    // * Time jobs spent queued in the dispatcher
+    pub static ref DISPATCH_QUEUE_WAIT_SECONDS: HistogramVec = register_histogram_vec!(
+        "titan_dispatch_queue_wait_seconds",
+        "Time a job waited in the dispatcher before a worker took it, by source (own, stolen)",
+        &["source"],
+        vec![0.001, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 15.0, 60.0]
+    ).unwrap();
+
+    // * Share of each lane's workers running a job
+    pub static ref DISPATCH_LANE_UTILIZATION: GaugeVec = register_gauge_vec!(
+        "titan_dispatch_lane_utilization",
+        "Share of a dispatcher lane's in-flight capacity in use (0 to 1)",
+        &["lane"]
+    ).unwrap();
+
+    // * Errors and warnings raised by rendered pages
+    pub static ref PAGE_DIAGNOSTICS_TOTAL: CounterVec = register_counter_vec!(
+        "titan_page_diagnostics_total",
+        "Console diagnostics raised by slow path renders, by type",
+        &["type"]
+    ).unwrap();
+
+    // * Fetch retries by category and whether one was scheduled or the budget ran out
+    pub static ref FETCH_RETRIES_TOTAL: CounterVec = register_counter_vec!(
+        "titan_fetch_retries_total",
+        "Transient fetch failures retried or given up on, by category and outcome",
+        &["category", "outcome"]
+    ).unwrap();
+
+    // * HTML snapshots evicted by reason
+    pub static ref SNAPSHOT_EVICTIONS_TOTAL: CounterVec = register_counter_vec!(
+        "titan_snapshot_evictions_total",
+        "Raw HTML snapshots evicted, by reason",
+        &["reason"]
+    ).unwrap();
+
+    // * Site rule selector lookups by field and whether they matched
+    pub static ref SITE_RULE_MATCHES_TOTAL: CounterVec = register_counter_vec!(
+        "titan_site_rule_matches_total",
+        "Site rule selector lookups, by field and outcome",
+        &["field", "outcome"]
+    ).unwrap();
+
+    // * Schema-guided LLM extractions by provider and outcome
+    pub static ref LLM_EXTRACTIONS_TOTAL: CounterVec = register_counter_vec!(
+        "titan_llm_extractions_total",
+        "LLM extractions, by provider and outcome",
+        &["provider", "outcome"]
+    ).unwrap();
+
+    // * robots.txt Disallow hits
+    pub static ref ROBOTS_BLOCKED_TOTAL: CounterVec = register_counter_vec!(
+        "titan_robots_blocked_total",
+        "Total URLs disallowed by robots.txt, by where they were dropped",
+        &["stage"]
+    ).unwrap();
+
     // * Queue depth
     pub static ref QUEUE_DEPTH: GaugeVec = register_gauge_vec!(
         "titan_queue_depth",
         "Current queue depth by queue name",
         &["queue"]
     ).unwrap();
+
+    // * Process-wide counters fed by the record_* functions
+    static ref GLOBAL_STATS: StatsCollector = StatsCollector::new();
+}
+
+/// Logging and metrics endpoint configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TelemetryConfig {
+    /// Tracing filter directive (e.g. "info", "titan_flow=debug")
+    pub log_level: String,
+    /// Emit JSON logs (false for human-readable output)
+    pub json_logs: bool,
+    /// Port for the Prometheus /metrics endpoint
+    pub metrics_port: u16,
+    /// OTLP gRPC collector for per-URL traces (e.g. "http://localhost:4317"; needs the `otel` feature)
+    pub otlp_endpoint: Option<String>,
+    /// Seconds between throughput and success-rate publications while a metrics server runs
+    pub stats_interval_secs: u64,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            log_level: DEFAULT_LOG_LEVEL.to_string(),
+            json_logs: true,
+            metrics_port: DEFAULT_METRICS_PORT,
+            otlp_endpoint: None,
+            stats_interval_secs: DEFAULT_STATS_INTERVAL_SECS,
+        }
+    }
 }
 
 /// Initializes the tracing subscriber with JSON formatting
@@ -135,6 +426,7 @@ pub fn init_tracing_pretty() {
 pub struct MetricsServerHandle {
     shutdown_tx: Option<oneshot::Sender<()>>,
     running: Arc<AtomicBool>,
+    stats_publisher: Option<JoinHandle<()>>,
 }
 
 impl MetricsServerHandle {
@@ -143,6 +435,9 @@ impl MetricsServerHandle {
         if let Some(tx) = self.shutdown_tx.take() {
             let _ = tx.send(());
         }
+        if let Some(publisher) = self.stats_publisher.take() {
+            publisher.abort();
+        }
         self.running.store(false, Ordering::Relaxed);
     }
 
@@ -170,6 +465,11 @@ impl MetricsServerHandle {
 /// }
 /// ```
 pub async fn start_metrics_server(port: u16) -> MetricsServerHandle {
+    start_metrics_server_with_stats(port, Duration::from_secs(DEFAULT_STATS_INTERVAL_SECS)).await
+}
+
+/// Starts the metrics server, publishing throughput and success rates every `stats_interval`
+pub async fn start_metrics_server_with_stats(port: u16, stats_interval: Duration) -> MetricsServerHandle {
     let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
     let running = Arc::new(AtomicBool::new(true));
     let running_clone = running.clone();
@@ -200,6 +500,7 @@ pub async fn start_metrics_server(port: u16) -> MetricsServerHandle {
     MetricsServerHandle {
         shutdown_tx: Some(shutdown_tx),
         running,
+        stats_publisher: Some(start_stats_publisher(stats_interval)),
     }
 }
 
@@ -225,6 +526,8 @@ async fn handle_metrics_request(
                 .body(hyper::Body::from(buffer))
                 .unwrap())
         }
+        // * Live crawl events as Server-Sent Events
+        "/events" => Ok(super::events::sse_response()),
         "/health" => Ok(hyper::Response::builder()
             .status(200)
             .body(hyper::Body::from("OK"))
@@ -252,11 +555,13 @@ pub fn get_metrics_string() -> String {
 /// Records a successful request
 pub fn record_request_success() {
     REQUESTS_TOTAL.with_label_values(&["success"]).inc();
+    GLOBAL_STATS.record_success();
 }
 
 /// Records a failed request
 pub fn record_request_failure() {
     REQUESTS_TOTAL.with_label_values(&["failure"]).inc();
+    GLOBAL_STATS.record_failure();
 }
 
 /// Records a soft ban (captcha/WAF detection)
@@ -323,6 +628,7 @@ pub fn record_bytes_downloaded(bytes: u64) {
     BYTES_TRANSFERRED_TOTAL
         .with_label_values(&["download"])
         .inc_by(bytes as f64);
+    GLOBAL_STATS.record_bytes(bytes);
 }
 
 /// Records bytes uploaded
@@ -330,6 +636,7 @@ pub fn record_bytes_uploaded(bytes: u64) {
     BYTES_TRANSFERRED_TOTAL
         .with_label_values(&["upload"])
         .inc_by(bytes as f64);
+    GLOBAL_STATS.record_bytes(bytes);
 }
 
 /// Records a page processed
@@ -339,6 +646,241 @@ pub fn record_page_processed(content_type: &str) {
         .inc();
 }
 
+/// Records a URL skipped by the crawl policy
+pub fn record_policy_skip(reason: &str) {
+    POLICY_SKIPS_TOTAL
+        .with_label_values(&[reason])
+        .inc();
+}
+
+/// Records the character encoding a response body was decoded from
+pub fn record_response_encoding(encoding: &str) {
+    RESPONSE_ENCODINGS_TOTAL
+        .with_label_values(&[encoding])
+        .inc();
+}
+
+/// Records a response body's size on the wire and after decompression
+pub fn record_response_body_bytes(content_encoding: &str, compressed: u64, decompressed: u64) {
+    RESPONSE_BODY_BYTES_TOTAL
+        .with_label_values(&[content_encoding, "compressed"])
+        .inc_by(compressed as f64);
+    RESPONSE_BODY_BYTES_TOTAL
+        .with_label_values(&[content_encoding, "decompressed"])
+        .inc_by(decompressed as f64);
+}
+
+/// Records the change classification of a crawled page
+pub fn record_content_change(kind: &str) {
+    CONTENT_CHANGES_TOTAL
+        .with_label_values(&[kind])
+        .inc();
+}
+
+/// Records the outcome of a webhook delivery (delivered, failed or dropped)
+pub fn record_webhook_delivery(result: &str) {
+    WEBHOOK_DELIVERIES_TOTAL
+        .with_label_values(&[result])
+        .inc();
+}
+
+/// Records how long a refinery stage took
+pub fn record_refinery_stage_duration(stage: &str, seconds: f64) {
+    REFINERY_STAGE_DURATION_SECONDS
+        .with_label_values(&[stage])
+        .observe(seconds);
+}
+
+/// Counts a refined document under its quality score bucket
+pub fn record_refinery_document(quality_score: f32) {
+    REFINERY_DOCUMENTS_TOTAL
+        .with_label_values(&[quality_bucket(quality_score)])
+        .inc();
+}
+
+/// Counts a page cut short by a size or time limit
+pub fn record_truncated_page(reason: &str) {
+    TRUNCATED_PAGES_TOTAL
+        .with_label_values(&[reason])
+        .inc();
+}
+
+/// Maps a 0.0 - 1.0 quality score to a bucket label of width 0.2
+pub(crate) fn quality_bucket(score: f32) -> &'static str {
+    match score {
+        s if s < 0.2 => "0.0-0.2",
+        s if s < 0.4 => "0.2-0.4",
+        s if s < 0.6 => "0.4-0.6",
+        s if s < 0.8 => "0.6-0.8",
+        _ => "0.8-1.0",
+    }
+}
+
+/// Counts a duplicate caught at the given level (url, hash or near)
+pub fn record_dedup_duplicate(level: &str) {
+    DEDUP_DUPLICATES_TOTAL
+        .with_label_values(&[level])
+        .inc();
+}
+
+/// Counts documents evicted from the LSH index (capacity or ttl)
+pub fn record_dedup_evictions(reason: &str, count: usize) {
+    DEDUP_EVICTIONS_TOTAL
+        .with_label_values(&[reason])
+        .inc_by(count as f64);
+}
+
+/// Updates the LSH index size gauges
+pub fn set_dedup_index_size(documents: usize, memory_bytes: usize) {
+    DEDUP_DOCUMENTS_INDEXED.set(documents as f64);
+    DEDUP_INDEX_MEMORY_BYTES.set(memory_bytes as f64);
+}
+
+/// Records how long an enrichment batch took
+pub fn record_enrichment_batch_duration(seconds: f64) {
+    ENRICHMENT_BATCH_DURATION_SECONDS.observe(seconds);
+}
+
+/// Updates today's estimated enrichment spend
+pub fn set_enrichment_spend(usd: f64) {
+    ENRICHMENT_SPEND_USD.set(usd);
+}
+
+/// Counts an enrichment call delayed by a per-minute limit (requests or tokens)
+pub fn record_enrichment_throttle(limit: &str) {
+    ENRICHMENT_THROTTLED_TOTAL
+        .with_label_values(&[limit])
+        .inc();
+}
+
+/// Records one garbage collection run: records reclaimed and its duration
+pub fn record_gc_run(reclaimed: usize, seconds: f64) {
+    GC_RECLAIMED_RECORDS_TOTAL.inc_by(reclaimed as f64);
+    GC_RUN_DURATION_SECONDS.observe(seconds);
+}
+
+/// Counts a record soft-deleted by the named expiry rule
+pub fn record_records_expired(rule: &str) {
+    RECORDS_EXPIRED_TOTAL
+        .with_label_values(&[rule])
+        .inc();
+}
+
+/// Counts a DNS resolution by how it was answered
+pub fn record_dns_lookup(result: &str) {
+    DNS_LOOKUPS_TOTAL
+        .with_label_values(&[result])
+        .inc();
+}
+
+/// Records a fast path response's time to headers, protocol and whether it opened a connection
+pub fn record_http_response(domain: &str, protocol: &str, new_connection: bool, seconds: f64) {
+    let connection = if new_connection { "new" } else { "reused" };
+    HTTP_TIME_TO_HEADERS_SECONDS
+        .with_label_values(&[domain, protocol, connection])
+        .observe(seconds);
+}
+
+/// Counts a redirect by whether it was followed or why it was rejected
+pub fn record_redirect(result: &str) {
+    REDIRECTS_TOTAL
+        .with_label_values(&[result])
+        .inc();
+}
+
+/// Counts a HEAD preflight by result, adding the declared size of skipped responses
+pub fn record_preflight(result: &str, avoided_bytes: u64) {
+    PREFLIGHT_TOTAL
+        .with_label_values(&[result])
+        .inc();
+    PREFLIGHT_AVOIDED_BYTES_TOTAL.inc_by(avoided_bytes as f64);
+}
+
+/// Records how long a render waited for its readiness condition and whether it was met
+pub fn record_render_wait(strategy: &str, result: &str, seconds: f64) {
+    RENDER_WAIT_SECONDS
+        .with_label_values(&[strategy, result])
+        .observe(seconds);
+}
+
+/// Counts a slow path browser relaunch by reason
+pub fn record_browser_recycle(reason: &str) {
+    BROWSER_RECYCLES_TOTAL
+        .with_label_values(&[reason])
+        .inc();
+}
+
+/// Counts a render stopped by a watchdog limit
+pub fn record_page_limit(limit: &str) {
+    PAGE_LIMIT_EXCEEDED_TOTAL
+        .with_label_values(&[limit])
+        .inc();
+}
+
+/// Records how long a job waited before a worker took it (from its own lane or stolen)
+pub fn record_dispatch_wait(source: &str, seconds: f64) {
+    DISPATCH_QUEUE_WAIT_SECONDS
+        .with_label_values(&[source])
+        .observe(seconds);
+}
+
+/// Updates the share of a dispatcher lane's capacity in use
+pub fn set_lane_utilization(lane: &str, utilization: f64) {
+    DISPATCH_LANE_UTILIZATION
+        .with_label_values(&[lane])
+        .set(utilization);
+}
+
+/// Counts an error or warning a rendered page raised, by type
+pub fn record_page_diagnostic(kind: &str) {
+    PAGE_DIAGNOSTICS_TOTAL
+        .with_label_values(&[kind])
+        .inc();
+}
+
+/// Counts a transient fetch failure by category; outcome is "scheduled" or "exhausted"
+pub fn record_fetch_retry(category: &str, outcome: &str) {
+    FETCH_RETRIES_TOTAL
+        .with_label_values(&[category, outcome])
+        .inc();
+}
+
+/// Counts an evicted HTML snapshot; reason is the eviction policy or "expired"
+pub fn record_snapshot_eviction(reason: &str) {
+    SNAPSHOT_EVICTIONS_TOTAL
+        .with_label_values(&[reason])
+        .inc();
+}
+
+/// Counts a site rule selector lookup; a miss falls back to the heuristic value
+pub fn record_site_rule_match(field: &str, matched: bool) {
+    SITE_RULE_MATCHES_TOTAL
+        .with_label_values(&[field, if matched { "matched" } else { "missed" }])
+        .inc();
+}
+
+/// Counts an LLM extraction (ok, cached, budget_exhausted, provider_error, invalid_answer)
+pub fn record_llm_extraction(provider: &str, outcome: &str) {
+    LLM_EXTRACTIONS_TOTAL
+        .with_label_values(&[provider, outcome])
+        .inc();
+}
+
+/// Counts a URL disallowed by robots.txt at `stage` (admission or dispatch)
+pub fn record_robots_blocked(stage: &str) {
+    ROBOTS_BLOCKED_TOTAL
+        .with_label_values(&[stage])
+        .inc();
+}
+
+/// Records one summary attempt (ok or error) and its duration
+pub fn record_enrichment_summary(outcome: &str, seconds: f64) {
+    ENRICHMENT_SUMMARIES_TOTAL
+        .with_label_values(&[outcome])
+        .inc();
+    ENRICHMENT_SUMMARY_DURATION_SECONDS.observe(seconds);
+}
+
 /// Updates queue depth for a named queue
 pub fn set_queue_depth(queue_name: &str, depth: i64) {
     QUEUE_DEPTH
@@ -359,6 +901,7 @@ pub struct StatsCollector {
     success_count: std::sync::atomic::AtomicU64,
     failure_count: std::sync::atomic::AtomicU64,
     bytes_transferred: std::sync::atomic::AtomicU64,
+    #[allow(dead_code)]
     last_update: std::sync::atomic::AtomicU64,
 }
 
@@ -403,12 +946,112 @@ impl StatsCollector {
         self.failure_count.store(0, Ordering::Relaxed);
         self.bytes_transferred.store(0, Ordering::Relaxed);
     }
+
+    /// Captures the current counter values
+    pub fn snapshot(&self) -> StatsSnapshot {
+        StatsSnapshot {
+            success_count: self.success_count.load(Ordering::Relaxed),
+            failure_count: self.failure_count.load(Ordering::Relaxed),
+            bytes_transferred: self.bytes_transferred.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Spawns a task publishing this collector's interval rates every `interval`
+    pub fn spawn_publisher(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move { publish_stats(&self, interval).await })
+    }
+}
+
+/// Counter values of a `StatsCollector` at one point in time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatsSnapshot {
+    pub success_count: u64,
+    pub failure_count: u64,
+    pub bytes_transferred: u64,
+}
+
+impl StatsSnapshot {
+    /// Computes rates over the interval since an earlier snapshot
+    ///
+    /// Counters that went backwards (after `reset`) count as zero for the interval.
+    pub fn rates_since(&self, earlier: &StatsSnapshot, elapsed: Duration) -> IntervalStats {
+        let success = self.success_count.saturating_sub(earlier.success_count);
+        let failure = self.failure_count.saturating_sub(earlier.failure_count);
+        let bytes = self.bytes_transferred.saturating_sub(earlier.bytes_transferred);
+        let secs = elapsed.as_secs_f64();
+
+        IntervalStats {
+            throughput_mbps: if secs > 0.0 {
+                bytes as f64 / 1_000_000.0 / secs
+            } else {
+                0.0
+            },
+            success_rate: (success + failure > 0).then(|| success as f64 / (success + failure) as f64),
+        }
+    }
+}
+
+/// Rates over one publishing interval
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntervalStats {
+    /// Megabytes per second, as reported by `titan_throughput_mbps`
+    pub throughput_mbps: f64,
+    /// Share of successful requests (None when no request finished in the interval)
+    pub success_rate: Option<f64>,
+}
+
+impl IntervalStats {
+    /// Sets the throughput gauge, and the success/error rate gauges when requests finished
+    pub fn publish(&self) {
+        set_throughput_mbps(self.throughput_mbps);
+        if let Some(rate) = self.success_rate {
+            set_global_success_rate(rate);
+            set_global_error_rate(1.0 - rate);
+        }
+    }
+}
+
+/// Returns the process-wide collector fed by the `record_request_*` and `record_bytes_*` functions
+pub fn global_stats() -> &'static StatsCollector {
+    &GLOBAL_STATS
+}
+
+/// Spawns a task publishing the process-wide interval rates every `interval`
+///
+/// Started automatically by `start_metrics_server`.
+pub fn start_stats_publisher(interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(publish_stats(global_stats(), interval))
+}
+
+async fn publish_stats(collector: &StatsCollector, interval: Duration) {
+    let mut tick = tokio::time::interval(interval.max(Duration::from_millis(1)));
+    // * The first tick completes immediately
+    tick.tick().await;
+    let mut last = collector.snapshot();
+    let mut last_at = Instant::now();
+
+    loop {
+        tick.tick().await;
+        let current = collector.snapshot();
+        let now = Instant::now();
+        current.rates_since(&last, now - last_at).publish();
+        last = current;
+        last_at = now;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_telemetry_config_default() {
+        let config = TelemetryConfig::default();
+        assert_eq!(config.metrics_port, DEFAULT_METRICS_PORT);
+        assert!(config.json_logs);
+        assert!(EnvFilter::try_new(&config.log_level).is_ok());
+    }
+
     #[test]
     fn test_record_request_success() {
         // * Reset metrics for test isolation
@@ -423,6 +1066,14 @@ mod tests {
         // * Metric should be incremented
     }
 
+    #[test]
+    fn test_quality_bucket() {
+        assert_eq!(quality_bucket(0.0), "0.0-0.2");
+        assert_eq!(quality_bucket(0.45), "0.4-0.6");
+        assert_eq!(quality_bucket(0.8), "0.8-1.0");
+        assert_eq!(quality_bucket(1.0), "0.8-1.0");
+    }
+
     #[test]
     fn test_set_active_crawlers() {
         set_active_crawlers(5);
@@ -496,6 +1147,59 @@ mod tests {
         assert_eq!(collector.get_total_bytes(), 0);
     }
 
+    #[test]
+    fn test_interval_rates() {
+        let earlier = StatsSnapshot {
+            success_count: 10,
+            failure_count: 5,
+            bytes_transferred: 1_000_000,
+        };
+        let later = StatsSnapshot {
+            success_count: 13,
+            failure_count: 6,
+            bytes_transferred: 11_000_000,
+        };
+
+        let rates = later.rates_since(&earlier, Duration::from_secs(5));
+        assert!((rates.throughput_mbps - 2.0).abs() < 1e-9);
+        assert!((rates.success_rate.unwrap() - 0.75).abs() < 1e-9);
+
+        // * An idle interval has no success rate; a reset counts as idle
+        let idle = earlier.rates_since(&earlier, Duration::from_secs(5));
+        assert_eq!(idle.success_rate, None);
+        assert_eq!(idle.throughput_mbps, 0.0);
+        assert_eq!(StatsSnapshot::default().rates_since(&later, Duration::ZERO).success_rate, None);
+    }
+
+    #[test]
+    fn test_record_functions_feed_global_stats() {
+        let before = global_stats().snapshot();
+        record_request_success();
+        record_bytes_downloaded(512);
+        let after = global_stats().snapshot();
+
+        // * Other tests record concurrently
+        assert!(after.success_count > before.success_count);
+        assert!(after.bytes_transferred >= before.bytes_transferred + 512);
+    }
+
+    #[tokio::test]
+    async fn test_publisher_sets_throughput() {
+        let collector = Arc::new(StatsCollector::new());
+        let publisher = Arc::clone(&collector).spawn_publisher(Duration::from_millis(20));
+
+        // * ~200 MB/s; other publishers share the gauge, so wait for a reading this one produced
+        let published = tokio::time::timeout(Duration::from_secs(5), async {
+            while THROUGHPUT_MBPS.get() < 100.0 {
+                collector.record_bytes(1_000_000);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await;
+        publisher.abort();
+        assert!(published.is_ok());
+    }
+
     #[test]
     fn test_record_duration() {
         record_fast_path_duration(0.5);
@@ -517,6 +1221,24 @@ mod tests {
         // * Gauges should be set
     }
 
+    #[test]
+    fn test_policy_skip_counter() {
+        record_policy_skip("page_budget");
+        assert!(get_metrics_string().contains("titan_policy_skips_total"));
+    }
+
+    #[test]
+    fn test_content_change_counter() {
+        record_content_change("minor_edit");
+        assert!(get_metrics_string().contains("titan_content_changes_total"));
+    }
+
+    #[test]
+    fn test_webhook_delivery_counter() {
+        record_webhook_delivery("delivered");
+        assert!(get_metrics_string().contains("titan_webhook_deliveries_total"));
+    }
+
     #[test]
     fn test_domain_ban_rate() {
         set_domain_ban_rate("example.com", 0.25);
@@ -538,6 +1260,7 @@ mod tests {
         let handle = MetricsServerHandle {
             shutdown_tx: None,
             running: running.clone(),
+            stats_publisher: None,
         };
 
         assert!(handle.is_running());
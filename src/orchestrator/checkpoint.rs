@@ -0,0 +1,257 @@
+// * [NFR-02] Crawl Checkpoints
+// * Periodic snapshots of frontier, dedup and per-domain state so a crashed or
+// * interrupted crawl can resume where it stopped instead of from the seed list
+
+use super::crawler::CrawlReport;
+use super::fetcher::FetchFuture;
+use super::frontier::FrontierSnapshot;
+use crate::persistence::DedupSnapshot;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// Format version written into every checkpoint
+pub const CHECKPOINT_VERSION: u32 = 1;
+
+// * File name used inside a checkpoint directory
+const CHECKPOINT_FILE: &str = "checkpoint.json";
+
+// * Redis key prefix for checkpoints
+const CHECKPOINT_PREFIX: &str = "checkpoint";
+
+#[derive(Debug, Error)]
+pub enum CheckpointError {
+    #[error("Checkpoint I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Checkpoint serialization failed: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Checkpoint Redis operation failed: {0}")]
+    Redis(#[from] redis::RedisError),
+
+    #[error("Unsupported checkpoint version {found} (expected {CHECKPOINT_VERSION})")]
+    UnsupportedVersion { found: u32 },
+}
+
+/// Everything needed to continue a crawl
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrawlCheckpoint {
+    pub version: u32,
+    /// Unix timestamp (seconds) when the checkpoint was taken
+    pub created_at: u64,
+    /// Seed URLs of the original run
+    pub seeds: Vec<String>,
+    /// Pages dispatched so far; counts against `max_pages` on resume
+    pub dispatched: usize,
+    /// Counters and per-domain stats accumulated so far
+    pub report: CrawlReport,
+    /// Queued URLs (including pages in flight when the checkpoint was taken)
+    pub frontier: FrontierSnapshot,
+    /// URL, content-hash and near-duplicate state
+    pub dedup: DedupSnapshot,
+}
+
+impl CrawlCheckpoint {
+    /// Creates a checkpoint stamped with the current time
+    pub fn new(
+        seeds: Vec<String>,
+        dispatched: usize,
+        report: CrawlReport,
+        frontier: FrontierSnapshot,
+        dedup: DedupSnapshot,
+    ) -> Self {
+        Self {
+            version: CHECKPOINT_VERSION,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            seeds,
+            dispatched,
+            report,
+            frontier,
+            dedup,
+        }
+    }
+
+    /// Serializes to JSON bytes
+    pub fn to_bytes(&self) -> Result<Vec<u8>, CheckpointError> {
+        Ok(serde_json::to_vec(self)?)
+    }
+
+    /// Parses JSON bytes, rejecting unknown format versions
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CheckpointError> {
+        let checkpoint: Self = serde_json::from_slice(bytes)?;
+        if checkpoint.version != CHECKPOINT_VERSION {
+            return Err(CheckpointError::UnsupportedVersion {
+                found: checkpoint.version,
+            });
+        }
+        Ok(checkpoint)
+    }
+}
+
+/// Durable location for the latest checkpoint of a crawl
+pub trait CheckpointStore: Send + Sync {
+    /// Replaces the stored checkpoint
+    fn save<'a>(&'a self, checkpoint: &'a CrawlCheckpoint) -> FetchFuture<'a, Result<(), CheckpointError>>;
+
+    /// Returns the stored checkpoint, if any
+    fn load(&self) -> FetchFuture<'_, Result<Option<CrawlCheckpoint>, CheckpointError>>;
+}
+
+/// Stores the checkpoint as `checkpoint.json` inside a directory
+#[derive(Debug, Clone)]
+pub struct DirectoryCheckpointStore {
+    dir: PathBuf,
+}
+
+impl DirectoryCheckpointStore {
+    /// Creates a store rooted at `dir` (created on first save)
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Returns the checkpoint file path
+    pub fn path(&self) -> PathBuf {
+        self.dir.join(CHECKPOINT_FILE)
+    }
+
+    /// Returns the checkpoint directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl CheckpointStore for DirectoryCheckpointStore {
+    fn save<'a>(&'a self, checkpoint: &'a CrawlCheckpoint) -> FetchFuture<'a, Result<(), CheckpointError>> {
+        Box::pin(async move {
+            let bytes = checkpoint.to_bytes()?;
+            tokio::fs::create_dir_all(&self.dir).await?;
+
+            // * Write-then-rename so a crash mid-write never corrupts the last good checkpoint
+            let tmp = self.dir.join(format!("{}.tmp", CHECKPOINT_FILE));
+            tokio::fs::write(&tmp, bytes).await?;
+            tokio::fs::rename(&tmp, self.path()).await?;
+            Ok(())
+        })
+    }
+
+    fn load(&self) -> FetchFuture<'_, Result<Option<CrawlCheckpoint>, CheckpointError>> {
+        Box::pin(async move {
+            match tokio::fs::read(self.path()).await {
+                Ok(bytes) => CrawlCheckpoint::from_bytes(&bytes).map(Some),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e.into()),
+            }
+        })
+    }
+}
+
+/// Stores the checkpoint under a Redis key, shared by distributed workers
+#[derive(Clone)]
+pub struct RedisCheckpointStore {
+    redis: ConnectionManager,
+    key: String,
+}
+
+impl std::fmt::Debug for RedisCheckpointStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisCheckpointStore")
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+impl RedisCheckpointStore {
+    /// Connects to Redis; the checkpoint is stored at `checkpoint:<crawl_name>`
+    pub async fn connect(redis_url: &str, crawl_name: &str) -> Result<Self, CheckpointError> {
+        let client = redis::Client::open(redis_url)?;
+        Ok(Self {
+            redis: ConnectionManager::new(client).await?,
+            key: checkpoint_key(crawl_name),
+        })
+    }
+
+    /// Returns the Redis key holding the checkpoint
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl CheckpointStore for RedisCheckpointStore {
+    fn save<'a>(&'a self, checkpoint: &'a CrawlCheckpoint) -> FetchFuture<'a, Result<(), CheckpointError>> {
+        Box::pin(async move {
+            let bytes = checkpoint.to_bytes()?;
+            let mut redis = self.redis.clone();
+            redis.set::<_, _, ()>(&self.key, bytes).await?;
+            Ok(())
+        })
+    }
+
+    fn load(&self) -> FetchFuture<'_, Result<Option<CrawlCheckpoint>, CheckpointError>> {
+        Box::pin(async move {
+            let mut redis = self.redis.clone();
+            let bytes: Option<Vec<u8>> = redis.get(&self.key).await?;
+            bytes.map(|b| CrawlCheckpoint::from_bytes(&b)).transpose()
+        })
+    }
+}
+
+fn checkpoint_key(crawl_name: &str) -> String {
+    format!("{}:{}", CHECKPOINT_PREFIX, crawl_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::DedupManager;
+
+    fn sample() -> CrawlCheckpoint {
+        CrawlCheckpoint::new(
+            vec!["https://example.com/".to_string()],
+            3,
+            CrawlReport {
+                pages_persisted: 2,
+                ..Default::default()
+            },
+            FrontierSnapshot::default(),
+            DedupManager::new().snapshot(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_directory_store_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("titan-flow-checkpoint-{}", std::process::id()));
+        let store = DirectoryCheckpointStore::new(&dir);
+        assert!(store.load().await.unwrap().is_none());
+
+        let checkpoint = sample();
+        store.save(&checkpoint).await.unwrap();
+        let loaded = store.load().await.unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(loaded, Some(checkpoint));
+    }
+
+    #[test]
+    fn test_rejects_unknown_version() {
+        let mut checkpoint = sample();
+        checkpoint.version = CHECKPOINT_VERSION + 1;
+        let bytes = serde_json::to_vec(&checkpoint).unwrap();
+
+        assert!(matches!(
+            CrawlCheckpoint::from_bytes(&bytes),
+            Err(CheckpointError::UnsupportedVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_checkpoint_key() {
+        assert_eq!(checkpoint_key("nightly"), "checkpoint:nightly");
+    }
+}
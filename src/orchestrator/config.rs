@@ -0,0 +1,204 @@
+// * [FR-01] Crawl Configuration
+// * Per-run settings for the orchestrator: scope, concurrency, routing, and enrichment
+
+use super::policy::CrawlPolicy;
+use super::report::ReportConfig;
+use super::frontier::CapacityPolicy;
+use super::spill::SpillConfig;
+use crate::engine::politeness::PolitenessConfig;
+use crate::engine::rate_limiter::CrawlDelayConfig;
+use crate::engine::url_filter::UrlFilterConfig;
+use crate::network::client::DEFAULT_MAX_RESPONSE_BYTES;
+use crate::network::decompress::DEFAULT_MAX_DECOMPRESSED_BYTES;
+use crate::network::connections::PoolConfig;
+use crate::engine::readiness::ReadinessConfig;
+use crate::engine::browser_limits::BrowserLimits;
+use crate::engine::dispatcher::DispatcherConfig;
+use crate::network::dns::DnsConfig;
+use crate::network::preflight::PreflightConfig;
+use crate::network::redirect::RedirectConfig;
+use crate::network::retry::RetryConfig;
+use crate::network::identity::IdentityProfile;
+use crate::ops::ResourceMonitorConfig;
+use crate::persistence::{ClusterReportConfig, DedupConfig, ExpiryConfig, GcConfig, ScorerConfig, WorkerConfig};
+use crate::refinery::RefineryConfig;
+use serde::{Deserialize, Serialize};
+
+// * Crawl budget defaults
+const DEFAULT_MAX_PAGES: usize = 100;
+const DEFAULT_MAX_DEPTH: usize = 3;
+const DEFAULT_CONCURRENCY: usize = 8;
+const DEFAULT_FRONTIER_CAPACITY: usize = 10_000;
+
+// * Time allowed for the enrichment worker to catch up after the crawl ends
+const DEFAULT_ENRICHMENT_DRAIN_TIMEOUT_MS: u64 = 30_000;
+
+// * Time allowed for in-flight pages to finish after a shutdown signal
+const DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_MS: u64 = 30_000;
+
+// * Checkpoint every five minutes when a checkpoint store is attached
+const DEFAULT_CHECKPOINT_INTERVAL_SECS: u64 = 300;
+
+/// Configuration for a single crawl run
+///
+/// Component settings (refinery, link scorer, crawl delay, worker) are not
+/// serialized here; they live in their own sections of `TitanConfig`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CrawlConfig {
+    /// Maximum number of pages to fetch
+    pub max_pages: usize,
+    /// Maximum link depth from the seed URLs (seeds are depth 0)
+    pub max_depth: usize,
+    /// Maximum number of pages processed concurrently
+    pub concurrency: usize,
+    /// Maximum number of URLs waiting in the frontier (in memory and spilled, or only
+    /// in memory with the `spill_to_disk` policy)
+    pub frontier_capacity: usize,
+    /// What a full frontier does with newly discovered URLs
+    pub frontier_policy: CapacityPolicy,
+    /// Spill cold frontier entries to disk beyond a memory budget
+    pub spill: SpillConfig,
+    /// Only follow links on the seed URLs' hosts
+    pub same_domain_only: bool,
+    /// Fetch robots.txt per domain and honour Disallow and Crawl-Delay
+    pub respect_robots_txt: bool,
+    /// Hosts (and their subdomains) exempt from robots.txt Allow/Disallow, e.g. internal or
+    /// staging sites; their Crawl-Delay still applies
+    pub robots_exempt_hosts: Vec<String>,
+    /// Route low-density pages (and banned fetches) through the headless browser
+    pub enable_slow_path: bool,
+    /// When slow path renders are extracted: wait strategy, timeout and per-site overrides
+    pub readiness: ReadinessConfig,
+    /// Per-page watchdogs (time, memory, CPU) and when the headless browser is relaunched
+    pub browser_limits: BrowserLimits,
+    /// Extract from the page's declared AMP alternate instead, when it yields content
+    pub prefer_amp: bool,
+    /// Largest response body accepted after undoing Content-Encoding
+    pub max_decompressed_bytes: usize,
+    /// Response bodies are cut to this many bytes and refined as truncated
+    pub max_response_bytes: usize,
+    /// User-Agent used for robots.txt matching
+    pub user_agent: String,
+    /// Caching of DNS lookups for page and robots.txt requests
+    pub dns: DnsConfig,
+    /// Idle connection pooling of the fast path clients
+    pub pool: PoolConfig,
+    /// Fast path redirect following: hop limit, cross-domain rules and meta refresh
+    pub redirect: RedirectConfig,
+    /// HEAD checks that skip media, archives and oversized files before fetching them
+    pub preflight: PreflightConfig,
+    /// Retries of timeouts, dropped connections and 5xx responses, with backoff and per-URL budgets
+    pub retry: RetryConfig,
+    /// Redis URL shared by the rate limiter and circuit breaker (None keeps state local)
+    pub redis_url: Option<String>,
+    /// Tier 1 (datacenter) proxy URLs for the escalation ladder
+    pub tier1_proxies: Vec<String>,
+    /// Tier 2 (residential) proxy URLs for the escalation ladder
+    pub tier2_proxies: Vec<String>,
+    /// Run the AI enrichment worker alongside the crawl
+    pub enable_enrichment: bool,
+    /// Maximum time to wait for enrichment to drain after the crawl
+    pub enrichment_drain_timeout_ms: u64,
+    /// Maximum time to wait for in-flight pages after a shutdown signal
+    pub shutdown_drain_timeout_ms: u64,
+    /// Seconds between checkpoints (0 keeps only the final checkpoint)
+    pub checkpoint_interval_secs: u64,
+    /// JSON and Markdown crawl summaries, refreshed at each checkpoint interval
+    pub report: ReportConfig,
+    /// Per-domain budgets, depth limits and URL patterns
+    pub policy: CrawlPolicy,
+    /// Glob/regex/domain rules deciding which discovered links are followed
+    pub url_filter: UrlFilterConfig,
+    /// Adaptive per-domain crawl delay after bans
+    pub politeness: PolitenessConfig,
+    /// Memory watermarks at which dispatch pauses
+    pub memory: ResourceMonitorConfig,
+    /// Host-sharded worker lanes that run dispatched pages
+    pub dispatcher: DispatcherConfig,
+    /// Near-duplicate detection settings
+    pub dedup: DedupConfig,
+    /// Periodic export of near-duplicate clusters (syndicated copies of one page)
+    pub clusters: ClusterReportConfig,
+    /// Purging of long soft-deleted records (needs `Orchestrator::with_record_purger`)
+    pub gc: GcConfig,
+    /// Per-domain and per-URL-pattern maximum record age
+    pub expiry: ExpiryConfig,
+    /// Refinery pipeline configuration
+    #[serde(skip)]
+    pub refinery: RefineryConfig,
+    /// Link scorer used to order the frontier
+    #[serde(skip)]
+    pub link_scorer: ScorerConfig,
+    /// Crawl delay for domains without a robots.txt Crawl-Delay
+    #[serde(skip)]
+    pub crawl_delay: CrawlDelayConfig,
+    /// Enrichment worker configuration
+    #[serde(skip)]
+    pub worker: WorkerConfig,
+}
+
+impl Default for CrawlConfig {
+    fn default() -> Self {
+        Self {
+            max_pages: DEFAULT_MAX_PAGES,
+            max_depth: DEFAULT_MAX_DEPTH,
+            concurrency: DEFAULT_CONCURRENCY,
+            frontier_capacity: DEFAULT_FRONTIER_CAPACITY,
+            frontier_policy: CapacityPolicy::default(),
+            spill: SpillConfig::default(),
+            same_domain_only: true,
+            respect_robots_txt: true,
+            robots_exempt_hosts: Vec::new(),
+            enable_slow_path: true,
+            readiness: ReadinessConfig::default(),
+            browser_limits: BrowserLimits::default(),
+            prefer_amp: false,
+            max_decompressed_bytes: DEFAULT_MAX_DECOMPRESSED_BYTES,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            user_agent: IdentityProfile::generate_chrome_120().user_agent,
+            dns: DnsConfig::default(),
+            pool: PoolConfig::default(),
+            redirect: RedirectConfig::default(),
+            preflight: PreflightConfig::default(),
+            retry: RetryConfig::default(),
+            redis_url: None,
+            tier1_proxies: Vec::new(),
+            tier2_proxies: Vec::new(),
+            enable_enrichment: true,
+            enrichment_drain_timeout_ms: DEFAULT_ENRICHMENT_DRAIN_TIMEOUT_MS,
+            shutdown_drain_timeout_ms: DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_MS,
+            checkpoint_interval_secs: DEFAULT_CHECKPOINT_INTERVAL_SECS,
+            report: ReportConfig::default(),
+            policy: CrawlPolicy::default(),
+            url_filter: UrlFilterConfig::default(),
+            politeness: PolitenessConfig::default(),
+            memory: ResourceMonitorConfig::default(),
+            dispatcher: DispatcherConfig::default(),
+            dedup: DedupConfig::default(),
+            clusters: ClusterReportConfig::default(),
+            gc: GcConfig::default(),
+            expiry: ExpiryConfig::default(),
+            refinery: RefineryConfig::default(),
+            link_scorer: ScorerConfig::default(),
+            crawl_delay: CrawlDelayConfig::default(),
+            worker: WorkerConfig::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crawl_config_defaults() {
+        let config = CrawlConfig::default();
+        assert_eq!(config.max_pages, DEFAULT_MAX_PAGES);
+        assert_eq!(config.max_depth, DEFAULT_MAX_DEPTH);
+        assert!(config.same_domain_only);
+        assert!(config.respect_robots_txt);
+        assert!(config.enable_enrichment);
+        assert!(config.user_agent.contains("Chrome"));
+    }
+}
@@ -0,0 +1,307 @@
+// * Crawl Control Plane
+// * Shared handle for steering a running crawl from outside the orchestrator: inject
+// * seeds, pause, resume or quarantine domains, pause the whole crawl, cancel it, and read
+// * live frontier depth and per-domain stats
+
+use super::crawler::{CrawlReport, DomainCrawlStats};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::Notify;
+
+/// Live view of a crawl, published by the orchestrator after every dispatch round
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CrawlProgress {
+    /// False before the first crawl starts and after it finishes
+    pub running: bool,
+    /// True while the whole crawl is paused
+    pub paused: bool,
+    /// URLs waiting in the frontier
+    pub frontier_depth: usize,
+    /// Pages being fetched or processed
+    pub in_flight: usize,
+    /// Frontier URLs held back because their domain is paused, quarantined or backing off
+    pub parked: usize,
+    /// Pages dispatched so far (counts against `max_pages`)
+    pub dispatched: usize,
+    /// Counters so far, including per-domain statistics
+    pub report: CrawlReport,
+}
+
+/// Handle for steering a running crawl
+///
+/// Attach it with `Orchestrator::with_control`; commands take effect between dispatch
+/// rounds. Seeds and pauses issued before the crawl starts are applied when it does.
+///
+/// # Example
+/// ```ignore
+/// let control = Arc::new(CrawlControl::new());
+/// let orchestrator = Orchestrator::new().with_control(Arc::clone(&control));
+/// control.pause_domain("slow.example.com");
+/// control.enqueue_seeds(["https://news.example.com/"]);
+/// println!("{} queued", control.progress().frontier_depth);
+/// ```
+#[derive(Debug, Default)]
+pub struct CrawlControl {
+    pending_seeds: Mutex<Vec<String>>,
+    paused: RwLock<BTreeSet<String>>,
+    // * Domain -> end of its quarantine
+    quarantined: RwLock<BTreeMap<String, SystemTime>>,
+    crawl_paused: AtomicBool,
+    cancelled: AtomicBool,
+    progress: RwLock<CrawlProgress>,
+    changed: Notify,
+}
+
+impl CrawlControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues seed URLs for the running crawl; returns how many were http(s) URLs
+    ///
+    /// Seeds widen the crawl scope to their hosts, like the seeds a crawl started with.
+    pub fn enqueue_seeds<I, S>(&self, urls: I) -> usize
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let accepted: Vec<String> = urls
+            .into_iter()
+            .map(|u| u.as_ref().trim().to_string())
+            .filter(|u| u.starts_with("http://") || u.starts_with("https://"))
+            .collect();
+        let count = accepted.len();
+        if count > 0 {
+            self.pending_seeds.lock().unwrap().extend(accepted);
+            self.changed.notify_one();
+        }
+        count
+    }
+
+    /// Stops dispatching pages for a domain; returns false if it was already paused
+    pub fn pause_domain(&self, domain: &str) -> bool {
+        self.paused.write().unwrap().insert(domain.to_lowercase())
+    }
+
+    /// Resumes a paused domain; returns false if it was not paused
+    pub fn resume_domain(&self, domain: &str) -> bool {
+        let resumed = self.paused.write().unwrap().remove(&domain.to_lowercase());
+        if resumed {
+            self.changed.notify_one();
+        }
+        resumed
+    }
+
+    /// Returns true if the domain is paused
+    pub fn is_paused(&self, domain: &str) -> bool {
+        self.paused.read().unwrap().contains(&domain.to_lowercase())
+    }
+
+    /// Returns the paused domains in alphabetical order
+    pub fn paused_domains(&self) -> Vec<String> {
+        self.paused.read().unwrap().iter().cloned().collect()
+    }
+
+    /// Holds a domain's pages for `duration`, then releases them without a resume
+    ///
+    /// Quarantining again replaces the end time. Returns when the quarantine ends (Unix seconds).
+    pub fn quarantine_domain(&self, domain: &str, duration: Duration) -> u64 {
+        let until = SystemTime::now() + duration;
+        self.quarantined.write().unwrap().insert(domain.to_lowercase(), until);
+        unix_secs(until)
+    }
+
+    /// Ends a quarantine early; returns false if the domain was not quarantined
+    pub fn release_quarantine(&self, domain: &str) -> bool {
+        let released = self.quarantined.write().unwrap().remove(&domain.to_lowercase());
+        let released = released.is_some_and(|until| until > SystemTime::now());
+        if released {
+            self.changed.notify_one();
+        }
+        released
+    }
+
+    /// Returns when a domain's quarantine ends (Unix seconds), or None if it is not quarantined
+    pub fn quarantined_until(&self, domain: &str) -> Option<u64> {
+        self.quarantine_end(domain).map(unix_secs)
+    }
+
+    /// Returns the quarantined domains and when each quarantine ends (Unix seconds)
+    pub fn quarantined_domains(&self) -> BTreeMap<String, u64> {
+        let now = SystemTime::now();
+        let mut quarantined = self.quarantined.write().unwrap();
+        quarantined.retain(|_, until| *until > now);
+        quarantined
+            .iter()
+            .map(|(domain, until)| (domain.clone(), unix_secs(*until)))
+            .collect()
+    }
+
+    /// Returns true if the domain's pages are held back (paused or quarantined)
+    pub fn is_held(&self, domain: &str) -> bool {
+        self.is_paused(domain) || self.quarantine_end(domain).is_some()
+    }
+
+    /// Stops dispatching pages for every domain; pages in flight finish
+    ///
+    /// Returns false if the crawl was already paused.
+    pub fn pause_crawl(&self) -> bool {
+        !self.crawl_paused.swap(true, Ordering::SeqCst)
+    }
+
+    /// Resumes a paused crawl; returns false if it was not paused
+    pub fn resume_crawl(&self) -> bool {
+        let resumed = self.crawl_paused.swap(false, Ordering::SeqCst);
+        if resumed {
+            self.changed.notify_one();
+        }
+        resumed
+    }
+
+    /// Returns true while the whole crawl is paused
+    pub fn is_crawl_paused(&self) -> bool {
+        self.crawl_paused.load(Ordering::SeqCst)
+    }
+
+    /// Ends the crawl like a shutdown: dispatching stops and in-flight pages drain
+    ///
+    /// A cancelled crawl stays cancelled; the report is marked interrupted.
+    pub fn cancel(&self) {
+        if !self.cancelled.swap(true, Ordering::SeqCst) {
+            self.changed.notify_one();
+        }
+    }
+
+    /// Returns true once the crawl was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Returns the latest published progress
+    pub fn progress(&self) -> CrawlProgress {
+        self.progress.read().unwrap().clone()
+    }
+
+    /// Returns the statistics of a single domain
+    pub fn domain_stats(&self, domain: &str) -> Option<DomainCrawlStats> {
+        self.progress
+            .read()
+            .unwrap()
+            .report
+            .domains
+            .get(&domain.to_lowercase())
+            .cloned()
+    }
+
+    /// Returns when a domain's quarantine ends on the monotonic clock
+    pub(crate) fn quarantine_deadline(&self, domain: &str) -> Option<Instant> {
+        let until = self.quarantine_end(domain)?;
+        let remaining = until.duration_since(SystemTime::now()).unwrap_or_default();
+        Some(Instant::now() + remaining)
+    }
+
+    fn quarantine_end(&self, domain: &str) -> Option<SystemTime> {
+        self.quarantined
+            .read()
+            .unwrap()
+            .get(&domain.to_lowercase())
+            .copied()
+            .filter(|until| *until > SystemTime::now())
+    }
+
+    /// Removes and returns the seeds queued since the last call
+    pub(crate) fn take_seeds(&self) -> Vec<String> {
+        std::mem::take(&mut *self.pending_seeds.lock().unwrap())
+    }
+
+    pub(crate) fn publish(&self, progress: CrawlProgress) {
+        *self.progress.write().unwrap() = progress;
+    }
+
+    /// Completes when seeds are queued, a domain or the crawl is resumed, or it is cancelled
+    pub(crate) async fn changed(&self) {
+        self.changed.notified().await;
+    }
+}
+
+// * Rounded up so a quarantine never appears to end early
+fn unix_secs(time: SystemTime) -> u64 {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    since_epoch.as_secs() + u64::from(since_epoch.subsec_nanos() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enqueue_seeds_keeps_http_urls() {
+        let control = CrawlControl::new();
+        assert_eq!(control.enqueue_seeds(["https://a.com/", " http://b.com/ ", "ftp://c.com/"]), 2);
+        assert_eq!(control.take_seeds(), vec!["https://a.com/", "http://b.com/"]);
+        assert!(control.take_seeds().is_empty());
+    }
+
+    #[test]
+    fn test_pause_and_resume() {
+        let control = CrawlControl::new();
+        assert!(control.pause_domain("Example.com"));
+        assert!(!control.pause_domain("example.com"));
+        assert!(control.is_paused("EXAMPLE.COM"));
+        assert_eq!(control.paused_domains(), vec!["example.com"]);
+
+        assert!(control.resume_domain("example.com"));
+        assert!(!control.resume_domain("example.com"));
+        assert!(!control.is_paused("example.com"));
+    }
+
+    #[test]
+    fn test_quarantine_expires() {
+        let control = CrawlControl::new();
+        let until = control.quarantine_domain("Example.com", Duration::from_secs(3 * 3600));
+        assert!(control.is_held("example.com"));
+        assert!(!control.is_paused("example.com"));
+        assert_eq!(control.quarantined_until("example.com"), Some(until));
+        assert_eq!(control.quarantined_domains().into_keys().collect::<Vec<_>>(), vec!["example.com"]);
+        assert!(control.release_quarantine("example.com"));
+        assert!(!control.release_quarantine("example.com"));
+
+        control.quarantine_domain("example.com", Duration::ZERO);
+        assert!(!control.is_held("example.com"));
+        assert_eq!(control.quarantined_until("example.com"), None);
+        assert!(control.quarantined_domains().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pause_crawl_and_cancel_wake_the_crawl() {
+        let control = CrawlControl::new();
+        assert!(control.pause_crawl());
+        assert!(!control.pause_crawl());
+        assert!(control.is_crawl_paused());
+        assert!(control.resume_crawl());
+        assert!(!control.resume_crawl());
+        tokio::time::timeout(std::time::Duration::from_secs(1), control.changed())
+            .await
+            .unwrap();
+
+        assert!(!control.is_cancelled());
+        control.cancel();
+        assert!(control.is_cancelled());
+        tokio::time::timeout(std::time::Duration::from_secs(1), control.changed())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_changed_wakes_after_seeds() {
+        let control = CrawlControl::new();
+        control.enqueue_seeds(["https://a.com/"]);
+        // * The permit is stored, so a later wait completes immediately
+        tokio::time::timeout(std::time::Duration::from_secs(1), control.changed())
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,2801 @@
+// * [FR-01] [NFR-02] End-to-End Crawl Orchestrator
+// * Drives the frontier through governance, fetch routing, refinery, dedup, persistence,
+// * and background AI enrichment
+
+use super::checkpoint::{CheckpointStore, CrawlCheckpoint};
+use super::config::CrawlConfig;
+use super::control::{CrawlControl, CrawlProgress};
+use super::fetcher::{ArchivingFetcher, HttpFetcher, PageFetcher};
+use super::frontier::{extract_links, host_of, CrawlFrontier, FrontierEntry, PendingUrl};
+use super::hreflang::HreflangIndex;
+use super::policy::{PolicyEnforcer, PolicyError, PolicyViolation};
+use super::report::{CrawlSummary, ReportConfig};
+use super::seeds::{SeedError, SeedLoader};
+use super::spill::SpillStore;
+use crate::config::ConfigWatcher;
+use crate::engine::circuit_breaker::{CircuitBreaker, CircuitBreakerError};
+use crate::engine::density::{DensityMetrics, RoutingPath};
+use crate::engine::dispatcher::Dispatcher;
+use crate::engine::normalization::{canonical_key, normalize_url};
+use crate::engine::politeness::{FetchSignal, PolitenessController};
+use crate::engine::rate_limiter::{CrawlDelayConfig, RateLimitError, RateLimitManager};
+use crate::engine::slow_path::PageDiagnostic;
+use crate::engine::url_filter::{UrlFilter, UrlFilterError};
+use crate::network::cache::{CacheValidators, ConditionalFetch, ValidatorStore};
+use crate::network::dns::DnsCache;
+use crate::network::errors::NetworkError;
+use crate::network::redirect::RedirectChain;
+use crate::network::retry::{RetryCategory, RetryTracker};
+use crate::ops::{self, AlertManager, CrawlEvent, DomainStatsRegistry, ResourceMonitor, ShutdownSignal};
+use crate::persistence::{
+    AIEnrichmentWorker, AuditSample, AuditSampler, AuthorityError, ChangeTracker, ChunkRecord, ChunkStore,
+    ClusterReporter, DedupManager, EnrichmentError, EnrichmentFilter, ExpiryError, ExpiryScheduler, GarbageCollector,
+    InMemoryRecordStore, LinkScorer, MultimodalRecordBuilder, NerProvider, RecordProvider, RecordPurger, RecordSink,
+    RecordUpdater, ScorerConfig, SnapshotStore, SummaryProvider, TopicEmbedder, WarcWriter,
+};
+use crate::refinery::{ExtractionSource, RefineryPool, RefineryResult, Truncated};
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::sync::{mpsc, watch};
+use tracing::{debug, info, info_span, instrument, warn, Instrument, Span};
+use url::Url;
+
+// * Prometheus queue label for the frontier depth gauge
+const FRONTIER_QUEUE_NAME: &str = "frontier";
+
+// * Sleep between dispatch attempts while memory pressure blocks new work
+const PRESSURE_BACKOFF_MS: u64 = 250;
+
+// * Poll interval while waiting for enrichment to drain
+const DRAIN_POLL_INTERVAL_MS: u64 = 50;
+
+#[derive(Debug, Error)]
+pub enum OrchestratorError {
+    #[error("No valid http(s) seed URLs provided")]
+    NoValidSeeds,
+
+    #[error("Fetcher setup failed: {0}")]
+    Network(#[from] NetworkError),
+
+    #[error("Rate limiter setup failed: {0}")]
+    RateLimit(#[from] RateLimitError),
+
+    #[error("Circuit breaker setup failed: {0}")]
+    CircuitBreaker(#[from] CircuitBreakerError),
+
+    #[error("Crawl policy is invalid: {0}")]
+    Policy(#[from] PolicyError),
+
+    #[error("URL filter is invalid: {0}")]
+    UrlFilter(#[from] UrlFilterError),
+
+    #[error("Frontier spill directory is unusable: {0}")]
+    Spill(#[from] std::io::Error),
+
+    #[error("Expiry rules are invalid: {0}")]
+    Expiry(#[from] ExpiryError),
+
+    #[error("Link scorer setup failed: {0}")]
+    Authority(#[from] AuthorityError),
+
+    #[error("Seed list could not be loaded: {0}")]
+    Seeds(#[from] SeedError),
+}
+
+/// Storage backend usable by the orchestrator and the enrichment worker
+pub trait CrawlStore: RecordSink + RecordProvider + RecordUpdater + Clone + 'static {}
+
+impl<T> CrawlStore for T where T: RecordSink + RecordProvider + RecordUpdater + Clone + 'static {}
+
+/// Summary of a completed crawl run
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CrawlReport {
+    /// Pages whose HTML was retrieved (fast or slow path)
+    pub pages_fetched: usize,
+    /// Unique records written to the store
+    pub pages_persisted: usize,
+    /// Pages dropped as URL, hash, or near duplicates
+    pub duplicates_skipped: usize,
+    /// Pages that yielded no extractable text
+    pub empty_pages: usize,
+    /// URLs disallowed by robots.txt
+    pub robots_blocked: usize,
+    /// URLs skipped because their domain is blacklisted
+    pub blacklisted_skipped: usize,
+    /// URLs handed off to the external slow render queue
+    pub handed_off: usize,
+    /// URLs that could not be fetched
+    pub fetch_errors: usize,
+    /// Records the store rejected
+    pub storage_errors: usize,
+    /// Pages rendered through the headless browser
+    pub slow_path_renders: usize,
+    /// Discovered links accepted into the frontier
+    pub links_enqueued: usize,
+    /// Records enriched before the run returned
+    pub records_enriched: usize,
+    /// True when the run was stopped early by a shutdown signal
+    pub interrupted: bool,
+    /// In-flight pages aborted because the shutdown drain deadline passed
+    pub pages_abandoned: usize,
+    /// URLs kept out by the crawl policy (patterns, depth or domain budgets)
+    pub policy_skipped: usize,
+    /// Pages the server reported unchanged (HTTP 304) since the previous crawl
+    #[serde(default)]
+    pub pages_unchanged: usize,
+    /// Pages whose content differs from the version tracked on an earlier crawl
+    #[serde(default)]
+    pub pages_changed: usize,
+    /// URLs not fetched because a HEAD preflight found media, an archive or an oversized file
+    #[serde(default)]
+    pub preflight_skipped: usize,
+    /// Transient fetch failures sent back to the frontier for another attempt
+    #[serde(default)]
+    pub fetch_retries: usize,
+    /// Fetched pages not stored because a translation in a preferred language exists
+    #[serde(default)]
+    pub language_variants_skipped: usize,
+    /// Failed pages by error kind (e.g. `http_403`, `timeout`)
+    #[serde(default)]
+    pub errors: BTreeMap<String, usize>,
+    /// Refined pages by quality score bucket (0.0-0.2 ... 0.8-1.0)
+    #[serde(default)]
+    pub quality_scores: BTreeMap<String, usize>,
+    /// Per-domain counters, keyed by host
+    pub domains: BTreeMap<String, DomainCrawlStats>,
+}
+
+/// Crawl counters for a single host
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DomainCrawlStats {
+    pub pages_dispatched: usize,
+    pub pages_fetched: usize,
+    pub bytes_fetched: u64,
+    pub pages_persisted: usize,
+    pub fetch_errors: usize,
+}
+
+impl CrawlReport {
+    fn record(&mut self, outcome: &PageOutcome) {
+        if outcome.fetched {
+            self.pages_fetched += 1;
+        }
+        if outcome.slow_path {
+            self.slow_path_renders += 1;
+        }
+        if outcome.changed {
+            self.pages_changed += 1;
+        }
+        self.robots_blocked += outcome.robots_blocked_links;
+        if let Some(score) = outcome.quality_score {
+            *self.quality_scores.entry(ops::telemetry::quality_bucket(score).to_string()).or_default() += 1;
+        }
+        if outcome.status == PageStatus::FetchFailed {
+            let kind = outcome.error.as_deref().unwrap_or("unknown");
+            *self.errors.entry(kind.to_string()).or_default() += 1;
+        }
+
+        match outcome.status {
+            PageStatus::Persisted => self.pages_persisted += 1,
+            PageStatus::Duplicate => self.duplicates_skipped += 1,
+            PageStatus::Empty => self.empty_pages += 1,
+            PageStatus::RobotsBlocked => self.robots_blocked += 1,
+            PageStatus::Blacklisted => self.blacklisted_skipped += 1,
+            PageStatus::HandedOff => self.handed_off += 1,
+            PageStatus::FetchFailed => self.fetch_errors += 1,
+            PageStatus::StorageFailed => self.storage_errors += 1,
+            PageStatus::Unchanged => self.pages_unchanged += 1,
+            PageStatus::PreflightSkipped => self.preflight_skipped += 1,
+            PageStatus::LanguageVariant => self.language_variants_skipped += 1,
+        }
+
+        if let Some(domain) = host_of(&outcome.url) {
+            let stats = self.domains.entry(domain).or_default();
+            if outcome.fetched {
+                stats.pages_fetched += 1;
+            }
+            stats.bytes_fetched += outcome.bytes;
+            match outcome.status {
+                PageStatus::Persisted => stats.pages_persisted += 1,
+                PageStatus::FetchFailed => stats.fetch_errors += 1,
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Final disposition of a single URL
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PageStatus {
+    Persisted,
+    Duplicate,
+    Empty,
+    RobotsBlocked,
+    Blacklisted,
+    HandedOff,
+    FetchFailed,
+    StorageFailed,
+    Unchanged,
+    PreflightSkipped,
+    LanguageVariant,
+}
+
+/// Result of processing one frontier entry
+#[derive(Debug)]
+struct PageOutcome {
+    url: String,
+    depth: usize,
+    status: PageStatus,
+    fetched: bool,
+    bytes: u64,
+    slow_path: bool,
+    links: Vec<(String, String)>,
+    // * Discovered links dropped because robots.txt disallows them
+    robots_blocked_links: usize,
+    // * Cache validators from the fast path response, stored once the page is processed
+    validators: Option<CacheValidators>,
+    changed: bool,
+    // * Charset the fast path decoded the body from
+    encoding: Option<String>,
+    // * The fast path body was cut at the response size limit
+    truncated: bool,
+    // * Redirects the fast path followed to reach the page
+    redirect: Option<RedirectChain>,
+    // * Errors the page raised while the slow path rendered it
+    diagnostics: Vec<PageDiagnostic>,
+    // * Why the page failed, set for FetchFailed outcomes
+    error: Option<String>,
+    // * Set when the fast path failure is transient and may be retried
+    retry: Option<RetryCategory>,
+    // * Set when the domain's rate limit needs a long wait; the page is fetched after it instead
+    deferred: Option<Duration>,
+    quality_score: Option<f32>,
+}
+
+impl PageOutcome {
+    fn new(url: &str, depth: usize) -> Self {
+        Self {
+            url: url.to_string(),
+            depth,
+            status: PageStatus::FetchFailed,
+            fetched: false,
+            bytes: 0,
+            slow_path: false,
+            links: Vec::new(),
+            robots_blocked_links: 0,
+            validators: None,
+            changed: false,
+            encoding: None,
+            truncated: false,
+            redirect: None,
+            diagnostics: Vec::new(),
+            error: None,
+            retry: None,
+            deferred: None,
+            quality_score: None,
+        }
+    }
+
+    fn with_status(mut self, status: PageStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    fn with_error(mut self, kind: &str) -> Self {
+        self.error = Some(kind.to_string());
+        self
+    }
+}
+
+// * Domain -> robots.txt body (None when absent), filled once per domain
+type RobotsCell = Arc<tokio::sync::OnceCell<Option<String>>>;
+
+/// robots.txt bodies per domain
+///
+/// The map lock only guards the lookup of a domain's cell; fetches run on the cell, so
+/// concurrent first visits to one domain share a single request and other domains never
+/// wait on it.
+#[derive(Default)]
+struct RobotsCache {
+    domains: Mutex<HashMap<String, RobotsCell>>,
+}
+
+impl RobotsCache {
+    fn cell(&self, domain: &str) -> RobotsCell {
+        Arc::clone(self.domains.lock().unwrap().entry(domain.to_string()).or_default())
+    }
+
+    /// Returns the domain's robots.txt, running `fetch` if no visit has loaded it yet
+    async fn get_or_fetch<F, Fut>(&self, domain: &str, fetch: F) -> Option<String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Option<String>>,
+    {
+        self.cell(domain).get_or_init(fetch).await.clone()
+    }
+
+    /// Returns the domain's robots.txt if a fetch has already completed; never waits
+    fn cached(&self, domain: &str) -> Option<Option<String>> {
+        self.domains.lock().unwrap().get(domain)?.get().cloned()
+    }
+}
+
+/// Components shared by all in-flight page tasks of a run
+struct CrawlContext<S> {
+    config: CrawlConfig,
+    fetcher: Arc<dyn PageFetcher>,
+    rate_limiter: RateLimitManager,
+    politeness: PolitenessController,
+    // * Shared with the alert manager, which records fetch outcomes into it
+    domain_stats: Arc<DomainStatsRegistry>,
+    circuit_breaker: CircuitBreaker,
+    // * Parses on the blocking pool so refining never stalls in-flight fetches
+    refinery: RefineryPool,
+    // * Shared with the garbage collector, which forgets purged records
+    dedup: Arc<Mutex<DedupManager>>,
+    store: S,
+    http_cache: Option<Arc<dyn ValidatorStore>>,
+    changes: Option<Arc<ChangeTracker>>,
+    result_sinks: Vec<Arc<dyn RecordSink>>,
+    chunks: Option<Arc<dyn ChunkStore>>,
+    snapshots: Option<Arc<SnapshotStore>>,
+    audit: Option<Arc<AuditSampler>>,
+    // * Language variants declared by fetched pages; also consulted for discovered links
+    hreflang: Mutex<HreflangIndex>,
+    robots: RobotsCache,
+}
+
+impl<S: CrawlStore> CrawlContext<S> {
+    /// Runs a single URL through the full pipeline
+    ///
+    /// # Pipeline Steps:
+    /// 1. robots.txt compliance (first visit also registers Crawl-Delay)
+    /// 2. Circuit breaker check (tripped domains go to the external render queue)
+    /// 3. Per-domain rate limiting and blacklist check
+    /// 4. Fast path fetch (conditional when cached), escalating to the slow path on bans or low density
+    /// 5. Link discovery, refinery, dedup, and persistence
+    async fn process_page(self: Arc<Self>, entry: FrontierEntry) -> PageOutcome {
+        let mut outcome = self.process_entry(entry).await;
+        // * Validators are only kept for processed pages so failed pages are fetched in full again
+        if matches!(
+            outcome.status,
+            PageStatus::Persisted | PageStatus::Duplicate | PageStatus::Empty
+        ) {
+            if let (Some(cache), Some(validators)) = (&self.http_cache, outcome.validators.take()) {
+                if let Err(e) = cache.put(&outcome.url, &validators).await {
+                    warn!(url = %outcome.url, error = %e, "Failed to store cache validators");
+                }
+            }
+        }
+        outcome
+    }
+
+    /// Runs steps 1-5 for a single entry
+    ///
+    /// The `page` span is the root of the URL's trace; each step gets a child span.
+    #[instrument(name = "page", skip_all, fields(url = %entry.url, depth = entry.depth))]
+    async fn process_entry(&self, entry: FrontierEntry) -> PageOutcome {
+        let outcome = PageOutcome::new(&entry.url, entry.depth);
+        let Some(domain) = host_of(&entry.url) else {
+            return outcome.with_error("invalid_url");
+        };
+
+        // * Step 1: robots.txt
+        if self.config.respect_robots_txt {
+            if let Some(robots) = self.robots_for(&domain, &entry.url).await {
+                if !self.robots_exempt(&domain) && !self.rate_limiter.is_crawl_allowed(&robots, &entry.url) {
+                    debug!(url = %entry.url, "Blocked by robots.txt");
+                    ops::record_robots_blocked("dispatch");
+                    return outcome.with_status(PageStatus::RobotsBlocked);
+                }
+            }
+        }
+
+        // * Step 2: circuit breaker
+        if self.config.enable_slow_path
+            && self
+                .circuit_breaker
+                .should_bypass_local(&domain)
+                .await
+                .unwrap_or(false)
+        {
+            return match self.circuit_breaker.handoff_to_queue(&entry.url).await {
+                Ok(()) => outcome.with_status(PageStatus::HandedOff),
+                Err(e) => {
+                    warn!(url = %entry.url, error = %e, "Slow render handoff failed");
+                    outcome.with_error("handoff_failed")
+                }
+            };
+        }
+
+        // * Step 3: rate limiting
+        match self.rate_limiter.acquire(&domain, false).await {
+            Ok(()) => {}
+            Err(RateLimitError::DomainBlacklisted) => {
+                return outcome.with_status(PageStatus::Blacklisted);
+            }
+            // * Another replica's 429 backoff or a long shared-bucket wait; the run loop holds the page
+            Err(RateLimitError::RateLimitExceeded(wait)) => {
+                debug!(url = %entry.url, wait_ms = wait.as_millis() as u64, "Domain rate limit exceeded");
+                let mut outcome = outcome.with_error("rate_limited");
+                outcome.deferred = Some(wait);
+                return outcome;
+            }
+            Err(e) => {
+                warn!(domain = %domain, error = %e, "Rate limiter unavailable");
+                return outcome.with_error("rate_limiter_unavailable");
+            }
+        }
+
+        // * Step 4: fetch
+        let mut outcome = outcome;
+        let html = match self.fetch_html(&entry.url, &domain, &mut outcome).await {
+            Ok(html) => html,
+            Err(status) => return outcome.with_status(status),
+        };
+        outcome.fetched = true;
+        outcome.bytes = html.len() as u64;
+        ops::record_bytes_downloaded(outcome.bytes);
+        ops::record_page_processed("html");
+        ops::publish_event(CrawlEvent::PageFetched {
+            url: entry.url.clone(),
+            domain: domain.clone(),
+            bytes: outcome.bytes,
+            slow_path: outcome.slow_path,
+        });
+
+        // * Step 5: links, refinery, dedup, persistence
+        if entry.depth < self.config.max_depth {
+            // * Relative links resolve against the URL the page was served from
+            let base = outcome.redirect.as_ref().map_or(entry.url.as_str(), |r| r.final_url.as_str());
+            outcome.links = extract_links(&html, base);
+            if self.config.respect_robots_txt {
+                self.drop_disallowed_links(&mut outcome);
+            }
+        }
+
+        // * The raw HTML, kept so pages that extract badly can be inspected; it is only stored
+        // * once the record is going to be persisted
+        let snapshot = self
+            .snapshots
+            .as_ref()
+            .map(|store| (Arc::clone(store), SnapshotStore::hash_of(&html), html.clone()));
+        // * Only sampled pages pay for the copy
+        let audit_html = self
+            .audit
+            .as_ref()
+            .filter(|sampler| sampler.should_sample(&entry.url))
+            .map(|_| html.clone());
+        let mut result = self
+            .refinery
+            .process_for_domain_async(html, domain.clone())
+            .instrument(info_span!("refinery"))
+            .await;
+        if self.config.prefer_amp {
+            if let Some(amp) = self.refine_amp(&entry.url, &result, &mut outcome).await {
+                result = amp;
+            }
+        }
+        result.stats.encoding = outcome.encoding.clone();
+        result.diagnostics = std::mem::take(&mut outcome.diagnostics);
+        if outcome.truncated && result.metadata.extraction_source == ExtractionSource::Original {
+            ops::record_truncated_page(Truncated::ResponseBytes.as_str());
+            result.truncated = Some(Truncated::ResponseBytes);
+        }
+        if let (Some(sampler), Some(html)) = (&self.audit, audit_html) {
+            let sample = AuditSample::new(entry.url.clone(), domain.clone(), html, result.clone());
+            if let Err(e) = sampler.record(sample) {
+                warn!(url = %entry.url, error = %e, "Failed to write audit sample");
+            }
+        }
+        outcome.quality_score = Some(result.stats.quality_score);
+
+        // * Translations join one cluster; a non-preferred one hands over to its preferred variants
+        let hreflang_cluster = if result.metadata.alternate_languages.is_empty() {
+            None
+        } else {
+            let served_from = outcome.redirect.as_ref().map_or(entry.url.as_str(), |r| r.final_url.as_str());
+            let mut index = self.hreflang.lock().unwrap();
+            let cluster = index.observe(served_from, &result.metadata.alternate_languages);
+            if index.is_unwanted(served_from) {
+                debug!(url = %entry.url, "Skipping translation outside the preferred languages");
+                ops::record_policy_skip(PolicyViolation::LanguageVariant.as_str());
+                let preferred = index.preferred_variants(served_from);
+                outcome.links.extend(preferred.into_iter().map(|url| (url, String::new())));
+                return outcome.with_status(PageStatus::LanguageVariant);
+            }
+            cluster
+        };
+        if result.persistable_text().trim().is_empty() {
+            return outcome.with_status(PageStatus::Empty);
+        }
+
+        let mut builder = MultimodalRecordBuilder::from_refinery(&result, entry.url.clone())
+            .crawl_depth(entry.depth as u32);
+        if let Some(parent) = &entry.parent_url {
+            builder = builder.parent_url(parent.clone());
+        }
+        if let Some(redirect) = &outcome.redirect {
+            builder = builder.redirected(redirect.urls(), redirect.final_url.clone());
+        }
+        if let Some((_, hash, _)) = &snapshot {
+            builder = builder.html_snapshot(hash.clone());
+        }
+        if let Some(cluster) = hreflang_cluster {
+            builder = builder.hreflang_cluster(cluster);
+        }
+        let mut record = builder.build();
+
+        if let Some(tracker) = &self.changes {
+            let change = tracker.observe(&record.url, record.content_hash, &record.text_content);
+            ops::record_content_change(change.kind.as_str());
+            outcome.changed = change.kind.is_change();
+        }
+
+        let served_from = outcome.redirect.as_ref().map_or(entry.url.as_str(), |r| r.final_url.as_str());
+        let canonical = result
+            .metadata
+            .canonical_url
+            .as_deref()
+            .and_then(|canonical| canonical_key(served_from, canonical));
+        let dedup = info_span!("dedup").in_scope(|| {
+            self.dedup.lock().unwrap().check_and_index_canonical(
+                &record.url,
+                canonical.as_deref(),
+                record.content_hash,
+                &record.text_content,
+                &record.id,
+            )
+        });
+        if dedup.is_duplicate() {
+            debug!(url = %entry.url, result = ?dedup, "Skipping duplicate");
+            return outcome.with_status(PageStatus::Duplicate);
+        }
+
+        // * zstd compression, the file write and eviction stay off the async runtime
+        if let Some((store, _, html)) = snapshot {
+            let stored = match tokio::task::spawn_blocking(move || store.put(&html)).await {
+                Ok(put) => put.map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            if let Err(e) = stored {
+                warn!(url = %entry.url, error = %e, "Failed to store HTML snapshot");
+                record.html_snapshot = None;
+            }
+        }
+
+        // * Result sinks only see records the store accepted
+        let forwarded = (!self.result_sinks.is_empty()).then(|| record.clone());
+        let chunks = self
+            .chunks
+            .as_ref()
+            .map(|store| (store, ChunkRecord::from_text_chunks(&record, &result.chunks)));
+        let record_id = record.id.clone();
+        let persist = async {
+            self.store.insert_record(record).await?;
+            if let Some((store, chunks)) = chunks {
+                if let Err(e) = store.replace_chunks(&entry.url, chunks).await {
+                    warn!(url = %entry.url, error = %e, "Failed to store chunks");
+                }
+            }
+            if let Some(record) = forwarded {
+                for sink in &self.result_sinks {
+                    if let Err(e) = sink.insert_record(record.clone()).await {
+                        warn!(url = %entry.url, error = %e, "Result sink rejected record");
+                    }
+                }
+            }
+            Ok::<(), EnrichmentError>(())
+        };
+        match persist.instrument(info_span!("persist")).await {
+            Ok(()) => {
+                if self.config.enable_enrichment {
+                    ops::otel::hand_off_span(&record_id, &Span::current());
+                }
+                ops::publish_event(CrawlEvent::RecordPersisted {
+                    url: entry.url.clone(),
+                    record_id: record_id.into(),
+                });
+                outcome.with_status(PageStatus::Persisted)
+            }
+            Err(e) => {
+                warn!(url = %entry.url, error = %e, "Failed to persist record");
+                outcome.with_status(PageStatus::StorageFailed)
+            }
+        }
+    }
+
+    /// Fetches and refines the AMP alternate a page declares
+    ///
+    /// Returns None (keeping the original extraction) when there is no alternate,
+    /// it cannot be fetched, or it yields no text.
+    async fn refine_amp(
+        &self,
+        url: &str,
+        original: &RefineryResult,
+        outcome: &mut PageOutcome,
+    ) -> Option<RefineryResult> {
+        let amp_url = Url::parse(url).ok()?.join(original.metadata.amp_url.as_deref()?).ok()?;
+        // * AMP pages may list themselves as their own alternate
+        if amp_url.as_str() == url || !matches!(amp_url.scheme(), "http" | "https") {
+            return None;
+        }
+        let amp_domain = amp_url.host_str()?.to_string();
+        self.rate_limiter.acquire(&amp_domain, false).await.ok()?;
+
+        let html = match self.fetcher.fetch(amp_url.as_str()).await {
+            Ok(html) => html,
+            Err(e) => {
+                debug!(url = %amp_url, error = %e, "AMP fetch failed; using the original page");
+                return None;
+            }
+        };
+        outcome.bytes += html.len() as u64;
+        ops::record_bytes_downloaded(html.len() as u64);
+
+        let mut amp = self
+            .refinery
+            .process_for_domain_async(html, amp_domain)
+            .instrument(info_span!("refinery"))
+            .await;
+        if amp.persistable_text().trim().is_empty() {
+            return None;
+        }
+        amp.metadata.extraction_source = ExtractionSource::Amp;
+        amp.metadata.amp_url = Some(amp_url.to_string());
+        Some(amp)
+    }
+
+    /// Returns the cached robots.txt for a domain, fetching it on first visit
+    async fn robots_for(&self, domain: &str, url: &str) -> Option<String> {
+        self.robots
+            .get_or_fetch(domain, || async {
+                let robots = self.fetcher.fetch_robots_txt(url).await;
+                self.rate_limiter
+                    .register_domain(domain, robots.as_deref())
+                    .await;
+                robots
+            })
+            .await
+    }
+
+    /// Drops discovered links that robots.txt disallows before they reach the frontier
+    ///
+    /// Only hosts whose robots.txt fetch has completed (always the page's own host) are
+    /// checked here; links to other hosts, including ones whose fetch is still in flight,
+    /// are checked when dispatched. This is synchronous, so admission never waits on a
+    /// robots.txt fetch.
+    fn drop_disallowed_links(&self, outcome: &mut PageOutcome) {
+        let mut cache: HashMap<String, Option<Option<String>>> = HashMap::new();
+        let before = outcome.links.len();
+        outcome.links.retain(|(url, _)| {
+            let Some(host) = host_of(url) else {
+                return true;
+            };
+            let robots = cache.entry(host.clone()).or_insert_with(|| self.robots.cached(&host));
+            match robots {
+                Some(Some(robots)) if !self.robots_exempt(&host) => self.rate_limiter.is_crawl_allowed(robots, url),
+                _ => true,
+            }
+        });
+        outcome.robots_blocked_links = before - outcome.links.len();
+        for _ in 0..outcome.robots_blocked_links {
+            ops::record_robots_blocked("admission");
+        }
+    }
+
+    /// Returns true for hosts configured to skip robots.txt Allow/Disallow checks
+    fn robots_exempt(&self, host: &str) -> bool {
+        self.config
+            .robots_exempt_hosts
+            .iter()
+            .any(|exempt| host == exempt || host.ends_with(&format!(".{}", exempt)))
+    }
+
+    /// Fetches HTML, routing between the fast and slow paths
+    #[instrument(name = "fetch", skip_all)]
+    async fn fetch_html(
+        &self,
+        url: &str,
+        domain: &str,
+        outcome: &mut PageOutcome,
+    ) -> Result<String, PageStatus> {
+        let cached = match &self.http_cache {
+            Some(cache) => cache.get(url).await.unwrap_or_else(|e| {
+                warn!(url = %url, error = %e, "Failed to read cache validators");
+                None
+            }),
+            None => None,
+        };
+
+        let started = Instant::now();
+        let fetched = self.fetcher.fetch_conditional(url, cached.as_ref()).await;
+        self.domain_stats.record_latency(domain, started.elapsed());
+        match fetched {
+            Ok(ConditionalFetch::NotModified) => {
+                ops::record_fast_path_duration(started.elapsed().as_secs_f64());
+                ops::record_request_success();
+                self.adapt_politeness(domain, FetchSignal::Success).await;
+                debug!(url = %url, "Not modified since the previous crawl");
+                Err(PageStatus::Unchanged)
+            }
+            Ok(ConditionalFetch::Modified { body: html, validators, encoding, truncated, redirect }) => {
+                ops::record_fast_path_duration(started.elapsed().as_secs_f64());
+                ops::record_request_success();
+                self.adapt_politeness(domain, FetchSignal::Success).await;
+                outcome.validators = validators;
+                outcome.encoding = encoding;
+                outcome.redirect = redirect;
+
+                // * Low-density pages are usually JS shells; re-render when possible
+                if self.config.enable_slow_path
+                    && DensityMetrics::compute(&html).routing == RoutingPath::Slow
+                {
+                    if let Ok(rendered) = self.render(url, domain, outcome).await {
+                        return Ok(rendered);
+                    }
+                }
+                outcome.truncated = truncated;
+                Ok(html)
+            }
+            // * Not a failure: the URL is simply not a page worth downloading
+            Err(NetworkError::Preflight(skip)) => {
+                debug!(url = %url, reason = %skip, "Skipped after HEAD preflight");
+                Err(PageStatus::PreflightSkipped)
+            }
+            Err(e) => {
+                ops::record_request_failure();
+                outcome.error = Some(e.kind());
+                outcome.retry = RetryCategory::classify(&e);
+                let banned = self.record_fetch_error(domain, url, &e).await;
+                let signal = if banned { FetchSignal::Ban } else { FetchSignal::Failure };
+                self.adapt_politeness(domain, signal).await;
+                // * Throttling is waited out; rendering would only add requests during the backoff
+                let throttled = matches!(e, NetworkError::Throttled { .. });
+                if self.config.enable_slow_path && banned && !throttled {
+                    self.render(url, domain, outcome).await
+                } else {
+                    Err(PageStatus::FetchFailed)
+                }
+            }
+        }
+    }
+
+    /// Feeds a fast path result to the politeness controller and applies any new backoff
+    async fn adapt_politeness(&self, domain: &str, signal: FetchSignal) {
+        if let Some(backoff) = self.politeness.record(domain, signal) {
+            info!(domain = %domain, backoff = backoff, signal = ?signal, "Adjusting crawl delay");
+            self.rate_limiter.set_backoff(domain, backoff).await;
+        }
+    }
+
+    /// Renders a page through the slow path, feeding failures to the circuit breaker
+    #[instrument(name = "render", skip_all)]
+    async fn render(
+        &self,
+        url: &str,
+        domain: &str,
+        outcome: &mut PageOutcome,
+    ) -> Result<String, PageStatus> {
+        // * The browser issues its own request; it needs its own (longer) permit
+        if self.rate_limiter.acquire(domain, true).await.is_err() {
+            return Err(PageStatus::Blacklisted);
+        }
+
+        let started = Instant::now();
+        match self.fetcher.render_page(url).await {
+            Ok(rendered) => {
+                ops::record_slow_path_duration(started.elapsed().as_secs_f64());
+                outcome.slow_path = true;
+                outcome.diagnostics = rendered.diagnostics;
+                Ok(rendered.html)
+            }
+            Err(e) => {
+                warn!(url = %url, error = %e, "Slow path render failed");
+                match self.circuit_breaker.handle_failure(domain, url).await {
+                    Ok(true) => Err(PageStatus::HandedOff),
+                    _ => Err(PageStatus::FetchFailed),
+                }
+            }
+        }
+    }
+
+    /// Records governance state for bans; returns true for bans
+    ///
+    /// Ban metrics are recorded by the `BanDetector` as each response is classified.
+    async fn record_fetch_error(&self, domain: &str, url: &str, err: &NetworkError) -> bool {
+        warn!(url = %url, error = %err, "Fast path fetch failed");
+
+        // * Bans only surface here after the proxy ladder was exhausted
+        let governance = match err {
+            NetworkError::Throttled { retry_after, .. } => self.rate_limiter.record_429(domain, *retry_after).await,
+            // * Unresolvable hosts count toward the domain's circuit breaker, but are not bans
+            e if e.is_dns_failure() => {
+                if let Err(e) = self.circuit_breaker.record_failure(domain).await {
+                    warn!(domain = %domain, error = %e, "Failed to record DNS failure");
+                }
+                return false;
+            }
+            NetworkError::HardBan(_) => self.rate_limiter.record_tier2_failure(domain).await,
+            NetworkError::SoftBan(_) => {
+                // * Repeated challenges trip the breaker so the domain goes straight to the browser
+                if let Err(e) = self.circuit_breaker.record_failure(domain).await {
+                    warn!(domain = %domain, error = %e, "Failed to record challenge");
+                }
+                self.rate_limiter.record_tier2_failure(domain).await
+            }
+            _ => return false,
+        };
+
+        if let Err(e) = governance {
+            warn!(domain = %domain, error = %e, "Failed to record ban");
+        }
+        true
+    }
+}
+
+/// Top-level crawler wiring every pipeline stage together
+///
+/// # Example
+/// ```ignore
+/// use titan_flow::orchestrator::{CrawlConfig, Orchestrator};
+///
+/// let orchestrator = Orchestrator::new();
+/// let report = orchestrator
+///     .run(vec!["https://example.com".to_string()], CrawlConfig::default())
+///     .await?;
+///
+/// println!("Persisted {} pages", report.pages_persisted);
+/// println!("Stored records: {}", orchestrator.store().count());
+/// ```
+pub struct Orchestrator<S = Arc<InMemoryRecordStore>> {
+    store: S,
+    fetcher: Option<Arc<dyn PageFetcher>>,
+    result_sinks: Vec<Arc<dyn RecordSink>>,
+    rate_limit_updates: Option<watch::Receiver<CrawlDelayConfig>>,
+    scorer_updates: Option<watch::Receiver<ScorerConfig>>,
+    shutdown: Option<ShutdownSignal>,
+    checkpoints: Option<Arc<dyn CheckpointStore>>,
+    alerts: Option<Arc<AlertManager>>,
+    http_cache: Option<Arc<dyn ValidatorStore>>,
+    changes: Option<Arc<ChangeTracker>>,
+    chunks: Option<Arc<dyn ChunkStore>>,
+    purger: Option<Arc<dyn RecordPurger>>,
+    ner: Option<Arc<dyn NerProvider>>,
+    summarizer: Option<Arc<dyn SummaryProvider>>,
+    topic_embedder: Option<Arc<dyn TopicEmbedder>>,
+    control: Option<Arc<CrawlControl>>,
+    archive: Option<Arc<WarcWriter>>,
+    snapshots: Option<Arc<SnapshotStore>>,
+    audit: Option<Arc<AuditSampler>>,
+}
+
+/// Crawl state that survives a checkpoint/resume cycle
+struct CrawlState {
+    seeds: Vec<String>,
+    frontier: CrawlFrontier,
+    dedup: DedupManager,
+    report: CrawlReport,
+    dispatched: usize,
+}
+
+/// Handles one run steers and reports through: the orchestrator's own for `run`/`resume`,
+/// or a job's when several crawls share the orchestrator
+#[derive(Clone, Default)]
+pub(crate) struct RunHandles {
+    pub(crate) control: Option<Arc<CrawlControl>>,
+    pub(crate) checkpoints: Option<Arc<dyn CheckpointStore>>,
+    pub(crate) result_sinks: Vec<Arc<dyn RecordSink>>,
+}
+
+impl Orchestrator {
+    /// Creates an orchestrator backed by an in-memory record store
+    pub fn new() -> Self {
+        Self::with_store(Arc::new(InMemoryRecordStore::new()))
+    }
+}
+
+impl Default for Orchestrator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: CrawlStore> Orchestrator<S> {
+    /// Creates an orchestrator that persists into the given store
+    pub fn with_store(store: S) -> Self {
+        Self {
+            store,
+            fetcher: None,
+            result_sinks: Vec::new(),
+            rate_limit_updates: None,
+            scorer_updates: None,
+            shutdown: None,
+            checkpoints: None,
+            alerts: None,
+            http_cache: None,
+            changes: None,
+            chunks: None,
+            purger: None,
+            ner: None,
+            summarizer: None,
+            topic_embedder: None,
+            control: None,
+            archive: None,
+            snapshots: None,
+            audit: None,
+        }
+    }
+
+    /// Replaces the default HTTP/browser fetcher
+    pub fn with_fetcher(mut self, fetcher: Arc<dyn PageFetcher>) -> Self {
+        self.fetcher = Some(fetcher);
+        self
+    }
+
+    /// Forwards every persisted record to an additional sink (e.g. a records.jsonl log or a `WebhookSink`)
+    ///
+    /// Sink failures are logged and do not affect the page status.
+    pub fn with_result_sink(mut self, sink: Arc<dyn RecordSink>) -> Self {
+        self.result_sinks.push(sink);
+        self
+    }
+
+    /// Stops dispatching and drains in-flight pages when the signal fires
+    pub fn with_shutdown(mut self, signal: ShutdownSignal) -> Self {
+        self.shutdown = Some(signal);
+        self
+    }
+
+    /// Periodically saves crawl state so the run can be resumed after a crash
+    pub fn with_checkpoint_store(mut self, store: Arc<dyn CheckpointStore>) -> Self {
+        self.checkpoints = Some(store);
+        self
+    }
+
+    /// Revalidates pages with stored ETag/Last-Modified validators
+    ///
+    /// Pages answered with HTTP 304 are counted as unchanged and skip the Refinery,
+    /// dedup and persistence; their links are not followed again.
+    pub fn with_http_cache(mut self, cache: Arc<dyn ValidatorStore>) -> Self {
+        self.http_cache = Some(cache);
+        self
+    }
+
+    /// Compares every extracted page with its tracked version and broadcasts changes
+    ///
+    /// Restore the tracker from an earlier crawl's snapshot to detect changes across runs.
+    pub fn with_change_tracker(mut self, tracker: Arc<ChangeTracker>) -> Self {
+        self.changes = Some(tracker);
+        self
+    }
+
+    /// Stores each persisted page's refinery chunks in `store` for chunk-level retrieval
+    ///
+    /// The enrichment worker embeds the chunks along with the records.
+    pub fn with_chunk_store(mut self, store: Arc<dyn ChunkStore>) -> Self {
+        self.chunks = Some(store);
+        self
+    }
+
+    /// Purges long soft-deleted records from `store` while crawling (enable with `gc.enabled`)
+    ///
+    /// Purged pages are also removed from the dedup index, so they can be crawled again.
+    pub fn with_record_purger(mut self, store: Arc<dyn RecordPurger>) -> Self {
+        self.purger = Some(store);
+        self
+    }
+
+    /// Adds named entity recognition to the enrichment worker
+    pub fn with_ner_provider(mut self, provider: Arc<dyn NerProvider>) -> Self {
+        self.ner = Some(provider);
+        self
+    }
+
+    /// Replaces the enrichment worker's default summarizer (enable with `worker.compute_summary`)
+    pub fn with_summary_provider(mut self, provider: Arc<dyn SummaryProvider>) -> Self {
+        self.summarizer = Some(provider);
+        self
+    }
+
+    /// Scores links by embedding similarity to `link_scorer.topic.description`
+    pub fn with_topic_embedder(mut self, embedder: Arc<dyn TopicEmbedder>) -> Self {
+        self.topic_embedder = Some(embedder);
+        self
+    }
+
+    /// Accepts seeds and domain pauses from a control handle and publishes live progress to it
+    pub fn with_control(mut self, control: Arc<CrawlControl>) -> Self {
+        self.control = Some(control);
+        self
+    }
+
+    /// Archives every fetched response and rendered page to a WARC file
+    pub fn with_warc_archive(mut self, archive: Arc<WarcWriter>) -> Self {
+        self.archive = Some(archive);
+        self
+    }
+
+    /// Keeps the raw HTML of every persisted page, referenced by `html_snapshot`
+    pub fn with_snapshot_store(mut self, store: Arc<SnapshotStore>) -> Self {
+        self.snapshots = Some(store);
+        self
+    }
+
+    /// Copies a random sample of processed pages (HTML and refinery result) for quality audits
+    pub fn with_audit_sampler(mut self, sampler: Arc<AuditSampler>) -> Self {
+        self.audit = Some(sampler);
+        self
+    }
+
+    /// Reports per-domain ban rates to an alert manager
+    ///
+    /// Domains whose ban rate raises the SEV-3 alert are held at the maximum crawl delay.
+    /// Without one, each run tracks ban rates with a default alert manager.
+    pub fn with_alert_manager(mut self, alerts: Arc<AlertManager>) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
+    /// Applies crawl delay and link scorer changes from a config watcher mid-crawl
+    pub fn with_config_watcher(mut self, watcher: &ConfigWatcher) -> Self {
+        self.rate_limit_updates = Some(watcher.subscribe_rate_limits());
+        self.scorer_updates = Some(watcher.subscribe_scorer());
+        self
+    }
+
+    /// Returns the record store
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Crawls outward from the seed URLs until the frontier or page budget is exhausted
+    pub async fn run(
+        &self,
+        seed_urls: Vec<String>,
+        config: CrawlConfig,
+    ) -> Result<CrawlReport, OrchestratorError> {
+        let state = self.initial_state(seed_urls, &config)?;
+        self.crawl(state, config, self.run_handles()).await
+    }
+
+    /// Loads seeds from the loader's sources and crawls outward from them
+    pub async fn run_from(&self, loader: &SeedLoader, config: CrawlConfig) -> Result<CrawlReport, OrchestratorError> {
+        let batch = loader.load().await?;
+        self.run(batch.urls(), config).await
+    }
+
+    /// Runs a crawl with its own control handle and extra sink, alongside other runs
+    ///
+    /// The orchestrator's control handle and checkpoint store are not used; its shutdown
+    /// signal and result sinks are.
+    pub(crate) async fn run_job(
+        &self,
+        seed_urls: Vec<String>,
+        config: CrawlConfig,
+        control: Arc<CrawlControl>,
+        sink: Option<Arc<dyn RecordSink>>,
+    ) -> Result<CrawlReport, OrchestratorError> {
+        let state = self.initial_state(seed_urls, &config)?;
+        let mut result_sinks = self.result_sinks.clone();
+        result_sinks.extend(sink);
+        let handles = RunHandles {
+            control: Some(control),
+            checkpoints: None,
+            result_sinks,
+        };
+        self.crawl(state, config, handles).await
+    }
+
+    fn run_handles(&self) -> RunHandles {
+        RunHandles {
+            control: self.control.clone(),
+            checkpoints: self.checkpoints.clone(),
+            result_sinks: self.result_sinks.clone(),
+        }
+    }
+
+    /// Validates the seeds and queues them in a fresh frontier
+    fn initial_state(&self, seed_urls: Vec<String>, config: &CrawlConfig) -> Result<CrawlState, OrchestratorError> {
+        let seeds: Vec<String> = seed_urls
+            .iter()
+            .map(|s| s.trim())
+            .filter_map(|s| normalize_url(s, s))
+            .filter(|s| s.starts_with("http://") || s.starts_with("https://"))
+            .collect();
+        if seeds.is_empty() {
+            return Err(OrchestratorError::NoValidSeeds);
+        }
+
+        let scorer = self.link_scorer(&config.link_scorer, &seeds)?;
+        let mut frontier =
+            CrawlFrontier::with_scorer(config.frontier_capacity, config.max_depth, scorer);
+        if config.same_domain_only {
+            frontier = frontier.with_allowed_hosts(seeds.iter().filter_map(|s| host_of(s)));
+        }
+        for seed in &seeds {
+            frontier.push(seed, "", 0);
+        }
+
+        let state = CrawlState {
+            seeds,
+            frontier,
+            dedup: DedupManager::with_config(config.dedup.clone()),
+            report: CrawlReport::default(),
+            dispatched: 0,
+        };
+        Ok(state)
+    }
+
+    /// Continues a crawl from a checkpoint
+    ///
+    /// Counters carry over and `dispatched` pages count against `max_pages`.
+    /// Pages that were in flight when the checkpoint was taken are fetched again.
+    pub async fn resume(
+        &self,
+        checkpoint: CrawlCheckpoint,
+        config: CrawlConfig,
+    ) -> Result<CrawlReport, OrchestratorError> {
+        info!(
+            pending = checkpoint.frontier.pending.len(),
+            dispatched = checkpoint.dispatched,
+            created_at = checkpoint.created_at,
+            "Resuming crawl from checkpoint"
+        );
+
+        let scorer = self.link_scorer(&config.link_scorer, &checkpoint.seeds)?;
+        let frontier = CrawlFrontier::from_snapshot(
+            checkpoint.frontier,
+            config.frontier_capacity,
+            config.max_depth,
+            scorer,
+        );
+        let report = CrawlReport {
+            interrupted: false,
+            pages_abandoned: 0,
+            ..checkpoint.report
+        };
+
+        let state = CrawlState {
+            seeds: checkpoint.seeds,
+            frontier,
+            dedup: DedupManager::from_snapshot_with_config(&checkpoint.dedup, config.dedup.clone()),
+            report,
+            dispatched: checkpoint.dispatched,
+        };
+        self.crawl(state, config, self.run_handles()).await
+    }
+
+    /// Drives the frontier until it, the page budget, or a shutdown ends the run
+    async fn crawl(
+        &self,
+        state: CrawlState,
+        config: CrawlConfig,
+        run: RunHandles,
+    ) -> Result<CrawlReport, OrchestratorError> {
+        let CrawlState {
+            mut seeds,
+            frontier,
+            dedup,
+            mut report,
+            mut dispatched,
+        } = state;
+
+        let policy = PolicyEnforcer::new(config.policy.clone())?;
+        // * Seeds are already queued; the filter only governs discovered links
+        let url_filter = UrlFilter::new(&config.url_filter)?.with_sites(&seeds);
+        let mut frontier = frontier
+            .with_url_filter(Arc::new(url_filter))
+            .with_capacity_policy(config.frontier_policy);
+        if let Some(dir) = &config.spill.dir {
+            frontier = frontier.with_spill(SpillStore::create(dir)?, config.spill.memory_budget_bytes);
+        }
+
+        let mut fetcher: Arc<dyn PageFetcher> = match &self.fetcher {
+            Some(fetcher) => Arc::clone(fetcher),
+            None => {
+                let mut fetcher = HttpFetcher::new(config.tier1_proxies.clone(), config.tier2_proxies.clone())?
+                    .with_max_decompressed_bytes(config.max_decompressed_bytes)
+                    .with_max_response_bytes(config.max_response_bytes)
+                    .with_pool(config.pool.clone())
+                    .with_redirect_policy(config.redirect.clone())
+                    .with_preflight(config.preflight.clone())
+                    .with_readiness(config.readiness.clone())
+                    .with_browser_limits(config.browser_limits.clone());
+                if config.dns.enabled {
+                    fetcher = fetcher.with_dns_cache(Arc::new(DnsCache::new(config.dns.clone())))?;
+                }
+                Arc::new(fetcher)
+            }
+        };
+        if let Some(archive) = &self.archive {
+            fetcher = Arc::new(ArchivingFetcher::new(fetcher, Arc::clone(archive)));
+        }
+
+        let alerts = self
+            .alerts
+            .clone()
+            .unwrap_or_else(|| Arc::new(AlertManager::new()));
+
+        let redis_url = config.redis_url.as_deref();
+        let ctx = Arc::new(CrawlContext {
+            rate_limiter: RateLimitManager::new(redis_url, &config.user_agent)
+                .await?
+                .with_default_delay(config.crawl_delay.clone()),
+            politeness: PolitenessController::new(config.politeness.clone())
+                .with_alert_manager(Arc::clone(&alerts)),
+            domain_stats: alerts.domain_stats(),
+            circuit_breaker: CircuitBreaker::new(redis_url).await?,
+            refinery: RefineryPool::new(config.refinery.clone()),
+            dedup: Arc::new(Mutex::new(dedup)),
+            store: self.store.clone(),
+            http_cache: self.http_cache.clone(),
+            changes: self.changes.clone(),
+            result_sinks: run.result_sinks.clone(),
+            chunks: self.chunks.clone(),
+            snapshots: self.snapshots.clone(),
+            audit: self.audit.clone(),
+            hreflang: Mutex::new(HreflangIndex::new(&config.policy.preferred_languages)),
+            robots: RobotsCache::default(),
+            fetcher: Arc::clone(&fetcher),
+            config: config.clone(),
+        });
+
+        let monitor =
+            Arc::new(ResourceMonitor::with_config(config.memory.clone()).with_alert_manager(Arc::clone(&alerts)));
+        let mut pressure = monitor.subscribe();
+        let monitor_handle = Arc::clone(&monitor).spawn();
+        let gc_handle = self.purger.as_ref().filter(|_| config.gc.enabled).map(|purger| {
+            let gc = GarbageCollector::new(config.gc.clone(), Arc::clone(purger)).with_dedup(Arc::clone(&ctx.dedup));
+            Arc::new(gc).spawn()
+        });
+        let clusters = config.clusters.enabled.then(|| {
+            Arc::new(ClusterReporter::new(config.clusters.clone(), Arc::clone(&ctx.dedup)))
+        });
+        let clusters_handle = clusters.as_ref().map(|reporter| Arc::clone(reporter).spawn());
+        // * Expired records of `recrawl` rules come back here and are fetched again
+        let (recrawl_tx, mut recrawl_rx) = mpsc::unbounded_channel();
+        let expiry_handle = if config.expiry.enabled {
+            let expiry = ExpiryScheduler::new(config.expiry.clone(), self.store.clone())?
+                .with_dedup(Arc::clone(&ctx.dedup))
+                .with_recrawl(recrawl_tx);
+            Some(Arc::new(expiry).spawn())
+        } else {
+            None
+        };
+
+        let enrichment = if config.enable_enrichment {
+            let mut worker =
+                AIEnrichmentWorker::with_config(config.worker.clone()).with_alert_manager(Arc::clone(&alerts));
+            if let Some(ner) = &self.ner {
+                worker = worker.with_ner(Arc::clone(ner));
+            }
+            if let Some(summarizer) = &self.summarizer {
+                worker = worker.with_summarizer(Arc::clone(summarizer));
+            }
+            if let Some(chunks) = &self.chunks {
+                worker = worker.with_chunk_store(Arc::clone(chunks));
+            }
+            let handle = worker.start(self.store.clone(), self.store.clone()).await;
+            Some((worker, handle))
+        } else {
+            None
+        };
+
+        info!(
+            seeds = seeds.len(),
+            queued = frontier.len(),
+            max_pages = config.max_pages,
+            max_depth = config.max_depth,
+            "Crawl started"
+        );
+        ops::publish_event(CrawlEvent::CrawlStarted {
+            seeds: seeds.len(),
+            queued: frontier.len(),
+            max_pages: config.max_pages,
+            max_depth: config.max_depth,
+        });
+
+        let mut rate_limit_updates = self.rate_limit_updates.clone();
+        let mut scorer_updates = self.scorer_updates.clone();
+
+        // * Pages run on host-sharded lanes; each reports back here once, even if it panics
+        let (outcome_tx, mut outcomes) = mpsc::unbounded_channel();
+        let page_ctx = Arc::clone(&ctx);
+        let dispatcher = Dispatcher::spawn(config.dispatcher.clone(), move |entry: FrontierEntry| {
+            let (ctx, outcome_tx) = (Arc::clone(&page_ctx), outcome_tx.clone());
+            async move {
+                let url = entry.url.clone();
+                match AssertUnwindSafe(ctx.process_page(entry)).catch_unwind().await {
+                    Ok(outcome) => {
+                        let _ = outcome_tx.send(Ok(outcome));
+                    }
+                    Err(panic) => {
+                        let _ = outcome_tx.send(Err(url));
+                        std::panic::resume_unwind(panic);
+                    }
+                }
+            }
+        });
+        // * Pages submitted to the dispatcher whose outcome hasn't come back yet
+        let mut running = 0;
+        // * URL -> entry of pages being processed, re-queued by checkpoints
+        let mut in_flight: HashMap<String, FrontierEntry> = HashMap::new();
+        let checkpoint_interval = Duration::from_secs(config.checkpoint_interval_secs);
+        let mut next_checkpoint = Instant::now() + checkpoint_interval;
+        let mut drain_deadline = None;
+        let concurrency = config.concurrency.max(1);
+        // * Popped entries of paused, quarantined or throttled domains, returned to the frontier
+        // * on resume or once the domain's quarantine or Retry-After backoff ends
+        let mut parked: Vec<FrontierEntry> = Vec::new();
+        // * Transiently failed entries and when they may be fetched again
+        let mut retrying: Vec<(Instant, FrontierEntry)> = Vec::new();
+        let mut retries = RetryTracker::new(config.retry.clone());
+        let held = |domain: &str| {
+            run.control.as_ref().is_some_and(|c| c.is_held(domain))
+                || ctx.rate_limiter.backoff_deadline(domain).is_some()
+        };
+
+        loop {
+            // * Hot-reloaded settings take effect between dispatches
+            if let Some(rx) = rate_limit_updates.as_mut() {
+                if rx.has_changed().unwrap_or(false) {
+                    let delay = rx.borrow_and_update().clone();
+                    info!(standard_delay_ms = delay.standard_delay_ms, "Applying reloaded crawl delay");
+                    ctx.rate_limiter.set_default_delay(delay).await;
+                }
+            }
+            if let Some(rx) = scorer_updates.as_mut() {
+                if rx.has_changed().unwrap_or(false) {
+                    let reloaded = self.link_scorer(&rx.borrow_and_update(), &seeds);
+                    match reloaded {
+                        Ok(mut scorer) => {
+                            // * Weights learned so far survive the reload while learning stays enabled
+                            if let (Some(_), Some(learner)) = (scorer.learner(), frontier.scorer().learner()) {
+                                scorer = scorer.with_learner(Arc::clone(learner));
+                            }
+                            info!("Applying reloaded link scorer weights");
+                            frontier.set_scorer(scorer);
+                        }
+                        Err(e) => warn!(error = %e, "Keeping the current link scorer"),
+                    }
+                }
+            }
+
+            if let Some(control) = &run.control {
+                let new_seeds: Vec<String> = control
+                    .take_seeds()
+                    .iter()
+                    .filter_map(|s| normalize_url(s, s))
+                    .collect();
+                if !new_seeds.is_empty() {
+                    for seed in &new_seeds {
+                        if let Some(host) = host_of(seed) {
+                            frontier.allow_host(&host);
+                        }
+                    }
+                    seeds.extend(new_seeds.iter().cloned());
+                    frontier.set_url_filter(Arc::new(UrlFilter::new(&config.url_filter)?.with_sites(&seeds)));
+                    let scorer = frontier.scorer().clone().with_seed_domains(new_seeds.iter().filter_map(|s| host_of(s)));
+                    frontier.set_scorer(scorer);
+                    let queued = new_seeds.iter().filter(|s| frontier.push(s, "", 0)).count();
+                    info!(submitted = new_seeds.len(), queued, "Added seeds from the control plane");
+                }
+            }
+
+            let (still_held, released): (Vec<_>, Vec<_>) =
+                parked.drain(..).partition(|entry| host_of(&entry.url).is_some_and(|host| held(&host)));
+            parked = still_held;
+            for entry in released {
+                requeue(&mut frontier, &mut report, entry, "parked");
+            }
+            let now = Instant::now();
+            let (due, waiting): (Vec<_>, Vec<_>) = retrying.drain(..).partition(|(at, _)| *at <= now);
+            retrying = waiting;
+            for (_, entry) in due {
+                requeue(&mut frontier, &mut report, entry, "retry");
+            }
+
+            while let Ok(url) = recrawl_rx.try_recv() {
+                debug!(url = %url, "Re-queueing expired page");
+                requeue(&mut frontier, &mut report, FrontierEntry::new(url, 0), "recrawl");
+            }
+
+            let cancelled = run.control.as_ref().is_some_and(|c| c.is_cancelled());
+            if drain_deadline.is_none() && (cancelled || self.shutdown.as_ref().is_some_and(|s| s.is_triggered())) {
+                info!(
+                    in_flight = running,
+                    queued = frontier.len(),
+                    cancelled,
+                    "Shutdown requested; draining in-flight pages"
+                );
+                report.interrupted = true;
+                drain_deadline = Some(
+                    tokio::time::Instant::now()
+                        + Duration::from_millis(config.shutdown_drain_timeout_ms),
+                );
+            }
+
+            // * Entering memory pressure also drops the browser if no render holds it [NFR-02]
+            if pressure.has_changed().unwrap_or(false) && *pressure.borrow_and_update() {
+                ctx.fetcher.release_idle().await;
+            }
+
+            // * Only admit new work while memory pressure is low and no shutdown is pending [NFR-02]
+            let crawl_paused = run.control.as_ref().is_some_and(|c| c.is_crawl_paused());
+            if drain_deadline.is_none() && !crawl_paused && !monitor.is_under_pressure() {
+                while running < concurrency && dispatched < config.max_pages {
+                    let Some(entry) = frontier.pop() else {
+                        break;
+                    };
+                    let domain = host_of(&entry.url);
+                    // * Budgets are checked at pop time too: earlier links were queued before they ran out
+                    if let Some(domain) = &domain {
+                        if held(domain) {
+                            parked.push(entry);
+                            continue;
+                        }
+                        let stats = report.domains.entry(domain.clone()).or_default();
+                        if let Err(violation) = policy.check_budget(domain, stats) {
+                            skip_by_policy(&mut report, &entry.url, violation);
+                            continue;
+                        }
+                    }
+                    if let Err(e) = dispatcher.submit(domain.as_deref().unwrap_or_default(), entry.clone()) {
+                        warn!(url = %entry.url, error = %e, "Dispatcher refused page; leaving it queued");
+                        requeue(&mut frontier, &mut report, entry, "dispatch");
+                        break;
+                    }
+                    if let Some(stats) = domain.and_then(|domain| report.domains.get_mut(&domain)) {
+                        stats.pages_dispatched += 1;
+                    }
+                    dispatched += 1;
+                    running += 1;
+                    in_flight.insert(entry.url.clone(), entry);
+                    ops::increment_active_crawlers();
+                }
+            }
+            ops::set_queue_depth(FRONTIER_QUEUE_NAME, frontier.len() as i64);
+            if let Some(control) = &run.control {
+                control.publish(CrawlProgress {
+                    running: true,
+                    paused: crawl_paused,
+                    frontier_depth: frontier.len(),
+                    in_flight: running,
+                    parked: parked.len(),
+                    dispatched,
+                    report: report.clone(),
+                });
+            }
+
+            let joined = match drain_deadline {
+                Some(deadline) => tokio::select! {
+                    joined = next_outcome(&mut outcomes, running) => joined,
+                    _ = tokio::time::sleep_until(deadline) => {
+                        warn!(abandoned = running, "Shutdown drain deadline reached; aborting in-flight pages");
+                        report.pages_abandoned = running;
+                        for _ in 0..running {
+                            ops::decrement_active_crawlers();
+                        }
+                        dispatcher.abort();
+                        break;
+                    }
+                },
+                None => tokio::select! {
+                    joined = next_outcome(&mut outcomes, running) => joined,
+                    _ = wait_for_shutdown(self.shutdown.as_ref()) => continue,
+                    _ = wait_for_control(run.control.as_deref()), if running > 0 => continue,
+                },
+            };
+            let Some(joined) = joined else {
+                if drain_deadline.is_some()
+                    || dispatched >= config.max_pages
+                    || (frontier.is_empty() && parked.is_empty() && retrying.is_empty())
+                {
+                    break;
+                }
+                if crawl_paused {
+                    // * Nothing runs until the crawl is resumed or cancelled
+                    tokio::select! {
+                        _ = wait_for_control(run.control.as_deref()) => {}
+                        _ = wait_for_shutdown(self.shutdown.as_ref()) => {}
+                    }
+                    continue;
+                }
+                if frontier.is_empty() {
+                    // * Only held domains and pending retries have work left; wait for a resume, a
+                    // * backoff, quarantine or retry delay to end, new seeds or shutdown
+                    let next_release = parked
+                        .iter()
+                        .filter_map(|entry| host_of(&entry.url))
+                        .filter_map(|host| {
+                            let quarantine = run.control.as_ref().and_then(|c| c.quarantine_deadline(&host));
+                            ctx.rate_limiter.backoff_deadline(&host).max(quarantine)
+                        })
+                        .chain(retrying.iter().map(|(at, _)| *at))
+                        .min();
+                    tokio::select! {
+                        _ = wait_for_control(run.control.as_deref()) => {}
+                        _ = wait_for_shutdown(self.shutdown.as_ref()) => {}
+                        _ = tokio::time::sleep_until(next_release.unwrap_or_else(Instant::now).into()),
+                            if next_release.is_some() => {}
+                    }
+                    continue;
+                }
+                tokio::time::sleep(Duration::from_millis(PRESSURE_BACKOFF_MS)).await;
+                continue;
+            };
+            running -= 1;
+            ops::decrement_active_crawlers();
+
+            match joined {
+                Ok(outcome) => {
+                    let entry = in_flight.remove(&outcome.url);
+                    // * Rate-limited pages wait like those of held domains; they haven't failed
+                    if let (Some(wait), Some(entry)) = (outcome.deferred, &entry) {
+                        debug!(url = %outcome.url, wait_ms = wait.as_millis() as u64, "Holding rate-limited page");
+                        refund_dispatch(&mut report, &mut dispatched, &outcome.url);
+                        retrying.push((Instant::now() + wait, entry.clone()));
+                        continue;
+                    }
+                    // * Transient failures go back to the frontier after a backoff; the worker moves on
+                    if let (PageStatus::FetchFailed, Some(category), Some(entry)) = (outcome.status, outcome.retry, entry) {
+                        if let Some(delay) = retries.schedule(&outcome.url, category) {
+                            debug!(url = %outcome.url, category = category.as_str(), delay_ms = delay.as_millis() as u64, "Retrying fetch");
+                            ops::record_fetch_retry(category.as_str(), "scheduled");
+                            report.fetch_retries += 1;
+                            refund_dispatch(&mut report, &mut dispatched, &outcome.url);
+                            retrying.push((Instant::now() + delay, entry));
+                            continue;
+                        }
+                        ops::record_fetch_retry(category.as_str(), "exhausted");
+                    }
+                    retries.forget(&outcome.url);
+                    report.record(&outcome);
+                    // * Fetched pages teach the scorer which links pay off
+                    if matches!(outcome.status, PageStatus::Persisted | PageStatus::Duplicate | PageStatus::Empty) {
+                        frontier.scorer().record_outcome(
+                            &outcome.url,
+                            outcome.quality_score.unwrap_or(0.0),
+                            outcome.status == PageStatus::Duplicate,
+                        );
+                    }
+                    // * The redirect target was just fetched under the original URL; don't queue it again
+                    if let Some(redirect) = &outcome.redirect {
+                        if let Some(target) = normalize_url(&redirect.final_url, &redirect.final_url) {
+                            frontier.mark_seen(&target);
+                        }
+                    }
+                    let hreflang = ctx.hreflang.lock().unwrap();
+                    for (url, anchor) in &outcome.links {
+                        if hreflang.is_unwanted(url) {
+                            skip_by_policy(&mut report, url, PolicyViolation::LanguageVariant);
+                            continue;
+                        }
+                        if let Err(violation) = check_link(&policy, &report, url, outcome.depth + 1) {
+                            skip_by_policy(&mut report, url, violation);
+                            continue;
+                        }
+                        if frontier.push_discovered(url, anchor, &outcome.url, outcome.depth) {
+                            report.links_enqueued += 1;
+                        }
+                    }
+                }
+                // * A panicking page isn't checkpointed as pending, so a resume won't hit it again
+                Err(url) => {
+                    in_flight.remove(&url);
+                    warn!(url = %url, "Crawl task panicked");
+                    report.fetch_errors += 1;
+                    *report.errors.entry("task_aborted".to_string()).or_default() += 1;
+                }
+            }
+
+            if !checkpoint_interval.is_zero() && Instant::now() >= next_checkpoint {
+                if let Some(store) = &run.checkpoints {
+                    let checkpoint =
+                        build_checkpoint(&seeds, dispatched, &report, &frontier, &in_flight, held_back(&parked, &retrying), &ctx);
+                    save_checkpoint(store.as_ref(), &checkpoint).await;
+                }
+                write_summary(&config.report, &report, false).await;
+                next_checkpoint = Instant::now() + checkpoint_interval;
+            }
+        }
+
+        dispatcher.shutdown().await;
+        monitor_handle.abort();
+        if let Some(handle) = gc_handle {
+            handle.abort();
+        }
+        if let Some(handle) = expiry_handle {
+            handle.abort();
+        }
+        if let Some(handle) = clusters_handle {
+            handle.abort();
+        }
+        if let Some(reporter) = &clusters {
+            reporter.write_logged();
+        }
+        fetcher.shutdown().await;
+
+        // * A final checkpoint lets an interrupted run continue (abandoned pages are re-queued)
+        if let Some(store) = &run.checkpoints {
+            let checkpoint =
+                build_checkpoint(&seeds, dispatched, &report, &frontier, &in_flight, held_back(&parked, &retrying), &ctx);
+            save_checkpoint(store.as_ref(), &checkpoint).await;
+        }
+
+        if let Some((worker, handle)) = enrichment {
+            // * An interrupted run stops the worker without waiting for the backlog
+            if !report.interrupted {
+                self.drain_enrichment(config.enrichment_drain_timeout_ms).await;
+            }
+            handle.shutdown().await;
+            report.records_enriched = worker.processed_count();
+        }
+        write_summary(&config.report, &report, true).await;
+
+        if let Some(control) = &run.control {
+            control.publish(CrawlProgress {
+                running: false,
+                frontier_depth: frontier.len(),
+                parked: parked.len(),
+                dispatched,
+                report: report.clone(),
+                ..Default::default()
+            });
+        }
+
+        info!(
+            fetched = report.pages_fetched,
+            persisted = report.pages_persisted,
+            duplicates = report.duplicates_skipped,
+            errors = report.fetch_errors,
+            interrupted = report.interrupted,
+            "Crawl finished"
+        );
+        ops::publish_event(CrawlEvent::CrawlFinished {
+            pages_fetched: report.pages_fetched,
+            pages_persisted: report.pages_persisted,
+            fetch_errors: report.fetch_errors,
+            interrupted: report.interrupted,
+        });
+
+        Ok(report)
+    }
+
+    /// Builds the configured link scorer with the crawl's seed hosts as on-site domains
+    fn link_scorer(&self, config: &ScorerConfig, seeds: &[String]) -> Result<LinkScorer, AuthorityError> {
+        let scorer = LinkScorer::from_config(config.clone())?.with_seed_domains(seeds.iter().filter_map(|s| host_of(s)));
+        Ok(match &self.topic_embedder {
+            Some(embedder) => scorer.with_topic_embedder(Arc::clone(embedder)),
+            None => scorer,
+        })
+    }
+
+    /// Waits until the store has no unenriched records or the timeout elapses
+    async fn drain_enrichment(&self, timeout_ms: u64) {
+        let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+        while Instant::now() < deadline {
+            match self.store.fetch_unenriched(EnrichmentFilter::new(1)).await {
+                Ok(batch) if !batch.is_empty() => {
+                    tokio::time::sleep(Duration::from_millis(DRAIN_POLL_INTERVAL_MS)).await;
+                }
+                _ => return,
+            }
+        }
+        warn!(timeout_ms, "Enrichment did not drain before timeout");
+    }
+}
+
+/// Applies URL patterns, the depth limit and the link's domain budget
+fn check_link(
+    policy: &PolicyEnforcer,
+    report: &CrawlReport,
+    url: &str,
+    depth: usize,
+) -> Result<(), PolicyViolation> {
+    let Some(domain) = host_of(url) else {
+        return Ok(());
+    };
+    policy.check_url(url, &domain, depth)?;
+    match report.domains.get(&domain) {
+        Some(stats) => policy.check_budget(&domain, stats),
+        None => Ok(()),
+    }
+}
+
+/// Counts a URL the policy kept out of the crawl
+/// Returns a held-back entry to the frontier; one the frontier turns away (e.g. when full)
+/// is counted under `requeue_rejected` instead of vanishing
+fn requeue(frontier: &mut CrawlFrontier, report: &mut CrawlReport, entry: FrontierEntry, source: &str) {
+    let url = entry.url.clone();
+    if !frontier.requeue(entry) {
+        warn!(url = %url, source, "Frontier rejected a re-queued page; dropping it");
+        *report.errors.entry("requeue_rejected".to_string()).or_default() += 1;
+    }
+}
+
+fn skip_by_policy(report: &mut CrawlReport, url: &str, violation: PolicyViolation) {
+    debug!(url = %url, reason = violation.as_str(), "Skipped by crawl policy");
+    report.policy_skipped += 1;
+    ops::record_policy_skip(violation.as_str());
+}
+
+/// Snapshots the run, returning in-flight pages to the pending list
+fn build_checkpoint<'a, S>(
+    seeds: &[String],
+    dispatched: usize,
+    report: &CrawlReport,
+    frontier: &CrawlFrontier,
+    in_flight: &HashMap<String, FrontierEntry>,
+    parked: impl IntoIterator<Item = &'a FrontierEntry>,
+    ctx: &CrawlContext<S>,
+) -> CrawlCheckpoint {
+    let mut snapshot = frontier.snapshot();
+    snapshot.pending.extend(in_flight.values().map(PendingUrl::from));
+    snapshot.pending.extend(parked.into_iter().map(PendingUrl::from));
+    let dedup = ctx.dedup.lock().unwrap().snapshot();
+
+    // * Re-queued pages are dispatched again on resume; don't charge their domain twice
+    let mut report = report.clone();
+    for url in in_flight.keys() {
+        if let Some(stats) = host_of(url).and_then(|host| report.domains.get_mut(&host)) {
+            stats.pages_dispatched = stats.pages_dispatched.saturating_sub(1);
+        }
+    }
+
+    CrawlCheckpoint::new(
+        seeds.to_vec(),
+        dispatched.saturating_sub(in_flight.len()),
+        report,
+        snapshot,
+        dedup,
+    )
+}
+
+/// Returns a page's dispatch to the page budgets; it is charged again when dispatched again
+fn refund_dispatch(report: &mut CrawlReport, dispatched: &mut usize, url: &str) {
+    *dispatched -= 1;
+    if let Some(stats) = host_of(url).and_then(|host| report.domains.get_mut(&host)) {
+        stats.pages_dispatched = stats.pages_dispatched.saturating_sub(1);
+    }
+}
+
+/// Entries held out of the frontier: parked domains and pending retries
+fn held_back<'a>(
+    parked: &'a [FrontierEntry],
+    retrying: &'a [(Instant, FrontierEntry)],
+) -> impl Iterator<Item = &'a FrontierEntry> {
+    parked.iter().chain(retrying.iter().map(|(_, entry)| entry))
+}
+
+/// Writes the crawl summaries when a report directory is configured; failures are logged
+async fn write_summary(config: &ReportConfig, report: &CrawlReport, complete: bool) {
+    let Some(dir) = &config.dir else {
+        return;
+    };
+    let summary = CrawlSummary::from_report(report, config, complete);
+    if let Err(e) = summary.write_to(dir, config.markdown_domains).await {
+        warn!(dir = %dir.display(), error = %e, "Failed to write crawl summary");
+    }
+}
+
+/// Saves a checkpoint; failures are logged so they never abort the crawl
+async fn save_checkpoint(store: &dyn CheckpointStore, checkpoint: &CrawlCheckpoint) {
+    match store.save(checkpoint).await {
+        Ok(()) => debug!(
+            pending = checkpoint.frontier.pending.len(),
+            dispatched = checkpoint.dispatched,
+            "Checkpoint saved"
+        ),
+        Err(e) => warn!(error = %e, "Checkpoint save failed"),
+    }
+}
+
+/// Waits for the next page the dispatcher finished; `Err` carries the URL of a page
+/// that panicked, and `None` means nothing is running
+async fn next_outcome(
+    outcomes: &mut mpsc::UnboundedReceiver<Result<PageOutcome, String>>,
+    running: usize,
+) -> Option<Result<PageOutcome, String>> {
+    if running == 0 {
+        return None;
+    }
+    outcomes.recv().await
+}
+
+/// Resolves when the optional shutdown signal fires
+async fn wait_for_shutdown(signal: Option<&ShutdownSignal>) {
+    match signal {
+        Some(signal) => signal.triggered().await,
+        None => std::future::pending().await,
+    }
+}
+
+async fn wait_for_control(control: Option<&CrawlControl>) {
+    match control {
+        Some(control) => control.changed().await,
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orchestrator::checkpoint::CheckpointError;
+    use crate::orchestrator::policy::CrawlPolicy;
+    use crate::orchestrator::report::{SUMMARY_JSON_FILE, SUMMARY_MARKDOWN_FILE};
+    use crate::network::cache::InMemoryValidatorStore;
+    use crate::network::retry::{RetryConfig, RetryRule};
+    use crate::persistence::ChangeKind;
+    use crate::orchestrator::fetcher::FetchFuture;
+    use crate::orchestrator::test_support::{article, MockFetcher};
+    use crate::persistence::WorkerConfig;
+
+    fn test_config() -> CrawlConfig {
+        CrawlConfig {
+            enable_slow_path: false,
+            enable_enrichment: false,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_crawl_follows_links_and_persists() {
+        let fetcher = MockFetcher::default()
+            .page(
+                "https://example.com/",
+                article("gardening", &["/docs/soil", "https://other.com/offsite"]),
+            )
+            .page("https://example.com/docs/soil", article("astronomy", &[]));
+
+        let orchestrator = Orchestrator::new().with_fetcher(Arc::new(fetcher));
+        let report = orchestrator
+            .run(vec!["https://example.com".to_string()], test_config())
+            .await
+            .unwrap();
+
+        assert_eq!(report.pages_fetched, 2);
+        assert_eq!(report.pages_persisted, 2);
+        assert_eq!(report.links_enqueued, 1, "off-domain link must be ignored");
+        assert_eq!(orchestrator.store().count(), 2);
+
+        let records = orchestrator.store().records();
+        let soil = records.iter().find(|r| r.url.ends_with("/docs/soil")).unwrap();
+        assert_eq!(soil.parent_url.as_deref(), Some("https://example.com/"));
+        assert_eq!(soil.crawl_depth, 1);
+    }
+
+    #[tokio::test]
+    async fn test_robots_fetch_does_not_block_other_domains() {
+        let cache = Arc::new(RobotsCache::default());
+        let fetches = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let release = Arc::new(tokio::sync::Notify::new());
+
+        // * Two first visits to slow.example share one fetch that hangs until released
+        let waiters: Vec<_> = (0..2)
+            .map(|_| {
+                let (cache, fetches, release) = (Arc::clone(&cache), Arc::clone(&fetches), Arc::clone(&release));
+                tokio::spawn(async move {
+                    cache
+                        .get_or_fetch("slow.example", || async move {
+                            fetches.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                            release.notified().await;
+                            Some("User-agent: *\nDisallow: /".to_string())
+                        })
+                        .await
+                })
+            })
+            .collect();
+        tokio::task::yield_now().await;
+
+        let fast = tokio::time::timeout(
+            Duration::from_secs(1),
+            cache.get_or_fetch("fast.example", || async { None }),
+        )
+        .await;
+        assert_eq!(fast, Ok(None));
+
+        release.notify_one();
+        for waiter in waiters {
+            assert!(waiter.await.unwrap().is_some());
+        }
+        assert_eq!(fetches.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cached_robots_never_waits_on_a_fetch() {
+        let cache = Arc::new(RobotsCache::default());
+        let release = Arc::new(tokio::sync::Notify::new());
+        let pending = {
+            let (cache, release) = (Arc::clone(&cache), Arc::clone(&release));
+            tokio::spawn(async move {
+                cache
+                    .get_or_fetch("slow.example", || async move {
+                        release.notified().await;
+                        Some("User-agent: *\nDisallow: /".to_string())
+                    })
+                    .await
+            })
+        };
+        tokio::task::yield_now().await;
+
+        // * In flight and unknown hosts both read as not cached
+        assert_eq!(cache.cached("slow.example"), None);
+        assert_eq!(cache.cached("other.example"), None);
+
+        release.notify_one();
+        pending.await.unwrap();
+        assert_eq!(cache.cached("slow.example"), Some(Some("User-agent: *\nDisallow: /".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_crawl_respects_robots_and_depth() {
+        let mut fetcher = MockFetcher::default()
+            .page(
+                "https://example.com/",
+                article("cooking", &["/private/recipes", "/docs/baking"]),
+            )
+            .page("https://example.com/docs/baking", article("sailing", &["/docs/deeper"]));
+        fetcher.robots = Some("User-agent: *\nDisallow: /private/".to_string());
+
+        let config = CrawlConfig {
+            max_depth: 1,
+            ..test_config()
+        };
+        let orchestrator = Orchestrator::new().with_fetcher(Arc::new(fetcher));
+        let report = orchestrator
+            .run(vec!["https://example.com/".to_string()], config)
+            .await
+            .unwrap();
+
+        assert_eq!(report.robots_blocked, 1);
+        assert_eq!(report.pages_persisted, 2);
+        // * /private/recipes is dropped at admission; /docs/deeper sits at depth 2
+        assert_eq!(report.links_enqueued, 1);
+    }
+
+    #[tokio::test]
+    async fn test_robots_exempt_hosts_skip_disallow() {
+        let mut fetcher = MockFetcher::default()
+            .page("https://staging.example.com/", article("cooking", &["/private/recipes"]))
+            .page("https://staging.example.com/private/recipes", article("sailing", &[]));
+        fetcher.robots = Some("User-agent: *\nDisallow: /".to_string());
+
+        let config = CrawlConfig {
+            robots_exempt_hosts: vec!["example.com".to_string()],
+            ..test_config()
+        };
+        let orchestrator = Orchestrator::new().with_fetcher(Arc::new(fetcher));
+        let report = orchestrator
+            .run(vec!["https://staging.example.com/".to_string()], config)
+            .await
+            .unwrap();
+
+        assert_eq!(report.robots_blocked, 0);
+        assert_eq!(report.pages_persisted, 2);
+    }
+
+    #[tokio::test]
+    async fn test_result_sink_receives_persisted_records() {
+        let fetcher = MockFetcher::default()
+            .page(
+                "https://example.com/",
+                article("gardening", &["https://example.com/copy"]),
+            )
+            .page("https://example.com/copy", article("gardening", &[]));
+        let results = Arc::new(InMemoryRecordStore::new());
+        let orchestrator = Orchestrator::new()
+            .with_fetcher(Arc::new(fetcher))
+            .with_result_sink(results.clone());
+
+        let report = orchestrator
+            .run(vec!["https://example.com/".to_string()], test_config())
+            .await
+            .unwrap();
+
+        // * The duplicate page is not forwarded
+        assert_eq!(report.pages_persisted, 1);
+        assert_eq!(results.count(), 1);
+        assert_eq!(results.records()[0].url, "https://example.com/");
+    }
+
+    #[tokio::test]
+    async fn test_crawl_skips_duplicates_and_counts_errors() {
+        let fetcher = MockFetcher::default()
+            .page("https://a.example.com/", article("pottery", &[]))
+            .page("https://b.example.com/", article("pottery", &[]));
+
+        let orchestrator = Orchestrator::new().with_fetcher(Arc::new(fetcher));
+        let report = orchestrator
+            .run(
+                vec![
+                    "https://a.example.com/".to_string(),
+                    "https://b.example.com/".to_string(),
+                    "https://missing.example.com/".to_string(),
+                ],
+                test_config(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(report.pages_persisted, 1);
+        assert_eq!(report.duplicates_skipped, 1);
+        assert_eq!(report.fetch_errors, 1);
+        assert_eq!(report.errors["empty_response"], 1);
+        assert_eq!(report.quality_scores.values().sum::<usize>(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_crawl_writes_summary() {
+        let dir = std::env::temp_dir().join(format!("titan-summary-{}", std::process::id()));
+        let fetcher = MockFetcher::default().page("https://example.com/", article("gardening", &["/gone"]));
+
+        let config = CrawlConfig {
+            report: ReportConfig {
+                dir: Some(dir.clone()),
+                ..Default::default()
+            },
+            ..test_config()
+        };
+        let orchestrator = Orchestrator::new().with_fetcher(Arc::new(fetcher));
+        orchestrator
+            .run(vec!["https://example.com/".to_string()], config)
+            .await
+            .unwrap();
+
+        let json = std::fs::read_to_string(dir.join(SUMMARY_JSON_FILE)).unwrap();
+        let summary: CrawlSummary = serde_json::from_str(&json).unwrap();
+        assert!(summary.complete);
+        assert_eq!(summary.unique_pages, 1);
+        assert_eq!(summary.top_errors[0].kind, "empty_response");
+        let markdown = std::fs::read_to_string(dir.join(SUMMARY_MARKDOWN_FILE)).unwrap();
+        assert!(markdown.contains("| example.com |"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_soft_ban_escalates_to_slow_path() {
+        let mut fetcher = MockFetcher::default();
+        fetcher.soft_banned.push("https://example.com/".to_string());
+        fetcher
+            .rendered
+            .insert("https://example.com/".to_string(), article("chess", &[]));
+
+        let config = CrawlConfig {
+            enable_slow_path: true,
+            ..test_config()
+        };
+        let orchestrator = Orchestrator::new().with_fetcher(Arc::new(fetcher));
+        let report = orchestrator
+            .run(vec!["https://example.com/".to_string()], config)
+            .await
+            .unwrap();
+
+        assert_eq!(report.slow_path_renders, 1);
+        assert_eq!(report.pages_persisted, 1);
+    }
+
+    #[tokio::test]
+    async fn test_throttled_domain_waits_for_retry_after() {
+        let mut fetcher = MockFetcher::default().page("https://example.com/b", article("chess", &[]));
+        fetcher
+            .throttled
+            .insert("https://example.com/a".to_string(), Duration::from_millis(1500));
+
+        let config = CrawlConfig {
+            concurrency: 1,
+            crawl_delay: CrawlDelayConfig {
+                standard_delay_ms: 1,
+                slow_path_delay_ms: 1,
+            },
+            ..test_config()
+        };
+        let orchestrator = Orchestrator::new().with_fetcher(Arc::new(fetcher));
+        let started = Instant::now();
+        let report = orchestrator
+            .run(
+                vec!["https://example.com/a".to_string(), "https://example.com/b".to_string()],
+                config,
+            )
+            .await
+            .unwrap();
+
+        // * /b is held until the domain's Retry-After has passed, not rendered or dropped
+        assert_eq!(report.errors.get("http_429"), Some(&1));
+        assert_eq!(report.slow_path_renders, 0);
+        assert_eq!(report.pages_persisted, 1);
+        assert!(started.elapsed() >= Duration::from_millis(1500));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_page_is_held_not_failed() {
+        let mut fetcher = MockFetcher::default();
+        for url in ["https://example.com/a", "https://example.com/b"] {
+            fetcher.throttled.insert(url.to_string(), Duration::from_secs(60));
+        }
+        let coordinator = ops::Coordinator::new();
+        let store = Arc::new(MemoryCheckpointStore::default());
+        let config = CrawlConfig {
+            concurrency: 2,
+            crawl_delay: CrawlDelayConfig {
+                standard_delay_ms: 300,
+                slow_path_delay_ms: 300,
+            },
+            ..test_config()
+        };
+
+        let orchestrator = Orchestrator::new()
+            .with_fetcher(Arc::new(fetcher))
+            .with_checkpoint_store(store.clone())
+            .with_shutdown(coordinator.signal());
+        let run = orchestrator.run(
+            vec!["https://example.com/a".to_string(), "https://example.com/b".to_string()],
+            config,
+        );
+        let trigger = async {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            coordinator.trigger();
+        };
+        let (report, _) = tokio::join!(run, trigger);
+        let report = report.unwrap();
+
+        // * The first page's 429 backs the domain off while the second waits for its crawl delay;
+        // * the second is then held for the backoff instead of failing
+        assert_eq!(report.errors.get("http_429"), Some(&1));
+        assert!(!report.errors.contains_key("rate_limited"));
+        assert_eq!(report.fetch_errors, 1);
+        let checkpoint = store.load().await.unwrap().expect("final checkpoint saved");
+        assert_eq!(checkpoint.frontier.pending.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_recrawl_skips_unchanged_pages() {
+        let mut fetcher = MockFetcher::default()
+            .page("https://a.example.com/", article("gardening", &[]))
+            .page("https://b.example.com/", article("sailing", &[]));
+        fetcher
+            .etags
+            .insert("https://a.example.com/".to_string(), "\"v1\"".to_string());
+        let fetcher = Arc::new(fetcher);
+        let cache = Arc::new(InMemoryValidatorStore::new());
+        let seeds = vec![
+            "https://a.example.com/".to_string(),
+            "https://b.example.com/".to_string(),
+        ];
+
+        let first = Orchestrator::new()
+            .with_fetcher(fetcher.clone())
+            .with_http_cache(cache.clone())
+            .run(seeds.clone(), test_config())
+            .await
+            .unwrap();
+        assert_eq!(first.pages_persisted, 2);
+        assert_eq!(cache.len(), 1);
+
+        let orchestrator = Orchestrator::new()
+            .with_fetcher(fetcher)
+            .with_http_cache(cache);
+        let second = orchestrator.run(seeds, test_config()).await.unwrap();
+        assert_eq!(second.pages_unchanged, 1);
+        assert_eq!(second.pages_fetched, 1);
+        assert_eq!(second.pages_persisted, 1);
+        assert_eq!(second.fetch_errors, 0);
+    }
+
+    #[tokio::test]
+    async fn test_recrawl_tracks_content_changes() {
+        let tracker = Arc::new(ChangeTracker::new());
+        let mut events = tracker.subscribe();
+        let seeds = vec!["https://example.com/".to_string()];
+
+        let mut changed = Vec::new();
+        for topic in ["gardening", "astronomy", "astronomy"] {
+            let fetcher = MockFetcher::default().page("https://example.com/", article(topic, &[]));
+            let report = Orchestrator::new()
+                .with_fetcher(Arc::new(fetcher))
+                .with_change_tracker(tracker.clone())
+                .run(seeds.clone(), test_config())
+                .await
+                .unwrap();
+            changed.push(report.pages_changed);
+        }
+
+        assert_eq!(changed, vec![0, 1, 0]);
+        assert_eq!(tracker.revision("https://example.com/"), Some(2));
+        assert_eq!(events.recv().await.unwrap().kind, ChangeKind::New);
+        assert_eq!(events.recv().await.unwrap().kind, ChangeKind::MajorRewrite);
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_control_seeds_widen_scope() {
+        let fetcher = MockFetcher::default()
+            .page("https://example.com/", article("gardening", &[]))
+            .page("https://other.com/", article("astronomy", &[]));
+        let control = Arc::new(CrawlControl::new());
+        // * Seeds queued before the run are applied when it starts
+        control.enqueue_seeds(["https://other.com/"]);
+
+        let orchestrator = Orchestrator::new()
+            .with_fetcher(Arc::new(fetcher))
+            .with_control(Arc::clone(&control));
+        let report = orchestrator
+            .run(vec!["https://example.com/".to_string()], test_config())
+            .await
+            .unwrap();
+
+        assert_eq!(report.pages_persisted, 2);
+        let progress = control.progress();
+        assert!(!progress.running);
+        assert_eq!(progress.report, report);
+        assert_eq!(control.domain_stats("other.com").unwrap().pages_persisted, 1);
+    }
+
+    #[tokio::test]
+    async fn test_paused_domain_waits_for_resume() {
+        let fetcher = MockFetcher::default()
+            .page("https://example.com/", article("gardening", &["/docs/soil"]))
+            .page("https://example.com/docs/soil", article("astronomy", &[]));
+        let control = Arc::new(CrawlControl::new());
+        control.pause_domain("example.com");
+
+        let orchestrator = Arc::new(
+            Orchestrator::new()
+                .with_fetcher(Arc::new(fetcher))
+                .with_control(Arc::clone(&control)),
+        );
+        let run = tokio::spawn({
+            let orchestrator = Arc::clone(&orchestrator);
+            async move {
+                orchestrator
+                    .run(vec!["https://example.com/".to_string()], test_config())
+                    .await
+            }
+        });
+
+        while control.progress().parked == 0 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert_eq!(control.progress().report.pages_fetched, 0);
+        assert!(!run.is_finished());
+
+        control.resume_domain("example.com");
+        let report = run.await.unwrap().unwrap();
+        assert_eq!(report.pages_persisted, 2);
+    }
+
+    #[tokio::test]
+    async fn test_quarantined_domain_is_released_when_it_ends() {
+        let fetcher = MockFetcher::default()
+            .page("https://example.com/", article("gardening", &["/docs/soil"]))
+            .page("https://example.com/docs/soil", article("astronomy", &[]));
+        let control = Arc::new(CrawlControl::new());
+        control.quarantine_domain("example.com", Duration::from_millis(300));
+
+        let started = Instant::now();
+        let report = Orchestrator::new()
+            .with_fetcher(Arc::new(fetcher))
+            .with_control(Arc::clone(&control))
+            .run(vec!["https://example.com/".to_string()], test_config())
+            .await
+            .unwrap();
+
+        assert_eq!(report.pages_persisted, 2);
+        assert!(started.elapsed() >= Duration::from_millis(300));
+        assert!(control.quarantined_domains().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transient_failures_are_retried_within_budget() {
+        let fetcher = MockFetcher::default()
+            .page("https://example.com/", article("gardening", &[]))
+            .page("https://other.com/", article("astronomy", &[]))
+            .flaky("https://example.com/", 2)
+            .flaky("https://other.com/", 10);
+        let config = CrawlConfig {
+            same_domain_only: false,
+            retry: RetryConfig {
+                connection: RetryRule {
+                    max_retries: 2,
+                    base_delay_ms: 20,
+                },
+                jitter: 0.0,
+                ..Default::default()
+            },
+            ..test_config()
+        };
+
+        let started = Instant::now();
+        let report = Orchestrator::new()
+            .with_fetcher(Arc::new(fetcher))
+            .run(vec!["https://example.com/".to_string(), "https://other.com/".to_string()], config)
+            .await
+            .unwrap();
+
+        assert_eq!(report.pages_persisted, 1);
+        assert_eq!(report.fetch_retries, 4);
+        assert_eq!(report.fetch_errors, 1);
+        assert_eq!(report.errors.get("connect"), Some(&1));
+        // * Retries wait 20ms then 40ms and are not charged to the page budget
+        assert!(started.elapsed() >= Duration::from_millis(60));
+        assert_eq!(report.domains["example.com"].pages_dispatched, 1);
+    }
+
+    #[tokio::test]
+    async fn test_fetched_pages_are_archived_to_warc() {
+        let fetcher = MockFetcher::default()
+            .page("https://example.com/", article("gardening", &["/b"]))
+            .page("https://example.com/b", article("chess", &[]));
+        let path = std::env::temp_dir().join(format!("titan-crawl-{}.warc.gz", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let archive = Arc::new(WarcWriter::create(&path, true).unwrap());
+
+        let report = Orchestrator::new()
+            .with_fetcher(Arc::new(fetcher))
+            .with_warc_archive(archive)
+            .run(vec!["https://example.com/".to_string()], test_config())
+            .await
+            .unwrap();
+
+        let mut urls: Vec<String> = crate::persistence::WarcReader::open(&path)
+            .unwrap()
+            .pages()
+            .map(|page| page.unwrap().url)
+            .collect();
+        urls.sort();
+        assert_eq!(report.pages_persisted, 2);
+        assert_eq!(urls, vec!["https://example.com/", "https://example.com/b"]);
+        // * Transcoded bodies are not wire responses, so none are archived as such
+        let types: Vec<String> = crate::persistence::WarcReader::open(&path)
+            .unwrap()
+            .filter_map(|record| record.unwrap().warc_type().map(str::to_string))
+            .collect();
+        assert!(types.iter().all(|t| t == "warcinfo" || t == "conversion"));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_persisted_records_reference_html_snapshots() {
+        let html = article("gardening", &["/empty"]);
+        let fetcher = MockFetcher::default()
+            .page("https://example.com/", html.clone())
+            .page("https://example.com/empty", "<html><body></body></html>".to_string());
+        let dir = std::env::temp_dir().join(format!("titan-crawl-snapshots-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let snapshots = Arc::new(
+            SnapshotStore::open(crate::persistence::SnapshotConfig {
+                dir: Some(dir.clone()),
+                ..Default::default()
+            })
+            .unwrap(),
+        );
+
+        let orchestrator = Orchestrator::new()
+            .with_fetcher(Arc::new(fetcher))
+            .with_snapshot_store(Arc::clone(&snapshots));
+        let report = orchestrator
+            .run(vec!["https://example.com/".to_string()], test_config())
+            .await
+            .unwrap();
+
+        let record = &orchestrator.store().records()[0];
+        let hash = record.html_snapshot.as_deref().unwrap();
+        assert_eq!(snapshots.get(hash).unwrap(), Some(html));
+        // * Pages that aren't persisted leave no snapshot behind
+        assert_eq!(report.empty_pages, 1);
+        assert_eq!(snapshots.len(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_audit_sampler_copies_processed_pages() {
+        let html = article("beekeeping", &[]);
+        let fetcher = MockFetcher::default().page("https://example.com/", html.clone());
+        let dir = std::env::temp_dir().join(format!("titan-crawl-audit-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let sampler = Arc::new(
+            AuditSampler::open(crate::persistence::AuditConfig {
+                dir: Some(dir.clone()),
+                sample_percent: 100.0,
+                ..Default::default()
+            })
+            .unwrap(),
+        );
+
+        Orchestrator::new()
+            .with_fetcher(Arc::new(fetcher))
+            .with_audit_sampler(Arc::clone(&sampler))
+            .run(vec!["https://example.com/".to_string()], test_config())
+            .await
+            .unwrap();
+
+        let samples = crate::persistence::load_samples(&dir).unwrap();
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].url, "https://example.com/");
+        assert_eq!(samples[0].html, html);
+        assert!(samples[0].result.content.text.contains("beekeeping"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_crawl_publishes_live_events() {
+        let fetcher = MockFetcher::default().page("https://events.example.com/", article("sailing", &[]));
+        let mut events = ops::subscribe_events();
+        Orchestrator::new()
+            .with_fetcher(Arc::new(fetcher))
+            .run(vec!["https://events.example.com/".to_string()], test_config())
+            .await
+            .unwrap();
+
+        // * Other tests publish to the same bus; keep this crawl's page events
+        let mut names = Vec::new();
+        while let Ok(envelope) = events.try_recv() {
+            match &envelope.event {
+                CrawlEvent::PageFetched { url, .. } | CrawlEvent::RecordPersisted { url, .. }
+                    if url == "https://events.example.com/" =>
+                {
+                    names.push(envelope.event.name())
+                }
+                CrawlEvent::CrawlStarted { .. } | CrawlEvent::CrawlFinished { .. } => {
+                    names.push(envelope.event.name())
+                }
+                _ => {}
+            }
+        }
+        assert!(names.contains(&"crawl_started"));
+        assert!(names.contains(&"crawl_finished"));
+        let page: Vec<_> = names.iter().filter(|n| !n.starts_with("crawl_")).collect();
+        assert_eq!(page, [&"page_fetched", &"record_persisted"]);
+    }
+
+    #[tokio::test]
+    async fn test_chunk_store_receives_page_chunks() {
+        let fetcher = MockFetcher::default().page("https://example.com/", article("gardening", &[]));
+        let store = Arc::new(InMemoryRecordStore::new());
+        let orchestrator = Orchestrator::with_store(store.clone())
+            .with_fetcher(Arc::new(fetcher))
+            .with_chunk_store(store.clone());
+
+        orchestrator
+            .run(vec!["https://example.com/".to_string()], test_config())
+            .await
+            .unwrap();
+
+        let record = &store.records()[0];
+        let chunks = store.chunks();
+        assert_eq!(chunks.len(), record.chunk_count as usize);
+        assert!(chunks.iter().all(|c| c.record_id == record.id && c.url == record.url));
+    }
+
+    #[tokio::test]
+    async fn test_prefer_amp_extracts_alternate() {
+        let original = article("gardening", &[]).replace(
+            "<head>",
+            r#"<head><link rel="amphtml" href="/amp/">"#,
+        );
+        let fetcher = MockFetcher::default()
+            .page("https://example.com/", original.clone())
+            .page("https://example.com/amp/", article("sailing", &[]));
+
+        // * Off by default: the original page is extracted
+        let orchestrator = Orchestrator::new().with_fetcher(Arc::new(fetcher));
+        orchestrator
+            .run(vec!["https://example.com/".to_string()], test_config())
+            .await
+            .unwrap();
+        assert!(orchestrator.store().records()[0].text_content.contains("Compost"));
+
+        let fetcher = MockFetcher::default()
+            .page("https://example.com/", original)
+            .page("https://example.com/amp/", article("sailing", &[]));
+        let orchestrator = Orchestrator::new().with_fetcher(Arc::new(fetcher));
+        let config = CrawlConfig {
+            prefer_amp: true,
+            ..test_config()
+        };
+        let report = orchestrator
+            .run(vec!["https://example.com/".to_string()], config)
+            .await
+            .unwrap();
+
+        assert_eq!(report.pages_persisted, 1);
+        let record = &orchestrator.store().records()[0];
+        assert_eq!(record.url, "https://example.com/");
+        assert!(record.text_content.contains("mainsail"));
+    }
+
+    #[tokio::test]
+    async fn test_bans_feed_alert_manager() {
+        let mut fetcher = MockFetcher::default();
+        let seeds: Vec<String> = (0..10)
+            .map(|i| format!("https://banned.example.com/page-{}", i))
+            .collect();
+        fetcher.soft_banned = seeds.clone();
+
+        let config = CrawlConfig {
+            crawl_delay: CrawlDelayConfig {
+                standard_delay_ms: 0,
+                slow_path_delay_ms: 0,
+            },
+            ..test_config()
+        };
+        let alerts = Arc::new(AlertManager::new());
+        let orchestrator = Orchestrator::new()
+            .with_fetcher(Arc::new(fetcher))
+            .with_alert_manager(Arc::clone(&alerts));
+        let report = orchestrator.run(seeds, config).await.unwrap();
+
+        assert_eq!(report.fetch_errors, 10);
+        assert_eq!(alerts.get_stats().active_alert_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_crawl_runs_enrichment() {
+        let fetcher = MockFetcher::default().page("https://example.com/", article("weaving", &[]));
+        let config = CrawlConfig {
+            enable_enrichment: true,
+            worker: WorkerConfig {
+                poll_interval_ms: 10,
+                ..Default::default()
+            },
+            enrichment_drain_timeout_ms: 5_000,
+            ..test_config()
+        };
+
+        let orchestrator = Orchestrator::new().with_fetcher(Arc::new(fetcher));
+        let report = orchestrator
+            .run(vec!["https://example.com/".to_string()], config)
+            .await
+            .unwrap();
+
+        assert_eq!(report.records_enriched, 1);
+        assert_eq!(orchestrator.store().get_enriched_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_drains_in_flight_pages() {
+        let fetcher = MockFetcher::default()
+            .page(
+                "https://example.com/",
+                article("gardening", &["/docs/soil", "/docs/stars"]),
+            )
+            .page("https://example.com/docs/soil", article("astronomy", &[]))
+            .page("https://example.com/docs/stars", article("cooking", &[]))
+            .with_latency(Duration::from_millis(100));
+        let coordinator = ops::Coordinator::new();
+        let config = CrawlConfig {
+            concurrency: 1,
+            ..test_config()
+        };
+
+        let orchestrator = Orchestrator::new()
+            .with_fetcher(Arc::new(fetcher))
+            .with_shutdown(coordinator.signal());
+        let run = orchestrator.run(vec!["https://example.com/".to_string()], config);
+        let trigger = async {
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            coordinator.trigger();
+        };
+        let (report, _) = tokio::join!(run, trigger);
+        let report = report.unwrap();
+
+        // * The seed finishes; its links stay queued
+        assert!(report.interrupted);
+        assert_eq!(report.pages_persisted, 1);
+        assert_eq!(report.pages_abandoned, 0);
+        assert_eq!(report.links_enqueued, 2);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_abandons_pages_past_deadline() {
+        let fetcher = MockFetcher::default()
+            .page("https://example.com/", article("sailing", &[]))
+            .with_latency(Duration::from_secs(10));
+        let coordinator = ops::Coordinator::new();
+        let config = CrawlConfig {
+            shutdown_drain_timeout_ms: 20,
+            ..test_config()
+        };
+
+        let orchestrator = Orchestrator::new()
+            .with_fetcher(Arc::new(fetcher))
+            .with_shutdown(coordinator.signal());
+        let run = orchestrator.run(vec!["https://example.com/".to_string()], config);
+        let trigger = async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            coordinator.trigger();
+        };
+        let (report, _) = tokio::time::timeout(Duration::from_secs(5), async {
+            tokio::join!(run, trigger)
+        })
+        .await
+        .expect("shutdown did not bound the crawl");
+        let report = report.unwrap();
+
+        assert!(report.interrupted);
+        assert_eq!(report.pages_abandoned, 1);
+        assert_eq!(report.pages_persisted, 0);
+    }
+
+    // * Keeps the latest checkpoint in memory
+    #[derive(Default)]
+    struct MemoryCheckpointStore {
+        latest: Mutex<Option<CrawlCheckpoint>>,
+    }
+
+    impl CheckpointStore for MemoryCheckpointStore {
+        fn save<'a>(
+            &'a self,
+            checkpoint: &'a CrawlCheckpoint,
+        ) -> FetchFuture<'a, Result<(), CheckpointError>> {
+            *self.latest.lock().unwrap() = Some(checkpoint.clone());
+            Box::pin(async { Ok(()) })
+        }
+
+        fn load(&self) -> FetchFuture<'_, Result<Option<CrawlCheckpoint>, CheckpointError>> {
+            let latest = self.latest.lock().unwrap().clone();
+            Box::pin(async move { Ok(latest) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_and_resume() {
+        let fetcher = Arc::new(
+            MockFetcher::default()
+                .page(
+                    "https://example.com/",
+                    article("gardening", &["/docs/soil", "/docs/stars"]),
+                )
+                .page("https://example.com/docs/soil", article("astronomy", &[]))
+                .page("https://example.com/docs/stars", article("cooking", &[])),
+        );
+        let store = Arc::new(MemoryCheckpointStore::default());
+
+        let first = Orchestrator::new()
+            .with_fetcher(fetcher.clone())
+            .with_checkpoint_store(store.clone());
+        let config = CrawlConfig {
+            max_pages: 1,
+            ..test_config()
+        };
+        first
+            .run(vec!["https://example.com/".to_string()], config)
+            .await
+            .unwrap();
+
+        let checkpoint = store.load().await.unwrap().expect("final checkpoint saved");
+        assert_eq!(checkpoint.dispatched, 1);
+        assert_eq!(checkpoint.frontier.pending.len(), 2);
+        assert_eq!(checkpoint.report.pages_persisted, 1);
+
+        let second = Orchestrator::new().with_fetcher(fetcher);
+        let config = CrawlConfig {
+            max_pages: 3,
+            ..test_config()
+        };
+        let report = second.resume(checkpoint, config).await.unwrap();
+
+        // * Only the queued pages are fetched; counters carry over
+        assert_eq!(report.pages_fetched, 3);
+        assert_eq!(report.pages_persisted, 3);
+        assert_eq!(second.store().count(), 2);
+        assert_eq!(report.domains["example.com"].pages_persisted, 3);
+    }
+
+    #[test]
+    fn test_rejected_requeue_is_counted() {
+        let mut frontier = CrawlFrontier::new(1, 3);
+        let mut report = CrawlReport::default();
+        assert!(frontier.push("https://example.com/queued", "", 0));
+
+        requeue(&mut frontier, &mut report, FrontierEntry::new("https://example.com/retry", 0), "retry");
+
+        assert_eq!(report.errors.get("requeue_rejected"), Some(&1));
+        assert_eq!(frontier.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_crawl_policy_limits_domain() {
+        let fetcher = MockFetcher::default()
+            .page(
+                "https://example.com/",
+                article("gardening", &["/calendar/2024/05", "/docs/soil", "/docs/stars"]),
+            )
+            .page("https://example.com/docs/soil", article("astronomy", &[]))
+            .page("https://example.com/docs/stars", article("cooking", &[]));
+        let config = CrawlConfig {
+            policy: CrawlPolicy {
+                max_pages_per_domain: Some(2),
+                denied_url_patterns: vec!["/calendar/".to_string()],
+                ..Default::default()
+            },
+            ..test_config()
+        };
+
+        let orchestrator = Orchestrator::new().with_fetcher(Arc::new(fetcher));
+        let report = orchestrator
+            .run(vec!["https://example.com/".to_string()], config)
+            .await
+            .unwrap();
+
+        // * The calendar link is denied; the second docs page exceeds the budget
+        assert_eq!(report.pages_fetched, 2);
+        assert_eq!(report.policy_skipped, 2);
+        assert_eq!(report.links_enqueued, 2);
+        assert_eq!(report.domains["example.com"].pages_dispatched, 2);
+    }
+
+    #[tokio::test]
+    async fn test_crawl_keeps_to_preferred_languages() {
+        let alternates = ["en", "de", "fr"]
+            .iter()
+            .map(|lang| format!(r#"<link rel="alternate" hreflang="{lang}" href="https://example.com/{lang}/story">"#))
+            .collect::<String>();
+        let translated = |topic: &str, links: &[&str]| article(topic, links).replacen("<head>", &format!("<head>{}", alternates), 1);
+        let fetcher = MockFetcher::default()
+            .page("https://example.com/de/story", translated("gardening", &["/fr/story", "/about"]))
+            .page("https://example.com/en/story", translated("astronomy", &[]))
+            .page("https://example.com/fr/story", translated("cooking", &[]))
+            .page("https://example.com/about", article("sailing", &[]));
+        let config = CrawlConfig {
+            policy: CrawlPolicy {
+                preferred_languages: vec!["en".to_string()],
+                ..Default::default()
+            },
+            ..test_config()
+        };
+
+        let orchestrator = Orchestrator::new().with_fetcher(Arc::new(fetcher));
+        let report = orchestrator
+            .run(vec!["https://example.com/de/story".to_string()], config)
+            .await
+            .unwrap();
+
+        // * The German seed hands over to the English variant; the French link is never fetched
+        assert_eq!(report.language_variants_skipped, 1);
+        assert_eq!(report.policy_skipped, 1);
+        assert_eq!(report.pages_fetched, 3);
+        let records = orchestrator.store().records();
+        let mut urls: Vec<&str> = records.iter().map(|r| r.url.as_str()).collect();
+        urls.sort_unstable();
+        assert_eq!(urls, ["https://example.com/about", "https://example.com/en/story"]);
+
+        let english = records.iter().find(|r| r.url.ends_with("/en/story")).unwrap();
+        assert!(english.hreflang_cluster.is_some());
+        assert!(records.iter().find(|r| r.url.ends_with("/about")).unwrap().hreflang_cluster.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_run_rejects_invalid_policy() {
+        let config = CrawlConfig {
+            policy: CrawlPolicy {
+                allowed_url_patterns: vec!["[".to_string()],
+                ..Default::default()
+            },
+            ..test_config()
+        };
+        let orchestrator = Orchestrator::new().with_fetcher(Arc::new(MockFetcher::default()));
+        let result = orchestrator
+            .run(vec!["https://example.com/".to_string()], config)
+            .await;
+
+        assert!(matches!(result, Err(OrchestratorError::Policy(_))));
+    }
+
+    #[tokio::test]
+    async fn test_run_rejects_invalid_seeds() {
+        let orchestrator = Orchestrator::new().with_fetcher(Arc::new(MockFetcher::default()));
+        let result = orchestrator
+            .run(vec!["not a url".to_string(), "ftp://example.com".to_string()], test_config())
+            .await;
+
+        assert!(matches!(result, Err(OrchestratorError::NoValidSeeds)));
+    }
+}
@@ -0,0 +1,256 @@
+// * On-Demand Extraction
+// * Single-URL fetch -> optional render -> Refinery, outside of any crawl. Backs the
+// * POST /extract endpoint so Titan-Flow can run as an extraction microservice
+
+use super::fetcher::{HttpFetcher, PageFetcher, RenderedPage};
+use crate::engine::density::{DensityMetrics, RoutingPath};
+use crate::engine::slow_path::SlowPathError;
+use crate::network::errors::NetworkError;
+use crate::refinery::{Refinery, RefineryConfig, RefineryResult};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::debug;
+use url::Url;
+
+// * Fetch plus render budget when the caller does not set one
+const DEFAULT_EXTRACT_TIMEOUT_MS: u64 = 30_000;
+
+/// When the headless browser renders the page
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RenderMode {
+    /// Fast path only
+    Never,
+    /// Render low-density pages (JS shells) and pages the fast path was banned from
+    #[default]
+    Auto,
+    /// Skip the fast path and always render
+    Always,
+}
+
+/// Options for a single extraction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExtractOptions {
+    pub render: RenderMode,
+    /// Budget for fetching and rendering; refinery processing is not counted
+    pub timeout_ms: u64,
+    pub refinery: RefineryConfig,
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        Self {
+            render: RenderMode::default(),
+            timeout_ms: DEFAULT_EXTRACT_TIMEOUT_MS,
+            refinery: RefineryConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ExtractError {
+    #[error("Invalid URL '{0}': only absolute http(s) URLs can be extracted")]
+    InvalidUrl(String),
+
+    #[error("Fetch failed: {0}")]
+    Fetch(#[from] NetworkError),
+
+    #[error("Render failed: {0}")]
+    Render(#[from] SlowPathError),
+
+    #[error("Extraction timed out after {0}ms")]
+    Timeout(u64),
+}
+
+/// Runs single-URL extractions over a shared fetcher
+///
+/// Unlike a crawl, extractions ignore robots.txt, rate limits and the circuit breaker:
+/// the caller asked for this exact page.
+///
+/// # Example
+/// ```ignore
+/// let extractor = Extractor::new()?;
+/// let result = extractor.extract("https://example.com/", &ExtractOptions::default()).await?;
+/// println!("{:?}", result.metadata.title);
+/// ```
+pub struct Extractor {
+    fetcher: Arc<dyn PageFetcher>,
+}
+
+impl Extractor {
+    /// Creates an extractor with a direct (proxy-less) HTTP fetcher
+    pub fn new() -> Result<Self, NetworkError> {
+        Ok(Self::with_fetcher(Arc::new(HttpFetcher::new(Vec::new(), Vec::new())?)))
+    }
+
+    /// Creates an extractor over a custom fetcher
+    pub fn with_fetcher(fetcher: Arc<dyn PageFetcher>) -> Self {
+        Self { fetcher }
+    }
+
+    /// Fetches, optionally renders, and refines a single page
+    pub async fn extract(&self, url: &str, options: &ExtractOptions) -> Result<RefineryResult, ExtractError> {
+        let parsed = match Url::parse(url.trim()) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => parsed,
+            _ => return Err(ExtractError::InvalidUrl(url.to_string())),
+        };
+        let url = parsed.to_string();
+        let page = tokio::time::timeout(
+            Duration::from_millis(options.timeout_ms),
+            self.fetch_html(&url, options.render),
+        )
+        .await
+        .map_err(|_| ExtractError::Timeout(options.timeout_ms))??;
+        let domain = parsed.host_str().unwrap_or_default();
+        let mut result = Refinery::with_config(options.refinery.clone()).process_for_domain(&page.html, domain);
+        result.diagnostics = page.diagnostics;
+        Ok(result)
+    }
+
+    /// Releases the browser, if one was launched
+    pub async fn shutdown(&self) {
+        self.fetcher.shutdown().await;
+    }
+
+    // * Fast path HTML carries no diagnostics
+    async fn fetch_html(&self, url: &str, mode: RenderMode) -> Result<RenderedPage, ExtractError> {
+        let fetched = |html: String| RenderedPage {
+            html,
+            diagnostics: Vec::new(),
+        };
+        match mode {
+            RenderMode::Always => Ok(self.fetcher.render_page(url).await?),
+            RenderMode::Never => Ok(fetched(self.fetcher.fetch(url).await?)),
+            RenderMode::Auto => match self.fetcher.fetch(url).await {
+                Ok(html) if DensityMetrics::compute(&html).routing == RoutingPath::Slow => {
+                    // * Keep the fast path HTML if the browser fails
+                    match self.fetcher.render_page(url).await {
+                        Ok(rendered) => Ok(rendered),
+                        Err(e) => {
+                            debug!(url = %url, error = %e, "Render failed; using fast path HTML");
+                            Ok(fetched(html))
+                        }
+                    }
+                }
+                Ok(html) => Ok(fetched(html)),
+                Err(NetworkError::SoftBan(_) | NetworkError::HardBan(_) | NetworkError::Throttled { .. }) => {
+                    Ok(self.fetcher.render_page(url).await?)
+                }
+                Err(e) => Err(e.into()),
+            },
+        }
+    }
+}
+
+/// Extracts a single URL with a one-off extractor
+///
+/// Services handling many requests should keep an `Extractor` so the browser is reused.
+pub async fn extract_url(url: &str, options: &ExtractOptions) -> Result<RefineryResult, ExtractError> {
+    let extractor = Extractor::new()?;
+    let result = extractor.extract(url, options).await;
+    extractor.shutdown().await;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orchestrator::test_support::MockFetcher;
+
+    const ARTICLE: &str = r#"<html><head><title>Soil handbook</title></head><body><article>
+        <h1>Everything about soil</h1>
+        <p>Compost heaps warm quickly when layered with straw, coffee grounds and kitchen peelings.
+        Mulch suppresses weeds while worms aerate heavy clay beds. Contact soil@example.com.</p>
+        </article></body></html>"#;
+
+    const SHELL: &str = r#"<html><head><title>App</title><script src="/app.js"></script>
+        <script>window.boot()</script></head><body><div id="root"></div></body></html>"#;
+
+    fn extractor(fetcher: MockFetcher) -> Extractor {
+        Extractor::with_fetcher(Arc::new(fetcher))
+    }
+
+    fn fetcher(page: &str, rendered: Option<&str>) -> MockFetcher {
+        let url = "https://example.com/";
+        let mut fetcher = MockFetcher::default().page(url, page);
+        fetcher.rendered.extend(rendered.map(|html| (url.to_string(), html.to_string())));
+        fetcher
+    }
+
+    #[tokio::test]
+    async fn test_extracts_fast_path_page() {
+        let result = extractor(fetcher(ARTICLE, None))
+            .extract("https://example.com", &ExtractOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(result.metadata.title.as_deref(), Some("Soil handbook"));
+        assert!(result.content.text.contains("Compost heaps"));
+        assert!(result.entities.total_count > 0);
+    }
+
+    #[tokio::test]
+    async fn test_render_modes() {
+        let auto = extractor(fetcher(SHELL, Some(ARTICLE)))
+            .extract("https://example.com/", &ExtractOptions::default())
+            .await
+            .unwrap();
+        assert!(auto.content.text.contains("Compost heaps"));
+
+        let never = ExtractOptions {
+            render: RenderMode::Never,
+            ..Default::default()
+        };
+        let fast = extractor(fetcher(SHELL, Some(ARTICLE)))
+            .extract("https://example.com/", &never)
+            .await
+            .unwrap();
+        assert!(!fast.content.text.contains("Compost heaps"));
+
+        // * Banned on the fast path: Auto falls back to the browser
+        let mut banned = fetcher(SHELL, Some(ARTICLE));
+        banned.hard_banned.push("https://example.com/".to_string());
+        let rendered = extractor(banned)
+            .extract("https://example.com/", &ExtractOptions::default())
+            .await
+            .unwrap();
+        assert!(rendered.content.text.contains("Compost heaps"));
+    }
+
+    #[tokio::test]
+    async fn test_extract_errors() {
+        let err = extractor(fetcher(ARTICLE, None))
+            .extract("ftp://example.com/", &ExtractOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ExtractError::InvalidUrl(_)));
+
+        let err = extractor(fetcher(ARTICLE, None))
+            .extract(
+                "https://example.com/",
+                &ExtractOptions {
+                    render: RenderMode::Always,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ExtractError::Render(SlowPathError::BrowserCrash)));
+
+        let mut slow = fetcher(ARTICLE, None);
+        slow.latency = Duration::from_secs(5);
+        let err = extractor(slow)
+            .extract(
+                "https://example.com/",
+                &ExtractOptions {
+                    timeout_ms: 20,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ExtractError::Timeout(20)));
+    }
+}
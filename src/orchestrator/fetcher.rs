@@ -0,0 +1,402 @@
+// * [FR-01] [FR-03] Page Fetcher
+// * Abstracts the fast path (proxy escalation ladder) and slow path (headless browser)
+// * so the orchestrator can be driven by alternative transports in tests and embeddings
+
+use super::frontier::host_of;
+use crate::engine::browser_limits::BrowserLimits;
+use crate::engine::fingerprint::{BrowserProfile, FingerprintRotator};
+use crate::engine::readiness::ReadinessConfig;
+use crate::engine::slow_path::{PageDiagnostic, SlowPathError, SlowPathRenderer};
+use crate::network::cache::{CacheValidators, ConditionalFetch};
+use crate::network::connections::PoolConfig;
+use crate::network::dns::DnsCache;
+use crate::network::errors::NetworkError;
+use crate::network::preflight::PreflightConfig;
+use crate::network::proxy::ProxyManager;
+use crate::network::redirect::RedirectConfig;
+use crate::persistence::warc::WarcWriter;
+use reqwest::Client;
+use std::sync::Arc;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::warn;
+use url::Url;
+
+// * robots.txt requests are cheap; fail fast rather than stall a domain
+const ROBOTS_TIMEOUT_SECS: u64 = 10;
+
+// * Fetched bodies are transcoded to UTF-8 before the orchestrator sees them
+const HTML_UTF8: &str = "text/html; charset=utf-8";
+
+/// Boxed future returned by fetcher methods
+pub type FetchFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// HTML from the slow path with the errors the page raised while rendering
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RenderedPage {
+    pub html: String,
+    pub diagnostics: Vec<PageDiagnostic>,
+}
+
+/// Transport used by the orchestrator to retrieve pages
+pub trait PageFetcher: Send + Sync {
+    /// Fetches raw HTML over the fast path
+    fn fetch<'a>(&'a self, url: &'a str) -> FetchFuture<'a, Result<String, NetworkError>>;
+
+    /// Fetches over the fast path, revalidating against stored cache validators
+    ///
+    /// The default ignores the validators and never reports `NotModified`.
+    fn fetch_conditional<'a>(
+        &'a self,
+        url: &'a str,
+        _validators: Option<&'a CacheValidators>,
+    ) -> FetchFuture<'a, Result<ConditionalFetch, NetworkError>> {
+        Box::pin(async move {
+            let body = self.fetch(url).await?;
+            Ok(ConditionalFetch::Modified {
+                body,
+                validators: None,
+                encoding: None,
+                truncated: false,
+                redirect: None,
+            })
+        })
+    }
+
+    /// Renders a page in a headless browser and returns the final HTML
+    fn render<'a>(&'a self, url: &'a str) -> FetchFuture<'a, Result<String, SlowPathError>>;
+
+    /// Renders a page, keeping the console diagnostics it raised
+    ///
+    /// The default wraps `render` and reports no diagnostics.
+    fn render_page<'a>(&'a self, url: &'a str) -> FetchFuture<'a, Result<RenderedPage, SlowPathError>> {
+        Box::pin(async move {
+            let html = self.render(url).await?;
+            Ok(RenderedPage {
+                html,
+                diagnostics: Vec::new(),
+            })
+        })
+    }
+
+    /// Fetches robots.txt for the URL's origin (None when absent or unreachable)
+    fn fetch_robots_txt<'a>(&'a self, url: &'a str) -> FetchFuture<'a, Option<String>>;
+
+    /// Releases any held resources (browser processes, connections)
+    fn shutdown(&self) -> FetchFuture<'_, ()> {
+        Box::pin(async {})
+    }
+
+    /// Frees idle resources under memory pressure; they are recreated on demand
+    fn release_idle(&self) -> FetchFuture<'_, ()> {
+        Box::pin(async {})
+    }
+}
+
+/// Production fetcher: proxy escalation ladder plus a lazily launched browser
+///
+/// Each domain is served with its own browser profile, rotated after bans.
+pub struct HttpFetcher {
+    proxies: ProxyManager,
+    robots_client: Client,
+    renderer: Mutex<SlowPathRenderer>,
+    fingerprints: Arc<FingerprintRotator>,
+}
+
+impl HttpFetcher {
+    /// Creates a fetcher with the given Tier 1 and Tier 2 proxy pools
+    pub fn new(tier1: Vec<String>, tier2: Vec<String>) -> Result<Self, NetworkError> {
+        let robots_client = Client::builder()
+            .timeout(Duration::from_secs(ROBOTS_TIMEOUT_SECS))
+            .build()?;
+
+        Ok(Self {
+            proxies: ProxyManager::new(tier1, tier2),
+            robots_client,
+            renderer: Mutex::new(SlowPathRenderer::new()),
+            fingerprints: Arc::new(FingerprintRotator::new()),
+        })
+    }
+
+    /// Replaces the browser profile rotator (e.g. to share it across fetchers)
+    pub fn with_fingerprints(mut self, fingerprints: Arc<FingerprintRotator>) -> Self {
+        self.fingerprints = fingerprints;
+        self
+    }
+
+    /// Resolves hosts (pages and robots.txt) through a shared DNS cache
+    pub fn with_dns_cache(mut self, dns: Arc<DnsCache>) -> Result<Self, NetworkError> {
+        self.robots_client = Client::builder()
+            .timeout(Duration::from_secs(ROBOTS_TIMEOUT_SECS))
+            .dns_resolver(Arc::clone(&dns))
+            .build()?;
+        self.proxies = self.proxies.with_dns_cache(dns);
+        Ok(self)
+    }
+
+    /// Applies connection pool settings to the fast path clients
+    pub fn with_pool(mut self, pool: PoolConfig) -> Self {
+        self.proxies = self.proxies.with_pool(pool);
+        self
+    }
+
+    /// Applies the redirect policy (hop limit, cross-domain rules, meta refresh) to the fast path
+    pub fn with_redirect_policy(mut self, redirect: RedirectConfig) -> Self {
+        self.proxies = self.proxies.with_redirect_policy(redirect);
+        self
+    }
+
+    /// Checks non-page URLs with a HEAD request before fetching them
+    pub fn with_preflight(mut self, preflight: PreflightConfig) -> Self {
+        self.proxies = self.proxies.with_preflight(preflight);
+        self
+    }
+
+    /// Sets when slow path renders are extracted (wait strategy, timeout, per-site overrides)
+    pub fn with_readiness(mut self, readiness: ReadinessConfig) -> Self {
+        self.renderer = Mutex::new(self.renderer.into_inner().with_readiness(readiness));
+        self
+    }
+
+    /// Sets the slow path's per-page watchdogs and browser recycling policy
+    pub fn with_browser_limits(mut self, limits: BrowserLimits) -> Self {
+        self.renderer = Mutex::new(self.renderer.into_inner().with_limits(limits));
+        self
+    }
+
+    /// Caps the size of decompressed response bodies (decompression bomb guard)
+    pub fn with_max_decompressed_bytes(mut self, max_bytes: usize) -> Self {
+        self.proxies = self.proxies.with_max_decompressed_bytes(max_bytes);
+        self
+    }
+
+    /// Cuts response bodies longer than `max_bytes`, flagging them as truncated
+    pub fn with_max_response_bytes(mut self, max_bytes: usize) -> Self {
+        self.proxies = self.proxies.with_max_response_bytes(max_bytes);
+        self
+    }
+
+    /// Returns the profile presented to the URL's domain
+    fn profile_for(&self, url: &str) -> Arc<BrowserProfile> {
+        match host_of(url) {
+            Some(domain) => self.fingerprints.profile_for(&domain),
+            None => Arc::new(BrowserProfile::default()),
+        }
+    }
+}
+
+impl PageFetcher for HttpFetcher {
+    fn fetch<'a>(&'a self, url: &'a str) -> FetchFuture<'a, Result<String, NetworkError>> {
+        Box::pin(async move {
+            let fetched = self.fetch_conditional(url, None).await?;
+            fetched.into_body().ok_or(NetworkError::EmptyResponse(0))
+        })
+    }
+
+    fn fetch_conditional<'a>(
+        &'a self,
+        url: &'a str,
+        validators: Option<&'a CacheValidators>,
+    ) -> FetchFuture<'a, Result<ConditionalFetch, NetworkError>> {
+        Box::pin(async move {
+            let profile = self.profile_for(url);
+            let result = self.proxies.fetch_conditional(url, &profile, validators).await;
+
+            // * A ban that survived the whole proxy ladder points at the fingerprint itself
+            if let (Err(NetworkError::SoftBan(_) | NetworkError::HardBan(_) | NetworkError::Throttled { .. }), Some(domain)) =
+                (&result, host_of(url))
+            {
+                self.fingerprints.rotate(&domain);
+            }
+            result
+        })
+    }
+
+    fn render<'a>(&'a self, url: &'a str) -> FetchFuture<'a, Result<String, SlowPathError>> {
+        Box::pin(async move { self.render_page(url).await.map(|page| page.html) })
+    }
+
+    fn render_page<'a>(&'a self, url: &'a str) -> FetchFuture<'a, Result<RenderedPage, SlowPathError>> {
+        Box::pin(async move {
+            // * A single browser is shared; renders are serialized through the lock
+            let profile = self.profile_for(url);
+            let mut renderer = self.renderer.lock().await;
+            let result = renderer.render_with_profile(url, &profile).await?;
+            Ok(RenderedPage {
+                html: result.html,
+                diagnostics: result.diagnostics,
+            })
+        })
+    }
+
+    fn fetch_robots_txt<'a>(&'a self, url: &'a str) -> FetchFuture<'a, Option<String>> {
+        Box::pin(async move {
+            let robots_url = robots_url(url)?;
+            let profile = self.profile_for(url);
+            let resp = self
+                .robots_client
+                .get(&robots_url)
+                .headers(profile.to_header_map())
+                .send()
+                .await
+                .ok()?;
+            if !resp.status().is_success() {
+                return None;
+            }
+            resp.text().await.ok()
+        })
+    }
+
+    fn shutdown(&self) -> FetchFuture<'_, ()> {
+        Box::pin(async move {
+            self.renderer.lock().await.shutdown().await;
+        })
+    }
+
+    fn release_idle(&self) -> FetchFuture<'_, ()> {
+        Box::pin(async move {
+            // * A browser that is rendering right now is not idle
+            if let Ok(mut renderer) = self.renderer.try_lock() {
+                if renderer.is_running() {
+                    renderer.shutdown().await;
+                }
+            }
+        })
+    }
+}
+
+/// Fetcher that archives everything another fetcher retrieves to a WARC file
+///
+/// Fast path bodies reach this layer already transcoded to UTF-8, without the wire status
+/// and headers, so they are stored as `conversion` records rather than HTTP responses;
+/// renders are stored as `resource` records. Archive failures are logged and never fail
+/// the fetch.
+pub struct ArchivingFetcher {
+    inner: Arc<dyn PageFetcher>,
+    archive: Arc<WarcWriter>,
+}
+
+impl ArchivingFetcher {
+    pub fn new(inner: Arc<dyn PageFetcher>, archive: Arc<WarcWriter>) -> Self {
+        Self { inner, archive }
+    }
+
+    fn archive_body(&self, url: &str, content_type: &str, body: &str, truncated: bool) {
+        if let Err(e) = self.archive.write_conversion(url, content_type, body.as_bytes(), truncated) {
+            warn!(url, error = %e, "Failed to archive response");
+        }
+    }
+}
+
+impl PageFetcher for ArchivingFetcher {
+    fn fetch<'a>(&'a self, url: &'a str) -> FetchFuture<'a, Result<String, NetworkError>> {
+        Box::pin(async move {
+            let body = self.inner.fetch(url).await?;
+            self.archive_body(url, HTML_UTF8, &body, false);
+            Ok(body)
+        })
+    }
+
+    fn fetch_conditional<'a>(
+        &'a self,
+        url: &'a str,
+        validators: Option<&'a CacheValidators>,
+    ) -> FetchFuture<'a, Result<ConditionalFetch, NetworkError>> {
+        Box::pin(async move {
+            let fetched = self.inner.fetch_conditional(url, validators).await?;
+            if let ConditionalFetch::Modified {
+                body,
+                truncated,
+                redirect,
+                ..
+            } = &fetched
+            {
+                // * Redirected bodies are archived under the URL that served them
+                let served_from = redirect.as_ref().map_or(url, |chain| chain.final_url.as_str());
+                self.archive_body(served_from, HTML_UTF8, body, *truncated);
+            }
+            Ok(fetched)
+        })
+    }
+
+    fn render<'a>(&'a self, url: &'a str) -> FetchFuture<'a, Result<String, SlowPathError>> {
+        Box::pin(async move { self.render_page(url).await.map(|page| page.html) })
+    }
+
+    fn render_page<'a>(&'a self, url: &'a str) -> FetchFuture<'a, Result<RenderedPage, SlowPathError>> {
+        Box::pin(async move {
+            let page = self.inner.render_page(url).await?;
+            if let Err(e) = self.archive.write_resource(url, HTML_UTF8, page.html.as_bytes()) {
+                warn!(url, error = %e, "Failed to archive rendered page");
+            }
+            Ok(page)
+        })
+    }
+
+    fn fetch_robots_txt<'a>(&'a self, url: &'a str) -> FetchFuture<'a, Option<String>> {
+        Box::pin(async move {
+            let robots = self.inner.fetch_robots_txt(url).await;
+            if let (Some(body), Some(robots_url)) = (&robots, robots_url(url)) {
+                self.archive_body(&robots_url, "text/plain; charset=utf-8", body, false);
+            }
+            robots
+        })
+    }
+
+    fn shutdown(&self) -> FetchFuture<'_, ()> {
+        self.inner.shutdown()
+    }
+
+    fn release_idle(&self) -> FetchFuture<'_, ()> {
+        self.inner.release_idle()
+    }
+}
+
+/// Returns the robots.txt URL for the origin of `url`
+pub fn robots_url(url: &str) -> Option<String> {
+    let mut parsed = Url::parse(url).ok()?;
+    parsed.host_str()?;
+    parsed.set_path("/robots.txt");
+    parsed.set_query(None);
+    parsed.set_fragment(None);
+    Some(parsed.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_robots_url() {
+        assert_eq!(
+            robots_url("https://example.com/docs/page?x=1#top"),
+            Some("https://example.com/robots.txt".to_string())
+        );
+        assert_eq!(
+            robots_url("http://example.com:8080/a"),
+            Some("http://example.com:8080/robots.txt".to_string())
+        );
+        assert_eq!(robots_url("not a url"), None);
+    }
+
+    #[test]
+    fn test_http_fetcher_builds_without_proxies() {
+        assert!(HttpFetcher::new(Vec::new(), Vec::new()).is_ok());
+    }
+
+    #[test]
+    fn test_fetcher_shares_domain_profiles() {
+        let rotator = Arc::new(FingerprintRotator::new());
+        let fetcher = HttpFetcher::new(Vec::new(), Vec::new())
+            .unwrap()
+            .with_fingerprints(Arc::clone(&rotator));
+
+        let profile = fetcher.profile_for("https://example.com/a");
+        assert_eq!(profile, fetcher.profile_for("https://example.com/b?page=2"));
+
+        let rotated = rotator.rotate("example.com");
+        assert_eq!(fetcher.profile_for("https://example.com/a"), rotated);
+    }
+}
@@ -0,0 +1,768 @@
+// * [EDD-4] Crawl Frontier
+// * Priority-ordered URL queue with depth tracking, scope filtering and seen-set dedup
+
+use super::spill::SpillStore;
+use crate::engine::normalization::normalize_url;
+use crate::engine::url_filter::UrlFilter;
+use crate::persistence::{LinkScorer, PriorityLinkQueue, ScoredLink};
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::{Arc, LazyLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+use url::Url;
+
+// * Per-entry bookkeeping beyond the URL and anchor text (scores, map and vec slots)
+const ENTRY_OVERHEAD_BYTES: usize = 96;
+
+static LINK_SELECTOR: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("a[href]").unwrap());
+
+/// A URL popped from the frontier, ready to be fetched
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontierEntry {
+    pub url: String,
+    pub depth: usize,
+    pub score: f32,
+    /// Page the URL was found on (None for seeds)
+    pub parent_url: Option<String>,
+    /// When the URL was first queued (Unix seconds)
+    pub discovered_at: u64,
+}
+
+impl FrontierEntry {
+    /// Creates an entry without a parent page, e.g. to re-queue a URL
+    pub fn new(url: impl Into<String>, depth: usize) -> Self {
+        Self {
+            url: url.into(),
+            depth,
+            score: 0.0,
+            parent_url: None,
+            discovered_at: 0,
+        }
+    }
+}
+
+/// A queued URL as stored in a checkpoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingUrl {
+    pub url: String,
+    pub anchor_text: String,
+    pub depth: usize,
+    // * Provenance; defaults keep checkpoints written before it was tracked readable
+    #[serde(default)]
+    pub parent_url: Option<String>,
+    #[serde(default)]
+    pub discovered_at: u64,
+}
+
+impl From<&FrontierEntry> for PendingUrl {
+    fn from(entry: &FrontierEntry) -> Self {
+        Self {
+            url: entry.url.clone(),
+            anchor_text: String::new(),
+            depth: entry.depth,
+            parent_url: entry.parent_url.clone(),
+            discovered_at: entry.discovered_at,
+        }
+    }
+}
+
+impl From<&ScoredLink> for PendingUrl {
+    fn from(link: &ScoredLink) -> Self {
+        Self {
+            url: link.url.clone(),
+            anchor_text: link.anchor_text.clone(),
+            depth: link.depth,
+            parent_url: link.parent_url.clone(),
+            discovered_at: link.discovered_at,
+        }
+    }
+}
+
+/// Serializable frontier state: queued URLs, the seen set and the crawl scope
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FrontierSnapshot {
+    /// Waiting URLs in priority order
+    pub pending: Vec<PendingUrl>,
+    /// Every URL ever accepted, including those already fetched
+    pub seen: Vec<String>,
+    /// Host scope (None when unscoped)
+    pub allowed_hosts: Option<Vec<String>>,
+}
+
+/// What a full frontier does with a newly discovered URL
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapacityPolicy {
+    /// Drop the new URL
+    #[default]
+    RejectNew,
+    /// Drop the lowest-scored waiting URL if the new one scores higher
+    ///
+    /// Evicted URLs leave the seen set, so a later discovery can queue them again.
+    EvictLowest,
+    /// Spill the coldest waiting URLs to disk; capacity then only bounds the in-memory queue
+    ///
+    /// Needs a spill store (`crawl.spill.dir`); without one the new URL is dropped.
+    SpillToDisk,
+}
+
+/// Disk overflow for the lowest-priority waiting URLs
+#[derive(Debug)]
+struct FrontierSpill {
+    store: SpillStore,
+    budget_bytes: usize,
+    /// Set after a failed write; the queue then stays in memory
+    disabled: bool,
+}
+
+/// Crawl frontier backed by the intrinsic link scorer
+///
+/// With a spill store attached, waiting URLs beyond the memory budget are written to
+/// disk lowest-priority first and read back once the in-memory queue runs dry.
+#[derive(Debug)]
+pub struct CrawlFrontier {
+    queue: PriorityLinkQueue,
+    seen: HashSet<String>,
+    allowed_hosts: Option<HashSet<String>>,
+    url_filter: Option<Arc<UrlFilter>>,
+    max_depth: usize,
+    capacity: usize,
+    queued_bytes: usize,
+    spill: Option<FrontierSpill>,
+    capacity_policy: CapacityPolicy,
+}
+
+impl CrawlFrontier {
+    /// Creates an unscoped frontier
+    pub fn new(capacity: usize, max_depth: usize) -> Self {
+        Self::with_scorer(capacity, max_depth, LinkScorer::new())
+    }
+
+    /// Creates an unscoped frontier ordered by a custom link scorer
+    pub fn with_scorer(capacity: usize, max_depth: usize, scorer: LinkScorer) -> Self {
+        Self {
+            queue: PriorityLinkQueue::with_scorer(capacity, scorer),
+            seen: HashSet::new(),
+            allowed_hosts: None,
+            url_filter: None,
+            max_depth,
+            capacity,
+            queued_bytes: 0,
+            spill: None,
+            capacity_policy: CapacityPolicy::default(),
+        }
+    }
+
+    /// Spills waiting URLs to `store` while their estimated size exceeds `budget_bytes`
+    pub fn with_spill(mut self, store: SpillStore, budget_bytes: usize) -> Self {
+        self.spill = Some(FrontierSpill {
+            store,
+            budget_bytes,
+            disabled: false,
+        });
+        self.spill_if_over_budget();
+        self
+    }
+
+    /// Sets what happens to new URLs once the frontier is full
+    pub fn with_capacity_policy(mut self, policy: CapacityPolicy) -> Self {
+        self.capacity_policy = policy;
+        self
+    }
+
+    /// Restricts the frontier to the given hosts
+    pub fn with_allowed_hosts<I, S>(mut self, hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_hosts = Some(hosts.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Rejects URLs that fail the filter's allow/deny and same-site rules
+    pub fn with_url_filter(mut self, filter: Arc<UrlFilter>) -> Self {
+        self.url_filter = Some(filter);
+        self
+    }
+
+    /// Replaces the URL filter (e.g. after seeds from new sites were added)
+    pub fn set_url_filter(&mut self, filter: Arc<UrlFilter>) {
+        self.url_filter = Some(filter);
+    }
+
+    /// Adds a host to a scoped frontier; unscoped frontiers already accept every host
+    pub fn allow_host(&mut self, host: &str) {
+        if let Some(allowed) = &mut self.allowed_hosts {
+            allowed.insert(host.to_string());
+        }
+    }
+
+    /// Adds a normalized URL at the given depth
+    ///
+    /// Returns false when the URL was already seen, is out of scope or
+    /// filtered, exceeds the depth limit, or the queue is full and the
+    /// capacity policy could not make room.
+    pub fn push(&mut self, url: &str, anchor_text: &str, depth: usize) -> bool {
+        self.push_with_parent(url, anchor_text, depth, None)
+    }
+
+    /// Adds a URL found on `parent_url`, one level deeper than the parent
+    pub fn push_discovered(&mut self, url: &str, anchor_text: &str, parent_url: &str, parent_depth: usize) -> bool {
+        self.push_with_parent(url, anchor_text, parent_depth + 1, Some(parent_url.to_string()))
+    }
+
+    fn push_with_parent(&mut self, url: &str, anchor_text: &str, depth: usize, parent_url: Option<String>) -> bool {
+        if depth > self.max_depth || self.seen.contains(url) || !self.in_scope(url) {
+            return false;
+        }
+        if !self.enqueue(url, anchor_text, depth, parent_url, current_timestamp()) {
+            return false;
+        }
+        self.seen.insert(url.to_string());
+        true
+    }
+
+    /// Marks a URL as seen without queueing it, e.g. a redirect target fetched under another URL
+    pub fn mark_seen(&mut self, url: &str) {
+        self.seen.insert(url.to_string());
+    }
+
+    /// Puts a popped entry back without the seen, scope and depth checks
+    ///
+    /// The anchor text is not kept, so the entry is re-scored from its URL alone.
+    pub fn requeue(&mut self, entry: FrontierEntry) -> bool {
+        let discovered_at = if entry.discovered_at == 0 { current_timestamp() } else { entry.discovered_at };
+        self.enqueue(&entry.url, "", entry.depth, entry.parent_url, discovered_at)
+    }
+
+    // * Queues a URL that passed the push checks, applying the capacity policy
+    fn enqueue(
+        &mut self,
+        url: &str,
+        anchor_text: &str,
+        depth: usize,
+        parent_url: Option<String>,
+        discovered_at: u64,
+    ) -> bool {
+        if !self.make_room(url, anchor_text)
+            || !self.queue.push_with_origin(url, anchor_text, depth, parent_url, discovered_at)
+        {
+            return false;
+        }
+        self.queued_bytes += entry_bytes(url, anchor_text);
+        self.spill_if_over_budget();
+        true
+    }
+
+    /// Removes and returns the highest priority URL
+    pub fn pop(&mut self) -> Option<FrontierEntry> {
+        if self.queue.is_empty() {
+            self.restore_spilled();
+        }
+        let link = self.queue.pop()?;
+        self.queued_bytes = self
+            .queued_bytes
+            .saturating_sub(entry_bytes(&link.url, &link.anchor_text));
+        Some(FrontierEntry {
+            url: link.url,
+            depth: link.depth,
+            score: link.score,
+            parent_url: link.parent_url,
+            discovered_at: link.discovered_at,
+        })
+    }
+
+    /// Returns true if the URL passes the URL filter and its host is in scope
+    pub fn in_scope(&self, url: &str) -> bool {
+        if self.url_filter.as_ref().is_some_and(|f| !f.allows(url)) {
+            return false;
+        }
+        let Some(allowed) = &self.allowed_hosts else {
+            return true;
+        };
+
+        Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| allowed.contains(h)))
+            .unwrap_or(false)
+    }
+
+    /// Rebuilds a frontier from a snapshot
+    ///
+    /// Pending URLs are re-scored with `scorer`; the depth limit applies only to
+    /// URLs discovered after the restore.
+    pub fn from_snapshot(
+        snapshot: FrontierSnapshot,
+        capacity: usize,
+        max_depth: usize,
+        scorer: LinkScorer,
+    ) -> Self {
+        let mut frontier = Self::with_scorer(capacity.max(snapshot.pending.len()), max_depth, scorer);
+        frontier.allowed_hosts = snapshot.allowed_hosts.map(|hosts| hosts.into_iter().collect());
+        frontier.seen = snapshot.seen.into_iter().collect();
+
+        for pending in snapshot.pending {
+            let PendingUrl { url, anchor_text, depth, parent_url, discovered_at } = pending;
+            if frontier.queue.push_with_origin(&url, &anchor_text, depth, parent_url, discovered_at) {
+                frontier.queued_bytes += entry_bytes(&url, &anchor_text);
+                frontier.seen.insert(url);
+            }
+        }
+
+        frontier
+    }
+
+    /// Captures the queued URLs (including spilled ones), seen set and scope
+    pub fn snapshot(&self) -> FrontierSnapshot {
+        let mut seen: Vec<String> = self.seen.iter().cloned().collect();
+        seen.sort();
+
+        let mut pending: Vec<PendingUrl> = self.queue.iter().map(PendingUrl::from).collect();
+        if let Some(spill) = &self.spill {
+            match spill.store.read_all() {
+                Ok(spilled) => pending.extend(spilled),
+                Err(e) => warn!(error = %e, "Spilled frontier entries left out of the snapshot"),
+            }
+        }
+
+        FrontierSnapshot {
+            pending,
+            seen,
+            allowed_hosts: self.allowed_hosts.as_ref().map(|hosts| {
+                let mut hosts: Vec<String> = hosts.iter().cloned().collect();
+                hosts.sort();
+                hosts
+            }),
+        }
+    }
+
+    /// Returns the link scorer ordering the frontier
+    pub fn scorer(&self) -> &LinkScorer {
+        self.queue.scorer()
+    }
+
+    /// Replaces the link scorer and re-ranks waiting URLs
+    ///
+    /// Spilled URLs are scored with the new scorer when they are read back.
+    pub fn set_scorer(&mut self, scorer: LinkScorer) {
+        self.queue.set_scorer(scorer);
+        self.queued_bytes = self.queue.iter().map(|link| entry_bytes(&link.url, &link.anchor_text)).sum();
+    }
+
+    /// Returns the number of URLs waiting to be fetched, in memory or spilled
+    pub fn len(&self) -> usize {
+        self.queue.len() + self.spilled_count()
+    }
+
+    /// Returns true if no URLs are waiting
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of waiting URLs currently spilled to disk
+    pub fn spilled_count(&self) -> usize {
+        self.spill.as_ref().map_or(0, |spill| spill.store.len())
+    }
+
+    /// Returns the estimated memory held by waiting URLs
+    pub fn queued_bytes(&self) -> usize {
+        self.queued_bytes
+    }
+
+    /// Returns true if a URL can be queued, applying the capacity policy when full
+    fn make_room(&mut self, url: &str, anchor_text: &str) -> bool {
+        match self.capacity_policy {
+            CapacityPolicy::SpillToDisk if self.spill.as_ref().is_some_and(|spill| !spill.disabled) => {
+                if self.queue.len() >= self.capacity {
+                    self.spill_cold(self.queue.len() / 2);
+                }
+                self.queue.len() < self.capacity
+            }
+            _ if self.len() < self.capacity => true,
+            CapacityPolicy::EvictLowest => {
+                let score = self.queue.scorer().score(url, anchor_text).score;
+                if !self.queue.peek_lowest().is_some_and(|lowest| lowest.score < score) {
+                    return false;
+                }
+                let Some(evicted) = self.queue.pop_lowest() else {
+                    return false;
+                };
+                self.queued_bytes = self
+                    .queued_bytes
+                    .saturating_sub(entry_bytes(&evicted.url, &evicted.anchor_text));
+                self.seen.remove(&evicted.url);
+                debug!(evicted = %evicted.url, url, "Frontier full; evicted the lowest-scored URL");
+                true
+            }
+            CapacityPolicy::RejectNew | CapacityPolicy::SpillToDisk => false,
+        }
+    }
+
+    /// Moves the coldest waiting URLs to disk, keeping half the budget in memory
+    fn spill_if_over_budget(&mut self) {
+        let Some(spill) = &self.spill else {
+            return;
+        };
+        if spill.disabled || self.queued_bytes <= spill.budget_bytes {
+            return;
+        }
+
+        let keep_bytes = spill.budget_bytes / 2;
+        let mut kept_bytes = 0;
+        let keep = self
+            .queue
+            .iter()
+            .take_while(|link| {
+                kept_bytes += entry_bytes(&link.url, &link.anchor_text);
+                kept_bytes <= keep_bytes
+            })
+            .count();
+        self.spill_cold(keep);
+    }
+
+    /// Moves all but the `keep` highest priority waiting URLs to disk
+    fn spill_cold(&mut self, keep: usize) {
+        let Some(spill) = &mut self.spill else {
+            return;
+        };
+        let cold = self.queue.split_off(keep);
+        if cold.is_empty() {
+            return;
+        }
+        let entries: Vec<PendingUrl> = cold.iter().map(PendingUrl::from).collect();
+
+        match spill.store.write(&entries) {
+            Ok(()) => {
+                let spilled_bytes: usize = cold.iter().map(|link| entry_bytes(&link.url, &link.anchor_text)).sum();
+                self.queued_bytes = self.queued_bytes.saturating_sub(spilled_bytes);
+                debug!(spilled = entries.len(), in_memory = self.queue.len(), "Spilled cold frontier entries");
+            }
+            Err(e) => {
+                warn!(error = %e, "Frontier spill failed; keeping the queue in memory");
+                spill.disabled = true;
+                self.enqueue_restored(entries);
+            }
+        }
+    }
+
+    /// Reads the oldest spilled segment back into the queue
+    fn restore_spilled(&mut self) {
+        let Some(spill) = &mut self.spill else {
+            return;
+        };
+        match spill.store.restore() {
+            Ok(Some(entries)) => self.enqueue_restored(entries),
+            Ok(None) => {}
+            Err(e) => warn!(error = %e, "Dropped unreadable spilled frontier entries"),
+        }
+    }
+
+    /// Queues entries that were already accepted once, skipping seen/scope/capacity checks
+    fn enqueue_restored(&mut self, entries: Vec<PendingUrl>) {
+        // * Restored entries were counted in len(), so they fit the queue's capacity
+        for entry in entries {
+            let bytes = entry_bytes(&entry.url, &entry.anchor_text);
+            if self.queue.push_with_origin(
+                &entry.url,
+                &entry.anchor_text,
+                entry.depth,
+                entry.parent_url,
+                entry.discovered_at,
+            ) {
+                self.queued_bytes += bytes;
+            }
+        }
+    }
+
+    /// Returns the number of distinct URLs ever accepted
+    pub fn seen_count(&self) -> usize {
+        self.seen.len()
+    }
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Estimated memory held by one waiting URL
+fn entry_bytes(url: &str, anchor_text: &str) -> usize {
+    // * The URL is held by the queue and the seen set
+    url.len() * 2 + anchor_text.len() + ENTRY_OVERHEAD_BYTES
+}
+
+/// Extracts normalized http(s) links and their anchor text from HTML
+pub fn extract_links(html: &str, base_url: &str) -> Vec<(String, String)> {
+    let document = Html::parse_document(html);
+    let mut seen = HashSet::new();
+
+    document
+        .select(&LINK_SELECTOR)
+        .filter_map(|a| {
+            let href = a.value().attr("href")?;
+            let url = normalize_url(href, base_url)?;
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                return None;
+            }
+            let anchor = a.text().collect::<Vec<_>>().join(" ");
+            let anchor = anchor.split_whitespace().collect::<Vec<_>>().join(" ");
+            Some((url, anchor))
+        })
+        .filter(|(url, _)| seen.insert(url.clone()))
+        .collect()
+}
+
+/// Returns the lowercased host of a URL
+pub fn host_of(url: &str) -> Option<String> {
+    Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_lowercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::ScorerConfig;
+
+    #[test]
+    fn test_frontier_pops_by_priority() {
+        let mut frontier = CrawlFrontier::new(10, 3);
+        frontier.push("https://example.com/ad/banner", "Advertisement", 1);
+        frontier.push("https://example.com/docs/guide", "Documentation Guide", 1);
+
+        let first = frontier.pop().unwrap();
+        assert_eq!(first.url, "https://example.com/docs/guide");
+        assert_eq!(first.depth, 1);
+        assert_eq!(frontier.len(), 1);
+    }
+
+    #[test]
+    fn test_frontier_rejects_seen_and_deep_urls() {
+        let mut frontier = CrawlFrontier::new(10, 1);
+        assert!(frontier.push("https://example.com/a", "", 0));
+        assert!(!frontier.push("https://example.com/a", "", 1));
+        assert!(!frontier.push("https://example.com/b", "", 2));
+
+        // * Popped URLs stay in the seen set
+        frontier.pop();
+        assert!(!frontier.push("https://example.com/a", "", 0));
+        assert_eq!(frontier.seen_count(), 1);
+    }
+
+    #[test]
+    fn test_frontier_scope() {
+        let mut frontier = CrawlFrontier::new(10, 3).with_allowed_hosts(["example.com"]);
+        assert!(frontier.push("https://example.com/page", "", 0));
+        assert!(!frontier.push("https://other.com/page", "", 0));
+        assert!(!frontier.in_scope("not a url"));
+
+        frontier.allow_host("other.com");
+        assert!(frontier.push("https://other.com/page", "", 0));
+    }
+
+    #[test]
+    fn test_requeue_bypasses_seen_set() {
+        let mut frontier = CrawlFrontier::new(10, 1);
+        frontier.push("https://example.com/a", "", 1);
+        let entry = frontier.pop().unwrap();
+
+        assert!(!frontier.push("https://example.com/a", "", 1));
+        assert!(frontier.requeue(entry));
+        assert_eq!(frontier.pop().unwrap().depth, 1);
+    }
+
+    #[test]
+    fn test_discovered_links_keep_their_parent() {
+        let mut frontier = CrawlFrontier::new(10, 2);
+        frontier.push("https://example.com/", "", 0);
+        let seed = frontier.pop().unwrap();
+        assert_eq!(seed.parent_url, None);
+        assert!(seed.discovered_at > 0);
+
+        assert!(frontier.push_discovered("https://example.com/docs", "Docs", &seed.url, seed.depth));
+        let snapshot = frontier.snapshot();
+        assert_eq!(snapshot.pending[0].parent_url.as_deref(), Some("https://example.com/"));
+
+        let child = frontier.pop().unwrap();
+        assert_eq!(child.depth, 1);
+        assert_eq!(child.parent_url.as_deref(), Some("https://example.com/"));
+
+        // * Re-queued entries keep their provenance
+        let discovered_at = child.discovered_at;
+        assert!(frontier.requeue(child));
+        let child = frontier.pop().unwrap();
+        assert_eq!(child.parent_url.as_deref(), Some("https://example.com/"));
+        assert_eq!(child.discovered_at, discovered_at);
+
+        assert!(!frontier.push_discovered("https://example.com/deep", "", "https://example.com/docs", 2));
+    }
+
+    #[test]
+    fn test_frontier_url_filter() {
+        use crate::engine::url_filter::UrlFilterConfig;
+
+        let filter = UrlFilter::new(&UrlFilterConfig {
+            deny: vec!["*/calendar/**".to_string()],
+            same_site: true,
+            ..Default::default()
+        })
+        .unwrap()
+        .with_sites(["https://www.example.co.uk/"]);
+        let mut frontier = CrawlFrontier::new(10, 3).with_url_filter(Arc::new(filter));
+
+        assert!(frontier.push("https://blog.example.co.uk/post", "", 1));
+        assert!(!frontier.push("https://www.example.co.uk/calendar/2024", "", 1));
+        assert!(!frontier.push("https://other.co.uk/", "", 1));
+        assert_eq!(frontier.seen_count(), 1);
+    }
+
+    #[test]
+    fn test_set_scorer_reranks_waiting_urls() {
+        let favouring = |path: &str| {
+            LinkScorer::with_config(ScorerConfig {
+                high_value_paths: vec![path.to_string()],
+                ..Default::default()
+            })
+        };
+        let mut frontier = CrawlFrontier::with_scorer(10, 3, favouring("/news/"));
+        frontier.push("https://example.com/news/item", "", 0);
+        frontier.push("https://example.com/shop/item", "", 0);
+
+        frontier.set_scorer(favouring("/shop/"));
+
+        assert_eq!(frontier.pop().unwrap().url, "https://example.com/shop/item");
+        assert_eq!(frontier.len(), 1);
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let mut frontier = CrawlFrontier::new(10, 3).with_allowed_hosts(["example.com"]);
+        frontier.push("https://example.com/about", "About", 2);
+        frontier.push("https://example.com/docs/guide", "Guide", 1);
+        assert_eq!(frontier.pop().unwrap().url, "https://example.com/docs/guide");
+
+        let snapshot = frontier.snapshot();
+        assert_eq!(snapshot.pending.len(), 1);
+        assert_eq!(snapshot.seen.len(), 2);
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let mut restored =
+            CrawlFrontier::from_snapshot(serde_json::from_str(&json).unwrap(), 10, 3, LinkScorer::new());
+
+        assert_eq!(restored.len(), 1);
+        assert!(!restored.push("https://example.com/docs/guide", "", 0));
+        assert!(!restored.push("https://other.com/", "", 0));
+        let entry = restored.pop().unwrap();
+        assert_eq!(entry.url, "https://example.com/about");
+        assert_eq!(entry.depth, 2);
+    }
+
+    fn spilling_frontier(capacity: usize) -> CrawlFrontier {
+        let store = SpillStore::create(std::env::temp_dir()).unwrap();
+        // * Room for about four entries
+        CrawlFrontier::new(capacity, 3).with_spill(store, 600)
+    }
+
+    #[test]
+    fn test_spilled_entries_restore_on_demand() {
+        let mut frontier = spilling_frontier(100);
+        for i in 0..20 {
+            assert!(frontier.push(&format!("https://example.com/page-{}", i), "", i % 3));
+        }
+        assert!(frontier.push("https://example.com/docs/guide", "Read the guide", 1));
+
+        assert!(frontier.spilled_count() > 0);
+        assert!(frontier.queued_bytes() <= 600);
+        assert_eq!(frontier.len(), 21);
+        assert_eq!(frontier.snapshot().pending.len(), 21);
+
+        // * The hottest URL never left memory
+        assert_eq!(frontier.pop().unwrap().url, "https://example.com/docs/guide");
+
+        let mut popped = HashSet::new();
+        while let Some(entry) = frontier.pop() {
+            let i: usize = entry.url.rsplit('-').next().unwrap().parse().unwrap();
+            assert_eq!(entry.depth, i % 3);
+            popped.insert(entry.url);
+        }
+        assert_eq!(popped.len(), 20);
+        assert!(frontier.is_empty());
+        assert_eq!(frontier.spilled_count(), 0);
+        assert!(!frontier.push("https://example.com/page-3", "", 0));
+    }
+
+    #[test]
+    fn test_capacity_counts_spilled_entries() {
+        let mut frontier = spilling_frontier(8);
+        for i in 0..8 {
+            assert!(frontier.push(&format!("https://example.com/page-{}", i), "", 1));
+        }
+        assert!(frontier.spilled_count() > 0);
+        assert!(!frontier.push("https://example.com/page-8", "", 1));
+        assert!(!frontier.requeue(FrontierEntry::new("https://example.com/page-9", 1)));
+    }
+
+    #[test]
+    fn test_evict_lowest_policy() {
+        let mut frontier = CrawlFrontier::new(2, 3).with_capacity_policy(CapacityPolicy::EvictLowest);
+        assert!(frontier.push("https://example.com/ad/banner", "Sponsored Ad", 1));
+        assert!(frontier.push("https://example.com/about", "About", 1));
+
+        // * A better late discovery displaces the junk; a worse one is still dropped
+        assert!(frontier.push("https://example.com/docs/guide", "Documentation Guide", 1));
+        assert!(!frontier.push("https://example.com/login?next=1", "", 1));
+        assert_eq!(frontier.len(), 2);
+
+        let urls: Vec<String> = std::iter::from_fn(|| frontier.pop()).map(|entry| entry.url).collect();
+        assert_eq!(urls, ["https://example.com/docs/guide", "https://example.com/about"]);
+        // * The evicted URL can be discovered again
+        assert!(frontier.push("https://example.com/ad/banner", "Sponsored Ad", 1));
+    }
+
+    #[test]
+    fn test_spill_to_disk_policy() {
+        let store = SpillStore::create(std::env::temp_dir()).unwrap();
+        let mut frontier = CrawlFrontier::new(4, 3)
+            .with_spill(store, usize::MAX)
+            .with_capacity_policy(CapacityPolicy::SpillToDisk);
+        for i in 0..10 {
+            assert!(frontier.push(&format!("https://example.com/page-{}", i), "", 1));
+        }
+        assert_eq!(frontier.len(), 10);
+        assert!(frontier.spilled_count() > 0);
+        assert_eq!(std::iter::from_fn(|| frontier.pop()).count(), 10);
+
+        // * Without a spill store the policy falls back to rejecting
+        let mut in_memory = CrawlFrontier::new(1, 3).with_capacity_policy(CapacityPolicy::SpillToDisk);
+        assert!(in_memory.push("https://example.com/a", "", 1));
+        assert!(!in_memory.push("https://example.com/b", "", 1));
+    }
+
+    #[test]
+    fn test_extract_links_normalizes_and_dedupes() {
+        let html = r##"
+            <a href="/docs?utm_source=x">  API
+                Docs </a>
+            <a href="/docs">Docs again</a>
+            <a href="mailto:team@example.com">Mail</a>
+            <a href="#top">Top</a>
+        "##;
+        let links = extract_links(html, "https://Example.com/index.html");
+
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0], ("https://example.com/docs".to_string(), "API Docs".to_string()));
+        assert_eq!(links[1].0, "https://example.com/index.html");
+    }
+
+    #[test]
+    fn test_host_of() {
+        assert_eq!(host_of("https://Example.COM/x"), Some("example.com".to_string()));
+        assert_eq!(host_of("nonsense"), None);
+    }
+}
@@ -0,0 +1,200 @@
+// * [FR-01] hreflang Language Clusters
+// * Groups the translations a page declares with `<link rel="alternate" hreflang>` into
+// * clusters, so the crawler can stay on the preferred languages of the crawl policy and
+// * stored records carry an ID that ties the variants of one article together.
+
+use crate::engine::normalization::normalize_url;
+use crate::refinery::LanguageAlternate;
+use std::collections::HashMap;
+use xxhash_rust::xxh64::xxh64;
+
+/// Language variants seen during a crawl, keyed by normalized URL
+///
+/// A cluster's ID is the hash of the URLs first seen together; pages that later
+/// declare any of those URLs join the existing cluster instead of starting a new one.
+#[derive(Debug, Default)]
+pub struct HreflangIndex {
+    // * Lowercased BCP 47 tags; empty keeps every language
+    preferred: Vec<String>,
+    // * URL -> cluster ID and the languages the URL is declared for
+    variants: HashMap<String, Variant>,
+    // * Cluster ID -> member URLs
+    clusters: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug)]
+struct Variant {
+    cluster: String,
+    languages: Vec<String>,
+}
+
+impl HreflangIndex {
+    pub fn new(preferred_languages: &[String]) -> Self {
+        Self {
+            preferred: preferred_languages.iter().map(|tag| tag.trim().to_lowercase()).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Whether a tag is one of the preferred languages or a regional form of one ("en" covers "en-gb")
+    pub fn is_preferred(&self, language: &str) -> bool {
+        self.preferred.iter().any(|preferred| {
+            language == preferred
+                || language
+                    .strip_prefix(preferred.as_str())
+                    .is_some_and(|region| region.starts_with('-'))
+        })
+    }
+
+    /// Records the alternates `page_url` declares and returns the cluster ID they share
+    ///
+    /// Returns None when the page declares no alternates.
+    pub fn observe(&mut self, page_url: &str, alternates: &[LanguageAlternate]) -> Option<String> {
+        let mut members: Vec<(String, Option<&str>)> = alternates
+            .iter()
+            .filter_map(|alternate| {
+                let url = normalize_url(&alternate.url, page_url)?;
+                Some((url, Some(alternate.hreflang.as_str())))
+            })
+            .collect();
+        if members.is_empty() {
+            return None;
+        }
+        // * The page belongs to its cluster even when it omits the self-referencing entry
+        let page = normalize_url(page_url, page_url).unwrap_or_else(|| page_url.to_string());
+        if !members.iter().any(|(url, _)| *url == page) {
+            members.push((page, None));
+        }
+
+        let cluster = members
+            .iter()
+            .find_map(|(url, _)| self.variants.get(url).map(|variant| variant.cluster.clone()))
+            .unwrap_or_else(|| {
+                let mut urls: Vec<&str> = members.iter().map(|(url, _)| url.as_str()).collect();
+                urls.sort_unstable();
+                urls.dedup();
+                format!("{:016x}", xxh64(urls.join("\n").as_bytes(), 0))
+            });
+
+        for (url, language) in members {
+            let variant = self.variants.entry(url.clone()).or_insert_with(|| {
+                self.clusters.entry(cluster.clone()).or_default().push(url);
+                Variant {
+                    cluster: cluster.clone(),
+                    languages: Vec::new(),
+                }
+            });
+            if let Some(language) = language {
+                if !variant.languages.iter().any(|known| known == language) {
+                    variant.languages.push(language.to_string());
+                }
+            }
+        }
+        Some(cluster)
+    }
+
+    /// Returns the cluster a URL was declared in
+    pub fn cluster_of(&self, url: &str) -> Option<&str> {
+        self.variants.get(url).map(|variant| variant.cluster.as_str())
+    }
+
+    /// Whether a URL is a translation in a non-preferred language of a page that has a
+    /// variant in a preferred language
+    ///
+    /// URLs in no cluster, or whose cluster has no preferred variant, are always wanted.
+    pub fn is_unwanted(&self, url: &str) -> bool {
+        if self.preferred.is_empty() {
+            return false;
+        }
+        let Some(variant) = self.variants.get(url) else {
+            return false;
+        };
+        !variant.languages.is_empty()
+            && !variant.languages.iter().any(|language| self.is_preferred(language))
+            && !self.preferred_variants(url).is_empty()
+    }
+
+    /// Returns the members of a URL's cluster declared in a preferred language
+    pub fn preferred_variants(&self, url: &str) -> Vec<String> {
+        let Some(members) = self.cluster_of(url).and_then(|cluster| self.clusters.get(cluster)) else {
+            return Vec::new();
+        };
+        members
+            .iter()
+            .filter(|member| {
+                self.variants
+                    .get(member.as_str())
+                    .is_some_and(|variant| variant.languages.iter().any(|language| self.is_preferred(language)))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Number of URLs in any cluster
+    pub fn len(&self) -> usize {
+        self.variants.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.variants.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alternate(hreflang: &str, url: &str) -> LanguageAlternate {
+        LanguageAlternate {
+            hreflang: hreflang.to_string(),
+            url: url.to_string(),
+        }
+    }
+
+    fn story_alternates() -> Vec<LanguageAlternate> {
+        vec![
+            alternate("en-gb", "https://example.com/en/story"),
+            alternate("de", "/de/story"),
+            alternate("fr", "https://example.com/fr/story"),
+            alternate("x-default", "https://example.com/en/story"),
+        ]
+    }
+
+    #[test]
+    fn test_variants_share_one_cluster() {
+        let mut index = HreflangIndex::new(&[]);
+        let cluster = index.observe("https://example.com/de/story", &story_alternates()).unwrap();
+        assert_eq!(index.cluster_of("https://example.com/fr/story"), Some(cluster.as_str()));
+
+        // * A later page that only knows part of the cluster joins it
+        let partial = [alternate("it", "https://example.com/it/story"), alternate("fr", "https://example.com/fr/story")];
+        assert_eq!(index.observe("https://example.com/it/story", &partial), Some(cluster.clone()));
+        assert_eq!(index.cluster_of("https://example.com/it/story"), Some(cluster.as_str()));
+        assert_eq!(index.len(), 4);
+
+        assert_eq!(index.observe("https://example.com/about", &[]), None);
+        // * Without preferred languages nothing is skipped
+        assert!(!index.is_unwanted("https://example.com/fr/story"));
+    }
+
+    #[test]
+    fn test_only_preferred_languages_are_wanted() {
+        let mut index = HreflangIndex::new(&["EN".to_string()]);
+        index.observe("https://example.com/de/story", &story_alternates());
+
+        assert!(index.is_preferred("en-gb"));
+        assert!(!index.is_preferred("eng"));
+        assert!(!index.is_unwanted("https://example.com/en/story"));
+        assert!(index.is_unwanted("https://example.com/de/story"));
+        assert!(index.is_unwanted("https://example.com/fr/story"));
+        assert!(!index.is_unwanted("https://example.com/unrelated"));
+        assert_eq!(
+            index.preferred_variants("https://example.com/de/story"),
+            vec!["https://example.com/en/story".to_string()]
+        );
+
+        // * A cluster with no preferred variant is crawled in whatever languages it has
+        index.observe("https://example.com/de/only", &[alternate("de", "https://example.com/de/only")]);
+        assert!(!index.is_unwanted("https://example.com/de/only"));
+    }
+}
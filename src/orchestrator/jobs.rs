@@ -0,0 +1,439 @@
+// * Crawl Jobs
+// * Runs several independent crawls in one process. Each job brings its own seeds, config
+// * (policy, budgets, scope) and output sink, gets its own frontier, dedup state and stats,
+// * and is paused, resumed or cancelled without touching the other jobs.
+
+use super::config::CrawlConfig;
+use super::control::{CrawlControl, CrawlProgress};
+use super::crawler::{CrawlReport, CrawlStore, Orchestrator};
+use crate::persistence::{InMemoryRecordStore, RecordSink};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum JobError {
+    #[error("Crawl job {0} not found")]
+    NotFound(String),
+
+    #[error("Crawl job {0} already exists")]
+    Duplicate(String),
+
+    #[error("Crawl job {0} has already finished")]
+    Finished(String),
+
+    #[error("Crawl job {0} is still running")]
+    Running(String),
+
+    #[error("Crawl job {0} failed: {1}")]
+    Failed(String, String),
+}
+
+/// An independent crawl submitted to a `JobManager`
+pub struct CrawlJob {
+    pub id: String,
+    pub seeds: Vec<String>,
+    pub config: CrawlConfig,
+    /// Receives the job's records in addition to the orchestrator's store and sinks
+    pub sink: Option<Arc<dyn RecordSink>>,
+}
+
+impl CrawlJob {
+    pub fn new(id: impl Into<String>, seeds: Vec<String>, config: CrawlConfig) -> Self {
+        Self {
+            id: id.into(),
+            seeds,
+            config,
+            sink: None,
+        }
+    }
+
+    /// Forwards the job's persisted records to `sink` (e.g. a per-tenant `WebhookSink`)
+    pub fn with_sink(mut self, sink: Arc<dyn RecordSink>) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+}
+
+impl std::fmt::Debug for CrawlJob {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CrawlJob")
+            .field("id", &self.id)
+            .field("seeds", &self.seeds)
+            .field("sink", &self.sink.is_some())
+            .finish()
+    }
+}
+
+/// Lifecycle of a crawl job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Running,
+    Paused,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+impl JobState {
+    /// Returns true once the job's crawl has ended
+    pub fn is_finished(&self) -> bool {
+        matches!(self, JobState::Completed | JobState::Cancelled | JobState::Failed)
+    }
+}
+
+/// Snapshot of one job
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JobStatus {
+    pub id: String,
+    pub state: JobState,
+    /// Live progress, final once the job has finished
+    pub progress: CrawlProgress,
+    /// Why a failed job failed
+    pub error: Option<String>,
+    /// Unix timestamps in seconds
+    pub started_at: u64,
+    pub finished_at: Option<u64>,
+}
+
+#[derive(Clone)]
+struct FinishedJob {
+    result: Result<CrawlReport, String>,
+    finished_at: u64,
+    // * Orders jobs that finished within the same second
+    finished: Instant,
+}
+
+type JobOutcome = Option<FinishedJob>;
+
+struct JobEntry {
+    control: Arc<CrawlControl>,
+    started_at: u64,
+    outcome: watch::Receiver<JobOutcome>,
+}
+
+impl JobEntry {
+    fn status(&self, id: &str) -> JobStatus {
+        let outcome = self.outcome.borrow();
+        let (state, error) = match outcome.as_ref().map(|finished| &finished.result) {
+            None if self.control.is_crawl_paused() => (JobState::Paused, None),
+            None => (JobState::Running, None),
+            Some(Ok(_)) if self.control.is_cancelled() => (JobState::Cancelled, None),
+            Some(Ok(_)) => (JobState::Completed, None),
+            Some(Err(e)) => (JobState::Failed, Some(e.clone())),
+        };
+        JobStatus {
+            id: id.to_string(),
+            state,
+            progress: self.control.progress(),
+            error,
+            started_at: self.started_at,
+            finished_at: outcome.as_ref().map(|finished| finished.finished_at),
+        }
+    }
+
+    fn finished(&self) -> Option<Instant> {
+        self.outcome.borrow().as_ref().map(|finished| finished.finished)
+    }
+}
+
+/// Runs crawl jobs concurrently on a shared orchestrator
+///
+/// The orchestrator's store, fetcher, shutdown signal and result sinks are shared; its
+/// control handle and checkpoint store are not used by jobs. A process shutdown drains
+/// every job.
+///
+/// # Example
+/// ```ignore
+/// let jobs = JobManager::new(Arc::new(Orchestrator::new()));
+/// jobs.submit(CrawlJob::new("news", vec!["https://news.example.com/".into()], news_config))?;
+/// jobs.submit(CrawlJob::new("docs", vec!["https://docs.example.org/".into()], docs_config))?;
+/// jobs.pause("news")?;
+/// let report = jobs.wait("docs").await?;
+/// ```
+pub struct JobManager<S = Arc<InMemoryRecordStore>> {
+    orchestrator: Arc<Orchestrator<S>>,
+    jobs: Mutex<HashMap<String, JobEntry>>,
+    retained_finished: Option<usize>,
+}
+
+impl<S: CrawlStore> JobManager<S> {
+    pub fn new(orchestrator: Arc<Orchestrator<S>>) -> Self {
+        Self {
+            orchestrator,
+            jobs: Mutex::new(HashMap::new()),
+            retained_finished: None,
+        }
+    }
+
+    /// Keeps at most `limit` finished jobs; the oldest are forgotten when a job is submitted
+    pub fn with_retained_finished(mut self, limit: usize) -> Self {
+        self.retained_finished = Some(limit);
+        self
+    }
+
+    /// Starts a job in the background
+    ///
+    /// Ids must be unique among the jobs held, finished ones included, until removed.
+    pub fn submit(&self, job: CrawlJob) -> Result<(), JobError> {
+        let mut jobs = self.jobs.lock().unwrap();
+        if jobs.contains_key(&job.id) {
+            return Err(JobError::Duplicate(job.id));
+        }
+        if let Some(limit) = self.retained_finished {
+            prune_finished(&mut jobs, limit);
+        }
+
+        let control = Arc::new(CrawlControl::new());
+        let (tx, rx) = watch::channel(None);
+        let orchestrator = Arc::clone(&self.orchestrator);
+        let run_control = Arc::clone(&control);
+        let CrawlJob { id, seeds, config, sink } = job;
+        info!(job = %id, seeds = seeds.len(), "Crawl job submitted");
+        let job_id = id.clone();
+        tokio::spawn(async move {
+            let result = orchestrator
+                .run_job(seeds, config, run_control, sink)
+                .await
+                .map_err(|e| e.to_string());
+            match &result {
+                Ok(report) => info!(job = %job_id, persisted = report.pages_persisted, "Crawl job finished"),
+                Err(e) => warn!(job = %job_id, error = %e, "Crawl job failed"),
+            }
+            let _ = tx.send(Some(FinishedJob {
+                result,
+                finished_at: current_timestamp(),
+                finished: Instant::now(),
+            }));
+        });
+
+        jobs.insert(
+            id,
+            JobEntry {
+                control,
+                started_at: current_timestamp(),
+                outcome: rx,
+            },
+        );
+        Ok(())
+    }
+
+    /// Returns a job's control handle, for seeds and per-domain pauses
+    pub fn control(&self, id: &str) -> Result<Arc<CrawlControl>, JobError> {
+        self.with_job(id, |entry| Arc::clone(&entry.control))
+    }
+
+    /// Stops dispatching the job's pages; returns false if it was already paused
+    pub fn pause(&self, id: &str) -> Result<bool, JobError> {
+        self.with_running_job(id, |control| control.pause_crawl())
+    }
+
+    /// Resumes a paused job; returns false if it was not paused
+    pub fn resume(&self, id: &str) -> Result<bool, JobError> {
+        self.with_running_job(id, |control| control.resume_crawl())
+    }
+
+    /// Cancels a job; its in-flight pages drain and its report is marked interrupted
+    pub fn cancel(&self, id: &str) -> Result<(), JobError> {
+        self.with_running_job(id, |control| control.cancel())
+    }
+
+    /// Returns a job's status
+    pub fn status(&self, id: &str) -> Result<JobStatus, JobError> {
+        self.with_job(id, |entry| entry.status(id))
+    }
+
+    /// Returns every job's status, ordered by id
+    pub fn list(&self) -> Vec<JobStatus> {
+        let jobs = self.jobs.lock().unwrap();
+        let mut statuses: Vec<JobStatus> = jobs.iter().map(|(id, entry)| entry.status(id)).collect();
+        statuses.sort_by(|a, b| a.id.cmp(&b.id));
+        statuses
+    }
+
+    /// Waits for a job to finish and returns its report
+    pub async fn wait(&self, id: &str) -> Result<CrawlReport, JobError> {
+        let mut outcome = self.with_job(id, |entry| entry.outcome.clone())?;
+        let result = match outcome.wait_for(Option::is_some).await {
+            Ok(finished) => finished.as_ref().map(|finished| finished.result.clone()),
+            // * The task ended without reporting (it panicked)
+            Err(_) => Some(Err("crawl task ended unexpectedly".to_string())),
+        };
+        match result {
+            Some(Ok(report)) => Ok(report),
+            Some(Err(e)) => Err(JobError::Failed(id.to_string(), e)),
+            None => unreachable!("wait_for only returns finished outcomes"),
+        }
+    }
+
+    /// Forgets a finished job and returns its final status
+    pub fn remove(&self, id: &str) -> Result<JobStatus, JobError> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let entry = jobs.get(id).ok_or_else(|| JobError::NotFound(id.to_string()))?;
+        let status = entry.status(id);
+        if !status.state.is_finished() {
+            return Err(JobError::Running(id.to_string()));
+        }
+        jobs.remove(id);
+        Ok(status)
+    }
+
+    fn with_job<T>(&self, id: &str, f: impl FnOnce(&JobEntry) -> T) -> Result<T, JobError> {
+        let jobs = self.jobs.lock().unwrap();
+        jobs.get(id).map(f).ok_or_else(|| JobError::NotFound(id.to_string()))
+    }
+
+    fn with_running_job<T>(&self, id: &str, f: impl FnOnce(&CrawlControl) -> T) -> Result<T, JobError> {
+        self.with_job(id, |entry| {
+            if entry.outcome.borrow().is_some() {
+                Err(JobError::Finished(id.to_string()))
+            } else {
+                Ok(f(&entry.control))
+            }
+        })?
+    }
+}
+
+/// Forgets the oldest finished jobs until at most `limit` remain
+fn prune_finished(jobs: &mut HashMap<String, JobEntry>, limit: usize) {
+    let mut finished: Vec<(Instant, String)> = jobs
+        .iter()
+        .filter_map(|(id, entry)| entry.finished().map(|at| (at, id.clone())))
+        .collect();
+    if finished.len() <= limit {
+        return;
+    }
+    finished.sort();
+    for (_, id) in finished.drain(..finished.len() - limit) {
+        jobs.remove(&id);
+    }
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::rate_limiter::CrawlDelayConfig;
+    use crate::orchestrator::test_support::{chain_article, MockFetcher};
+    use std::time::Duration;
+
+    fn config(max_pages: usize) -> CrawlConfig {
+        CrawlConfig {
+            max_pages,
+            max_depth: 50,
+            enable_slow_path: false,
+            enable_enrichment: false,
+            crawl_delay: CrawlDelayConfig {
+                standard_delay_ms: 0,
+                slow_path_delay_ms: 0,
+            },
+            ..Default::default()
+        }
+    }
+
+    fn manager() -> JobManager {
+        let fetcher = MockFetcher::default().generated(chain_article);
+        JobManager::new(Arc::new(Orchestrator::new().with_fetcher(Arc::new(fetcher))))
+    }
+
+    #[tokio::test]
+    async fn test_jobs_run_concurrently_with_isolated_stats_and_sinks() {
+        let jobs = manager();
+        let a_sink = Arc::new(InMemoryRecordStore::new());
+        let b_sink = Arc::new(InMemoryRecordStore::new());
+        jobs.submit(CrawlJob::new("a", vec!["https://a.example/".into()], config(2)).with_sink(a_sink.clone()))
+            .unwrap();
+        jobs.submit(CrawlJob::new("b", vec!["https://b.example/".into()], config(3)).with_sink(b_sink.clone()))
+            .unwrap();
+
+        let a = jobs.wait("a").await.unwrap();
+        let b = jobs.wait("b").await.unwrap();
+        assert_eq!((a.pages_persisted, b.pages_persisted), (2, 3));
+        assert_eq!(a.domains.keys().collect::<Vec<_>>(), vec!["a.example"]);
+        assert_eq!(b.domains.keys().collect::<Vec<_>>(), vec!["b.example"]);
+        assert_eq!((a_sink.count(), b_sink.count()), (2, 3));
+
+        let states: Vec<_> = jobs.list().into_iter().map(|s| (s.id, s.state)).collect();
+        assert_eq!(
+            states,
+            vec![("a".to_string(), JobState::Completed), ("b".to_string(), JobState::Completed)]
+        );
+        assert_eq!(jobs.status("b").unwrap().progress.report.pages_persisted, 3);
+    }
+
+    #[tokio::test]
+    async fn test_pause_resume_and_cancel() {
+        let jobs = manager();
+        jobs.submit(CrawlJob::new("slow", vec!["https://slow.example/".into()], config(10_000)))
+            .unwrap();
+        assert_eq!(jobs.pause("slow"), Ok(true));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let status = jobs.status("slow").unwrap();
+        assert_eq!(status.state, JobState::Paused);
+        assert_eq!(status.progress.dispatched, 0);
+
+        assert_eq!(jobs.resume("slow"), Ok(true));
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while jobs.status("slow").unwrap().progress.dispatched == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(jobs.remove("slow").unwrap_err(), JobError::Running("slow".into()));
+
+        jobs.cancel("slow").unwrap();
+        let report = jobs.wait("slow").await.unwrap();
+        assert!(report.interrupted);
+        assert!(report.pages_persisted < 10_000);
+        assert_eq!(jobs.status("slow").unwrap().state, JobState::Cancelled);
+        assert_eq!(jobs.pause("slow"), Err(JobError::Finished("slow".into())));
+        assert_eq!(jobs.remove("slow").unwrap().state, JobState::Cancelled);
+        assert_eq!(jobs.status("slow"), Err(JobError::NotFound("slow".into())));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_ids_and_failed_jobs() {
+        let jobs = manager();
+        jobs.submit(CrawlJob::new("bad", vec!["not a url".into()], config(1))).unwrap();
+        assert_eq!(
+            jobs.submit(CrawlJob::new("bad", vec![], config(1))),
+            Err(JobError::Duplicate("bad".into()))
+        );
+        assert!(matches!(jobs.wait("bad").await, Err(JobError::Failed(id, _)) if id == "bad"));
+        let status = jobs.status("bad").unwrap();
+        assert_eq!(status.state, JobState::Failed);
+        assert!(status.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_oldest_finished_jobs_are_pruned() {
+        let jobs = manager().with_retained_finished(1);
+        for id in ["first", "second"] {
+            jobs.submit(CrawlJob::new(id, vec![format!("https://{}.example/", id)], config(1)))
+                .unwrap();
+            jobs.wait(id).await.unwrap();
+        }
+        let status = jobs.status("second").unwrap();
+        assert!(status.finished_at.is_some_and(|at| at >= status.started_at));
+
+        // * Only finished jobs count against the limit; the running one is kept
+        jobs.submit(CrawlJob::new("third", vec!["https://third.example/".into()], config(10_000)))
+            .unwrap();
+        let ids: Vec<_> = jobs.list().into_iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec!["second", "third"]);
+        jobs.cancel("third").unwrap();
+        jobs.wait("third").await.unwrap();
+    }
+}
@@ -0,0 +1,64 @@
+// * Milestone 7: Crawl Orchestration
+// * Goal: Turn the engine, network, refinery, and persistence layers into a working crawler
+// * This module provides the frontier, fetch abstraction, and the top-level Orchestrator
+
+pub mod checkpoint;
+pub mod config;
+pub mod control;
+pub mod crawler;
+pub mod extract;
+pub mod fetcher;
+pub mod frontier;
+pub mod hreflang;
+pub mod jobs;
+pub mod policy;
+pub mod report;
+pub mod seeds;
+pub mod spill;
+#[cfg(test)]
+pub(crate) mod test_support;
+
+// * Re-exports for convenient access
+pub use checkpoint::{
+    CheckpointError, CheckpointStore, CrawlCheckpoint, DirectoryCheckpointStore,
+    RedisCheckpointStore, CHECKPOINT_VERSION,
+};
+pub use config::CrawlConfig;
+pub use control::{CrawlControl, CrawlProgress};
+pub use crawler::{CrawlReport, CrawlStore, DomainCrawlStats, Orchestrator, OrchestratorError};
+pub use extract::{extract_url, ExtractError, ExtractOptions, Extractor, RenderMode};
+pub use fetcher::{robots_url, ArchivingFetcher, FetchFuture, HttpFetcher, PageFetcher, RenderedPage};
+pub use frontier::{extract_links, CapacityPolicy, CrawlFrontier, FrontierEntry, FrontierSnapshot, PendingUrl};
+pub use hreflang::HreflangIndex;
+pub use jobs::{CrawlJob, JobError, JobManager, JobState, JobStatus};
+pub use policy::{CrawlPolicy, DomainBudget, PolicyEnforcer, PolicyError, PolicyViolation};
+pub use report::{CrawlSummary, DomainSummary, ErrorCount, ReportConfig};
+pub use seeds::{
+    seed_source, FileSeedSource, HttpSeedSource, RejectedSeed, Seed, SeedBatch, SeedError, SeedFormat, SeedLoader,
+    SeedSource, StdinSeedSource,
+};
+pub use spill::{SpillConfig, SpillStore};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_exports() {
+        // * Verify all major types are accessible
+        let _config = CrawlConfig::default();
+        let _orchestrator = Orchestrator::new();
+        let _frontier = CrawlFrontier::new(10, 1);
+        let _report = CrawlReport::default();
+    }
+
+    #[test]
+    fn test_report_serializes() {
+        let report = CrawlReport {
+            pages_persisted: 3,
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains("\"pages_persisted\":3"));
+    }
+}
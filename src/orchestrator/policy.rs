@@ -0,0 +1,269 @@
+// * [FR-01] Crawl Policy
+// * Per-domain page/byte budgets, depth limits and URL allow/deny patterns that keep
+// * the crawler out of calendar pages and faceted-search URL spaces
+
+use super::crawler::DomainCrawlStats;
+use regex::RegexSet;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PolicyError {
+    #[error("Invalid {list} pattern: {source}")]
+    InvalidPattern {
+        list: &'static str,
+        #[source]
+        source: regex::Error,
+    },
+}
+
+/// Why a URL was kept out of the crawl
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyViolation {
+    /// Matched a denied URL pattern
+    Denied,
+    /// Matched none of the allowed URL patterns
+    NotAllowed,
+    /// Deeper than the domain's depth limit
+    TooDeep,
+    /// The domain's page budget is spent
+    PageBudget,
+    /// The domain's byte budget is spent
+    ByteBudget,
+    /// A translation outside the preferred languages of a page with a preferred variant
+    LanguageVariant,
+}
+
+impl PolicyViolation {
+    /// Returns the Prometheus label for this violation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PolicyViolation::Denied => "denied_pattern",
+            PolicyViolation::NotAllowed => "not_allowed",
+            PolicyViolation::TooDeep => "depth_limit",
+            PolicyViolation::PageBudget => "page_budget",
+            PolicyViolation::ByteBudget => "byte_budget",
+            PolicyViolation::LanguageVariant => "language_variant",
+        }
+    }
+}
+
+/// Budget overrides for a single host
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DomainBudget {
+    pub max_pages: Option<usize>,
+    pub max_depth: Option<usize>,
+    pub max_bytes: Option<u64>,
+}
+
+/// Limits applied to every domain of a crawl
+///
+/// Budgets are counted per host. Patterns are regular expressions matched
+/// against the full normalized URL; a denied match always wins, and a
+/// non-empty allow list rejects URLs matching none of its patterns.
+///
+/// # Example
+/// ```toml
+/// [crawl.policy]
+/// max_pages_per_domain = 500
+/// denied_url_patterns = ["/calendar/", "[?&](sort|filter|page)="]
+///
+/// preferred_languages = ["en"]
+///
+/// [crawl.policy.domains."docs.example.com"]
+/// max_pages = 5000
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CrawlPolicy {
+    /// Maximum pages dispatched per domain
+    pub max_pages_per_domain: Option<usize>,
+    /// Maximum link depth per domain (never deeper than `crawl.max_depth`)
+    pub max_depth: Option<usize>,
+    /// Maximum bytes downloaded per domain
+    pub max_bytes: Option<u64>,
+    /// URLs must match at least one of these (when non-empty)
+    pub allowed_url_patterns: Vec<String>,
+    /// URLs matching any of these are never crawled
+    pub denied_url_patterns: Vec<String>,
+    /// Per-host overrides of the budgets above
+    pub domains: BTreeMap<String, DomainBudget>,
+    /// hreflang languages to crawl (e.g. "en" also covers "en-gb"); translations in other
+    /// languages are skipped when a preferred variant exists. Empty crawls every language.
+    pub preferred_languages: Vec<String>,
+}
+
+impl CrawlPolicy {
+    /// Returns the effective budget for a host
+    pub fn budget_for(&self, domain: &str) -> DomainBudget {
+        let overrides = self.domains.get(domain).cloned().unwrap_or_default();
+        DomainBudget {
+            max_pages: overrides.max_pages.or(self.max_pages_per_domain),
+            max_depth: overrides.max_depth.or(self.max_depth),
+            max_bytes: overrides.max_bytes.or(self.max_bytes),
+        }
+    }
+}
+
+/// A `CrawlPolicy` with its patterns compiled
+#[derive(Debug, Clone)]
+pub struct PolicyEnforcer {
+    policy: CrawlPolicy,
+    allowed: RegexSet,
+    denied: RegexSet,
+}
+
+impl PolicyEnforcer {
+    /// Compiles the policy's URL patterns
+    pub fn new(policy: CrawlPolicy) -> Result<Self, PolicyError> {
+        let allowed = RegexSet::new(&policy.allowed_url_patterns).map_err(|source| {
+            PolicyError::InvalidPattern {
+                list: "allowed_url_patterns",
+                source,
+            }
+        })?;
+        let denied = RegexSet::new(&policy.denied_url_patterns).map_err(|source| {
+            PolicyError::InvalidPattern {
+                list: "denied_url_patterns",
+                source,
+            }
+        })?;
+
+        Ok(Self {
+            policy,
+            allowed,
+            denied,
+        })
+    }
+
+    /// Returns the underlying policy
+    pub fn policy(&self) -> &CrawlPolicy {
+        &self.policy
+    }
+
+    /// Checks URL patterns and the domain depth limit
+    pub fn check_url(&self, url: &str, domain: &str, depth: usize) -> Result<(), PolicyViolation> {
+        if self.denied.is_match(url) {
+            return Err(PolicyViolation::Denied);
+        }
+        if !self.allowed.is_empty() && !self.allowed.is_match(url) {
+            return Err(PolicyViolation::NotAllowed);
+        }
+        if self
+            .policy
+            .budget_for(domain)
+            .max_depth
+            .is_some_and(|max| depth > max)
+        {
+            return Err(PolicyViolation::TooDeep);
+        }
+        Ok(())
+    }
+
+    /// Checks whether a domain has budget left for another page
+    pub fn check_budget(&self, domain: &str, stats: &DomainCrawlStats) -> Result<(), PolicyViolation> {
+        let budget = self.policy.budget_for(domain);
+        if budget.max_pages.is_some_and(|max| stats.pages_dispatched >= max) {
+            return Err(PolicyViolation::PageBudget);
+        }
+        if budget.max_bytes.is_some_and(|max| stats.bytes_fetched >= max) {
+            return Err(PolicyViolation::ByteBudget);
+        }
+        Ok(())
+    }
+}
+
+impl Default for PolicyEnforcer {
+    fn default() -> Self {
+        Self::new(CrawlPolicy::default()).expect("empty pattern lists always compile")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_patterns() {
+        let enforcer = PolicyEnforcer::new(CrawlPolicy {
+            allowed_url_patterns: vec!["^https://example\\.com/".to_string()],
+            denied_url_patterns: vec!["/calendar/".to_string(), "[?&]sort=".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert!(enforcer.check_url("https://example.com/docs", "example.com", 1).is_ok());
+        assert_eq!(
+            enforcer.check_url("https://example.com/calendar/2024/05", "example.com", 1),
+            Err(PolicyViolation::Denied)
+        );
+        assert_eq!(
+            enforcer.check_url("https://example.com/shop?sort=price", "example.com", 1),
+            Err(PolicyViolation::Denied)
+        );
+        assert_eq!(
+            enforcer.check_url("https://other.com/", "other.com", 1),
+            Err(PolicyViolation::NotAllowed)
+        );
+    }
+
+    #[test]
+    fn test_domain_overrides() {
+        let mut policy = CrawlPolicy {
+            max_pages_per_domain: Some(2),
+            max_depth: Some(1),
+            ..Default::default()
+        };
+        policy.domains.insert(
+            "docs.example.com".to_string(),
+            DomainBudget {
+                max_pages: Some(10),
+                ..Default::default()
+            },
+        );
+        let enforcer = PolicyEnforcer::new(policy).unwrap();
+
+        let stats = DomainCrawlStats {
+            pages_dispatched: 2,
+            ..Default::default()
+        };
+        assert_eq!(enforcer.check_budget("example.com", &stats), Err(PolicyViolation::PageBudget));
+        assert!(enforcer.check_budget("docs.example.com", &stats).is_ok());
+
+        // * The override keeps the global depth limit
+        assert_eq!(
+            enforcer.check_url("https://docs.example.com/a/b", "docs.example.com", 2),
+            Err(PolicyViolation::TooDeep)
+        );
+    }
+
+    #[test]
+    fn test_byte_budget() {
+        let enforcer = PolicyEnforcer::new(CrawlPolicy {
+            max_bytes: Some(1_000),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let stats = DomainCrawlStats {
+            bytes_fetched: 1_200,
+            ..Default::default()
+        };
+        assert_eq!(enforcer.check_budget("example.com", &stats), Err(PolicyViolation::ByteBudget));
+        assert!(enforcer.check_budget("example.com", &DomainCrawlStats::default()).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let result = PolicyEnforcer::new(CrawlPolicy {
+            denied_url_patterns: vec!["(unclosed".to_string()],
+            ..Default::default()
+        });
+        assert!(matches!(
+            result,
+            Err(PolicyError::InvalidPattern { list: "denied_url_patterns", .. })
+        ));
+    }
+}
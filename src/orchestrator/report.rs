@@ -0,0 +1,348 @@
+// * End-of-Crawl Report
+// * Turns a CrawlReport into a summary (totals, per-domain breakdown, top errors, quality
+// * distribution) written as JSON and Markdown on every checkpoint and when the crawl ends
+
+use super::crawler::{CrawlReport, DomainCrawlStats};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// File name of the JSON summary inside `ReportConfig::dir`
+pub const SUMMARY_JSON_FILE: &str = "crawl-summary.json";
+
+/// File name of the Markdown summary inside `ReportConfig::dir`
+pub const SUMMARY_MARKDOWN_FILE: &str = "crawl-summary.md";
+
+// * Error kinds listed in the summary
+const DEFAULT_TOP_ERRORS: usize = 10;
+
+// * Domains listed in the Markdown table (the JSON summary lists all of them)
+const DEFAULT_MARKDOWN_DOMAINS: usize = 25;
+
+/// Crawl summary settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReportConfig {
+    /// Directory the summaries are written to (None disables them)
+    pub dir: Option<PathBuf>,
+    /// Number of most frequent error kinds listed
+    pub top_errors: usize,
+    /// Number of busiest domains listed in the Markdown summary
+    pub markdown_domains: usize,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            top_errors: DEFAULT_TOP_ERRORS,
+            markdown_domains: DEFAULT_MARKDOWN_DOMAINS,
+        }
+    }
+}
+
+/// Counters of one domain in a summary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DomainSummary {
+    pub domain: String,
+    #[serde(flatten)]
+    pub stats: DomainCrawlStats,
+}
+
+/// How often an error kind occurred
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorCount {
+    pub kind: String,
+    pub count: usize,
+}
+
+/// Human- and machine-readable summary of a crawl
+///
+/// # Example
+/// ```ignore
+/// let summary = CrawlSummary::from_report(&report, &ReportConfig::default(), true);
+/// println!("{}", summary.to_markdown(25));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrawlSummary {
+    /// Unix time the summary was generated, in seconds
+    pub generated_at: u64,
+    /// False for summaries written at a checkpoint while the crawl is still running
+    pub complete: bool,
+    pub interrupted: bool,
+    pub pages_fetched: usize,
+    /// Unique records written to the store
+    pub unique_pages: usize,
+    pub duplicates_skipped: usize,
+    /// Share of deduplicated pages that were duplicates (0.0 - 1.0)
+    pub duplicate_rate: f64,
+    pub empty_pages: usize,
+    pub fetch_errors: usize,
+    pub total_bytes: u64,
+    /// Domains ordered by pages fetched, busiest first
+    pub domains: Vec<DomainSummary>,
+    /// Most frequent fetch error kinds, most frequent first
+    pub top_errors: Vec<ErrorCount>,
+    /// Refined pages by quality score bucket
+    pub quality_scores: BTreeMap<String, usize>,
+}
+
+impl CrawlSummary {
+    /// Summarizes a crawl report
+    pub fn from_report(report: &CrawlReport, config: &ReportConfig, complete: bool) -> Self {
+        let mut domains: Vec<DomainSummary> = report
+            .domains
+            .iter()
+            .map(|(domain, stats)| DomainSummary {
+                domain: domain.clone(),
+                stats: stats.clone(),
+            })
+            .collect();
+        // * Stable sort keeps ties in alphabetical order
+        domains.sort_by_key(|d| Reverse(d.stats.pages_fetched));
+
+        let mut top_errors: Vec<ErrorCount> = report
+            .errors
+            .iter()
+            .map(|(kind, count)| ErrorCount {
+                kind: kind.clone(),
+                count: *count,
+            })
+            .collect();
+        top_errors.sort_by_key(|e| Reverse(e.count));
+        top_errors.truncate(config.top_errors);
+
+        let deduplicated = report.pages_persisted + report.duplicates_skipped;
+        let duplicate_rate = match deduplicated {
+            0 => 0.0,
+            total => report.duplicates_skipped as f64 / total as f64,
+        };
+
+        Self {
+            generated_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            complete,
+            interrupted: report.interrupted,
+            pages_fetched: report.pages_fetched,
+            unique_pages: report.pages_persisted,
+            duplicates_skipped: report.duplicates_skipped,
+            duplicate_rate,
+            empty_pages: report.empty_pages,
+            fetch_errors: report.fetch_errors,
+            total_bytes: report.domains.values().map(|stats| stats.bytes_fetched).sum(),
+            domains,
+            top_errors,
+            quality_scores: report.quality_scores.clone(),
+        }
+    }
+
+    /// Renders the summary as pretty-printed JSON
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Renders the summary as Markdown, listing at most `max_domains` domains
+    pub fn to_markdown(&self, max_domains: usize) -> String {
+        let status = match (self.complete, self.interrupted) {
+            (_, true) => "interrupted",
+            (true, false) => "complete",
+            (false, false) => "in progress",
+        };
+
+        // * Writing to a String cannot fail
+        let mut md = String::new();
+        let _ = writeln!(md, "# Crawl Report\n");
+        let _ = writeln!(md, "Status: {} (generated at {})\n", status, self.generated_at);
+
+        let _ = writeln!(md, "## Totals\n");
+        let _ = writeln!(md, "| Metric | Value |\n|--------|-------|");
+        let _ = writeln!(md, "| Pages fetched | {} |", self.pages_fetched);
+        let _ = writeln!(md, "| Unique pages | {} |", self.unique_pages);
+        let _ = writeln!(
+            md,
+            "| Duplicates skipped | {} ({:.1}%) |",
+            self.duplicates_skipped,
+            self.duplicate_rate * 100.0
+        );
+        let _ = writeln!(md, "| Empty pages | {} |", self.empty_pages);
+        let _ = writeln!(md, "| Fetch errors | {} |", self.fetch_errors);
+        let _ = writeln!(md, "| Bytes fetched | {} |\n", format_bytes(self.total_bytes));
+
+        let _ = writeln!(md, "## Domains\n");
+        if self.domains.is_empty() {
+            let _ = writeln!(md, "No domains crawled.\n");
+        } else {
+            let _ = writeln!(
+                md,
+                "| Domain | Dispatched | Fetched | Persisted | Errors | Bytes |\n\
+                 |--------|------------|---------|-----------|--------|-------|"
+            );
+            for domain in self.domains.iter().take(max_domains) {
+                let _ = writeln!(
+                    md,
+                    "| {} | {} | {} | {} | {} | {} |",
+                    domain.domain,
+                    domain.stats.pages_dispatched,
+                    domain.stats.pages_fetched,
+                    domain.stats.pages_persisted,
+                    domain.stats.fetch_errors,
+                    format_bytes(domain.stats.bytes_fetched)
+                );
+            }
+            if self.domains.len() > max_domains {
+                let _ = writeln!(
+                    md,
+                    "\n...and {} more in the JSON summary.",
+                    self.domains.len() - max_domains
+                );
+            }
+            let _ = writeln!(md);
+        }
+
+        let _ = writeln!(md, "## Top Errors\n");
+        if self.top_errors.is_empty() {
+            let _ = writeln!(md, "No fetch errors.\n");
+        } else {
+            let _ = writeln!(md, "| Error | Count |\n|-------|-------|");
+            for error in &self.top_errors {
+                let _ = writeln!(md, "| {} | {} |", error.kind, error.count);
+            }
+            let _ = writeln!(md);
+        }
+
+        let _ = writeln!(md, "## Quality Scores\n");
+        if self.quality_scores.is_empty() {
+            let _ = writeln!(md, "No pages refined.");
+        } else {
+            let _ = writeln!(md, "| Score | Pages |\n|-------|-------|");
+            for (bucket, count) in &self.quality_scores {
+                let _ = writeln!(md, "| {} | {} |", bucket, count);
+            }
+        }
+        md
+    }
+
+    /// Writes the JSON and Markdown summaries into `dir`
+    pub async fn write_to(&self, dir: &Path, max_domains: usize) -> io::Result<()> {
+        tokio::fs::create_dir_all(dir).await?;
+        tokio::fs::write(dir.join(SUMMARY_JSON_FILE), self.to_json()?).await?;
+        tokio::fs::write(dir.join(SUMMARY_MARKDOWN_FILE), self.to_markdown(max_domains)).await
+    }
+}
+
+/// Formats a byte count with a binary unit (e.g. `1.5 MiB`)
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> CrawlReport {
+        let mut report = CrawlReport {
+            pages_fetched: 6,
+            pages_persisted: 3,
+            duplicates_skipped: 1,
+            fetch_errors: 4,
+            ..Default::default()
+        };
+        report.domains.insert(
+            "a.example.com".to_string(),
+            DomainCrawlStats {
+                pages_fetched: 2,
+                bytes_fetched: 2048,
+                ..Default::default()
+            },
+        );
+        report.domains.insert(
+            "b.example.com".to_string(),
+            DomainCrawlStats {
+                pages_fetched: 4,
+                bytes_fetched: 1024 * 1024,
+                ..Default::default()
+            },
+        );
+        report.errors.insert("http_403".to_string(), 1);
+        report.errors.insert("timeout".to_string(), 3);
+        report.quality_scores.insert("0.8-1.0".to_string(), 3);
+        report
+    }
+
+    #[test]
+    fn test_summary_from_report() {
+        let config = ReportConfig {
+            top_errors: 1,
+            ..Default::default()
+        };
+        let summary = CrawlSummary::from_report(&report(), &config, true);
+        assert_eq!(summary.unique_pages, 3);
+        assert!((summary.duplicate_rate - 0.25).abs() < f64::EPSILON);
+        assert_eq!(summary.total_bytes, 1024 * 1024 + 2048);
+        assert_eq!(summary.domains[0].domain, "b.example.com");
+        assert_eq!(
+            summary.top_errors,
+            vec![ErrorCount {
+                kind: "timeout".to_string(),
+                count: 3
+            }]
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&summary.to_json().unwrap()).unwrap();
+        assert_eq!(json["domains"][0]["pages_fetched"], 4);
+        assert_eq!(json["quality_scores"]["0.8-1.0"], 3);
+    }
+
+    #[test]
+    fn test_markdown_summary() {
+        let summary = CrawlSummary::from_report(&report(), &ReportConfig::default(), false);
+        let md = summary.to_markdown(1);
+        assert!(md.starts_with("# Crawl Report\n\nStatus: in progress"));
+        assert!(md.contains("| Duplicates skipped | 1 (25.0%) |"));
+        assert!(md.contains("| b.example.com | 0 | 4 | 0 | 0 | 1.0 MiB |"));
+        assert!(!md.contains("a.example.com"));
+        assert!(md.contains("...and 1 more in the JSON summary."));
+        assert!(md.contains("| timeout | 3 |\n| http_403 | 1 |"));
+        assert!(md.contains("| 0.8-1.0 | 3 |"));
+
+        let empty = CrawlSummary::from_report(&CrawlReport::default(), &ReportConfig::default(), true);
+        let md = empty.to_markdown(10);
+        assert!(md.contains("Status: complete"));
+        assert!(md.contains("No fetch errors."));
+    }
+
+    #[tokio::test]
+    async fn test_write_to_directory() {
+        let dir = std::env::temp_dir().join(format!("titan-report-{}", std::process::id()));
+        let summary = CrawlSummary::from_report(&report(), &ReportConfig::default(), true);
+        summary.write_to(&dir, 10).await.unwrap();
+
+        let json = std::fs::read_to_string(dir.join(SUMMARY_JSON_FILE)).unwrap();
+        assert_eq!(serde_json::from_str::<CrawlSummary>(&json).unwrap(), summary);
+        assert!(dir.join(SUMMARY_MARKDOWN_FILE).exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+}
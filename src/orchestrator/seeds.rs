@@ -0,0 +1,504 @@
+// * Seed Ingestion
+// * Loads seed lists from files, stdin or a remote URL list, as plain URL lists or CSV with a
+// * `url` column. The loader validates and normalizes every seed, drops duplicates and
+// * optionally skips URLs that are already stored from earlier crawls.
+
+use super::fetcher::FetchFuture;
+use crate::engine::normalization::normalize_url;
+use crate::persistence::RecordSearch;
+use reqwest::header::CONTENT_TYPE;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::io::AsyncReadExt;
+use tracing::{info, warn};
+
+// * Longest wait for a remote seed list
+const HTTP_LIST_TIMEOUT_SECS: u64 = 30;
+
+#[derive(Debug, Error)]
+pub enum SeedError {
+    #[error("Failed to read seeds from {0}: {1}")]
+    Io(String, #[source] std::io::Error),
+
+    #[error("Failed to download seeds from {0}: {1}")]
+    Http(String, String),
+
+    #[error("{source_name}:{line}: {message}")]
+    Csv {
+        source_name: String,
+        line: usize,
+        message: String,
+    },
+
+    #[error("Failed to check crawled URLs: {0}")]
+    Store(String),
+}
+
+/// Layout of a seed list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeedFormat {
+    /// One URL per line; blank lines and '#' comments are ignored
+    Lines,
+    /// Header row with a `url` column; other columns (e.g. exported notes) are ignored
+    Csv,
+}
+
+impl SeedFormat {
+    /// Picks CSV for `.csv` paths and URL lists, plain lines otherwise
+    pub fn from_path(path: &str) -> Self {
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        if path.to_ascii_lowercase().ends_with(".csv") {
+            SeedFormat::Csv
+        } else {
+            SeedFormat::Lines
+        }
+    }
+
+    /// Parses a seed list; `source_name` labels CSV errors
+    pub fn parse(&self, contents: &str, source_name: &str) -> Result<Vec<Seed>, SeedError> {
+        match self {
+            SeedFormat::Lines => Ok(parse_lines(contents)),
+            SeedFormat::Csv => parse_csv(contents, source_name),
+        }
+    }
+}
+
+/// A seed as read from its source, before validation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Seed {
+    pub url: String,
+    /// Line in the source (1-based)
+    pub line: usize,
+}
+
+/// Where seeds come from
+pub trait SeedSource: Send + Sync {
+    /// Label used in logs and rejection reports (a path, URL or "stdin")
+    fn name(&self) -> String;
+
+    /// Reads every seed in the source
+    fn load(&self) -> FetchFuture<'_, Result<Vec<Seed>, SeedError>>;
+}
+
+/// Seed list in a local file
+#[derive(Debug, Clone)]
+pub struct FileSeedSource {
+    path: PathBuf,
+    format: SeedFormat,
+}
+
+impl FileSeedSource {
+    /// Reads `path` as CSV when it ends in `.csv`, as a URL list otherwise
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let format = SeedFormat::from_path(&path.to_string_lossy());
+        Self { path, format }
+    }
+
+    pub fn with_format(mut self, format: SeedFormat) -> Self {
+        self.format = format;
+        self
+    }
+}
+
+impl SeedSource for FileSeedSource {
+    fn name(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    fn load(&self) -> FetchFuture<'_, Result<Vec<Seed>, SeedError>> {
+        Box::pin(async move {
+            let contents = tokio::fs::read_to_string(&self.path)
+                .await
+                .map_err(|e| SeedError::Io(self.name(), e))?;
+            self.format.parse(&contents, &self.name())
+        })
+    }
+}
+
+/// Seed list downloaded from a URL
+#[derive(Debug, Clone)]
+pub struct HttpSeedSource {
+    url: String,
+    format: Option<SeedFormat>,
+}
+
+impl HttpSeedSource {
+    /// Reads the list as CSV when it is served as `text/csv` or its path ends in `.csv`
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            format: None,
+        }
+    }
+
+    pub fn with_format(mut self, format: SeedFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+}
+
+impl SeedSource for HttpSeedSource {
+    fn name(&self) -> String {
+        self.url.clone()
+    }
+
+    fn load(&self) -> FetchFuture<'_, Result<Vec<Seed>, SeedError>> {
+        Box::pin(async move {
+            let http_error = |e: reqwest::Error| SeedError::Http(self.url.clone(), e.to_string());
+            let client = reqwest::Client::builder()
+                .timeout(Duration::from_secs(HTTP_LIST_TIMEOUT_SECS))
+                .build()
+                .map_err(http_error)?;
+            let response = client
+                .get(&self.url)
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(http_error)?;
+            let served_csv = response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|v| v.trim().to_ascii_lowercase().starts_with("text/csv"));
+            let format = self.format.unwrap_or(if served_csv {
+                SeedFormat::Csv
+            } else {
+                SeedFormat::from_path(&self.url)
+            });
+            let contents = response.text().await.map_err(http_error)?;
+            format.parse(&contents, &self.url)
+        })
+    }
+}
+
+/// Seed list piped to standard input
+#[derive(Debug, Clone)]
+pub struct StdinSeedSource {
+    format: SeedFormat,
+}
+
+impl StdinSeedSource {
+    pub fn new(format: SeedFormat) -> Self {
+        Self { format }
+    }
+}
+
+impl SeedSource for StdinSeedSource {
+    fn name(&self) -> String {
+        "stdin".to_string()
+    }
+
+    fn load(&self) -> FetchFuture<'_, Result<Vec<Seed>, SeedError>> {
+        Box::pin(async move {
+            let mut contents = String::new();
+            tokio::io::stdin()
+                .read_to_string(&mut contents)
+                .await
+                .map_err(|e| SeedError::Io(self.name(), e))?;
+            self.format.parse(&contents, &self.name())
+        })
+    }
+}
+
+/// Picks a source for a CLI argument: "-" is stdin, http(s) URLs are downloaded,
+/// anything else is a file path
+///
+/// `format` overrides the detected layout; stdin without one is read as a URL list.
+pub fn seed_source(spec: &str, format: Option<SeedFormat>) -> Box<dyn SeedSource> {
+    if spec == "-" {
+        Box::new(StdinSeedSource::new(format.unwrap_or(SeedFormat::Lines)))
+    } else if spec.starts_with("http://") || spec.starts_with("https://") {
+        let source = HttpSeedSource::new(spec);
+        Box::new(match format {
+            Some(format) => source.with_format(format),
+            None => source,
+        })
+    } else {
+        let source = FileSeedSource::new(Path::new(spec));
+        Box::new(match format {
+            Some(format) => source.with_format(format),
+            None => source,
+        })
+    }
+}
+
+/// A seed the loader did not accept
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RejectedSeed {
+    /// Name of the source it came from
+    pub source: String,
+    pub line: usize,
+    pub url: String,
+    pub reason: String,
+}
+
+/// Validated seeds from every source
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SeedBatch {
+    /// Normalized seeds in source order
+    pub seeds: Vec<Seed>,
+    /// Seeds that are not valid http(s) URLs
+    pub rejected: Vec<RejectedSeed>,
+    /// Seeds listed more than once (after normalization)
+    pub duplicates: usize,
+    /// Seeds skipped because their URL is already stored
+    pub already_crawled: usize,
+}
+
+impl SeedBatch {
+    /// Returns the seed URLs
+    pub fn urls(&self) -> Vec<String> {
+        self.seeds.iter().map(|seed| seed.url.clone()).collect()
+    }
+}
+
+/// Reads, validates and deduplicates seeds from one or more sources
+///
+/// # Example
+/// ```ignore
+/// let batch = SeedLoader::new()
+///     .with_source(FileSeedSource::new("seeds.csv"))
+///     .with_source(HttpSeedSource::new("https://lists.example.com/seeds.txt"))
+///     .with_crawled(store.clone())
+///     .load()
+///     .await?;
+/// orchestrator.run(batch.urls(), config).await?;
+/// ```
+#[derive(Default)]
+pub struct SeedLoader {
+    sources: Vec<Box<dyn SeedSource>>,
+    crawled: Option<Arc<dyn RecordSearch>>,
+}
+
+impl SeedLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a source; sources are read in the order they were added
+    pub fn with_source(mut self, source: impl SeedSource + 'static) -> Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+
+    /// Adds an already boxed source (e.g. from `seed_source`)
+    pub fn with_boxed_source(mut self, source: Box<dyn SeedSource>) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    /// Skips seeds whose normalized URL is already stored in `store`
+    pub fn with_crawled(mut self, store: Arc<dyn RecordSearch>) -> Self {
+        self.crawled = Some(store);
+        self
+    }
+
+    /// Reads every source; a source that cannot be read fails the whole load
+    pub async fn load(&self) -> Result<SeedBatch, SeedError> {
+        let mut batch = SeedBatch::default();
+        let mut seen = HashSet::new();
+
+        for source in &self.sources {
+            let name = source.name();
+            for mut seed in source.load().await? {
+                let raw = seed.url.trim();
+                let normalized = normalize_url(raw, raw).filter(|u| u.starts_with("http://") || u.starts_with("https://"));
+                let Some(url) = normalized else {
+                    batch.rejected.push(RejectedSeed {
+                        source: name.clone(),
+                        line: seed.line,
+                        url: seed.url,
+                        reason: "not an http(s) URL".to_string(),
+                    });
+                    continue;
+                };
+                if !seen.insert(url.clone()) {
+                    batch.duplicates += 1;
+                    continue;
+                }
+                if let Some(store) = &self.crawled {
+                    let stored = store
+                        .get_record_by_url(&url)
+                        .await
+                        .map_err(|e| SeedError::Store(e.to_string()))?;
+                    if stored.is_some() {
+                        batch.already_crawled += 1;
+                        continue;
+                    }
+                }
+                seed.url = url;
+                batch.seeds.push(seed);
+            }
+        }
+
+        for rejected in &batch.rejected {
+            warn!(source = %rejected.source, line = rejected.line, url = %rejected.url, "Rejected seed");
+        }
+        info!(
+            sources = self.sources.len(),
+            seeds = batch.seeds.len(),
+            rejected = batch.rejected.len(),
+            duplicates = batch.duplicates,
+            already_crawled = batch.already_crawled,
+            "Seeds loaded"
+        );
+        Ok(batch)
+    }
+}
+
+fn parse_lines(contents: &str) -> Vec<Seed> {
+    contents
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let url = line.split('#').next().unwrap_or_default().trim();
+            (!url.is_empty()).then(|| Seed {
+                url: url.to_string(),
+                line: idx + 1,
+            })
+        })
+        .collect()
+}
+
+fn parse_csv(contents: &str, source_name: &str) -> Result<Vec<Seed>, SeedError> {
+    let csv_error = |line: usize, message: String| SeedError::Csv {
+        source_name: source_name.to_string(),
+        line,
+        message,
+    };
+    let mut rows = contents
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim_start_matches('\u{feff}')))
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let Some((header_line, header)) = rows.next() else {
+        return Ok(Vec::new());
+    };
+    let columns: Vec<String> = split_csv_row(header)
+        .map_err(|e| csv_error(header_line, e))?
+        .into_iter()
+        .map(|column| column.trim().to_ascii_lowercase())
+        .collect();
+    let url_column = columns
+        .iter()
+        .position(|column| column == "url")
+        .ok_or_else(|| csv_error(header_line, "header has no \"url\" column".to_string()))?;
+
+    let mut seeds = Vec::new();
+    for (line, row) in rows {
+        let fields = split_csv_row(row).map_err(|e| csv_error(line, e))?;
+        let url = fields.get(url_column).map(|f| f.trim()).unwrap_or_default();
+        if url.is_empty() || url.starts_with('#') {
+            continue;
+        }
+        seeds.push(Seed {
+            url: url.to_string(),
+            line,
+        });
+    }
+    Ok(seeds)
+}
+
+// * RFC 4180 fields on one line: commas separate, quotes wrap commas and "" is a literal quote
+fn split_csv_row(row: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = row.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted field".to_string());
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::{InMemoryRecordStore, MultimodalRecord};
+
+    // * Serves a fixed seed list from memory
+    struct StaticSource(&'static str, SeedFormat);
+
+    impl SeedSource for StaticSource {
+        fn name(&self) -> String {
+            "static".to_string()
+        }
+
+        fn load(&self) -> FetchFuture<'_, Result<Vec<Seed>, SeedError>> {
+            Box::pin(async move { self.1.parse(self.0, "static") })
+        }
+    }
+
+    #[test]
+    fn test_line_lists_skip_comments() {
+        let seeds = SeedFormat::Lines.parse("# seeds\nhttps://a.com\n\n  https://b.com  # docs\n", "seeds.txt").unwrap();
+        let urls: Vec<_> = seeds.iter().map(|s| (s.url.as_str(), s.line)).collect();
+        assert_eq!(urls, vec![("https://a.com", 2), ("https://b.com", 4)]);
+        assert_eq!(SeedFormat::from_path("https://x.com/Seeds.CSV?v=2"), SeedFormat::Csv);
+        assert_eq!(SeedFormat::from_path("seeds.txt"), SeedFormat::Lines);
+    }
+
+    #[test]
+    fn test_csv_reads_the_url_column() {
+        let csv = "Category,URL,note\nnews,https://a.com/,\"Daily, with \"\"quotes\"\"\"\n,https://b.com/,\n";
+        let seeds = SeedFormat::Csv.parse(csv, "seeds.csv").unwrap();
+
+        let urls: Vec<_> = seeds.iter().map(|s| (s.url.as_str(), s.line)).collect();
+        assert_eq!(urls, vec![("https://a.com/", 2), ("https://b.com/", 3)]);
+
+        let err = SeedFormat::Csv.parse("link\nhttps://a.com/\n", "seeds.csv").unwrap_err();
+        assert_eq!(err.to_string(), "seeds.csv:1: header has no \"url\" column");
+        assert!(SeedFormat::Csv.parse("url\n\"https://a.com/\n", "seeds.csv").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_loader_validates_normalizes_and_dedups() {
+        let store = Arc::new(InMemoryRecordStore::new());
+        store.add(MultimodalRecord::new("https://done.com/".to_string(), 0, "Done".to_string()));
+
+        let batch = SeedLoader::new()
+            .with_source(StaticSource("HTTPS://A.com/#top\nftp://files.com/\nhttps://a.com/\n", SeedFormat::Lines))
+            .with_source(StaticSource("url,tag\nhttps://done.com/,old\nhttps://b.com/,new\n", SeedFormat::Csv))
+            .with_crawled(store)
+            .load()
+            .await
+            .unwrap();
+
+        assert_eq!(batch.urls(), vec!["https://a.com/", "https://b.com/"]);
+        assert_eq!(batch.duplicates, 1);
+        assert_eq!(batch.already_crawled, 1);
+        assert_eq!(
+            batch.rejected,
+            vec![RejectedSeed {
+                source: "static".to_string(),
+                line: 2,
+                url: "ftp://files.com/".to_string(),
+                reason: "not an http(s) URL".to_string(),
+            }]
+        );
+    }
+}
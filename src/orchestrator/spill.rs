@@ -0,0 +1,192 @@
+// * Frontier Spill Store
+// * JSON-lines segment files that hold cold frontier entries while the in-memory
+// * queue is over its memory budget, so large crawls do not need pre-sharded seeds
+
+use super::frontier::PendingUrl;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// * 64 MiB of queued URLs stays in memory before spilling
+const DEFAULT_MEMORY_BUDGET_BYTES: usize = 64 * 1024 * 1024;
+
+/// Frontier spilling settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpillConfig {
+    /// Directory for spill segments (None keeps the whole frontier in memory)
+    pub dir: Option<PathBuf>,
+    /// Estimated bytes of queued URLs kept in memory before cold entries spill to disk
+    pub memory_budget_bytes: usize,
+}
+
+impl Default for SpillConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            memory_budget_bytes: DEFAULT_MEMORY_BUDGET_BYTES,
+        }
+    }
+}
+
+/// A written segment file
+#[derive(Debug)]
+struct Segment {
+    path: PathBuf,
+    len: usize,
+}
+
+/// Spilled frontier entries, restored oldest segment first
+///
+/// Each store owns a private directory that is removed when the store is dropped;
+/// checkpoints, not spill files, are what survive a restart.
+#[derive(Debug)]
+pub struct SpillStore {
+    dir: PathBuf,
+    segments: VecDeque<Segment>,
+    next_segment: u64,
+}
+
+impl SpillStore {
+    /// Creates a private spill directory under `root`
+    pub fn create(root: impl AsRef<Path>) -> io::Result<Self> {
+        static STORES: AtomicU64 = AtomicU64::new(0);
+        let dir = root.as_ref().join(format!(
+            "frontier-{}-{}",
+            std::process::id(),
+            STORES.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            segments: VecDeque::new(),
+            next_segment: 0,
+        })
+    }
+
+    /// Writes entries to a new segment
+    pub fn write(&mut self, entries: &[PendingUrl]) -> io::Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let path = self.dir.join(format!("segment-{:08}.jsonl", self.next_segment));
+        let written = write_segment(&path, entries);
+        if written.is_err() {
+            let _ = fs::remove_file(&path);
+        }
+        written?;
+
+        self.next_segment += 1;
+        self.segments.push_back(Segment {
+            path,
+            len: entries.len(),
+        });
+        Ok(())
+    }
+
+    /// Removes and returns the oldest segment's entries
+    ///
+    /// The segment is dropped even when it cannot be read, so a damaged file is
+    /// reported once instead of blocking every later restore.
+    pub fn restore(&mut self) -> io::Result<Option<Vec<PendingUrl>>> {
+        let Some(segment) = self.segments.pop_front() else {
+            return Ok(None);
+        };
+        let entries = read_segment(&segment.path);
+        let _ = fs::remove_file(&segment.path);
+        entries.map(Some)
+    }
+
+    /// Reads every spilled entry without removing it
+    pub fn read_all(&self) -> io::Result<Vec<PendingUrl>> {
+        let mut entries = Vec::with_capacity(self.len());
+        for segment in &self.segments {
+            entries.extend(read_segment(&segment.path)?);
+        }
+        Ok(entries)
+    }
+
+    /// Returns the number of spilled entries
+    pub fn len(&self) -> usize {
+        self.segments.iter().map(|segment| segment.len).sum()
+    }
+
+    /// Returns true if nothing is spilled
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Returns the store's private directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl Drop for SpillStore {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn write_segment(path: &Path, entries: &[PendingUrl]) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    for entry in entries {
+        serde_json::to_writer(&mut writer, entry)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()
+}
+
+fn read_segment(path: &Path) -> io::Result<Vec<PendingUrl>> {
+    BufReader::new(File::open(path)?)
+        .lines()
+        .map(|line| Ok(serde_json::from_str(&line?)?))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(url: &str) -> PendingUrl {
+        PendingUrl {
+            url: url.to_string(),
+            anchor_text: "docs".to_string(),
+            depth: 2,
+            parent_url: Some("https://example.com/".to_string()),
+            discovered_at: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn test_segments_restore_oldest_first() {
+        let mut store = SpillStore::create(std::env::temp_dir()).unwrap();
+        store.write(&[pending("https://example.com/a"), pending("https://example.com/b")]).unwrap();
+        store.write(&[pending("https://example.com/c")]).unwrap();
+        store.write(&[]).unwrap();
+        assert_eq!(store.len(), 3);
+        assert_eq!(store.read_all().unwrap().len(), 3);
+
+        let first = store.restore().unwrap().unwrap();
+        assert_eq!(first, vec![pending("https://example.com/a"), pending("https://example.com/b")]);
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.restore().unwrap().unwrap(), vec![pending("https://example.com/c")]);
+        assert!(store.restore().unwrap().is_none());
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_drop_removes_directory() {
+        let mut store = SpillStore::create(std::env::temp_dir()).unwrap();
+        store.write(&[pending("https://example.com/a")]).unwrap();
+        let dir = store.dir().to_path_buf();
+        assert!(dir.exists());
+
+        drop(store);
+        assert!(!dir.exists());
+    }
+}
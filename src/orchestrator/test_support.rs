@@ -0,0 +1,161 @@
+// * Test Support
+// * The canned PageFetcher shared by the orchestrator, job, extraction and server tests,
+// * plus article builders whose topics stay below the near-duplicate threshold
+
+use super::fetcher::{FetchFuture, PageFetcher};
+use crate::engine::slow_path::SlowPathError;
+use crate::network::cache::{CacheValidators, ConditionalFetch};
+use crate::network::errors::NetworkError;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Serves canned pages keyed by URL
+#[derive(Default)]
+pub(crate) struct MockFetcher {
+    pub pages: HashMap<String, String>,
+    /// Builds the page for URLs missing from `pages` (e.g. an endless link chain)
+    pub generate: Option<fn(&str) -> String>,
+    pub robots: Option<String>,
+    pub soft_banned: Vec<String>,
+    pub hard_banned: Vec<String>,
+    // * URL -> Retry-After of the 429 it always answers with
+    pub throttled: HashMap<String, Duration>,
+    pub rendered: HashMap<String, String>,
+    // * URL -> ETag served with the page
+    pub etags: HashMap<String, String>,
+    // * URL -> refused connections left before the page is served
+    pub flaky: Mutex<HashMap<String, u32>>,
+    pub latency: Duration,
+}
+
+impl MockFetcher {
+    pub fn page(mut self, url: &str, html: impl Into<String>) -> Self {
+        self.pages.insert(url.to_string(), html.into());
+        self
+    }
+
+    pub fn generated(mut self, generate: fn(&str) -> String) -> Self {
+        self.generate = Some(generate);
+        self
+    }
+
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    pub fn flaky(self, url: &str, failures: u32) -> Self {
+        self.flaky.lock().unwrap().insert(url.to_string(), failures);
+        self
+    }
+}
+
+// * A real connection error: the port is closed as soon as it is reserved
+async fn refused_connection() -> NetworkError {
+    let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    NetworkError::Reqwest(reqwest::get(format!("http://127.0.0.1:{}/", port)).await.unwrap_err())
+}
+
+impl PageFetcher for MockFetcher {
+    fn fetch<'a>(&'a self, url: &'a str) -> FetchFuture<'a, Result<String, NetworkError>> {
+        let result = if self.soft_banned.iter().any(|u| u == url) {
+            Err(NetworkError::SoftBan("Simulated".into()))
+        } else if self.hard_banned.iter().any(|u| u == url) {
+            Err(NetworkError::HardBan(403))
+        } else if let Some(retry_after) = self.throttled.get(url) {
+            Err(NetworkError::Throttled { status: 429, retry_after: Some(*retry_after) })
+        } else {
+            self.pages
+                .get(url)
+                .cloned()
+                .or_else(|| self.generate.map(|generate| generate(url)))
+                .ok_or(NetworkError::EmptyResponse(0))
+        };
+        let refuse = match self.flaky.lock().unwrap().get_mut(url) {
+            Some(left) if *left > 0 => {
+                *left -= 1;
+                true
+            }
+            _ => false,
+        };
+        let latency = self.latency;
+        Box::pin(async move {
+            if !latency.is_zero() {
+                tokio::time::sleep(latency).await;
+            }
+            if refuse {
+                return Err(refused_connection().await);
+            }
+            result
+        })
+    }
+
+    fn fetch_conditional<'a>(
+        &'a self,
+        url: &'a str,
+        validators: Option<&'a CacheValidators>,
+    ) -> FetchFuture<'a, Result<ConditionalFetch, NetworkError>> {
+        let etag = self.etags.get(url).cloned();
+        Box::pin(async move {
+            if etag.is_some() && validators.and_then(|v| v.etag.clone()) == etag {
+                return Ok(ConditionalFetch::NotModified);
+            }
+            let body = self.fetch(url).await?;
+            Ok(ConditionalFetch::Modified {
+                body,
+                validators: etag.map(|etag| CacheValidators {
+                    etag: Some(etag),
+                    last_modified: None,
+                }),
+                encoding: None,
+                truncated: false,
+                redirect: None,
+            })
+        })
+    }
+
+    fn render<'a>(&'a self, url: &'a str) -> FetchFuture<'a, Result<String, SlowPathError>> {
+        let result = self.rendered.get(url).cloned().ok_or(SlowPathError::BrowserCrash);
+        Box::pin(async move { result })
+    }
+
+    fn fetch_robots_txt<'a>(&'a self, _url: &'a str) -> FetchFuture<'a, Option<String>> {
+        let robots = self.robots.clone();
+        Box::pin(async move { robots })
+    }
+}
+
+// * Distinct prose per topic keeps pages below the near-duplicate threshold
+pub(crate) fn topic_text(topic: &str) -> &'static str {
+    match topic {
+        "gardening" => "Compost heaps warm quickly when layered with straw, coffee grounds and kitchen peelings. Mulch suppresses weeds while worms aerate heavy clay beds.",
+        "astronomy" => "Jupiter's moons transit visibly through modest binoculars. Nebulae reveal faint hydrogen filaments whenever sky darkness exceeds magnitude six.",
+        "cooking" => "Braised shallots caramelize slowly beside thyme, butter and a splash of vinegar. Knead brioche dough until glossy before proofing overnight.",
+        "sailing" => "Reefing the mainsail early avoids broaching when gusts exceed twenty knots. Tack upwind by easing jib sheets and hauling the tiller firmly.",
+        _ => "Wheel-thrown stoneware needs bisque firing before glazes of feldspar, silica and cobalt oxide vitrify in the kiln.",
+    }
+}
+
+/// Builds an article page about `topic` linking to `links`
+pub(crate) fn article(topic: &str, links: &[&str]) -> String {
+    let anchors: String = links
+        .iter()
+        .map(|l| format!(r#"<a href="{}">Read the {} guide</a> "#, l, topic))
+        .collect();
+    let text = topic_text(topic);
+    format!(
+        r#"<html><head><title>{topic} handbook</title></head><body><article>
+        <h1>Everything about {topic}</h1>
+        <p>{text}</p>
+        <p>{anchors}</p>
+        </article></body></html>"#
+    )
+}
+
+/// Builds an article linking to `<url>/next`, so any URL starts an endless same-host chain
+pub(crate) fn chain_article(url: &str) -> String {
+    const TOPICS: [&str; 4] = ["gardening", "astronomy", "cooking", "sailing"];
+    let topic = TOPICS[url.matches("/next").count() % TOPICS.len()];
+    article(topic, &[&format!("{}/next", url.trim_end_matches('/'))])
+}
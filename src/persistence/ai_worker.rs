@@ -2,30 +2,59 @@
 // * Background worker for computing embeddings and sentiment scores
 // * Strictly non-blocking to the main crawl loop
 
-use crate::persistence::schema::{EnrichmentBatch, EnrichmentFilter, MultimodalRecord, EMBEDDING_DIM};
+use crate::persistence::budget::{estimate_tokens, EnrichmentBudget, EnrichmentBudgetConfig};
+use crate::persistence::ner::{entities_to_json, NerProvider};
+use crate::persistence::schema::{
+    ChunkRecord, EnrichmentBatch, EnrichmentFilter, MultimodalRecord, RecordFilter, RecordId,
+    RecordRevision, SimilarChunk, SimilarRecord, EMBEDDING_DIM,
+};
+use crate::persistence::summary::{LeadSummarizer, SummaryProvider};
+use crate::ops::{self, AlertManager};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::sync::mpsc;
-use tokio::time::{interval, Duration};
+use tokio::time::{interval, Duration, Instant};
+use tracing::Instrument;
 
 // * Worker configuration constants
 const DEFAULT_BATCH_SIZE: usize = 10;
 const DEFAULT_POLL_INTERVAL_MS: u64 = 5000;
 const DEFAULT_MAX_RETRIES: usize = 3;
+const DEFAULT_EMBEDDING_MODEL: &str = "hash-placeholder";
+const DEFAULT_EMBEDDING_VERSION: u32 = 1;
+const DEFAULT_SUMMARY_WORDS: usize = 60;
+
+// * Label of the pending-enrichment gauge in titan_queue_depth
+const QUEUE_NAME: &str = "enrichment";
 
 /// AI Enrichment Worker for background processing
-#[derive(Debug)]
 pub struct AIEnrichmentWorker {
     config: WorkerConfig,
+    providers: Providers,
     running: Arc<AtomicBool>,
     processed_count: Arc<AtomicUsize>,
     error_count: Arc<AtomicUsize>,
 }
 
+impl std::fmt::Debug for AIEnrichmentWorker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AIEnrichmentWorker")
+            .field("config", &self.config)
+            .field("providers", &self.providers)
+            .field("running", &self.running)
+            .field("processed_count", &self.processed_count)
+            .field("error_count", &self.error_count)
+            .finish()
+    }
+}
+
 /// Configuration for the AI enrichment worker
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct WorkerConfig {
     /// Number of records to process per batch
     pub batch_size: usize,
@@ -37,6 +66,18 @@ pub struct WorkerConfig {
     pub compute_embeddings: bool,
     /// Whether to compute sentiment scores
     pub compute_sentiment: bool,
+    /// Name of the embedding model, stored on every record it embeds
+    pub embedding_model: String,
+    /// Version of the embedding model; bump it when the model's output changes
+    pub embedding_version: u32,
+    /// Re-embed records whose embedding came from another model or version
+    pub reembed_stale: bool,
+    /// Whether to generate summaries
+    pub compute_summary: bool,
+    /// Target summary length in words
+    pub summary_target_words: usize,
+    /// Provider rate limits and daily spend cap
+    pub budget: EnrichmentBudgetConfig,
 }
 
 impl Default for WorkerConfig {
@@ -47,8 +88,31 @@ impl Default for WorkerConfig {
             max_retries: DEFAULT_MAX_RETRIES,
             compute_embeddings: true,
             compute_sentiment: true,
+            embedding_model: DEFAULT_EMBEDDING_MODEL.to_string(),
+            embedding_version: DEFAULT_EMBEDDING_VERSION,
+            reembed_stale: false,
+            compute_summary: false,
+            summary_target_words: DEFAULT_SUMMARY_WORDS,
+            budget: EnrichmentBudgetConfig::default(),
+        }
+    }
+}
+
+impl WorkerConfig {
+    /// Builds the filter for one batch of records to enrich
+    pub fn enrichment_filter(&self) -> EnrichmentFilter {
+        let filter = EnrichmentFilter::new(self.batch_size).missing_summary(self.compute_summary);
+        // * Without embeddings enabled, stale records could never be refreshed and would be refetched forever
+        if self.reembed_stale && self.compute_embeddings {
+            filter.current_embedding(self.embedding_model.clone(), self.embedding_version)
+        } else {
+            filter
         }
     }
+
+    fn embedding_is_stale(&self, record: &MultimodalRecord) -> bool {
+        self.reembed_stale && record.embedding_is_stale(&self.embedding_model, self.embedding_version)
+    }
 }
 
 impl AIEnrichmentWorker {
@@ -60,6 +124,7 @@ impl AIEnrichmentWorker {
     /// Creates a new worker with custom configuration
     pub fn with_config(config: WorkerConfig) -> Self {
         Self {
+            providers: Providers::new(&config),
             config,
             running: Arc::new(AtomicBool::new(false)),
             processed_count: Arc::new(AtomicUsize::new(0)),
@@ -67,6 +132,36 @@ impl AIEnrichmentWorker {
         }
     }
 
+    /// Tags people, organizations and locations in each record with `provider`
+    pub fn with_ner(mut self, provider: Arc<dyn NerProvider>) -> Self {
+        self.providers.ner = Some(provider);
+        self
+    }
+
+    /// Generates summaries with `provider` instead of `LeadSummarizer` (needs `compute_summary`)
+    pub fn with_summarizer(mut self, provider: Arc<dyn SummaryProvider>) -> Self {
+        self.providers.summarizer = Some(provider);
+        self
+    }
+
+    /// Embeds the chunks in `store` alongside whole records (needs `compute_embeddings`)
+    pub fn with_chunk_store(mut self, store: Arc<dyn ChunkStore>) -> Self {
+        self.providers.chunks = Some(store);
+        self
+    }
+
+    /// Fires a SEV-3 alert through `alerts` when the daily enrichment budget runs out
+    pub fn with_alert_manager(mut self, alerts: Arc<AlertManager>) -> Self {
+        self.providers.budget =
+            Arc::new(EnrichmentBudget::new(self.config.budget.clone()).with_alert_manager(alerts));
+        self
+    }
+
+    /// Returns the shared rate limiter and spend tracker
+    pub fn budget(&self) -> &Arc<EnrichmentBudget> {
+        &self.providers.budget
+    }
+
     /// Returns the worker's configuration
     pub fn config(&self) -> &WorkerConfig {
         &self.config
@@ -106,12 +201,14 @@ impl AIEnrichmentWorker {
         let processed = self.processed_count.clone();
         let errors = self.error_count.clone();
         let config = self.config.clone();
+        let providers = self.providers.clone();
 
         running.store(true, Ordering::Relaxed);
 
         let handle = tokio::spawn(async move {
             Self::worker_loop(
                 config,
+                providers,
                 provider,
                 updater,
                 running,
@@ -129,8 +226,10 @@ impl AIEnrichmentWorker {
     }
 
     /// Main worker loop - polls for records and processes them
+    #[allow(clippy::too_many_arguments)]
     async fn worker_loop<P, U>(
         config: WorkerConfig,
+        providers: Providers,
         provider: P,
         updater: U,
         running: Arc<AtomicBool>,
@@ -146,8 +245,11 @@ impl AIEnrichmentWorker {
         tracing::info!(
             batch_size = config.batch_size,
             poll_interval_ms = config.poll_interval_ms,
+            ner = providers.ner.as_ref().map(|ner| ner.name()),
+            summarizer = config.compute_summary.then(|| providers.summarizer().name()),
             "AI enrichment worker started"
         );
+        let filter = config.enrichment_filter().missing_entities(providers.ner.is_some());
 
         loop {
             tokio::select! {
@@ -160,22 +262,46 @@ impl AIEnrichmentWorker {
                         break;
                     }
 
+                    match provider.count_unenriched().await {
+                        Ok(Some(pending)) => ops::set_queue_depth(QUEUE_NAME, pending as i64),
+                        Ok(None) => {}
+                        Err(e) => tracing::debug!(error = %e, "Failed to count pending records"),
+                    }
+
+                    // * Paused until the next UTC day once the spend cap is reached
+                    if providers.budget.is_exhausted() {
+                        continue;
+                    }
+
                     // * Fetch batch of records needing enrichment
-                    let filter = EnrichmentFilter::new(config.batch_size);
-                    match provider.fetch_unenriched(filter).await {
+                    match provider.fetch_unenriched(filter.clone()).await {
                         Ok(batch) if !batch.is_empty() => {
                             tracing::debug!(count = batch.len(), "Processing batch");
+                            let started = Instant::now();
 
                             for mut record in batch.records {
-                                match Self::enrich_record(&mut record, &config).await {
+                                // * Continues the trace of the page that produced the record
+                                let span = tracing::info_span!("enrich", url = %record.url, record_id = %record.id);
+                                ops::otel::adopt_span(&record.id, &span);
+                                let enriched = Self::enrich_record(&mut record, &config, &providers)
+                                    .instrument(span.clone())
+                                    .await;
+                                match enriched {
                                     Ok(()) => {
-                                        if let Err(e) = updater.update_record(&record).await {
+                                        if let Err(e) = updater.update_record(&record).instrument(span).await {
                                             tracing::error!(error = %e, "Failed to update record");
                                             errors.fetch_add(1, Ordering::Relaxed);
                                         } else {
                                             processed.fetch_add(1, Ordering::Relaxed);
                                         }
                                     }
+                                    Err(EnrichmentError::BudgetExhausted(_)) => {
+                                        // * Keep whatever was computed before the budget ran out
+                                        if let Err(e) = updater.update_record(&record).instrument(span).await {
+                                            tracing::error!(error = %e, "Failed to update record");
+                                        }
+                                        break;
+                                    }
                                     Err(e) => {
                                         tracing::warn!(
                                             record_id = %record.id,
@@ -186,6 +312,7 @@ impl AIEnrichmentWorker {
                                     }
                                 }
                             }
+                            ops::record_enrichment_batch_duration(started.elapsed().as_secs_f64());
                         }
                         Ok(_) => {
                             // * No records to process, continue polling
@@ -195,6 +322,10 @@ impl AIEnrichmentWorker {
                             errors.fetch_add(1, Ordering::Relaxed);
                         }
                     }
+
+                    if let Some(store) = providers.chunks.as_ref().filter(|_| config.compute_embeddings) {
+                        Self::embed_chunks(store.as_ref(), &filter, &config, &providers, &errors).await;
+                    }
                 }
             }
         }
@@ -208,24 +339,99 @@ impl AIEnrichmentWorker {
     }
 
     /// Enriches a single record with embeddings and sentiment
-    async fn enrich_record(record: &mut MultimodalRecord, config: &WorkerConfig) -> Result<(), EnrichmentError> {
+    async fn enrich_record(
+        record: &mut MultimodalRecord,
+        config: &WorkerConfig,
+        providers: &Providers,
+    ) -> Result<(), EnrichmentError> {
+        // * Every provider call sends the full text, so each is billed the same estimate
+        let tokens = estimate_tokens(&record.text_content);
+        let budget = &providers.budget;
+
         // * Compute embedding if needed and configured
-        if config.compute_embeddings && record.embedding.is_none() {
+        if config.compute_embeddings && (record.embedding.is_none() || config.embedding_is_stale(record)) {
+            budget.acquire(tokens).await?;
             let embedding = compute_embedding(&record.text_content).await?;
             record
                 .set_embedding(embedding)
                 .map_err(|e| EnrichmentError::EmbeddingError(e.to_string()))?;
+            record.embedding_model = Some(config.embedding_model.clone());
+            record.embedding_version = Some(config.embedding_version);
         }
 
         // * Compute sentiment if needed and configured
         if config.compute_sentiment && record.sentiment_score.is_none() {
+            budget.acquire(tokens).await?;
             let sentiment = compute_sentiment(&record.text_content).await?;
             record.set_sentiment(sentiment);
         }
 
+        // * Tag named entities once per content version
+        if let Some(ner) = providers.ner.as_ref().filter(|_| record.entities_json.is_none()) {
+            budget.acquire(tokens).await?;
+            let entities = ner.extract_entities(&record.text_content).await?;
+            record.entities_json = Some(entities_to_json(&entities)?);
+            record.touch();
+        }
+
+        if config.compute_summary && record.summary.is_none() {
+            budget.acquire(tokens).await?;
+            let started = Instant::now();
+            let summary = providers
+                .summarizer()
+                .summarize(&record.text_content, config.summary_target_words)
+                .await;
+            let outcome = if summary.is_ok() { "ok" } else { "error" };
+            ops::record_enrichment_summary(outcome, started.elapsed().as_secs_f64());
+            record.summary = Some(summary?);
+            record.touch();
+        }
+
         Ok(())
     }
 
+    /// Embeds one batch of chunks that have no (current) embedding
+    async fn embed_chunks(
+        store: &dyn ChunkStore,
+        filter: &EnrichmentFilter,
+        config: &WorkerConfig,
+        providers: &Providers,
+        errors: &AtomicUsize,
+    ) {
+        let chunks = match store.fetch_unembedded_chunks(filter.clone()).await {
+            Ok(chunks) => chunks,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to fetch chunks");
+                errors.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+        };
+
+        for mut chunk in chunks {
+            let embedded = async {
+                providers.budget.acquire(estimate_tokens(&chunk.text)).await?;
+                let embedding = compute_embedding(&chunk.text).await?;
+                chunk
+                    .set_embedding(embedding, config.embedding_model.clone(), config.embedding_version)
+                    .map_err(|e| EnrichmentError::EmbeddingError(e.to_string()))?;
+                store.update_chunk(&chunk).await
+            };
+            match embedded.await {
+                Ok(()) => {}
+                Err(EnrichmentError::BudgetExhausted(_)) => break,
+                Err(e) => {
+                    tracing::warn!(
+                        record_id = %chunk.record_id,
+                        chunk_index = chunk.chunk_index,
+                        error = %e,
+                        "Failed to embed chunk"
+                    );
+                    errors.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
     /// Stops the worker gracefully
     pub fn stop(&self) {
         self.running.store(false, Ordering::Relaxed);
@@ -247,6 +453,41 @@ impl Default for AIEnrichmentWorker {
     }
 }
 
+/// Optional enrichment providers, and the budget they share, used by the worker loop
+#[derive(Clone)]
+struct Providers {
+    ner: Option<Arc<dyn NerProvider>>,
+    summarizer: Option<Arc<dyn SummaryProvider>>,
+    chunks: Option<Arc<dyn ChunkStore>>,
+    budget: Arc<EnrichmentBudget>,
+}
+
+impl Providers {
+    fn new(config: &WorkerConfig) -> Self {
+        Self {
+            ner: None,
+            summarizer: None,
+            chunks: None,
+            budget: Arc::new(EnrichmentBudget::new(config.budget.clone())),
+        }
+    }
+
+    fn summarizer(&self) -> &dyn SummaryProvider {
+        self.summarizer.as_deref().unwrap_or(&LeadSummarizer)
+    }
+}
+
+impl std::fmt::Debug for Providers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Providers")
+            .field("ner", &self.ner.as_ref().map(|ner| ner.name()))
+            .field("summarizer", &self.summarizer.as_ref().map(|summarizer| summarizer.name()))
+            .field("chunks", &self.chunks.is_some())
+            .field("budget", &self.budget)
+            .finish()
+    }
+}
+
 /// Handle for controlling a running worker
 pub struct WorkerHandle {
     shutdown_tx: mpsc::Sender<()>,
@@ -275,12 +516,25 @@ pub struct WorkerStats {
 }
 
 /// Type alias for async result
-type AsyncResult<T> = Pin<Box<dyn Future<Output = Result<T, EnrichmentError>> + Send>>;
+pub(crate) type AsyncResult<T> = Pin<Box<dyn Future<Output = Result<T, EnrichmentError>> + Send>>;
 
 /// Trait for providing records that need enrichment
 pub trait RecordProvider: Send + Sync {
     /// Fetches a batch of records that need enrichment
     fn fetch_unenriched(&self, filter: EnrichmentFilter) -> AsyncResult<EnrichmentBatch>;
+
+    /// Fetches up to `limit` matching records after skipping `offset` matches, in a stable order
+    fn fetch_records(
+        &self,
+        filter: RecordFilter,
+        offset: usize,
+        limit: usize,
+    ) -> AsyncResult<Vec<MultimodalRecord>>;
+
+    /// Counts records waiting for enrichment; None when the backend cannot count cheaply
+    fn count_unenriched(&self) -> AsyncResult<Option<usize>> {
+        Box::pin(async { Ok(None) })
+    }
 }
 
 /// Trait for updating enriched records
@@ -289,6 +543,108 @@ pub trait RecordUpdater: Send + Sync {
     fn update_record(&self, record: &MultimodalRecord) -> AsyncResult<()>;
 }
 
+/// Trait for inserting newly crawled records
+pub trait RecordSink: Send + Sync {
+    /// Inserts a record into storage
+    fn insert_record(&self, record: MultimodalRecord) -> AsyncResult<()>;
+}
+
+/// Trait for auditing how a URL's content changed over time
+pub trait RecordHistory: Send + Sync {
+    /// Returns every content version stored for a URL, oldest first (the last entry is current)
+    fn revision_history(&self, url: &str) -> AsyncResult<Vec<RecordRevision>>;
+}
+
+/// Trait for permanently removing soft-deleted records
+pub trait RecordPurger: Send + Sync {
+    /// Hard-deletes records soft-deleted before `deleted_before` (Unix seconds), with their
+    /// revisions and chunks, and returns the removed records
+    fn purge_deleted(&self, deleted_before: u64) -> AsyncResult<Vec<MultimodalRecord>>;
+
+    /// Reclaims the space purged records leave behind; a no-op by default
+    fn compact(&self) -> AsyncResult<()> {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Trait for looking up stored records by key or embedding similarity
+pub trait RecordSearch: Send + Sync {
+    /// Returns the live record with the given id
+    fn get_record(&self, id: &str) -> AsyncResult<Option<MultimodalRecord>>;
+
+    /// Returns the live record stored for a URL
+    fn get_record_by_url(&self, url: &str) -> AsyncResult<Option<MultimodalRecord>>;
+
+    /// Returns up to `limit` embedded records passing `filter` most similar to the query, best match first
+    ///
+    /// Backends with a vector index may return approximate nearest neighbours; the
+    /// in-memory store scans every record exactly.
+    fn search_similar(
+        &self,
+        embedding: Vec<f32>,
+        limit: usize,
+        filter: RecordFilter,
+    ) -> AsyncResult<Vec<SimilarRecord>>;
+}
+
+/// Finds up to `limit` records similar to the stored record `id`, excluding the record itself
+///
+/// Fails with `StorageError` when the record does not exist or has no embedding yet.
+pub async fn find_similar_records<S>(
+    store: &S,
+    id: &str,
+    limit: usize,
+    filter: RecordFilter,
+) -> Result<Vec<SimilarRecord>, EnrichmentError>
+where
+    S: RecordSearch + ?Sized,
+{
+    let record = store
+        .get_record(id)
+        .await?
+        .ok_or_else(|| EnrichmentError::StorageError(format!("Record {} not found", id)))?;
+    let embedding = record
+        .embedding
+        .ok_or_else(|| EnrichmentError::StorageError(format!("Record {} has no embedding", id)))?;
+
+    let mut matches = store.search_similar(embedding, limit + 1, filter).await?;
+    matches.retain(|m| m.record.id != record.id);
+    matches.truncate(limit);
+    Ok(matches)
+}
+
+/// Trait for providing record chunks that need embedding
+pub trait ChunkProvider: Send + Sync {
+    /// Fetches up to `filter.limit` chunks that `filter.matches_chunk` selects
+    fn fetch_unembedded_chunks(&self, filter: EnrichmentFilter) -> AsyncResult<Vec<ChunkRecord>>;
+
+    /// Returns a record's chunks, ordered by `chunk_index`
+    fn chunks_for_record(&self, record_id: &str) -> AsyncResult<Vec<ChunkRecord>>;
+}
+
+/// Trait for updating embedded chunks
+pub trait ChunkUpdater: Send + Sync {
+    /// Updates a chunk with its embedding
+    fn update_chunk(&self, chunk: &ChunkRecord) -> AsyncResult<()>;
+}
+
+/// Trait for storing the chunks of newly crawled records
+pub trait ChunkSink: Send + Sync {
+    /// Replaces every chunk stored for a URL with the chunks of its latest content
+    fn replace_chunks(&self, url: &str, chunks: Vec<ChunkRecord>) -> AsyncResult<()>;
+}
+
+/// Trait for chunk-level retrieval
+pub trait ChunkSearch: Send + Sync {
+    /// Returns up to `limit` embedded chunks most similar to the query, best match first
+    fn search_similar_chunks(&self, embedding: Vec<f32>, limit: usize) -> AsyncResult<Vec<SimilarChunk>>;
+}
+
+/// Chunk storage written by the crawler and embedded by the enrichment worker
+pub trait ChunkStore: ChunkProvider + ChunkUpdater + ChunkSink + ChunkSearch {}
+
+impl<T> ChunkStore for T where T: ChunkProvider + ChunkUpdater + ChunkSink + ChunkSearch {}
+
 /// Errors that can occur during enrichment
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum EnrichmentError {
@@ -298,6 +654,15 @@ pub enum EnrichmentError {
     #[error("Sentiment computation failed: {0}")]
     SentimentError(String),
 
+    #[error("Entity extraction failed: {0}")]
+    EntityError(String),
+
+    #[error("Summarization failed: {0}")]
+    SummaryError(String),
+
+    #[error("Daily enrichment budget of ${0:.2} exhausted")]
+    BudgetExhausted(f64),
+
     #[error("Storage error: {0}")]
     StorageError(String),
 
@@ -394,18 +759,64 @@ fn hash_text(text: &str) -> u64 {
 }
 
 /// In-memory record store for testing
+///
+/// Inserting a record for a URL that is already stored updates it in place and keeps
+/// the superseded version in the revision history.
 #[derive(Debug, Default)]
 pub struct InMemoryRecordStore {
     records: std::sync::RwLock<Vec<MultimodalRecord>>,
+    revisions: std::sync::RwLock<Vec<RecordRevision>>,
+    chunks: std::sync::RwLock<Vec<ChunkRecord>>,
 }
 
 impl InMemoryRecordStore {
     pub fn new() -> Self {
         Self {
             records: std::sync::RwLock::new(Vec::new()),
+            revisions: std::sync::RwLock::new(Vec::new()),
+            chunks: std::sync::RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Inserts a record, or revises the stored record with the same URL
+    ///
+    /// Returns the superseded version when the content changed.
+    pub fn upsert(&self, record: MultimodalRecord) -> Option<RecordRevision> {
+        let mut records = self.records.write().unwrap();
+        match records.iter_mut().find(|r| r.url == record.url && !r.is_deleted) {
+            Some(existing) => {
+                let previous = existing.apply_revision(record)?;
+                self.revisions.write().unwrap().push(previous.clone());
+                Some(previous)
+            }
+            None => {
+                records.push(record);
+                None
+            }
         }
     }
 
+    /// Returns the stored versions of a URL, oldest first
+    pub fn revisions_for(&self, url: &str) -> Vec<RecordRevision> {
+        let mut history: Vec<RecordRevision> = self
+            .revisions
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|r| r.url == url)
+            .cloned()
+            .collect();
+        history.extend(
+            self.records
+                .read()
+                .unwrap()
+                .iter()
+                .filter(|r| r.url == url && !r.is_deleted)
+                .map(MultimodalRecord::to_revision),
+        );
+        history
+    }
+
     pub fn add(&self, record: MultimodalRecord) {
         let mut records = self.records.write().unwrap();
         records.push(record);
@@ -415,6 +826,16 @@ impl InMemoryRecordStore {
         self.records.read().unwrap().len()
     }
 
+    /// Returns a snapshot of all stored records
+    pub fn records(&self) -> Vec<MultimodalRecord> {
+        self.records.read().unwrap().clone()
+    }
+
+    /// Returns a snapshot of all stored chunks
+    pub fn chunks(&self) -> Vec<ChunkRecord> {
+        self.chunks.read().unwrap().clone()
+    }
+
     pub fn get_enriched_count(&self) -> usize {
         self.records
             .read()
@@ -430,13 +851,44 @@ impl RecordProvider for InMemoryRecordStore {
         let records = self.records.read().unwrap();
         let unenriched: Vec<MultimodalRecord> = records
             .iter()
-            .filter(|r| r.needs_enrichment() && (filter.include_deleted || !r.is_deleted))
+            .filter(|r| filter.matches(r))
             .take(filter.limit)
             .cloned()
             .collect();
 
         Box::pin(async move { Ok(EnrichmentBatch::new(unenriched)) })
     }
+
+    fn fetch_records(
+        &self,
+        filter: RecordFilter,
+        offset: usize,
+        limit: usize,
+    ) -> AsyncResult<Vec<MultimodalRecord>> {
+        let records: Vec<MultimodalRecord> = self
+            .records
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|r| filter.matches(r))
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect();
+
+        Box::pin(async move { Ok(records) })
+    }
+
+    fn count_unenriched(&self) -> AsyncResult<Option<usize>> {
+        let pending = self
+            .records
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|r| r.needs_enrichment() && !r.is_deleted)
+            .count();
+        Box::pin(async move { Ok(Some(pending)) })
+    }
 }
 
 impl RecordUpdater for InMemoryRecordStore {
@@ -451,11 +903,67 @@ impl RecordUpdater for InMemoryRecordStore {
     }
 }
 
+impl RecordSink for InMemoryRecordStore {
+    fn insert_record(&self, record: MultimodalRecord) -> AsyncResult<()> {
+        self.upsert(record);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+impl RecordHistory for InMemoryRecordStore {
+    fn revision_history(&self, url: &str) -> AsyncResult<Vec<RecordRevision>> {
+        let history = self.revisions_for(url);
+        Box::pin(async move { Ok(history) })
+    }
+}
+
+impl RecordPurger for InMemoryRecordStore {
+    fn purge_deleted(&self, deleted_before: u64) -> AsyncResult<Vec<MultimodalRecord>> {
+        let mut records = self.records.write().unwrap();
+        let (purged, kept): (Vec<MultimodalRecord>, Vec<MultimodalRecord>) =
+            records.drain(..).partition(|r| r.deleted_before(deleted_before));
+        *records = kept;
+
+        let purged_ids: HashSet<&RecordId> = purged.iter().map(|r| &r.id).collect();
+        self.revisions
+            .write()
+            .unwrap()
+            .retain(|r| !purged_ids.contains(&r.record_id));
+        self.chunks
+            .write()
+            .unwrap()
+            .retain(|c| !purged_ids.contains(&c.record_id));
+        drop(purged_ids);
+
+        Box::pin(async move { Ok(purged) })
+    }
+
+    fn compact(&self) -> AsyncResult<()> {
+        self.records.write().unwrap().shrink_to_fit();
+        self.revisions.write().unwrap().shrink_to_fit();
+        self.chunks.write().unwrap().shrink_to_fit();
+        Box::pin(async { Ok(()) })
+    }
+}
+
 // * Implement traits for Arc<InMemoryRecordStore> to support shared ownership
 impl RecordProvider for Arc<InMemoryRecordStore> {
     fn fetch_unenriched(&self, filter: EnrichmentFilter) -> AsyncResult<EnrichmentBatch> {
         (**self).fetch_unenriched(filter)
     }
+
+    fn fetch_records(
+        &self,
+        filter: RecordFilter,
+        offset: usize,
+        limit: usize,
+    ) -> AsyncResult<Vec<MultimodalRecord>> {
+        (**self).fetch_records(filter, offset, limit)
+    }
+
+    fn count_unenriched(&self) -> AsyncResult<Option<usize>> {
+        (**self).count_unenriched()
+    }
 }
 
 impl RecordUpdater for Arc<InMemoryRecordStore> {
@@ -464,6 +972,193 @@ impl RecordUpdater for Arc<InMemoryRecordStore> {
     }
 }
 
+impl RecordSink for Arc<InMemoryRecordStore> {
+    fn insert_record(&self, record: MultimodalRecord) -> AsyncResult<()> {
+        (**self).insert_record(record)
+    }
+}
+
+impl RecordHistory for Arc<InMemoryRecordStore> {
+    fn revision_history(&self, url: &str) -> AsyncResult<Vec<RecordRevision>> {
+        (**self).revision_history(url)
+    }
+}
+
+impl RecordPurger for Arc<InMemoryRecordStore> {
+    fn purge_deleted(&self, deleted_before: u64) -> AsyncResult<Vec<MultimodalRecord>> {
+        (**self).purge_deleted(deleted_before)
+    }
+
+    fn compact(&self) -> AsyncResult<()> {
+        (**self).compact()
+    }
+}
+
+impl RecordSearch for InMemoryRecordStore {
+    fn get_record(&self, id: &str) -> AsyncResult<Option<MultimodalRecord>> {
+        let record = self
+            .records
+            .read()
+            .unwrap()
+            .iter()
+            .find(|r| r.id == id && !r.is_deleted)
+            .cloned();
+        Box::pin(async move { Ok(record) })
+    }
+
+    fn get_record_by_url(&self, url: &str) -> AsyncResult<Option<MultimodalRecord>> {
+        let record = self
+            .records
+            .read()
+            .unwrap()
+            .iter()
+            .find(|r| r.url == url && !r.is_deleted)
+            .cloned();
+        Box::pin(async move { Ok(record) })
+    }
+
+    /// Exact cosine scan over every embedded record
+    fn search_similar(
+        &self,
+        embedding: Vec<f32>,
+        limit: usize,
+        filter: RecordFilter,
+    ) -> AsyncResult<Vec<SimilarRecord>> {
+        let mut matches: Vec<SimilarRecord> = self
+            .records
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|r| filter.matches(r))
+            .filter_map(|r| {
+                r.similarity_to(&embedding).map(|score| SimilarRecord {
+                    record: r.clone(),
+                    score,
+                })
+            })
+            .collect();
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches.truncate(limit);
+        Box::pin(async move { Ok(matches) })
+    }
+}
+
+impl ChunkProvider for InMemoryRecordStore {
+    fn fetch_unembedded_chunks(&self, filter: EnrichmentFilter) -> AsyncResult<Vec<ChunkRecord>> {
+        let chunks: Vec<ChunkRecord> = self
+            .chunks
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|c| filter.matches_chunk(c))
+            .take(filter.limit)
+            .cloned()
+            .collect();
+        Box::pin(async move { Ok(chunks) })
+    }
+
+    fn chunks_for_record(&self, record_id: &str) -> AsyncResult<Vec<ChunkRecord>> {
+        let mut chunks: Vec<ChunkRecord> = self
+            .chunks
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|c| c.record_id == record_id)
+            .cloned()
+            .collect();
+        chunks.sort_by_key(|c| c.chunk_index);
+        Box::pin(async move { Ok(chunks) })
+    }
+}
+
+impl ChunkUpdater for InMemoryRecordStore {
+    fn update_chunk(&self, chunk: &ChunkRecord) -> AsyncResult<()> {
+        let mut chunks = self.chunks.write().unwrap();
+        match chunks
+            .iter_mut()
+            .find(|c| c.record_id == chunk.record_id && c.chunk_index == chunk.chunk_index)
+        {
+            Some(existing) => {
+                *existing = chunk.clone();
+                Box::pin(async { Ok(()) })
+            }
+            None => Box::pin(async { Err(EnrichmentError::StorageError("Chunk not found".to_string())) }),
+        }
+    }
+}
+
+impl ChunkSink for InMemoryRecordStore {
+    /// Links the chunks to the stored record for the URL, whose ID survives re-crawls,
+    /// and keeps the embeddings of chunks whose text did not change
+    fn replace_chunks(&self, url: &str, mut chunks: Vec<ChunkRecord>) -> AsyncResult<()> {
+        let live_id = self
+            .records
+            .read()
+            .unwrap()
+            .iter()
+            .find(|r| r.url == url && !r.is_deleted)
+            .map(|r| r.id.clone());
+
+        let mut stored = self.chunks.write().unwrap();
+        for chunk in &mut chunks {
+            if let Some(id) = &live_id {
+                chunk.record_id = id.clone();
+            }
+            let unchanged = stored
+                .iter()
+                .find(|c| c.url == url && c.chunk_index == chunk.chunk_index && c.text == chunk.text);
+            if let Some(previous) = unchanged.filter(|_| chunk.embedding.is_none()) {
+                chunk.embedding = previous.embedding.clone();
+                chunk.embedding_model = previous.embedding_model.clone();
+                chunk.embedding_version = previous.embedding_version;
+            }
+        }
+        stored.retain(|c| c.url != url);
+        stored.extend(chunks);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+impl ChunkSearch for InMemoryRecordStore {
+    /// Brute-force cosine scan over every embedded chunk
+    fn search_similar_chunks(&self, embedding: Vec<f32>, limit: usize) -> AsyncResult<Vec<SimilarChunk>> {
+        let mut matches: Vec<SimilarChunk> = self
+            .chunks
+            .read()
+            .unwrap()
+            .iter()
+            .filter_map(|c| {
+                c.similarity_to(&embedding).map(|score| SimilarChunk {
+                    chunk: c.clone(),
+                    score,
+                })
+            })
+            .collect();
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches.truncate(limit);
+        Box::pin(async move { Ok(matches) })
+    }
+}
+
+impl RecordSearch for Arc<InMemoryRecordStore> {
+    fn get_record(&self, id: &str) -> AsyncResult<Option<MultimodalRecord>> {
+        (**self).get_record(id)
+    }
+
+    fn get_record_by_url(&self, url: &str) -> AsyncResult<Option<MultimodalRecord>> {
+        (**self).get_record_by_url(url)
+    }
+
+    fn search_similar(
+        &self,
+        embedding: Vec<f32>,
+        limit: usize,
+        filter: RecordFilter,
+    ) -> AsyncResult<Vec<SimilarRecord>> {
+        (**self).search_similar(embedding, limit, filter)
+    }
+}
+
 /// Builder for creating enrichment pipelines
 #[derive(Debug)]
 pub struct EnrichmentPipelineBuilder {
@@ -502,6 +1197,23 @@ impl EnrichmentPipelineBuilder {
         self
     }
 
+    pub fn embedding_model(mut self, model: impl Into<String>, version: u32) -> Self {
+        self.config.embedding_model = model.into();
+        self.config.embedding_version = version;
+        self
+    }
+
+    pub fn reembed_stale(mut self, enabled: bool) -> Self {
+        self.config.reembed_stale = enabled;
+        self
+    }
+
+    pub fn with_summary(mut self, enabled: bool, target_words: usize) -> Self {
+        self.config.compute_summary = enabled;
+        self.config.summary_target_words = target_words;
+        self
+    }
+
     pub fn build(self) -> AIEnrichmentWorker {
         AIEnrichmentWorker::with_config(self.config)
     }
@@ -577,6 +1289,7 @@ mod tests {
 
         assert_eq!(batch.len(), 1);
         assert!(batch.records[0].needs_enrichment());
+        assert_eq!(store.count_unenriched().await.unwrap(), Some(1));
     }
 
     #[tokio::test]
@@ -600,6 +1313,97 @@ mod tests {
         assert_eq!(store.get_enriched_count(), 1);
     }
 
+    #[tokio::test]
+    async fn test_in_memory_store_sink() {
+        let store = InMemoryRecordStore::new();
+        let record = MultimodalRecord::new(
+            "https://example.com".to_string(),
+            12345,
+            "Test content".to_string(),
+        );
+
+        store.insert_record(record).await.unwrap();
+
+        assert_eq!(store.count(), 1);
+        assert_eq!(store.records()[0].url, "https://example.com");
+        assert_eq!(store.get_enriched_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_search_similar() {
+        let store = InMemoryRecordStore::new();
+        for (i, text) in ["rust async runtimes", "gardening in spring", "tokio executors"].iter().enumerate() {
+            let mut record = MultimodalRecord::new(format!("https://example.com/{}", i), i as u64, text.to_string());
+            record.embedding = Some(compute_embedding(text).await.unwrap());
+            store.add(record);
+        }
+        store.add(MultimodalRecord::new("https://example.com/raw".to_string(), 9, "no embedding".to_string()));
+
+        let query = compute_embedding("gardening in spring").await.unwrap();
+        let matches = store.search_similar(query, 2, RecordFilter::new()).await.unwrap();
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].record.url, "https://example.com/1");
+        assert!((matches[0].score - 1.0).abs() < 1e-4);
+        assert!(matches[0].score >= matches[1].score);
+
+        let id = matches[0].record.id.clone();
+        assert_eq!(store.get_record(&id).await.unwrap().unwrap().url, "https://example.com/1");
+        assert!(store.get_record("missing").await.unwrap().is_none());
+        assert!(store.get_record_by_url("https://example.com/raw").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_find_similar_records_applies_filter() {
+        let store = InMemoryRecordStore::new();
+        let mut ids = Vec::new();
+        for (i, text) in ["tide tables", "tide charts", "sourdough"].iter().enumerate() {
+            let mut record = MultimodalRecord::new(format!("https://example.com/{}", i), i as u64, text.to_string());
+            record.embedding = Some(compute_embedding(text).await.unwrap());
+            record.is_deleted = i == 1;
+            ids.push(record.id.clone());
+            store.add(record);
+        }
+
+        // * The query record itself and soft-deleted records are left out
+        let matches = find_similar_records(&store, &ids[0], 5, RecordFilter::new()).await.unwrap();
+        let urls: Vec<&str> = matches.iter().map(|m| m.record.url.as_str()).collect();
+        assert_eq!(urls, ["https://example.com/2"]);
+
+        let filter = RecordFilter::new().include_deleted(true);
+        assert_eq!(find_similar_records(&store, &ids[0], 5, filter).await.unwrap().len(), 2);
+        assert!(find_similar_records(&store, "missing", 5, RecordFilter::new()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_revision_history() {
+        let store = InMemoryRecordStore::new();
+        let url = "https://example.com/story";
+
+        store
+            .insert_record(MultimodalRecord::new(url.to_string(), 1, "Original".to_string()))
+            .await
+            .unwrap();
+        // * Same content: no new revision
+        store
+            .insert_record(MultimodalRecord::new(url.to_string(), 1, "Original".to_string()))
+            .await
+            .unwrap();
+        store
+            .insert_record(MultimodalRecord::new(url.to_string(), 2, "Corrected".to_string()))
+            .await
+            .unwrap();
+
+        assert_eq!(store.count(), 1);
+        assert_eq!(store.records()[0].text_content, "Corrected");
+
+        let history = store.revision_history(url).await.unwrap();
+        let hashes: Vec<(u32, u64)> = history.iter().map(|r| (r.revision, r.content_hash)).collect();
+        assert_eq!(hashes, vec![(1, 1), (2, 2)]);
+        assert!(history[0].valid_until.is_some());
+        assert!(history[1].valid_until.is_none());
+        assert!(store.revision_history("https://example.com/other").await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_worker_config_default() {
         let config = WorkerConfig::default();
@@ -656,6 +1460,8 @@ mod tests {
             "Terrible bad content".to_string(),
         ));
 
+        let batches_before = crate::ops::telemetry::ENRICHMENT_BATCH_DURATION_SECONDS.get_sample_count();
+
         // * Start worker
         let handle = worker.start(store.clone(), store.clone()).await;
 
@@ -668,6 +1474,145 @@ mod tests {
         // * Verify processing occurred
         assert!(worker.processed_count() > 0, "Should have processed records");
         assert_eq!(store.get_enriched_count(), 2, "Both records should be enriched");
+        assert!(crate::ops::telemetry::ENRICHMENT_BATCH_DURATION_SECONDS.get_sample_count() > batches_before);
+    }
+
+    #[tokio::test]
+    async fn test_worker_reembeds_stale_records() {
+        let store = Arc::new(InMemoryRecordStore::new());
+        let mut legacy = MultimodalRecord::new("https://example.com/1".to_string(), 1, "Old embedding".to_string());
+        legacy.set_embedding(vec![0.0; EMBEDDING_DIM]).unwrap();
+        legacy.set_sentiment(0.0);
+        store.add(legacy);
+
+        let config = WorkerConfig {
+            poll_interval_ms: 20,
+            embedding_model: "minilm".to_string(),
+            embedding_version: 2,
+            ..Default::default()
+        };
+        // * Stale embeddings are left alone until re-embedding is switched on
+        assert!(store.fetch_unenriched(config.enrichment_filter()).await.unwrap().is_empty());
+        let reembed = WorkerConfig {
+            reembed_stale: true,
+            ..config
+        };
+        assert_eq!(store.fetch_unenriched(reembed.enrichment_filter()).await.unwrap().len(), 1);
+
+        let handle = AIEnrichmentWorker::with_config(reembed.clone()).start(store.clone(), store.clone()).await;
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        handle.shutdown().await;
+
+        let record = &store.records()[0];
+        assert_eq!(record.embedding_model.as_deref(), Some("minilm"));
+        assert_eq!(record.embedding_version, Some(2));
+        assert!(record.embedding.as_ref().unwrap().iter().any(|v| *v != 0.0));
+        assert!(store.fetch_unenriched(reembed.enrichment_filter()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_worker_embeds_chunks_for_retrieval() {
+        let store = Arc::new(InMemoryRecordStore::new());
+        let record = MultimodalRecord::new("https://example.com/c".to_string(), 1, "Chunked page".to_string());
+        let record_id = record.id.clone();
+        store.add(record.clone());
+        let chunks = ["tides follow the moon", "bread needs patient proofing"]
+            .iter()
+            .enumerate()
+            .map(|(i, text)| ChunkRecord::new(RecordId::generate(), record.url.clone(), i as u32, *text))
+            .collect();
+        store.replace_chunks(&record.url, chunks).await.unwrap();
+
+        // * Chunks are linked to the stored record, not the ID they were built with
+        let stored = store.chunks_for_record(&record_id).await.unwrap();
+        assert_eq!(stored.len(), 2);
+
+        let config = WorkerConfig {
+            poll_interval_ms: 20,
+            ..Default::default()
+        };
+        let handle = AIEnrichmentWorker::with_config(config)
+            .with_chunk_store(store.clone())
+            .start(store.clone(), store.clone())
+            .await;
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        handle.shutdown().await;
+
+        assert!(store.fetch_unembedded_chunks(EnrichmentFilter::new(10)).await.unwrap().is_empty());
+        let query = compute_embedding("bread needs patient proofing").await.unwrap();
+        let matches = store.search_similar_chunks(query, 1).await.unwrap();
+        assert_eq!(matches[0].chunk.chunk_index, 1);
+        assert_eq!(matches[0].chunk.embedding_model.as_deref(), Some(DEFAULT_EMBEDDING_MODEL));
+
+        // * Re-storing unchanged chunks keeps their embeddings
+        let unchanged = store.chunks_for_record(&record_id).await.unwrap();
+        let refreshed = unchanged
+            .into_iter()
+            .map(|c| ChunkRecord::new(c.record_id, c.url, c.chunk_index, c.text))
+            .collect();
+        store.replace_chunks(&record.url, refreshed).await.unwrap();
+        assert!(store.chunks().iter().all(|c| c.embedding.is_some()));
+    }
+
+    #[tokio::test]
+    async fn test_worker_tags_named_entities() {
+        use crate::persistence::{GazetteerNer, NamedEntityKind};
+
+        let store = Arc::new(InMemoryRecordStore::new());
+        // * Already embedded and scored, so only NER selects it
+        let mut record = MultimodalRecord::new(
+            "https://example.com/news".to_string(),
+            1,
+            "Acme opened an office in Lisbon.".to_string(),
+        );
+        record.set_embedding(vec![0.1; EMBEDDING_DIM]).unwrap();
+        record.set_sentiment(0.0);
+        store.add(record);
+
+        let ner = GazetteerNer::new()
+            .with_names(NamedEntityKind::Organization, ["Acme"])
+            .with_names(NamedEntityKind::Location, ["Lisbon"]);
+        let worker = AIEnrichmentWorker::with_config(WorkerConfig {
+            poll_interval_ms: 20,
+            ..Default::default()
+        })
+        .with_ner(Arc::new(ner));
+
+        let handle = worker.start(store.clone(), store.clone()).await;
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        handle.shutdown().await;
+
+        let entities = store.records()[0].entities();
+        let kinds: Vec<_> = entities.iter().map(|e| (e.text.as_str(), e.kind)).collect();
+        assert_eq!(kinds, [("Acme", NamedEntityKind::Organization), ("Lisbon", NamedEntityKind::Location)]);
+        assert_eq!(worker.processed_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_worker_generates_summaries() {
+        let store = Arc::new(InMemoryRecordStore::new());
+        store.add(MultimodalRecord::new(
+            "https://example.com/report".to_string(),
+            1,
+            "Quarterly revenue grew by a tenth. Costs held steady. Hiring resumes next spring.".to_string(),
+        ));
+        let summaries_before = crate::ops::telemetry::ENRICHMENT_SUMMARIES_TOTAL
+            .with_label_values(&["ok"])
+            .get();
+
+        let worker = EnrichmentPipelineBuilder::new()
+            .poll_interval_ms(20)
+            .with_summary(true, 6)
+            .build();
+        let handle = worker.start(store.clone(), store.clone()).await;
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        handle.shutdown().await;
+
+        let record = &store.records()[0];
+        assert_eq!(record.summary.as_deref(), Some("Quarterly revenue grew by a tenth."));
+        assert!(crate::ops::telemetry::ENRICHMENT_SUMMARIES_TOTAL.with_label_values(&["ok"]).get() > summaries_before);
+        // * Summarized records are not fetched again
+        assert!(store.fetch_unenriched(worker.config().enrichment_filter()).await.unwrap().is_empty());
     }
 
     #[tokio::test]
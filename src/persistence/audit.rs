@@ -0,0 +1,349 @@
+// * Extraction Quality Audit Sampling
+// * Copies a random share of the pages a crawl processes (raw HTML plus the refinery result)
+// * into an audit directory, so extraction quality can be reviewed on an unbiased sample
+// * instead of the pages someone happened to notice.
+
+use crate::refinery::RefineryResult;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use xxhash_rust::xxh64::xxh64;
+
+// * Sample file suffix
+const SAMPLE_EXTENSION: &str = "json";
+
+// * Resolution of the sampling draw: 0.0001%
+const DRAW_SCALE: u64 = 1_000_000;
+
+#[derive(Debug, Error)]
+pub enum AuditError {
+    #[error("Audit sample I/O failed: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Audit sample {path} is not valid JSON: {source}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("No audit sample {0}")]
+    NotFound(String),
+}
+
+/// Audit sampling settings
+///
+/// # Example
+/// ```toml
+/// [audit]
+/// dir = "./data/audit"
+/// sample_percent = 2.5
+/// max_samples = 200
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuditConfig {
+    /// Directory samples are written to (None disables sampling)
+    pub dir: Option<PathBuf>,
+    /// Share of processed pages sampled, in percent (0-100)
+    pub sample_percent: f64,
+    /// Samples taken per crawl before sampling stops (0 is unlimited)
+    pub max_samples: usize,
+    /// Seed of the sampling draw, to repeat a crawl's sample; None draws a new sample every crawl
+    pub seed: Option<u64>,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            sample_percent: 1.0,
+            max_samples: 500,
+            seed: None,
+        }
+    }
+}
+
+/// One sampled page as written to the audit directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditSample {
+    /// Hex ID, also the file name
+    pub id: String,
+    pub url: String,
+    pub domain: String,
+    /// Unix seconds
+    pub sampled_at: u64,
+    /// The HTML as fetched, before refining
+    pub html: String,
+    pub result: RefineryResult,
+}
+
+impl AuditSample {
+    pub fn new(url: String, domain: String, html: String, result: RefineryResult) -> Self {
+        Self {
+            id: format!("{:016x}", xxh64(url.as_bytes(), 0)),
+            url,
+            domain,
+            sampled_at: current_timestamp(),
+            html,
+            result,
+        }
+    }
+}
+
+/// Draws the crawl's sample and writes it to the audit directory
+///
+/// The draw hashes each URL with the crawl's seed, so a URL is either in or out of the
+/// sample for the whole crawl, and `should_sample` can run before the HTML is copied.
+///
+/// # Example
+/// ```ignore
+/// let sampler = AuditSampler::open(config.audit.clone())?;
+/// if sampler.should_sample(&url) {
+///     sampler.record(AuditSample::new(url, domain, html.clone(), result.clone()))?;
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AuditSampler {
+    config: AuditConfig,
+    dir: PathBuf,
+    seed: u64,
+    taken: AtomicUsize,
+}
+
+impl AuditSampler {
+    /// Creates the audit directory if needed
+    ///
+    /// ! Samples from earlier crawls in the same directory are kept; use a fresh
+    /// ! directory per crawl to audit crawls separately.
+    pub fn open(config: AuditConfig) -> Result<Self, AuditError> {
+        let dir = config
+            .dir
+            .clone()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "audit.dir is not set"))?;
+        fs::create_dir_all(&dir)?;
+        let seed = config.seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos() as u64
+        });
+        Ok(Self {
+            config,
+            dir,
+            seed,
+            taken: AtomicUsize::new(0),
+        })
+    }
+
+    pub fn config(&self) -> &AuditConfig {
+        &self.config
+    }
+
+    /// Whether `url` falls in the sample (and the sample is not full yet)
+    pub fn should_sample(&self, url: &str) -> bool {
+        let threshold = (self.config.sample_percent.clamp(0.0, 100.0) / 100.0 * DRAW_SCALE as f64) as u64;
+        !self.is_full() && xxh64(url.as_bytes(), self.seed) % DRAW_SCALE < threshold
+    }
+
+    /// Writes a sample, returning its path
+    ///
+    /// Returns None once `max_samples` samples were taken.
+    pub fn record(&self, sample: AuditSample) -> Result<Option<PathBuf>, AuditError> {
+        let max = self.config.max_samples;
+        let reserved = self
+            .taken
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |taken| {
+                (max == 0 || taken < max).then_some(taken + 1)
+            })
+            .is_ok();
+        if !reserved {
+            return Ok(None);
+        }
+
+        let path = sample_path(&self.dir, &sample.id);
+        // * Written beside the target and renamed so the review CLI never reads a partial file
+        let partial = path.with_extension("json.partial");
+        fs::write(&partial, serde_json::to_vec(&sample).map_err(io::Error::from)?)?;
+        fs::rename(&partial, &path)?;
+        Ok(Some(path))
+    }
+
+    /// Samples taken so far
+    pub fn taken(&self) -> usize {
+        self.taken.load(Ordering::SeqCst)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.config.max_samples != 0 && self.taken() >= self.config.max_samples
+    }
+}
+
+/// Loads every sample in an audit directory, oldest first
+pub fn load_samples(dir: &Path) -> Result<Vec<AuditSample>, AuditError> {
+    let mut samples = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == SAMPLE_EXTENSION) {
+            samples.push(read_sample(&path)?);
+        }
+    }
+    samples.sort_by(|a, b| a.sampled_at.cmp(&b.sampled_at).then_with(|| a.id.cmp(&b.id)));
+    Ok(samples)
+}
+
+/// Loads one sample by ID
+pub fn load_sample(dir: &Path, id: &str) -> Result<AuditSample, AuditError> {
+    let path = sample_path(dir, id);
+    if !path.exists() {
+        return Err(AuditError::NotFound(id.to_string()));
+    }
+    read_sample(&path)
+}
+
+fn read_sample(path: &Path) -> Result<AuditSample, AuditError> {
+    serde_json::from_slice(&fs::read(path)?).map_err(|source| AuditError::Parse {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn sample_path(dir: &Path, id: &str) -> PathBuf {
+    dir.join(format!("{}.{}", id, SAMPLE_EXTENSION))
+}
+
+/// Aggregate extraction statistics over a set of samples
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AuditReport {
+    pub samples: usize,
+    pub mean_quality: f32,
+    /// Samples scoring below the report's quality threshold
+    pub low_quality: usize,
+    /// Samples where no main content area was found
+    pub without_main_content: usize,
+    /// Samples with no extracted text
+    pub empty: usize,
+    /// Samples cut short by a size or time limit
+    pub truncated: usize,
+    /// Sample IDs, quality and URL, worst first
+    pub ranked: Vec<(String, f32, String)>,
+}
+
+impl AuditReport {
+    /// Summarizes samples, flagging those scoring below `min_quality`
+    pub fn new(samples: &[AuditSample], min_quality: f32) -> Self {
+        let quality = |sample: &AuditSample| sample.result.stats.quality_score;
+        let mut ranked: Vec<(String, f32, String)> = samples
+            .iter()
+            .map(|sample| (sample.id.clone(), quality(sample), sample.url.clone()))
+            .collect();
+        ranked.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        let count = |keep: &dyn Fn(&AuditSample) -> bool| samples.iter().filter(|sample| keep(sample)).count();
+        Self {
+            samples: samples.len(),
+            mean_quality: if samples.is_empty() {
+                0.0
+            } else {
+                samples.iter().map(quality).sum::<f32>() / samples.len() as f32
+            },
+            low_quality: count(&|sample| quality(sample) < min_quality),
+            without_main_content: count(&|sample| !sample.result.stats.has_main_content),
+            empty: count(&|sample| sample.result.content.text.trim().is_empty()),
+            truncated: count(&|sample| sample.result.truncated.is_some()),
+            ranked,
+        }
+    }
+
+    /// Renders the report as a plain text table
+    pub fn render(&self) -> String {
+        let mut out = format!(
+            "{} samples, mean quality {:.2}: {} low quality, {} without main content, {} empty, {} truncated\n",
+            self.samples, self.mean_quality, self.low_quality, self.without_main_content, self.empty, self.truncated
+        );
+        for (id, quality, url) in &self.ranked {
+            out.push_str(&format!("{}  {:.2}  {}\n", id, quality, url));
+        }
+        out
+    }
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::refinery::Refinery;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("titan-audit-{}-{}", name, std::process::id()))
+    }
+
+    fn config(dir: &Path, sample_percent: f64, max_samples: usize) -> AuditConfig {
+        AuditConfig {
+            dir: Some(dir.to_path_buf()),
+            sample_percent,
+            max_samples,
+            seed: Some(7),
+        }
+    }
+
+    #[test]
+    fn test_draw_matches_the_sample_rate() {
+        let dir = temp_dir("draw");
+        let sampler = AuditSampler::open(config(&dir, 10.0, 0)).unwrap();
+        let urls: Vec<String> = (0..10_000).map(|i| format!("https://example.com/p/{}", i)).collect();
+        let drawn = urls.iter().filter(|url| sampler.should_sample(url)).count();
+        assert!((800..1200).contains(&drawn), "drew {}", drawn);
+
+        // * The same seed draws the same sample; 0% and 100% are exact
+        let again = AuditSampler::open(config(&dir, 10.0, 0)).unwrap();
+        assert!(urls.iter().all(|url| sampler.should_sample(url) == again.should_sample(url)));
+        assert!(!AuditSampler::open(config(&dir, 0.0, 0)).unwrap().should_sample(&urls[0]));
+        assert!(urls.iter().all(|url| AuditSampler::open(config(&dir, 100.0, 0)).unwrap().should_sample(url)));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_samples_round_trip_and_report() {
+        let dir = temp_dir("roundtrip");
+        let sampler = AuditSampler::open(config(&dir, 100.0, 2)).unwrap();
+        let article = format!(
+            "<html><body><article><h1>Tides</h1><p>{}</p></article></body></html>",
+            "The spring tides reached the harbor wall twice this week, flooding the lower quay. ".repeat(4)
+        );
+        let pages = [("https://a.example/tides", article.as_str()), ("https://a.example/empty", "<html></html>")];
+        for (url, html) in pages {
+            let result = Refinery::new().process(html);
+            let sample = AuditSample::new(url.to_string(), "a.example".to_string(), html.to_string(), result);
+            assert!(sampler.record(sample).unwrap().is_some());
+        }
+        let extra = AuditSample::new("https://a.example/x".into(), "a.example".into(), String::new(), Default::default());
+        assert_eq!(sampler.record(extra).unwrap(), None);
+        assert!(sampler.is_full());
+        assert!(!sampler.should_sample("https://a.example/y"));
+
+        let samples = load_samples(&dir).unwrap();
+        assert_eq!(samples.len(), 2);
+        let tides = load_sample(&dir, &samples.iter().find(|s| s.url.ends_with("tides")).unwrap().id).unwrap();
+        assert_eq!(tides.html, article);
+        assert!(tides.result.content.text.contains("spring tides"));
+        assert!(matches!(load_sample(&dir, "0000000000000000"), Err(AuditError::NotFound(_))));
+
+        let report = AuditReport::new(&samples, 0.4);
+        assert_eq!(report.samples, 2);
+        assert_eq!(report.empty, 1);
+        assert_eq!(report.low_quality, 1);
+        assert_eq!(report.ranked[0].2, "https://a.example/empty");
+        assert!(report.render().starts_with("2 samples"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
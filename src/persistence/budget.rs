@@ -0,0 +1,321 @@
+// * [FR-09] Enrichment Rate Limiting and Cost Budget
+// * Throttles calls to paid enrichment providers (requests and tokens per minute) and stops
+// * enrichment for the rest of the UTC day once the daily spend reaches its budget.
+
+use super::ai_worker::EnrichmentError;
+use crate::ops::{self, AlertManager, AlertType};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::time::{Duration, Instant};
+
+// * Rate limits are enforced over fixed one-minute windows
+const WINDOW: Duration = Duration::from_secs(60);
+const SECONDS_PER_DAY: u64 = 86_400;
+
+// * Rough tokens-per-character ratio for English text (about 4 characters per token)
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Limits on enrichment provider usage; 0 disables a limit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnrichmentBudgetConfig {
+    /// Provider calls allowed per minute
+    pub requests_per_minute: u32,
+    /// Tokens allowed per minute (estimated when a call starts, settled to the billed count)
+    pub tokens_per_minute: u32,
+    /// Spend allowed per UTC day, in USD
+    pub daily_budget_usd: f64,
+    /// Price of one provider call, in USD
+    pub cost_per_request_usd: f64,
+    /// Price of 1000 tokens, prompt or completion, in USD
+    pub cost_per_1k_tokens_usd: f64,
+}
+
+impl Default for EnrichmentBudgetConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_minute: 0,
+            tokens_per_minute: 0,
+            daily_budget_usd: 0.0,
+            cost_per_request_usd: 0.0,
+            cost_per_1k_tokens_usd: 0.0,
+        }
+    }
+}
+
+impl EnrichmentBudgetConfig {
+    /// Estimated price of one call sending `tokens` tokens
+    pub fn call_cost(&self, tokens: usize) -> f64 {
+        self.cost_per_request_usd + self.cost_per_1k_tokens_usd * tokens as f64 / 1000.0
+    }
+}
+
+/// Shared rate limiter and spend tracker for enrichment provider calls
+///
+/// # Example
+/// ```ignore
+/// let budget = EnrichmentBudget::new(EnrichmentBudgetConfig {
+///     requests_per_minute: 500,
+///     daily_budget_usd: 50.0,
+///     cost_per_1k_tokens_usd: 0.0001,
+///     ..Default::default()
+/// });
+/// budget.acquire(estimate_tokens(text)).await?;
+/// ```
+#[derive(Debug)]
+pub struct EnrichmentBudget {
+    config: EnrichmentBudgetConfig,
+    state: Mutex<BudgetState>,
+    alerts: Option<Arc<AlertManager>>,
+}
+
+#[derive(Debug)]
+struct BudgetState {
+    window_start: Instant,
+    window_requests: u32,
+    window_tokens: u64,
+    day: u64,
+    spent_usd: f64,
+}
+
+impl EnrichmentBudget {
+    pub fn new(config: EnrichmentBudgetConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(BudgetState {
+                window_start: Instant::now(),
+                window_requests: 0,
+                window_tokens: 0,
+                day: utc_day(),
+                spent_usd: 0.0,
+            }),
+            alerts: None,
+        }
+    }
+
+    /// Fires a SEV-3 alert when the daily budget runs out (resolved the next day)
+    pub fn with_alert_manager(mut self, alerts: Arc<AlertManager>) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
+    pub fn config(&self) -> &EnrichmentBudgetConfig {
+        &self.config
+    }
+
+    /// Spend recorded so far today, in USD
+    pub fn spent_today(&self) -> f64 {
+        let mut state = self.state.lock().unwrap();
+        self.roll_day(&mut state, utc_day());
+        state.spent_usd
+    }
+
+    /// Returns true once today's spend has reached the daily budget
+    pub fn is_exhausted(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        self.roll_day(&mut state, utc_day());
+        self.over_budget(&state)
+    }
+
+    /// Reserves one provider call sending `tokens` tokens
+    ///
+    /// Waits for the next minute when a rate limit is reached and fails with
+    /// `BudgetExhausted` once the daily budget is spent.
+    pub async fn acquire(&self, tokens: usize) -> Result<(), EnrichmentError> {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap();
+                self.roll_day(&mut state, utc_day());
+                if self.over_budget(&state) {
+                    return Err(EnrichmentError::BudgetExhausted(self.config.daily_budget_usd));
+                }
+
+                if state.window_start.elapsed() >= WINDOW {
+                    state.window_start = Instant::now();
+                    state.window_requests = 0;
+                    state.window_tokens = 0;
+                }
+                match self.rate_limited(&state, tokens) {
+                    Some(limit) => {
+                        ops::record_enrichment_throttle(limit);
+                        WINDOW.saturating_sub(state.window_start.elapsed())
+                    }
+                    None => {
+                        state.window_requests += 1;
+                        state.window_tokens += tokens as u64;
+                        state.spent_usd += self.config.call_cost(tokens);
+                        ops::set_enrichment_spend(state.spent_usd);
+                        if self.over_budget(&state) {
+                            self.fire_exhausted_alert(state.spent_usd);
+                        }
+                        return Ok(());
+                    }
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Reconciles a call reserved with `estimated` tokens against the `billed` tokens
+    /// the provider reported (prompt plus completion)
+    ///
+    /// Adjusts the minute's token count and today's spend by the difference; a call
+    /// that turns out larger than estimated can exhaust the budget after the fact.
+    pub fn settle(&self, estimated: usize, billed: usize) {
+        if billed == estimated {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        self.roll_day(&mut state, utc_day());
+        let was_over = self.over_budget(&state);
+        state.window_tokens = (state.window_tokens + billed as u64).saturating_sub(estimated as u64);
+        let delta = self.config.cost_per_1k_tokens_usd * (billed as f64 - estimated as f64) / 1000.0;
+        state.spent_usd = (state.spent_usd + delta).max(0.0);
+        ops::set_enrichment_spend(state.spent_usd);
+        if !was_over && self.over_budget(&state) {
+            self.fire_exhausted_alert(state.spent_usd);
+        }
+    }
+
+    // * Names the per-minute limit this call would exceed; an oversized call still runs alone
+    fn rate_limited(&self, state: &BudgetState, tokens: usize) -> Option<&'static str> {
+        let rpm = self.config.requests_per_minute;
+        if rpm > 0 && state.window_requests >= rpm {
+            return Some("requests");
+        }
+        let tpm = u64::from(self.config.tokens_per_minute);
+        if tpm > 0 && state.window_requests > 0 && state.window_tokens + tokens as u64 > tpm {
+            return Some("tokens");
+        }
+        None
+    }
+
+    fn over_budget(&self, state: &BudgetState) -> bool {
+        self.config.daily_budget_usd > 0.0 && state.spent_usd >= self.config.daily_budget_usd
+    }
+
+    // * A new UTC day resets the spend and lifts the pause
+    fn roll_day(&self, state: &mut BudgetState, today: u64) {
+        if state.day == today {
+            return;
+        }
+        state.day = today;
+        state.spent_usd = 0.0;
+        ops::set_enrichment_spend(0.0);
+        if let Some(alerts) = &self.alerts {
+            alerts.resolve_alert(&AlertType::EnrichmentBudgetExhausted);
+        }
+    }
+
+    fn fire_exhausted_alert(&self, spent_usd: f64) {
+        tracing::warn!(
+            spent_usd = spent_usd,
+            budget_usd = self.config.daily_budget_usd,
+            "Enrichment budget exhausted, pausing until the next UTC day"
+        );
+        if let Some(alerts) = &self.alerts {
+            alerts.fire_enrichment_budget_alert(spent_usd, self.config.daily_budget_usd);
+        }
+    }
+
+    #[cfg(test)]
+    fn set_day(&self, day: u64) {
+        self.state.lock().unwrap().day = day;
+    }
+}
+
+/// Estimates how many tokens a provider will bill for `text`
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+fn utc_day() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        / SECONDS_PER_DAY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_requests_per_minute_waits_for_next_window() {
+        let budget = EnrichmentBudget::new(EnrichmentBudgetConfig {
+            requests_per_minute: 2,
+            ..Default::default()
+        });
+        let started = Instant::now();
+        for _ in 0..3 {
+            budget.acquire(10).await.unwrap();
+        }
+        assert!(started.elapsed() >= WINDOW);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_tokens_per_minute() {
+        let budget = EnrichmentBudget::new(EnrichmentBudgetConfig {
+            tokens_per_minute: 100,
+            ..Default::default()
+        });
+        let started = Instant::now();
+        // * A single oversized call is let through rather than blocked forever
+        budget.acquire(500).await.unwrap();
+        budget.acquire(50).await.unwrap();
+        assert!(started.elapsed() >= WINDOW);
+    }
+
+    #[tokio::test]
+    async fn test_daily_budget_pauses_and_alerts() {
+        let alerts = Arc::new(AlertManager::new());
+        let budget = EnrichmentBudget::new(EnrichmentBudgetConfig {
+            daily_budget_usd: 1.0,
+            cost_per_1k_tokens_usd: 0.5,
+            ..Default::default()
+        })
+        .with_alert_manager(Arc::clone(&alerts));
+
+        budget.acquire(1000).await.unwrap();
+        assert!(!budget.is_exhausted());
+        budget.acquire(1000).await.unwrap();
+        assert!(budget.is_exhausted());
+        assert!(alerts.is_active(&AlertType::EnrichmentBudgetExhausted));
+        assert!(matches!(budget.acquire(1).await, Err(EnrichmentError::BudgetExhausted(_))));
+
+        // * The next UTC day starts a fresh budget
+        budget.set_day(utc_day() - 1);
+        assert!(!budget.is_exhausted());
+        assert_eq!(budget.spent_today(), 0.0);
+        assert!(!alerts.is_active(&AlertType::EnrichmentBudgetExhausted));
+    }
+
+    #[tokio::test]
+    async fn test_settle_reconciles_billed_tokens() {
+        let budget = EnrichmentBudget::new(EnrichmentBudgetConfig {
+            daily_budget_usd: 1.0,
+            cost_per_1k_tokens_usd: 0.5,
+            ..Default::default()
+        });
+        budget.acquire(1000).await.unwrap();
+        // * Over-estimated: the difference is refunded
+        budget.settle(1000, 400);
+        assert!((budget.spent_today() - 0.2).abs() < 1e-9);
+        assert_eq!(budget.state.lock().unwrap().window_tokens, 400);
+
+        // * A long completion pushes the day over budget
+        budget.acquire(100).await.unwrap();
+        budget.settle(100, 1700);
+        assert!((budget.spent_today() - 1.05).abs() < 1e-9);
+        assert!(budget.is_exhausted());
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+}
@@ -0,0 +1,370 @@
+// * [FR-05] Content Change Tracking
+// * Compares each crawled page against the version seen for the same URL on earlier
+// * crawls, classifies the change, keeps a revision counter, and broadcasts change events
+
+use super::dedup::MinHashSignature;
+use super::structural_hash::ContentHashMode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
+
+// * Pages at least this similar to their previous version count as minor edits
+const DEFAULT_MINOR_EDIT_THRESHOLD: f64 = 0.8;
+
+// * Events buffered per subscriber before slow subscribers start lagging
+const DEFAULT_EVENT_CAPACITY: usize = 1024;
+
+/// How a page differs from its previous version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// First time the URL was seen
+    New,
+    /// Identical content hash
+    Unchanged,
+    /// Content changed but stays above the minor edit similarity threshold
+    MinorEdit,
+    /// Content changed beyond the minor edit threshold
+    MajorRewrite,
+}
+
+impl ChangeKind {
+    /// Returns the Prometheus label for this kind
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::New => "new",
+            ChangeKind::Unchanged => "unchanged",
+            ChangeKind::MinorEdit => "minor_edit",
+            ChangeKind::MajorRewrite => "major_rewrite",
+        }
+    }
+
+    /// Returns true when the page content differs from the stored version
+    pub fn is_change(&self) -> bool {
+        matches!(self, ChangeKind::MinorEdit | ChangeKind::MajorRewrite)
+    }
+}
+
+/// Result of comparing a page with its previous version
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    pub url: String,
+    pub kind: ChangeKind,
+    /// Revision after this observation (1 for new pages, bumped on every change)
+    pub revision: u32,
+    pub previous_hash: Option<u64>,
+    /// Hash the tracker compared (the structural hash in `ContentHashMode::Structural`)
+    pub content_hash: u64,
+    /// Estimated Jaccard similarity to the previous version (1.0 when unchanged, 0.0 when new)
+    pub similarity: f64,
+    pub detected_at: u64,
+}
+
+/// Stored version of a single URL
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageVersion {
+    pub content_hash: u64,
+    pub signature: Vec<u64>,
+    pub revision: u32,
+    pub first_seen: u64,
+    pub last_changed: u64,
+    pub last_seen: u64,
+}
+
+/// Serializable tracker state, persisted between crawls
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChangeTrackerSnapshot {
+    pub versions: HashMap<String, PageVersion>,
+}
+
+/// Thresholds for change classification
+#[derive(Debug, Clone)]
+pub struct ChangeTrackerConfig {
+    /// Minimum similarity for a change to count as a minor edit
+    pub minor_edit_threshold: f64,
+    /// Events buffered per subscriber
+    pub event_capacity: usize,
+    /// What is compared between versions
+    ///
+    /// ! Stored versions hold hashes of the mode they were taken with; switching modes
+    /// ! against an earlier snapshot reports every page as changed once.
+    pub hash_mode: ContentHashMode,
+}
+
+impl Default for ChangeTrackerConfig {
+    fn default() -> Self {
+        Self {
+            minor_edit_threshold: DEFAULT_MINOR_EDIT_THRESHOLD,
+            event_capacity: DEFAULT_EVENT_CAPACITY,
+            hash_mode: ContentHashMode::default(),
+        }
+    }
+}
+
+/// Per-URL content version tracker
+///
+/// # Example
+/// ```ignore
+/// let tracker = ChangeTracker::new();
+/// let mut events = tracker.subscribe();
+/// tracker.observe(&record.url, record.content_hash, &record.text_content);
+/// while let Ok(event) = events.recv().await {
+///     println!("{} is now at revision {}", event.url, event.revision);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ChangeTracker {
+    config: ChangeTrackerConfig,
+    versions: RwLock<HashMap<String, PageVersion>>,
+    events: broadcast::Sender<ChangeEvent>,
+}
+
+impl ChangeTracker {
+    /// Creates an empty tracker with default thresholds
+    pub fn new() -> Self {
+        Self::with_config(ChangeTrackerConfig::default())
+    }
+
+    /// Creates an empty tracker with custom thresholds
+    pub fn with_config(config: ChangeTrackerConfig) -> Self {
+        let (events, _) = broadcast::channel(config.event_capacity.max(1));
+        Self {
+            config,
+            versions: RwLock::new(HashMap::new()),
+            events,
+        }
+    }
+
+    /// Subscribes to events for new and changed pages (unchanged pages are not broadcast)
+    pub fn subscribe(&self) -> broadcast::Receiver<ChangeEvent> {
+        self.events.subscribe()
+    }
+
+    /// Compares a page with its stored version, records it, and broadcasts the change
+    ///
+    /// In structural mode `content_hash` is replaced by the hash of the text's stable regions.
+    pub fn observe(&self, url: &str, content_hash: u64, text: &str) -> ChangeEvent {
+        let (content_hash, text) = self.config.hash_mode.comparable(content_hash, text);
+        let text = text.as_ref();
+        let now = current_timestamp();
+        let mut versions = self.versions.write().unwrap();
+
+        let event = match versions.get_mut(url) {
+            None => {
+                let signature = MinHashSignature::from_text(text, url.to_string());
+                versions.insert(
+                    url.to_string(),
+                    PageVersion {
+                        content_hash,
+                        signature: signature.signature,
+                        revision: 1,
+                        first_seen: now,
+                        last_changed: now,
+                        last_seen: now,
+                    },
+                );
+                ChangeEvent {
+                    url: url.to_string(),
+                    kind: ChangeKind::New,
+                    revision: 1,
+                    previous_hash: None,
+                    content_hash,
+                    similarity: 0.0,
+                    detected_at: now,
+                }
+            }
+            Some(version) if version.content_hash == content_hash => {
+                version.last_seen = now;
+                ChangeEvent {
+                    url: url.to_string(),
+                    kind: ChangeKind::Unchanged,
+                    revision: version.revision,
+                    previous_hash: Some(content_hash),
+                    content_hash,
+                    similarity: 1.0,
+                    detected_at: now,
+                }
+            }
+            Some(version) => {
+                let signature = MinHashSignature::from_text(text, url.to_string());
+                let previous = MinHashSignature {
+                    signature: std::mem::take(&mut version.signature),
+                    document_id: url.to_string(),
+                };
+                let similarity = signature.jaccard_similarity(&previous);
+                let kind = if similarity >= self.config.minor_edit_threshold {
+                    ChangeKind::MinorEdit
+                } else {
+                    ChangeKind::MajorRewrite
+                };
+                let previous_hash = version.content_hash;
+
+                version.content_hash = content_hash;
+                version.signature = signature.signature;
+                version.revision += 1;
+                version.last_changed = now;
+                version.last_seen = now;
+                ChangeEvent {
+                    url: url.to_string(),
+                    kind,
+                    revision: version.revision,
+                    previous_hash: Some(previous_hash),
+                    content_hash,
+                    similarity,
+                    detected_at: now,
+                }
+            }
+        };
+        drop(versions);
+
+        if event.kind != ChangeKind::Unchanged {
+            // * Sending only fails without subscribers
+            let _ = self.events.send(event.clone());
+        }
+        event
+    }
+
+    /// Returns the stored version of a URL
+    pub fn version(&self, url: &str) -> Option<PageVersion> {
+        self.versions.read().unwrap().get(url).cloned()
+    }
+
+    /// Returns the current revision of a URL
+    pub fn revision(&self, url: &str) -> Option<u32> {
+        self.versions.read().unwrap().get(url).map(|v| v.revision)
+    }
+
+    /// Returns the number of tracked URLs
+    pub fn len(&self) -> usize {
+        self.versions.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Captures all stored versions
+    pub fn snapshot(&self) -> ChangeTrackerSnapshot {
+        ChangeTrackerSnapshot {
+            versions: self.versions.read().unwrap().clone(),
+        }
+    }
+
+    /// Replaces the stored versions with a snapshot from an earlier crawl
+    pub fn restore(&self, snapshot: &ChangeTrackerSnapshot) {
+        *self.versions.write().unwrap() = snapshot.versions.clone();
+    }
+}
+
+impl Default for ChangeTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::schema::MultimodalRecord;
+    use crate::persistence::structural_hash::structural_hash;
+
+    const ORIGINAL: &str = "The city council approved the new budget on Tuesday after a long debate \
+        about road repairs, school funding, library hours and the proposed expansion of the \
+        riverside park along the eastern bank.";
+
+    #[test]
+    fn test_new_and_unchanged() {
+        let tracker = ChangeTracker::new();
+
+        let event = tracker.observe("https://news.example.com/a", 1, ORIGINAL);
+        assert_eq!(event.kind, ChangeKind::New);
+        assert_eq!(event.revision, 1);
+
+        let event = tracker.observe("https://news.example.com/a", 1, ORIGINAL);
+        assert_eq!(event.kind, ChangeKind::Unchanged);
+        assert_eq!(event.revision, 1);
+        assert_eq!(tracker.len(), 1);
+    }
+
+    #[test]
+    fn test_minor_edit_and_major_rewrite() {
+        let tracker = ChangeTracker::new();
+        tracker.observe("https://news.example.com/a", 1, ORIGINAL);
+
+        let edited = ORIGINAL.replace("eastern bank", "eastern shore");
+        let event = tracker.observe("https://news.example.com/a", 2, &edited);
+        assert_eq!(event.kind, ChangeKind::MinorEdit);
+        assert_eq!(event.revision, 2);
+        assert_eq!(event.previous_hash, Some(1));
+        assert!(event.similarity >= DEFAULT_MINOR_EDIT_THRESHOLD);
+
+        let rewritten = "Heavy snowfall closed mountain passes overnight, stranding hikers \
+            and delaying freight trains across the northern valleys until plows arrived.";
+        let event = tracker.observe("https://news.example.com/a", 3, rewritten);
+        assert_eq!(event.kind, ChangeKind::MajorRewrite);
+        assert_eq!(event.revision, 3);
+        assert_eq!(tracker.revision("https://news.example.com/a"), Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_changes() {
+        let tracker = ChangeTracker::new();
+        let mut events = tracker.subscribe();
+
+        tracker.observe("https://news.example.com/a", 1, ORIGINAL);
+        tracker.observe("https://news.example.com/a", 1, ORIGINAL);
+        tracker.observe("https://news.example.com/a", 2, &ORIGINAL.replace("long", "short"));
+
+        assert_eq!(events.recv().await.unwrap().kind, ChangeKind::New);
+        // * Unchanged observations are not broadcast
+        assert_eq!(events.recv().await.unwrap().kind, ChangeKind::MinorEdit);
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let tracker = ChangeTracker::new();
+        tracker.observe("https://news.example.com/a", 1, ORIGINAL);
+        tracker.observe("https://news.example.com/a", 2, "Completely different text here");
+
+        let json = serde_json::to_string(&tracker.snapshot()).unwrap();
+        let restored = ChangeTracker::new();
+        restored.restore(&serde_json::from_str(&json).unwrap());
+
+        assert_eq!(restored.revision("https://news.example.com/a"), Some(2));
+        assert_eq!(
+            restored.observe("https://news.example.com/a", 2, "Completely different text here").kind,
+            ChangeKind::Unchanged
+        );
+    }
+
+    #[test]
+    fn test_structural_mode_ignores_rotating_widgets() {
+        let tracker = ChangeTracker::with_config(ChangeTrackerConfig {
+            hash_mode: ContentHashMode::Structural,
+            ..Default::default()
+        });
+        let url = "https://news.example.com/a";
+        let first = format!("Posted 5 minutes ago\n\n{ORIGINAL}\n\nMost read\n\n• Bridge closed");
+        let later = format!("Posted 3 hours ago\n\n{ORIGINAL}\n\nMost read\n\n• Zoo reopens");
+
+        tracker.observe(url, MultimodalRecord::hash_content(&first), &first);
+        let event = tracker.observe(url, MultimodalRecord::hash_content(&later), &later);
+        assert_eq!(event.kind, ChangeKind::Unchanged);
+        assert_eq!(event.content_hash, structural_hash(ORIGINAL));
+
+        // * The same pages in full mode count as an edit
+        let full = ChangeTracker::new();
+        full.observe(url, MultimodalRecord::hash_content(&first), &first);
+        assert!(full.observe(url, MultimodalRecord::hash_content(&later), &later).kind.is_change());
+    }
+}
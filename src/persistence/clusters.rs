@@ -0,0 +1,282 @@
+// * [FR-05] Near-Duplicate Clusters
+// * Groups the pages the dedup index matched against each other (syndicated articles carried
+// * by several outlets) and periodically exports them with a representative per cluster, so
+// * downstream ranking can treat a cluster as one story.
+
+use super::dedup::DedupManager;
+use super::export::{export_clusters_jsonl, ExportError};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+use tokio::time::Duration;
+
+/// Cluster report settings (`[crawl.clusters]`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClusterReportConfig {
+    /// Write the cluster report during crawls (needs `path`)
+    pub enabled: bool,
+    /// JSONL file the report is written to, one cluster per line
+    pub path: Option<PathBuf>,
+    /// Seconds between reports; a final one is written when the crawl ends
+    pub interval_secs: u64,
+    /// Smallest cluster reported, counting the original
+    pub min_size: usize,
+}
+
+impl Default for ClusterReportConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: None,
+            interval_secs: 3600,
+            min_size: 2,
+        }
+    }
+}
+
+/// One page of a duplicate cluster
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClusterMember {
+    pub document_id: String,
+    pub url: String,
+    /// Estimated Jaccard similarity to the original (1.0 for the original and exact copies)
+    pub similarity: f64,
+    /// Length of the page's text in bytes
+    pub text_len: usize,
+}
+
+/// Pages the dedup index matched against one indexed original
+///
+/// Only the original is stored by the crawler; the other members were dropped as duplicates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateCluster {
+    /// Document ID of the indexed page the others matched
+    pub original_id: String,
+    /// The original first (when its origin is known), then duplicates in the order they were seen
+    pub members: Vec<ClusterMember>,
+}
+
+impl DuplicateCluster {
+    /// The member with the longest text (the fullest copy of a syndicated story); ties go
+    /// to the earliest member, so the original wins against equally long copies
+    pub fn representative(&self) -> Option<&ClusterMember> {
+        self.members
+            .iter()
+            .reduce(|best, member| if member.text_len > best.text_len { member } else { best })
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn contains(&self, document_id: &str) -> bool {
+        self.members.iter().any(|member| member.document_id == document_id)
+    }
+}
+
+/// A cluster as exported, with its representative resolved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClusterEntry {
+    pub representative_id: String,
+    #[serde(flatten)]
+    pub cluster: DuplicateCluster,
+}
+
+/// All clusters of at least `min_size` members at one point in time
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClusterReport {
+    /// Unix seconds
+    pub generated_at: u64,
+    /// Largest clusters first
+    pub clusters: Vec<ClusterEntry>,
+}
+
+impl ClusterReport {
+    /// Builds a report from clusters, dropping those smaller than `min_size`
+    pub fn new(clusters: Vec<DuplicateCluster>, min_size: usize) -> Self {
+        let mut clusters: Vec<ClusterEntry> = clusters
+            .into_iter()
+            .filter(|cluster| cluster.len() >= min_size.max(1))
+            .filter_map(|cluster| {
+                Some(ClusterEntry {
+                    representative_id: cluster.representative()?.document_id.clone(),
+                    cluster,
+                })
+            })
+            .collect();
+        clusters.sort_by(|a, b| {
+            b.cluster
+                .len()
+                .cmp(&a.cluster.len())
+                .then_with(|| a.cluster.original_id.cmp(&b.cluster.original_id))
+        });
+        Self {
+            generated_at: current_timestamp(),
+            clusters,
+        }
+    }
+
+    /// Pages covered by the reported clusters
+    pub fn member_count(&self) -> usize {
+        self.clusters.iter().map(|entry| entry.cluster.len()).sum()
+    }
+}
+
+/// Periodically exports the dedup index's clusters
+///
+/// # Example
+/// ```ignore
+/// let reporter = ClusterReporter::new(config.clusters.clone(), Arc::clone(&dedup));
+/// let handle = Arc::new(reporter).spawn();
+/// ```
+pub struct ClusterReporter {
+    config: ClusterReportConfig,
+    dedup: Arc<Mutex<DedupManager>>,
+}
+
+impl ClusterReporter {
+    pub fn new(config: ClusterReportConfig, dedup: Arc<Mutex<DedupManager>>) -> Self {
+        Self { config, dedup }
+    }
+
+    pub fn config(&self) -> &ClusterReportConfig {
+        &self.config
+    }
+
+    /// Builds the current report
+    pub fn report(&self) -> ClusterReport {
+        let clusters = self.dedup.lock().unwrap().clusters();
+        ClusterReport::new(clusters, self.config.min_size)
+    }
+
+    /// Writes the current report to `path`, replacing the previous one
+    ///
+    /// Returns the report; nothing is written without a path.
+    pub fn run_once(&self) -> Result<ClusterReport, ExportError> {
+        let report = self.report();
+        if let Some(path) = &self.config.path {
+            // * Written beside the target and renamed so readers never see a partial file
+            let partial = path.with_extension("jsonl.partial");
+            export_clusters_jsonl(&report, BufWriter::new(File::create(&partial)?))?;
+            std::fs::rename(&partial, path)?;
+        }
+        Ok(report)
+    }
+
+    /// Spawns the report loop; the first report is written after one interval
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let period = Duration::from_secs(self.config.interval_secs.max(1));
+            let mut tick = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            loop {
+                tick.tick().await;
+                self.write_logged();
+            }
+        })
+    }
+
+    /// Runs once, logging the outcome instead of returning it
+    pub fn write_logged(&self) {
+        match self.run_once() {
+            Ok(report) => tracing::info!(
+                clusters = report.clusters.len(),
+                pages = report.member_count(),
+                "Wrote near-duplicate cluster report"
+            ),
+            Err(e) => tracing::warn!(error = %e, "Failed to write cluster report"),
+        }
+    }
+}
+
+impl std::fmt::Debug for ClusterReporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClusterReporter").field("config", &self.config).finish()
+    }
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STORY: &str = "The city council approved the new harbor budget on Tuesday after a long debate \
+        about dredging costs, ferry schedules and the future of the old fish market on the waterfront.";
+
+    fn member(id: &str, text_len: usize) -> ClusterMember {
+        ClusterMember {
+            document_id: id.to_string(),
+            url: format!("https://{}.example/story", id),
+            similarity: 1.0,
+            text_len,
+        }
+    }
+
+    #[test]
+    fn test_representative_is_the_fullest_copy() {
+        let cluster = DuplicateCluster {
+            original_id: "a".to_string(),
+            members: vec![member("a", 900), member("b", 1200), member("c", 1200)],
+        };
+        assert_eq!(cluster.representative().unwrap().document_id, "b");
+
+        let small = DuplicateCluster {
+            original_id: "z".to_string(),
+            members: vec![member("z", 10)],
+        };
+        let report = ClusterReport::new(vec![small, cluster], 2);
+        assert_eq!(report.clusters.len(), 1);
+        assert_eq!(report.clusters[0].representative_id, "b");
+        assert_eq!(report.member_count(), 3);
+    }
+
+    #[test]
+    fn test_reporter_writes_syndicated_clusters() {
+        let dedup = Arc::new(Mutex::new(DedupManager::new()));
+        {
+            let mut dedup = dedup.lock().unwrap();
+            dedup.check_and_index("https://wire.example/harbor", 1, STORY, "wire");
+            let copy = format!("{} Staff.", STORY);
+            assert!(dedup.check_and_index("https://daily.example/harbor", 2, &copy, "daily").is_duplicate());
+            assert!(dedup.check_and_index("https://weekly.example/harbor", 1, STORY, "weekly").is_duplicate());
+        }
+
+        let dir = std::env::temp_dir().join(format!("titan-clusters-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("clusters.jsonl");
+        let reporter = ClusterReporter::new(
+            ClusterReportConfig {
+                path: Some(path.clone()),
+                ..Default::default()
+            },
+            dedup,
+        );
+
+        let report = reporter.run_once().unwrap();
+        assert_eq!(report.clusters.len(), 1);
+        let entry = &report.clusters[0];
+        assert_eq!(entry.cluster.original_id, "wire");
+        assert_eq!(entry.representative_id, "daily");
+        let ids: Vec<&str> = entry.cluster.members.iter().map(|m| m.document_id.as_str()).collect();
+        assert_eq!(ids, ["wire", "daily", "weekly"]);
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        let line: ClusterEntry = serde_json::from_str(written.lines().next().unwrap()).unwrap();
+        assert_eq!(&line, entry);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,8 +1,15 @@
 // * [FR-05] Deduplication with LSHBloom MinHash
 // * Implements near-duplicate detection using MinHash signatures and LSH banding
 
-use std::collections::{HashMap, HashSet};
+use super::clusters::{ClusterMember, DuplicateCluster};
+use crate::ops;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::hash::{Hash, Hasher};
+use std::mem::size_of;
+use std::time::{Duration, Instant};
+use xxhash_rust::xxh64::xxh64;
 
 // * LSH configuration constants
 const NUM_HASH_FUNCTIONS: usize = 100;
@@ -13,6 +20,65 @@ const JACCARD_THRESHOLD: f64 = 0.85;
 // * Shingle size for text tokenization
 const SHINGLE_SIZE: usize = 3;
 
+// * MinHash family: h_i(x) = (a_i * x + b_i) mod p
+const LARGE_PRIME: u64 = 4_294_967_311; // * Prime larger than u32::MAX
+const COEFF_A: [u64; NUM_HASH_FUNCTIONS] = hash_coefficients(0xBF58476D1CE4E5B9);
+const COEFF_B: [u64; NUM_HASH_FUNCTIONS] = hash_coefficients(0x94D049BB133111EB);
+
+// * Hash functions evaluated together per shingle (four bands' worth)
+const HASH_BLOCK: usize = ROWS_PER_BAND * 4;
+
+// * Below this many shingles, spawning threads costs more than it saves
+const PARALLEL_MIN_SHINGLES: usize = 16_384;
+
+/// How MinHash signatures are computed; every strategy yields identical signatures
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MinHashStrategy {
+    /// One hash function at a time over all shingles
+    Serial,
+    /// Blocks of hash functions per shingle, in a loop the compiler can vectorize
+    #[default]
+    Blocked,
+    /// Blocked, with shingles split across threads (falls back to blocked for short texts)
+    Parallel,
+}
+
+/// Deduplication settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DedupConfig {
+    /// MinHash signature computation strategy
+    pub minhash: MinHashStrategy,
+    /// Most documents kept in the LSH index before the least recently used is evicted (0 = unbounded)
+    pub max_documents: usize,
+    /// Evict LSH index documents idle for this many seconds (0 = never)
+    pub ttl_secs: u64,
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self {
+            minhash: MinHashStrategy::default(),
+            max_documents: 1_000_000,
+            ttl_secs: 0,
+        }
+    }
+}
+
+impl DedupConfig {
+    /// Builds an empty LSH index with these settings
+    fn lsh_index(&self) -> LSHIndex {
+        let index = LSHIndex::new()
+            .with_strategy(self.minhash)
+            .with_max_documents(self.max_documents);
+        match self.ttl_secs {
+            0 => index,
+            secs => index.with_ttl(Duration::from_secs(secs)),
+        }
+    }
+}
+
 /// MinHash signature for a document
 #[derive(Debug, Clone)]
 pub struct MinHashSignature {
@@ -23,8 +89,13 @@ pub struct MinHashSignature {
 impl MinHashSignature {
     /// Computes MinHash signature from text content
     pub fn from_text(text: &str, document_id: String) -> Self {
-        let shingles = generate_shingles(text, SHINGLE_SIZE);
-        let signature = compute_minhash(&shingles);
+        Self::from_text_with(text, document_id, MinHashStrategy::default())
+    }
+
+    /// Computes MinHash signature from text content using the given strategy
+    pub fn from_text_with(text: &str, document_id: String, strategy: MinHashStrategy) -> Self {
+        let shingles: Vec<u64> = generate_shingles(text, SHINGLE_SIZE).into_iter().collect();
+        let signature = compute_minhash(&shingles, strategy);
         Self {
             signature,
             document_id,
@@ -33,32 +104,42 @@ impl MinHashSignature {
 
     /// Computes estimated Jaccard similarity between two signatures
     pub fn jaccard_similarity(&self, other: &MinHashSignature) -> f64 {
-        if self.signature.len() != other.signature.len() {
-            return 0.0;
-        }
-
-        let matches = self
-            .signature
-            .iter()
-            .zip(other.signature.iter())
-            .filter(|(a, b)| a == b)
-            .count();
-
-        matches as f64 / self.signature.len() as f64
+        similarity(&self.signature, &other.signature)
     }
 }
 
 /// LSH Index for fast near-duplicate detection
+///
+/// Document IDs are interned as 64-bit hashes; bands hold only those keys. The index
+/// can be capped by document count (least recently used first) and by idle time.
 #[derive(Debug)]
 pub struct LSHIndex {
-    // * Band -> Hash -> Document IDs
-    bands: Vec<HashMap<u64, Vec<String>>>,
-    // * Document ID -> Signature
-    signatures: HashMap<String, MinHashSignature>,
+    // * Band -> Hash -> interned document keys
+    bands: Vec<HashMap<u64, Vec<u64>>>,
+    // * Interned key -> document
+    documents: HashMap<u64, IndexedDocument>,
+    // * Recency tick -> interned key, least recently used first
+    recency: BTreeMap<u64, u64>,
+    clock: u64,
+    // * Total length of indexed document IDs, for the memory estimate
+    id_bytes: usize,
     // * Configuration
     num_bands: usize,
     rows_per_band: usize,
     threshold: f64,
+    strategy: MinHashStrategy,
+    max_documents: usize,
+    ttl: Option<Duration>,
+}
+
+/// A document held by the LSH index
+#[derive(Debug)]
+struct IndexedDocument {
+    id: String,
+    signature: Box<[u64]>,
+    // * Key into `recency`
+    tick: u64,
+    last_used: Instant,
 }
 
 impl LSHIndex {
@@ -71,136 +152,271 @@ impl LSHIndex {
     pub fn with_config(num_bands: usize, rows_per_band: usize, threshold: f64) -> Self {
         Self {
             bands: (0..num_bands).map(|_| HashMap::new()).collect(),
-            signatures: HashMap::new(),
+            documents: HashMap::new(),
+            recency: BTreeMap::new(),
+            clock: 0,
+            id_bytes: 0,
             num_bands,
             rows_per_band,
             threshold,
+            strategy: MinHashStrategy::default(),
+            max_documents: 0,
+            ttl: None,
         }
     }
 
+    /// Sets how signatures of indexed and checked documents are computed
+    pub fn with_strategy(mut self, strategy: MinHashStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Caps the index at `max_documents` (0 is unbounded), evicting the least recently used
+    pub fn with_max_documents(mut self, max_documents: usize) -> Self {
+        self.max_documents = max_documents;
+        self
+    }
+
+    /// Evicts documents neither indexed nor matched as a duplicate within `ttl`
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
     /// Indexes a document and returns true if it's a duplicate
     ///
     /// Returns `DedupResult::Duplicate` if Jaccard > threshold, `DedupResult::Unique` otherwise
     pub fn index_document(&mut self, text: &str, document_id: &str) -> DedupResult {
-        let signature = MinHashSignature::from_text(text, document_id.to_string());
-
-        // * Find candidate duplicates using LSH banding
-        let candidates = self.find_candidates(&signature);
-
-        // * Verify candidates with actual Jaccard similarity
-        for candidate_id in candidates {
-            if let Some(candidate_sig) = self.signatures.get(&candidate_id) {
-                let similarity = signature.jaccard_similarity(candidate_sig);
-                if similarity >= self.threshold {
-                    tracing::info!(
-                        document_id = document_id,
-                        duplicate_of = candidate_id,
-                        similarity = similarity,
-                        "Duplicate detected"
-                    );
-                    return DedupResult::Duplicate {
-                        original_id: candidate_id,
-                        similarity,
-                    };
-                }
-            }
+        self.evict_expired();
+        let signature = MinHashSignature::from_text_with(text, document_id.to_string(), self.strategy);
+
+        // * Find candidate duplicates using LSH banding, then verify with Jaccard similarity
+        if let Some((key, original_id, similarity)) = self.best_match(&signature) {
+            tracing::info!(
+                document_id = document_id,
+                duplicate_of = original_id,
+                similarity = similarity,
+                "Duplicate detected"
+            );
+            // * A matched original is still in use, so it moves to the back of the eviction queue
+            self.touch(key);
+            return DedupResult::Duplicate {
+                original_id,
+                similarity,
+            };
         }
 
         // * Not a duplicate, add to index
-        self.add_to_index(&signature);
-        self.signatures
-            .insert(document_id.to_string(), signature);
+        self.insert_signature(signature);
 
         DedupResult::Unique
     }
 
     /// Checks if a document is a duplicate without indexing it
     pub fn check_duplicate(&self, text: &str) -> Option<DedupResult> {
-        let signature = MinHashSignature::from_text(text, String::new());
-        let candidates = self.find_candidates(&signature);
-
-        for candidate_id in candidates {
-            if let Some(candidate_sig) = self.signatures.get(&candidate_id) {
-                let similarity = signature.jaccard_similarity(candidate_sig);
-                if similarity >= self.threshold {
-                    return Some(DedupResult::Duplicate {
-                        original_id: candidate_id,
-                        similarity,
-                    });
-                }
-            }
-        }
+        let signature = MinHashSignature::from_text_with(text, String::new(), self.strategy);
+        self.best_match(&signature)
+            .map(|(_, original_id, similarity)| DedupResult::Duplicate {
+                original_id,
+                similarity,
+            })
+    }
 
-        None
+    /// Returns the first candidate at or above the similarity threshold
+    fn best_match(&self, signature: &MinHashSignature) -> Option<(u64, String, f64)> {
+        self.find_candidates(signature).into_iter().find_map(|key| {
+            let candidate = self.documents.get(&key)?;
+            let similarity = similarity(&signature.signature, &candidate.signature);
+            (similarity >= self.threshold).then(|| (key, candidate.id.clone(), similarity))
+        })
     }
 
     /// Finds candidate duplicates using LSH banding technique
-    fn find_candidates(&self, signature: &MinHashSignature) -> HashSet<String> {
+    fn find_candidates(&self, signature: &MinHashSignature) -> HashSet<u64> {
         let mut candidates = HashSet::new();
 
         for (band_idx, band_map) in self.bands.iter().enumerate() {
-            let band_hash = self.compute_band_hash(signature, band_idx);
-            if let Some(doc_ids) = band_map.get(&band_hash) {
-                candidates.extend(doc_ids.iter().cloned());
+            let band_hash = self.compute_band_hash(&signature.signature, band_idx);
+            if let Some(keys) = band_map.get(&band_hash) {
+                candidates.extend(keys.iter().copied());
             }
         }
 
         // * Remove self if present
-        candidates.remove(&signature.document_id);
+        candidates.remove(&document_key(&signature.document_id));
         candidates
     }
 
-    /// Adds a signature to the LSH index
-    fn add_to_index(&mut self, signature: &MinHashSignature) {
-        for band_idx in 0..self.num_bands {
-            let band_hash = self.compute_band_hash(signature, band_idx);
-            self.bands[band_idx]
-                .entry(band_hash)
-                .or_default()
-                .push(signature.document_id.clone());
-        }
-    }
-
     /// Computes hash for a specific band of the signature
-    fn compute_band_hash(&self, signature: &MinHashSignature, band_idx: usize) -> u64 {
+    fn compute_band_hash(&self, signature: &[u64], band_idx: usize) -> u64 {
         let start = band_idx * self.rows_per_band;
-        let end = std::cmp::min(start + self.rows_per_band, signature.signature.len());
+        let end = std::cmp::min(start + self.rows_per_band, signature.len());
 
         let mut hasher = std::collections::hash_map::DefaultHasher::new();
-        for i in start..end {
-            signature.signature[i].hash(&mut hasher);
+        for value in &signature[start..end] {
+            value.hash(&mut hasher);
         }
         hasher.finish()
     }
 
+    /// Re-indexes a previously computed signature (used when restoring snapshots)
+    fn insert_signature(&mut self, signature: MinHashSignature) {
+        let key = document_key(&signature.document_id);
+        self.remove_key(key);
+
+        for band_idx in 0..self.num_bands {
+            let band_hash = self.compute_band_hash(&signature.signature, band_idx);
+            self.bands[band_idx].entry(band_hash).or_default().push(key);
+        }
+        let tick = self.next_tick();
+        self.recency.insert(tick, key);
+        self.id_bytes += signature.document_id.len();
+        self.documents.insert(
+            key,
+            IndexedDocument {
+                id: signature.document_id,
+                signature: signature.signature.into_boxed_slice(),
+                tick,
+                last_used: Instant::now(),
+            },
+        );
+
+        while self.max_documents > 0 && self.documents.len() > self.max_documents {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            self.remove_key(oldest);
+            ops::record_dedup_evictions("capacity", 1);
+        }
+    }
+
+    /// Marks a document as just used
+    fn touch(&mut self, key: u64) {
+        let tick = self.next_tick();
+        if let Some(document) = self.documents.get_mut(&key) {
+            self.recency.remove(&document.tick);
+            self.recency.insert(tick, key);
+            document.tick = tick;
+            document.last_used = Instant::now();
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    /// Evicts documents idle for longer than the TTL; returns how many were removed
+    pub fn evict_expired(&mut self) -> usize {
+        let Some(ttl) = self.ttl else {
+            return 0;
+        };
+        let mut evicted = 0;
+        // * Recency order is also last-used order, so expired documents are at the front
+        while let Some((_, &key)) = self.recency.first_key_value() {
+            if self.documents.get(&key).is_some_and(|doc| doc.last_used.elapsed() < ttl) {
+                break;
+            }
+            self.remove_key(key);
+            evicted += 1;
+        }
+        if evicted > 0 {
+            ops::record_dedup_evictions("ttl", evicted);
+        }
+        evicted
+    }
+
+    /// Whether a document is indexed
+    pub fn contains(&self, document_id: &str) -> bool {
+        self.documents
+            .get(&document_key(document_id))
+            .is_some_and(|document| document.id == document_id)
+    }
+
     /// Returns the number of indexed documents
     pub fn document_count(&self) -> usize {
-        self.signatures.len()
+        self.documents.len()
+    }
+
+    /// Estimates the heap held by signatures, band buckets and the eviction queue, in bytes
+    ///
+    /// Counts each document's entry, signature, ID, one interned key per band and
+    /// per-entry container overhead; hash map slack is ignored.
+    pub fn memory_estimate(&self) -> usize {
+        let per_document = size_of::<u64>()
+            + size_of::<IndexedDocument>()
+            + NUM_HASH_FUNCTIONS * size_of::<u64>()
+            + self.num_bands * 2 * size_of::<u64>()
+            + 2 * size_of::<u64>();
+        self.documents.len() * per_document + self.id_bytes
+    }
+
+    /// Iterates (document id, signature) from least to most recently used
+    fn documents_by_recency(&self) -> impl Iterator<Item = (&str, &[u64])> {
+        self.recency.values().filter_map(|key| {
+            let document = self.documents.get(key)?;
+            Some((document.id.as_str(), &*document.signature))
+        })
     }
 
     /// Removes a document from the index
     pub fn remove_document(&mut self, document_id: &str) -> bool {
-        if let Some(signature) = self.signatures.remove(document_id) {
-            // * Remove from all band buckets
-            for band_idx in 0..self.num_bands {
-                let band_hash = self.compute_band_hash(&signature, band_idx);
-                if let Some(doc_ids) = self.bands[band_idx].get_mut(&band_hash) {
-                    doc_ids.retain(|id| id != document_id);
+        let key = document_key(document_id);
+        match self.documents.get(&key) {
+            Some(document) if document.id == document_id => self.remove_key(key),
+            _ => false,
+        }
+    }
+
+    /// Drops a document from every structure; returns false if it was not indexed
+    fn remove_key(&mut self, key: u64) -> bool {
+        let Some(document) = self.documents.remove(&key) else {
+            return false;
+        };
+        self.id_bytes -= document.id.len();
+        self.recency.remove(&document.tick);
+        // * Remove from all band buckets
+        for band_idx in 0..self.num_bands {
+            let band_hash = self.compute_band_hash(&document.signature, band_idx);
+            if let Entry::Occupied(mut bucket) = self.bands[band_idx].entry(band_hash) {
+                bucket.get_mut().retain(|&k| k != key);
+                if bucket.get().is_empty() {
+                    bucket.remove();
                 }
             }
-            true
-        } else {
-            false
         }
+        true
     }
 
     /// Clears the entire index
     pub fn clear(&mut self) {
         self.bands.iter_mut().for_each(|band| band.clear());
-        self.signatures.clear();
+        self.documents.clear();
+        self.recency.clear();
+        self.id_bytes = 0;
     }
 }
 
+fn canonical_hash(canonical: &str) -> u64 {
+    xxh64(canonical.as_bytes(), 0)
+}
+
+/// Interns a document ID as a 64-bit key
+///
+/// ! IDs whose hashes collide replace each other; at 64 bits this is negligible.
+fn document_key(document_id: &str) -> u64 {
+    xxh64(document_id.as_bytes(), 0)
+}
+
+/// Share of matching signature positions (estimated Jaccard similarity)
+fn similarity(a: &[u64], b: &[u64]) -> f64 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let matches = a.iter().zip(b).filter(|(a, b)| a == b).count();
+    matches as f64 / a.len() as f64
+}
+
 impl Default for LSHIndex {
     fn default() -> Self {
         Self::new()
@@ -270,37 +486,94 @@ fn hash_string(s: &str) -> u64 {
     hasher.finish()
 }
 
-/// Computes MinHash signature for a set of shingles
-fn compute_minhash(shingles: &HashSet<u64>) -> Vec<u64> {
-    if shingles.is_empty() {
-        return vec![u64::MAX; NUM_HASH_FUNCTIONS];
+/// Derives one coefficient per hash function from its index
+const fn hash_coefficients(multiplier: u64) -> [u64; NUM_HASH_FUNCTIONS] {
+    let mut coefficients = [0; NUM_HASH_FUNCTIONS];
+    let mut i = 0;
+    while i < NUM_HASH_FUNCTIONS {
+        coefficients[i] = (i as u64 + 1).wrapping_mul(multiplier);
+        i += 1;
     }
+    coefficients
+}
 
+/// Computes MinHash signature for a set of shingles
+fn compute_minhash(shingles: &[u64], strategy: MinHashStrategy) -> Vec<u64> {
     let mut signature = vec![u64::MAX; NUM_HASH_FUNCTIONS];
+    match strategy {
+        MinHashStrategy::Serial => minhash_serial(shingles, &mut signature),
+        MinHashStrategy::Blocked => minhash_blocked(shingles, &mut signature),
+        MinHashStrategy::Parallel => {
+            let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+            minhash_parallel(shingles, &mut signature, threads)
+        }
+    }
+    signature
+}
 
-    // * Generate hash coefficients for each hash function
-    // * Using simple polynomial hash: h_i(x) = (a_i * x + b_i) mod p
-    const LARGE_PRIME: u64 = 4_294_967_311; // * Prime larger than u32::MAX
-
+/// Reference implementation: each hash function scans every shingle
+fn minhash_serial(shingles: &[u64], signature: &mut [u64]) {
     for (i, min_hash) in signature.iter_mut().enumerate() {
-        let a = (i as u64 + 1) * 0xBF58476D1CE4E5B9;
-        let b = (i as u64 + 1) * 0x94D049BB133111EB;
+        for &shingle in shingles {
+            let hash_value = COEFF_A[i].wrapping_mul(shingle).wrapping_add(COEFF_B[i]) % LARGE_PRIME;
+            *min_hash = (*min_hash).min(hash_value);
+        }
+    }
+}
 
+/// Evaluates `HASH_BLOCK` hash functions per shingle over contiguous coefficient slices
+fn minhash_blocked(shingles: &[u64], signature: &mut [u64]) {
+    let blocks = signature
+        .chunks_mut(HASH_BLOCK)
+        .zip(COEFF_A.chunks(HASH_BLOCK).zip(COEFF_B.chunks(HASH_BLOCK)));
+    for (mins, (a, b)) in blocks {
         for &shingle in shingles {
-            let hash_value = (a.wrapping_mul(shingle).wrapping_add(b)) % LARGE_PRIME;
-            if hash_value < *min_hash {
-                *min_hash = hash_value;
+            for ((min_hash, &a), &b) in mins.iter_mut().zip(a).zip(b) {
+                *min_hash = (*min_hash).min(a.wrapping_mul(shingle).wrapping_add(b) % LARGE_PRIME);
             }
         }
     }
+}
 
-    signature
+/// Splits shingles across `threads` workers and merges their partial signatures
+///
+/// The minimum is order-independent, so the result equals the serial signature.
+fn minhash_parallel(shingles: &[u64], signature: &mut [u64], threads: usize) {
+    if threads <= 1 || shingles.len() < PARALLEL_MIN_SHINGLES {
+        minhash_blocked(shingles, signature);
+        return;
+    }
+
+    let len = signature.len();
+    let per_thread = shingles.len().div_ceil(threads);
+    let partials: Vec<Vec<u64>> = std::thread::scope(|scope| {
+        let workers: Vec<_> = shingles
+            .chunks(per_thread)
+            .map(|part| {
+                scope.spawn(move || {
+                    let mut partial = vec![u64::MAX; len];
+                    minhash_blocked(part, &mut partial);
+                    partial
+                })
+            })
+            .collect();
+        workers.into_iter().map(|worker| worker.join().unwrap()).collect()
+    });
+
+    for partial in partials {
+        for (min_hash, value) in signature.iter_mut().zip(partial) {
+            *min_hash = (*min_hash).min(value);
+        }
+    }
 }
 
-/// Bloom filter for quick membership testing (LSHBloom optimization)
+/// Counting Bloom filter for quick membership testing (LSHBloom optimization)
+///
+/// Each slot holds a small counter instead of a bit, so items can be removed again.
+/// A counter that saturates at `u8::MAX` stays set for good.
 #[derive(Debug)]
 pub struct BloomFilter {
-    bits: Vec<bool>,
+    counters: Vec<u8>,
     num_hash_functions: usize,
     size: usize,
 }
@@ -309,7 +582,7 @@ impl BloomFilter {
     /// Creates a new Bloom filter with specified size and hash count
     pub fn new(size: usize, num_hash_functions: usize) -> Self {
         Self {
-            bits: vec![false; size],
+            counters: vec![0; size],
             num_hash_functions,
             size,
         }
@@ -331,7 +604,7 @@ impl BloomFilter {
     pub fn add(&mut self, item: &str) {
         for i in 0..self.num_hash_functions {
             let idx = self.hash(item, i);
-            self.bits[idx] = true;
+            self.counters[idx] = self.counters[idx].saturating_add(1);
         }
     }
 
@@ -339,13 +612,30 @@ impl BloomFilter {
     pub fn might_contain(&self, item: &str) -> bool {
         for i in 0..self.num_hash_functions {
             let idx = self.hash(item, i);
-            if !self.bits[idx] {
+            if self.counters[idx] == 0 {
                 return false;
             }
         }
         true
     }
 
+    /// Removes an item added earlier; returns false when it is not in the filter
+    ///
+    /// ! Removing an item that was never added can clear other items' slots
+    /// ! (a false positive looks present), so only remove what was added.
+    pub fn remove(&mut self, item: &str) -> bool {
+        if !self.might_contain(item) {
+            return false;
+        }
+        for i in 0..self.num_hash_functions {
+            let idx = self.hash(item, i);
+            if self.counters[idx] < u8::MAX {
+                self.counters[idx] -= 1;
+            }
+        }
+        true
+    }
+
     /// Computes hash for a specific hash function index
     fn hash(&self, item: &str, seed: usize) -> usize {
         let mut hasher = std::collections::hash_map::DefaultHasher::new();
@@ -356,25 +646,131 @@ impl BloomFilter {
 
     /// Clears the filter
     pub fn clear(&mut self) {
-        self.bits.fill(false);
+        self.counters.fill(0);
     }
+
+    /// Packs the filter into 64-bit words for serialization, plus the counters above 1
+    pub fn snapshot(&self) -> BloomSnapshot {
+        let mut words = vec![0u64; self.size.div_ceil(64)];
+        let mut counts = Vec::new();
+        for (idx, &count) in self.counters.iter().enumerate().filter(|(_, count)| **count > 0) {
+            words[idx / 64] |= 1 << (idx % 64);
+            if count > 1 {
+                counts.push((idx, count));
+            }
+        }
+        BloomSnapshot {
+            words,
+            size: self.size,
+            num_hash_functions: self.num_hash_functions,
+            counts,
+        }
+    }
+
+    /// Rebuilds a filter from a snapshot
+    pub fn from_snapshot(snapshot: &BloomSnapshot) -> Self {
+        let mut counters: Vec<u8> = (0..snapshot.size)
+            .map(|idx| {
+                let set = snapshot
+                    .words
+                    .get(idx / 64)
+                    .is_some_and(|word| word & (1 << (idx % 64)) != 0);
+                u8::from(set)
+            })
+            .collect();
+        for &(idx, count) in &snapshot.counts {
+            if let Some(counter) = counters.get_mut(idx) {
+                *counter = count;
+            }
+        }
+        Self {
+            counters,
+            num_hash_functions: snapshot.num_hash_functions,
+            size: snapshot.size.max(1),
+        }
+    }
+}
+
+/// Serialized Bloom filter slots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BloomSnapshot {
+    /// One bit per slot, set when its counter is non-zero
+    pub words: Vec<u64>,
+    pub size: usize,
+    pub num_hash_functions: usize,
+    /// (slot, counter) for counters above 1; empty in snapshots taken before counting
+    #[serde(default)]
+    pub counts: Vec<(usize, u8)>,
+}
+
+/// Serializable dedup state, used by crawl checkpoints
+///
+/// Bloom and band hashes use the standard library's default hasher, so a
+/// snapshot should be restored by a binary built with the same toolchain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DedupSnapshot {
+    /// (document id, MinHash signature) for every indexed document
+    pub signatures: Vec<(String, Vec<u64>)>,
+    pub url_bloom: BloomSnapshot,
+    pub content_hashes: Vec<u64>,
+    /// Where indexed documents came from (empty in snapshots taken before clustering)
+    #[serde(default)]
+    pub origins: Vec<DocumentOrigin>,
+    /// (canonical URL hash, document id) for every indexed canonical
+    #[serde(default)]
+    pub canonicals: Vec<(u64, String)>,
+    #[serde(default)]
+    pub clusters: Vec<DuplicateCluster>,
+}
+
+/// URL, hash and length of a unique document, kept so later duplicates can name it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentOrigin {
+    pub document_id: String,
+    pub url: String,
+    pub content_hash: u64,
+    pub text_len: usize,
+    /// Hash of the canonical URL the document was indexed under
+    #[serde(default)]
+    pub canonical_hash: Option<u64>,
 }
 
 /// Deduplication manager combining LSH Index and Bloom Filter
+///
+/// Besides the yes/no verdict it groups each original with the near and exact
+/// duplicates matched against it (see `find_cluster`).
 #[derive(Debug)]
 pub struct DedupManager {
     lsh_index: LSHIndex,
     url_bloom: BloomFilter,
     content_hash_set: HashSet<u64>,
+    // * Canonical URL hash -> document that declared it
+    canonicals: HashMap<u64, String>,
+    // * Unique documents by ID, and which of them first had each content hash
+    origins: HashMap<String, DocumentOrigin>,
+    hash_owners: HashMap<u64, String>,
+    // * Original ID -> cluster, and duplicate ID -> original ID
+    clusters: HashMap<String, DuplicateCluster>,
+    member_of: HashMap<String, String>,
 }
 
 impl DedupManager {
     /// Creates a new deduplication manager
     pub fn new() -> Self {
+        Self::with_config(DedupConfig::default())
+    }
+
+    /// Creates a deduplication manager with custom configuration
+    pub fn with_config(config: DedupConfig) -> Self {
         Self {
-            lsh_index: LSHIndex::new(),
+            lsh_index: config.lsh_index(),
             url_bloom: BloomFilter::with_capacity(100_000, 0.01),
             content_hash_set: HashSet::new(),
+            canonicals: HashMap::new(),
+            origins: HashMap::new(),
+            hash_owners: HashMap::new(),
+            clusters: HashMap::new(),
+            member_of: HashMap::new(),
         }
     }
 
@@ -383,24 +779,155 @@ impl DedupManager {
         self.url_bloom.might_contain(url)
     }
 
+    /// Checks whether a normalized canonical URL was declared by, or is the URL of, an indexed page
+    pub fn check_canonical(&self, canonical: &str) -> bool {
+        self.canonicals.contains_key(&canonical_hash(canonical)) || self.url_bloom.might_contain(canonical)
+    }
+
     /// Checks content hash deduplication (medium path)
     pub fn check_content_hash(&self, hash: u64) -> bool {
         self.content_hash_set.contains(&hash)
     }
 
     /// Full deduplication check including near-duplicate detection
+    ///
+    /// Duplicates are counted by level and the index gauges updated as documents are added.
     pub fn check_and_index(
         &mut self,
         url: &str,
         content_hash: u64,
         text: &str,
         document_id: &str,
+    ) -> DedupCheckResult {
+        self.check_and_index_canonical(url, None, content_hash, text, document_id)
+    }
+
+    /// `check_and_index` with the page's normalized canonical URL (see `canonical_key`)
+    ///
+    /// A canonical already indexed marks the page `DuplicateCanonical` before any hashing,
+    /// which catches tracking-parameter variants of one page cheaply.
+    pub fn check_and_index_canonical(
+        &mut self,
+        url: &str,
+        canonical: Option<&str>,
+        content_hash: u64,
+        text: &str,
+        document_id: &str,
+    ) -> DedupCheckResult {
+        let result = self.check_levels(url, canonical, content_hash, text, document_id);
+        match &result {
+            DedupCheckResult::Unique => self.publish_index_metrics(),
+            duplicate => ops::record_dedup_duplicate(duplicate.level()),
+        }
+        self.track_cluster(&result, url, canonical, content_hash, text.len(), document_id);
+        result
+    }
+
+    /// Records a unique document's origin, or adds a duplicate to its original's cluster
+    fn track_cluster(
+        &mut self,
+        result: &DedupCheckResult,
+        url: &str,
+        canonical: Option<&str>,
+        content_hash: u64,
+        text_len: usize,
+        document_id: &str,
+    ) {
+        let (original_id, similarity) = match result {
+            DedupCheckResult::Unique => {
+                self.hash_owners.entry(content_hash).or_insert_with(|| document_id.to_string());
+                self.origins.insert(
+                    document_id.to_string(),
+                    DocumentOrigin {
+                        document_id: document_id.to_string(),
+                        url: url.to_string(),
+                        content_hash,
+                        text_len,
+                        canonical_hash: canonical.map(canonical_hash),
+                    },
+                );
+                self.prune_origins();
+                return;
+            }
+            DedupCheckResult::NearDuplicate {
+                original_id,
+                similarity,
+            } => (original_id.clone(), *similarity),
+            DedupCheckResult::DuplicateHash => match self.hash_owners.get(&content_hash) {
+                Some(owner) => (owner.clone(), 1.0),
+                None => return,
+            },
+            // * A URL seen again is a recrawl, and a shared canonical the same page; neither is a copy
+            DedupCheckResult::DuplicateUrl | DedupCheckResult::DuplicateCanonical => return,
+        };
+
+        let origins = &self.origins;
+        let cluster = self.clusters.entry(original_id.clone()).or_insert_with(|| DuplicateCluster {
+            members: origins
+                .get(&original_id)
+                .map(|origin| ClusterMember {
+                    document_id: origin.document_id.clone(),
+                    url: origin.url.clone(),
+                    similarity: 1.0,
+                    text_len: origin.text_len,
+                })
+                .into_iter()
+                .collect(),
+            original_id: original_id.clone(),
+        });
+        if !cluster.contains(document_id) {
+            cluster.members.push(ClusterMember {
+                document_id: document_id.to_string(),
+                url: url.to_string(),
+                similarity,
+                text_len,
+            });
+            self.member_of.insert(document_id.to_string(), original_id);
+        }
+    }
+
+    // * Origins follow the LSH index: once a document is evicted nothing can match it again
+    fn prune_origins(&mut self) {
+        if self.origins.len() <= 2 * self.lsh_index.document_count() + 1024 {
+            return;
+        }
+        let index = &self.lsh_index;
+        self.origins.retain(|id, _| index.contains(id));
+        let origins = &self.origins;
+        self.hash_owners.retain(|_, id| origins.contains_key(id));
+    }
+
+    /// Returns the cluster a document belongs to, as its original or as a duplicate
+    ///
+    /// None when nothing was matched against the document (or it was itself never seen).
+    pub fn find_cluster(&self, document_id: &str) -> Option<DuplicateCluster> {
+        let original_id = self.member_of.get(document_id).map_or(document_id, String::as_str);
+        self.clusters.get(original_id).cloned()
+    }
+
+    /// Returns every cluster with at least one duplicate
+    pub fn clusters(&self) -> Vec<DuplicateCluster> {
+        self.clusters.values().cloned().collect()
+    }
+
+    fn check_levels(
+        &mut self,
+        url: &str,
+        canonical: Option<&str>,
+        content_hash: u64,
+        text: &str,
+        document_id: &str,
     ) -> DedupCheckResult {
         // * Level 1: URL check
         if self.check_url(url) {
             return DedupCheckResult::DuplicateUrl;
         }
 
+        // * Level 1b: Canonical URL check
+        if canonical.is_some_and(|canonical| self.check_canonical(canonical)) {
+            return DedupCheckResult::DuplicateCanonical;
+        }
+
         // * Level 2: Content hash check
         if self.check_content_hash(content_hash) {
             return DedupCheckResult::DuplicateHash;
@@ -416,14 +943,136 @@ impl DedupManager {
                 similarity,
             },
             DedupResult::Unique => {
-                // * Add to URL bloom and hash set
+                // * Add to URL bloom, canonical map and hash set
                 self.url_bloom.add(url);
+                if let Some(canonical) = canonical {
+                    self.canonicals
+                        .entry(canonical_hash(canonical))
+                        .or_insert_with(|| document_id.to_string());
+                }
                 self.content_hash_set.insert(content_hash);
                 DedupCheckResult::Unique
             }
         }
     }
 
+    /// Forgets a persisted page so the same URL or content is accepted again
+    ///
+    /// Clears the URL's Bloom filter slots, the content hash and the LSH entry.
+    /// Returns true when any of them was present.
+    pub fn remove(&mut self, url: &str, content_hash: u64, document_id: &str) -> bool {
+        let in_bloom = self.url_bloom.remove(url);
+        let in_hashes = self.content_hash_set.remove(&content_hash);
+        let in_index = self.lsh_index.remove_document(document_id);
+        if in_index {
+            self.publish_index_metrics();
+        }
+        match self.origins.remove(document_id) {
+            Some(origin) => {
+                if let Some(hash) = origin.canonical_hash {
+                    if self.canonicals.get(&hash).is_some_and(|owner| owner == document_id) {
+                        self.canonicals.remove(&hash);
+                    }
+                }
+            }
+            // * Origins are pruned with the LSH index, so fall back to a scan
+            None => self.canonicals.retain(|_, owner| owner != document_id),
+        }
+        if self.hash_owners.get(&content_hash).is_some_and(|id| id == document_id) {
+            self.hash_owners.remove(&content_hash);
+        }
+        // * A purged original takes its cluster with it; a purged duplicate just leaves
+        if let Some(cluster) = self.clusters.remove(document_id) {
+            for member in &cluster.members {
+                self.member_of.remove(&member.document_id);
+            }
+        } else if let Some(original_id) = self.member_of.remove(document_id) {
+            if let Some(cluster) = self.clusters.get_mut(&original_id) {
+                cluster.members.retain(|member| member.document_id != document_id);
+            }
+        }
+        in_bloom || in_hashes || in_index
+    }
+
+    /// Captures the full dedup state
+    pub fn snapshot(&self) -> DedupSnapshot {
+        let mut content_hashes: Vec<u64> = self.content_hash_set.iter().copied().collect();
+        content_hashes.sort_unstable();
+
+        DedupSnapshot {
+            signatures: self
+                .lsh_index
+                .documents_by_recency()
+                .map(|(document_id, signature)| (document_id.to_string(), signature.to_vec()))
+                .collect(),
+            url_bloom: self.url_bloom.snapshot(),
+            content_hashes,
+            origins: self.origins.values().cloned().collect(),
+            canonicals: self.canonicals.iter().map(|(hash, id)| (*hash, id.clone())).collect(),
+            clusters: self.clusters.values().cloned().collect(),
+        }
+    }
+
+    /// Restores a manager from a snapshot, rebuilding the LSH bands
+    pub fn from_snapshot(snapshot: &DedupSnapshot) -> Self {
+        Self::from_snapshot_with_config(snapshot, DedupConfig::default())
+    }
+
+    /// Restores a manager from a snapshot with custom configuration
+    pub fn from_snapshot_with_config(snapshot: &DedupSnapshot, config: DedupConfig) -> Self {
+        let mut lsh_index = config.lsh_index();
+        for (document_id, signature) in &snapshot.signatures {
+            lsh_index.insert_signature(MinHashSignature {
+                signature: signature.clone(),
+                document_id: document_id.clone(),
+            });
+        }
+
+        let mut hash_owners = HashMap::new();
+        for origin in &snapshot.origins {
+            hash_owners.entry(origin.content_hash).or_insert_with(|| origin.document_id.clone());
+        }
+        let member_of = snapshot
+            .clusters
+            .iter()
+            .flat_map(|cluster| {
+                cluster
+                    .members
+                    .iter()
+                    .filter(|member| member.document_id != cluster.original_id)
+                    .map(|member| (member.document_id.clone(), cluster.original_id.clone()))
+            })
+            .collect();
+
+        let manager = Self {
+            lsh_index,
+            url_bloom: BloomFilter::from_snapshot(&snapshot.url_bloom),
+            content_hash_set: snapshot.content_hashes.iter().copied().collect(),
+            canonicals: snapshot.canonicals.iter().cloned().collect(),
+            origins: snapshot
+                .origins
+                .iter()
+                .map(|origin| (origin.document_id.clone(), origin.clone()))
+                .collect(),
+            hash_owners,
+            clusters: snapshot
+                .clusters
+                .iter()
+                .map(|cluster| (cluster.original_id.clone(), cluster.clone()))
+                .collect(),
+            member_of,
+        };
+        manager.publish_index_metrics();
+        manager
+    }
+
+    fn publish_index_metrics(&self) {
+        ops::set_dedup_index_size(
+            self.lsh_index.document_count(),
+            self.lsh_index.memory_estimate(),
+        );
+    }
+
     /// Returns statistics about the deduplication state
     pub fn stats(&self) -> DedupStats {
         DedupStats {
@@ -446,6 +1095,8 @@ pub enum DedupCheckResult {
     Unique,
     /// Exact URL duplicate
     DuplicateUrl,
+    /// Declares the canonical URL of an indexed page
+    DuplicateCanonical,
     /// Exact content hash duplicate
     DuplicateHash,
     /// Near-duplicate detected via LSH
@@ -453,6 +1104,17 @@ pub enum DedupCheckResult {
 }
 
 impl DedupCheckResult {
+    /// Metric label: unique, url, canonical, hash or near
+    pub fn level(&self) -> &'static str {
+        match self {
+            DedupCheckResult::Unique => "unique",
+            DedupCheckResult::DuplicateUrl => "url",
+            DedupCheckResult::DuplicateCanonical => "canonical",
+            DedupCheckResult::DuplicateHash => "hash",
+            DedupCheckResult::NearDuplicate { .. } => "near",
+        }
+    }
+
     pub fn is_duplicate(&self) -> bool {
         !matches!(self, DedupCheckResult::Unique)
     }
@@ -586,6 +1248,121 @@ mod tests {
         // * This is a probabilistic test
     }
 
+    #[test]
+    fn test_counting_bloom_filter_removal() {
+        let mut bloom = BloomFilter::with_capacity(1000, 0.01);
+        bloom.add("https://example.com/kept");
+        bloom.add("https://example.com/gone");
+        bloom.add("https://example.com/gone");
+
+        // * Added twice, so one removal leaves it present
+        assert!(bloom.remove("https://example.com/gone"));
+        assert!(bloom.might_contain("https://example.com/gone"));
+        assert!(bloom.remove("https://example.com/gone"));
+        assert!(!bloom.might_contain("https://example.com/gone"));
+        assert!(!bloom.remove("https://example.com/gone"));
+        assert!(bloom.might_contain("https://example.com/kept"));
+
+        // * Counters survive a snapshot
+        bloom.add("https://example.com/kept");
+        let mut restored = BloomFilter::from_snapshot(&bloom.snapshot());
+        assert!(restored.remove("https://example.com/kept"));
+        assert!(restored.might_contain("https://example.com/kept"));
+    }
+
+    #[test]
+    fn test_dedup_manager_remove_accepts_page_again() {
+        let text = "Removed pages can be crawled again once garbage collection forgets them";
+        let mut manager = DedupManager::new();
+        assert!(manager.check_and_index("https://example.com/a", 42, text, "doc-a").is_unique());
+
+        assert!(manager.remove("https://example.com/a", 42, "doc-a"));
+        assert!(!manager.remove("https://example.com/a", 42, "doc-a"));
+        assert!(manager.check_and_index("https://example.com/a", 42, text, "doc-b").is_unique());
+    }
+
+    #[test]
+    fn test_find_cluster_groups_duplicates_with_their_original() {
+        let story = "Regional rail fares will rise by four percent in March, the transport authority \
+            said, citing higher energy prices and the cost of new rolling stock on the coastal line.";
+        let mut manager = DedupManager::new();
+        manager.check_and_index("https://wire.example/fares", 7, story, "wire");
+        manager.check_and_index("https://news.example/fares", 8, &format!("{} Updated.", story), "news");
+        manager.check_and_index("https://copy.example/fares", 7, story, "copy");
+        // * A recrawled URL is not a copy
+        manager.check_and_index("https://wire.example/fares", 9, story, "again");
+
+        let cluster = manager.find_cluster("copy").unwrap();
+        assert_eq!(cluster, manager.find_cluster("wire").unwrap());
+        assert_eq!(cluster.original_id, "wire");
+        let members: Vec<(&str, &str)> = cluster
+            .members
+            .iter()
+            .map(|m| (m.document_id.as_str(), m.url.as_str()))
+            .collect();
+        assert_eq!(
+            members,
+            [
+                ("wire", "https://wire.example/fares"),
+                ("news", "https://news.example/fares"),
+                ("copy", "https://copy.example/fares"),
+            ]
+        );
+        assert!(cluster.members[1].similarity < 1.0);
+        assert!(manager.find_cluster("again").is_none());
+
+        // * Clusters survive a checkpoint; purging the original drops its cluster
+        let mut restored = DedupManager::from_snapshot(&manager.snapshot());
+        assert_eq!(restored.find_cluster("news"), Some(cluster));
+        restored.check_and_index("https://late.example/fares", 7, story, "late");
+        assert_eq!(restored.find_cluster("late").unwrap().len(), 4);
+        restored.remove("https://wire.example/fares", 7, "wire");
+        assert!(restored.find_cluster("news").is_none());
+        assert!(restored.clusters().is_empty());
+    }
+
+    #[test]
+    fn test_canonical_level_catches_tracking_variants() {
+        use crate::engine::normalization::canonical_key;
+
+        let page = "https://shop.example/boots?utm_source=mail";
+        assert_eq!(
+            canonical_key(page, "/boots?color=red#reviews").as_deref(),
+            Some("https://shop.example/boots?color=red")
+        );
+        // * Homepage and off-site canonicals are not trusted
+        assert_eq!(canonical_key(page, "https://shop.example/"), None);
+        assert_eq!(canonical_key(page, "https://other.example/boots"), None);
+
+        let mut manager = DedupManager::new();
+        let canonical = canonical_key("https://shop.example/boots", "https://shop.example/boots");
+        let first = manager.check_and_index_canonical(
+            "https://shop.example/boots",
+            canonical.as_deref(),
+            1,
+            "Waterproof leather boots with a cushioned sole",
+            "boots",
+        );
+        assert!(first.is_unique());
+
+        // * Different text (rotating recommendations) would pass every later level
+        let variant = manager.check_and_index_canonical(
+            "https://shop.example/boots?sessionid=9",
+            Some("https://shop.example/boots"),
+            2,
+            "Completely different sidebar text for this session",
+            "variant",
+        );
+        assert_eq!(variant.level(), "canonical");
+
+        // * A canonical naming an indexed URL counts as well
+        manager.check_and_index("https://shop.example/hats", 3, "Knitted wool hats in six colors", "hats");
+        assert!(manager.check_canonical("https://shop.example/hats"));
+
+        manager.remove("https://shop.example/boots", 1, "boots");
+        assert!(!manager.check_canonical("https://shop.example/boots"));
+    }
+
     #[test]
     fn test_dedup_manager_full_workflow() {
         let mut manager = DedupManager::new();
@@ -621,6 +1398,75 @@ mod tests {
         assert_eq!(stats.unique_hashes, 1);
     }
 
+    #[test]
+    fn test_duplicates_counted_by_level() {
+        use crate::ops::telemetry::DEDUP_DUPLICATES_TOTAL;
+
+        let count = |level: &str| DEDUP_DUPLICATES_TOTAL.with_label_values(&[level]).get();
+        let (url_before, hash_before) = (count("url"), count("hash"));
+
+        let mut manager = DedupManager::new();
+        manager.check_and_index("https://example.com/a", 1, "Original wording of page a", "a");
+        let url = manager.check_and_index("https://example.com/a", 2, "Other text", "b");
+        let hash = manager.check_and_index("https://example.com/c", 1, "More text", "c");
+        assert_eq!(url.level(), "url");
+        assert_eq!(hash.level(), "hash");
+
+        // * Other tests dedup concurrently, so counters only grow by at least one
+        assert!(count("url") > url_before);
+        assert!(count("hash") > hash_before);
+    }
+
+    #[test]
+    fn test_lsh_memory_estimate_tracks_documents() {
+        let mut index = LSHIndex::new();
+        assert_eq!(index.memory_estimate(), 0);
+
+        index.index_document("The quick brown fox jumps over the lazy dog", "doc-1");
+        let one = index.memory_estimate();
+        assert!(one > NUM_HASH_FUNCTIONS * size_of::<u64>());
+
+        index.index_document("Completely unrelated text about sailing and tides", "doc-2");
+        assert!(index.memory_estimate() > one);
+
+        index.remove_document("doc-2");
+        assert_eq!(index.memory_estimate(), one);
+        index.clear();
+        assert_eq!(index.memory_estimate(), 0);
+    }
+
+    #[test]
+    fn test_lsh_capacity_evicts_least_recently_used() {
+        let mut index = LSHIndex::new().with_max_documents(2);
+        let fox = "The quick brown fox jumps over the lazy dog in the morning sun";
+        index.index_document(fox, "fox");
+        index.index_document("Sailing across the bay requires patience with tides and wind", "sail");
+
+        // * Matching "fox" as a duplicate makes "sail" the least recently used
+        assert!(index.index_document(fox, "fox-copy").is_duplicate());
+        index.index_document("Baking sourdough bread needs a lively starter and time", "bread");
+
+        assert_eq!(index.document_count(), 2);
+        assert!(!index.remove_document("sail"));
+        assert!(index.check_duplicate(fox).is_some());
+        let order: Vec<_> = index.documents_by_recency().map(|(id, _)| id).collect();
+        assert_eq!(order, ["fox", "bread"]);
+    }
+
+    #[test]
+    fn test_lsh_ttl_expires_idle_documents() {
+        let mut index = LSHIndex::new().with_ttl(Duration::from_millis(20));
+        index.index_document("The quick brown fox jumps over the lazy dog", "doc-1");
+        assert_eq!(index.evict_expired(), 0);
+
+        std::thread::sleep(Duration::from_millis(30));
+        index.index_document("Completely unrelated text about sailing and tides", "doc-2");
+        assert_eq!(index.document_count(), 1);
+        assert!(index.check_duplicate("The quick brown fox jumps over the lazy dog").is_none());
+        assert!(index.remove_document("doc-2"));
+        assert_eq!(index.memory_estimate(), 0);
+    }
+
     #[test]
     fn test_shingle_generation() {
         let shingles = generate_shingles("hello", 3);
@@ -683,4 +1529,64 @@ mod tests {
         let result2 = index.index_document("   ", "whitespace");
         assert!(result2.is_unique() || result2.is_duplicate());
     }
+
+    #[test]
+    fn test_dedup_snapshot_roundtrip() {
+        let text = "Snapshot content about lighthouse keepers and the tides of the northern coast";
+        let mut manager = DedupManager::new();
+        assert!(manager
+            .check_and_index("https://example.com/a", 42, text, "doc-a")
+            .is_unique());
+
+        let snapshot = manager.snapshot();
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let mut restored = DedupManager::from_snapshot(&serde_json::from_str(&json).unwrap());
+
+        assert!(restored.check_url("https://example.com/a"));
+        assert!(restored.check_content_hash(42));
+        assert_eq!(restored.stats().indexed_documents, 1);
+        assert!(matches!(
+            restored.check_and_index("https://example.com/b", 7, text, "doc-b"),
+            DedupCheckResult::NearDuplicate { .. }
+        ));
+    }
+
+    #[test]
+    fn test_minhash_strategies_are_identical() {
+        let texts = [
+            String::new(),
+            "ab".to_string(),
+            "The quick brown fox jumps over the lazy dog near the riverbank".to_string(),
+            "Long article text with many distinct shingles. ".repeat(50),
+        ];
+        for text in &texts {
+            let serial = MinHashSignature::from_text_with(text, "d".to_string(), MinHashStrategy::Serial);
+            for strategy in [MinHashStrategy::Blocked, MinHashStrategy::Parallel] {
+                let other = MinHashSignature::from_text_with(text, "d".to_string(), strategy);
+                assert_eq!(serial.signature, other.signature, "{:?}", strategy);
+            }
+        }
+
+        // * Force the threaded path on a large shingle set
+        let shingles: Vec<u64> = (0..PARALLEL_MIN_SHINGLES as u64 * 2).map(|i| i.wrapping_mul(0x9E37_79B9)).collect();
+        let mut serial = vec![u64::MAX; NUM_HASH_FUNCTIONS];
+        let mut parallel = serial.clone();
+        minhash_serial(&shingles, &mut serial);
+        minhash_parallel(&shingles, &mut parallel, 4);
+        assert_eq!(serial, parallel);
+    }
+
+    #[test]
+    fn test_index_uses_configured_strategy() {
+        let mut manager = DedupManager::with_config(DedupConfig {
+            minhash: MinHashStrategy::Parallel,
+            ..Default::default()
+        });
+        let text = "Shared article body used to check near-duplicate detection across strategies.";
+        assert!(manager.check_and_index("https://a.example/1", 1, text, "a").is_unique());
+        assert!(manager.check_and_index("https://a.example/2", 2, text, "b").is_duplicate());
+
+        let config: DedupConfig = serde_json::from_str(r#"{"minhash": "serial"}"#).unwrap();
+        assert_eq!(config.minhash, MinHashStrategy::Serial);
+    }
 }
@@ -0,0 +1,350 @@
+// * [FR-05] TTL-Based Record Expiry
+// * Per-domain and per-category (URL pattern) retention rules, e.g. "expire news after 90 days".
+// * A scheduler soft-deletes records older than their rule allows and can hand them back for recrawl.
+
+use super::ai_worker::{EnrichmentError, RecordProvider, RecordUpdater};
+use super::dedup::DedupManager;
+use super::schema::{MultimodalRecord, RecordFilter};
+use crate::ops;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::Duration;
+use url::Url;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+// * Records scanned per page when looking for expired records
+const SCAN_BATCH_SIZE: usize = 500;
+
+#[derive(Debug, Error)]
+pub enum ExpiryError {
+    #[error("Invalid url_pattern in expiry rule {index}: {source}")]
+    InvalidPattern {
+        index: usize,
+        #[source]
+        source: regex::Error,
+    },
+}
+
+/// Maximum age for the records a rule matches
+///
+/// `domain` matches the host and its subdomains; `url_pattern` is a regular expression
+/// matched against the URL (e.g. `"/news/"` for a news category). A rule with neither
+/// matches every record.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExpiryRule {
+    /// Label for logs and the `rule` metric label
+    pub name: String,
+    pub domain: Option<String>,
+    pub url_pattern: Option<String>,
+    /// Days since the content was stored before the record expires
+    pub max_age_days: u64,
+    /// Queue expired URLs for a fresh crawl
+    pub recrawl: bool,
+}
+
+/// Expiry settings; the first matching rule applies to a record
+///
+/// # Example
+/// ```toml
+/// [crawl.expiry]
+/// enabled = true
+///
+/// [[crawl.expiry.rules]]
+/// name = "news"
+/// url_pattern = "/news/"
+/// max_age_days = 90
+/// recrawl = true
+///
+/// [[crawl.expiry.rules]]
+/// name = "docs"
+/// domain = "docs.example.com"
+/// max_age_days = 365
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExpiryConfig {
+    /// Run the expiry scheduler during crawls
+    pub enabled: bool,
+    /// Seconds between expiry runs
+    pub interval_secs: u64,
+    pub rules: Vec<ExpiryRule>,
+}
+
+impl Default for ExpiryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: 3600,
+            rules: Vec::new(),
+        }
+    }
+}
+
+/// `ExpiryConfig` rules with their patterns compiled
+#[derive(Debug, Clone)]
+pub struct ExpiryPolicy {
+    rules: Vec<(ExpiryRule, Option<Regex>)>,
+}
+
+impl ExpiryPolicy {
+    pub fn new(rules: &[ExpiryRule]) -> Result<Self, ExpiryError> {
+        let rules = rules
+            .iter()
+            .enumerate()
+            .map(|(index, rule)| {
+                let pattern = rule
+                    .url_pattern
+                    .as_deref()
+                    .map(Regex::new)
+                    .transpose()
+                    .map_err(|source| ExpiryError::InvalidPattern { index, source })?;
+                Ok((rule.clone(), pattern))
+            })
+            .collect::<Result<_, ExpiryError>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Returns the first rule matching a URL
+    pub fn rule_for(&self, url: &str) -> Option<&ExpiryRule> {
+        let host = Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_lowercase));
+        self.rules
+            .iter()
+            .find(|(rule, pattern)| {
+                let domain_matches = rule.domain.as_deref().is_none_or(|domain| {
+                    let domain = domain.to_lowercase();
+                    host.as_deref()
+                        .is_some_and(|host| host == domain || host.ends_with(&format!(".{}", domain)))
+                });
+                domain_matches && pattern.as_ref().is_none_or(|p| p.is_match(url))
+            })
+            .map(|(rule, _)| rule)
+    }
+
+    /// Returns the rule a live record has outlived at `now` (Unix seconds)
+    pub fn expired_rule(&self, record: &MultimodalRecord, now: u64) -> Option<&ExpiryRule> {
+        if record.is_deleted {
+            return None;
+        }
+        let rule = self.rule_for(&record.url)?;
+        let stored_at = if record.revised_at == 0 { record.created_at } else { record.revised_at };
+        let max_age = rule.max_age_days.saturating_mul(SECONDS_PER_DAY);
+        (now.saturating_sub(stored_at) > max_age).then_some(rule)
+    }
+}
+
+/// Outcome of one expiry run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExpiryReport {
+    /// Records soft-deleted
+    pub expired: usize,
+    /// URLs queued for recrawl
+    pub recrawl: Vec<String>,
+}
+
+/// Periodically soft-deletes records past their rule's maximum age
+///
+/// Expired records are left for `GarbageCollector` to purge. URLs of rules with
+/// `recrawl` are forgotten by the dedup index and sent to the recrawl channel.
+pub struct ExpiryScheduler<S> {
+    config: ExpiryConfig,
+    policy: ExpiryPolicy,
+    store: S,
+    dedup: Option<Arc<Mutex<DedupManager>>>,
+    recrawl: Option<mpsc::UnboundedSender<String>>,
+}
+
+impl<S> ExpiryScheduler<S>
+where
+    S: RecordProvider + RecordUpdater + 'static,
+{
+    pub fn new(config: ExpiryConfig, store: S) -> Result<Self, ExpiryError> {
+        Ok(Self {
+            policy: ExpiryPolicy::new(&config.rules)?,
+            config,
+            store,
+            dedup: None,
+            recrawl: None,
+        })
+    }
+
+    /// Forgets recrawled pages in this dedup index so they are not skipped as duplicates
+    pub fn with_dedup(mut self, dedup: Arc<Mutex<DedupManager>>) -> Self {
+        self.dedup = Some(dedup);
+        self
+    }
+
+    /// Sends the URLs of expired records whose rule asks for a recrawl
+    pub fn with_recrawl(mut self, sender: mpsc::UnboundedSender<String>) -> Self {
+        self.recrawl = Some(sender);
+        self
+    }
+
+    pub fn policy(&self) -> &ExpiryPolicy {
+        &self.policy
+    }
+
+    /// Soft-deletes every live record past its rule's maximum age
+    pub async fn run_once(&self) -> Result<ExpiryReport, EnrichmentError> {
+        let now = current_timestamp();
+        let mut expired: Vec<(MultimodalRecord, String, bool)> = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = self
+                .store
+                .fetch_records(RecordFilter::new(), offset, SCAN_BATCH_SIZE)
+                .await?;
+            let fetched = page.len();
+            for record in page {
+                if let Some(rule) = self.policy.expired_rule(&record, now) {
+                    let (name, recrawl) = (rule.name.clone(), rule.recrawl);
+                    expired.push((record, name, recrawl));
+                }
+            }
+            if fetched < SCAN_BATCH_SIZE {
+                break;
+            }
+            offset += fetched;
+        }
+
+        // * Deleting only after the scan keeps the pages above from shifting
+        let mut report = ExpiryReport::default();
+        for (mut record, rule, recrawl) in expired {
+            record.soft_delete();
+            self.store.update_record(&record).await?;
+            ops::record_records_expired(&rule);
+            report.expired += 1;
+
+            if recrawl {
+                if let Some(dedup) = &self.dedup {
+                    dedup
+                        .lock()
+                        .unwrap()
+                        .remove(&record.url, record.content_hash, &record.id);
+                }
+                if let Some(sender) = &self.recrawl {
+                    let _ = sender.send(record.url.clone());
+                }
+                report.recrawl.push(record.url);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Spawns the expiry loop; the first run starts immediately
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let period = Duration::from_secs(self.config.interval_secs.max(1));
+            let mut tick = tokio::time::interval(period);
+            loop {
+                tick.tick().await;
+                match self.run_once().await {
+                    Ok(report) if report.expired > 0 => tracing::info!(
+                        expired = report.expired,
+                        recrawl = report.recrawl.len(),
+                        "Expired records past their TTL"
+                    ),
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!(error = %e, "Record expiry failed"),
+                }
+            }
+        })
+    }
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::InMemoryRecordStore;
+
+    fn rules() -> Vec<ExpiryRule> {
+        vec![
+            ExpiryRule {
+                name: "news".to_string(),
+                url_pattern: Some("/news/".to_string()),
+                max_age_days: 90,
+                recrawl: true,
+                ..Default::default()
+            },
+            ExpiryRule {
+                name: "docs".to_string(),
+                domain: Some("docs.example.com".to_string()),
+                max_age_days: 365,
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_first_matching_rule_applies() {
+        let policy = ExpiryPolicy::new(&rules()).unwrap();
+        let name = |url: &str| policy.rule_for(url).map(|r| r.name.as_str());
+
+        assert_eq!(name("https://example.com/news/tides"), Some("news"));
+        assert_eq!(name("https://eu.docs.example.com/guide"), Some("docs"));
+        assert_eq!(name("https://docs.example.com/news/release"), Some("news"));
+        assert_eq!(name("https://notdocs.example.com/guide"), None);
+
+        let invalid = ExpiryRule {
+            url_pattern: Some("(".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            ExpiryPolicy::new(&[invalid]),
+            Err(ExpiryError::InvalidPattern { index: 0, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_expires_and_requeues() {
+        let store = Arc::new(InMemoryRecordStore::new());
+        let now = current_timestamp();
+        for (url, age_days) in [
+            ("https://example.com/news/old", 120),
+            ("https://example.com/news/fresh", 10),
+            ("https://docs.example.com/old", 120),
+        ] {
+            let mut record = MultimodalRecord::new(url.to_string(), 1, "Text".to_string());
+            record.revised_at = now - age_days * SECONDS_PER_DAY;
+            store.add(record);
+        }
+
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let config = ExpiryConfig {
+            enabled: true,
+            rules: rules(),
+            ..Default::default()
+        };
+        let scheduler = ExpiryScheduler::new(config, store.clone()).unwrap().with_recrawl(sender);
+        let report = scheduler.run_once().await.unwrap();
+
+        assert_eq!(report.expired, 1);
+        assert_eq!(report.recrawl, ["https://example.com/news/old"]);
+        assert_eq!(receiver.try_recv().unwrap(), "https://example.com/news/old");
+        let deleted: Vec<String> = store
+            .records()
+            .into_iter()
+            .filter(|r| r.is_deleted)
+            .map(|r| r.url)
+            .collect();
+        assert_eq!(deleted, ["https://example.com/news/old"]);
+
+        // * Already expired records are not expired again
+        assert_eq!(scheduler.run_once().await.unwrap(), ExpiryReport::default());
+    }
+}
@@ -0,0 +1,460 @@
+// * Batch Export
+// * Streams stored records out of any RecordProvider page by page, so exports never hold
+// * the full record set in memory. JSONL is always available; Parquet needs the
+// * `parquet-export` feature.
+
+use super::ai_worker::{EnrichmentError, RecordProvider};
+use super::clusters::ClusterReport;
+use super::schema::{MultimodalRecord, RecordFilter};
+use std::io::Write;
+use thiserror::Error;
+
+#[cfg(feature = "parquet-export")]
+use parquet::{
+    data_type::{BoolType, ByteArray, ByteArrayType, DataType, FloatType, Int32Type, Int64Type},
+    errors::ParquetError,
+    file::{
+        properties::WriterProperties,
+        writer::{SerializedFileWriter, SerializedRowGroupWriter},
+    },
+    schema::parser::parse_message_type,
+};
+#[cfg(feature = "parquet-export")]
+use std::{fs::File, path::Path, sync::Arc};
+
+// * Records fetched from the provider per page (and written per Parquet row group)
+const EXPORT_BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error("Failed to read records: {0}")]
+    Storage(#[from] EnrichmentError),
+
+    #[error("Failed to write export: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to serialize record: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[cfg(feature = "parquet-export")]
+    #[error("Failed to write Parquet: {0}")]
+    Parquet(#[from] ParquetError),
+}
+
+/// Writes every record matching `filter` as one JSON object per line
+///
+/// Returns the number of exported records. The writer is flushed but not closed.
+///
+/// # Example
+/// ```ignore
+/// let file = BufWriter::new(File::create("records.jsonl")?);
+/// let count = export_jsonl(&store, &RecordFilter::new().enriched_only(true), file).await?;
+/// ```
+pub async fn export_jsonl<P, W>(provider: &P, filter: &RecordFilter, writer: W) -> Result<usize, ExportError>
+where
+    P: RecordProvider + ?Sized,
+    W: Write,
+{
+    export_jsonl_in_batches(provider, filter, writer, EXPORT_BATCH_SIZE).await
+}
+
+async fn export_jsonl_in_batches<P, W>(
+    provider: &P,
+    filter: &RecordFilter,
+    mut writer: W,
+    batch_size: usize,
+) -> Result<usize, ExportError>
+where
+    P: RecordProvider + ?Sized,
+    W: Write,
+{
+    let count = for_each_batch(provider, filter, batch_size, |records| {
+        for record in records {
+            serde_json::to_writer(&mut writer, record)?;
+            writer.write_all(b"\n")?;
+        }
+        Ok(())
+    })
+    .await?;
+    writer.flush()?;
+    Ok(count)
+}
+
+/// Writes a near-duplicate cluster report as one JSON cluster per line, largest first
+///
+/// Returns the number of exported clusters. The writer is flushed but not closed.
+pub fn export_clusters_jsonl<W: Write>(report: &ClusterReport, mut writer: W) -> Result<usize, ExportError> {
+    for entry in &report.clusters {
+        serde_json::to_writer(&mut writer, entry)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(report.clusters.len())
+}
+
+/// Writes every record matching `filter` to a Parquet file, one row group per page
+///
+/// Unsigned fields use the `UINT_32`/`UINT_64` annotations and the embedding is a
+/// nullable `LIST<FLOAT>`. Returns the number of exported records.
+#[cfg(feature = "parquet-export")]
+pub async fn export_parquet<P>(
+    provider: &P,
+    filter: &RecordFilter,
+    path: impl AsRef<Path>,
+) -> Result<usize, ExportError>
+where
+    P: RecordProvider + ?Sized,
+{
+    export_parquet_in_batches(provider, filter, path.as_ref(), EXPORT_BATCH_SIZE).await
+}
+
+#[cfg(feature = "parquet-export")]
+async fn export_parquet_in_batches<P>(
+    provider: &P,
+    filter: &RecordFilter,
+    path: &Path,
+    batch_size: usize,
+) -> Result<usize, ExportError>
+where
+    P: RecordProvider + ?Sized,
+{
+    let schema = Arc::new(parse_message_type(PARQUET_SCHEMA)?);
+    let properties = Arc::new(WriterProperties::builder().build());
+    let mut writer = SerializedFileWriter::new(File::create(path)?, schema, properties)?;
+
+    let count = for_each_batch(provider, filter, batch_size, |records| {
+        let mut row_group = writer.next_row_group()?;
+        write_row_group(&mut row_group, records)?;
+        row_group.close()?;
+        Ok(())
+    })
+    .await?;
+    writer.close()?;
+    Ok(count)
+}
+
+// * Pages through the provider until a short page; returns the number of records seen
+async fn for_each_batch<P, F>(
+    provider: &P,
+    filter: &RecordFilter,
+    batch_size: usize,
+    mut write: F,
+) -> Result<usize, ExportError>
+where
+    P: RecordProvider + ?Sized,
+    F: FnMut(&[MultimodalRecord]) -> Result<(), ExportError>,
+{
+    let batch_size = batch_size.max(1);
+    let mut offset = 0;
+    loop {
+        let records = provider.fetch_records(filter.clone(), offset, batch_size).await?;
+        if !records.is_empty() {
+            write(&records)?;
+        }
+        offset += records.len();
+        if records.len() < batch_size {
+            return Ok(offset);
+        }
+    }
+}
+
+// ! Column order must match write_row_group
+#[cfg(feature = "parquet-export")]
+const PARQUET_SCHEMA: &str = "
+    message multimodal_record {
+        required binary id (UTF8);
+        required binary url (UTF8);
+        required int64 content_hash (UINT_64);
+        optional binary title (UTF8);
+        required binary text_content (UTF8);
+        required binary media_json (UTF8);
+        optional group embedding (LIST) {
+            repeated group list {
+                required float element;
+            }
+        }
+        optional float sentiment_score;
+        optional binary embedding_model (UTF8);
+        optional int32 embedding_version (UINT_32);
+        optional binary entities_json (UTF8);
+        optional binary summary (UTF8);
+        required int32 word_count (UINT_32);
+        required int32 chunk_count (UINT_32);
+        required float quality_score;
+        required boolean is_deleted;
+        optional int64 deleted_at (UINT_64);
+        required int64 created_at (UINT_64);
+        required int64 updated_at (UINT_64);
+        required int32 revision (UINT_32);
+        required int64 revised_at (UINT_64);
+        optional binary parent_url (UTF8);
+        required int32 crawl_depth (UINT_32);
+        optional group redirect_chain (LIST) {
+            repeated group list {
+                required binary element (UTF8);
+            }
+        }
+        optional binary final_url (UTF8);
+        required binary diagnostics (UTF8);
+        optional binary html_snapshot (UTF8);
+        optional binary hreflang_cluster (UTF8);
+    }
+";
+
+#[cfg(feature = "parquet-export")]
+fn write_row_group(
+    row_group: &mut SerializedRowGroupWriter<'_, File>,
+    records: &[MultimodalRecord],
+) -> Result<(), ExportError> {
+    let text = |value: &str| ByteArray::from(value);
+
+    write_required::<ByteArrayType>(row_group, records.iter().map(|r| text(r.id.as_str())).collect())?;
+    write_required::<ByteArrayType>(row_group, records.iter().map(|r| text(&r.url)).collect())?;
+    // * Unsigned values are stored bit-for-bit; the UINT annotation restores them on read
+    write_required::<Int64Type>(row_group, records.iter().map(|r| r.content_hash as i64).collect())?;
+    write_optional::<ByteArrayType>(row_group, records.iter().map(|r| r.title.as_deref().map(text)))?;
+    write_required::<ByteArrayType>(row_group, records.iter().map(|r| text(&r.text_content)).collect())?;
+    write_required::<ByteArrayType>(row_group, records.iter().map(|r| text(&r.media_json)).collect())?;
+    write_list::<FloatType, _>(row_group, records.iter().map(|r| r.embedding.as_ref().map(|e| e.iter().copied())))?;
+    write_optional::<FloatType>(row_group, records.iter().map(|r| r.sentiment_score))?;
+    write_optional::<ByteArrayType>(row_group, records.iter().map(|r| r.embedding_model.as_deref().map(text)))?;
+    write_optional::<Int32Type>(row_group, records.iter().map(|r| r.embedding_version.map(|v| v as i32)))?;
+    write_optional::<ByteArrayType>(row_group, records.iter().map(|r| r.entities_json.as_deref().map(text)))?;
+    write_optional::<ByteArrayType>(row_group, records.iter().map(|r| r.summary.as_deref().map(text)))?;
+    write_required::<Int32Type>(row_group, records.iter().map(|r| r.word_count as i32).collect())?;
+    write_required::<Int32Type>(row_group, records.iter().map(|r| r.chunk_count as i32).collect())?;
+    write_required::<FloatType>(row_group, records.iter().map(|r| r.quality_score).collect())?;
+    write_required::<BoolType>(row_group, records.iter().map(|r| r.is_deleted).collect())?;
+    write_optional::<Int64Type>(row_group, records.iter().map(|r| r.deleted_at.map(|t| t as i64)))?;
+    write_required::<Int64Type>(row_group, records.iter().map(|r| r.created_at as i64).collect())?;
+    write_required::<Int64Type>(row_group, records.iter().map(|r| r.updated_at as i64).collect())?;
+    write_required::<Int32Type>(row_group, records.iter().map(|r| r.revision as i32).collect())?;
+    write_required::<Int64Type>(row_group, records.iter().map(|r| r.revised_at as i64).collect())?;
+    write_optional::<ByteArrayType>(row_group, records.iter().map(|r| r.parent_url.as_deref().map(text)))?;
+    write_required::<Int32Type>(row_group, records.iter().map(|r| r.crawl_depth as i32).collect())?;
+    // * Never null; records without redirects have an empty chain
+    let chains = records.iter().map(|r| Some(r.redirect_chain.iter().map(|url| text(url))));
+    write_list::<ByteArrayType, _>(row_group, chains)?;
+    write_optional::<ByteArrayType>(row_group, records.iter().map(|r| r.final_url.as_deref().map(text)))?;
+    // * Diagnostics are nested structs, so they are stored as a JSON array like media_json
+    let diagnostics = records
+        .iter()
+        .map(|r| serde_json::to_vec(&r.diagnostics).map(ByteArray::from))
+        .collect::<Result<_, _>>()?;
+    write_required::<ByteArrayType>(row_group, diagnostics)?;
+    write_optional::<ByteArrayType>(row_group, records.iter().map(|r| r.html_snapshot.as_deref().map(text)))?;
+    write_optional::<ByteArrayType>(row_group, records.iter().map(|r| r.hreflang_cluster.as_deref().map(text)))?;
+    Ok(())
+}
+
+#[cfg(feature = "parquet-export")]
+fn write_column<T: DataType>(
+    row_group: &mut SerializedRowGroupWriter<'_, File>,
+    values: &[T::T],
+    def_levels: Option<&[i16]>,
+    rep_levels: Option<&[i16]>,
+) -> Result<(), ExportError> {
+    let mut column = row_group
+        .next_column()?
+        .ok_or_else(|| ParquetError::General("Parquet schema has fewer columns than the record".to_string()))?;
+    column.typed::<T>().write_batch(values, def_levels, rep_levels)?;
+    column.close()?;
+    Ok(())
+}
+
+#[cfg(feature = "parquet-export")]
+fn write_required<T: DataType>(
+    row_group: &mut SerializedRowGroupWriter<'_, File>,
+    values: Vec<T::T>,
+) -> Result<(), ExportError> {
+    write_column::<T>(row_group, &values, None, None)
+}
+
+#[cfg(feature = "parquet-export")]
+fn write_optional<T: DataType>(
+    row_group: &mut SerializedRowGroupWriter<'_, File>,
+    values: impl Iterator<Item = Option<T::T>>,
+) -> Result<(), ExportError> {
+    let mut present = Vec::new();
+    let mut def_levels = Vec::new();
+    for value in values {
+        def_levels.push(i16::from(value.is_some()));
+        present.extend(value);
+    }
+    write_column::<T>(row_group, &present, Some(&def_levels), None)
+}
+
+// * Definition levels: 0 = no list, 1 = empty list, 2 = element present
+// * Repetition levels: 0 starts a new record's list, 1 continues it
+#[cfg(feature = "parquet-export")]
+fn write_list<T: DataType, L: IntoIterator<Item = T::T>>(
+    row_group: &mut SerializedRowGroupWriter<'_, File>,
+    lists: impl Iterator<Item = Option<L>>,
+) -> Result<(), ExportError> {
+    let mut values = Vec::new();
+    let mut def_levels = Vec::new();
+    let mut rep_levels = Vec::new();
+    for list in lists {
+        let Some(list) = list else {
+            def_levels.push(0);
+            rep_levels.push(0);
+            continue;
+        };
+        let start = values.len();
+        for (i, value) in list.into_iter().enumerate() {
+            values.push(value);
+            def_levels.push(2);
+            rep_levels.push(i16::from(i > 0));
+        }
+        if values.len() == start {
+            def_levels.push(1);
+            rep_levels.push(0);
+        }
+    }
+    write_column::<T>(row_group, &values, Some(&def_levels), Some(&rep_levels))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::ai_worker::InMemoryRecordStore;
+    use crate::persistence::schema::EMBEDDING_DIM;
+
+    fn store_with_records(count: usize) -> InMemoryRecordStore {
+        let store = InMemoryRecordStore::new();
+        for i in 0..count {
+            let mut record = MultimodalRecord::builder(
+                format!("https://example.com/{}", i),
+                i as u64,
+                format!("Body of page {}", i),
+            )
+            .title(format!("Page {}", i))
+            .word_count(4)
+            .parent_url("https://example.com/")
+            .crawl_depth(1)
+            .html_snapshot(format!("{:016x}", i))
+            .hreflang_cluster("example-cluster")
+            .redirected(
+                vec![format!("http://example.com/{}", i)],
+                format!("https://example.com/{}", i),
+            )
+            .build();
+            if i % 2 == 0 {
+                record.embedding = Some(vec![0.5; EMBEDDING_DIM]);
+                record.sentiment_score = Some(0.25);
+                record.embedding_model = Some("hash-placeholder".to_string());
+                record.embedding_version = Some(1);
+                record.entities_json = Some("[]".to_string());
+                record.summary = Some(format!("Summary of page {}", i));
+            }
+            store.add(record);
+        }
+        store
+    }
+
+    #[tokio::test]
+    async fn test_export_jsonl_pages_through_provider() {
+        let store = store_with_records(5);
+        let mut output = Vec::new();
+
+        let count = export_jsonl_in_batches(&store, &RecordFilter::new(), &mut output, 2)
+            .await
+            .unwrap();
+        assert_eq!(count, 5);
+
+        let urls: Vec<String> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<MultimodalRecord>(line).unwrap().url)
+            .collect();
+        let expected: Vec<String> = (0..5).map(|i| format!("https://example.com/{}", i)).collect();
+        assert_eq!(urls, expected);
+    }
+
+    #[tokio::test]
+    async fn test_export_jsonl_applies_filter() {
+        let store = store_with_records(5);
+        let mut output = Vec::new();
+
+        let count = export_jsonl(&store, &RecordFilter::new().enriched_only(true), &mut output)
+            .await
+            .unwrap();
+        assert_eq!(count, 3);
+        assert_eq!(String::from_utf8(output).unwrap().lines().count(), 3);
+
+        // * An empty store writes nothing
+        let mut output = Vec::new();
+        let empty = InMemoryRecordStore::new();
+        assert_eq!(export_jsonl(&empty, &RecordFilter::new(), &mut output).await.unwrap(), 0);
+        assert!(output.is_empty());
+    }
+
+    #[cfg(feature = "parquet-export")]
+    #[tokio::test]
+    async fn test_export_parquet_roundtrip() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+        use crate::engine::slow_path::{DiagnosticKind, PageDiagnostic};
+        use parquet::record::{Field, Row};
+
+        // * Looks up a column of a read-back row by name
+        fn column<'a>(row: &'a Row, name: &str) -> &'a Field {
+            row.get_column_iter()
+                .find(|(column, _)| column.as_str() == name)
+                .map(|(_, field)| field)
+                .unwrap()
+        }
+
+        let store = store_with_records(4);
+        let mut deleted = MultimodalRecord::new("https://example.com/deleted".into(), 99, "Gone".into());
+        deleted.soft_delete();
+        deleted.diagnostics = vec![PageDiagnostic {
+            kind: DiagnosticKind::UncaughtError,
+            message: "TypeError: x is undefined".to_string(),
+            source: Some("app.js:1:10".to_string()),
+            timestamp: 1_700_000_000_000,
+        }];
+        store.add(deleted.clone());
+        let path = std::env::temp_dir().join(format!("titan-flow-export-{}.parquet", std::process::id()));
+
+        let count = export_parquet_in_batches(&store, &RecordFilter::new().include_deleted(true), &path, 2)
+            .await
+            .unwrap();
+        assert_eq!(count, 5);
+
+        let reader = SerializedFileReader::try_from(File::open(&path).unwrap()).unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.file_metadata().num_rows(), 5);
+        assert_eq!(metadata.num_row_groups(), 3);
+        assert_eq!(metadata.file_metadata().schema_descr().num_columns(), 28);
+
+        let rows: Vec<Row> = reader.get_row_iter(None).unwrap().collect::<Result<_, _>>().unwrap();
+        assert_eq!(rows.len(), 5);
+        assert_eq!(column(&rows[0], "embedding_model"), &Field::Str("hash-placeholder".to_string()));
+        assert_eq!(column(&rows[0], "embedding_version"), &Field::UInt(1));
+        assert_eq!(column(&rows[0], "entities_json"), &Field::Str("[]".to_string()));
+        assert_eq!(column(&rows[0], "summary"), &Field::Str("Summary of page 0".to_string()));
+        assert_eq!(column(&rows[0], "deleted_at"), &Field::Null);
+        assert_eq!(column(&rows[0], "parent_url"), &Field::Str("https://example.com/".to_string()));
+        assert_eq!(column(&rows[0], "crawl_depth"), &Field::UInt(1));
+        let Field::ListInternal(chain) = column(&rows[0], "redirect_chain") else {
+            panic!("redirect_chain is not a list");
+        };
+        assert_eq!(chain.elements(), [Field::Str("http://example.com/0".to_string())]);
+        assert_eq!(column(&rows[0], "final_url"), &Field::Str("https://example.com/0".to_string()));
+        assert_eq!(column(&rows[0], "diagnostics"), &Field::Str("[]".to_string()));
+        assert_eq!(column(&rows[0], "html_snapshot"), &Field::Str("0000000000000000".to_string()));
+        assert_eq!(column(&rows[0], "hreflang_cluster"), &Field::Str("example-cluster".to_string()));
+        assert_eq!(column(&rows[1], "embedding_model"), &Field::Null);
+        let gone = rows
+            .iter()
+            .find(|row| column(row, "url") == &Field::Str(deleted.url.clone()))
+            .unwrap();
+        assert_eq!(column(gone, "deleted_at"), &Field::ULong(deleted.deleted_at.unwrap()));
+        let Field::Str(diagnostics) = column(gone, "diagnostics") else {
+            panic!("diagnostics is not a string");
+        };
+        assert_eq!(serde_json::from_str::<Vec<PageDiagnostic>>(diagnostics).unwrap(), deleted.diagnostics);
+
+        std::fs::remove_file(&path).ok();
+    }
+}
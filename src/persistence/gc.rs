@@ -0,0 +1,195 @@
+// * [FR-05] Soft-Delete Garbage Collection
+// * Background maintenance job: hard-deletes records soft-deleted longer than the retention
+// * period, forgets them in the dedup index so their URLs can be crawled again, then compacts the store.
+
+use super::ai_worker::{EnrichmentError, RecordPurger};
+use super::dedup::DedupManager;
+use crate::ops;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, Instant};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Garbage collection settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GcConfig {
+    /// Run the garbage collector during crawls
+    pub enabled: bool,
+    /// Days a soft-deleted record is kept before it is removed for good
+    pub retention_days: u64,
+    /// Seconds between collection runs
+    pub interval_secs: u64,
+}
+
+impl Default for GcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retention_days: 30,
+            interval_secs: 3600,
+        }
+    }
+}
+
+/// Outcome of one collection run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcReport {
+    /// Records permanently removed
+    pub reclaimed: usize,
+    /// Removed records that were still known to the dedup index
+    pub dedup_removed: usize,
+}
+
+/// Periodically purges soft-deleted records past their retention period
+///
+/// # Example
+/// ```ignore
+/// let gc = GarbageCollector::new(GcConfig::default(), store.clone()).with_dedup(dedup);
+/// let report = gc.run_once().await?;
+/// ```
+pub struct GarbageCollector {
+    config: GcConfig,
+    store: Arc<dyn RecordPurger>,
+    dedup: Option<Arc<Mutex<DedupManager>>>,
+}
+
+impl GarbageCollector {
+    pub fn new(config: GcConfig, store: Arc<dyn RecordPurger>) -> Self {
+        Self {
+            config,
+            store,
+            dedup: None,
+        }
+    }
+
+    /// Also forgets purged records in this dedup index (URL filter, content hash and LSH entry)
+    pub fn with_dedup(mut self, dedup: Arc<Mutex<DedupManager>>) -> Self {
+        self.dedup = Some(dedup);
+        self
+    }
+
+    pub fn config(&self) -> &GcConfig {
+        &self.config
+    }
+
+    /// Purges records soft-deleted more than `retention_days` ago, then compacts the store
+    pub async fn run_once(&self) -> Result<GcReport, EnrichmentError> {
+        let started = Instant::now();
+        let retention = self.config.retention_days.saturating_mul(SECONDS_PER_DAY);
+        let cutoff = current_timestamp().saturating_sub(retention);
+
+        let purged = self.store.purge_deleted(cutoff).await?;
+        let dedup_removed = match &self.dedup {
+            Some(dedup) => {
+                let mut dedup = dedup.lock().unwrap();
+                purged
+                    .iter()
+                    .filter(|r| dedup.remove(&r.url, r.content_hash, &r.id))
+                    .count()
+            }
+            None => 0,
+        };
+        if !purged.is_empty() {
+            self.store.compact().await?;
+        }
+
+        let report = GcReport {
+            reclaimed: purged.len(),
+            dedup_removed,
+        };
+        ops::record_gc_run(report.reclaimed, started.elapsed().as_secs_f64());
+        Ok(report)
+    }
+
+    /// Spawns the collection loop; the first run starts immediately
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let period = Duration::from_secs(self.config.interval_secs.max(1));
+            let mut tick = tokio::time::interval(period);
+            loop {
+                tick.tick().await;
+                match self.run_once().await {
+                    Ok(report) if report.reclaimed > 0 => tracing::info!(
+                        reclaimed = report.reclaimed,
+                        dedup_removed = report.dedup_removed,
+                        "Garbage collected soft-deleted records"
+                    ),
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!(error = %e, "Garbage collection failed"),
+                }
+            }
+        })
+    }
+}
+
+impl std::fmt::Debug for GarbageCollector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GarbageCollector")
+            .field("config", &self.config)
+            .field("dedup", &self.dedup.is_some())
+            .finish()
+    }
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::{InMemoryRecordStore, MultimodalRecord, RecordHistory};
+
+    fn record(url: &str, text: &str) -> MultimodalRecord {
+        MultimodalRecord::new(url.to_string(), MultimodalRecord::hash_content(text), text.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_gc_purges_expired_soft_deletes() {
+        let store = Arc::new(InMemoryRecordStore::new());
+        let dedup = Arc::new(Mutex::new(DedupManager::new()));
+        let now = current_timestamp();
+
+        let pages = [
+            ("https://example.com/old", "Harbour news from last spring, long since retracted"),
+            ("https://example.com/recent", "A correction published this week about the ferry timetable"),
+            ("https://example.com/live", "The current lighthouse opening hours for visitors"),
+        ];
+        for (i, (url, text)) in pages.iter().enumerate() {
+            let mut record = record(url, text);
+            dedup
+                .lock()
+                .unwrap()
+                .check_and_index(url, record.content_hash, text, &record.id);
+            if i < 2 {
+                record.soft_delete();
+            }
+            // * Deleted 45 days ago, past the 30 day retention
+            if i == 0 {
+                record.deleted_at = Some(now - 45 * SECONDS_PER_DAY);
+            }
+            store.add(record);
+        }
+
+        let gc = GarbageCollector::new(GcConfig::default(), store.clone()).with_dedup(Arc::clone(&dedup));
+        let report = gc.run_once().await.unwrap();
+
+        assert_eq!(report, GcReport { reclaimed: 1, dedup_removed: 1 });
+        let urls: Vec<String> = store.records().into_iter().map(|r| r.url).collect();
+        assert_eq!(urls, ["https://example.com/recent", "https://example.com/live"]);
+        assert!(store.revision_history("https://example.com/old").await.unwrap().is_empty());
+
+        // * The purged page can be crawled again; the others are still known
+        assert!(!dedup.lock().unwrap().check_url("https://example.com/old"));
+        assert!(dedup.lock().unwrap().check_url("https://example.com/recent"));
+
+        assert_eq!(gc.run_once().await.unwrap(), GcReport::default());
+    }
+}
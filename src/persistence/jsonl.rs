@@ -0,0 +1,182 @@
+// * JSONL Record Log
+// * Appends records to a JSON-lines file as the crawl stores them, so records.jsonl grows
+// * during the run instead of being written once at the end. The file is a log: a later
+// * line with the same id (an enriched copy, say) supersedes the earlier one on read.
+
+use super::ai_worker::{AsyncResult, EnrichmentError, RecordSink};
+use super::schema::MultimodalRecord;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum JsonlError {
+    #[error("Record log I/O failed: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("{path}:{line}: {source}")]
+    Parse {
+        path: PathBuf,
+        line: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// `RecordSink` appending each record to a JSON-lines file
+///
+/// Every record is flushed as it is written, so a crash or kill loses at most the
+/// record being written.
+///
+/// # Example
+/// ```ignore
+/// let log = Arc::new(JsonlRecordSink::create("out/records.jsonl", false)?);
+/// let orchestrator = Orchestrator::new().with_result_sink(log.clone());
+/// ```
+pub struct JsonlRecordSink {
+    path: PathBuf,
+    writer: Mutex<BufWriter<File>>,
+    written: AtomicUsize,
+}
+
+impl JsonlRecordSink {
+    /// Opens `path`, truncating it unless `append` is set
+    pub fn create(path: impl AsRef<Path>, append: bool) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = if append {
+            fs::OpenOptions::new().create(true).append(true).open(&path)?
+        } else {
+            File::create(&path)?
+        };
+        Ok(Self {
+            path,
+            writer: Mutex::new(BufWriter::new(file)),
+            written: AtomicUsize::new(0),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Lines written since the file was opened
+    pub fn written(&self) -> usize {
+        self.written.load(Ordering::Relaxed)
+    }
+
+    /// Appends one record and flushes it to the file
+    pub fn append(&self, record: &MultimodalRecord) -> io::Result<()> {
+        let line = serde_json::to_vec(record)?;
+        let mut writer = self.writer.lock().unwrap();
+        writer.write_all(&line)?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+        self.written.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+impl RecordSink for JsonlRecordSink {
+    fn insert_record(&self, record: MultimodalRecord) -> AsyncResult<()> {
+        let result = self
+            .append(&record)
+            .map_err(|e| EnrichmentError::StorageError(format!("{}: {}", self.path.display(), e)));
+        Box::pin(async move { result })
+    }
+}
+
+impl RecordSink for Arc<JsonlRecordSink> {
+    fn insert_record(&self, record: MultimodalRecord) -> AsyncResult<()> {
+        (**self).insert_record(record)
+    }
+}
+
+/// Reads a record log, keeping the last line written for each record id
+///
+/// Records come back in the order their id first appeared.
+pub fn read_jsonl_records(path: impl AsRef<Path>) -> Result<Vec<MultimodalRecord>, JsonlError> {
+    let path = path.as_ref();
+    let reader = BufReader::new(File::open(path)?);
+    let mut records: Vec<MultimodalRecord> = Vec::new();
+    let mut positions = HashMap::new();
+
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: MultimodalRecord = serde_json::from_str(&line).map_err(|source| JsonlError::Parse {
+            path: path.to_path_buf(),
+            line: idx + 1,
+            source,
+        })?;
+        match positions.get(&record.id) {
+            Some(&position) => records[position] = record,
+            None => {
+                positions.insert(record.id.clone(), records.len());
+                records.push(record);
+            }
+        }
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_log(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("titan-flow-jsonl-{}-{}.jsonl", name, std::process::id()))
+    }
+
+    #[tokio::test]
+    async fn test_records_are_on_disk_as_soon_as_they_are_stored() {
+        let path = temp_log("stream");
+        let log = Arc::new(JsonlRecordSink::create(&path, false).unwrap());
+        log.insert_record(MultimodalRecord::new("https://a.com".into(), 1, "first".into()))
+            .await
+            .unwrap();
+
+        // * Readable while the sink is still open
+        let loaded = read_jsonl_records(&path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].url, "https://a.com");
+        assert_eq!(log.written(), 1);
+
+        // * Reopening in append mode keeps earlier lines
+        drop(log);
+        let log = JsonlRecordSink::create(&path, true).unwrap();
+        log.append(&MultimodalRecord::new("https://b.com".into(), 2, "second".into())).unwrap();
+        assert_eq!(read_jsonl_records(&path).unwrap().len(), 2);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_later_lines_supersede_earlier_ones() {
+        let path = temp_log("supersede");
+        let log = JsonlRecordSink::create(&path, false).unwrap();
+        let mut first = MultimodalRecord::new("https://a.com".into(), 1, "first".into());
+        log.append(&first).unwrap();
+        log.append(&MultimodalRecord::new("https://b.com".into(), 2, "second".into())).unwrap();
+        first.sentiment_score = Some(0.5);
+        log.append(&first).unwrap();
+        fs::OpenOptions::new().append(true).open(&path).unwrap().write_all(b"{oops\n").unwrap();
+
+        let err = read_jsonl_records(&path).unwrap_err();
+        assert!(matches!(err, JsonlError::Parse { line: 4, .. }));
+
+        let text = fs::read_to_string(&path).unwrap();
+        fs::write(&path, text.trim_end_matches("{oops\n")).unwrap();
+        let loaded = read_jsonl_records(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].sentiment_score, Some(0.5));
+        assert_eq!(loaded[1].url, "https://b.com");
+    }
+}
@@ -0,0 +1,272 @@
+// * [EDD-6] LanceDB Record Store
+// * Keeps `MultimodalRecord`s in a LanceDB table so `enrich` can work on a store without
+// * loading it into memory. Each row holds the full record as JSON next to the columns
+// * queries filter on, so new record fields need no table migration.
+
+use super::ai_worker::{AsyncResult, EnrichmentError, RecordProvider, RecordSink, RecordUpdater};
+use super::schema::{EnrichmentBatch, EnrichmentFilter, MultimodalRecord, RecordFilter, EMBEDDING_DIM};
+use arrow_array::types::Float32Type;
+use arrow_array::{
+    Array, ArrayRef, BooleanArray, FixedSizeListArray, RecordBatch, RecordBatchIterator, RecordBatchReader,
+    StringArray,
+};
+use futures::TryStreamExt;
+use lancedb::arrow::arrow_schema::{DataType, Field, Schema, SchemaRef};
+use lancedb::query::{ExecutableQuery, QueryBase};
+use lancedb::Table;
+use std::sync::Arc;
+
+// * Filter selecting rows the enrichment worker still has to process
+const PENDING_FILTER: &str = "needs_enrichment = true AND is_deleted = false";
+
+fn storage_error(e: impl std::fmt::Display) -> EnrichmentError {
+    EnrichmentError::StorageError(format!("LanceDB: {}", e))
+}
+
+/// Arrow schema of a record table
+///
+/// - `id`, `url`: record identity
+/// - `is_deleted`, `needs_enrichment`: filter columns kept in sync with the record
+/// - `vector`: the embedding (null until enriched or when not `EMBEDDING_DIM` long)
+/// - `record`: the full record as JSON, which is what reads return
+pub fn record_table_schema() -> SchemaRef {
+    let item = Arc::new(Field::new("item", DataType::Float32, true));
+    Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("url", DataType::Utf8, false),
+        Field::new("is_deleted", DataType::Boolean, false),
+        Field::new("needs_enrichment", DataType::Boolean, false),
+        Field::new("vector", DataType::FixedSizeList(item, EMBEDDING_DIM as i32), true),
+        Field::new("record", DataType::Utf8, false),
+    ]))
+}
+
+/// `RecordProvider`, `RecordUpdater` and `RecordSink` over one LanceDB table
+///
+/// # Example
+/// ```ignore
+/// let store = LanceRecordStore::open("./data/lance", "records").await?;
+/// let handle = worker.start(store.clone(), store.clone()).await;
+/// ```
+#[derive(Clone)]
+pub struct LanceRecordStore {
+    table: Table,
+}
+
+impl LanceRecordStore {
+    /// Opens `table` in the database at `uri`, creating it empty when it does not exist
+    pub async fn open(uri: &str, table: &str) -> Result<Self, EnrichmentError> {
+        let db = lancedb::connect(uri).execute().await.map_err(storage_error)?;
+        let names = db.table_names().execute().await.map_err(storage_error)?;
+        let table = if names.iter().any(|name| name == table) {
+            db.open_table(table).execute().await
+        } else {
+            db.create_empty_table(table, record_table_schema()).execute().await
+        }
+        .map_err(storage_error)?;
+        Ok(Self { table })
+    }
+
+    /// Number of rows in the table, soft-deleted records included
+    pub async fn count(&self) -> Result<usize, EnrichmentError> {
+        self.table.count_rows(None).await.map_err(storage_error)
+    }
+
+    /// Inserts records, replacing rows that already have their id
+    pub async fn upsert(&self, records: &[MultimodalRecord]) -> Result<(), EnrichmentError> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        let mut merge = self.table.merge_insert(&["id"]);
+        merge.when_matched_update_all(None).when_not_matched_insert_all();
+        merge.execute(to_reader(records)?).await.map_err(storage_error)
+    }
+
+    // * Reads every record a filter selects; row order is not stable across writes
+    async fn scan(&self, filter: Option<&str>) -> Result<Vec<MultimodalRecord>, EnrichmentError> {
+        let mut query = self.table.query();
+        if let Some(filter) = filter {
+            query = query.only_if(filter);
+        }
+        let batches: Vec<RecordBatch> = query
+            .execute()
+            .await
+            .map_err(storage_error)?
+            .try_collect()
+            .await
+            .map_err(storage_error)?;
+        let mut records = Vec::new();
+        for batch in &batches {
+            records.extend(from_batch(batch)?);
+        }
+        Ok(records)
+    }
+}
+
+impl RecordProvider for LanceRecordStore {
+    fn fetch_unenriched(&self, filter: EnrichmentFilter) -> AsyncResult<EnrichmentBatch> {
+        let store = self.clone();
+        Box::pin(async move {
+            // * Re-embedding, NER and summary backfills select enriched rows too
+            let plain = filter.current_embedding.is_none() && !filter.missing_entities && !filter.missing_summary;
+            let predicate = match (plain, filter.include_deleted) {
+                (true, false) => Some(PENDING_FILTER),
+                (true, true) => Some("needs_enrichment = true"),
+                (false, false) => Some("is_deleted = false"),
+                (false, true) => None,
+            };
+            let records = store
+                .scan(predicate)
+                .await?
+                .into_iter()
+                .filter(|r| filter.matches(r))
+                .take(filter.limit)
+                .collect();
+            Ok(EnrichmentBatch::new(records))
+        })
+    }
+
+    fn fetch_records(
+        &self,
+        filter: RecordFilter,
+        offset: usize,
+        limit: usize,
+    ) -> AsyncResult<Vec<MultimodalRecord>> {
+        let store = self.clone();
+        Box::pin(async move {
+            let predicate = (!filter.include_deleted).then_some("is_deleted = false");
+            let mut records = store.scan(predicate).await?;
+            // * Creation order gives pages a stable order the table itself does not
+            records.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+            Ok(records
+                .into_iter()
+                .filter(|r| filter.matches(r))
+                .skip(offset)
+                .take(limit)
+                .collect())
+        })
+    }
+
+    fn count_unenriched(&self) -> AsyncResult<Option<usize>> {
+        let table = self.table.clone();
+        Box::pin(async move {
+            let pending = table
+                .count_rows(Some(PENDING_FILTER.to_string()))
+                .await
+                .map_err(storage_error)?;
+            Ok(Some(pending))
+        })
+    }
+}
+
+impl RecordUpdater for LanceRecordStore {
+    fn update_record(&self, record: &MultimodalRecord) -> AsyncResult<()> {
+        let (store, record) = (self.clone(), record.clone());
+        Box::pin(async move {
+            let id_filter = format!("id = '{}'", record.id.replace('\'', "''"));
+            if store.table.count_rows(Some(id_filter)).await.map_err(storage_error)? == 0 {
+                return Err(EnrichmentError::StorageError("Record not found".to_string()));
+            }
+            store.upsert(std::slice::from_ref(&record)).await
+        })
+    }
+}
+
+impl RecordSink for LanceRecordStore {
+    fn insert_record(&self, record: MultimodalRecord) -> AsyncResult<()> {
+        let store = self.clone();
+        Box::pin(async move { store.upsert(std::slice::from_ref(&record)).await })
+    }
+}
+
+fn to_reader(records: &[MultimodalRecord]) -> Result<Box<dyn RecordBatchReader + Send>, EnrichmentError> {
+    let schema = record_table_schema();
+    let json = records
+        .iter()
+        .map(serde_json::to_string)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| EnrichmentError::StorageError(e.to_string()))?;
+    let vectors = records.iter().map(|r| {
+        r.embedding
+            .as_ref()
+            .filter(|embedding| embedding.len() == EMBEDDING_DIM)
+            .map(|embedding| embedding.iter().copied().map(Some).collect::<Vec<_>>())
+    });
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(records.iter().map(|r| r.id.as_str()))),
+        Arc::new(StringArray::from_iter_values(records.iter().map(|r| r.url.as_str()))),
+        Arc::new(BooleanArray::from_iter(records.iter().map(|r| Some(r.is_deleted)))),
+        Arc::new(BooleanArray::from_iter(records.iter().map(|r| Some(r.needs_enrichment())))),
+        Arc::new(FixedSizeListArray::from_iter_primitive::<Float32Type, _, _>(
+            vectors,
+            EMBEDDING_DIM as i32,
+        )),
+        Arc::new(StringArray::from_iter_values(json)),
+    ];
+    let batch = RecordBatch::try_new(schema.clone(), columns).map_err(storage_error)?;
+    Ok(Box::new(RecordBatchIterator::new(vec![Ok(batch)].into_iter(), schema)))
+}
+
+fn from_batch(batch: &RecordBatch) -> Result<Vec<MultimodalRecord>, EnrichmentError> {
+    let column = batch
+        .column_by_name("record")
+        .and_then(|column| column.as_any().downcast_ref::<StringArray>())
+        .ok_or_else(|| storage_error("table has no 'record' column"))?;
+    (0..column.len())
+        .filter(|&row| column.is_valid(row))
+        .map(|row| serde_json::from_str(column.value(row)).map_err(|e| EnrichmentError::StorageError(e.to_string())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_db(name: &str) -> String {
+        let dir = std::env::temp_dir().join(format!("titan-flow-lance-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir.to_string_lossy().into_owned()
+    }
+
+    #[tokio::test]
+    async fn test_records_roundtrip_and_enrichment_updates() {
+        let uri = temp_db("roundtrip");
+        let store = LanceRecordStore::open(&uri, "records").await.unwrap();
+        let first = MultimodalRecord::new("https://a.com".into(), 1, "first".into());
+        store.insert_record(first.clone()).await.unwrap();
+        store
+            .insert_record(MultimodalRecord::new("https://b.com".into(), 2, "second".into()))
+            .await
+            .unwrap();
+
+        assert_eq!(store.count().await.unwrap(), 2);
+        assert_eq!(store.count_unenriched().await.unwrap(), Some(2));
+        assert_eq!(store.fetch_unenriched(EnrichmentFilter::new(1)).await.unwrap().len(), 1);
+
+        let mut enriched = first.clone();
+        enriched.embedding = Some(vec![0.1; EMBEDDING_DIM]);
+        enriched.sentiment_score = Some(0.5);
+        store.update_record(&enriched).await.unwrap();
+
+        // * Reopening sees the update; the row was replaced, not duplicated
+        let store = LanceRecordStore::open(&uri, "records").await.unwrap();
+        assert_eq!(store.count().await.unwrap(), 2);
+        let pending = store.fetch_unenriched(EnrichmentFilter::new(10)).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending.records[0].url, "https://b.com");
+        let all = store.fetch_records(RecordFilter::new(), 0, 10).await.unwrap();
+        let stored = all.iter().find(|r| r.id == first.id).unwrap();
+        assert_eq!(stored.sentiment_score, Some(0.5));
+        let _ = std::fs::remove_dir_all(&uri);
+    }
+
+    #[tokio::test]
+    async fn test_updating_a_missing_record_fails() {
+        let uri = temp_db("missing");
+        let store = LanceRecordStore::open(&uri, "records").await.unwrap();
+        let record = MultimodalRecord::new("https://a.com".into(), 1, "first".into());
+        assert!(store.update_record(&record).await.is_err());
+        assert_eq!(store.count().await.unwrap(), 0);
+        let _ = std::fs::remove_dir_all(&uri);
+    }
+}
@@ -1,8 +1,15 @@
 // * [EDD-4] Link Intrinsic Scorer
 // * Scores URLs based on intrinsic properties for crawl prioritization
 
-use std::sync::LazyLock;
+use std::collections::{BinaryHeap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, RwLock};
 use regex::Regex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use crate::engine::url_filter::UrlFilter;
+use crate::persistence::schema::cosine_similarity;
+use crate::refinery::language::{self, Language};
 
 // * Scoring constants from specification
 const SCORE_MIN: f32 = 0.0;
@@ -27,6 +34,9 @@ const MIN_TITLE_LENGTH: usize = 3;
 // * Maximum path depth before penalty
 const MAX_PATH_DEPTH: usize = 4;
 
+// * Leading path directories turned into learning features
+const LEARNED_PATH_SEGMENTS: usize = 2;
+
 // * Precompiled regex patterns for keyword detection
 static NAV_KEYWORDS: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"(?i)\b(nav|menu|sidebar|header|footer|breadcrumb|navigation)\b").unwrap()
@@ -61,6 +71,12 @@ pub struct ScoredLink {
     pub anchor_text: String,
     pub score: f32,
     pub breakdown: ScoreBreakdown,
+    /// Links followed from a seed to reach this URL (seeds are depth 0)
+    pub depth: usize,
+    /// Page the link was found on (None for seeds)
+    pub parent_url: Option<String>,
+    /// When the link was queued (Unix seconds, 0 if never queued)
+    pub discovered_at: u64,
 }
 
 impl ScoredLink {
@@ -72,8 +88,19 @@ impl ScoredLink {
             anchor_text,
             score: 5.0, // * Base score
             breakdown,
+            depth: 0,
+            parent_url: None,
+            discovered_at: 0,
         }
     }
+
+    /// Sets where the link was found
+    pub fn with_origin(mut self, depth: usize, parent_url: Option<String>, discovered_at: u64) -> Self {
+        self.depth = depth;
+        self.parent_url = parent_url;
+        self.discovered_at = discovered_at;
+        self
+    }
 }
 
 impl PartialEq for ScoredLink {
@@ -113,17 +140,31 @@ pub struct ScoreBreakdown {
     pub query_penalty: f32,
     pub fragment_penalty: f32,
     pub keyword_bonus: f32,
+    /// Seed-domain bonus, external-domain penalty and authority adjustment
+    pub domain_bonus: f32,
+    /// Topic relevance adjustment and anchor language penalty
+    pub topic_bonus: f32,
+    /// Adjustment learned from crawl outcomes (0 unless learning is enabled)
+    pub learned_bonus: f32,
     pub final_score: f32,
+    /// True when the URL filter rejected the link
+    pub filtered: bool,
 }
 
 /// Link scorer for prioritizing crawl queue
 #[derive(Debug, Clone)]
 pub struct LinkScorer {
     config: ScorerConfig,
+    url_filter: Option<Arc<UrlFilter>>,
+    learner: Option<Arc<LinkLearner>>,
+    seed_domains: Vec<String>,
+    authority: Option<Arc<DomainAuthority>>,
+    embedder: Option<TopicEmbedding>,
 }
 
 /// Configuration for link scoring
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ScorerConfig {
     /// Base score for all links
     pub base_score: f32,
@@ -137,6 +178,108 @@ pub struct ScorerConfig {
     pub high_value_paths: Vec<String>,
     /// Custom low-value path patterns
     pub low_value_paths: Vec<String>,
+    /// Domains whose links count as on-site, subdomains included
+    ///
+    /// The orchestrator adds the crawl's seed hosts to this list.
+    pub seed_domains: Vec<String>,
+    /// Bonus for links to a seed domain
+    pub seed_domain_bonus: f32,
+    /// Penalty subtracted from links leaving the seed domains (none without seed domains)
+    pub external_domain_penalty: f32,
+    /// File of `domain score` lines with authority scores between 0 and 1
+    pub authority_file: Option<PathBuf>,
+    /// Bonus for authority 1.0 and penalty for 0.0; unlisted domains are neutral
+    pub authority_weight: f32,
+    /// Target topic and languages for focused crawls
+    pub topic: TopicConfig,
+    /// Adjusts scores from crawl outcomes reported via `LinkScorer::record_outcome`
+    pub learning: LearningConfig,
+}
+
+/// Topic relevance settings for focused crawling
+///
+/// # Example
+/// ```toml
+/// [scorer.topic]
+/// keywords = ["climate", "emissions", "carbon tax"]
+/// languages = ["en", "de"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TopicConfig {
+    /// Words or phrases describing the topic, matched against anchor text and URL tokens
+    pub keywords: Vec<String>,
+    /// Topic description compared with links by embedding (needs `LinkScorer::with_topic_embedder`)
+    pub description: Option<String>,
+    /// Bonus for a fully relevant link and penalty for an unrelated one
+    pub weight: f32,
+    /// Preferred anchor text languages; empty accepts any
+    pub languages: Vec<Language>,
+    /// Penalty for anchor text detected in a language not listed
+    pub language_penalty: f32,
+}
+
+impl Default for TopicConfig {
+    fn default() -> Self {
+        Self {
+            keywords: Vec::new(),
+            description: None,
+            weight: 2.0,
+            languages: Vec::new(),
+            language_penalty: 1.0,
+        }
+    }
+}
+
+/// Embeds link text for topic relevance scoring
+///
+/// Called for every scored link, so implementations should be fast (e.g. a local model).
+pub trait TopicEmbedder: Send + Sync {
+    /// Returns the embedding of `text`, or None if it cannot be embedded
+    fn embed(&self, text: &str) -> Option<Vec<f32>>;
+}
+
+// * An embedder with the topic description already embedded
+#[derive(Clone)]
+struct TopicEmbedding {
+    embedder: Arc<dyn TopicEmbedder>,
+    topic: Vec<f32>,
+}
+
+impl std::fmt::Debug for TopicEmbedding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TopicEmbedding")
+            .field("dimensions", &self.topic.len())
+            .finish()
+    }
+}
+
+/// Online learning settings for the link scorer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LearningConfig {
+    /// Learn from crawl outcomes
+    pub enabled: bool,
+    /// Step size of each logistic regression update
+    pub learning_rate: f32,
+    /// Largest score change learning may apply, in either direction
+    pub max_adjustment: f32,
+    /// Outcomes to observe before learned adjustments are applied
+    pub min_observations: u64,
+    /// Cap on distinct features (hosts, path directories) tracked
+    pub max_features: usize,
+}
+
+impl Default for LearningConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            learning_rate: 0.1,
+            max_adjustment: 2.0,
+            min_observations: 20,
+            max_features: 10_000,
+        }
+    }
 }
 
 impl Default for ScorerConfig {
@@ -164,21 +307,245 @@ impl Default for ScorerConfig {
                 "/click/".to_string(),
                 "/redirect/".to_string(),
             ],
+            seed_domains: Vec::new(),
+            seed_domain_bonus: 1.0,
+            external_domain_penalty: 1.0,
+            authority_file: None,
+            authority_weight: 1.0,
+            topic: TopicConfig::default(),
+            learning: LearningConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum AuthorityError {
+    #[error("Failed to read domain authority file {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Invalid domain authority entry at line {line}: {message}")]
+    Parse { line: usize, message: String },
+}
+
+/// Per-domain authority scores between 0 and 1
+///
+/// Subdomains inherit the score of their closest listed parent domain.
+///
+/// # Format
+/// ```text
+/// # domain  score
+/// docs.rs   0.9
+/// example.com,0.4
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DomainAuthority {
+    scores: HashMap<String, f32>,
+}
+
+impl DomainAuthority {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses `domain score` lines (whitespace or comma separated, `#` comments)
+    pub fn parse(contents: &str) -> Result<Self, AuthorityError> {
+        let mut authority = Self::new();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let parse_error = |message: &str| AuthorityError::Parse {
+                line: index + 1,
+                message: message.to_string(),
+            };
+            let mut fields = line.split(|c: char| c == ',' || c.is_whitespace()).filter(|f| !f.is_empty());
+            let (Some(domain), Some(score), None) = (fields.next(), fields.next(), fields.next()) else {
+                return Err(parse_error("expected `domain score`"));
+            };
+            let score: f32 = score.parse().map_err(|_| parse_error("score is not a number"))?;
+            if !(0.0..=1.0).contains(&score) {
+                return Err(parse_error("score must be between 0 and 1"));
+            }
+            authority.insert(domain, score);
         }
+        Ok(authority)
+    }
+
+    /// Reads and parses an authority file
+    pub fn from_file(path: &Path) -> Result<Self, AuthorityError> {
+        let contents = std::fs::read_to_string(path).map_err(|source| AuthorityError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(&contents)
+    }
+
+    pub fn insert(&mut self, domain: &str, score: f32) {
+        self.scores.insert(domain.trim_end_matches('.').to_lowercase(), score);
+    }
+
+    /// Score of a host or its closest listed parent domain
+    pub fn get(&self, host: &str) -> Option<f32> {
+        let host = host.to_lowercase();
+        let mut candidate = host.as_str();
+        loop {
+            if let Some(score) = self.scores.get(candidate) {
+                return Some(*score);
+            }
+            candidate = candidate.split_once('.')?.1;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
     }
 }
 
 impl LinkScorer {
     /// Creates a new link scorer with default configuration
     pub fn new() -> Self {
-        Self {
-            config: ScorerConfig::default(),
-        }
+        Self::with_config(ScorerConfig::default())
     }
 
     /// Creates a new link scorer with custom configuration
     pub fn with_config(config: ScorerConfig) -> Self {
-        Self { config }
+        let learner = config
+            .learning
+            .enabled
+            .then(|| Arc::new(LinkLearner::new(config.learning.clone())));
+        let seed_domains = config.seed_domains.iter().map(|d| site_domain(d)).collect();
+        Self {
+            config,
+            url_filter: None,
+            learner,
+            seed_domains,
+            authority: None,
+            embedder: None,
+        }
+    }
+
+    /// Creates a scorer and loads the configured `authority_file`, if any
+    pub fn from_config(config: ScorerConfig) -> Result<Self, AuthorityError> {
+        let authority = config
+            .authority_file
+            .as_deref()
+            .map(DomainAuthority::from_file)
+            .transpose()?;
+        let scorer = Self::with_config(config);
+        Ok(match authority {
+            Some(authority) => scorer.with_domain_authority(Arc::new(authority)),
+            None => scorer,
+        })
+    }
+
+    /// Adds domains whose links count as on-site (subdomains included)
+    pub fn with_seed_domains<I, S>(mut self, domains: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for domain in domains {
+            let domain = site_domain(domain.as_ref());
+            if !self.seed_domains.contains(&domain) {
+                self.seed_domains.push(domain);
+            }
+        }
+        self
+    }
+
+    /// Adjusts link scores by the authority of their domain
+    pub fn with_domain_authority(mut self, authority: Arc<DomainAuthority>) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Scores links by embedding similarity to `topic.description`
+    ///
+    /// Has no effect without a description or when the description cannot be embedded.
+    pub fn with_topic_embedder(mut self, embedder: Arc<dyn TopicEmbedder>) -> Self {
+        self.embedder = self
+            .config
+            .topic
+            .description
+            .as_deref()
+            .and_then(|description| embedder.embed(description))
+            .map(|topic| TopicEmbedding { embedder, topic });
+        self
+    }
+
+    /// Relevance of a link to the configured topic, from 0.0 to 1.0
+    ///
+    /// Returns None when no topic is configured.
+    pub fn topic_relevance(&self, url: &str, anchor_text: &str) -> Option<f32> {
+        let keywords = &self.config.topic.keywords;
+        if keywords.is_empty() && self.embedder.is_none() {
+            return None;
+        }
+        let text = format!("{} {}", anchor_text, url_words(url));
+
+        // * Each matched keyword adds half; prefix matching catches plurals ("emission" -> "emissions")
+        let tokens: Vec<String> = language::words(&text).collect();
+        let matched = keywords
+            .iter()
+            .filter(|keyword| {
+                let phrase: Vec<String> = language::words(keyword).collect();
+                !phrase.is_empty()
+                    && tokens.windows(phrase.len()).any(|window| {
+                        window.iter().zip(&phrase).all(|(token, word)| token.starts_with(word.as_str()))
+                    })
+            })
+            .count();
+        let keyword_relevance = (matched as f32 * 0.5).min(1.0);
+
+        let embedding_relevance = self
+            .embedder
+            .as_ref()
+            .and_then(|e| cosine_similarity(&e.embedder.embed(&text)?, &e.topic))
+            .unwrap_or(0.0)
+            .clamp(0.0, 1.0);
+
+        Some(keyword_relevance.max(embedding_relevance))
+    }
+
+    /// Shares an existing learner, e.g. to keep learned weights across a config reload
+    pub fn with_learner(mut self, learner: Arc<LinkLearner>) -> Self {
+        self.learner = Some(learner);
+        self
+    }
+
+    /// Returns the learner shared by this scorer and its clones, if learning is enabled
+    pub fn learner(&self) -> Option<&Arc<LinkLearner>> {
+        self.learner.as_ref()
+    }
+
+    /// Reports what crawling `url` yielded so later links like it are scored accordingly
+    ///
+    /// `quality_score` is the refinery's 0.0 - 1.0 content quality; duplicates count as
+    /// worthless regardless of quality. A no-op unless learning is enabled.
+    pub fn record_outcome(&self, url: &str, quality_score: f32, was_duplicate: bool) {
+        if let Some(learner) = &self.learner {
+            learner.record_outcome(url, quality_score, was_duplicate);
+        }
+    }
+
+    /// Scores links rejected by the filter at the minimum and marks them filtered
+    pub fn with_url_filter(mut self, filter: Arc<UrlFilter>) -> Self {
+        self.url_filter = Some(filter);
+        self
+    }
+
+    /// Returns true if the URL passes the scorer's URL filter (if any)
+    pub fn allows(&self, url: &str) -> bool {
+        self.url_filter.as_ref().is_none_or(|f| f.allows(url))
     }
 
     /// Scores a single link
@@ -188,6 +555,16 @@ impl LinkScorer {
             ..Default::default()
         };
 
+        if !self.allows(url) {
+            breakdown.filtered = true;
+            breakdown.final_score = SCORE_MIN;
+            return ScoredLink {
+                score: SCORE_MIN,
+                breakdown,
+                ..ScoredLink::new(url.to_string(), anchor_text.to_string())
+            };
+        }
+
         let mut score = self.config.base_score;
 
         // * Parse URL for analysis
@@ -199,19 +576,28 @@ impl LinkScorer {
         // * Score based on URL structure
         if let Ok(parsed_url) = parsed {
             score += self.score_url_structure(&parsed_url, &mut breakdown);
+            score += self.score_domain(&parsed_url, &mut breakdown);
         }
 
         // * Score based on keyword analysis
         score += self.score_keywords(url, anchor_text, &mut breakdown);
 
+        // * Score based on the target topic and languages
+        score += self.score_topic(url, anchor_text, &mut breakdown);
+
+        // * Adjustment learned from earlier crawl outcomes
+        if let Some(learner) = &self.learner {
+            breakdown.learned_bonus = learner.adjustment(url);
+            score += breakdown.learned_bonus;
+        }
+
         // * Clamp to valid range
         breakdown.final_score = score.clamp(SCORE_MIN, SCORE_MAX);
 
         ScoredLink {
-            url: url.to_string(),
-            anchor_text: anchor_text.to_string(),
             score: breakdown.final_score,
             breakdown,
+            ..ScoredLink::new(url.to_string(), anchor_text.to_string())
         }
     }
 
@@ -323,6 +709,56 @@ impl LinkScorer {
         score
     }
 
+    /// Scores based on the link's domain: seed-domain bonus or external penalty, and authority
+    fn score_domain(&self, url: &url::Url, breakdown: &mut ScoreBreakdown) -> f32 {
+        let Some(host) = url.host_str().map(str::to_lowercase) else {
+            return 0.0;
+        };
+        let mut score = 0.0;
+
+        if !self.seed_domains.is_empty() {
+            let on_site = self
+                .seed_domains
+                .iter()
+                .any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)));
+            score += if on_site {
+                self.config.seed_domain_bonus
+            } else {
+                -self.config.external_domain_penalty
+            };
+        }
+
+        // * Authority is centered on 0.5 so unlisted domains stay neutral
+        if let Some(authority) = self.authority.as_ref().and_then(|a| a.get(&host)) {
+            score += self.config.authority_weight * (2.0 * authority - 1.0);
+        }
+
+        breakdown.domain_bonus = score;
+        score
+    }
+
+    /// Scores relevance to the target topic and penalizes anchors in other languages
+    fn score_topic(&self, url: &str, anchor_text: &str, breakdown: &mut ScoreBreakdown) -> f32 {
+        let topic = &self.config.topic;
+        let mut score = 0.0;
+
+        // * Centered on 0.5 relevance: unrelated links lose as much as relevant ones gain
+        if let Some(relevance) = self.topic_relevance(url, anchor_text) {
+            score += topic.weight * (2.0 * relevance - 1.0);
+        }
+
+        if !topic.languages.is_empty() {
+            if let Some(detected) = anchor_language(anchor_text) {
+                if !topic.languages.contains(&detected) {
+                    score -= topic.language_penalty;
+                }
+            }
+        }
+
+        breakdown.topic_bonus = score;
+        score
+    }
+
     /// Scores based on keyword analysis
     fn score_keywords(&self, url: &str, anchor_text: &str, breakdown: &mut ScoreBreakdown) -> f32 {
         let mut score = 0.0;
@@ -367,57 +803,347 @@ impl Default for LinkScorer {
     }
 }
 
-/// Priority queue wrapper for scored links
+/// Online logistic regression over URL features, trained on crawl outcomes
+///
+/// Features are the host, the leading path directories and the scorer's path and keyword
+/// signals. The model predicts whether a URL yields unique, high-quality content; scores
+/// move by up to `max_adjustment` towards that prediction.
+#[derive(Debug)]
+pub struct LinkLearner {
+    config: LearningConfig,
+    state: RwLock<LearnerState>,
+}
+
+#[derive(Debug, Default)]
+struct LearnerState {
+    bias: f32,
+    weights: HashMap<String, f32>,
+    observations: u64,
+}
+
+impl LinkLearner {
+    pub fn new(config: LearningConfig) -> Self {
+        Self {
+            config,
+            state: RwLock::new(LearnerState::default()),
+        }
+    }
+
+    /// Takes one gradient step towards the observed outcome
+    pub fn record_outcome(&self, url: &str, quality_score: f32, was_duplicate: bool) {
+        let target = if was_duplicate { 0.0 } else { quality_score.clamp(0.0, 1.0) };
+        let features = learning_features(url);
+        let mut state = self.state.write().unwrap();
+
+        let logit = state.bias + features.iter().map(|f| state.weights.get(f).copied().unwrap_or(0.0)).sum::<f32>();
+        let step = self.config.learning_rate * (target - sigmoid(logit));
+        state.bias += step;
+        for feature in features {
+            if let Some(weight) = state.weights.get_mut(&feature) {
+                *weight += step;
+            } else if state.weights.len() < self.config.max_features {
+                state.weights.insert(feature, step);
+            }
+        }
+        state.observations += 1;
+    }
+
+    /// Score adjustment for a URL, in `-max_adjustment..=max_adjustment`
+    ///
+    /// Only the feature weights count: the bias tracks the crawl-wide yield, which would
+    /// shift every link equally.
+    pub fn adjustment(&self, url: &str) -> f32 {
+        let state = self.state.read().unwrap();
+        if state.observations < self.config.min_observations {
+            return 0.0;
+        }
+        let logit: f32 = learning_features(url)
+            .iter()
+            .filter_map(|f| state.weights.get(f))
+            .sum();
+        self.config.max_adjustment * (2.0 * sigmoid(logit) - 1.0)
+    }
+
+    /// Learned weight of a feature such as `host:example.com` or `dir:news`
+    pub fn weight(&self, feature: &str) -> Option<f32> {
+        self.state.read().unwrap().weights.get(feature).copied()
+    }
+
+    /// Outcomes recorded so far
+    pub fn observations(&self) -> u64 {
+        self.state.read().unwrap().observations
+    }
+}
+
+// * URL path and query split into words ("/climate-change/news?id=1" -> "climate change news id 1")
+fn url_words(url: &str) -> String {
+    let Ok(parsed) = url::Url::parse(url) else {
+        return String::new();
+    };
+    let path_and_query = format!("{} {}", parsed.path(), parsed.query().unwrap_or(""));
+    path_and_query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// * Language of short anchor text: by script, else by the language with the most stopwords
+fn anchor_language(text: &str) -> Option<Language> {
+    if let Some(language) = Language::detect(text) {
+        return Some(language);
+    }
+    let words: Vec<String> = language::words(text).collect();
+    let mut counts: Vec<(Language, usize)> = Language::ALL
+        .into_iter()
+        .filter_map(|language| {
+            let stopwords = language.stopwords()?;
+            Some((language, words.iter().filter(|w| stopwords.contains(w.as_str())).count()))
+        })
+        .filter(|(_, count)| *count > 0)
+        .collect();
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    // * A tie (e.g. a word that is a stopword in two languages) is not a detection
+    match counts.as_slice() {
+        [(language, _)] => Some(*language),
+        [(language, first), (_, second), ..] if first > second => Some(*language),
+        _ => None,
+    }
+}
+
+// * Seed hosts match their whole site: `www.example.com` also covers `blog.example.com`
+fn site_domain(domain: &str) -> String {
+    let domain = domain.trim().trim_end_matches('.').to_lowercase();
+    match domain.strip_prefix("www.") {
+        Some(site) => site.to_string(),
+        None => domain,
+    }
+}
+
+fn current_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+// * Binary features of a URL; a feature present in the list has value 1
+fn learning_features(url: &str) -> Vec<String> {
+    let mut features = Vec::new();
+    let Ok(parsed) = url::Url::parse(url) else {
+        return features;
+    };
+    if let Some(host) = parsed.host_str() {
+        features.push(format!("host:{}", host.to_lowercase()));
+    }
+
+    let path = parsed.path().to_lowercase();
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    // * Only directories: the last segment is usually a page-specific slug
+    for segment in segments.iter().take(segments.len().saturating_sub(1)).take(LEARNED_PATH_SEGMENTS) {
+        features.push(format!("dir:{}", segment));
+    }
+    if segments.len() > MAX_PATH_DEPTH {
+        features.push("deep_path".to_string());
+    }
+    if parsed.query().is_some() {
+        features.push("query".to_string());
+    }
+
+    for (keywords, name) in [
+        (&HIGH_VALUE_KEYWORDS, "keyword:high_value"),
+        (&SOCIAL_KEYWORDS, "keyword:social"),
+        (&UTILITY_KEYWORDS, "keyword:utility"),
+        (&LEGAL_KEYWORDS, "keyword:legal"),
+        (&AD_KEYWORDS, "keyword:ad"),
+    ] {
+        if keywords.is_match(url) {
+            features.push(name.to_string());
+        }
+    }
+    features
+}
+
+// * Up-front allocation cap; large capacities grow on demand instead
+const INITIAL_QUEUE_ALLOCATION: usize = 1024;
+
+// * Heap entry: higher score first, then earlier insertion (FIFO among equal scores)
+#[derive(Debug)]
+struct QueuedLink {
+    link: ScoredLink,
+    seq: u64,
+}
+
+impl PartialEq for QueuedLink {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for QueuedLink {}
+
+impl PartialOrd for QueuedLink {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedLink {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // * BinaryHeap pops the greatest entry, so ScoredLink's "best first" order is reversed
+        other.link.cmp(&self.link).then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+/// Priority queue of scored links backed by a binary heap
+///
+/// `push` and `pop` are O(log n); links with equal scores come out in insertion order.
 #[derive(Debug)]
 pub struct PriorityLinkQueue {
-    links: Vec<ScoredLink>,
+    links: BinaryHeap<QueuedLink>,
     scorer: LinkScorer,
     capacity: usize,
+    next_seq: u64,
 }
 
 impl PriorityLinkQueue {
     /// Creates a new priority queue with specified capacity
     pub fn new(capacity: usize) -> Self {
-        Self {
-            links: Vec::with_capacity(capacity),
-            scorer: LinkScorer::new(),
-            capacity,
-        }
+        Self::with_scorer(capacity, LinkScorer::new())
     }
 
     /// Creates a queue with custom scorer
     pub fn with_scorer(capacity: usize, scorer: LinkScorer) -> Self {
         Self {
-            links: Vec::with_capacity(capacity),
+            links: BinaryHeap::with_capacity(capacity.min(INITIAL_QUEUE_ALLOCATION)),
             scorer,
             capacity,
+            next_seq: 0,
         }
     }
 
     /// Adds a link to the queue with scoring
     pub fn push(&mut self, url: &str, anchor_text: &str) -> bool {
+        self.push_with_origin(url, anchor_text, 0, None, current_timestamp())
+    }
+
+    /// Adds a link found `depth` links from the seeds on `parent_url`, discovered at `discovered_at`
+    pub fn push_with_origin(
+        &mut self,
+        url: &str,
+        anchor_text: &str,
+        depth: usize,
+        parent_url: Option<String>,
+        discovered_at: u64,
+    ) -> bool {
         if self.links.len() >= self.capacity {
             return false;
         }
 
         let scored = self.scorer.score(url, anchor_text);
-        self.links.push(scored);
-        self.links.sort();
+        if scored.breakdown.filtered {
+            return false;
+        }
+        self.enqueue(scored.with_origin(depth, parent_url, discovered_at));
         true
     }
 
+    /// Adds the links discovered on one page, returning how many were queued
+    ///
+    /// The links are one level deeper than `parent_url`, which sits at `parent_depth`.
+    pub fn push_many<'a, I>(&mut self, links: I, parent_url: &str, parent_depth: usize) -> usize
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let links = links.into_iter();
+        self.links.reserve(links.size_hint().0.min(self.capacity.saturating_sub(self.links.len())));
+        let now = current_timestamp();
+        links
+            .filter(|(url, anchor_text)| {
+                self.push_with_origin(url, anchor_text, parent_depth + 1, Some(parent_url.to_string()), now)
+            })
+            .count()
+    }
+
+    /// Adds several links
+    ///
+    /// Returns the URLs that were rejected (filtered by the scorer or over capacity).
+    pub fn push_batch<'a, I>(&mut self, links: I) -> Vec<String>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        links
+            .into_iter()
+            .filter(|(url, anchor_text)| !self.push(url, anchor_text))
+            .map(|(url, _)| url.to_string())
+            .collect()
+    }
+
+    /// Removes and returns the links ranked below the first `at`, in priority order
+    pub fn split_off(&mut self, at: usize) -> Vec<ScoredLink> {
+        if at >= self.links.len() {
+            return Vec::new();
+        }
+        let mut ranked = std::mem::take(&mut self.links).into_sorted_vec();
+        // * into_sorted_vec is ascending by heap order, i.e. lowest priority first
+        ranked.reverse();
+        let cold = ranked.split_off(at);
+        self.links = ranked.into();
+        cold.into_iter().map(|queued| queued.link).collect()
+    }
+
+    /// Returns the scorer ranking this queue
+    pub fn scorer(&self) -> &LinkScorer {
+        &self.scorer
+    }
+
+    /// Replaces the scorer and re-ranks the queued links with it
+    pub fn set_scorer(&mut self, scorer: LinkScorer) {
+        self.scorer = scorer;
+        let rescored: Vec<QueuedLink> = std::mem::take(&mut self.links)
+            .into_iter()
+            .map(|queued| {
+                let ScoredLink { url, anchor_text, depth, parent_url, discovered_at, .. } = queued.link;
+                QueuedLink {
+                    link: self.scorer.score(&url, &anchor_text).with_origin(depth, parent_url, discovered_at),
+                    seq: queued.seq,
+                }
+            })
+            .filter(|queued| !queued.link.breakdown.filtered)
+            .collect();
+        self.links = rescored.into();
+    }
+
     /// Removes and returns the highest priority link
     pub fn pop(&mut self) -> Option<ScoredLink> {
-        if self.links.is_empty() {
-            None
-        } else {
-            Some(self.links.remove(0))
-        }
+        self.links.pop().map(|queued| queued.link)
     }
 
     /// Peeks at the highest priority link without removing
     pub fn peek(&self) -> Option<&ScoredLink> {
-        self.links.first()
+        self.links.peek().map(|queued| &queued.link)
+    }
+
+    /// Peeks at the lowest priority link (O(n))
+    pub fn peek_lowest(&self) -> Option<&ScoredLink> {
+        self.links.iter().min().map(|queued| &queued.link)
+    }
+
+    /// Removes and returns the lowest priority link (O(n))
+    pub fn pop_lowest(&mut self) -> Option<ScoredLink> {
+        let mut links = std::mem::take(&mut self.links).into_vec();
+        let lowest = links
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(index, _)| index);
+        let popped = lowest.map(|index| links.swap_remove(index).link);
+        self.links = links.into();
+        popped
     }
 
     /// Returns the number of links in the queue
@@ -440,9 +1166,25 @@ impl PriorityLinkQueue {
         self.links.clear();
     }
 
+    /// Iterates over queued links in priority order without removing them
+    ///
+    /// Sorts a view of the heap, so this is O(n log n).
+    pub fn iter(&self) -> impl Iterator<Item = &ScoredLink> {
+        let mut ranked: Vec<&QueuedLink> = self.links.iter().collect();
+        ranked.sort_by(|a, b| b.cmp(a));
+        ranked.into_iter().map(|queued| &queued.link)
+    }
+
     /// Drains all links from the queue in priority order
     pub fn drain(&mut self) -> Vec<ScoredLink> {
-        std::mem::take(&mut self.links)
+        let mut ranked = std::mem::take(&mut self.links).into_sorted_vec();
+        ranked.reverse();
+        ranked.into_iter().map(|queued| queued.link).collect()
+    }
+
+    fn enqueue(&mut self, link: ScoredLink) {
+        self.links.push(QueuedLink { link, seq: self.next_seq });
+        self.next_seq += 1;
     }
 }
 
@@ -644,20 +1386,76 @@ mod tests {
         assert!(queue.is_full());
     }
 
+    #[test]
+    fn test_priority_queue_batch_and_split() {
+        let mut queue = PriorityLinkQueue::new(3);
+        let rejected = queue.push_batch([
+            ("https://example.com/login", ""),
+            ("https://example.com/docs/guide", "Read the guide"),
+            ("https://example.com/about", "About"),
+            ("https://example.com/extra", "Extra"),
+        ]);
+        assert_eq!(rejected, vec!["https://example.com/extra".to_string()]);
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.peek().unwrap().url, "https://example.com/docs/guide");
+
+        let tail = queue.split_off(1);
+        assert_eq!(tail.len(), 2);
+        assert!(tail[0].score >= tail[1].score);
+        assert_eq!(queue.len(), 1);
+        assert!(queue.split_off(5).is_empty());
+    }
+
+    #[test]
+    fn test_priority_queue_heap_order() {
+        let mut queue = PriorityLinkQueue::new(100);
+        let urls: Vec<String> = (0..20).map(|i| format!("https://example.com/page-{}", i)).collect();
+        let accepted = queue.push_many(
+            urls.iter()
+                .map(|url| (url.as_str(), "Page"))
+                .chain([("https://example.com/docs/start", "Getting started guide")]),
+            "https://example.com/",
+            0,
+        );
+        assert_eq!(accepted, 21);
+        let top = queue.iter().next().unwrap();
+        assert_eq!(top.url, "https://example.com/docs/start");
+        assert_eq!(top.depth, 1);
+        assert_eq!(top.parent_url.as_deref(), Some("https://example.com/"));
+        assert!(top.discovered_at > 0);
+
+        // * Equal scores keep insertion order, both when popping and when iterating
+        assert_eq!(queue.pop().unwrap().url, "https://example.com/docs/start");
+        let listed: Vec<String> = queue.iter().map(|link| link.url.clone()).collect();
+        assert_eq!(listed, urls);
+        let popped: Vec<String> = std::iter::from_fn(|| queue.pop()).map(|link| link.url).collect();
+        assert_eq!(popped, urls);
+    }
+
+    #[test]
+    fn test_priority_queue_lowest() {
+        let mut queue = PriorityLinkQueue::new(10);
+        assert!(queue.pop_lowest().is_none());
+        queue.push("https://example.com/docs/guide", "Documentation Guide");
+        queue.push("https://example.com/ad/banner", "Sponsored Ad");
+        queue.push("https://example.com/about", "About");
+
+        assert_eq!(queue.peek_lowest().unwrap().url, "https://example.com/ad/banner");
+        assert_eq!(queue.pop_lowest().unwrap().url, "https://example.com/ad/banner");
+        assert_eq!(queue.pop_lowest().unwrap().url, "https://example.com/about");
+        assert_eq!(queue.pop().unwrap().url, "https://example.com/docs/guide");
+    }
+
     #[test]
     fn test_scored_link_ordering() {
         let high = ScoredLink {
-            url: "high".to_string(),
-            anchor_text: "".to_string(),
             score: 8.0,
-            breakdown: ScoreBreakdown::default(),
+            ..ScoredLink::new("high".to_string(), "".to_string())
         };
 
         let low = ScoredLink {
-            url: "low".to_string(),
-            anchor_text: "".to_string(),
             score: 3.0,
-            breakdown: ScoreBreakdown::default(),
+            ..ScoredLink::new("low".to_string(), "".to_string())
         };
 
         assert!(high < low, "Higher score should sort first (lower in ordering)");
@@ -684,6 +1482,7 @@ mod tests {
             penalize_query_params: false,
             high_value_paths: vec!["/custom/".to_string()],
             low_value_paths: vec!["/bad/".to_string()],
+            ..Default::default()
         };
 
         let scorer = LinkScorer::with_config(config);
@@ -722,4 +1521,155 @@ mod tests {
             "Social keywords should get penalty"
         );
     }
+
+    #[test]
+    fn test_url_filter_rejects_links() {
+        use crate::engine::url_filter::UrlFilterConfig;
+
+        let filter = UrlFilter::new(&UrlFilterConfig {
+            allow: vec!["*.example.com/news/**".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+        let scorer = LinkScorer::new().with_url_filter(Arc::new(filter));
+
+        let blocked = scorer.score("https://www.example.com/shop/", "Documentation Guide");
+        assert!(blocked.breakdown.filtered);
+        assert_eq!(blocked.score, SCORE_MIN);
+        assert!(!scorer.score("https://www.example.com/news/a", "News").breakdown.filtered);
+
+        let mut queue = PriorityLinkQueue::with_scorer(10, scorer);
+        assert!(!queue.push("https://www.example.com/shop/", ""));
+        assert!(queue.push("https://www.example.com/news/a", ""));
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[test]
+    fn test_learning_from_outcomes() {
+        let scorer = LinkScorer::with_config(ScorerConfig {
+            learning: LearningConfig {
+                enabled: true,
+                min_observations: 10,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let frontier_copy = scorer.clone();
+        let before = scorer.score("https://example.com/recipes/new-soup", "Soup").score;
+
+        for i in 0..50 {
+            scorer.record_outcome(&format!("https://example.com/recipes/dish-{}", i), 0.9, false);
+            scorer.record_outcome(&format!("https://example.com/tags/tag-{}", i), 0.8, true);
+        }
+
+        // * Clones share what was learned
+        let recipe = frontier_copy.score("https://example.com/recipes/new-soup", "Soup");
+        let tag = frontier_copy.score("https://example.com/tags/new-tag", "Soup");
+        assert!(recipe.breakdown.learned_bonus > 0.0);
+        assert!(tag.breakdown.learned_bonus < 0.0);
+        assert!(recipe.score > before);
+        assert!(recipe.breakdown.learned_bonus <= LearningConfig::default().max_adjustment);
+        assert!(scorer.learner().unwrap().weight("dir:recipes").unwrap() > 0.0);
+        assert_eq!(scorer.learner().unwrap().observations(), 100);
+
+        // * Disabled learning ignores outcomes
+        let fixed = LinkScorer::new();
+        fixed.record_outcome("https://example.com/recipes/dish", 1.0, false);
+        assert!(fixed.learner().is_none());
+        assert_eq!(fixed.score("https://example.com/recipes/dish", "Soup").breakdown.learned_bonus, 0.0);
+    }
+
+    #[test]
+    fn test_seed_domain_and_authority_scoring() {
+        let authority = DomainAuthority::parse("# curated\ndocs.rs 0.9\nspam.example, 0.1\n").unwrap();
+        assert_eq!(authority.get("sub.docs.rs"), Some(0.9));
+        assert_eq!(authority.get("example.org"), None);
+
+        let scorer = LinkScorer::with_config(ScorerConfig {
+            authority_weight: 2.0,
+            ..Default::default()
+        })
+        .with_seed_domains(["www.example.com"])
+        .with_domain_authority(Arc::new(authority));
+
+        let on_site = scorer.score("https://blog.example.com/post", "Post");
+        let external = scorer.score("https://example.org/post", "Post");
+        let trusted = scorer.score("https://docs.rs/post", "Post");
+        let spam = scorer.score("https://spam.example/post", "Post");
+
+        assert_eq!(on_site.breakdown.domain_bonus, 1.0);
+        assert_eq!(external.breakdown.domain_bonus, -1.0);
+        assert!(on_site.score > trusted.score && trusted.score > external.score);
+        assert!(spam.score < external.score);
+
+        // * Without seed domains there is no on-site bonus or external penalty
+        let unfocused = LinkScorer::new().score("https://example.org/post", "Post");
+        assert_eq!(unfocused.breakdown.domain_bonus, 0.0);
+
+        assert!(matches!(
+            DomainAuthority::parse("docs.rs 1.5"),
+            Err(AuthorityError::Parse { line: 1, .. })
+        ));
+        assert!(matches!(
+            LinkScorer::from_config(ScorerConfig {
+                authority_file: Some(PathBuf::from("/nonexistent/authority.txt")),
+                ..Default::default()
+            }),
+            Err(AuthorityError::Io { .. })
+        ));
+    }
+
+    #[test]
+    fn test_topic_relevance_scoring() {
+        let scorer = LinkScorer::with_config(ScorerConfig {
+            topic: TopicConfig {
+                keywords: vec!["climate".to_string(), "carbon tax".to_string(), "emission".to_string()],
+                languages: vec![Language::En],
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        assert_eq!(scorer.topic_relevance("https://example.com/sport/match", "Cup final"), Some(0.0));
+        assert_eq!(scorer.topic_relevance("https://example.com/climate/", "Latest"), Some(0.5));
+        assert_eq!(
+            scorer.topic_relevance("https://example.com/news/1", "Carbon tax cuts emissions"),
+            Some(1.0)
+        );
+        assert_eq!(LinkScorer::new().topic_relevance("https://example.com/climate/", ""), None);
+
+        let on_topic = scorer.score("https://example.com/news/2", "Climate talks stall");
+        let off_topic = scorer.score("https://example.com/news/3", "Transfer window opens");
+        assert_eq!(on_topic.breakdown.topic_bonus, 0.0);
+        assert_eq!(off_topic.breakdown.topic_bonus, -2.0);
+        assert!(on_topic.score > off_topic.score);
+
+        // * Anchors in another language lose the language penalty on top
+        let german = scorer.score("https://example.com/news/4", "Das Klima und die Emissionen");
+        assert_eq!(german.breakdown.topic_bonus, -1.0);
+    }
+
+    #[test]
+    fn test_topic_embedding_relevance() {
+        // * Two-dimensional toy embedding: (mentions weather, mentions markets)
+        struct ToyEmbedder;
+        impl TopicEmbedder for ToyEmbedder {
+            fn embed(&self, text: &str) -> Option<Vec<f32>> {
+                let text = text.to_lowercase();
+                Some(vec![text.contains("weather") as u8 as f32, text.contains("market") as u8 as f32])
+            }
+        }
+
+        let scorer = LinkScorer::with_config(ScorerConfig {
+            topic: TopicConfig {
+                description: Some("Extreme weather coverage".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with_topic_embedder(Arc::new(ToyEmbedder));
+
+        assert_eq!(scorer.topic_relevance("https://example.com/a", "Weather warning"), Some(1.0));
+        assert_eq!(scorer.topic_relevance("https://example.com/b", "Market report"), Some(0.0));
+    }
 }
@@ -3,28 +3,70 @@
 // * This module provides storage, deduplication, link scoring, and AI processing
 
 pub mod ai_worker;
+pub mod audit;
+pub mod budget;
+pub mod change_tracker;
+pub mod clusters;
 pub mod dedup;
+pub mod expiry;
+pub mod export;
+pub mod gc;
+pub mod jsonl;
+pub mod lance_store;
 pub mod link_scorer;
+pub mod ner;
+pub mod reprocess;
 pub mod schema;
+pub mod snapshot;
+pub mod structural_hash;
+pub mod summary;
+pub mod warc;
+pub mod webhook;
 
 // * Re-exports for convenient access
 pub use ai_worker::{
-    compute_embedding, compute_sentiment, AIEnrichmentWorker, EnrichmentError,
-    EnrichmentPipelineBuilder, InMemoryRecordStore, RecordProvider, RecordUpdater,
-    WorkerConfig, WorkerHandle, WorkerStats,
+    compute_embedding, compute_sentiment, find_similar_records, AIEnrichmentWorker, ChunkProvider,
+    ChunkSearch, ChunkSink, ChunkStore, ChunkUpdater, EnrichmentError, EnrichmentPipelineBuilder,
+    InMemoryRecordStore, RecordHistory, RecordProvider, RecordPurger, RecordSearch, RecordSink,
+    RecordUpdater, WorkerConfig, WorkerHandle, WorkerStats,
+};
+pub use audit::{load_sample, load_samples, AuditConfig, AuditError, AuditReport, AuditSample, AuditSampler};
+pub use budget::{estimate_tokens, EnrichmentBudget, EnrichmentBudgetConfig};
+pub use change_tracker::{
+    ChangeEvent, ChangeKind, ChangeTracker, ChangeTrackerConfig, ChangeTrackerSnapshot,
+    PageVersion,
+};
+pub use clusters::{
+    ClusterEntry, ClusterMember, ClusterReport, ClusterReportConfig, ClusterReporter, DuplicateCluster,
 };
 pub use dedup::{
-    BloomFilter, DedupCheckResult, DedupManager, DedupResult, DedupStats, LSHIndex,
-    MinHashSignature,
+    BloomFilter, BloomSnapshot, DedupCheckResult, DedupConfig, DedupManager, DedupResult,
+    DedupSnapshot, DedupStats, DocumentOrigin, LSHIndex, MinHashSignature, MinHashStrategy,
 };
+#[cfg(feature = "parquet-export")]
+pub use export::export_parquet;
+pub use expiry::{ExpiryConfig, ExpiryError, ExpiryPolicy, ExpiryReport, ExpiryRule, ExpiryScheduler};
+pub use export::{export_clusters_jsonl, export_jsonl, ExportError};
+pub use gc::{GarbageCollector, GcConfig, GcReport};
+pub use jsonl::{read_jsonl_records, JsonlError, JsonlRecordSink};
+pub use lance_store::{record_table_schema, LanceRecordStore};
 pub use link_scorer::{
-    score_link, score_links, LinkScorer, PriorityLinkQueue, ScoreBreakdown, ScoredLink,
-    ScorerConfig,
+    score_link, score_links, AuthorityError, DomainAuthority, LearningConfig, LinkLearner,
+    LinkScorer, PriorityLinkQueue, ScoreBreakdown, ScoredLink, ScorerConfig, TopicConfig,
+    TopicEmbedder,
 };
+pub use ner::{entities_to_json, GazetteerNer, NamedEntity, NamedEntityKind, NerProvider};
+pub use reprocess::{reprocess_records, warc_files, ReprocessReport};
 pub use schema::{
-    EnrichmentBatch, EnrichmentFilter, MediaReference, MediaType, MultimodalRecord,
-    MultimodalRecordBuilder, SchemaError, EMBEDDING_DIM, SENTIMENT_MAX, SENTIMENT_MIN,
+    ChunkRecord, EnrichmentBatch, EnrichmentFilter, IdScheme, MediaReference, MediaType,
+    MultimodalRecord, MultimodalRecordBuilder, RecordFilter, RecordId, RecordRevision, SchemaError,
+    SimilarChunk, SimilarRecord, EMBEDDING_DIM, SENTIMENT_MAX, SENTIMENT_MIN,
 };
+pub use snapshot::{SnapshotConfig, SnapshotError, SnapshotEviction, SnapshotStore};
+pub use structural_hash::{structural_hash, structural_text, ContentHashMode};
+pub use summary::{LeadSummarizer, SummaryProvider};
+pub use warc::{ArchivedPage, HttpCapture, WarcError, WarcReader, WarcRecord, WarcWriter, WARC_VERSION};
+pub use webhook::{WebhookConfig, WebhookError, WebhookSink, WebhookStats};
 
 #[cfg(test)]
 mod tests {
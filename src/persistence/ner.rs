@@ -0,0 +1,173 @@
+// * [FR-09] Named Entity Recognition Enrichment
+// * Optional enrichment stage tagging the people, organizations and locations a record mentions.
+// * Models plug in through `NerProvider`; `GazetteerNer` matches a fixed list of known names.
+
+use super::ai_worker::{AsyncResult, EnrichmentError};
+use serde::{Deserialize, Serialize};
+
+/// Category of a named entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NamedEntityKind {
+    Person,
+    Organization,
+    Location,
+}
+
+/// A named entity found in a record's text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamedEntity {
+    /// Entity as written in the text
+    pub text: String,
+    pub kind: NamedEntityKind,
+    /// Byte offsets of the mention in the text
+    pub start: usize,
+    pub end: usize,
+    /// Provider confidence (0.0 to 1.0)
+    pub confidence: f32,
+}
+
+/// Trait for named entity recognition backends (local models, hosted APIs)
+pub trait NerProvider: Send + Sync {
+    /// Provider name, used in logs
+    fn name(&self) -> &str;
+
+    /// Extracts the named entities mentioned in `text`, in order of appearance
+    fn extract_entities(&self, text: &str) -> AsyncResult<Vec<NamedEntity>>;
+}
+
+/// Dictionary-based NER: tags exact, whole-word mentions of known names
+///
+/// When names overlap, the longest wins ("New York Times" over "New York").
+///
+/// # Example
+/// ```ignore
+/// use titan_flow::persistence::{GazetteerNer, NamedEntityKind};
+///
+/// let ner = GazetteerNer::new()
+///     .with_names(NamedEntityKind::Person, ["Ada Lovelace"])
+///     .with_names(NamedEntityKind::Location, ["London"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GazetteerNer {
+    // * Kept longest first so longer names claim their span before shorter ones
+    names: Vec<(String, NamedEntityKind)>,
+}
+
+impl GazetteerNer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds names of the given kind
+    pub fn with_names<I, N>(mut self, kind: NamedEntityKind, names: I) -> Self
+    where
+        I: IntoIterator<Item = N>,
+        N: Into<String>,
+    {
+        self.names.extend(
+            names
+                .into_iter()
+                .map(|name| (name.into(), kind))
+                .filter(|(name, _)| !name.trim().is_empty()),
+        );
+        self.names.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+        self
+    }
+
+    /// Number of known names
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Finds every whole-word mention of a known name
+    pub fn find(&self, text: &str) -> Vec<NamedEntity> {
+        let mut entities: Vec<NamedEntity> = Vec::new();
+        for (name, kind) in &self.names {
+            for (start, _) in text.match_indices(name.as_str()) {
+                let end = start + name.len();
+                let overlaps = entities.iter().any(|e| start < e.end && e.start < end);
+                if overlaps || !is_word_boundary(text, start, end) {
+                    continue;
+                }
+                entities.push(NamedEntity {
+                    text: name.clone(),
+                    kind: *kind,
+                    start,
+                    end,
+                    confidence: 1.0,
+                });
+            }
+        }
+        entities.sort_by_key(|e| e.start);
+        entities
+    }
+}
+
+impl NerProvider for GazetteerNer {
+    fn name(&self) -> &str {
+        "gazetteer"
+    }
+
+    fn extract_entities(&self, text: &str) -> AsyncResult<Vec<NamedEntity>> {
+        let entities = self.find(text);
+        Box::pin(async move { Ok(entities) })
+    }
+}
+
+/// Serializes entities for `MultimodalRecord::entities_json`
+pub fn entities_to_json(entities: &[NamedEntity]) -> Result<String, EnrichmentError> {
+    serde_json::to_string(entities).map_err(|e| EnrichmentError::EntityError(e.to_string()))
+}
+
+// * A mention must not continue an alphanumeric word on either side
+fn is_word_boundary(text: &str, start: usize, end: usize) -> bool {
+    let before = text[..start].chars().next_back();
+    let after = text[end..].chars().next();
+    !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gazetteer() -> GazetteerNer {
+        GazetteerNer::new()
+            .with_names(NamedEntityKind::Person, ["Ada Lovelace"])
+            .with_names(NamedEntityKind::Organization, ["New York Times", "Acme"])
+            .with_names(NamedEntityKind::Location, ["New York", "London"])
+    }
+
+    #[tokio::test]
+    async fn test_gazetteer_tags_known_names() {
+        let text = "Ada Lovelace told the New York Times about London, then flew to New York.";
+        let entities = gazetteer().extract_entities(text).await.unwrap();
+
+        let found: Vec<(&str, NamedEntityKind)> = entities.iter().map(|e| (e.text.as_str(), e.kind)).collect();
+        assert_eq!(
+            found,
+            [
+                ("Ada Lovelace", NamedEntityKind::Person),
+                ("New York Times", NamedEntityKind::Organization),
+                ("London", NamedEntityKind::Location),
+                ("New York", NamedEntityKind::Location),
+            ]
+        );
+        assert_eq!(&text[entities[2].start..entities[2].end], "London");
+    }
+
+    #[test]
+    fn test_gazetteer_matches_whole_words_only() {
+        let entities = gazetteer().find("Acmeville is not Acme.");
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities[0].start, 17);
+
+        let json = entities_to_json(&entities).unwrap();
+        assert!(json.contains(r#""kind":"organization""#));
+        assert!(GazetteerNer::new().find("Anything").is_empty());
+    }
+}
@@ -0,0 +1,168 @@
+// * Offline Reprocessing
+// * Re-runs the Refinery over archived HTML and revises stored records in place, so cleaner
+// * and chunker improvements reach existing data without crawling the web again
+
+use super::schema::{MultimodalRecord, MultimodalRecordBuilder};
+use super::warc::ArchivedPage;
+use crate::refinery::Refinery;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Outcome of a reprocessing run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ReprocessReport {
+    /// Archived pages read (the latest capture of each URL is used)
+    pub pages: usize,
+    /// Records whose extracted content changed (a new revision, enrichment cleared)
+    pub updated: usize,
+    /// Records whose extraction came out the same
+    pub unchanged: usize,
+    /// Records left as they were because the new extraction found no text
+    pub empty: usize,
+    /// Archived URLs without a stored record
+    pub unmatched: usize,
+}
+
+/// Re-extracts every record with an archived page and revises the ones whose content changed
+///
+/// Pages are matched on the record's URL or the URL its content was served from after
+/// redirects. Revised records drop their embeddings and sentiment, so the next `enrich`
+/// run recomputes them.
+pub fn reprocess_records(
+    records: &mut [MultimodalRecord],
+    pages: impl IntoIterator<Item = ArchivedPage>,
+    refinery: &Refinery,
+) -> ReprocessReport {
+    let mut report = ReprocessReport::default();
+    // * Later captures (e.g. a render after the fast path fetch) replace earlier ones
+    let mut latest = HashMap::new();
+    for page in pages {
+        report.pages += 1;
+        latest.insert(page.url.clone(), page);
+    }
+
+    for record in records.iter_mut() {
+        let page = record
+            .final_url
+            .as_ref()
+            .and_then(|url| latest.remove(url))
+            .or_else(|| latest.remove(&record.url));
+        let Some(page) = page else {
+            continue;
+        };
+        let result = page.refine(refinery);
+        if result.persistable_text().trim().is_empty() {
+            report.empty += 1;
+            continue;
+        }
+        let revised = MultimodalRecordBuilder::from_refinery(&result, record.url.clone()).build();
+        match record.apply_revision(revised) {
+            Some(_) => report.updated += 1,
+            None => report.unchanged += 1,
+        }
+    }
+    report.unmatched = latest.len();
+    report
+}
+
+/// Returns the WARC archives at `path`: the file itself, or the .warc / .warc.gz files in a
+/// directory in name order
+pub fn warc_files(path: &Path) -> io::Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files: Vec<PathBuf> = std::fs::read_dir(path)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|file| {
+            let name = file.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+            file.is_file() && (name.ends_with(".warc") || name.ends_with(".warc.gz"))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::refinery::RefineryConfig;
+
+    fn page(url: &str, body: &str) -> ArchivedPage {
+        ArchivedPage {
+            url: url.to_string(),
+            html: format!("<html><body><article><p>{}</p></article></body></html>", body),
+            date: String::new(),
+            rendered: false,
+        }
+    }
+
+    #[test]
+    fn test_reprocess_revises_changed_records() {
+        let refinery = Refinery::new();
+        let text = "Tomatoes need full sun, deep watering and rich soil to grow well in summer gardens.";
+        let mut records = vec![
+            MultimodalRecordBuilder::from_refinery(&refinery.process(&page("https://a.com/", text).html), "https://a.com/")
+                .build(),
+            MultimodalRecord::new("https://b.com/".to_string(), 1, "stale text".to_string()),
+            MultimodalRecord::new("https://c.com/".to_string(), 2, "kept".to_string()),
+        ];
+        records[1].embedding = Some(vec![0.5; 4]);
+        records[2].final_url = Some("https://c.com/moved".to_string());
+
+        let pages = vec![
+            page("https://a.com/", text),
+            page("https://b.com/", "An old capture that a later one replaces."),
+            page("https://b.com/", "Chess openings reward development, center control and king safety."),
+            page("https://c.com/moved", ""),
+            page("https://d.com/", "Nobody stored this page."),
+        ];
+        let report = reprocess_records(&mut records, pages, &refinery);
+
+        assert_eq!(
+            report,
+            ReprocessReport {
+                pages: 5,
+                updated: 1,
+                unchanged: 1,
+                empty: 1,
+                unmatched: 1,
+            }
+        );
+        assert!(records[1].text_content.contains("Chess openings"));
+        assert_eq!(records[1].revision, 2);
+        assert_eq!(records[1].embedding, None);
+        assert_eq!(records[2].text_content, "kept");
+    }
+
+    #[test]
+    fn test_new_refinery_settings_change_records() {
+        let text = "Contact jane.doe@example.com about the community garden plots this spring season.";
+        let mut records =
+            vec![MultimodalRecordBuilder::from_refinery(&Refinery::new().process(&page("https://a.com/", text).html), "https://a.com/")
+                .build()];
+
+        let redacting = Refinery::with_config(RefineryConfig {
+            redaction: Some(Default::default()),
+            ..Default::default()
+        });
+        let report = reprocess_records(&mut records, vec![page("https://a.com/", text)], &redacting);
+
+        assert_eq!(report.updated, 1);
+        assert!(!records[0].text_content.contains("jane.doe@example.com"));
+    }
+
+    #[test]
+    fn test_warc_files_in_directory() {
+        let dir = std::env::temp_dir().join(format!("titan-reprocess-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["b.warc.gz", "a.warc", "notes.txt"] {
+            std::fs::write(dir.join(name), b"").unwrap();
+        }
+
+        assert_eq!(warc_files(&dir).unwrap(), vec![dir.join("a.warc"), dir.join("b.warc.gz")]);
+        assert_eq!(warc_files(&dir.join("a.warc")).unwrap(), vec![dir.join("a.warc")]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
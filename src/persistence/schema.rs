@@ -1,8 +1,15 @@
 // * [PRD-4] [EDD-6] LanceDB Schema for Multimodal Web Objects
 // * Defines the core data structures for vector database persistence
 
+use super::ner::NamedEntity;
+use crate::engine::slow_path::PageDiagnostic;
+use crate::refinery::{RefineryResult, TextChunk};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Deref;
 use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+use xxhash_rust::xxh64::xxh64;
 
 // * Embedding dimension for vector search (768-dim as per spec)
 pub const EMBEDDING_DIM: usize = 768;
@@ -11,22 +18,123 @@ pub const EMBEDDING_DIM: usize = 768;
 pub const SENTIMENT_MIN: f32 = -1.0;
 pub const SENTIMENT_MAX: f32 = 1.0;
 
+/// How new record IDs are generated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdScheme {
+    /// Random UUID v4
+    #[default]
+    UuidV4,
+    /// Time-ordered UUID v7; IDs sort by creation time
+    UuidV7,
+}
+
+impl IdScheme {
+    /// Generates a new ID under this scheme
+    pub fn generate(&self) -> RecordId {
+        let uuid = match self {
+            IdScheme::UuidV4 => Uuid::new_v4(),
+            IdScheme::UuidV7 => Uuid::now_v7(),
+        };
+        RecordId(uuid.hyphenated().to_string())
+    }
+}
+
+/// Identifier of a stored record
+///
+/// Serialized as a plain string, so records written before the newtype stay readable.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RecordId(String);
+
+impl RecordId {
+    /// Generates a random (UUID v4) ID
+    pub fn generate() -> Self {
+        IdScheme::default().generate()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for RecordId {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for RecordId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RecordId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for RecordId {
+    fn from(id: String) -> Self {
+        Self(id)
+    }
+}
+
+impl From<&str> for RecordId {
+    fn from(id: &str) -> Self {
+        Self(id.to_string())
+    }
+}
+
+impl From<RecordId> for String {
+    fn from(id: RecordId) -> Self {
+        id.0
+    }
+}
+
+impl PartialEq<str> for RecordId {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for RecordId {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
 /// Primary record structure for LanceDB storage
 ///
 /// # Fields
-/// - `id`: Unique identifier (UUID v4 format)
+/// - `id`: Unique identifier (UUID v4 by default, see `IdScheme`)
 /// - `url`: Normalized URL (from engine/normalization.rs)
 /// - `content_hash`: xxHash64 fingerprint for deduplication
 /// - `media_json`: Serialized media references (images, videos)
 /// - `embedding`: 768-dimensional vector for semantic search
+/// - `embedding_model` / `embedding_version`: Model that produced the embedding
+/// - `entities_json`: Serialized named entities (None until NER has run)
+/// - `summary`: Short abstract (None until summarized)
 /// - `sentiment_score`: Sentiment analysis result (-1.0 to 1.0)
 /// - `is_deleted`: Soft deletion flag
+/// - `deleted_at`: When the record was soft-deleted
 /// - `created_at`: Record creation timestamp
 /// - `updated_at`: Last modification timestamp
+/// - `revision`: Content version, starting at 1 (see `RecordRevision`)
+/// - `revised_at`: When the current content version was stored
+/// - `parent_url` / `crawl_depth`: Page the URL was discovered on and its link distance from a seed
+/// - `redirect_chain` / `final_url`: URLs that redirected, and the URL the content was served from
+/// - `diagnostics`: Errors and warnings the page raised while the slow path rendered it
+/// - `html_snapshot`: Hash of the raw HTML in the snapshot store (see `SnapshotStore`)
+/// - `hreflang_cluster`: Shared by the language variants of one page (see `HreflangIndex`)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MultimodalRecord {
     // * Core identifiers
-    pub id: String,
+    pub id: RecordId,
     pub url: String,
     pub content_hash: u64,
 
@@ -38,6 +146,15 @@ pub struct MultimodalRecord {
     // * AI enrichment fields (nullable until processed)
     pub embedding: Option<Vec<f32>>,
     pub sentiment_score: Option<f32>,
+    // * None for records embedded before model versioning
+    #[serde(default)]
+    pub embedding_model: Option<String>,
+    #[serde(default)]
+    pub embedding_version: Option<u32>,
+    #[serde(default)]
+    pub entities_json: Option<String>,
+    #[serde(default)]
+    pub summary: Option<String>,
 
     // * Metadata
     pub word_count: u32,
@@ -46,8 +163,40 @@ pub struct MultimodalRecord {
 
     // * Lifecycle
     pub is_deleted: bool,
+    #[serde(default)]
+    pub deleted_at: Option<u64>,
     pub created_at: u64,
     pub updated_at: u64,
+
+    // * Versioning (defaults keep records written before revisions existed readable)
+    #[serde(default = "first_revision")]
+    pub revision: u32,
+    #[serde(default)]
+    pub revised_at: u64,
+
+    // * Crawl provenance (None / 0 for seeds and records written before it was tracked)
+    #[serde(default)]
+    pub parent_url: Option<String>,
+    #[serde(default)]
+    pub crawl_depth: u32,
+
+    // * Redirects followed to reach the content (empty / None when served from `url`)
+    #[serde(default)]
+    pub redirect_chain: Vec<String>,
+    #[serde(default)]
+    pub final_url: Option<String>,
+
+    // * Console diagnostics from a slow path render (empty for fast path pages)
+    #[serde(default)]
+    pub diagnostics: Vec<PageDiagnostic>,
+
+    // * Raw HTML the content was extracted from (None when snapshots are disabled)
+    #[serde(default)]
+    pub html_snapshot: Option<String>,
+
+    // * Translations of the same page share an ID (None when the page declares no hreflang)
+    #[serde(default)]
+    pub hreflang_cluster: Option<String>,
 }
 
 impl MultimodalRecord {
@@ -55,7 +204,7 @@ impl MultimodalRecord {
     pub fn new(url: String, content_hash: u64, text_content: String) -> Self {
         let now = current_timestamp();
         Self {
-            id: generate_uuid(),
+            id: RecordId::generate(),
             url,
             content_hash,
             title: None,
@@ -63,15 +212,34 @@ impl MultimodalRecord {
             media_json: "[]".to_string(),
             embedding: None,
             sentiment_score: None,
+            embedding_model: None,
+            embedding_version: None,
+            entities_json: None,
+            summary: None,
             word_count: 0,
             chunk_count: 0,
             quality_score: 0.0,
             is_deleted: false,
+            deleted_at: None,
             created_at: now,
             updated_at: now,
+            revision: 1,
+            revised_at: now,
+            parent_url: None,
+            crawl_depth: 0,
+            redirect_chain: Vec::new(),
+            final_url: None,
+            diagnostics: Vec::new(),
+            html_snapshot: None,
+            hreflang_cluster: None,
         }
     }
 
+    /// Computes the `content_hash` of a record's text (xxHash64, seed 0)
+    pub fn hash_content(text: &str) -> u64 {
+        xxh64(text.as_bytes(), 0)
+    }
+
     /// Creates a record builder for fluent construction
     pub fn builder(url: String, content_hash: u64, text_content: String) -> MultimodalRecordBuilder {
         MultimodalRecordBuilder::new(url, content_hash, text_content)
@@ -82,6 +250,22 @@ impl MultimodalRecord {
         self.embedding.is_none() || self.sentiment_score.is_none()
     }
 
+    /// Returns the named entities found by NER (empty until NER has run)
+    pub fn entities(&self) -> Vec<NamedEntity> {
+        self.entities_json
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Checks if the record has an embedding from another model or version
+    ///
+    /// Embeddings without a recorded model count as stale.
+    pub fn embedding_is_stale(&self, model: &str, version: u32) -> bool {
+        self.embedding.is_some()
+            && (self.embedding_model.as_deref() != Some(model) || self.embedding_version != Some(version))
+    }
+
     /// Updates the record timestamps
     pub fn touch(&mut self) {
         self.updated_at = current_timestamp();
@@ -91,6 +275,21 @@ impl MultimodalRecord {
     pub fn soft_delete(&mut self) {
         self.is_deleted = true;
         self.touch();
+        self.deleted_at = Some(self.updated_at);
+    }
+
+    /// Checks if the record was soft-deleted before `timestamp`
+    ///
+    /// Records deleted before `deleted_at` existed fall back to `updated_at`.
+    pub fn deleted_before(&self, timestamp: u64) -> bool {
+        self.is_deleted && self.deleted_at.unwrap_or(self.updated_at) < timestamp
+    }
+
+    /// Returns the cosine similarity between this record's embedding and a query vector
+    ///
+    /// None when the record has no embedding or the dimensions differ.
+    pub fn similarity_to(&self, query: &[f32]) -> Option<f32> {
+        cosine_similarity(self.embedding.as_deref()?, query)
     }
 
     /// Sets the embedding vector with validation
@@ -116,13 +315,87 @@ impl MultimodalRecord {
     pub fn to_json(&self) -> String {
         serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string())
     }
+
+    /// Describes the current content version
+    pub fn to_revision(&self) -> RecordRevision {
+        RecordRevision {
+            record_id: self.id.clone(),
+            url: self.url.clone(),
+            revision: self.revision,
+            content_hash: self.content_hash,
+            title: self.title.clone(),
+            word_count: self.word_count,
+            // * Records written before versioning have no revised_at
+            valid_from: if self.revised_at == 0 { self.created_at } else { self.revised_at },
+            valid_until: None,
+        }
+    }
+
+    /// Replaces the content with a newer crawl of the same URL
+    ///
+    /// Returns the superseded version, or None when the content hash is unchanged.
+    /// Identity (`id`, `created_at`) is kept; enrichment comes from `newer` since it
+    /// no longer matches the old text.
+    pub fn apply_revision(&mut self, newer: MultimodalRecord) -> Option<RecordRevision> {
+        if newer.content_hash == self.content_hash {
+            return None;
+        }
+
+        let now = current_timestamp();
+        let previous = RecordRevision {
+            valid_until: Some(now),
+            ..self.to_revision()
+        };
+
+        self.content_hash = newer.content_hash;
+        self.title = newer.title;
+        self.text_content = newer.text_content;
+        self.media_json = newer.media_json;
+        self.embedding = newer.embedding;
+        self.sentiment_score = newer.sentiment_score;
+        self.embedding_model = newer.embedding_model;
+        self.embedding_version = newer.embedding_version;
+        self.entities_json = newer.entities_json;
+        self.summary = newer.summary;
+        self.word_count = newer.word_count;
+        self.chunk_count = newer.chunk_count;
+        self.quality_score = newer.quality_score;
+        // * Offline reprocessing revises content without a new snapshot of the same page
+        if newer.html_snapshot.is_some() {
+            self.html_snapshot = newer.html_snapshot;
+        }
+        if newer.hreflang_cluster.is_some() {
+            self.hreflang_cluster = newer.hreflang_cluster;
+        }
+        self.revision += 1;
+        self.revised_at = now;
+        self.updated_at = now;
+        Some(previous)
+    }
+}
+
+/// One content version of a record, kept so changes to scraped content can be audited
+///
+/// Superseded versions keep the hash, title and word count but not the full text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordRevision {
+    pub record_id: RecordId,
+    pub url: String,
+    pub revision: u32,
+    pub content_hash: u64,
+    pub title: Option<String>,
+    pub word_count: u32,
+    /// When this version was stored
+    pub valid_from: u64,
+    /// When a newer version replaced it (None for the current version)
+    pub valid_until: Option<u64>,
 }
 
 impl Default for MultimodalRecord {
     fn default() -> Self {
         let now = current_timestamp();
         Self {
-            id: generate_uuid(),
+            id: RecordId::generate(),
             url: String::new(),
             content_hash: 0,
             title: None,
@@ -130,12 +403,26 @@ impl Default for MultimodalRecord {
             media_json: "[]".to_string(),
             embedding: None,
             sentiment_score: None,
+            embedding_model: None,
+            embedding_version: None,
+            entities_json: None,
+            summary: None,
             word_count: 0,
             chunk_count: 0,
             quality_score: 0.0,
             is_deleted: false,
+            deleted_at: None,
             created_at: now,
             updated_at: now,
+            revision: 1,
+            revised_at: now,
+            parent_url: None,
+            crawl_depth: 0,
+            redirect_chain: Vec::new(),
+            final_url: None,
+            diagnostics: Vec::new(),
+            html_snapshot: None,
+            hreflang_cluster: None,
         }
     }
 }
@@ -153,6 +440,39 @@ impl MultimodalRecordBuilder {
         }
     }
 
+    /// Starts a record from a refinery result, filling the hash, counts, quality, title and media
+    ///
+    /// ! The text is `persistable_text()`, so the redacted copy is stored when redaction is on.
+    pub fn from_refinery(result: &RefineryResult, url: impl Into<String>) -> Self {
+        let text = result.persistable_text();
+        let mut builder = Self::new(url.into(), MultimodalRecord::hash_content(text), text.to_string())
+            .word_count(result.stats.word_count as u32)
+            .chunk_count(result.stats.chunk_count as u32)
+            .quality_score(result.stats.quality_score)
+            .diagnostics(result.diagnostics.clone());
+
+        if let Some(title) = &result.metadata.title {
+            builder = builder.title(title.clone());
+        }
+        // * The refinery only surfaces the page's og:image as media
+        let media: Vec<MediaReference> = result
+            .metadata
+            .og_image
+            .iter()
+            .map(|url| MediaReference::image(url.clone()))
+            .collect();
+        if !media.is_empty() {
+            builder = builder.media_json(serde_json::to_string(&media).unwrap_or_else(|_| "[]".to_string()));
+        }
+        builder
+    }
+
+    /// Replaces the generated ID (e.g. with one from `IdScheme::UuidV7`)
+    pub fn id(mut self, id: RecordId) -> Self {
+        self.record.id = id;
+        self
+    }
+
     pub fn title(mut self, title: impl Into<String>) -> Self {
         self.record.title = Some(title.into());
         self
@@ -191,11 +511,146 @@ impl MultimodalRecordBuilder {
         self
     }
 
+    /// Page the record's URL was discovered on
+    pub fn parent_url(mut self, url: impl Into<String>) -> Self {
+        self.record.parent_url = Some(url.into());
+        self
+    }
+
+    pub fn crawl_depth(mut self, depth: u32) -> Self {
+        self.record.crawl_depth = depth;
+        self
+    }
+
+    /// Redirects followed from the record's URL (starting with it) to `final_url`
+    pub fn redirected(mut self, chain: Vec<String>, final_url: impl Into<String>) -> Self {
+        self.record.redirect_chain = chain;
+        self.record.final_url = Some(final_url.into());
+        self
+    }
+
+    /// Errors and warnings the page raised while it rendered
+    pub fn diagnostics(mut self, diagnostics: Vec<PageDiagnostic>) -> Self {
+        self.record.diagnostics = diagnostics;
+        self
+    }
+
+    /// Hash of the raw HTML kept in the snapshot store
+    pub fn html_snapshot(mut self, hash: impl Into<String>) -> Self {
+        self.record.html_snapshot = Some(hash.into());
+        self
+    }
+
+    /// Language cluster shared with the page's translations
+    pub fn hreflang_cluster(mut self, cluster: impl Into<String>) -> Self {
+        self.record.hreflang_cluster = Some(cluster.into());
+        self
+    }
+
     pub fn build(self) -> MultimodalRecord {
         self.record
     }
 }
 
+/// Record returned by a similarity search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarRecord {
+    pub record: MultimodalRecord,
+    /// Cosine similarity to the query (1.0 = same direction)
+    pub score: f32,
+}
+
+/// One chunk of a record's text, embedded on its own for chunk-level retrieval (RAG)
+///
+/// Chunks come from the refinery's `SlidingWindowChunker` and are replaced as a set
+/// whenever their record's content changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChunkRecord {
+    pub record_id: RecordId,
+    pub url: String,
+    /// Position of the chunk within the record, starting at 0
+    pub chunk_index: u32,
+    pub text: String,
+    /// Word offsets of the chunk in the record's text
+    pub start_word: u32,
+    pub end_word: u32,
+    pub embedding: Option<Vec<f32>>,
+    pub embedding_model: Option<String>,
+    pub embedding_version: Option<u32>,
+}
+
+impl ChunkRecord {
+    pub fn new(record_id: RecordId, url: impl Into<String>, chunk_index: u32, text: impl Into<String>) -> Self {
+        Self {
+            record_id,
+            url: url.into(),
+            chunk_index,
+            text: text.into(),
+            start_word: 0,
+            end_word: 0,
+            embedding: None,
+            embedding_model: None,
+            embedding_version: None,
+        }
+    }
+
+    /// Converts the refinery's chunks of a record into unembedded chunk records
+    pub fn from_text_chunks(record: &MultimodalRecord, chunks: &[TextChunk]) -> Vec<Self> {
+        chunks
+            .iter()
+            .map(|chunk| {
+                let mut chunk_record = Self::new(
+                    record.id.clone(),
+                    record.url.clone(),
+                    chunk.chunk_index as u32,
+                    chunk.content.clone(),
+                );
+                chunk_record.start_word = chunk.start_index as u32;
+                chunk_record.end_word = chunk.end_index as u32;
+                chunk_record
+            })
+            .collect()
+    }
+
+    /// Checks if the chunk has an embedding from another model or version
+    pub fn embedding_is_stale(&self, model: &str, version: u32) -> bool {
+        self.embedding.is_some()
+            && (self.embedding_model.as_deref() != Some(model) || self.embedding_version != Some(version))
+    }
+
+    /// Sets the embedding vector and the model that produced it
+    pub fn set_embedding(
+        &mut self,
+        embedding: Vec<f32>,
+        model: impl Into<String>,
+        version: u32,
+    ) -> Result<(), SchemaError> {
+        if embedding.len() != EMBEDDING_DIM {
+            return Err(SchemaError::InvalidEmbeddingDimension {
+                expected: EMBEDDING_DIM,
+                actual: embedding.len(),
+            });
+        }
+        self.embedding = Some(embedding);
+        self.embedding_model = Some(model.into());
+        self.embedding_version = Some(version);
+        Ok(())
+    }
+
+    /// Returns the cosine similarity between this chunk's embedding and a query vector
+    pub fn similarity_to(&self, query: &[f32]) -> Option<f32> {
+        cosine_similarity(self.embedding.as_deref()?, query)
+    }
+}
+
+/// Chunk returned by a similarity search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarChunk {
+    pub chunk: ChunkRecord,
+    /// Cosine similarity to the query (1.0 = same direction)
+    pub score: f32,
+}
+
 /// Media reference structure for storing in media_json
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MediaReference {
@@ -257,23 +712,20 @@ pub enum SchemaError {
     InvalidRecord(String),
 }
 
-/// Generates a simple UUID v4 (time-based for uniqueness)
-fn generate_uuid() -> String {
-    let timestamp = current_timestamp();
-    let random_part: u64 = std::collections::hash_map::RandomState::new()
-        .build_hasher()
-        .finish();
-    format!(
-        "{:08x}-{:04x}-4{:03x}-{:04x}-{:012x}",
-        (timestamp >> 32) as u32,
-        (timestamp >> 16) as u16 & 0xFFFF,
-        (timestamp & 0x0FFF) as u16,
-        (random_part >> 48) as u16 | 0x8000,
-        random_part & 0xFFFFFFFFFFFF
-    )
+fn first_revision() -> u32 {
+    1
 }
 
-use std::hash::{BuildHasher, Hasher};
+// * None when the dimensions differ
+pub(crate) fn cosine_similarity(embedding: &[f32], query: &[f32]) -> Option<f32> {
+    if embedding.len() != query.len() {
+        return None;
+    }
+    let dot: f32 = embedding.iter().zip(query).map(|(a, b)| a * b).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let magnitude = norm(embedding) * norm(query);
+    Some(if magnitude > 0.0 { dot / magnitude } else { 0.0 })
+}
 
 /// Returns current Unix timestamp in seconds
 fn current_timestamp() -> u64 {
@@ -288,6 +740,12 @@ fn current_timestamp() -> u64 {
 pub struct EnrichmentFilter {
     pub limit: usize,
     pub include_deleted: bool,
+    /// Also select records embedded by a model other than this (name, version)
+    pub current_embedding: Option<(String, u32)>,
+    /// Also select records that NER has not run on
+    pub missing_entities: bool,
+    /// Also select records without a summary
+    pub missing_summary: bool,
 }
 
 impl EnrichmentFilter {
@@ -295,8 +753,87 @@ impl EnrichmentFilter {
         Self {
             limit,
             include_deleted: false,
+            current_embedding: None,
+            missing_entities: false,
+            missing_summary: false,
         }
     }
+
+    pub fn missing_entities(mut self, include: bool) -> Self {
+        self.missing_entities = include;
+        self
+    }
+
+    pub fn missing_summary(mut self, include: bool) -> Self {
+        self.missing_summary = include;
+        self
+    }
+
+    pub fn current_embedding(mut self, model: impl Into<String>, version: u32) -> Self {
+        self.current_embedding = Some((model.into(), version));
+        self
+    }
+
+    /// Checks whether a record should be (re-)enriched
+    pub fn matches(&self, record: &MultimodalRecord) -> bool {
+        if record.is_deleted && !self.include_deleted {
+            return false;
+        }
+        record.needs_enrichment()
+            || (self.missing_entities && record.entities_json.is_none())
+            || (self.missing_summary && record.summary.is_none())
+            || self
+                .current_embedding
+                .as_ref()
+                .is_some_and(|(model, version)| record.embedding_is_stale(model, *version))
+    }
+
+    /// Checks whether a chunk should be (re-)embedded; only `limit` and `current_embedding` apply
+    pub fn matches_chunk(&self, chunk: &ChunkRecord) -> bool {
+        chunk.embedding.is_none()
+            || self
+                .current_embedding
+                .as_ref()
+                .is_some_and(|(model, version)| chunk.embedding_is_stale(model, *version))
+    }
+}
+
+/// Predicate for selecting stored records (exports, audits)
+#[derive(Debug, Clone, Default)]
+pub struct RecordFilter {
+    pub include_deleted: bool,
+    /// Only records that already have an embedding and sentiment score
+    pub enriched_only: bool,
+    /// Only records updated at or after this Unix timestamp
+    pub updated_since: Option<u64>,
+}
+
+impl RecordFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn include_deleted(mut self, include: bool) -> Self {
+        self.include_deleted = include;
+        self
+    }
+
+    pub fn enriched_only(mut self, enriched_only: bool) -> Self {
+        self.enriched_only = enriched_only;
+        self
+    }
+
+    pub fn updated_since(mut self, timestamp: u64) -> Self {
+        self.updated_since = Some(timestamp);
+        self
+    }
+
+    /// Returns true when the record passes every condition
+    pub fn matches(&self, record: &MultimodalRecord) -> bool {
+        (self.include_deleted || !record.is_deleted)
+            && (!self.enriched_only || !record.needs_enrichment())
+            && self.updated_since.is_none_or(|since| record.updated_at >= since)
+    }
 }
 
 /// Batch of records for AI processing
@@ -310,7 +847,7 @@ impl EnrichmentBatch {
     pub fn new(records: Vec<MultimodalRecord>) -> Self {
         Self {
             records,
-            batch_id: generate_uuid(),
+            batch_id: RecordId::generate().into(),
         }
     }
 
@@ -344,6 +881,39 @@ mod tests {
         assert!(!record.is_deleted);
     }
 
+    #[test]
+    fn test_from_refinery() {
+        let html = format!(
+            r#"<html><head><title>Tide Tables</title><meta property="og:image" content="https://example.com/tide.jpg"></head>
+            <body><article><p>{}</p></article></body></html>"#,
+            "High and low tides follow the moon, twice a day along most coasts. ".repeat(6)
+        );
+        let mut result = crate::refinery::Refinery::new().process(&html);
+        result.diagnostics = vec![PageDiagnostic {
+            kind: crate::engine::slow_path::DiagnosticKind::CspViolation,
+            message: "script-src blocked inline".to_string(),
+            source: None,
+            timestamp: 1_700_000_000_000,
+        }];
+
+        let record = MultimodalRecordBuilder::from_refinery(&result, "https://example.com/tides").build();
+        assert_eq!(record.diagnostics, result.diagnostics);
+        assert_eq!(record.text_content, result.content.text);
+        assert_eq!(record.content_hash, MultimodalRecord::hash_content(&result.content.text));
+        assert_eq!(record.word_count as usize, result.stats.word_count);
+        assert_eq!(record.chunk_count as usize, result.stats.chunk_count);
+        assert_eq!(record.quality_score, result.stats.quality_score);
+        assert_eq!(record.title.as_deref(), Some("Tide Tables"));
+
+        let media: Vec<MediaReference> = serde_json::from_str(&record.media_json).unwrap();
+        assert_eq!(media.len(), 1);
+        assert_eq!(media[0].url, "https://example.com/tide.jpg");
+        assert_eq!(media[0].media_type, MediaType::Image);
+
+        assert_eq!(MultimodalRecord::hash_content("same text"), MultimodalRecord::hash_content("same text"));
+        assert_ne!(MultimodalRecord::hash_content("same text"), MultimodalRecord::hash_content("other text"));
+    }
+
     #[test]
     fn test_builder_pattern() {
         let record = MultimodalRecord::builder(
@@ -420,6 +990,9 @@ mod tests {
 
         assert!(record.is_deleted);
         assert!(record.updated_at >= original_updated);
+        assert_eq!(record.deleted_at, Some(record.updated_at));
+        assert!(record.deleted_before(record.updated_at + 1));
+        assert!(!record.deleted_before(record.updated_at));
     }
 
     #[test]
@@ -451,6 +1024,75 @@ mod tests {
         assert_eq!(parsed.content_hash, record.content_hash);
     }
 
+    #[test]
+    fn test_apply_revision() {
+        let mut record = MultimodalRecord::builder(
+            "https://example.com/news".to_string(),
+            1,
+            "First draft".to_string(),
+        )
+        .word_count(2)
+        .build();
+        record.set_sentiment(0.5);
+        let id = record.id.clone();
+
+        let same = MultimodalRecord::new(record.url.clone(), 1, "First draft".to_string());
+        assert!(record.apply_revision(same).is_none());
+        assert_eq!(record.revision, 1);
+
+        let newer = MultimodalRecord::builder(record.url.clone(), 2, "Corrected story".to_string())
+            .word_count(5)
+            .build();
+        let previous = record.apply_revision(newer).unwrap();
+
+        assert_eq!(previous.revision, 1);
+        assert_eq!(previous.content_hash, 1);
+        assert_eq!(previous.word_count, 2);
+        assert!(previous.valid_until.is_some());
+        assert_eq!(record.id, id);
+        assert_eq!(record.revision, 2);
+        assert_eq!(record.word_count, 5);
+        assert!(record.needs_enrichment(), "old enrichment no longer applies");
+        assert_eq!(record.to_revision().valid_until, None);
+    }
+
+    #[test]
+    fn test_legacy_record_defaults_to_first_revision() {
+        let mut value = serde_json::to_value(MultimodalRecord::default()).unwrap();
+        let object = value.as_object_mut().unwrap();
+        object.remove("revision");
+        object.remove("revised_at");
+
+        let record: MultimodalRecord = serde_json::from_value(value).unwrap();
+        assert_eq!(record.revision, 1);
+        assert_eq!(record.to_revision().valid_from, record.created_at);
+    }
+
+    #[test]
+    fn test_similarity_to() {
+        let mut record = MultimodalRecord::default();
+        assert_eq!(record.similarity_to(&[1.0, 0.0]), None);
+
+        record.embedding = Some(vec![1.0, 0.0]);
+        assert!((record.similarity_to(&[2.0, 0.0]).unwrap() - 1.0).abs() < 1e-6);
+        assert!(record.similarity_to(&[0.0, 1.0]).unwrap().abs() < 1e-6);
+        assert_eq!(record.similarity_to(&[1.0, 0.0, 0.0]), None);
+    }
+
+    #[test]
+    fn test_record_filter() {
+        let mut record = MultimodalRecord::new("https://example.com".to_string(), 1, "x".to_string());
+        record.updated_at = 100;
+
+        assert!(RecordFilter::new().matches(&record));
+        assert!(!RecordFilter::new().enriched_only(true).matches(&record));
+        assert!(!RecordFilter::new().updated_since(101).matches(&record));
+
+        record.soft_delete();
+        assert!(!RecordFilter::new().matches(&record));
+        assert!(RecordFilter::new().include_deleted(true).matches(&record));
+    }
+
     #[test]
     fn test_enrichment_batch() {
         let records = vec![
@@ -466,7 +1108,8 @@ mod tests {
 
     #[test]
     fn test_uuid_format() {
-        let uuid = generate_uuid();
+        let id = RecordId::generate();
+        let uuid = id.as_str();
 
         // * Check UUID v4 format: 8-4-4-4-12
         let parts: Vec<&str> = uuid.split('-').collect();
@@ -481,6 +1124,26 @@ mod tests {
         assert!(parts[2].starts_with('4'));
     }
 
+    #[test]
+    fn test_record_ids_are_unique_and_sortable() {
+        let ids: std::collections::HashSet<RecordId> = (0..10_000).map(|_| RecordId::generate()).collect();
+        assert_eq!(ids.len(), 10_000);
+
+        let first = IdScheme::UuidV7.generate();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let second = IdScheme::UuidV7.generate();
+        assert!(first < second);
+        assert!(second.as_str().split('-').nth(2).unwrap().starts_with('7'));
+
+        // * Serialized as a bare string
+        let json = serde_json::to_string(&first).unwrap();
+        assert_eq!(json, format!("\"{}\"", first));
+        let record = MultimodalRecord::builder("https://example.com".to_string(), 1, "Text".to_string())
+            .id(first.clone())
+            .build();
+        assert_eq!(record.id, first);
+    }
+
     #[test]
     fn test_quality_score_clamping() {
         let record = MultimodalRecord::builder(
@@ -503,4 +1166,27 @@ mod tests {
 
         assert!(record2.quality_score.abs() < f32::EPSILON);
     }
+
+    #[test]
+    fn test_chunk_records_from_text_chunks() {
+        use crate::refinery::{ChunkerConfig, SlidingWindowChunker};
+
+        let record = MultimodalRecord::new("https://example.com/a".to_string(), 1, "one two three four".to_string());
+        let chunker = SlidingWindowChunker::with_config(ChunkerConfig::new(2, 0, 1));
+        let mut chunks = ChunkRecord::from_text_chunks(&record, &chunker.chunk(&record.text_content));
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[1].record_id, record.id);
+        assert_eq!((chunks[1].chunk_index, chunks[1].text.as_str()), (1, "three four"));
+        assert_eq!((chunks[1].start_word, chunks[1].end_word), (2, 4));
+
+        let filter = EnrichmentFilter::new(10).current_embedding("model", 2);
+        let chunk = &mut chunks[0];
+        assert!(filter.matches_chunk(chunk));
+        assert!(chunk.set_embedding(vec![0.1; 3], "model", 1).is_err());
+        chunk.set_embedding(vec![0.1; EMBEDDING_DIM], "model", 1).unwrap();
+        assert!(!EnrichmentFilter::new(10).matches_chunk(chunk));
+        assert!(filter.matches_chunk(chunk));
+        assert!((chunk.similarity_to(&[0.2; EMBEDDING_DIM]).unwrap() - 1.0).abs() < 1e-5);
+    }
 }
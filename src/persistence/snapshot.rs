@@ -0,0 +1,486 @@
+// * Raw HTML Snapshot Store
+// * Keeps the HTML each record was extracted from, zstd-compressed and addressed by content
+// * hash, so extraction issues can be debugged against the page as it was fetched. A size
+// * budget and an optional age limit bound the store; eviction is oldest- or least-recently-used-first.
+
+use crate::ops;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use xxhash_rust::xxh64::xxh64;
+
+// * 1 GiB of compressed HTML
+const DEFAULT_MAX_BYTES: u64 = 1024 * 1024 * 1024;
+
+// * zstd's default level: fast enough for the crawl loop, ~10x on HTML
+const DEFAULT_COMPRESSION_LEVEL: i32 = 3;
+
+// * Snapshot file suffix
+const SNAPSHOT_EXTENSION: &str = "html.zst";
+
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("Snapshot I/O failed: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Snapshot of {size} bytes exceeds the {max_bytes} byte store budget")]
+    TooLarge { size: u64, max_bytes: u64 },
+}
+
+/// Which snapshots are evicted first once the store is over budget
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotEviction {
+    /// Snapshots stored earliest
+    #[default]
+    Oldest,
+    /// Snapshots stored or read least recently
+    LeastRecentlyUsed,
+}
+
+impl SnapshotEviction {
+    /// Metric label
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SnapshotEviction::Oldest => "oldest",
+            SnapshotEviction::LeastRecentlyUsed => "least_recently_used",
+        }
+    }
+}
+
+/// Raw HTML snapshot settings
+///
+/// # Example
+/// ```toml
+/// [snapshots]
+/// dir = "./data/snapshots"
+/// max_bytes = 536870912
+/// max_age_secs = 604800
+/// eviction = "least_recently_used"
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SnapshotConfig {
+    /// Directory for snapshot files (None disables snapshots)
+    pub dir: Option<PathBuf>,
+    /// Compressed bytes kept before snapshots are evicted
+    pub max_bytes: u64,
+    /// Snapshots older than this are evicted regardless of the budget
+    pub max_age_secs: Option<u64>,
+    pub eviction: SnapshotEviction,
+    /// zstd level (1-22)
+    pub compression_level: i32,
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            max_bytes: DEFAULT_MAX_BYTES,
+            max_age_secs: None,
+            eviction: SnapshotEviction::default(),
+            compression_level: DEFAULT_COMPRESSION_LEVEL,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SnapshotEntry {
+    /// Compressed size on disk
+    size: u64,
+    /// Unix seconds the snapshot was written
+    stored_at: u64,
+    /// Logical clock of the write, breaking ties between snapshots stored in the same second
+    stored_seq: u64,
+    /// Logical clock of the last write or read
+    used_seq: u64,
+}
+
+#[derive(Debug, Default)]
+struct SnapshotIndex {
+    entries: HashMap<String, SnapshotEntry>,
+    total_bytes: u64,
+    clock: u64,
+}
+
+impl SnapshotIndex {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn remove(&mut self, hash: &str) -> Option<SnapshotEntry> {
+        let entry = self.entries.remove(hash)?;
+        self.total_bytes -= entry.size;
+        Some(entry)
+    }
+}
+
+/// Content-addressed store of zstd-compressed HTML
+///
+/// Identical pages share one file. The index is rebuilt from the directory on open, with
+/// file modification times standing in for write and access order.
+///
+/// # Example
+/// ```ignore
+/// let store = SnapshotStore::open(SnapshotConfig { dir: Some("./snapshots".into()), ..Default::default() })?;
+/// let hash = store.put(&html)?;
+/// assert_eq!(store.get(&hash)?, Some(html));
+/// ```
+#[derive(Debug)]
+pub struct SnapshotStore {
+    dir: PathBuf,
+    config: SnapshotConfig,
+    index: Mutex<SnapshotIndex>,
+}
+
+impl SnapshotStore {
+    /// Opens the store in `config.dir` (created when missing), indexing existing snapshots
+    pub fn open(config: SnapshotConfig) -> Result<Self, SnapshotError> {
+        let dir = config
+            .dir
+            .clone()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "snapshots.dir is not set"))?;
+        fs::create_dir_all(&dir)?;
+
+        let mut found = Vec::new();
+        for shard in fs::read_dir(&dir)? {
+            let shard = shard?.path();
+            if !shard.is_dir() {
+                continue;
+            }
+            for file in fs::read_dir(&shard)? {
+                let file = file?;
+                let name = file.file_name().to_string_lossy().into_owned();
+                let Some(hash) = name.strip_suffix(&format!(".{}", SNAPSHOT_EXTENSION)) else {
+                    continue;
+                };
+                let meta = file.metadata()?;
+                let modified = meta
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map_or(0, |d| d.as_secs());
+                found.push((modified, hash.to_string(), meta.len()));
+            }
+        }
+        found.sort();
+
+        let mut index = SnapshotIndex::default();
+        for (stored_at, hash, size) in found {
+            let seq = index.tick();
+            index.total_bytes += size;
+            index.entries.insert(
+                hash,
+                SnapshotEntry {
+                    size,
+                    stored_at,
+                    stored_seq: seq,
+                    used_seq: seq,
+                },
+            );
+        }
+
+        let store = Self {
+            dir,
+            config,
+            index: Mutex::new(index),
+        };
+        store.evict_expired()?;
+        store.enforce_budget(None)?;
+        Ok(store)
+    }
+
+    /// Returns the content hash a page is stored under
+    pub fn hash_of(html: &str) -> String {
+        format!("{:016x}", xxh64(html.as_bytes(), 0))
+    }
+
+    /// Stores a page and returns its hash; storing a page already present only marks it used
+    pub fn put(&self, html: &str) -> Result<String, SnapshotError> {
+        let hash = Self::hash_of(html);
+        {
+            let mut index = self.lock();
+            let seq = index.tick();
+            if let Some(entry) = index.entries.get_mut(&hash) {
+                entry.used_seq = seq;
+                return Ok(hash);
+            }
+        }
+
+        let compressed = zstd::encode_all(html.as_bytes(), self.config.compression_level)?;
+        let size = compressed.len() as u64;
+        if size > self.config.max_bytes {
+            return Err(SnapshotError::TooLarge {
+                size,
+                max_bytes: self.config.max_bytes,
+            });
+        }
+        let path = self.path_of(&hash);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // * Written aside and renamed so readers never see a partial file
+        let partial = path.with_extension("partial");
+        fs::write(&partial, &compressed)?;
+        fs::rename(&partial, &path)?;
+
+        {
+            let mut index = self.lock();
+            let seq = index.tick();
+            // * A concurrent put of the same page already counted the file
+            if !index.entries.contains_key(&hash) {
+                index.total_bytes += size;
+                index.entries.insert(
+                    hash.clone(),
+                    SnapshotEntry {
+                        size,
+                        stored_at: current_timestamp(),
+                        stored_seq: seq,
+                        used_seq: seq,
+                    },
+                );
+            }
+        }
+        if self.config.max_age_secs.is_some() {
+            self.evict_expired()?;
+        }
+        self.enforce_budget(Some(&hash))?;
+        Ok(hash)
+    }
+
+    /// Returns the page stored under `hash`, or None when it was never stored or was evicted
+    pub fn get(&self, hash: &str) -> Result<Option<String>, SnapshotError> {
+        {
+            let mut index = self.lock();
+            let seq = index.tick();
+            match index.entries.get_mut(hash) {
+                Some(entry) => entry.used_seq = seq,
+                None => return Ok(None),
+            }
+        }
+        let compressed = match fs::read(self.path_of(hash)) {
+            Ok(compressed) => compressed,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                // * Removed behind the store's back
+                self.lock().remove(hash);
+                return Ok(None);
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let html = zstd::decode_all(compressed.as_slice())?;
+        Ok(Some(String::from_utf8_lossy(&html).into_owned()))
+    }
+
+    /// Returns true when a snapshot is stored under `hash`
+    pub fn contains(&self, hash: &str) -> bool {
+        self.lock().entries.contains_key(hash)
+    }
+
+    /// Deletes a snapshot; returns false when none was stored
+    pub fn remove(&self, hash: &str) -> Result<bool, SnapshotError> {
+        if self.lock().remove(hash).is_none() {
+            return Ok(false);
+        }
+        remove_file(&self.path_of(hash))?;
+        Ok(true)
+    }
+
+    /// Evicts snapshots older than `max_age_secs` and returns how many were removed
+    pub fn evict_expired(&self) -> Result<usize, SnapshotError> {
+        let Some(max_age) = self.config.max_age_secs else {
+            return Ok(0);
+        };
+        let cutoff = current_timestamp().saturating_sub(max_age);
+        let expired: Vec<String> = {
+            let mut index = self.lock();
+            let expired: Vec<String> = index
+                .entries
+                .iter()
+                .filter(|(_, entry)| entry.stored_at < cutoff)
+                .map(|(hash, _)| hash.clone())
+                .collect();
+            for hash in &expired {
+                index.remove(hash);
+            }
+            expired
+        };
+        for hash in &expired {
+            remove_file(&self.path_of(hash))?;
+            ops::record_snapshot_eviction("expired");
+        }
+        Ok(expired.len())
+    }
+
+    /// Returns the number of stored snapshots
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the compressed bytes stored
+    pub fn total_bytes(&self) -> u64 {
+        self.lock().total_bytes
+    }
+
+    /// Evicts snapshots by policy until the store fits its budget, sparing `keep`
+    fn enforce_budget(&self, keep: Option<&str>) -> Result<(), SnapshotError> {
+        let evicted: Vec<String> = {
+            let mut index = self.lock();
+            let mut evicted = Vec::new();
+            while index.total_bytes > self.config.max_bytes {
+                let victim = index
+                    .entries
+                    .iter()
+                    .filter(|(hash, _)| Some(hash.as_str()) != keep)
+                    .min_by_key(|(_, entry)| match self.config.eviction {
+                        SnapshotEviction::Oldest => entry.stored_seq,
+                        SnapshotEviction::LeastRecentlyUsed => entry.used_seq,
+                    })
+                    .map(|(hash, _)| hash.clone());
+                let Some(victim) = victim else {
+                    break;
+                };
+                index.remove(&victim);
+                evicted.push(victim);
+            }
+            evicted
+        };
+        for hash in &evicted {
+            remove_file(&self.path_of(hash))?;
+            ops::record_snapshot_eviction(self.config.eviction.as_str());
+        }
+        Ok(())
+    }
+
+    /// Returns the file a hash is stored in, sharded by its first two characters
+    fn path_of(&self, hash: &str) -> PathBuf {
+        let shard = hash.get(..2).unwrap_or("00");
+        self.dir.join(shard).join(format!("{}.{}", hash, SNAPSHOT_EXTENSION))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SnapshotIndex> {
+        self.index.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Deletes a file, treating one that is already gone as deleted
+fn remove_file(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(name: &str) -> SnapshotConfig {
+        let dir = std::env::temp_dir().join(format!("titan-snapshots-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        SnapshotConfig {
+            dir: Some(dir),
+            ..Default::default()
+        }
+    }
+
+    // * Pseudo-random text compresses poorly, so sizes stay predictable
+    fn page(seed: u64) -> String {
+        let words: String = (0..400u64)
+            .map(|i| format!("{:x} ", xxh64(&(seed * 1000 + i).to_le_bytes(), 0)))
+            .collect();
+        format!("<html><body><p>{}</p></body></html>", words)
+    }
+
+    #[test]
+    fn test_put_get_and_reopen() {
+        let config = config("reopen");
+        let store = SnapshotStore::open(config.clone()).unwrap();
+        let html = page(1);
+
+        let hash = store.put(&html).unwrap();
+        assert_eq!(hash, SnapshotStore::hash_of(&html));
+        assert_eq!(store.put(&html).unwrap(), hash);
+        assert_eq!(store.len(), 1);
+        assert!(store.total_bytes() < html.len() as u64);
+        assert_eq!(store.get(&hash).unwrap(), Some(html.clone()));
+        assert_eq!(store.get("0000000000000000").unwrap(), None);
+        drop(store);
+
+        let reopened = SnapshotStore::open(config.clone()).unwrap();
+        assert_eq!(reopened.get(&hash).unwrap(), Some(html));
+        assert!(reopened.remove(&hash).unwrap());
+        assert!(!reopened.contains(&hash) && reopened.is_empty());
+        fs::remove_dir_all(config.dir.unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_budget_eviction_policies() {
+        for eviction in [SnapshotEviction::Oldest, SnapshotEviction::LeastRecentlyUsed] {
+            let mut config = config(eviction.as_str());
+            let probe = SnapshotStore::open(config.clone()).unwrap();
+            let size = {
+                let hash = probe.put(&page(1)).unwrap();
+                let size = probe.total_bytes();
+                probe.remove(&hash).unwrap();
+                size
+            };
+            // * Room for two snapshots, not three
+            config.max_bytes = size * 5 / 2;
+            config.eviction = eviction;
+            let store = SnapshotStore::open(config.clone()).unwrap();
+
+            let first = store.put(&page(1)).unwrap();
+            let second = store.put(&page(2)).unwrap();
+            // * Reading the first snapshot makes the second the least recently used
+            store.get(&first).unwrap();
+            let third = store.put(&page(3)).unwrap();
+
+            assert_eq!(store.len(), 2);
+            assert!(store.total_bytes() <= config.max_bytes);
+            assert!(store.contains(&third));
+            match eviction {
+                SnapshotEviction::Oldest => assert!(!store.contains(&first) && store.contains(&second)),
+                SnapshotEviction::LeastRecentlyUsed => assert!(store.contains(&first) && !store.contains(&second)),
+            }
+            fs::remove_dir_all(config.dir.unwrap()).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_oversized_and_expired_snapshots() {
+        let config = SnapshotConfig {
+            max_bytes: 64,
+            ..config("limits")
+        };
+        let store = SnapshotStore::open(config.clone()).unwrap();
+        assert!(matches!(store.put(&page(1)), Err(SnapshotError::TooLarge { .. })));
+
+        let aging = SnapshotStore::open(SnapshotConfig {
+            max_age_secs: Some(60),
+            ..config.clone()
+        })
+        .unwrap();
+        let hash = aging.put("<p>small</p>").unwrap();
+        aging.lock().entries.get_mut(&hash).unwrap().stored_at -= 120;
+        assert_eq!(aging.evict_expired().unwrap(), 1);
+        assert_eq!(aging.get(&hash).unwrap(), None);
+        fs::remove_dir_all(config.dir.unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,154 @@
+// * [FR-05] Structural Content Hashing
+// * Rotating widgets make every recrawl look like an edit: "updated 5 minutes ago", view
+// * counters, related-article lists. The structural hash covers the cleaned main text with
+// * those volatile regions removed, so change detection only fires on real edits.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::sync::LazyLock;
+use xxhash_rust::xxh64::xxh64;
+
+// * Blocks at most this long can be dropped whole as timestamps or counters
+const MAX_VOLATILE_BLOCK_LEN: usize = 80;
+// * Blocks after a related-articles heading shorter than this are taken as its teasers
+const MAX_TEASER_LEN: usize = 160;
+
+static RE_CLOCK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\b\d{1,2}:\d{2}(:\d{2})?(\s?[ap]\.?m\.?)?\b").unwrap());
+static RE_ISO_TIMESTAMP: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}(:\d{2})?(\.\d+)?(Z|[+-]\d{2}:?\d{2})?").unwrap());
+static RE_RELATIVE_TIME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(\d+|an?|one)\s+(sec(ond)?s?|min(ute)?s?|hours?|hrs?|days?|weeks?|months?)\s+ago\b|\bjust now\b")
+        .unwrap()
+});
+static RE_COUNTER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b\d[\d,.]*\s?[km]?\s+(views?|reads?|readers|comments?|shares?|likes?|reactions?|replies|votes?|watching|online)\b",
+    )
+    .unwrap()
+});
+static RE_VOLATILE_LABEL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^(last\s+)?(updated|modified|published|posted|edited)\b|^(as of|last (refreshed|checked))\b")
+        .unwrap()
+});
+static RE_RELATED_HEADING: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)^(related( articles| stories| posts| content| reading)?|read next|more from|more stories|you (may|might) also like|recommended( for you)?|trending( now)?|most (read|popular|viewed)|popular( now)?|don't miss)\b",
+    )
+    .unwrap()
+});
+
+/// What the change tracker compares between crawls of a URL
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentHashMode {
+    /// The record's `content_hash`, over the full persisted text
+    #[default]
+    Full,
+    /// The main text without timestamps, counters and related-article blocks
+    Structural,
+}
+
+impl ContentHashMode {
+    /// Returns the hash and text this mode compares for a page
+    ///
+    /// `Full` passes both through unchanged; `Structural` strips the text once and hashes it.
+    pub fn comparable<'a>(&self, content_hash: u64, text: &'a str) -> (u64, Cow<'a, str>) {
+        match self {
+            ContentHashMode::Full => (content_hash, Cow::Borrowed(text)),
+            ContentHashMode::Structural => {
+                let text = structural_text(text);
+                (xxh64(text.as_bytes(), 0), Cow::Owned(text))
+            }
+        }
+    }
+}
+
+/// Strips volatile regions from extracted text
+///
+/// Works on the blank-line separated blocks the refinery emits:
+/// - short blocks that are only a timestamp, byline date or counter are dropped
+/// - a related-articles heading is dropped with the teasers that follow it
+/// - clock times, ISO timestamps, relative times and counters are removed inside other blocks
+pub fn structural_text(text: &str) -> String {
+    let mut kept = Vec::new();
+    let mut in_related = false;
+    for block in text.split("\n\n").map(str::trim).filter(|block| !block.is_empty()) {
+        if block.len() <= MAX_VOLATILE_BLOCK_LEN && RE_RELATED_HEADING.is_match(block) {
+            in_related = true;
+            continue;
+        }
+        if in_related {
+            if block.starts_with('•') || block.len() < MAX_TEASER_LEN {
+                continue;
+            }
+            in_related = false;
+        }
+
+        let masked = mask_volatile(block);
+        if block.len() <= MAX_VOLATILE_BLOCK_LEN && (RE_VOLATILE_LABEL.is_match(block) || is_residue(&masked)) {
+            continue;
+        }
+        kept.push(masked);
+    }
+    kept.join("\n\n")
+}
+
+/// Hashes the structural text (xxHash64, seed 0, like `MultimodalRecord::hash_content`)
+pub fn structural_hash(text: &str) -> u64 {
+    xxh64(structural_text(text).as_bytes(), 0)
+}
+
+fn mask_volatile(block: &str) -> String {
+    let block = RE_ISO_TIMESTAMP.replace_all(block, "");
+    let block = RE_CLOCK.replace_all(&block, "");
+    let block = RE_RELATIVE_TIME.replace_all(&block, "");
+    let block = RE_COUNTER.replace_all(&block, "");
+    block.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// * What is left of a block that was only volatile tokens: separators like "·" or "|"
+fn is_residue(masked: &str) -> bool {
+    !masked.chars().any(char::is_alphanumeric)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &str = "The harbor authority approved the dredging plan after a long debate about costs, \
+        ferry schedules and the future of the fish market on the old waterfront. Work starts in the spring.";
+
+    fn page(widgets: &str, related: &str) -> String {
+        format!("Harbor dredging approved\n\n{widgets}\n\n{BODY}\n\nRelated stories\n\n{related}")
+    }
+
+    #[test]
+    fn test_rotating_widgets_do_not_change_the_hash() {
+        let first = page("Updated 5 minutes ago · 1,204 views", "• Ferry fares rise\n\n• Gulls return early");
+        let second = page("Updated 2 hours ago · 13.5k views", "• New lighthouse keeper named");
+        assert_ne!(first, second);
+        assert_eq!(structural_hash(&first), structural_hash(&second));
+        assert_eq!(structural_text(&first), format!("Harbor dredging approved\n\n{BODY}"));
+
+        // * A real edit still changes it
+        let edited = page("Updated just now", "").replace("long debate", "short debate");
+        assert_ne!(structural_hash(&first), structural_hash(&edited));
+    }
+
+    #[test]
+    fn test_inline_volatile_tokens_are_masked() {
+        let live = format!("{BODY} The vote closed at 14:32 with 87 comments so far.");
+        let later = format!("{BODY} The vote closed at 9:05 pm with 212 comments so far.");
+        assert_eq!(structural_hash(&live), structural_hash(&later));
+        // * Long paragraphs after a related heading end its block
+        let article = format!("Related\n\n• Short teaser\n\n{BODY}");
+        assert_eq!(structural_text(&article), BODY);
+
+        assert_eq!(ContentHashMode::Full.comparable(7, &live), (7, Cow::Borrowed(live.as_str())));
+        let (hash, text) = ContentHashMode::Structural.comparable(7, &live);
+        assert_eq!(hash, structural_hash(&later));
+        assert!(!text.contains("14:32"));
+    }
+}
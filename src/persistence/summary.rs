@@ -0,0 +1,101 @@
+// * [FR-09] Summarization Enrichment
+// * Optional enrichment stage producing a short abstract per record.
+// * Models plug in through `SummaryProvider`; `LeadSummarizer` keeps the opening sentences.
+
+use super::ai_worker::AsyncResult;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Trait for summarization backends (local models, hosted APIs)
+pub trait SummaryProvider: Send + Sync {
+    /// Provider name, used in logs
+    fn name(&self) -> &str;
+
+    /// Summarizes `text` in roughly `target_words` words
+    fn summarize(&self, text: &str, target_words: usize) -> AsyncResult<String>;
+}
+
+/// Extractive summarizer: the lead sentences of the text, up to the target length
+///
+/// News and documentation put the gist first, so the lead is a cheap, model-free
+/// abstract. A first sentence longer than the target is cut at a word boundary.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LeadSummarizer;
+
+impl LeadSummarizer {
+    /// Returns the lead sentences of `text`, about `target_words` words long
+    pub fn lead(text: &str, target_words: usize) -> String {
+        let target_words = target_words.max(1);
+        let mut summary = String::new();
+        let mut words = 0;
+
+        for sentence in text.unicode_sentences() {
+            let sentence = sentence.trim();
+            let count = sentence.unicode_words().count();
+            if count == 0 {
+                continue;
+            }
+            if words > 0 && words + count > target_words {
+                break;
+            }
+            if !summary.is_empty() {
+                summary.push(' ');
+            }
+            summary.push_str(sentence);
+            words += count;
+            if words >= target_words {
+                break;
+            }
+        }
+
+        if words > target_words {
+            summary = truncate_words(&summary, target_words);
+        }
+        summary
+    }
+}
+
+impl SummaryProvider for LeadSummarizer {
+    fn name(&self) -> &str {
+        "lead"
+    }
+
+    fn summarize(&self, text: &str, target_words: usize) -> AsyncResult<String> {
+        let summary = Self::lead(text, target_words);
+        Box::pin(async move { Ok(summary) })
+    }
+}
+
+// * Keeps the first `max_words` words, marking the cut with an ellipsis
+fn truncate_words(text: &str, max_words: usize) -> String {
+    let cut = text
+        .unicode_word_indices()
+        .nth(max_words)
+        .map_or(text.len(), |(index, _)| index);
+    format!("{}…", text[..cut].trim_end().trim_end_matches([',', ';', ':']))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARTICLE: &str = "The river flooded the lower town overnight. Residents moved to higher ground \
+        before dawn. Officials expect the water to recede by Friday, and schools stay closed until then.";
+
+    #[tokio::test]
+    async fn test_lead_summary_keeps_whole_sentences() {
+        let summary = LeadSummarizer.summarize(ARTICLE, 14).await.unwrap();
+        assert_eq!(
+            summary,
+            "The river flooded the lower town overnight. Residents moved to higher ground before dawn."
+        );
+        // * A short text is kept whole
+        assert_eq!(LeadSummarizer::lead(ARTICLE, 500), ARTICLE);
+    }
+
+    #[test]
+    fn test_long_first_sentence_is_cut() {
+        let summary = LeadSummarizer::lead(ARTICLE, 3);
+        assert_eq!(summary, "The river flooded…");
+        assert!(LeadSummarizer::lead("", 10).is_empty());
+    }
+}
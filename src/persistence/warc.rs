@@ -0,0 +1,636 @@
+// * WARC Archive Output
+// * Archives fetched pages in WARC 1.1 so a crawl can be replayed and kept for
+// * compliance. Each record can be its own gzip member (the usual .warc.gz layout), and the
+// * reader turns an archive back into HTML pages for offline Refinery runs.
+
+use crate::network::decode::decode_body;
+use crate::network::decompress::{decompress_body, DEFAULT_MAX_DECOMPRESSED_BYTES};
+use crate::refinery::{Refinery, RefineryResult};
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use url::Url;
+use uuid::Uuid;
+
+/// Version line written at the start of every record
+pub const WARC_VERSION: &str = "WARC/1.1";
+
+// * First two bytes of a gzip member
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[derive(Debug, Error)]
+pub enum WarcError {
+    #[error("WARC I/O failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Malformed WARC record #{record}: {message}")]
+    Malformed { record: usize, message: String },
+}
+
+/// One HTTP exchange to archive as a request/response record pair
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpCapture<'a> {
+    /// URL the response was served from
+    pub url: &'a str,
+    pub status: u16,
+    /// Response headers; Content-Length is added from the body
+    pub headers: Vec<(String, String)>,
+    pub body: &'a [u8],
+    /// True when the body was cut at the fetcher's response size limit
+    pub truncated: bool,
+}
+
+/// Appends WARC records to a file, one gzip member per record when compressing
+///
+/// Records are flushed as they are written, so an interrupted crawl leaves a readable
+/// archive. A `warcinfo` record opens every new file.
+///
+/// # Example
+/// ```ignore
+/// let archive = WarcWriter::create("crawl.warc.gz", true)?;
+/// archive.write_response(&HttpCapture { url, status: 200, headers, body, truncated: false })?;
+/// ```
+pub struct WarcWriter {
+    path: PathBuf,
+    gzip: bool,
+    out: Mutex<BufWriter<File>>,
+    records: AtomicU64,
+}
+
+impl WarcWriter {
+    /// Opens `path` for appending, writing a `warcinfo` record when the file is new
+    pub fn create(path: impl AsRef<Path>, gzip: bool) -> Result<Self, WarcError> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let fresh = file.metadata()?.len() == 0;
+        let writer = Self {
+            path,
+            gzip,
+            out: Mutex::new(BufWriter::new(file)),
+            records: AtomicU64::new(0),
+        };
+        if fresh {
+            writer.write_warcinfo()?;
+        }
+        Ok(writer)
+    }
+
+    /// Returns the archive path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns true when records are gzip-compressed
+    pub fn is_gzip(&self) -> bool {
+        self.gzip
+    }
+
+    /// Returns how many records this writer has appended
+    pub fn records_written(&self) -> u64 {
+        self.records.load(Ordering::Relaxed)
+    }
+
+    /// Archives an HTTP exchange as a `request` and a `response` record; returns the
+    /// response's record id
+    pub fn write_response(&self, capture: &HttpCapture<'_>) -> Result<String, WarcError> {
+        let date = warc_date(current_timestamp());
+        let response_id = record_id();
+
+        let mut request = format!("GET {} HTTP/1.1\r\n", request_target(capture.url));
+        if let Some(host) = Url::parse(capture.url).ok().and_then(|u| u.host_str().map(str::to_string)) {
+            request.push_str(&format!("Host: {}\r\n", host));
+        }
+        request.push_str("\r\n");
+
+        let reason = reqwest::StatusCode::from_u16(capture.status)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+            .unwrap_or("");
+        let mut response = format!("HTTP/1.1 {} {}\r\n", capture.status, reason).into_bytes();
+        for (name, value) in &capture.headers {
+            response.extend_from_slice(format!("{}: {}\r\n", name, value).as_bytes());
+        }
+        response.extend_from_slice(format!("Content-Length: {}\r\n\r\n", capture.body.len()).as_bytes());
+        response.extend_from_slice(capture.body);
+
+        let mut response_headers = vec![
+            ("WARC-Type", "response".to_string()),
+            ("WARC-Record-ID", response_id.clone()),
+            ("WARC-Date", date.clone()),
+            ("WARC-Target-URI", capture.url.to_string()),
+            ("Content-Type", "application/http;msgtype=response".to_string()),
+        ];
+        if capture.truncated {
+            response_headers.push(("WARC-Truncated", "length".to_string()));
+        }
+        let request_headers = vec![
+            ("WARC-Type", "request".to_string()),
+            ("WARC-Record-ID", record_id()),
+            ("WARC-Date", date),
+            ("WARC-Target-URI", capture.url.to_string()),
+            ("WARC-Concurrent-To", response_id.clone()),
+            ("Content-Type", "application/http;msgtype=request".to_string()),
+        ];
+
+        // * Both records go out under one lock so pairs are never interleaved
+        let mut out = self.out.lock().unwrap_or_else(|e| e.into_inner());
+        self.append(&mut out, &request_headers, request.as_bytes())?;
+        self.append(&mut out, &response_headers, &response)?;
+        out.flush()?;
+        Ok(response_id)
+    }
+
+    /// Archives content that was not a plain HTTP response (e.g. a rendered DOM) as a
+    /// `resource` record; returns its record id
+    pub fn write_resource(&self, url: &str, content_type: &str, body: &[u8]) -> Result<String, WarcError> {
+        self.write_block("resource", url, content_type, body, false)
+    }
+
+    /// Archives a transformed copy of a response (e.g. a body transcoded to UTF-8) as a
+    /// `conversion` record; returns its record id
+    pub fn write_conversion(
+        &self,
+        url: &str,
+        content_type: &str,
+        body: &[u8],
+        truncated: bool,
+    ) -> Result<String, WarcError> {
+        self.write_block("conversion", url, content_type, body, truncated)
+    }
+
+    fn write_block(
+        &self,
+        warc_type: &str,
+        url: &str,
+        content_type: &str,
+        body: &[u8],
+        truncated: bool,
+    ) -> Result<String, WarcError> {
+        let id = record_id();
+        let mut headers = vec![
+            ("WARC-Type", warc_type.to_string()),
+            ("WARC-Record-ID", id.clone()),
+            ("WARC-Date", warc_date(current_timestamp())),
+            ("WARC-Target-URI", url.to_string()),
+            ("Content-Type", content_type.to_string()),
+        ];
+        if truncated {
+            headers.push(("WARC-Truncated", "length".to_string()));
+        }
+        let mut out = self.out.lock().unwrap_or_else(|e| e.into_inner());
+        self.append(&mut out, &headers, body)?;
+        out.flush()?;
+        Ok(id)
+    }
+
+    fn write_warcinfo(&self) -> Result<(), WarcError> {
+        let filename = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let headers = vec![
+            ("WARC-Type", "warcinfo".to_string()),
+            ("WARC-Record-ID", record_id()),
+            ("WARC-Date", warc_date(current_timestamp())),
+            ("WARC-Filename", filename),
+            ("Content-Type", "application/warc-fields".to_string()),
+        ];
+        let fields = format!(
+            "software: titan-flow/{}\r\nformat: WARC File Format 1.1\r\nconformsTo: https://iipc.github.io/warc-specifications/specifications/warc-format/warc-1.1/\r\n",
+            env!("CARGO_PKG_VERSION")
+        );
+        let mut out = self.out.lock().unwrap_or_else(|e| e.into_inner());
+        self.append(&mut out, &headers, fields.as_bytes())?;
+        out.flush()?;
+        Ok(())
+    }
+
+    fn append(&self, out: &mut BufWriter<File>, headers: &[(&str, String)], block: &[u8]) -> Result<(), WarcError> {
+        let mut record = format!("{}\r\n", WARC_VERSION).into_bytes();
+        for (name, value) in headers {
+            record.extend_from_slice(format!("{}: {}\r\n", name, value).as_bytes());
+        }
+        record.extend_from_slice(format!("Content-Length: {}\r\n\r\n", block.len()).as_bytes());
+        record.extend_from_slice(block);
+        record.extend_from_slice(b"\r\n\r\n");
+
+        if self.gzip {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&record)?;
+            out.write_all(&encoder.finish()?)?;
+        } else {
+            out.write_all(&record)?;
+        }
+        self.records.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+/// A record read back from an archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarcRecord {
+    /// Version line, e.g. "WARC/1.1"
+    pub version: String,
+    /// Named fields in file order
+    pub headers: Vec<(String, String)>,
+    pub block: Vec<u8>,
+}
+
+impl WarcRecord {
+    /// Returns the first field with the given name (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the WARC-Type ("response", "request", "resource", ...)
+    pub fn warc_type(&self) -> Option<&str> {
+        self.header("WARC-Type")
+    }
+
+    pub fn target_uri(&self) -> Option<&str> {
+        self.header("WARC-Target-URI")
+    }
+
+    pub fn record_id(&self) -> Option<&str> {
+        self.header("WARC-Record-ID")
+    }
+
+    /// Returns the HTML page this record holds: a successful HTML `response`, a transcoded
+    /// `conversion`, or an HTML `resource` such as a rendered DOM
+    pub fn page(&self) -> Option<ArchivedPage> {
+        let url = self.target_uri()?.to_string();
+        let date = self.header("WARC-Date").unwrap_or_default().to_string();
+        match self.warc_type()? {
+            "response" => {
+                let response = parse_http_response(&self.block)?;
+                if !(200..300).contains(&response.status) {
+                    return None;
+                }
+                let content_type = response.header("Content-Type");
+                if content_type.is_some_and(|ct| !is_html(ct)) {
+                    return None;
+                }
+                let mut body = response.body.to_vec();
+                if response
+                    .header("Transfer-Encoding")
+                    .is_some_and(|te| te.to_ascii_lowercase().contains("chunked"))
+                {
+                    body = dechunk(&body)?;
+                }
+                let body =
+                    decompress_body(&body, response.header("Content-Encoding"), DEFAULT_MAX_DECOMPRESSED_BYTES).ok()?;
+                Some(ArchivedPage {
+                    url,
+                    html: decode_body(&body, content_type).text,
+                    date,
+                    rendered: false,
+                })
+            }
+            kind @ ("resource" | "conversion") if self.header("Content-Type").is_some_and(is_html) => {
+                Some(ArchivedPage {
+                    url,
+                    html: decode_body(&self.block, self.header("Content-Type")).text,
+                    date,
+                    rendered: kind == "resource",
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// HTML recovered from an archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedPage {
+    pub url: String,
+    /// Body transcoded to UTF-8
+    pub html: String,
+    /// WARC-Date of the capture
+    pub date: String,
+    /// True for rendered DOM snapshots (`resource` records)
+    pub rendered: bool,
+}
+
+impl ArchivedPage {
+    /// Runs the page through the Refinery, applying the page domain's settings
+    pub fn refine(&self, refinery: &Refinery) -> RefineryResult {
+        match Url::parse(&self.url).ok().and_then(|u| u.host_str().map(str::to_string)) {
+            Some(domain) => refinery.process_for_domain(&self.html, &domain),
+            None => refinery.process(&self.html),
+        }
+    }
+}
+
+/// Reads records from a WARC file, plain or gzip (per-record or whole-file)
+pub struct WarcReader<R> {
+    input: R,
+    records: usize,
+}
+
+impl WarcReader<Box<dyn BufRead + Send>> {
+    /// Opens an archive, detecting gzip from its first bytes
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, WarcError> {
+        let mut file = BufReader::new(File::open(path)?);
+        let gzip = file.fill_buf()?.starts_with(&GZIP_MAGIC);
+        let input: Box<dyn BufRead + Send> = if gzip {
+            // * Every gzip member is read in turn, so per-record compression is transparent
+            Box::new(BufReader::new(MultiGzDecoder::new(file)))
+        } else {
+            Box::new(file)
+        };
+        Ok(Self::new(input))
+    }
+}
+
+impl<R: BufRead> WarcReader<R> {
+    /// Reads uncompressed WARC data
+    pub fn new(input: R) -> Self {
+        Self { input, records: 0 }
+    }
+
+    /// Returns the next record, or None at the end of the archive
+    pub fn next_record(&mut self) -> Result<Option<WarcRecord>, WarcError> {
+        let record = self.records + 1;
+        let malformed = |message: String| WarcError::Malformed { record, message };
+
+        // * Records end with two CRLFs; skip them (and any stray blank lines) before the next
+        let version = loop {
+            let Some(line) = self.read_line()? else {
+                return Ok(None);
+            };
+            if !line.is_empty() {
+                break line;
+            }
+        };
+        if !version.starts_with("WARC/") {
+            return Err(malformed(format!("expected a WARC version line, found '{}'", version)));
+        }
+
+        let mut headers = Vec::new();
+        loop {
+            let line = self
+                .read_line()?
+                .ok_or_else(|| malformed("archive ends inside the record header".to_string()))?;
+            if line.is_empty() {
+                break;
+            }
+            let (name, value) = line
+                .split_once(':')
+                .ok_or_else(|| malformed(format!("invalid header line '{}'", line)))?;
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+
+        let length = headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("Content-Length"))
+            .and_then(|(_, value)| value.parse::<usize>().ok())
+            .ok_or_else(|| malformed("missing or invalid Content-Length".to_string()))?;
+        let mut block = vec![0; length];
+        self.input.read_exact(&mut block).map_err(|e| match e.kind() {
+            std::io::ErrorKind::UnexpectedEof => malformed(format!("block shorter than {} bytes", length)),
+            _ => WarcError::Io(e),
+        })?;
+
+        self.records = record;
+        Ok(Some(WarcRecord {
+            version,
+            headers,
+            block,
+        }))
+    }
+
+    /// Iterates over the HTML pages in the archive, skipping every other record
+    pub fn pages(self) -> impl Iterator<Item = Result<ArchivedPage, WarcError>> {
+        self.filter_map(|record| match record {
+            Ok(record) => record.page().map(Ok),
+            Err(e) => Some(Err(e)),
+        })
+    }
+
+    fn read_line(&mut self) -> Result<Option<String>, WarcError> {
+        let mut line = Vec::new();
+        if self.input.read_until(b'\n', &mut line)? == 0 {
+            return Ok(None);
+        }
+        while line.last().is_some_and(|b| *b == b'\n' || *b == b'\r') {
+            line.pop();
+        }
+        Ok(Some(String::from_utf8_lossy(&line).into_owned()))
+    }
+}
+
+impl<R: BufRead> Iterator for WarcReader<R> {
+    type Item = Result<WarcRecord, WarcError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().transpose()
+    }
+}
+
+/// Status, headers and body of an archived HTTP response
+struct HttpResponse<'a> {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: &'a [u8],
+}
+
+impl HttpResponse<'_> {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn parse_http_response(block: &[u8]) -> Option<HttpResponse<'_>> {
+    let split = block.windows(4).position(|w| w == b"\r\n\r\n")?;
+    let head = std::str::from_utf8(&block[..split]).ok()?;
+    let mut lines = head.split("\r\n");
+    let status = lines.next()?.split_whitespace().nth(1)?.parse().ok()?;
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect();
+    Some(HttpResponse {
+        status,
+        headers,
+        body: &block[split + 4..],
+    })
+}
+
+/// Undoes `Transfer-Encoding: chunked`
+fn dechunk(mut body: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    loop {
+        let line_end = body.windows(2).position(|w| w == b"\r\n")?;
+        let size_line = std::str::from_utf8(&body[..line_end]).ok()?;
+        let size = usize::from_str_radix(size_line.split(';').next()?.trim(), 16).ok()?;
+        body = &body[line_end + 2..];
+        if size == 0 {
+            return Some(out);
+        }
+        out.extend_from_slice(body.get(..size)?);
+        body = body.get(size + 2..)?;
+    }
+}
+
+fn is_html(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    mime == "text/html" || mime == "application/xhtml+xml"
+}
+
+/// Returns the path and query sent in the request line
+fn request_target(url: &str) -> String {
+    match Url::parse(url) {
+        Ok(parsed) => match parsed.query() {
+            Some(query) => format!("{}?{}", parsed.path(), query),
+            None => parsed.path().to_string(),
+        },
+        Err(_) => url.to_string(),
+    }
+}
+
+fn record_id() -> String {
+    format!("<urn:uuid:{}>", Uuid::new_v4())
+}
+
+/// Formats Unix seconds as a WARC-Date (ISO 8601, UTC)
+fn warc_date(unix_secs: u64) -> String {
+    let days = (unix_secs / 86_400) as i64;
+    let secs = unix_secs % 86_400;
+    // * Civil date from days since the epoch (proleptic Gregorian calendar)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs / 3_600,
+        secs % 3_600 / 60,
+        secs % 60
+    )
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("titan-warc-{}-{}", std::process::id(), name))
+    }
+
+    fn capture<'a>(url: &'a str, body: &'a [u8]) -> HttpCapture<'a> {
+        HttpCapture {
+            url,
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "text/html; charset=utf-8".to_string())],
+            body,
+            truncated: false,
+        }
+    }
+
+    #[test]
+    fn test_round_trip_plain_and_gzip() {
+        for gzip in [false, true] {
+            let path = temp_path(if gzip { "round.warc.gz" } else { "round.warc" });
+            let _ = std::fs::remove_file(&path);
+            let writer = WarcWriter::create(&path, gzip).unwrap();
+            let id = writer
+                .write_response(&capture("https://example.com/a?x=1", b"<html><body>Hello</body></html>"))
+                .unwrap();
+            writer
+                .write_resource("https://example.com/b", "text/html; charset=utf-8", b"<p>Rendered</p>")
+                .unwrap();
+            writer
+                .write_conversion("https://example.com/c", "text/html; charset=utf-8", b"<p>Caf\xc3\xa9</p>", true)
+                .unwrap();
+            assert_eq!(writer.records_written(), 5);
+            drop(writer);
+
+            let records: Vec<WarcRecord> = WarcReader::open(&path).unwrap().map(Result::unwrap).collect();
+            let types: Vec<_> = records.iter().filter_map(|r| r.warc_type()).collect();
+            assert_eq!(types, vec!["warcinfo", "request", "response", "resource", "conversion"]);
+            assert_eq!(records[4].header("WARC-Truncated"), Some("length"));
+            assert!(records.iter().all(|r| r.version == WARC_VERSION));
+            assert_eq!(records[1].header("WARC-Concurrent-To"), Some(id.as_str()));
+            assert!(records[1].block.starts_with(b"GET /a?x=1 HTTP/1.1\r\nHost: example.com\r\n"));
+
+            let pages: Vec<ArchivedPage> = WarcReader::open(&path).unwrap().pages().map(Result::unwrap).collect();
+            assert_eq!(pages.len(), 3);
+            assert_eq!(pages[0].url, "https://example.com/a?x=1");
+            assert_eq!(pages[0].html, "<html><body>Hello</body></html>");
+            assert_eq!(pages[2].html, "<p>Café</p>");
+            assert!(!pages[0].rendered && pages[1].rendered && !pages[2].rendered);
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+
+    #[test]
+    fn test_reads_chunked_and_skips_non_html_responses() {
+        let mut archive = Vec::new();
+        for (url, block) in [
+            (
+                "https://example.com/chunked",
+                &b"HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nTransfer-Encoding: chunked\r\n\r\n5\r\n<p>Hi\r\n4\r\n</p>\r\n0\r\n\r\n"[..],
+            ),
+            ("https://example.com/img.png", &b"HTTP/1.1 200 OK\r\nContent-Type: image/png\r\n\r\nPNG"[..]),
+            ("https://example.com/missing", &b"HTTP/1.1 404 Not Found\r\nContent-Type: text/html\r\n\r\nGone"[..]),
+        ] {
+            archive.extend_from_slice(
+                format!(
+                    "WARC/1.0\r\nWARC-Type: response\r\nWARC-Target-URI: {}\r\nContent-Length: {}\r\n\r\n",
+                    url,
+                    block.len()
+                )
+                .as_bytes(),
+            );
+            archive.extend_from_slice(block);
+            archive.extend_from_slice(b"\r\n\r\n");
+        }
+
+        let pages: Vec<ArchivedPage> = WarcReader::new(&archive[..]).pages().map(Result::unwrap).collect();
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].html, "<p>Hi</p>");
+
+        let truncated = b"WARC/1.1\r\nWARC-Type: resource\r\nContent-Length: 99\r\n\r\nshort";
+        let err = WarcReader::new(&truncated[..]).next_record().unwrap_err();
+        assert!(matches!(err, WarcError::Malformed { record: 1, .. }));
+    }
+
+    #[test]
+    fn test_warc_date() {
+        assert_eq!(warc_date(0), "1970-01-01T00:00:00Z");
+        assert_eq!(warc_date(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(warc_date(1_792_233_296), "2026-10-17T10:34:56Z");
+    }
+}
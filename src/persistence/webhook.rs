@@ -0,0 +1,505 @@
+// * Webhook Result Sink
+// * Pushes every persisted record to an HTTP endpoint as signed JSON, for consumers that
+// * want crawl results without running a message broker. Records wait in a bounded queue
+// * and are delivered in order by a background task that retries with exponential backoff.
+
+use super::ai_worker::{AsyncResult, EnrichmentError, RecordSink};
+use super::schema::MultimodalRecord;
+use crate::ops;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+// * Records buffered before new ones are dropped
+const DEFAULT_QUEUE_CAPACITY: usize = 1000;
+
+// * Retries after the first attempt before a record is given up on
+const DEFAULT_MAX_RETRIES: u32 = 5;
+
+// * Delay before the first retry; doubled on each further retry
+const DEFAULT_INITIAL_BACKOFF_MS: u64 = 500;
+
+// * Longest delay between retries
+const DEFAULT_MAX_BACKOFF_MS: u64 = 30_000;
+
+// * Per-request timeout
+const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+
+/// Header carrying `sha256=<hex HMAC of "<timestamp>.<body>">`
+pub const SIGNATURE_HEADER: &str = "X-Titan-Signature";
+
+/// Header carrying the Unix timestamp included in the signature
+pub const TIMESTAMP_HEADER: &str = "X-Titan-Timestamp";
+
+/// Header carrying the record id, stable across retries
+pub const DELIVERY_HEADER: &str = "X-Titan-Delivery";
+
+// * Queue name reported by the titan_queue_depth gauge
+const QUEUE_NAME: &str = "webhook";
+
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    #[error("Invalid webhook URL '{0}' (expected http:// or https://)")]
+    InvalidUrl(String),
+
+    #[error("Webhook secret must not be empty")]
+    MissingSecret,
+
+    #[error("Failed to build webhook HTTP client: {0}")]
+    Client(#[from] reqwest::Error),
+}
+
+/// Webhook endpoint and delivery tuning
+///
+/// # Example
+/// ```toml
+/// [webhook]
+/// url = "https://hooks.example.com/titan"
+/// secret = "change-me"        # or TITAN_WEBHOOK__SECRET
+/// max_retries = 3
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebhookConfig {
+    /// Endpoint receiving one POST per record; the sink is disabled when unset
+    pub url: Option<String>,
+    /// Shared secret for the HMAC-SHA256 payload signature
+    pub secret: String,
+    /// Records buffered for delivery; records arriving while it is full are dropped
+    pub queue_capacity: usize,
+    /// Retries after a failed attempt (network errors, 429 and 5xx responses)
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled on each further retry
+    pub initial_backoff_ms: u64,
+    /// Longest delay between retries
+    pub max_backoff_ms: u64,
+    /// Per-request timeout
+    pub timeout_ms: u64,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            secret: String::new(),
+            queue_capacity: DEFAULT_QUEUE_CAPACITY,
+            max_retries: DEFAULT_MAX_RETRIES,
+            initial_backoff_ms: DEFAULT_INITIAL_BACKOFF_MS,
+            max_backoff_ms: DEFAULT_MAX_BACKOFF_MS,
+            timeout_ms: DEFAULT_TIMEOUT_MS,
+        }
+    }
+}
+
+impl WebhookConfig {
+    /// Creates a config for an endpoint with default delivery tuning
+    pub fn new(url: impl Into<String>, secret: impl Into<String>) -> Self {
+        Self {
+            url: Some(url.into()),
+            secret: secret.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the delay before retry number `retry` (starting at 0)
+    fn backoff(&self, retry: u32) -> Duration {
+        let delay = self
+            .initial_backoff_ms
+            .saturating_mul(1u64 << retry.min(32))
+            .min(self.max_backoff_ms);
+        Duration::from_millis(delay)
+    }
+}
+
+/// Delivery counters since the sink was created
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct WebhookStats {
+    pub queued: u64,
+    pub delivered: u64,
+    /// Records given up on after the last retry or a non-retryable response
+    pub failed: u64,
+    /// Records rejected because the queue was full or the sink was closed
+    pub dropped: u64,
+}
+
+#[derive(Debug, Default)]
+struct StatsCounters {
+    queued: AtomicU64,
+    delivered: AtomicU64,
+    failed: AtomicU64,
+    dropped: AtomicU64,
+}
+
+/// Record sink that POSTs each record as signed JSON to a webhook
+///
+/// Receivers verify a delivery by computing HMAC-SHA256 over
+/// `"<X-Titan-Timestamp>.<raw body>"` with the shared secret and comparing it with the
+/// hex digest in `X-Titan-Signature` (after the `sha256=` prefix).
+///
+/// # Example
+/// ```ignore
+/// let webhook = Arc::new(WebhookSink::new(WebhookConfig::new(url, secret))?);
+/// let orchestrator = Orchestrator::new().with_result_sink(webhook.clone());
+/// orchestrator.run(seeds, config).await?;
+/// webhook.close().await; // * Waits for queued records to be delivered
+/// ```
+#[derive(Debug)]
+pub struct WebhookSink {
+    sender: Mutex<Option<mpsc::Sender<MultimodalRecord>>>,
+    worker: Mutex<Option<JoinHandle<()>>>,
+    stats: Arc<StatsCounters>,
+}
+
+impl WebhookSink {
+    /// Validates the config and starts the delivery task (requires a Tokio runtime)
+    pub fn new(config: WebhookConfig) -> Result<Self, WebhookError> {
+        let url = config.url.clone().unwrap_or_default();
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(WebhookError::InvalidUrl(url));
+        }
+        if config.secret.is_empty() {
+            return Err(WebhookError::MissingSecret);
+        }
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms.max(1)))
+            .build()?;
+
+        let (sender, receiver) = mpsc::channel(config.queue_capacity.max(1));
+        let stats = Arc::new(StatsCounters::default());
+        let delivery = Delivery {
+            client,
+            url,
+            config,
+            stats: Arc::clone(&stats),
+        };
+        let worker = tokio::spawn(delivery.run(receiver));
+
+        Ok(Self {
+            sender: Mutex::new(Some(sender)),
+            worker: Mutex::new(Some(worker)),
+            stats,
+        })
+    }
+
+    /// Queues a record for delivery; returns false when it was dropped
+    pub fn enqueue(&self, record: MultimodalRecord) -> bool {
+        let sender = self.sender.lock().unwrap();
+        let accepted = sender
+            .as_ref()
+            .is_some_and(|sender| sender.try_send(record).is_ok());
+        if accepted {
+            self.stats.queued.fetch_add(1, Ordering::Relaxed);
+            if let Some(sender) = sender.as_ref() {
+                ops::set_queue_depth(QUEUE_NAME, (sender.max_capacity() - sender.capacity()) as i64);
+            }
+        } else {
+            self.stats.dropped.fetch_add(1, Ordering::Relaxed);
+            ops::record_webhook_delivery("dropped");
+        }
+        accepted
+    }
+
+    /// Stops accepting records and waits until the queued ones are delivered or given up on
+    pub async fn close(&self) {
+        drop(self.sender.lock().unwrap().take());
+        let worker = self.worker.lock().unwrap().take();
+        if let Some(worker) = worker {
+            if let Err(e) = worker.await {
+                warn!(error = %e, "Webhook delivery task failed");
+            }
+        }
+    }
+
+    /// Returns the delivery counters
+    pub fn stats(&self) -> WebhookStats {
+        WebhookStats {
+            queued: self.stats.queued.load(Ordering::Relaxed),
+            delivered: self.stats.delivered.load(Ordering::Relaxed),
+            failed: self.stats.failed.load(Ordering::Relaxed),
+            dropped: self.stats.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl RecordSink for WebhookSink {
+    fn insert_record(&self, record: MultimodalRecord) -> AsyncResult<()> {
+        let accepted = self.enqueue(record);
+        Box::pin(async move {
+            if accepted {
+                Ok(())
+            } else {
+                Err(EnrichmentError::StorageError("Webhook queue is full or closed".to_string()))
+            }
+        })
+    }
+}
+
+impl RecordSink for Arc<WebhookSink> {
+    fn insert_record(&self, record: MultimodalRecord) -> AsyncResult<()> {
+        (**self).insert_record(record)
+    }
+}
+
+/// Background delivery state
+struct Delivery {
+    client: reqwest::Client,
+    url: String,
+    config: WebhookConfig,
+    stats: Arc<StatsCounters>,
+}
+
+impl Delivery {
+    async fn run(self, mut receiver: mpsc::Receiver<MultimodalRecord>) {
+        while let Some(record) = receiver.recv().await {
+            ops::set_queue_depth(QUEUE_NAME, receiver.len() as i64);
+            if self.deliver(&record).await {
+                self.stats.delivered.fetch_add(1, Ordering::Relaxed);
+                ops::record_webhook_delivery("delivered");
+            } else {
+                self.stats.failed.fetch_add(1, Ordering::Relaxed);
+                ops::record_webhook_delivery("failed");
+            }
+        }
+    }
+
+    /// Sends one record, retrying transient failures; returns true once it was accepted
+    async fn deliver(&self, record: &MultimodalRecord) -> bool {
+        let body = match serde_json::to_string(record) {
+            Ok(body) => body,
+            Err(e) => {
+                warn!(url = %record.url, error = %e, "Failed to serialize webhook payload");
+                return false;
+            }
+        };
+
+        for attempt in 0..=self.config.max_retries {
+            if attempt > 0 {
+                tokio::time::sleep(self.config.backoff(attempt - 1)).await;
+            }
+
+            // * Signed per attempt so receivers can reject stale timestamps
+            let timestamp = current_timestamp();
+            let response = self
+                .client
+                .post(&self.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(TIMESTAMP_HEADER, timestamp.to_string())
+                .header(SIGNATURE_HEADER, sign(&self.config.secret, timestamp, &body))
+                .header(DELIVERY_HEADER, record.id.as_str())
+                .body(body.clone())
+                .send()
+                .await;
+
+            match response {
+                Ok(response) if response.status().is_success() => return true,
+                Ok(response) => {
+                    let status = response.status();
+                    if !(status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS) {
+                        warn!(url = %record.url, status = %status, "Webhook rejected record");
+                        return false;
+                    }
+                    debug!(url = %record.url, status = %status, attempt, "Webhook delivery failed");
+                }
+                Err(e) => {
+                    debug!(url = %record.url, error = %e, attempt, "Webhook delivery failed");
+                }
+            }
+        }
+
+        warn!(url = %record.url, retries = self.config.max_retries, "Giving up on webhook delivery");
+        false
+    }
+}
+
+/// Returns the `X-Titan-Signature` value for a payload
+pub fn sign(secret: &str, timestamp: u64, body: &str) -> String {
+    // * HMAC accepts keys of any length
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC key of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256={}", hex)
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::service::{make_service_fn, service_fn};
+    use hyper::{Body, Request, Response, Server, StatusCode};
+    use std::convert::Infallible;
+    use std::net::SocketAddr;
+
+    /// Captured request: (signature, timestamp, body)
+    type Captured = Arc<Mutex<Vec<(String, u64, String)>>>;
+
+    /// Starts a local endpoint that answers the first `failures` requests with 503
+    async fn start_endpoint(failures: usize) -> (String, Captured) {
+        let captured: Captured = Arc::new(Mutex::new(Vec::new()));
+        let attempts = Arc::new(AtomicU64::new(0));
+
+        let make_svc = {
+            let captured = Arc::clone(&captured);
+            make_service_fn(move |_conn| {
+                let captured = Arc::clone(&captured);
+                let attempts = Arc::clone(&attempts);
+                async move {
+                    Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                        let captured = Arc::clone(&captured);
+                        let attempts = Arc::clone(&attempts);
+                        async move {
+                            let header = |name: &str| {
+                                req.headers()
+                                    .get(name)
+                                    .and_then(|v| v.to_str().ok())
+                                    .unwrap_or_default()
+                                    .to_string()
+                            };
+                            let signature = header(SIGNATURE_HEADER);
+                            let timestamp = header(TIMESTAMP_HEADER).parse().unwrap_or_default();
+                            let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+
+                            if attempts.fetch_add(1, Ordering::SeqCst) < failures as u64 {
+                                let mut response = Response::new(Body::empty());
+                                *response.status_mut() = StatusCode::SERVICE_UNAVAILABLE;
+                                return Ok::<_, Infallible>(response);
+                            }
+                            captured.lock().unwrap().push((
+                                signature,
+                                timestamp,
+                                String::from_utf8(body.to_vec()).unwrap(),
+                            ));
+                            Ok(Response::new(Body::empty()))
+                        }
+                    }))
+                }
+            })
+        };
+
+        let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_svc);
+        let url = format!("http://{}/hook", server.local_addr());
+        tokio::spawn(server);
+        (url, captured)
+    }
+
+    fn fast_retry_config(url: &str) -> WebhookConfig {
+        WebhookConfig {
+            initial_backoff_ms: 1,
+            max_backoff_ms: 5,
+            ..WebhookConfig::new(url, "s3cret")
+        }
+    }
+
+    #[test]
+    fn test_signature_is_stable_and_keyed() {
+        let signature = sign("s3cret", 1_700_000_000, "{\"a\":1}");
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(signature.len(), "sha256=".len() + 64);
+        assert_eq!(signature, sign("s3cret", 1_700_000_000, "{\"a\":1}"));
+        assert_ne!(signature, sign("other", 1_700_000_000, "{\"a\":1}"));
+        assert_ne!(signature, sign("s3cret", 1_700_000_001, "{\"a\":1}"));
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let config = WebhookConfig {
+            initial_backoff_ms: 100,
+            max_backoff_ms: 350,
+            ..Default::default()
+        };
+        assert_eq!(config.backoff(0), Duration::from_millis(100));
+        assert_eq!(config.backoff(1), Duration::from_millis(200));
+        assert_eq!(config.backoff(2), Duration::from_millis(350));
+        assert_eq!(config.backoff(40), Duration::from_millis(350));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_config() {
+        assert!(matches!(
+            WebhookSink::new(WebhookConfig::default()),
+            Err(WebhookError::InvalidUrl(_))
+        ));
+        assert!(matches!(
+            WebhookSink::new(WebhookConfig::new("https://hooks.example.com", "")),
+            Err(WebhookError::MissingSecret)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_delivers_signed_records_after_retries() {
+        let (url, captured) = start_endpoint(2).await;
+        let sink = WebhookSink::new(fast_retry_config(&url)).unwrap();
+
+        let record = MultimodalRecord::new("https://example.com/a".to_string(), 7, "Body".to_string());
+        sink.insert_record(record.clone()).await.unwrap();
+        sink.close().await;
+
+        let captured = captured.lock().unwrap();
+        assert_eq!(captured.len(), 1);
+        let (signature, timestamp, body) = &captured[0];
+        assert_eq!(signature, &sign("s3cret", *timestamp, body));
+        let delivered: MultimodalRecord = serde_json::from_str(body).unwrap();
+        assert_eq!(delivered.id, record.id);
+
+        assert_eq!(
+            sink.stats(),
+            WebhookStats {
+                queued: 1,
+                delivered: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let (url, captured) = start_endpoint(usize::MAX).await;
+        let sink = WebhookSink::new(WebhookConfig {
+            max_retries: 2,
+            ..fast_retry_config(&url)
+        })
+        .unwrap();
+
+        sink.enqueue(MultimodalRecord::new("https://example.com/a".to_string(), 1, "x".to_string()));
+        sink.close().await;
+
+        assert!(captured.lock().unwrap().is_empty());
+        assert_eq!(sink.stats().failed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_full_or_closed_queue_drops_records() {
+        let (url, _captured) = start_endpoint(0).await;
+        let sink = WebhookSink::new(WebhookConfig {
+            queue_capacity: 1,
+            ..fast_retry_config(&url)
+        })
+        .unwrap();
+
+        let record = || MultimodalRecord::new("https://example.com/a".to_string(), 1, "x".to_string());
+        // * The delivery task has not run yet, so the second record finds the queue full
+        assert!(sink.enqueue(record()));
+        assert!(!sink.enqueue(record()));
+
+        sink.close().await;
+        assert!(sink.insert_record(record()).await.is_err());
+        assert_eq!(sink.stats().dropped, 2);
+        assert_eq!(sink.stats().delivered, 1);
+    }
+}
@@ -17,7 +17,8 @@ pub struct TextChunk {
 }
 
 /// Configuration for the sliding window chunker
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ChunkerConfig {
     /// Maximum words per chunk (default: CHUNK_TOKEN_THRESHOLD = 2048)
     pub window_size: usize,
@@ -166,7 +167,7 @@ impl SlidingWindowChunker {
         let step = self.config.window_size.saturating_sub(self.config.overlap);
         let remaining_after_first = word_count.saturating_sub(self.config.window_size);
 
-        1 + (remaining_after_first + step - 1) / step
+        1 + remaining_after_first.div_ceil(step)
     }
 
     /// Returns the current configuration
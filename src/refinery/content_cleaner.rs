@@ -2,10 +2,38 @@
 // * Removes navigation, footer, sidebar, ads, scripts, and extracts main content.
 // * Ported from crawl4ai content filtering strategies
 
-use scraper::{Html, Selector};
+use super::language::{count_words, words, Language};
+use super::sanitize::sanitize_element;
+use super::template::{block_hash, LearnedTemplate};
+use scraper::{ElementRef, Html, Selector};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::LazyLock;
 
+// * Quality model v2 component weights (sum to 1.0)
+const WEIGHT_STRUCTURE: f32 = 0.30;
+const WEIGHT_LINK_DENSITY: f32 = 0.15;
+const WEIGHT_STOPWORDS: f32 = 0.15;
+const WEIGHT_SENTENCE_LENGTH: f32 = 0.15;
+const WEIGHT_BOILERPLATE: f32 = 0.10;
+const WEIGHT_TEXT_TO_MARKUP: f32 = 0.15;
+
+// * Content area with half its text inside links scores 0 for link density
+const MAX_LINK_DENSITY: f32 = 0.5;
+// * Words per sentence: full score between the ideal bounds, 0 outside min..max
+const MIN_SENTENCE_WORDS: f32 = 3.0;
+const IDEAL_SENTENCE_WORDS: (f32, f32) = (10.0, 30.0);
+const MAX_SENTENCE_WORDS: f32 = 80.0;
+// * Extracted text making up a quarter of the raw HTML already scores full marks
+const TARGET_TEXT_TO_MARKUP: f32 = 0.25;
+
+static SELECTOR_HTML: LazyLock<Selector> = LazyLock::new(|| Selector::parse("html").unwrap());
+static SELECTOR_BODY: LazyLock<Selector> = LazyLock::new(|| Selector::parse("body").unwrap());
+static SELECTOR_CONTENT_LANGUAGE: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse(r#"meta[http-equiv="content-language" i]"#).unwrap()
+});
+static SELECTOR_LINKS: LazyLock<Selector> = LazyLock::new(|| Selector::parse("a").unwrap());
+
 // * Selectors for elements to remove (boilerplate)
 // * These are prepared for future DOM manipulation when full tree shaking is implemented
 #[allow(dead_code)]
@@ -92,8 +120,23 @@ static SELECTOR_PRE: LazyLock<Selector> = LazyLock::new(|| Selector::parse("pre"
 static SELECTOR_CODE: LazyLock<Selector> =
     LazyLock::new(|| Selector::parse("code").unwrap());
 
+// * Attribution selectors: captions, footnote markers, and cited sources
+static SELECTOR_FIGCAPTION: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("figcaption").unwrap());
+static SELECTOR_NOTE_REFS: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse(
+        r##"a[role="doc-noteref"], a.footnote-ref, sup a[href^="#"],
+           a[href^="#fn"], a[href^="#footnote"], a[href^="#cite_note"]"##,
+    )
+    .unwrap()
+});
+static SELECTOR_CITES: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("cite, blockquote[cite], q[cite]").unwrap());
+static SELECTOR_WITH_ID: LazyLock<Selector> = LazyLock::new(|| Selector::parse("[id]").unwrap());
+
 /// Configuration for content cleaning
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct CleanerConfig {
     /// Remove navigation elements
     pub remove_nav: bool,
@@ -113,6 +156,72 @@ pub struct CleanerConfig {
     pub min_paragraph_length: usize,
     /// Minimum word count for extracted content
     pub min_word_count: usize,
+    /// Language rules used when the page declares no supported language and
+    /// its script does not identify one ("en", "de", "fr", "es", "ru", "zh", "ja", "ko")
+    pub locale: String,
+    /// Picks language rules from the page's `lang` attribute or script (otherwise always `locale`)
+    pub detect_language: bool,
+    /// Replaces the built-in phrase sets of every language when set
+    pub boilerplate_phrases: Option<Vec<String>>,
+    /// Per-host adjustments of the phrase list
+    pub domains: BTreeMap<String, BoilerplateOverride>,
+    /// Also returns the main content as sanitized HTML (`CleanedContent::html`)
+    pub include_html: bool,
+}
+
+/// Boilerplate phrase adjustments for a single host
+///
+/// # Example
+/// ```toml
+/// [refinery.cleaner.domains."recipes.example.com"]
+/// allowed_phrases = ["cookie"]
+/// extra_phrases = ["jump to recipe"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BoilerplateOverride {
+    /// Replaces the global phrase list for this host
+    pub phrases: Option<Vec<String>>,
+    /// Phrases added on top of the list
+    pub extra_phrases: Vec<String>,
+    /// Phrases never treated as boilerplate on this host
+    pub allowed_phrases: Vec<String>,
+}
+
+impl CleanerConfig {
+    /// Returns the lowercased phrase list used for a host and page language
+    pub fn phrases_for(&self, domain: Option<&str>, language: Language) -> Vec<String> {
+        let global = self
+            .boilerplate_phrases
+            .clone()
+            .unwrap_or_else(|| default_boilerplate_phrases(language.code()));
+        let Some(overrides) = domain.and_then(|d| self.domains.get(d)) else {
+            return lowercase(global);
+        };
+
+        let allowed = lowercase(overrides.allowed_phrases.clone());
+        let mut phrases = lowercase(overrides.phrases.clone().unwrap_or(global));
+        phrases.extend(lowercase(overrides.extra_phrases.clone()));
+        phrases.retain(|phrase| !allowed.contains(phrase));
+        phrases
+    }
+}
+
+/// Returns the built-in boilerplate phrases for a locale (English for unknown locales)
+///
+/// Only the primary subtag is used, so "de-AT" selects the German set.
+pub fn default_boilerplate_phrases(locale: &str) -> Vec<String> {
+    Language::from_tag(locale)
+        .unwrap_or_default()
+        .boilerplate_phrases()
+        .iter()
+        .chain(["©"].iter())
+        .map(|phrase| phrase.to_string())
+        .collect()
+}
+
+fn lowercase(phrases: Vec<String>) -> Vec<String> {
+    phrases.into_iter().map(|phrase| phrase.to_lowercase()).collect()
 }
 
 impl Default for CleanerConfig {
@@ -127,6 +236,11 @@ impl Default for CleanerConfig {
             remove_iframes: true,
             min_paragraph_length: 20,
             min_word_count: 25, // * Lowered from 50 to be more permissive
+            locale: "en".to_string(),
+            detect_language: true,
+            boilerplate_phrases: None,
+            domains: BTreeMap::new(),
+            include_html: false,
         }
     }
 }
@@ -144,12 +258,130 @@ pub struct CleanedContent {
     pub code_blocks: Vec<String>,
     /// Blockquotes found in the content
     pub quotes: Vec<String>,
+    /// Figure captions (`<figcaption>`)
+    #[serde(default)]
+    pub captions: Vec<String>,
+    /// Footnotes and inline citations, in document order
+    #[serde(default)]
+    pub citations: Vec<Citation>,
+    /// Main content as sanitized HTML (set when `include_html` is enabled)
+    ///
+    /// Only p, h1-h6, ul, ol, li, table, pre, blockquote, a and img survive, with
+    /// their safe attributes; boilerplate blocks are dropped as in `text`.
+    #[serde(default)]
+    pub html: Option<String>,
     /// Word count of extracted text
     pub word_count: usize,
     /// Whether main content area was found
     pub found_main_content: bool,
+    /// Language whose boilerplate and stopword rules were applied
+    #[serde(default)]
+    pub language: Language,
     /// Extraction quality score (0.0 - 1.0)
     pub quality_score: f32,
+    /// Per-component inputs behind `quality_score`, for debugging low scores
+    #[serde(default)]
+    pub quality: QualityBreakdown,
+}
+
+/// A source the content attributes a statement to
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Citation {
+    /// Footnote marker ("1", "[2]") or cited title/author
+    pub label: String,
+    /// Link to the cited source, if any
+    pub url: Option<String>,
+    /// Footnote text the marker points to
+    pub note: Option<String>,
+}
+
+/// One signal of the quality model
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct QualityComponent {
+    /// Measured input (a ratio, or words per sentence)
+    pub value: f32,
+    /// Input mapped to 0.0 - 1.0
+    pub score: f32,
+    /// Share of the overall score this component carries
+    pub weight: f32,
+}
+
+impl QualityComponent {
+    fn new(value: f32, score: f32, weight: f32) -> Self {
+        Self {
+            value,
+            score: score.clamp(0.0, 1.0),
+            weight,
+        }
+    }
+
+    /// Points this component adds to the overall score
+    pub fn contribution(&self) -> f32 {
+        self.score * self.weight
+    }
+}
+
+/// Breakdown of the quality score into weighted components
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QualityBreakdown {
+    /// Checklist of main content area, headings, paragraphs, length and rich blocks
+    pub structure: QualityComponent,
+    /// Share of content area text inside links (lower is better)
+    pub link_density: QualityComponent,
+    /// Share of extracted words that are stopwords
+    pub stopword_ratio: QualityComponent,
+    /// Mean words per sentence
+    pub avg_sentence_length: QualityComponent,
+    /// Share of candidate text blocks dropped as boilerplate (lower is better)
+    pub boilerplate_ratio: QualityComponent,
+    /// Extracted text bytes per byte of HTML
+    pub text_to_markup_ratio: QualityComponent,
+}
+
+impl QualityBreakdown {
+    /// Returns every component with its name
+    pub fn components(&self) -> [(&'static str, &QualityComponent); 6] {
+        [
+            ("structure", &self.structure),
+            ("link_density", &self.link_density),
+            ("stopword_ratio", &self.stopword_ratio),
+            ("avg_sentence_length", &self.avg_sentence_length),
+            ("boilerplate_ratio", &self.boilerplate_ratio),
+            ("text_to_markup_ratio", &self.text_to_markup_ratio),
+        ]
+    }
+
+    /// Returns the weighted sum of the component scores
+    pub fn total(&self) -> f32 {
+        self.components()
+            .iter()
+            .map(|(_, component)| component.contribution())
+            .sum::<f32>()
+            .min(1.0)
+    }
+
+    /// Returns the component that costs the most points
+    pub fn weakest(&self) -> (&'static str, &QualityComponent) {
+        self.components()
+            .into_iter()
+            .max_by(|(_, a), (_, b)| {
+                let lost = |c: &QualityComponent| c.weight - c.contribution();
+                lost(a).total_cmp(&lost(b))
+            })
+            .unwrap()
+    }
+}
+
+/// Text block counts gathered during extraction
+#[derive(Debug, Default)]
+struct BlockStats {
+    kept_bytes: usize,
+    boilerplate_bytes: usize,
+    // * Non-whitespace characters in the content area, and the part of them inside links
+    area_text_chars: usize,
+    link_text_chars: usize,
+    // * Hashes of the blocks not dropped by phrase rules (collected for template learning)
+    hashes: Vec<u64>,
 }
 
 impl CleanedContent {
@@ -162,23 +394,70 @@ impl CleanedContent {
 /// Extracts and cleans main content from HTML
 pub struct ContentCleaner {
     config: CleanerConfig,
+    // * Lowercased phrase lists per language, resolved once: global, then per overridden host
+    phrases: HashMap<Language, Vec<String>>,
+    domain_phrases: HashMap<String, HashMap<Language, Vec<String>>>,
 }
 
 impl ContentCleaner {
     /// Creates a new cleaner with default configuration
     pub fn new() -> Self {
-        Self {
-            config: CleanerConfig::default(),
-        }
+        Self::with_config(CleanerConfig::default())
     }
 
     /// Creates a new cleaner with custom configuration
     pub fn with_config(config: CleanerConfig) -> Self {
-        Self { config }
+        let resolve = |domain: Option<&str>| {
+            Language::ALL
+                .into_iter()
+                .map(|language| (language, config.phrases_for(domain, language)))
+                .collect::<HashMap<_, _>>()
+        };
+        let domain_phrases = config
+            .domains
+            .keys()
+            .map(|domain| (domain.clone(), resolve(Some(domain))))
+            .collect();
+        Self {
+            phrases: resolve(None),
+            domain_phrases,
+            config,
+        }
     }
 
     /// Main extraction method - removes boilerplate and extracts content
     pub fn clean(&self, html: &str) -> CleanedContent {
+        self.clean_page(html, &self.phrases, None).0
+    }
+
+    /// Extracts content using the boilerplate overrides configured for `domain`
+    pub fn clean_for_domain(&self, html: &str, domain: &str) -> CleanedContent {
+        let phrases = self.domain_phrases.get(domain).unwrap_or(&self.phrases);
+        self.clean_page(html, phrases, None).0
+    }
+
+    /// Extracts content, also dropping the blocks of a learned site template
+    ///
+    /// Returns the content and the hashes of the page's text blocks (template blocks
+    /// included), for the caller to feed back into its `TemplateLearner`.
+    pub fn clean_with_template(
+        &self,
+        html: &str,
+        domain: Option<&str>,
+        template: &LearnedTemplate,
+    ) -> (CleanedContent, Vec<u64>) {
+        let phrases = domain
+            .and_then(|domain| self.domain_phrases.get(domain))
+            .unwrap_or(&self.phrases);
+        self.clean_page(html, phrases, Some(template))
+    }
+
+    fn clean_page(
+        &self,
+        html: &str,
+        phrases: &HashMap<Language, Vec<String>>,
+        template: Option<&LearnedTemplate>,
+    ) -> (CleanedContent, Vec<u64>) {
         let document = Html::parse_document(html);
         let mut result = CleanedContent::default();
 
@@ -186,77 +465,90 @@ impl ContentCleaner {
         let main_content = self.find_main_content(&document);
         result.found_main_content = main_content.is_some();
 
-        // * Step 2: Extract content from main area or full document
-        let content_html = if let Some(main) = main_content {
-            main
-        } else {
-            // * Fallback: use body content
-            let body_selector = Selector::parse("body").unwrap();
-            if let Some(body) = document.select(&body_selector).next() {
-                body.html()
-            } else {
-                html.to_string()
-            }
-        };
-
-        // * Step 3: Parse content area and extract text
-        let content_doc = Html::parse_fragment(&content_html);
-        self.extract_content(&content_doc, &mut result);
+        // * Step 2: Scope extraction to the main area, falling back to <body>.
+        // * The area is walked in place; the document is parsed exactly once.
+        let area = main_content
+            .or_else(|| document.select(&SELECTOR_BODY).next())
+            .unwrap_or_else(|| document.root_element());
+
+        // * Step 3: Extract text from the content area
+        result.language = self.page_language(&document, area);
+        let phrases = &phrases[&result.language];
+        let mut blocks = self.extract_content(area, phrases, template, &mut result);
+        blocks.area_text_chars = area.text().map(visible_len).sum();
+        blocks.link_text_chars = area
+            .select(&SELECTOR_LINKS)
+            .flat_map(|link| link.text())
+            .map(visible_len)
+            .sum();
+        if self.config.include_html {
+            let skip = |element: &ElementRef| self.is_boilerplate_block(element, phrases, template);
+            result.html = Some(sanitize_element(area, &skip));
+        }
 
         // * Step 4: Calculate quality score
-        result.quality_score = self.calculate_quality(&result);
+        result.quality = self.calculate_quality(&result, &blocks, html.len());
+        result.quality_score = result.quality.total();
 
-        result
+        (result, blocks.hashes)
     }
 
-    /// Finds the main content area using priority selectors
-    fn find_main_content(&self, document: &Html) -> Option<String> {
-        // * Priority 1: <article> tag
-        for article in document.select(&SELECTOR_ARTICLE) {
-            let html = article.html();
-            if self.is_valid_content_area(&html) {
-                return Some(html);
-            }
+    /// Picks the rule language: declared `lang`, then script detection, then the configured locale
+    fn page_language(&self, document: &Html, content: ElementRef) -> Language {
+        let fallback = Language::from_tag(&self.config.locale).unwrap_or_default();
+        if !self.config.detect_language {
+            return fallback;
         }
 
-        // * Priority 2: <main> tag or role="main"
-        for main in document.select(&SELECTOR_MAIN) {
-            let html = main.html();
-            if self.is_valid_content_area(&html) {
-                return Some(html);
-            }
-        }
+        let declared = document
+            .select(&SELECTOR_HTML)
+            .next()
+            .and_then(|html| html.value().attr("lang"))
+            .or_else(|| {
+                document
+                    .select(&SELECTOR_CONTENT_LANGUAGE)
+                    .next()
+                    .and_then(|meta| meta.value().attr("content"))
+            })
+            .and_then(Language::from_tag);
+        declared
+            .or_else(|| Language::detect(&content.text().collect::<String>()))
+            .unwrap_or(fallback)
+    }
 
+    /// Finds the main content area using priority selectors
+    fn find_main_content<'a>(&self, document: &'a Html) -> Option<ElementRef<'a>> {
+        // * Priority 1: <article> tag
+        // * Priority 2: <main> tag or role="main"
         // * Priority 3: Common content class/id patterns
-        for content in document.select(&SELECTOR_CONTENT) {
-            let html = content.html();
-            if self.is_valid_content_area(&html) {
-                return Some(html);
-            }
-        }
-
-        None
+        [&*SELECTOR_ARTICLE, &*SELECTOR_MAIN, &*SELECTOR_CONTENT]
+            .into_iter()
+            .find_map(|selector| document.select(selector).find(|area| self.is_valid_content_area(*area)))
     }
 
-    /// Validates if an HTML snippet is a valid content area
-    fn is_valid_content_area(&self, html: &str) -> bool {
-        let doc = Html::parse_fragment(html);
-        let text: String = doc.root_element().text().collect();
-        let word_count = text.split_whitespace().count();
-
-        word_count >= self.config.min_word_count
+    /// Validates if an element holds enough words to be the content area
+    fn is_valid_content_area(&self, area: ElementRef) -> bool {
+        let text: String = area.text().collect();
+        count_words(&text) >= self.config.min_word_count
     }
 
-    /// Extracts paragraphs, headings, code, and quotes from content
-    fn extract_content(&self, document: &Html, result: &mut CleanedContent) {
+    /// Extracts paragraphs, headings, code, and quotes from the content area
+    fn extract_content(
+        &self,
+        area: ElementRef,
+        phrases: &[String],
+        template: Option<&LearnedTemplate>,
+        result: &mut CleanedContent,
+    ) -> BlockStats {
         let mut all_text = Vec::new();
+        let mut blocks = BlockStats::default();
 
         // * Extract headings
-        for heading in document.select(&SELECTOR_HEADINGS) {
+        for heading in area.select(&SELECTOR_HEADINGS) {
             let text: String = heading.text().collect();
             let text = text.trim();
 
-            if !text.is_empty() && !self.is_boilerplate_text(text) {
+            if !text.is_empty() && self.keep_block(text, phrases, template, &mut blocks) {
                 // * Determine heading level from tag name
                 let tag = heading.value().name();
                 let level = tag.chars().nth(1).and_then(|c| c.to_digit(10)).unwrap_or(1) as u8;
@@ -267,28 +559,32 @@ impl ContentCleaner {
         }
 
         // * Extract paragraphs
-        for para in document.select(&SELECTOR_PARAGRAPHS) {
+        for para in area.select(&SELECTOR_PARAGRAPHS) {
             let text: String = para.text().collect();
             let text = text.trim();
 
-            if text.len() >= self.config.min_paragraph_length && !self.is_boilerplate_text(text) {
+            if text.len() >= self.config.min_paragraph_length
+                && self.keep_block(text, phrases, template, &mut blocks)
+            {
                 result.paragraphs.push(text.to_string());
                 all_text.push(text.to_string());
             }
         }
 
         // * Extract list items (often contain important content)
-        for item in document.select(&SELECTOR_LIST_ITEMS) {
+        for item in area.select(&SELECTOR_LIST_ITEMS) {
             let text: String = item.text().collect();
             let text = text.trim();
 
-            if text.len() >= self.config.min_paragraph_length && !self.is_boilerplate_text(text) {
+            if text.len() >= self.config.min_paragraph_length
+                && self.keep_block(text, phrases, template, &mut blocks)
+            {
                 all_text.push(format!("• {}", text));
             }
         }
 
         // * Extract blockquotes
-        for quote in document.select(&SELECTOR_BLOCKQUOTE) {
+        for quote in area.select(&SELECTOR_BLOCKQUOTE) {
             let text: String = quote.text().collect();
             let text = text.trim();
 
@@ -298,7 +594,7 @@ impl ContentCleaner {
         }
 
         // * Extract code blocks
-        for pre in document.select(&SELECTOR_PRE) {
+        for pre in area.select(&SELECTOR_PRE) {
             let text: String = pre.text().collect();
             if !text.is_empty() {
                 result.code_blocks.push(text);
@@ -306,7 +602,7 @@ impl ContentCleaner {
         }
 
         // * Also check standalone code elements
-        for code in document.select(&SELECTOR_CODE) {
+        for code in area.select(&SELECTOR_CODE) {
             // * Skip if parent is <pre> (already captured)
             let text: String = code.text().collect();
             if !text.is_empty() && text.len() > 20 {
@@ -317,41 +613,60 @@ impl ContentCleaner {
             }
         }
 
+        // * Extract figure captions and citations
+        for caption in area.select(&SELECTOR_FIGCAPTION) {
+            let text = collapse_whitespace(&caption.text().collect::<String>());
+            if !text.is_empty() {
+                result.captions.push(text);
+            }
+        }
+        result.citations = extract_citations(area);
+
         // * Build final text
         result.text = all_text.join("\n\n");
-        result.word_count = result.text.split_whitespace().count();
+        result.word_count = count_words(&result.text);
+        blocks
+    }
+
+    /// Counts a candidate block against the phrase rules and the site template; returns true if it is kept
+    fn keep_block(
+        &self,
+        text: &str,
+        phrases: &[String],
+        template: Option<&LearnedTemplate>,
+        blocks: &mut BlockStats,
+    ) -> bool {
+        let mut boilerplate = self.is_boilerplate_text(text, phrases);
+        if let (false, Some(template)) = (boilerplate, template) {
+            let hash = block_hash(text);
+            blocks.hashes.push(hash);
+            boilerplate = template.contains_hash(hash);
+        }
+        blocks.keep(text, boilerplate)
+    }
+
+    /// Checks if a text block element (paragraph, heading, list item) is boilerplate
+    fn is_boilerplate_block(
+        &self,
+        element: &ElementRef,
+        phrases: &[String],
+        template: Option<&LearnedTemplate>,
+    ) -> bool {
+        let is_block = matches!(element.value().name(), "p" | "li" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6");
+        if !is_block {
+            return false;
+        }
+        let text: String = element.text().collect();
+        let text = text.trim();
+        self.is_boilerplate_text(text, phrases) || template.is_some_and(|template| template.contains(text))
     }
 
     /// Checks if text looks like boilerplate content
-    fn is_boilerplate_text(&self, text: &str) -> bool {
+    fn is_boilerplate_text(&self, text: &str, phrases: &[String]) -> bool {
         let lower = text.to_lowercase();
 
-        // * Common boilerplate patterns
-        let boilerplate_patterns = [
-            "cookie",
-            "privacy policy",
-            "terms of service",
-            "terms and conditions",
-            "subscribe to",
-            "sign up for",
-            "follow us on",
-            "share this",
-            "related posts",
-            "you may also like",
-            "advertisement",
-            "sponsored",
-            "click here",
-            "read more",
-            "learn more",
-            "©",
-            "all rights reserved",
-            "powered by",
-        ];
-
-        for pattern in &boilerplate_patterns {
-            if lower.contains(pattern) {
-                return true;
-            }
+        if phrases.iter().any(|phrase| lower.contains(phrase.as_str())) {
+            return true;
         }
 
         // * Check for very short navigation-like text
@@ -363,8 +678,52 @@ impl ContentCleaner {
         false
     }
 
-    /// Calculates extraction quality score
-    fn calculate_quality(&self, result: &CleanedContent) -> f32 {
+    /// Scores extraction quality from structure and text statistics
+    ///
+    /// Without extracted words every component scores 0.
+    fn calculate_quality(&self, result: &CleanedContent, blocks: &BlockStats, html_len: usize) -> QualityBreakdown {
+        let has_text = if result.word_count > 0 { 1.0 } else { 0.0 };
+
+        let structure = self.structure_score(result);
+        let link_density = ratio(blocks.link_text_chars, blocks.area_text_chars);
+        let language = result.language;
+        let stopwords = stopword_ratio(&result.text, language);
+        let sentence_words = avg_sentence_length(&result.text);
+        let boilerplate = ratio(blocks.boilerplate_bytes, blocks.kept_bytes + blocks.boilerplate_bytes);
+        let text_to_markup = ratio(result.text.len(), html_len);
+
+        QualityBreakdown {
+            structure: QualityComponent::new(structure, structure, WEIGHT_STRUCTURE),
+            link_density: QualityComponent::new(
+                link_density,
+                has_text * (1.0 - link_density / MAX_LINK_DENSITY),
+                WEIGHT_LINK_DENSITY,
+            ),
+            stopword_ratio: match stopwords {
+                Some(stopwords) => QualityComponent::new(
+                    stopwords,
+                    stopwords / language.target_stopword_ratio(),
+                    WEIGHT_STOPWORDS,
+                ),
+                // * Languages without a stopword list are not penalized
+                None => QualityComponent::new(0.0, has_text, WEIGHT_STOPWORDS),
+            },
+            avg_sentence_length: QualityComponent::new(
+                sentence_words,
+                sentence_length_score(sentence_words),
+                WEIGHT_SENTENCE_LENGTH,
+            ),
+            boilerplate_ratio: QualityComponent::new(boilerplate, has_text * (1.0 - boilerplate), WEIGHT_BOILERPLATE),
+            text_to_markup_ratio: QualityComponent::new(
+                text_to_markup,
+                text_to_markup / TARGET_TEXT_TO_MARKUP,
+                WEIGHT_TEXT_TO_MARKUP,
+            ),
+        }
+    }
+
+    /// Scores the extraction checklist (the v1 quality score)
+    fn structure_score(&self, result: &CleanedContent) -> f32 {
         let mut score = 0.0_f32;
 
         // * +0.3 for finding main content area
@@ -403,6 +762,130 @@ impl ContentCleaner {
     }
 }
 
+impl BlockStats {
+    /// Counts a candidate block; returns true if it is kept
+    fn keep(&mut self, text: &str, boilerplate: bool) -> bool {
+        if boilerplate {
+            self.boilerplate_bytes += text.len();
+        } else {
+            self.kept_bytes += text.len();
+        }
+        !boilerplate
+    }
+}
+
+/// Collects footnote references (resolved to their note) and cited sources
+fn extract_citations(area: ElementRef) -> Vec<Citation> {
+    let targets: HashMap<&str, ElementRef> = area
+        .select(&SELECTOR_WITH_ID)
+        .filter_map(|element| Some((element.value().attr("id")?, element)))
+        .collect();
+    let mut citations = Vec::new();
+    let mut seen_notes = HashSet::new();
+
+    for marker in area.select(&SELECTOR_NOTE_REFS) {
+        let Some(id) = marker.value().attr("href").and_then(|href| href.strip_prefix('#')) else {
+            continue;
+        };
+        // * A note cited several times is reported once
+        if !seen_notes.insert(id) {
+            continue;
+        }
+        let note = targets.get(id);
+        citations.push(Citation {
+            label: collapse_whitespace(&marker.text().collect::<String>()),
+            url: note.and_then(|note| first_external_link(*note)),
+            note: note
+                .map(|note| {
+                    let text = collapse_whitespace(&note.text().collect::<String>());
+                    // * Drop back-reference arrows ("↩", "^") that footnote lists append or prepend
+                    text.trim_matches(|c: char| matches!(c, '↩' | '↑' | '^') || c.is_whitespace())
+                        .to_string()
+                })
+                .filter(|note| !note.is_empty()),
+        });
+    }
+
+    for cite in area.select(&SELECTOR_CITES) {
+        let url = match cite.value().name() {
+            "cite" => first_external_link(cite),
+            _ => cite.value().attr("cite").map(str::to_string),
+        };
+        let label = match cite.value().name() {
+            "cite" => collapse_whitespace(&cite.text().collect::<String>()),
+            // * A quote's own text is in `quotes`; the citation is the source it names
+            _ => String::new(),
+        };
+        if !label.is_empty() || url.is_some() {
+            citations.push(Citation { label, url, note: None });
+        }
+    }
+
+    citations
+}
+
+/// Returns the first link in `element` that leaves the page
+fn first_external_link(element: ElementRef) -> Option<String> {
+    element
+        .select(&SELECTOR_LINKS)
+        .filter_map(|link| link.value().attr("href"))
+        .find(|href| !href.starts_with('#'))
+        .map(str::to_string)
+}
+
+/// Joins text split across lines and nested tags with single spaces
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Returns `part / whole`, or 0 for an empty whole
+fn ratio(part: usize, whole: usize) -> f32 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f32 / whole as f32
+    }
+}
+
+/// Counts non-whitespace characters (indentation between tags is not content)
+fn visible_len(text: &str) -> usize {
+    text.chars().filter(|c| !c.is_whitespace()).count()
+}
+
+/// Share of words that are stopwords, or None when the language has no list
+fn stopword_ratio(text: &str, language: Language) -> Option<f32> {
+    let stopwords = language.stopwords()?;
+    let (total, matched) = words(text).fold((0, 0), |(total, matched), word| {
+        (total + 1, matched + usize::from(stopwords.contains(word.as_str())))
+    });
+    Some(ratio(matched, total))
+}
+
+/// Mean words per sentence; blocks without terminal punctuation count as one sentence
+fn avg_sentence_length(text: &str) -> f32 {
+    let (sentences, words) = text
+        .split("\n\n")
+        .flat_map(|block| block.split(['.', '!', '?', '。', '！', '？']))
+        .map(count_words)
+        .filter(|&words| words > 0)
+        .fold((0, 0), |(sentences, total), words| (sentences + 1, total + words));
+    ratio(words, sentences)
+}
+
+/// Maps words per sentence to 0.0 - 1.0, peaking between the ideal bounds
+fn sentence_length_score(words: f32) -> f32 {
+    let (ideal_min, ideal_max) = IDEAL_SENTENCE_WORDS;
+    if !(MIN_SENTENCE_WORDS..=MAX_SENTENCE_WORDS).contains(&words) {
+        0.0
+    } else if words < ideal_min {
+        (words - MIN_SENTENCE_WORDS) / (ideal_min - MIN_SENTENCE_WORDS)
+    } else if words > ideal_max {
+        (MAX_SENTENCE_WORDS - words) / (MAX_SENTENCE_WORDS - ideal_max)
+    } else {
+        1.0
+    }
+}
+
 impl Default for ContentCleaner {
     fn default() -> Self {
         Self::new()
@@ -574,6 +1057,79 @@ mod tests {
         assert!(poor_result.quality_score < 0.5);
     }
 
+    #[test]
+    fn test_quality_breakdown_sums_to_score() {
+        let html = r#"
+            <html><body><article>
+                <h1>Tide Tables</h1>
+                <p>Spring tides happen when the sun and the moon line up, and the range between high and low water grows.</p>
+                <p>Neap tides follow a week later, when the pull of the sun works against the moon and the range shrinks.</p>
+            </article></body></html>
+        "#;
+
+        let result = extract_content(html);
+        let breakdown = &result.quality;
+        assert!((breakdown.total() - result.quality_score).abs() < 1e-6);
+        let weights: f32 = breakdown.components().iter().map(|(_, c)| c.weight).sum();
+        assert!((weights - 1.0).abs() < 1e-6);
+        assert!(breakdown.stopword_ratio.value > 0.3);
+        assert!(breakdown.link_density.value < f32::EPSILON);
+        assert!((breakdown.avg_sentence_length.score - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_link_heavy_content_scores_lower() {
+        let prose = r#"<html><body><article>
+            <p>Braised shallots caramelize slowly in butter, and they are done when the edges turn deep brown.</p>
+            <p>Add a splash of vinegar at the end so the glaze stays bright and does not turn heavy.</p>
+        </article></body></html>"#;
+        let links = r#"<html><body><article>
+            <p><a href="/a">Braised shallots caramelize slowly in butter, and they are done when the edges turn deep brown.</a></p>
+            <p><a href="/b">Add a splash of vinegar at the end so the glaze stays bright and does not turn heavy.</a></p>
+        </article></body></html>"#;
+
+        let prose = extract_content(prose);
+        let links = extract_content(links);
+        assert!(links.quality.link_density.value > 0.9);
+        assert!(links.quality.link_density.score < f32::EPSILON);
+        assert!(links.quality_score < prose.quality_score);
+    }
+
+    #[test]
+    fn test_weakest_component() {
+        let breakdown = QualityBreakdown {
+            structure: QualityComponent::new(1.0, 0.9, 0.5),
+            link_density: QualityComponent::new(0.4, 0.2, 0.3),
+            stopword_ratio: QualityComponent::new(0.3, 1.0, 0.2),
+            ..Default::default()
+        };
+        assert_eq!(breakdown.weakest().0, "link_density");
+        assert!((breakdown.total() - (0.45 + 0.06 + 0.2)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_boilerplate_and_stopword_components() {
+        let html = r#"<html><body><article>
+            <p>Quarterly revenue, gross margin, operating income, headcount, churn, retention, pipeline.</p>
+            <p>Subscribe to our newsletter for weekly updates delivered straight to your inbox.</p>
+        </article></body></html>"#;
+
+        let result = extract_content(html);
+        assert!(result.quality.boilerplate_ratio.value > 0.4);
+        assert!(result.quality.stopword_ratio.value < 0.1);
+        assert!(result.quality.stopword_ratio.score < 0.5);
+    }
+
+    #[test]
+    fn test_sentence_length_score() {
+        assert_eq!(sentence_length_score(2.0), 0.0);
+        assert!((sentence_length_score(6.5) - 0.5).abs() < f32::EPSILON);
+        assert_eq!(sentence_length_score(20.0), 1.0);
+        assert!((sentence_length_score(55.0) - 0.5).abs() < f32::EPSILON);
+        assert_eq!(sentence_length_score(100.0), 0.0);
+        assert!((avg_sentence_length("One two three. Four five six seven!\n\nHeading") - 8.0 / 3.0).abs() < 1e-6);
+    }
+
     #[test]
     fn test_fallback_without_article() {
         let html = r#"
@@ -663,4 +1219,202 @@ mod tests {
         // * List items should be captured with bullet markers
         assert!(result.text.contains("•"));
     }
+
+    #[test]
+    fn test_cookie_mentions_are_not_boilerplate() {
+        let html = r#"<html><body><article>
+            <p>Chill the cookie dough for an hour so the butter firms up before baking.</p>
+            <p>We use cookies to improve your experience on this site.</p>
+            <p>Bake each cookie until the edges are golden and the centers still look soft.</p>
+        </article></body></html>"#;
+
+        let result = extract_content(html);
+        assert_eq!(result.paragraphs.len(), 2);
+        assert!(!result.text.contains("We use cookies"));
+    }
+
+    #[test]
+    fn test_domain_overrides() {
+        let mut config = CleanerConfig {
+            boilerplate_phrases: Some(vec!["Read More".to_string(), "sponsored".to_string()]),
+            ..Default::default()
+        };
+        config.domains.insert(
+            "blog.example.com".to_string(),
+            BoilerplateOverride {
+                extra_phrases: vec!["jump to recipe".to_string()],
+                allowed_phrases: vec!["read more".to_string()],
+                ..Default::default()
+            },
+        );
+        let cleaner = ContentCleaner::with_config(config);
+
+        let html = r#"<html><body><article>
+            <p>Read more about how the starter is fed twice a day during the first week.</p>
+            <p>Jump to recipe and skip the story about our trip to the bakery.</p>
+        </article></body></html>"#;
+
+        let global = cleaner.clean(html);
+        assert_eq!(global.paragraphs.len(), 1);
+        assert!(global.paragraphs[0].starts_with("Jump to recipe"));
+
+        let site = cleaner.clean_for_domain(html, "blog.example.com");
+        assert_eq!(site.paragraphs.len(), 1);
+        assert!(site.paragraphs[0].starts_with("Read more"));
+
+        // * Hosts without overrides use the global list
+        let other = cleaner.clean_for_domain(html, "other.example.com");
+        assert_eq!(other.paragraphs, global.paragraphs);
+    }
+
+    #[test]
+    fn test_learned_template_blocks_are_dropped() {
+        use crate::refinery::template::{TemplateConfig, TemplateLearner};
+
+        let cleaner = ContentCleaner::with_config(CleanerConfig {
+            include_html: true,
+            ..Default::default()
+        });
+        let bio = "Jane Doe writes about city planning from her home in Leeds.";
+        let html = format!(
+            "<html><body><article><p>{}</p><p>{bio}</p></article></body></html>",
+            "The new tram line opens next spring after four years of building work. ".repeat(3)
+        );
+        let learner = TemplateLearner::new(TemplateConfig {
+            enabled: true,
+            min_pages: 2,
+            ..Default::default()
+        });
+        learner.observe("city.example", &[block_hash(bio), 1]);
+        learner.observe("city.example", &[block_hash(bio), 2]);
+
+        let template = learner.template("city.example");
+        let (content, hashes) = cleaner.clean_with_template(&html, Some("city.example"), &template);
+        assert_eq!(content.paragraphs.len(), 1);
+        assert!(!content.text.contains("Jane Doe"));
+        assert!(!content.html.unwrap().contains("Jane Doe"));
+        // * Dropped blocks are still reported, so the learner keeps counting them
+        assert!(hashes.contains(&block_hash(bio)));
+        assert_eq!(hashes.len(), 2);
+        assert!(cleaner.clean(&html).text.contains("Jane Doe"));
+    }
+
+    #[test]
+    fn test_locale_phrase_sets() {
+        let german = default_boilerplate_phrases("de-AT");
+        assert!(german.iter().any(|p| p == "alle rechte vorbehalten"));
+        assert!(german.iter().any(|p| p == "©"));
+        assert_eq!(default_boilerplate_phrases("xx"), default_boilerplate_phrases("en"));
+
+        let cleaner = ContentCleaner::with_config(CleanerConfig {
+            locale: "de".to_string(),
+            ..Default::default()
+        });
+        let html = r#"<html><body><article>
+            <p>Der Hafen wurde im Jahr 1850 erweitert und ist seitdem ein wichtiger Umschlagplatz.</p>
+            <p>Alle Rechte vorbehalten durch den Verlag der Zeitung und seine Partner.</p>
+        </article></body></html>"#;
+        let result = cleaner.clean(html);
+        assert_eq!(result.paragraphs.len(), 1);
+        assert!(result.paragraphs[0].starts_with("Der Hafen"));
+    }
+
+    #[test]
+    fn test_chinese_page_uses_chinese_rules() {
+        let html = r#"<html lang="zh-CN"><body><article>
+            <h1>长江的航运</h1>
+            <p>长江是中国最长的河流，全长约六千三百公里，流经十一个省份，最后注入东海。</p>
+            <p>自古以来，长江就是连接东西部的重要航道，沿岸城市因此发展成为繁荣的商业中心。</p>
+            <p>版权所有，未经许可不得转载本网站的任何内容。</p>
+        </article></body></html>"#;
+
+        let result = extract_content(html);
+        assert_eq!(result.language, Language::Zh);
+        assert!(result.found_main_content);
+        assert_eq!(result.paragraphs.len(), 2);
+        assert!(result.word_count > 50);
+        assert!(result.quality.stopword_ratio.value > 0.0);
+        assert!(result.quality.avg_sentence_length.score > 0.0);
+    }
+
+    #[test]
+    fn test_language_detected_from_script() {
+        let html = r#"<html><body><article>
+            <p>Байкал является самым глубоким озером на планете и крупнейшим природным резервуаром пресной воды.</p>
+            <p>Все права защищены и охраняются законом, перепечатка запрещена без согласия редакции.</p>
+            <p>Озеро окружено горными хребтами, а в его воды впадает более трёхсот рек и ручьёв.</p>
+        </article></body></html>"#;
+
+        let result = extract_content(html);
+        assert_eq!(result.language, Language::Ru);
+        assert_eq!(result.paragraphs.len(), 2);
+
+        // * Without detection the English fallback keeps the Russian copyright line
+        let cleaner = ContentCleaner::with_config(CleanerConfig {
+            detect_language: false,
+            ..Default::default()
+        });
+        let result = cleaner.clean(html);
+        assert_eq!(result.language, Language::En);
+        assert_eq!(result.paragraphs.len(), 3);
+    }
+
+    #[test]
+    fn test_sanitized_html_output() {
+        let html = r#"<html><body><article>
+            <h2 onclick="track()">Installing the Tool</h2>
+            <p>Download the archive from <a href="https://example.com/dl" class="btn">the releases page</a> and unpack it.</p>
+            <p>Subscribe to our newsletter for weekly updates delivered straight to your inbox.</p>
+            <ul><li>Requires a 64-bit operating system and two gigabytes of memory.</li></ul>
+            <script>track()</script>
+        </article></body></html>"#;
+
+        assert!(extract_content(html).html.is_none());
+
+        let cleaner = ContentCleaner::with_config(CleanerConfig {
+            include_html: true,
+            ..Default::default()
+        });
+        let output = cleaner.clean(html).html.unwrap();
+        assert!(output.contains("<h2>Installing the Tool</h2>"));
+        assert!(output.contains(r#"<a href="https://example.com/dl">the releases page</a>"#));
+        assert!(output.contains("<ul><li>Requires a 64-bit"));
+        assert!(!output.contains("Subscribe"));
+        assert!(!output.contains("track()"));
+        assert!(!output.contains("<article"));
+    }
+
+    #[test]
+    fn test_captions_and_citations() {
+        let html = r##"<html><body><article>
+            <p>Coral cover on the reef fell by a third between 2016 and 2020<sup><a href="#fn1">1</a></sup>.</p>
+            <figure><img src="reef.jpg"><figcaption>Bleached coral near
+                Lizard Island, March 2017.</figcaption></figure>
+            <p>Recovery depends on water temperature<sup><a href="#fn2">2</a></sup> and storms<sup><a href="#fn1">1</a></sup>.</p>
+            <p>As argued in <cite><a href="https://example.org/reefs">The Future of Reefs</a></cite>, recovery is slow.</p>
+            <blockquote cite="https://example.org/interview">Reefs can recover if given a decade without bleaching.</blockquote>
+            <ol class="footnotes">
+                <li id="fn1">Hughes et al., <a href="https://doi.org/10.1038/x">Nature</a> (2018). <a href="#ref1">↩</a></li>
+                <li id="fn2">Marine heatwave report, 2020. <a href="#ref2">↩</a></li>
+            </ol>
+        </article></body></html>"##;
+
+        let result = extract_content(html);
+        assert_eq!(result.captions, vec!["Bleached coral near Lizard Island, March 2017.".to_string()]);
+
+        assert_eq!(result.citations.len(), 4);
+        assert_eq!(
+            result.citations[0],
+            Citation {
+                label: "1".to_string(),
+                url: Some("https://doi.org/10.1038/x".to_string()),
+                note: Some("Hughes et al., Nature (2018).".to_string()),
+            }
+        );
+        assert_eq!(result.citations[1].note.as_deref(), Some("Marine heatwave report, 2020."));
+        assert_eq!(result.citations[1].url, None);
+        assert_eq!(result.citations[2].label, "The Future of Reefs");
+        assert_eq!(result.citations[2].url.as_deref(), Some("https://example.org/reefs"));
+        assert_eq!(result.citations[3].url.as_deref(), Some("https://example.org/interview"));
+    }
 }
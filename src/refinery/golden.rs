@@ -0,0 +1,407 @@
+// * Golden Extraction Tests
+// * Runs the Refinery over a directory of saved pages and diffs each result against a stored
+// * golden JSON file, so a heuristic change shows up as field-level diffs across real pages
+// * and accepted changes are written back with `update`.
+
+use super::{Refinery, RefineryResult};
+use serde_json::Value;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+// * Suffix of the golden file stored next to each fixture
+const GOLDEN_SUFFIX: &str = ".golden.json";
+
+// * Float fields (quality scores) are compared with this tolerance
+const FLOAT_TOLERANCE: f64 = 1e-6;
+
+#[derive(Debug, Error)]
+pub enum GoldenError {
+    #[error("Failed to access {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("Invalid golden file {path}: {source}")]
+    Json {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// A saved page and its golden file
+///
+/// Fixtures may sit in per-site subdirectories named after the domain (e.g.
+/// `fixtures/example.com/article.html`); those are refined with the domain's settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    /// Path relative to the fixtures directory, without the extension
+    pub name: String,
+    pub html_path: PathBuf,
+    pub golden_path: PathBuf,
+    pub domain: Option<String>,
+}
+
+/// One field whose value differs from the golden file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDiff {
+    /// Dotted path into the result, with array indices (e.g. `chunks[2].text`)
+    pub path: String,
+    /// Golden value (None when the field is new)
+    pub expected: Option<Value>,
+    /// New value (None when the field disappeared)
+    pub actual: Option<Value>,
+}
+
+impl fmt::Display for FieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |value: &Option<Value>| value.as_ref().map_or("<missing>".to_string(), Value::to_string);
+        write!(f, "{}: {} -> {}", self.path, show(&self.expected), show(&self.actual))
+    }
+}
+
+/// Result of checking one fixture
+#[derive(Debug, Clone, PartialEq)]
+pub enum FixtureStatus {
+    Passed,
+    Changed(Vec<FieldDiff>),
+    /// No golden file yet; `update` writes one
+    MissingGolden,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixtureResult {
+    pub name: String,
+    pub status: FixtureStatus,
+}
+
+/// Outcome of a golden run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GoldenReport {
+    pub fixtures: Vec<FixtureResult>,
+}
+
+impl GoldenReport {
+    pub fn passed(&self) -> usize {
+        self.fixtures
+            .iter()
+            .filter(|f| f.status == FixtureStatus::Passed)
+            .count()
+    }
+
+    /// Returns the fixtures that changed or have no golden file
+    pub fn failures(&self) -> impl Iterator<Item = &FixtureResult> {
+        self.fixtures.iter().filter(|f| f.status != FixtureStatus::Passed)
+    }
+
+    /// Returns true when every fixture matches its golden file
+    pub fn is_clean(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// Renders the failures as a review-friendly listing, one diff per line
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for fixture in self.failures() {
+            match &fixture.status {
+                FixtureStatus::Changed(diffs) => {
+                    out.push_str(&format!("{} ({} fields changed)\n", fixture.name, diffs.len()));
+                    for diff in diffs {
+                        out.push_str(&format!("  {}\n", diff));
+                    }
+                }
+                FixtureStatus::MissingGolden => out.push_str(&format!("{} (no golden file)\n", fixture.name)),
+                FixtureStatus::Passed => {}
+            }
+        }
+        out.push_str(&format!("{}/{} fixtures match their goldens\n", self.passed(), self.fixtures.len()));
+        out
+    }
+}
+
+/// Runs the Refinery over a fixtures directory and compares against golden JSON
+///
+/// # Example
+/// ```ignore
+/// let report = GoldenHarness::new("tests/fixtures/golden").run()?;
+/// assert!(report.is_clean(), "{}", report.render());
+/// ```
+pub struct GoldenHarness {
+    dir: PathBuf,
+    refinery: Refinery,
+    ignored: Vec<String>,
+}
+
+impl GoldenHarness {
+    /// Creates a harness over `dir` using the default Refinery
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            refinery: Refinery::new(),
+            ignored: Vec::new(),
+        }
+    }
+
+    /// Uses a configured Refinery (e.g. the settings under review)
+    pub fn with_refinery(mut self, refinery: Refinery) -> Self {
+        self.refinery = refinery;
+        self
+    }
+
+    /// Skips a field and everything below it when diffing (e.g. `stats.encoding`)
+    pub fn with_ignored(mut self, path: impl Into<String>) -> Self {
+        self.ignored.push(path.into());
+        self
+    }
+
+    /// Returns the `.html` fixtures under the directory, in name order
+    pub fn fixtures(&self) -> Result<Vec<Fixture>, GoldenError> {
+        let mut fixtures = Vec::new();
+        collect_fixtures(&self.dir, &self.dir, &mut fixtures)?;
+        fixtures.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(fixtures)
+    }
+
+    /// Refines every fixture and diffs it against its golden file
+    pub fn run(&self) -> Result<GoldenReport, GoldenError> {
+        let mut report = GoldenReport::default();
+        for fixture in self.fixtures()? {
+            let actual = self.refine(&fixture)?;
+            let status = match read_golden(&fixture.golden_path)? {
+                None => FixtureStatus::MissingGolden,
+                Some(expected) => {
+                    let diffs = self.diff(&expected, &actual);
+                    if diffs.is_empty() {
+                        FixtureStatus::Passed
+                    } else {
+                        FixtureStatus::Changed(diffs)
+                    }
+                }
+            };
+            report.fixtures.push(FixtureResult {
+                name: fixture.name,
+                status,
+            });
+        }
+        Ok(report)
+    }
+
+    /// Rewrites the golden files that changed or are missing; returns how many were written
+    pub fn update(&self) -> Result<usize, GoldenError> {
+        let mut written = 0;
+        for fixture in self.fixtures()? {
+            let actual = self.refine(&fixture)?;
+            let current = read_golden(&fixture.golden_path)?;
+            if current.is_some_and(|expected| self.diff(&expected, &actual).is_empty()) {
+                continue;
+            }
+            let json = serde_json::to_string_pretty(&actual).map_err(|source| GoldenError::Json {
+                path: fixture.golden_path.clone(),
+                source,
+            })?;
+            fs::write(&fixture.golden_path, json + "\n").map_err(|source| GoldenError::Io {
+                path: fixture.golden_path.clone(),
+                source,
+            })?;
+            written += 1;
+        }
+        Ok(written)
+    }
+
+    /// Returns the field-level differences between a golden value and a new result
+    pub fn diff(&self, expected: &Value, actual: &Value) -> Vec<FieldDiff> {
+        let mut diffs = Vec::new();
+        diff_values("", Some(expected), Some(actual), &self.ignored, &mut diffs);
+        diffs
+    }
+
+    fn refine(&self, fixture: &Fixture) -> Result<Value, GoldenError> {
+        let bytes = fs::read(&fixture.html_path).map_err(|source| GoldenError::Io {
+            path: fixture.html_path.clone(),
+            source,
+        })?;
+        let html = String::from_utf8_lossy(&bytes);
+        let result: RefineryResult = match &fixture.domain {
+            Some(domain) => self.refinery.process_for_domain(&html, domain),
+            None => self.refinery.process(&html),
+        };
+        serde_json::to_value(&result).map_err(|source| GoldenError::Json {
+            path: fixture.golden_path.clone(),
+            source,
+        })
+    }
+}
+
+fn collect_fixtures(root: &Path, dir: &Path, fixtures: &mut Vec<Fixture>) -> Result<(), GoldenError> {
+    let io_error = |source| GoldenError::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if path.is_dir() {
+            collect_fixtures(root, &path, fixtures)?;
+            continue;
+        }
+        if path.extension().and_then(|e| e.to_str()) != Some("html") {
+            continue;
+        }
+        let relative = path.strip_prefix(root).unwrap_or(&path).with_extension("");
+        let name = relative.to_string_lossy().replace('\\', "/");
+        let file_name = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        let domain = path
+            .parent()
+            .filter(|parent| *parent != root)
+            .and_then(|parent| parent.file_name())
+            .map(|site| site.to_string_lossy().into_owned())
+            .filter(|site| site.contains('.'));
+        fixtures.push(Fixture {
+            name,
+            golden_path: path.with_file_name(format!("{}{}", file_name, GOLDEN_SUFFIX)),
+            html_path: path,
+            domain,
+        });
+    }
+    Ok(())
+}
+
+fn read_golden(path: &Path) -> Result<Option<Value>, GoldenError> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(source) => {
+            return Err(GoldenError::Io {
+                path: path.to_path_buf(),
+                source,
+            })
+        }
+    };
+    serde_json::from_str(&text).map(Some).map_err(|source| GoldenError::Json {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn diff_values(
+    path: &str,
+    expected: Option<&Value>,
+    actual: Option<&Value>,
+    ignored: &[String],
+    diffs: &mut Vec<FieldDiff>,
+) {
+    if ignored.iter().any(|prefix| {
+        path == prefix
+            || path
+                .strip_prefix(prefix.as_str())
+                .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('['))
+    }) {
+        return;
+    }
+    match (expected, actual) {
+        (Some(Value::Object(expected)), Some(Value::Object(actual))) => {
+            let mut keys: Vec<&String> = expected.keys().chain(actual.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let child = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                diff_values(&child, expected.get(key), actual.get(key), ignored, diffs);
+            }
+        }
+        (Some(Value::Array(expected)), Some(Value::Array(actual))) => {
+            for i in 0..expected.len().max(actual.len()) {
+                diff_values(&format!("{}[{}]", path, i), expected.get(i), actual.get(i), ignored, diffs);
+            }
+        }
+        (Some(Value::Number(a)), Some(Value::Number(b)))
+            if a.as_f64().zip(b.as_f64()).is_some_and(|(a, b)| (a - b).abs() <= FLOAT_TOLERANCE) => {}
+        (expected, actual) if expected == actual => {}
+        (expected, actual) => diffs.push(FieldDiff {
+            path: path.to_string(),
+            expected: expected.cloned(),
+            actual: actual.cloned(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::refinery::RefineryConfig;
+    use serde_json::json;
+
+    fn fixtures_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("titan-golden-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("example.com")).unwrap();
+        fs::write(
+            dir.join("plain.html"),
+            "<html><head><title>Plain</title></head><body><article><p>Tomatoes need full sun, deep watering and rich soil to thrive.</p></article></body></html>",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("example.com").join("contact.html"),
+            "<html><head><title>Contact</title></head><body><article><p>Write to jane.doe@example.com about the community garden plots.</p></article></body></html>",
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_diff_reports_field_paths() {
+        let harness = GoldenHarness::new("unused").with_ignored("stats.encoding");
+        let expected = json!({"metadata": {"title": "Old"}, "chunks": [{"text": "a"}], "stats": {"quality_score": 0.5, "encoding": "utf-8"}});
+        let actual = json!({"metadata": {"title": "New"}, "chunks": [{"text": "a"}, {"text": "b"}], "stats": {"quality_score": 0.5000000001, "encoding": null}});
+
+        let diffs = harness.diff(&expected, &actual);
+        let paths: Vec<&str> = diffs.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(paths, vec!["chunks[1]", "metadata.title"]);
+        assert_eq!(diffs[0].expected, None);
+        assert_eq!(diffs[1].to_string(), "metadata.title: \"Old\" -> \"New\"");
+    }
+
+    #[test]
+    fn test_update_then_run_is_clean() {
+        let dir = fixtures_dir("clean");
+        let harness = GoldenHarness::new(&dir);
+
+        let fixtures = harness.fixtures().unwrap();
+        assert_eq!(fixtures.len(), 2);
+        assert_eq!(fixtures[0].name, "example.com/contact");
+        assert_eq!(fixtures[0].domain.as_deref(), Some("example.com"));
+        assert_eq!(fixtures[1].domain, None);
+
+        let report = harness.run().unwrap();
+        assert!(report.fixtures.iter().all(|f| f.status == FixtureStatus::MissingGolden));
+        assert_eq!(harness.update().unwrap(), 2);
+        assert_eq!(harness.update().unwrap(), 0);
+        assert!(harness.run().unwrap().is_clean());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_changed_settings_show_up_as_diffs() {
+        let dir = fixtures_dir("changed");
+        GoldenHarness::new(&dir).update().unwrap();
+
+        let redacting = Refinery::with_config(RefineryConfig {
+            redaction: Some(Default::default()),
+            ..Default::default()
+        });
+        let report = GoldenHarness::new(&dir).with_refinery(redacting).run().unwrap();
+
+        assert_eq!(report.failures().count(), 2);
+        let FixtureStatus::Changed(diffs) = &report.fixtures[0].status else {
+            panic!("expected a diff, got {:?}", report.fixtures[0].status);
+        };
+        let redacted = diffs.iter().find(|d| d.path == "redacted_text").unwrap();
+        assert_eq!(redacted.expected, Some(Value::Null));
+        assert!(!redacted.actual.as_ref().unwrap().to_string().contains("jane.doe@example.com"));
+        assert!(report.render().contains("0/2 fixtures match"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
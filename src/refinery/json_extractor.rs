@@ -0,0 +1,349 @@
+// * JSON Body Extraction
+// * Maps fields of JSON API responses (CMS and headless-commerce endpoints) into the same
+// * content and metadata an HTML page yields. Rules select values with JSONPath (`$...`)
+// * or JSON Pointer (`/...`); the body becomes a synthetic article the cleaner refines.
+
+use super::metadata::PageMetadata;
+use super::sanitize::escape_into;
+use scraper::Html;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_json_path::JsonPath;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Error)]
+#[error("Invalid {field} expression '{expression}' for {domain}: {message}")]
+pub struct JsonRuleError {
+    pub domain: String,
+    pub field: &'static str,
+    pub expression: String,
+    pub message: String,
+}
+
+/// Field expressions for one API host
+///
+/// `domain` matches the host and its subdomains, most specific first. Expressions starting
+/// with `$` are JSONPath (RFC 9535) and may select many values; those starting with `/` are
+/// JSON Pointers (RFC 6901). String values may hold HTML, which is reduced to its text.
+///
+/// # Example
+/// ```toml
+/// [[refinery.json_rules]]
+/// domain = "cms.example.com"
+/// title = "$.title.rendered"
+/// body = "$.content.rendered"
+/// author = "$._embedded.author[*].name"
+/// date = "/date_gmt"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JsonRule {
+    pub domain: String,
+    pub title: Option<String>,
+    /// Every selected value becomes part of the main content, in order
+    pub body: Option<String>,
+    /// Every selected value is an author
+    pub author: Option<String>,
+    pub date: Option<String>,
+    pub description: Option<String>,
+    /// Selected strings are split on commas
+    pub keywords: Option<String>,
+    /// URL or cursor of the next page of results
+    pub pagination: Option<String>,
+}
+
+#[derive(Debug)]
+enum JsonSelector {
+    Path(JsonPath),
+    Pointer(String),
+}
+
+impl JsonSelector {
+    fn parse(expression: &str) -> Result<Self, String> {
+        let expression = expression.trim();
+        if expression.starts_with('$') {
+            JsonPath::parse(expression).map(Self::Path).map_err(|e| e.to_string())
+        } else if expression.starts_with('/') {
+            Ok(Self::Pointer(expression.to_string()))
+        } else {
+            Err("expected a JSONPath ($...) or a JSON Pointer (/...)".to_string())
+        }
+    }
+
+    fn select<'v>(&self, document: &'v Value) -> Vec<&'v Value> {
+        match self {
+            Self::Path(path) => path.query(document).all(),
+            Self::Pointer(pointer) => document.pointer(pointer).into_iter().collect(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CompiledJsonRule {
+    domain: String,
+    title: Option<JsonSelector>,
+    body: Option<JsonSelector>,
+    author: Option<JsonSelector>,
+    date: Option<JsonSelector>,
+    description: Option<JsonSelector>,
+    keywords: Option<JsonSelector>,
+    pagination: Option<JsonSelector>,
+}
+
+/// What a JSON rule found in a response
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonExtraction {
+    pub title: Option<String>,
+    /// Selected body values, as served (plain text or HTML)
+    pub body: Vec<String>,
+    pub authors: Vec<String>,
+    pub date: Option<String>,
+    pub description: Option<String>,
+    pub keywords: Vec<String>,
+    pub next_page: Option<String>,
+}
+
+/// Compiled JSON rules, looked up by host
+#[derive(Debug, Default)]
+pub struct JsonRuleSet {
+    rules: Vec<CompiledJsonRule>,
+}
+
+impl JsonRuleSet {
+    /// Compiles rules, failing on the first invalid expression
+    pub fn new(rules: &[JsonRule]) -> Result<Self, JsonRuleError> {
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                let compile = |field: &'static str, expression: &Option<String>| {
+                    expression
+                        .as_deref()
+                        .map(|expression| {
+                            JsonSelector::parse(expression).map_err(|message| JsonRuleError {
+                                domain: rule.domain.clone(),
+                                field,
+                                expression: expression.to_string(),
+                                message,
+                            })
+                        })
+                        .transpose()
+                };
+                Ok(CompiledJsonRule {
+                    domain: rule.domain.trim().trim_start_matches("*.").to_ascii_lowercase(),
+                    title: compile("title", &rule.title)?,
+                    body: compile("body", &rule.body)?,
+                    author: compile("author", &rule.author)?,
+                    date: compile("date", &rule.date)?,
+                    description: compile("description", &rule.description)?,
+                    keywords: compile("keywords", &rule.keywords)?,
+                    pagination: compile("pagination", &rule.pagination)?,
+                })
+            })
+            .collect::<Result<_, JsonRuleError>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Applies the host's rule to a response body
+    ///
+    /// Returns None when no rule covers the host or the body is not a JSON object or array,
+    /// so HTML pages from the same host keep the HTML pipeline.
+    pub fn extract(&self, body: &str, host: &str) -> Option<JsonExtraction> {
+        let host = host.to_ascii_lowercase();
+        let rule = self
+            .rules
+            .iter()
+            .filter(|rule| host == rule.domain || host.ends_with(&format!(".{}", rule.domain)))
+            .max_by_key(|rule| rule.domain.len())?;
+        let trimmed = body.trim_start_matches('\u{feff}').trim_start();
+        if !trimmed.starts_with(['{', '[']) {
+            return None;
+        }
+        let document: Value = serde_json::from_str(trimmed).ok()?;
+
+        let all = |selector: &Option<JsonSelector>| -> Vec<String> {
+            let mut values = Vec::new();
+            for value in selector.iter().flat_map(|selector| selector.select(&document)) {
+                flatten_into(value, &mut values);
+            }
+            values
+        };
+        let first = |selector: &Option<JsonSelector>| all(selector).into_iter().find_map(|value| plain_text(&value));
+        Some(JsonExtraction {
+            title: first(&rule.title),
+            body: all(&rule.body).into_iter().filter(|value| !value.trim().is_empty()).collect(),
+            authors: all(&rule.author).iter().filter_map(|value| plain_text(value)).collect(),
+            date: first(&rule.date),
+            description: first(&rule.description),
+            keywords: all(&rule.keywords)
+                .iter()
+                .flat_map(|value| value.split(','))
+                .filter_map(plain_text)
+                .collect(),
+            next_page: first(&rule.pagination),
+        })
+    }
+}
+
+impl JsonExtraction {
+    /// Wraps the body values in an article for the content cleaner; plain text is escaped
+    /// and split into paragraphs on blank lines
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<html><head>");
+        if let Some(title) = &self.title {
+            html.push_str("<title>");
+            escape_into(title, &mut html);
+            html.push_str("</title>");
+        }
+        html.push_str("</head><body><article>");
+        for value in &self.body {
+            if looks_like_html(value) {
+                html.push_str("<div>");
+                html.push_str(value);
+                html.push_str("</div>");
+            } else {
+                for paragraph in value.split("\n\n").filter(|p| !p.trim().is_empty()) {
+                    html.push_str("<p>");
+                    escape_into(paragraph.trim(), &mut html);
+                    html.push_str("</p>");
+                }
+            }
+        }
+        html.push_str("</article></body></html>");
+        html
+    }
+
+    /// Sets every metadata field the rule found; unset fields keep their value
+    pub fn apply(self, metadata: &mut PageMetadata) {
+        if let Some(title) = self.title {
+            metadata.title = Some(title);
+        }
+        if let Some(author) = self.authors.first() {
+            metadata.author = Some(author.clone());
+            metadata.authors = self.authors;
+        }
+        if let Some(date) = self.date {
+            metadata.date_published = Some(date);
+        }
+        if let Some(description) = self.description {
+            metadata.description = Some(description);
+        }
+        if !self.keywords.is_empty() {
+            metadata.keywords = self.keywords;
+        }
+        if let Some(next_page) = self.next_page {
+            metadata.next_page_url = Some(next_page);
+        }
+        metadata.extraction_method = "json_rules".to_string();
+    }
+}
+
+/// Collects the scalars under a value; arrays are flattened and objects skipped
+fn flatten_into(value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::String(text) => out.push(text.clone()),
+        Value::Number(number) => out.push(number.to_string()),
+        Value::Bool(flag) => out.push(flag.to_string()),
+        Value::Array(items) => items.iter().for_each(|item| flatten_into(item, out)),
+        Value::Null | Value::Object(_) => {}
+    }
+}
+
+fn looks_like_html(value: &str) -> bool {
+    value.contains('<') && value.contains('>')
+}
+
+/// Returns a value's whitespace-collapsed text, decoding entities and dropping any markup
+fn plain_text(value: &str) -> Option<String> {
+    let text = if looks_like_html(value) || value.contains('&') {
+        Html::parse_fragment(value).root_element().text().collect::<Vec<_>>().join(" ")
+    } else {
+        value.to_string()
+    };
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POST: &str = r#"{
+        "id": 42,
+        "date_gmt": "2024-03-01T08:00:00",
+        "title": {"rendered": "Pruning Apple Trees &#8211; A Guide"},
+        "content": {"rendered": "<p>Prune in late winter.</p><p>Cut crossing branches first.</p>"},
+        "excerpt": "How and when to prune.",
+        "tags": ["orchard, pruning", "winter"],
+        "_embedded": {"author": [{"name": "Rae Orchard"}, {"name": "Tom Bough"}]},
+        "_links": {"next": [{"href": "https://cms.example.com/wp-json/wp/v2/posts/43"}]}
+    }"#;
+
+    fn rules() -> JsonRuleSet {
+        JsonRuleSet::new(&[JsonRule {
+            domain: "cms.example.com".to_string(),
+            title: Some("$.title.rendered".to_string()),
+            body: Some("$.content.rendered".to_string()),
+            author: Some("$._embedded.author[*].name".to_string()),
+            date: Some("/date_gmt".to_string()),
+            description: Some("/excerpt".to_string()),
+            keywords: Some("$.tags".to_string()),
+            pagination: Some("$._links.next[0].href".to_string()),
+        }])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_extract_fields_with_paths_and_pointers() {
+        let found = rules().extract(POST, "api.cms.example.com").unwrap();
+
+        assert_eq!(found.title.as_deref(), Some("Pruning Apple Trees – A Guide"));
+        assert_eq!(found.authors, vec!["Rae Orchard", "Tom Bough"]);
+        assert_eq!(found.date.as_deref(), Some("2024-03-01T08:00:00"));
+        assert_eq!(found.description.as_deref(), Some("How and when to prune."));
+        assert_eq!(found.keywords, vec!["orchard", "pruning", "winter"]);
+        assert_eq!(found.next_page.as_deref(), Some("https://cms.example.com/wp-json/wp/v2/posts/43"));
+        assert!(found.to_html().contains("<div><p>Prune in late winter.</p>"));
+
+        let mut metadata = PageMetadata::default();
+        found.apply(&mut metadata);
+        assert_eq!(metadata.author.as_deref(), Some("Rae Orchard"));
+        assert_eq!(metadata.extraction_method, "json_rules");
+    }
+
+    #[test]
+    fn test_non_json_bodies_and_other_hosts_are_skipped() {
+        let rules = rules();
+        assert_eq!(rules.extract(POST, "example.org"), None);
+        assert_eq!(rules.extract("<html><body>Not JSON</body></html>", "cms.example.com"), None);
+        assert_eq!(rules.extract("{\"truncated\": ", "cms.example.com"), None);
+
+        let plain = JsonExtraction {
+            body: vec!["Fish & chips.\n\nSecond part.".to_string()],
+            ..Default::default()
+        };
+        assert!(plain.to_html().contains("<p>Fish &amp; chips.</p><p>Second part.</p>"));
+    }
+
+    #[test]
+    fn test_invalid_expressions_are_rejected() {
+        let error = JsonRuleSet::new(&[JsonRule {
+            domain: "cms.example.com".to_string(),
+            title: Some("$.title[".to_string()),
+            ..Default::default()
+        }])
+        .unwrap_err();
+        assert_eq!(error.field, "title");
+
+        let error = JsonRuleSet::new(&[JsonRule {
+            domain: "cms.example.com".to_string(),
+            body: Some("content.rendered".to_string()),
+            ..Default::default()
+        }])
+        .unwrap_err();
+        assert_eq!(error.field, "body");
+    }
+}
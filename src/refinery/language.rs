@@ -0,0 +1,394 @@
+// * Language rules for the content cleaner
+// * Picks boilerplate phrases and stopwords from the declared or detected page language,
+// * and counts words in scripts written without spaces (Chinese, Japanese).
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::LazyLock;
+use unicode_segmentation::UnicodeSegmentation;
+
+// * Share of letters in one script needed to detect a language from text alone
+const SCRIPT_DETECTION_THRESHOLD: f32 = 0.3;
+
+/// Languages with their own cleaner rules
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    En,
+    De,
+    Fr,
+    Es,
+    Ru,
+    Zh,
+    Ja,
+    Ko,
+}
+
+impl Language {
+    pub const ALL: [Language; 8] = [
+        Language::En,
+        Language::De,
+        Language::Fr,
+        Language::Es,
+        Language::Ru,
+        Language::Zh,
+        Language::Ja,
+        Language::Ko,
+    ];
+
+    /// Parses a BCP 47 tag by its primary subtag ("de-AT" -> German)
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_lowercase();
+        Self::ALL.into_iter().find(|language| language.code() == primary)
+    }
+
+    /// Guesses the language from its script
+    ///
+    /// Only scripts that identify a language are recognized (Han, Kana, Hangul,
+    /// Cyrillic); Latin text returns None.
+    pub fn detect(text: &str) -> Option<Self> {
+        let (mut letters, mut han, mut kana, mut hangul, mut cyrillic) = (0, 0, 0, 0, 0);
+        for c in text.chars().filter(|c| c.is_alphabetic()) {
+            letters += 1;
+            match c {
+                '\u{3040}'..='\u{30ff}' => kana += 1,
+                '\u{ac00}'..='\u{d7af}' | '\u{1100}'..='\u{11ff}' => hangul += 1,
+                '\u{0400}'..='\u{04ff}' => cyrillic += 1,
+                c if is_han(c) => han += 1,
+                _ => {}
+            }
+        }
+
+        let share = |count: usize| count as f32 / letters.max(1) as f32;
+        if share(hangul) >= SCRIPT_DETECTION_THRESHOLD {
+            Some(Language::Ko)
+        } else if share(han + kana) >= SCRIPT_DETECTION_THRESHOLD {
+            // * Japanese mixes kanji with kana; Chinese has no kana at all
+            Some(if kana > 0 { Language::Ja } else { Language::Zh })
+        } else if share(cyrillic) >= SCRIPT_DETECTION_THRESHOLD {
+            Some(Language::Ru)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the ISO 639-1 code
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::De => "de",
+            Language::Fr => "fr",
+            Language::Es => "es",
+            Language::Ru => "ru",
+            Language::Zh => "zh",
+            Language::Ja => "ja",
+            Language::Ko => "ko",
+        }
+    }
+
+    /// Returns the built-in boilerplate phrases (lowercase)
+    pub fn boilerplate_phrases(&self) -> &'static [&'static str] {
+        match self {
+            Language::En => &[
+                "privacy policy",
+                "terms of service",
+                "terms and conditions",
+                "subscribe to",
+                "sign up for",
+                "follow us on",
+                "share this",
+                "related posts",
+                "you may also like",
+                "advertisement",
+                "sponsored",
+                "click here",
+                "read more",
+                "learn more",
+                "all rights reserved",
+                "powered by",
+                // * Consent banners, not any mention of cookies (recipes, HTTP docs)
+                "we use cookies",
+                "accept cookies",
+                "cookie policy",
+                "cookie settings",
+            ],
+            Language::De => &[
+                "datenschutzerklärung",
+                "nutzungsbedingungen",
+                "alle rechte vorbehalten",
+                "newsletter abonnieren",
+                "jetzt anmelden",
+                "folgen sie uns",
+                "teilen sie",
+                "ähnliche artikel",
+                "das könnte sie auch interessieren",
+                "anzeige",
+                "hier klicken",
+                "weiterlesen",
+                "mehr erfahren",
+                "wir verwenden cookies",
+                "cookie-einstellungen",
+            ],
+            Language::Fr => &[
+                "politique de confidentialité",
+                "conditions d'utilisation",
+                "conditions générales",
+                "tous droits réservés",
+                "abonnez-vous",
+                "inscrivez-vous",
+                "suivez-nous",
+                "partager cet article",
+                "articles similaires",
+                "vous aimerez aussi",
+                "publicité",
+                "cliquez ici",
+                "lire la suite",
+                "en savoir plus",
+                "nous utilisons des cookies",
+            ],
+            Language::Es => &[
+                "política de privacidad",
+                "términos de servicio",
+                "términos y condiciones",
+                "todos los derechos reservados",
+                "suscríbete",
+                "regístrate",
+                "síguenos en",
+                "comparte esto",
+                "artículos relacionados",
+                "también te puede interesar",
+                "publicidad",
+                "haz clic aquí",
+                "leer más",
+                "más información",
+                "utilizamos cookies",
+            ],
+            Language::Ru => &[
+                "политика конфиденциальности",
+                "пользовательское соглашение",
+                "все права защищены",
+                "подписывайтесь",
+                "подпишитесь на",
+                "поделиться",
+                "похожие статьи",
+                "читайте также",
+                "реклама",
+                "нажмите здесь",
+                "читать далее",
+                "подробнее",
+                "мы используем cookie",
+            ],
+            Language::Zh => &[
+                "隐私政策",
+                "服务条款",
+                "版权所有",
+                "免责声明",
+                "关注我们",
+                "订阅",
+                "分享到",
+                "相关文章",
+                "猜你喜欢",
+                "广告",
+                "点击这里",
+                "阅读更多",
+                "阅读全文",
+            ],
+            Language::Ja => &[
+                "プライバシーポリシー",
+                "利用規約",
+                "無断転載",
+                "all rights reserved",
+                "フォローする",
+                "シェアする",
+                "関連記事",
+                "こちらもおすすめ",
+                "広告",
+                "ここをクリック",
+                "続きを読む",
+                "もっと見る",
+            ],
+            Language::Ko => &[
+                "개인정보처리방침",
+                "개인정보 처리방침",
+                "이용약관",
+                "무단 전재",
+                "구독하기",
+                "공유하기",
+                "관련 기사",
+                "추천 기사",
+                "광고",
+                "여기를 클릭",
+                "더 보기",
+                "더보기",
+            ],
+        }
+    }
+
+    /// Returns the stopword set, if the language has one
+    ///
+    /// Korean attaches particles to words, so it has no separate function words to count.
+    pub fn stopwords(&self) -> Option<&'static HashSet<&'static str>> {
+        match self {
+            Language::En => Some(&STOPWORDS_EN),
+            Language::De => Some(&STOPWORDS_DE),
+            Language::Fr => Some(&STOPWORDS_FR),
+            Language::Es => Some(&STOPWORDS_ES),
+            Language::Ru => Some(&STOPWORDS_RU),
+            Language::Zh => Some(&STOPWORDS_ZH),
+            Language::Ja => Some(&STOPWORDS_JA),
+            Language::Ko => None,
+        }
+    }
+
+    /// Share of stopwords in running prose of this language
+    ///
+    /// Chinese and Japanese are counted per character, which dilutes function words.
+    pub fn target_stopword_ratio(&self) -> f32 {
+        match self {
+            Language::Zh | Language::Ja => 0.15,
+            _ => 0.3,
+        }
+    }
+}
+
+/// Counts words, taking each Han or Kana character as one word
+///
+/// Chinese and Japanese are written without spaces, so splitting on
+/// whitespace would count a whole paragraph as a handful of words.
+pub fn count_words(text: &str) -> usize {
+    text.split_whitespace()
+        .map(|token| {
+            if token.chars().any(is_unspaced) {
+                token.unicode_words().count().max(1)
+            } else {
+                1
+            }
+        })
+        .sum()
+}
+
+/// Splits text into lowercase words the same way `count_words` counts them
+pub fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split_whitespace().flat_map(|token| {
+        let parts: Vec<&str> = if token.chars().any(is_unspaced) {
+            token.unicode_words().collect()
+        } else {
+            vec![token.trim_matches(|c: char| !c.is_alphanumeric())]
+        };
+        parts
+            .into_iter()
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_lowercase())
+    })
+}
+
+fn is_han(c: char) -> bool {
+    matches!(c, '\u{4e00}'..='\u{9fff}' | '\u{3400}'..='\u{4dbf}' | '\u{f900}'..='\u{faff}')
+}
+
+/// Characters of scripts written without spaces between words
+fn is_unspaced(c: char) -> bool {
+    is_han(c) || matches!(c, '\u{3040}'..='\u{30ff}')
+}
+
+fn stopword_set(words: &[&'static str]) -> HashSet<&'static str> {
+    words.iter().copied().collect()
+}
+
+static STOPWORDS_EN: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    stopword_set(&[
+        "a", "about", "after", "all", "also", "an", "and", "any", "are", "as", "at", "be",
+        "because", "been", "before", "being", "between", "both", "but", "by", "can", "could",
+        "did", "do", "does", "each", "for", "from", "had", "has", "have", "he", "her", "here",
+        "him", "his", "how", "i", "if", "in", "into", "is", "it", "its", "just", "more", "most",
+        "my", "no", "not", "now", "of", "on", "once", "only", "or", "other", "our", "out",
+        "over", "she", "should", "so", "some", "such", "than", "that", "the", "their", "them",
+        "then", "there", "these", "they", "this", "those", "through", "to", "too", "under",
+        "until", "up", "very", "was", "we", "were", "what", "when", "where", "which", "while",
+        "who", "why", "will", "with", "would", "you", "your",
+    ])
+});
+
+static STOPWORDS_DE: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    stopword_set(&[
+        "aber", "als", "am", "an", "auch", "auf", "aus", "bei", "bis", "das", "dass", "dem",
+        "den", "der", "des", "die", "doch", "durch", "ein", "eine", "einem", "einen", "einer",
+        "es", "für", "hat", "ich", "ihr", "im", "in", "ist", "mit", "nach", "nicht", "noch",
+        "nur", "oder", "sich", "sie", "sind", "so", "um", "und", "uns", "von", "vor", "war",
+        "wie", "wir", "wird", "wurde", "zu", "zum", "zur",
+    ])
+});
+
+static STOPWORDS_FR: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    stopword_set(&[
+        "à", "au", "aux", "avec", "ce", "ces", "dans", "de", "des", "du", "elle", "en", "est",
+        "et", "il", "ils", "je", "la", "le", "les", "leur", "lui", "mais", "même", "ne", "nous",
+        "on", "ou", "par", "pas", "plus", "pour", "qu'il", "que", "qui", "sa", "se", "ses",
+        "son", "sont", "sur", "un", "une", "vous", "y", "été", "être",
+    ])
+});
+
+static STOPWORDS_ES: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    stopword_set(&[
+        "a", "al", "como", "con", "de", "del", "el", "ella", "en", "es", "esta", "este", "fue",
+        "ha", "la", "las", "le", "lo", "los", "más", "me", "mi", "no", "o", "para", "pero",
+        "por", "que", "se", "si", "sin", "su", "sus", "también", "un", "una", "y", "ya",
+    ])
+});
+
+static STOPWORDS_RU: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    stopword_set(&[
+        "а", "без", "бы", "был", "была", "были", "было", "в", "во", "вот", "все", "да", "для",
+        "до", "если", "же", "за", "и", "из", "или", "к", "как", "ко", "ли", "на", "не", "него",
+        "нет", "но", "о", "об", "он", "она", "они", "от", "по", "при", "с", "со", "так", "то",
+        "только", "у", "уже", "что", "это", "я",
+    ])
+});
+
+static STOPWORDS_ZH: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    stopword_set(&[
+        "的", "了", "是", "在", "和", "有", "我", "他", "她", "它", "们", "这", "那", "也", "就",
+        "都", "而", "及", "与", "着", "或", "一", "个", "上", "不", "为", "之", "以", "于", "把",
+        "被", "从", "对", "到", "说", "要", "会", "很",
+    ])
+});
+
+static STOPWORDS_JA: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    stopword_set(&[
+        "の", "に", "は", "を", "た", "が", "で", "て", "と", "し", "れ", "さ", "な", "も", "い",
+        "か", "る", "す", "だ", "ま", "ら", "こ", "そ", "あ", "や", "へ", "ん", "う", "っ",
+    ])
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_tag() {
+        assert_eq!(Language::from_tag("de-AT"), Some(Language::De));
+        assert_eq!(Language::from_tag("zh_Hans"), Some(Language::Zh));
+        assert_eq!(Language::from_tag(" JA "), Some(Language::Ja));
+        assert_eq!(Language::from_tag("pt-BR"), None);
+        assert_eq!(Language::from_tag(""), None);
+    }
+
+    #[test]
+    fn test_detect_by_script() {
+        assert_eq!(Language::detect("北京是中国的首都，也是一座历史悠久的城市。"), Some(Language::Zh));
+        assert_eq!(Language::detect("東京は日本の首都であり、多くの人が住んでいます。"), Some(Language::Ja));
+        assert_eq!(Language::detect("서울은 대한민국의 수도입니다."), Some(Language::Ko));
+        assert_eq!(Language::detect("Москва является столицей России."), Some(Language::Ru));
+        assert_eq!(Language::detect("Paris is the capital of France."), None);
+        assert_eq!(Language::detect(""), None);
+    }
+
+    #[test]
+    fn test_count_words() {
+        assert_eq!(count_words("one two  three"), 3);
+        // * Each Han character is a word; the Latin token beside it counts once
+        assert_eq!(count_words("北京是首都 Beijing"), 6);
+        assert_eq!(words("The Cat, the hat!").collect::<Vec<_>>(), ["the", "cat", "the", "hat"]);
+    }
+}
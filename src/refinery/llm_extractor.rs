@@ -0,0 +1,476 @@
+// * Schema-Guided LLM Extraction
+// * Sends cleaned page text plus a caller's JSON schema to an LLM and returns the structured
+// * data it fills in. Answers are cached by content hash, and every provider call is charged
+// * to the shared enrichment budget so extraction and enrichment spend one daily allowance.
+
+use crate::ops;
+use crate::persistence::ai_worker::{AsyncResult, EnrichmentError};
+use crate::persistence::{estimate_tokens, EnrichmentBudget};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use thiserror::Error;
+use xxhash_rust::xxh64::xxh64;
+
+use super::RefineryResult;
+
+const DEFAULT_INSTRUCTION: &str = "Extract the requested information from the web page content. \
+    Answer with a single JSON value that follows the schema. Use null for anything the page does not state.";
+
+#[derive(Debug, Error)]
+pub enum LlmError {
+    /// Budget exhausted or the provider call failed
+    #[error(transparent)]
+    Enrichment(#[from] EnrichmentError),
+
+    #[error("LLM answer is not valid JSON: {0}")]
+    InvalidJson(String),
+
+    #[error("LLM answer is missing required field '{0}'")]
+    MissingField(String),
+}
+
+/// Prompt sent to a provider
+#[derive(Debug, Clone, PartialEq)]
+pub struct LlmRequest {
+    pub instruction: String,
+    pub content: String,
+    pub schema: Value,
+}
+
+/// A provider's answer
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LlmCompletion {
+    /// Raw answer text; expected to hold JSON
+    pub text: String,
+    /// Tokens billed for the prompt and the answer, when the provider reports them
+    pub prompt_tokens: Option<u64>,
+    pub completion_tokens: Option<u64>,
+}
+
+/// Trait for LLM backends (hosted APIs, local servers)
+pub trait LlmProvider: Send + Sync {
+    /// Provider name, used in logs and metrics
+    fn name(&self) -> &str;
+
+    /// Asks the model to fill `request.schema` from `request.content`
+    fn complete(&self, request: &LlmRequest) -> AsyncResult<LlmCompletion>;
+}
+
+/// Settings for `LlmExtractor` and `ChatCompletionsProvider`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LlmExtractorConfig {
+    /// OpenAI-compatible chat completions URL
+    pub endpoint: String,
+    pub model: String,
+    /// Environment variable holding the API key (unset sends no Authorization header)
+    pub api_key_env: String,
+    /// System prompt; the schema is sent as the response format
+    pub instruction: String,
+    /// Page text beyond this many characters is cut before sending (0 sends everything)
+    pub max_input_chars: usize,
+    /// Answers kept in memory; the oldest is dropped first (0 disables caching)
+    pub cache_entries: usize,
+    pub timeout_secs: u64,
+}
+
+impl Default for LlmExtractorConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: "https://api.openai.com/v1/chat/completions".to_string(),
+            model: "gpt-4o-mini".to_string(),
+            api_key_env: "OPENAI_API_KEY".to_string(),
+            instruction: DEFAULT_INSTRUCTION.to_string(),
+            max_input_chars: 24_000,
+            cache_entries: 10_000,
+            timeout_secs: 60,
+        }
+    }
+}
+
+/// Structured data extracted from one page
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LlmExtraction {
+    pub data: Value,
+    /// Served from the cache without a provider call
+    pub cached: bool,
+}
+
+/// `LlmProvider` for OpenAI-compatible `/chat/completions` endpoints using JSON schema output
+pub struct ChatCompletionsProvider {
+    client: reqwest::Client,
+    endpoint: String,
+    model: String,
+    api_key: Option<String>,
+}
+
+impl ChatCompletionsProvider {
+    /// Builds a provider from config, reading the API key from `api_key_env`
+    pub fn new(config: &LlmExtractorConfig) -> Result<Self, EnrichmentError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs.max(1)))
+            .build()
+            .map_err(|e| EnrichmentError::ProviderError(e.to_string()))?;
+        Ok(Self {
+            client,
+            endpoint: config.endpoint.clone(),
+            model: config.model.clone(),
+            api_key: std::env::var(&config.api_key_env).ok().filter(|key| !key.is_empty()),
+        })
+    }
+}
+
+impl LlmProvider for ChatCompletionsProvider {
+    fn name(&self) -> &str {
+        &self.model
+    }
+
+    fn complete(&self, request: &LlmRequest) -> AsyncResult<LlmCompletion> {
+        let body = json!({
+            "model": self.model,
+            "temperature": 0,
+            "messages": [
+                {"role": "system", "content": request.instruction},
+                {"role": "user", "content": request.content},
+            ],
+            "response_format": {
+                "type": "json_schema",
+                "json_schema": {"name": "extraction", "schema": request.schema},
+            },
+        });
+        let mut call = self.client.post(&self.endpoint).json(&body);
+        if let Some(key) = &self.api_key {
+            call = call.bearer_auth(key);
+        }
+        Box::pin(async move {
+            let provider_error = |e: reqwest::Error| EnrichmentError::ProviderError(e.to_string());
+            let answer: Value = call
+                .send()
+                .await
+                .and_then(|resp| resp.error_for_status())
+                .map_err(provider_error)?
+                .json()
+                .await
+                .map_err(provider_error)?;
+            let text = answer
+                .pointer("/choices/0/message/content")
+                .and_then(Value::as_str)
+                .ok_or_else(|| EnrichmentError::ProviderError("answer has no message content".to_string()))?;
+            Ok(LlmCompletion {
+                text: text.to_string(),
+                prompt_tokens: answer.pointer("/usage/prompt_tokens").and_then(Value::as_u64),
+                completion_tokens: answer.pointer("/usage/completion_tokens").and_then(Value::as_u64),
+            })
+        })
+    }
+}
+
+/// Schema-guided extraction over any `LlmProvider`
+///
+/// # Example
+/// ```ignore
+/// let config = LlmExtractorConfig::default();
+/// let extractor = LlmExtractor::new(Arc::new(ChatCompletionsProvider::new(&config)?), config)
+///     .with_budget(budget.clone());
+/// let schema = json!({"type": "object", "properties": {"price": {"type": "number"}}, "required": ["price"]});
+/// let found = extractor.extract_result(&refinery.process(html), &schema).await?;
+/// ```
+pub struct LlmExtractor {
+    provider: Arc<dyn LlmProvider>,
+    config: LlmExtractorConfig,
+    budget: Option<Arc<EnrichmentBudget>>,
+    cache: Mutex<AnswerCache>,
+}
+
+#[derive(Default)]
+struct AnswerCache {
+    answers: HashMap<u64, Value>,
+    order: VecDeque<u64>,
+}
+
+impl LlmExtractor {
+    pub fn new(provider: Arc<dyn LlmProvider>, config: LlmExtractorConfig) -> Self {
+        Self {
+            provider,
+            config,
+            budget: None,
+            cache: Mutex::new(AnswerCache::default()),
+        }
+    }
+
+    /// Charges every provider call to the shared enrichment budget
+    pub fn with_budget(mut self, budget: Arc<EnrichmentBudget>) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Extracts from a refined page: its title and persistable (redacted, when enabled) text
+    pub async fn extract_result(&self, result: &RefineryResult, schema: &Value) -> Result<LlmExtraction, LlmError> {
+        let content = match &result.metadata.title {
+            Some(title) => format!("{}\n\n{}", title, result.persistable_text()),
+            None => result.persistable_text().to_string(),
+        };
+        self.extract(&content, schema).await
+    }
+
+    /// Fills `schema` from `content`, answering repeated content and schema pairs from the cache
+    pub async fn extract(&self, content: &str, schema: &Value) -> Result<LlmExtraction, LlmError> {
+        let content = match content.char_indices().nth(self.config.max_input_chars) {
+            Some((cut, _)) if self.config.max_input_chars > 0 => &content[..cut],
+            _ => content,
+        };
+        let key = cache_key(&self.config.instruction, content, schema);
+        if let Some(data) = self.cache.lock().unwrap().answers.get(&key).cloned() {
+            ops::record_llm_extraction(self.provider.name(), "cached");
+            return Ok(LlmExtraction { data, cached: true });
+        }
+
+        let outcome = self.call(content, schema).await;
+        ops::record_llm_extraction(
+            self.provider.name(),
+            match &outcome {
+                Ok(_) => "ok",
+                Err(LlmError::Enrichment(EnrichmentError::BudgetExhausted(_))) => "budget_exhausted",
+                Err(LlmError::Enrichment(_)) => "provider_error",
+                Err(_) => "invalid_answer",
+            },
+        );
+        let data = outcome?;
+        self.remember(key, data.clone());
+        Ok(LlmExtraction { data, cached: false })
+    }
+
+    async fn call(&self, content: &str, schema: &Value) -> Result<Value, LlmError> {
+        let request = LlmRequest {
+            instruction: self.config.instruction.clone(),
+            content: content.to_string(),
+            schema: schema.clone(),
+        };
+        let reserved = match &self.budget {
+            Some(budget) => {
+                let prompt = format!("{}{}{}", request.instruction, request.content, request.schema);
+                let estimated = estimate_tokens(&prompt);
+                budget.acquire(estimated).await?;
+                Some((budget, estimated))
+            }
+            None => None,
+        };
+        let completion = self.provider.complete(&request).await?;
+        // * Charge what the provider billed, answer included, once it reports usage
+        if let Some((budget, estimated)) = reserved {
+            if completion.prompt_tokens.is_some() || completion.completion_tokens.is_some() {
+                let billed = completion.prompt_tokens.map_or(estimated, |tokens| tokens as usize)
+                    + completion.completion_tokens.unwrap_or(0) as usize;
+                budget.settle(estimated, billed);
+            }
+        }
+        let data = parse_answer(&completion.text)?;
+        if let Some(field) = missing_required(schema, &data) {
+            return Err(LlmError::MissingField(field));
+        }
+        Ok(data)
+    }
+
+    fn remember(&self, key: u64, data: Value) {
+        if self.config.cache_entries == 0 {
+            return;
+        }
+        let mut cache = self.cache.lock().unwrap();
+        if cache.answers.insert(key, data).is_none() {
+            cache.order.push_back(key);
+        }
+        while cache.order.len() > self.config.cache_entries {
+            if let Some(oldest) = cache.order.pop_front() {
+                cache.answers.remove(&oldest);
+            }
+        }
+    }
+}
+
+fn cache_key(instruction: &str, content: &str, schema: &Value) -> u64 {
+    let mut input = String::with_capacity(instruction.len() + content.len() + 64);
+    input.push_str(instruction);
+    input.push('\0');
+    input.push_str(&schema.to_string());
+    input.push('\0');
+    input.push_str(content);
+    xxh64(input.as_bytes(), 0)
+}
+
+/// Parses an answer, tolerating a Markdown code fence around the JSON
+fn parse_answer(text: &str) -> Result<Value, LlmError> {
+    let text = text.trim();
+    let text = match text.strip_prefix("```") {
+        Some(fenced) => fenced
+            .trim_start_matches("json")
+            .trim_end()
+            .trim_end_matches("```")
+            .trim(),
+        None => text,
+    };
+    serde_json::from_str(text).map_err(|e| LlmError::InvalidJson(e.to_string()))
+}
+
+/// Returns the first top-level `required` field of an object schema the answer lacks
+fn missing_required(schema: &Value, data: &Value) -> Option<String> {
+    schema
+        .get("required")?
+        .as_array()?
+        .iter()
+        .filter_map(Value::as_str)
+        .find(|field| data.get(field).is_none())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::EnrichmentBudgetConfig;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct ScriptedProvider {
+        answer: String,
+        calls: AtomicUsize,
+        // * Reported (prompt, completion) tokens
+        usage: (Option<u64>, Option<u64>),
+    }
+
+    impl LlmProvider for ScriptedProvider {
+        fn name(&self) -> &str {
+            "scripted"
+        }
+
+        fn complete(&self, _request: &LlmRequest) -> AsyncResult<LlmCompletion> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let text = self.answer.clone();
+            let (prompt_tokens, completion_tokens) = self.usage;
+            Box::pin(async move {
+                Ok(LlmCompletion {
+                    text,
+                    prompt_tokens,
+                    completion_tokens,
+                })
+            })
+        }
+    }
+
+    fn scripted(answer: &str) -> (LlmExtractor, Arc<ScriptedProvider>) {
+        let provider = Arc::new(ScriptedProvider {
+            answer: answer.to_string(),
+            calls: AtomicUsize::new(0),
+            usage: (None, None),
+        });
+        (LlmExtractor::new(provider.clone(), LlmExtractorConfig::default()), provider)
+    }
+
+    fn schema() -> Value {
+        json!({"type": "object", "properties": {"price": {"type": "number"}}, "required": ["price"]})
+    }
+
+    #[tokio::test]
+    async fn test_answers_are_cached_and_charged_to_budget() {
+        let (extractor, provider) = scripted("```json\n{\"price\": 19.5}\n```");
+        let budget = Arc::new(EnrichmentBudget::new(EnrichmentBudgetConfig {
+            daily_budget_usd: 1.0,
+            cost_per_request_usd: 0.5,
+            ..Default::default()
+        }));
+        let extractor = extractor.with_budget(budget.clone());
+
+        let first = extractor.extract("Blue kettle, now $19.50", &schema()).await.unwrap();
+        assert_eq!(first, LlmExtraction { data: json!({"price": 19.5}), cached: false });
+        assert!(extractor.extract("Blue kettle, now $19.50", &schema()).await.unwrap().cached);
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+        assert!((budget.spent_today() - 0.5).abs() < 1e-9);
+
+        extractor.extract("Red kettle, now $21", &schema()).await.unwrap();
+        let exhausted = extractor.extract("Green kettle, now $23", &schema()).await;
+        assert!(matches!(exhausted, Err(LlmError::Enrichment(EnrichmentError::BudgetExhausted(_)))));
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_reported_usage_is_settled_against_the_budget() {
+        let provider = Arc::new(ScriptedProvider {
+            answer: "{\"price\": 19.5}".to_string(),
+            calls: AtomicUsize::new(0),
+            usage: (Some(1500), Some(500)),
+        });
+        let budget = Arc::new(EnrichmentBudget::new(EnrichmentBudgetConfig {
+            cost_per_1k_tokens_usd: 1.0,
+            ..Default::default()
+        }));
+        let extractor = LlmExtractor::new(provider, LlmExtractorConfig::default()).with_budget(budget.clone());
+
+        extractor.extract("Blue kettle, now $19.50", &schema()).await.unwrap();
+        // * 2000 billed tokens replace the prompt estimate
+        assert!((budget.spent_today() - 2.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_answers_are_rejected_and_not_cached() {
+        let (extractor, provider) = scripted("{\"name\": \"Blue kettle\"}");
+        let missing = extractor.extract("Blue kettle", &schema()).await;
+        assert!(matches!(missing, Err(LlmError::MissingField(field)) if field == "price"));
+        // * Failed answers are not cached, so a retry calls the provider again
+        assert!(extractor.extract("Blue kettle", &schema()).await.is_err());
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+
+        let (extractor, _) = scripted("Sorry, I cannot help with that.");
+        assert!(matches!(extractor.extract("Blue kettle", &schema()).await, Err(LlmError::InvalidJson(_))));
+    }
+
+    #[tokio::test]
+    async fn test_chat_completions_provider_parses_answer() {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            let mut stream = listener.incoming().next().unwrap().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut length = 0;
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                line.clear();
+            }
+            let mut request = vec![0; length];
+            reader.read_exact(&mut request).unwrap();
+            let request: Value = serde_json::from_slice(&request).unwrap();
+            assert_eq!(request["response_format"]["json_schema"]["schema"]["required"][0], "price");
+
+            let body = r#"{"choices":[{"message":{"content":"{\"price\": 7}"}}],"usage":{"prompt_tokens":120,"completion_tokens":6}}"#;
+            let reply = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(reply.as_bytes()).unwrap();
+        });
+
+        let provider = ChatCompletionsProvider::new(&LlmExtractorConfig {
+            endpoint: format!("http://127.0.0.1:{}/v1/chat/completions", port),
+            api_key_env: "TITAN_TEST_UNSET_LLM_KEY".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        let completion = provider
+            .complete(&LlmRequest {
+                instruction: DEFAULT_INSTRUCTION.to_string(),
+                content: "Teapot, $7".to_string(),
+                schema: schema(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(completion.text, "{\"price\": 7}");
+        assert_eq!(completion.prompt_tokens, Some(120));
+        assert_eq!(completion.completion_tokens, Some(6));
+    }
+}
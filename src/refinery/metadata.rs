@@ -19,6 +19,10 @@ static SELECTOR_TIME: LazyLock<Selector> =
     LazyLock::new(|| Selector::parse("time[datetime]").unwrap());
 static SELECTOR_ARTICLE: LazyLock<Selector> =
     LazyLock::new(|| Selector::parse("article").unwrap());
+static SELECTOR_AMPHTML: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse(r#"link[rel="amphtml"][href]"#).unwrap());
+static SELECTOR_HREFLANG: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse(r#"link[rel~="alternate"][hreflang][href]"#).unwrap());
 
 // * Regex patterns for date extraction from text
 static DATE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
@@ -42,6 +46,15 @@ pub struct PageMetadata {
     pub site_name: Option<String>,
     pub publisher: Option<String>,
     pub canonical_url: Option<String>,
+    /// AMP version of the page (`<link rel="amphtml">`)
+    #[serde(default)]
+    pub amp_url: Option<String>,
+    /// Next page of a paginated article (set by a site rule's pagination selector)
+    #[serde(default)]
+    pub next_page_url: Option<String>,
+    /// Translations of the page (`<link rel="alternate" hreflang>`), hrefs as written
+    #[serde(default)]
+    pub alternate_languages: Vec<LanguageAlternate>,
 
     // * Social/SEO
     pub og_image: Option<String>,
@@ -56,6 +69,28 @@ pub struct PageMetadata {
 
     // * Extraction metadata
     pub extraction_method: String,
+    /// Which variant of the page the content was extracted from
+    #[serde(default)]
+    pub extraction_source: ExtractionSource,
+}
+
+/// A language variant a page declares
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LanguageAlternate {
+    /// BCP 47 tag, lowercased (`x-default` for the fallback variant)
+    pub hreflang: String,
+    pub url: String,
+}
+
+/// Page variant a result was extracted from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtractionSource {
+    /// The requested URL itself
+    #[default]
+    Original,
+    /// The page's declared AMP alternate
+    Amp,
 }
 
 impl PageMetadata {
@@ -303,53 +338,35 @@ impl MetadataExtractor {
             }
 
             match property {
-                "og:title" => {
-                    if metadata.title.is_none() {
-                        metadata.title = Some(content.to_string());
-                    }
+                "og:title" if metadata.title.is_none() => {
+                    metadata.title = Some(content.to_string());
                 }
-                "og:description" => {
-                    if metadata.description.is_none() {
-                        metadata.description = Some(content.to_string());
-                    }
+                "og:description" if metadata.description.is_none() => {
+                    metadata.description = Some(content.to_string());
                 }
-                "og:image" => {
-                    if metadata.og_image.is_none() {
-                        metadata.og_image = Some(content.to_string());
-                    }
+                "og:image" if metadata.og_image.is_none() => {
+                    metadata.og_image = Some(content.to_string());
                 }
-                "og:site_name" => {
-                    if metadata.site_name.is_none() {
-                        metadata.site_name = Some(content.to_string());
-                    }
+                "og:site_name" if metadata.site_name.is_none() => {
+                    metadata.site_name = Some(content.to_string());
                 }
                 "og:type" => {
                     metadata.og_type = Some(content.to_string());
                 }
-                "og:url" => {
-                    if metadata.canonical_url.is_none() {
-                        metadata.canonical_url = Some(content.to_string());
-                    }
+                "og:url" if metadata.canonical_url.is_none() => {
+                    metadata.canonical_url = Some(content.to_string());
                 }
-                "article:published_time" => {
-                    if metadata.date_published.is_none() {
-                        metadata.date_published = Some(content.to_string());
-                    }
+                "article:published_time" if metadata.date_published.is_none() => {
+                    metadata.date_published = Some(content.to_string());
                 }
-                "article:modified_time" => {
-                    if metadata.date_modified.is_none() {
-                        metadata.date_modified = Some(content.to_string());
-                    }
+                "article:modified_time" if metadata.date_modified.is_none() => {
+                    metadata.date_modified = Some(content.to_string());
                 }
-                "article:author" => {
-                    if metadata.author.is_none() {
-                        metadata.author = Some(content.to_string());
-                    }
+                "article:author" if metadata.author.is_none() => {
+                    metadata.author = Some(content.to_string());
                 }
-                "article:section" => {
-                    if metadata.section.is_none() {
-                        metadata.section = Some(content.to_string());
-                    }
+                "article:section" if metadata.section.is_none() => {
+                    metadata.section = Some(content.to_string());
                 }
                 _ => {}
             }
@@ -371,17 +388,13 @@ impl MetadataExtractor {
             }
 
             match name.to_lowercase().as_str() {
-                "description" => {
-                    if metadata.description.is_none() {
-                        metadata.description = Some(content.to_string());
-                    }
+                "description" if metadata.description.is_none() => {
+                    metadata.description = Some(content.to_string());
                 }
-                "author" => {
-                    if metadata.author.is_none() {
-                        metadata.author = Some(content.to_string());
-                        if !metadata.authors.contains(&content.to_string()) {
-                            metadata.authors.push(content.to_string());
-                        }
+                "author" if metadata.author.is_none() => {
+                    metadata.author = Some(content.to_string());
+                    if !metadata.authors.contains(&content.to_string()) {
+                        metadata.authors.push(content.to_string());
                     }
                 }
                 "keywords" => {
@@ -392,20 +405,14 @@ impl MetadataExtractor {
                         .collect();
                     metadata.keywords.extend(keywords);
                 }
-                "date" | "publish-date" | "pubdate" => {
-                    if metadata.date_published.is_none() {
-                        metadata.date_published = Some(content.to_string());
-                    }
+                "date" | "publish-date" | "pubdate" if metadata.date_published.is_none() => {
+                    metadata.date_published = Some(content.to_string());
                 }
-                "last-modified" | "revised" => {
-                    if metadata.date_modified.is_none() {
-                        metadata.date_modified = Some(content.to_string());
-                    }
+                "last-modified" | "revised" if metadata.date_modified.is_none() => {
+                    metadata.date_modified = Some(content.to_string());
                 }
-                "language" | "content-language" => {
-                    if metadata.language.is_none() {
-                        metadata.language = Some(content.to_string());
-                    }
+                "language" | "content-language" if metadata.language.is_none() => {
+                    metadata.language = Some(content.to_string());
                 }
                 _ => {}
             }
@@ -421,6 +428,25 @@ impl MetadataExtractor {
             }
         }
 
+        // * Extract AMP alternate link
+        if let Some(link) = document.select(&SELECTOR_AMPHTML).next() {
+            metadata.amp_url = link.value().attr("href").map(str::to_string);
+        }
+
+        // * Extract hreflang alternates
+        for link in document.select(&SELECTOR_HREFLANG) {
+            let (Some(hreflang), Some(href)) = (link.value().attr("hreflang"), link.value().attr("href")) else {
+                continue;
+            };
+            let alternate = LanguageAlternate {
+                hreflang: hreflang.trim().to_lowercase(),
+                url: href.trim().to_string(),
+            };
+            if !alternate.hreflang.is_empty() && !metadata.alternate_languages.contains(&alternate) {
+                metadata.alternate_languages.push(alternate);
+            }
+        }
+
         // * Extract language from html tag
         if metadata.language.is_none() {
             let html_selector = Selector::parse("html").unwrap();
@@ -699,4 +725,41 @@ mod tests {
         assert!(meta_with.has_essential_fields());
         assert!(!meta_without.has_essential_fields());
     }
+
+    #[test]
+    fn test_amp_alternate() {
+        let html = r#"<html><head>
+            <link rel="amphtml" href="https://example.com/story/amp">
+        </head></html>"#;
+
+        let metadata = MetadataExtractor::extract(html);
+        assert_eq!(metadata.amp_url.as_deref(), Some("https://example.com/story/amp"));
+        assert_eq!(metadata.extraction_source, ExtractionSource::Original);
+        assert!(MetadataExtractor::extract("<html></html>").amp_url.is_none());
+    }
+
+    #[test]
+    fn test_hreflang_alternates() {
+        let html = r#"<html><head>
+            <link rel="alternate" hreflang="en-GB" href="https://example.com/en/story">
+            <link rel="alternate" hreflang="de" href="https://example.com/de/story">
+            <link rel="alternate" hreflang="de" href="https://example.com/de/story">
+            <link rel="alternate" type="application/rss+xml" href="/feed.xml">
+        </head></html>"#;
+
+        let alternates = MetadataExtractor::extract(html).alternate_languages;
+        assert_eq!(
+            alternates,
+            vec![
+                LanguageAlternate {
+                    hreflang: "en-gb".to_string(),
+                    url: "https://example.com/en/story".to_string(),
+                },
+                LanguageAlternate {
+                    hreflang: "de".to_string(),
+                    url: "https://example.com/de/story".to_string(),
+                },
+            ]
+        );
+    }
 }
@@ -4,18 +4,61 @@
 
 pub mod chunker;
 pub mod content_cleaner;
+pub mod golden;
+pub mod json_extractor;
+pub mod language;
+#[cfg(feature = "llm")]
+pub mod llm_extractor;
 pub mod metadata;
+pub mod pool;
+pub mod product;
 pub mod regex_extractor;
+pub mod sanitize;
+pub mod site_rules;
 pub mod tables;
+pub mod template;
+#[cfg(feature = "xpath")]
+pub mod xpath;
 
 // * Re-exports for convenient access
 pub use chunker::{chunk_text, chunk_text_with_window, ChunkerConfig, SlidingWindowChunker, TextChunk};
-pub use content_cleaner::{extract_content, extract_text, CleanedContent, CleanerConfig, ContentCleaner};
-pub use metadata::{MetadataExtractor, PageMetadata};
-pub use regex_extractor::{EntityType, ExtractorConfig, ExtractionResult, RegexExtractor};
-pub use tables::{ExtractedTable, TableScorer};
-
+pub use content_cleaner::{
+    default_boilerplate_phrases, extract_content, extract_text, BoilerplateOverride, Citation,
+    CleanedContent, CleanerConfig, ContentCleaner, QualityBreakdown, QualityComponent,
+};
+pub use golden::{FieldDiff, Fixture, FixtureResult, FixtureStatus, GoldenError, GoldenHarness, GoldenReport};
+pub use json_extractor::{JsonExtraction, JsonRule, JsonRuleError, JsonRuleSet};
+pub use language::Language;
+pub use metadata::{ExtractionSource, LanguageAlternate, MetadataExtractor, PageMetadata};
+pub use pool::RefineryPool;
+pub use product::{Availability, ProductInfo, ProductSource};
+pub use regex_extractor::{
+    EntityType, ExtractorConfig, ExtractionResult, RedactionPolicy, RegexExtractor,
+};
+pub use sanitize::sanitize_html;
+pub use site_rules::{SiteExtraction, SiteRule, SiteRuleSet, SiteRulesError, SiteRulesFile};
+pub use tables::{
+    ColumnType, ExtractedTable, TableExtractionConfig, TableScorer, TableScorerConfig,
+};
+pub use template::{LearnedTemplate, TemplateConfig, TemplateLearner};
+#[cfg(feature = "llm")]
+pub use llm_extractor::{
+    ChatCompletionsProvider, LlmCompletion, LlmError, LlmExtraction, LlmExtractor, LlmExtractorConfig, LlmProvider,
+    LlmRequest,
+};
+#[cfg(feature = "xpath")]
+pub use xpath::{XPathError, XPathMatch, XPathQuery};
+
+use crate::engine::slow_path::{CapturedResponse, PageDiagnostic};
+use crate::ops;
+use crate::persistence::{MultimodalRecord, MultimodalRecordBuilder};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+// * Limits sized so ordinary pages (even long docs) never hit them
+const DEFAULT_MAX_DOM_NODES: usize = 100_000;
+const DEFAULT_MAX_PARSE_TIME_MS: u64 = 5_000;
 
 /// Unified result from the refinery pipeline
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -26,12 +69,46 @@ pub struct RefineryResult {
     pub metadata: PageMetadata,
     /// Extracted data tables
     pub tables: Vec<ExtractedTable>,
+    /// Price, availability and ratings when the page is a product page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub product: Option<ProductInfo>,
     /// Extracted entities (emails, URLs, dates, etc.)
     pub entities: ExtractionResult,
     /// Text chunks for embedding/processing
     pub chunks: Vec<TextChunk>,
+    /// PII-redacted copy of the main text (set when redaction is enabled)
+    pub redacted_text: Option<String>,
     /// Processing statistics
     pub stats: RefineryStats,
+    /// Set when a size or time limit cut processing short
+    #[serde(default)]
+    pub truncated: Option<Truncated>,
+    /// Errors the page raised while the slow path rendered it (set by the fetching caller)
+    #[serde(default)]
+    pub diagnostics: Vec<PageDiagnostic>,
+}
+
+/// Limit that cut a page's processing short
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Truncated {
+    /// The fetcher stopped reading at `max_response_bytes`
+    ResponseBytes,
+    /// The document was cut after `max_dom_nodes` elements
+    DomNodes,
+    /// `max_parse_time_ms` ran out; tables and entities were skipped
+    ParseTime,
+}
+
+impl Truncated {
+    /// Label used in telemetry
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Truncated::ResponseBytes => "response_bytes",
+            Truncated::DomNodes => "dom_nodes",
+            Truncated::ParseTime => "parse_time",
+        }
+    }
 }
 
 impl RefineryResult {
@@ -44,6 +121,21 @@ impl RefineryResult {
     pub fn to_json_pretty(&self) -> String {
         serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
     }
+
+    /// Returns the text safe for persistence (redacted when available)
+    pub fn persistable_text(&self) -> &str {
+        self.redacted_text.as_deref().unwrap_or(&self.content.text)
+    }
+
+    /// Builds a persistence record from this result
+    ///
+    /// ! When redaction is enabled, `text_content` holds the redacted text so
+    /// ! raw PII never reaches the vector store.
+    pub fn to_record(&self, url: String, content_hash: u64) -> MultimodalRecord {
+        let mut record = MultimodalRecordBuilder::from_refinery(self, url).build();
+        record.content_hash = content_hash;
+        record
+    }
 }
 
 /// Processing statistics from the refinery
@@ -56,34 +148,79 @@ pub struct RefineryStats {
     pub chunk_count: usize,
     pub quality_score: f32,
     pub has_main_content: bool,
+    /// Charset the page was transcoded from before refining (set by the crawler)
+    #[serde(default)]
+    pub encoding: Option<String>,
 }
 
 /// Configuration for the refinery pipeline
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct RefineryConfig {
     /// Content cleaner configuration
     pub cleaner: CleanerConfig,
+    /// Per-domain learning of repeated blocks dropped as boilerplate (`[refinery.templates]`)
+    pub templates: TemplateConfig,
     /// Chunker configuration
     pub chunker: ChunkerConfig,
     /// Entity extractor configuration
     pub extractor: ExtractorConfig,
+    /// Table extraction configuration
+    pub tables: TableExtractionConfig,
     /// Whether to extract tables
     pub extract_tables: bool,
+    /// Whether to recognize product pages and extract their price data
+    pub extract_product: bool,
     /// Whether to extract entities
     pub extract_entities: bool,
     /// Whether to generate chunks
     pub generate_chunks: bool,
+    /// PII redaction applied to the persisted text and chunks (None disables)
+    pub redaction: Option<RedactionPolicy>,
+    /// Elements parsed per page; the document is cut after this many (0 disables)
+    pub max_dom_nodes: usize,
+    /// Time budget per page; once spent, tables and entities are skipped (0 disables)
+    pub max_parse_time_ms: u64,
+    /// Documents a `RefineryPool` refines at once (0 uses the number of CPU cores)
+    pub pool_concurrency: usize,
+    /// Per-site selectors preferred over the heuristics (`[[refinery.site_rules]]`)
+    pub site_rules: Vec<SiteRule>,
+    /// TOML or JSON file of further site rules, merged after the inline ones
+    pub site_rules_file: Option<PathBuf>,
+    /// Field expressions for JSON API responses (`[[refinery.json_rules]]`)
+    pub json_rules: Vec<JsonRule>,
+}
+
+impl RefineryConfig {
+    /// Compiles the inline site rules together with those in `site_rules_file`
+    pub fn compile_site_rules(&self) -> Result<SiteRuleSet, SiteRulesError> {
+        let mut rules = self.site_rules.clone();
+        if let Some(path) = &self.site_rules_file {
+            rules.extend(SiteRulesFile::load(path)?.site);
+        }
+        SiteRuleSet::new(&rules)
+    }
 }
 
 impl Default for RefineryConfig {
     fn default() -> Self {
         Self {
             cleaner: CleanerConfig::default(),
+            templates: TemplateConfig::default(),
             chunker: ChunkerConfig::default(),
             extractor: ExtractorConfig::default(),
+            tables: TableExtractionConfig::default(),
             extract_tables: true,
+            extract_product: true,
             extract_entities: true,
             generate_chunks: true,
+            redaction: None,
+            max_dom_nodes: DEFAULT_MAX_DOM_NODES,
+            max_parse_time_ms: DEFAULT_MAX_PARSE_TIME_MS,
+            pool_concurrency: 0,
+            site_rules: Vec::new(),
+            site_rules_file: None,
+            json_rules: Vec::new(),
         }
     }
 }
@@ -104,8 +241,12 @@ impl Default for RefineryConfig {
 pub struct Refinery {
     config: RefineryConfig,
     cleaner: ContentCleaner,
+    // * Present when `templates.enabled`; shared by every page the refinery processes
+    templates: Option<TemplateLearner>,
     chunker: SlidingWindowChunker,
     extractor: RegexExtractor,
+    site_rules: SiteRuleSet,
+    json_rules: JsonRuleSet,
 }
 
 impl Refinery {
@@ -114,18 +255,38 @@ impl Refinery {
         let config = RefineryConfig::default();
         Self {
             cleaner: ContentCleaner::with_config(config.cleaner.clone()),
+            templates: None,
             chunker: SlidingWindowChunker::with_config(config.chunker.clone()),
             extractor: RegexExtractor::with_config(config.extractor.clone()),
+            site_rules: SiteRuleSet::default(),
+            json_rules: JsonRuleSet::default(),
             config,
         }
     }
 
     /// Creates a new refinery with custom configuration
+    ///
+    /// Site and JSON rules that fail to compile are skipped with a warning (config validation
+    /// reports them).
     pub fn with_config(config: RefineryConfig) -> Self {
+        let site_rules = config.compile_site_rules().unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Site rules disabled");
+            SiteRuleSet::default()
+        });
+        let json_rules = JsonRuleSet::new(&config.json_rules).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "JSON rules disabled");
+            JsonRuleSet::default()
+        });
         Self {
             cleaner: ContentCleaner::with_config(config.cleaner.clone()),
+            templates: config
+                .templates
+                .enabled
+                .then(|| TemplateLearner::new(config.templates.clone())),
             chunker: SlidingWindowChunker::with_config(config.chunker.clone()),
             extractor: RegexExtractor::with_config(config.extractor.clone()),
+            site_rules,
+            json_rules,
             config,
         }
     }
@@ -135,31 +296,127 @@ impl Refinery {
     /// # Pipeline Steps:
     /// 1. Extract and clean main content (remove boilerplate)
     /// 2. Extract page metadata (JSON-LD, meta tags, fallbacks)
-    /// 3. Extract data tables (heuristic scoring)
-    /// 4. Extract entities (regex patterns)
+    /// 3. Extract data tables (heuristic scoring) and product details
+    /// 4. Extract entities (regex patterns), then redact PII if configured
     /// 5. Generate text chunks (sliding window)
     pub fn process(&self, html: &str) -> RefineryResult {
-        let mut result = RefineryResult::default();
+        self.process_page(html, None)
+    }
 
-        // * Step 1: Clean and extract main content
-        result.content = self.cleaner.clean(html);
+    /// Processes HTML from `domain`, applying that host's cleaner overrides and site rule
+    ///
+    /// With template learning enabled, the page's blocks are also counted towards the
+    /// domain's template, and blocks already learned are dropped.
+    ///
+    /// A JSON body from a host with a JSON rule is mapped through that rule instead.
+    pub fn process_for_domain(&self, html: &str, domain: &str) -> RefineryResult {
+        self.process_page(html, Some(domain))
+    }
+
+    /// Maps a JSON API response through `domain`'s JSON rule and refines the selected body
+    ///
+    /// Returns None when no JSON rule covers the domain or the body is not JSON.
+    pub fn process_json(&self, body: &str, domain: &str) -> Option<RefineryResult> {
+        let found = self.json_rules.extract(body, domain)?;
+        Some(self.process_extracted_json(found, domain))
+    }
+
+    /// Maps the JSON API responses a slow path render captured through the JSON rules of
+    /// their hosts, returning each mapped response's URL and result
+    pub fn process_captured(&self, responses: &[CapturedResponse]) -> Vec<(String, RefineryResult)> {
+        if self.json_rules.is_empty() {
+            return Vec::new();
+        }
+        responses
+            .iter()
+            .filter_map(|response| {
+                let host = url::Url::parse(&response.url).ok()?.host_str()?.to_string();
+                Some((response.url.clone(), self.process_json(&response.body, &host)?))
+            })
+            .collect()
+    }
+
+    fn process_extracted_json(&self, found: JsonExtraction, domain: &str) -> RefineryResult {
+        let html = found.to_html();
+        let mut result = self.refine_html(&html, Some(domain));
+        found.apply(&mut result.metadata);
+        result
+    }
+
+    fn process_page(&self, html: &str, domain: Option<&str>) -> RefineryResult {
+        if let Some(found) = domain.and_then(|domain| self.json_rules.extract(html, domain)) {
+            return self.process_extracted_json(found, domain.unwrap_or_default());
+        }
+        self.refine_html(html, domain)
+    }
 
+    fn refine_html(&self, html: &str, domain: Option<&str>) -> RefineryResult {
+        let started = Instant::now();
+        let mut truncated = None;
+        let html = match truncate_dom(html, self.config.max_dom_nodes) {
+            Some(cut) => {
+                truncated = Some(Truncated::DomNodes);
+                cut
+            }
+            None => html,
+        };
+
+        // * Step 1: Clean and extract main content
         // * Step 2: Extract metadata
-        result.metadata = MetadataExtractor::extract(html);
+        let mut result = RefineryResult {
+            content: timed("clean", || match (domain, &self.templates) {
+                (Some(domain), Some(learner)) => {
+                    let template = learner.template(domain);
+                    let (content, blocks) = self.cleaner.clean_with_template(html, Some(domain), &template);
+                    learner.observe(domain, &blocks);
+                    content
+                }
+                (Some(domain), None) => self.cleaner.clean_for_domain(html, domain),
+                (None, _) => self.cleaner.clean(html),
+            }),
+            metadata: timed("metadata", || MetadataExtractor::extract(html)),
+            ..Default::default()
+        };
+
+        // * Step 2b: Explicit site selectors win over the heuristics for every field they find
+        if let Some(found) = domain.and_then(|domain| self.site_rules.extract(html, domain)) {
+            found.apply(&mut result.content, &mut result.metadata);
+        }
+
+        // * Pathological pages stop here; redaction and chunking still run on the text
+        let within_budget = self.config.max_parse_time_ms == 0
+            || started.elapsed() < Duration::from_millis(self.config.max_parse_time_ms);
+        if !within_budget {
+            truncated.get_or_insert(Truncated::ParseTime);
+        }
 
         // * Step 3: Extract data tables
-        if self.config.extract_tables {
-            result.tables = TableScorer::extract_all_tables(html);
+        if self.config.extract_tables && within_budget {
+            result.tables = timed("tables", || {
+                TableScorer::extract_all_tables_with_config(html, &self.config.tables)
+            });
+        }
+
+        // * Step 3b: Recognize product pages (JSON-LD, microdata, then DOM patterns)
+        if self.config.extract_product && within_budget {
+            result.product = timed("product", || ProductInfo::extract(html));
         }
 
         // * Step 4: Extract entities from cleaned text
-        if self.config.extract_entities {
-            result.entities = self.extractor.extract(&result.content.text);
+        if self.config.extract_entities && within_budget {
+            result.entities = timed("entities", || self.extractor.extract(&result.content.text));
+        }
+
+        // * Step 4b: Redact PII before anything is chunked for embedding
+        if let Some(policy) = self.config.redaction {
+            result.redacted_text = Some(timed("redaction", || {
+                RegexExtractor::redact(&result.content.text, policy)
+            }));
         }
 
-        // * Step 5: Generate chunks from cleaned text
+        // * Step 5: Generate chunks from the persistable text
         if self.config.generate_chunks && !result.content.text.is_empty() {
-            result.chunks = self.chunker.chunk(&result.content.text);
+            result.chunks = timed("chunking", || self.chunker.chunk(result.persistable_text()));
         }
 
         // * Calculate statistics
@@ -171,7 +428,13 @@ impl Refinery {
             chunk_count: result.chunks.len(),
             quality_score: result.content.quality_score,
             has_main_content: result.content.found_main_content,
+            encoding: None,
         };
+        ops::record_refinery_document(result.stats.quality_score);
+        if let Some(reason) = truncated {
+            ops::record_truncated_page(reason.as_str());
+        }
+        result.truncated = truncated;
 
         result
     }
@@ -186,6 +449,11 @@ impl Refinery {
         MetadataExtractor::extract(html)
     }
 
+    /// Returns the site template learner (None unless `templates.enabled`)
+    pub fn templates(&self) -> Option<&TemplateLearner> {
+        self.templates.as_ref()
+    }
+
     /// Returns the current configuration
     pub fn config(&self) -> &RefineryConfig {
         &self.config
@@ -198,6 +466,36 @@ impl Default for Refinery {
     }
 }
 
+/// Runs one pipeline stage, recording its duration under `titan_refinery_stage_duration_seconds`
+fn timed<T>(stage: &str, run: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let output = run();
+    ops::record_refinery_stage_duration(stage, started.elapsed().as_secs_f64());
+    output
+}
+
+/// Cuts `html` just before its `max_nodes + 1`-th start tag
+///
+/// Counting start tags is a byte scan, so oversized documents are cut before
+/// anything is parsed. Returns None when the document is within the limit.
+fn truncate_dom(html: &str, max_nodes: usize) -> Option<&str> {
+    if max_nodes == 0 {
+        return None;
+    }
+    let bytes = html.as_bytes();
+    let mut seen = 0;
+    for (i, window) in bytes.windows(2).enumerate() {
+        if window[0] == b'<' && window[1].is_ascii_alphabetic() {
+            seen += 1;
+            if seen > max_nodes {
+                // * '<' is ASCII, so `i` is always a char boundary
+                return Some(&html[..i]);
+            }
+        }
+    }
+    None
+}
+
 /// Convenience function to process HTML with default settings
 pub fn process_html(html: &str) -> RefineryResult {
     Refinery::new().process(html)
@@ -286,6 +584,34 @@ mod tests {
         assert!(result.metadata.title.is_some());
     }
 
+    #[test]
+    fn test_stage_metrics_recorded() {
+        use crate::ops::telemetry::{REFINERY_DOCUMENTS_TOTAL, REFINERY_STAGE_DURATION_SECONDS};
+
+        let samples = |stage: &str| {
+            REFINERY_STAGE_DURATION_SECONDS
+                .with_label_values(&[stage])
+                .get_sample_count()
+        };
+        let documents = || -> f64 {
+            ["0.0-0.2", "0.2-0.4", "0.4-0.6", "0.6-0.8", "0.8-1.0"]
+                .iter()
+                .map(|bucket| REFINERY_DOCUMENTS_TOTAL.with_label_values(&[bucket]).get())
+                .sum()
+        };
+        let stages = ["clean", "metadata", "tables", "entities", "chunking"];
+        let before: Vec<u64> = stages.iter().map(|s| samples(s)).collect();
+        let documents_before = documents();
+
+        Refinery::new().process(sample_html());
+
+        // * Other tests refine concurrently, so counts only grow by at least one
+        for (stage, before) in stages.iter().zip(before) {
+            assert!(samples(stage) > before, "no sample for stage {}", stage);
+        }
+        assert!(documents() > documents_before);
+    }
+
     #[test]
     fn test_content_only_processing() {
         let refinery = Refinery::new();
@@ -326,6 +652,57 @@ mod tests {
         assert!(result.metadata.title.is_some());
     }
 
+    #[test]
+    fn test_product_pages_carry_product_info() {
+        let html = r#"<html><head><script type="application/ld+json">
+            {"@type": "Product", "name": "Field Notebook", "offers": {"price": 9.5, "priceCurrency": "USD"}}
+            </script></head><body><article><h1>Field Notebook</h1><p>Dot grid pages.</p></article></body></html>"#;
+        let product = Refinery::new().process(html).product.unwrap();
+        assert_eq!(product.name.as_deref(), Some("Field Notebook"));
+        assert_eq!(product.price, Some(9.5));
+
+        assert!(process_html(sample_html()).product.is_none());
+        let config = RefineryConfig {
+            extract_product: false,
+            ..Default::default()
+        };
+        assert!(Refinery::with_config(config).process(html).product.is_none());
+    }
+
+    #[test]
+    fn test_site_template_blocks_are_learned_and_dropped() {
+        let page = |topic: &str| {
+            format!(
+                "<html><body><article><h1>{topic}</h1><p>{}</p>\
+                 <p>Our reporters cover the harbor district every single weekday morning.</p>\
+                 </article></body></html>",
+                format!("This story about {topic} has plenty of words to be kept as content. ").repeat(4)
+            )
+        };
+        let refinery = Refinery::with_config(RefineryConfig {
+            templates: TemplateConfig {
+                enabled: true,
+                min_pages: 3,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        for topic in ["ferries", "tides", "cranes"] {
+            let result = refinery.process_for_domain(&page(topic), "harbor.example");
+            assert!(result.content.text.contains("Our reporters"));
+        }
+        let result = refinery.process_for_domain(&page("gulls"), "harbor.example");
+        assert!(!result.content.text.contains("Our reporters"));
+        assert!(result.content.text.contains("gulls"));
+        assert_eq!(refinery.templates().unwrap().pages("harbor.example"), 4);
+
+        // * Other hosts and domain-less processing are unaffected
+        assert!(refinery.process_for_domain(&page("gulls"), "other.example").content.text.contains("Our reporters"));
+        assert!(refinery.process(&page("gulls")).content.text.contains("Our reporters"));
+        assert!(Refinery::new().templates().is_none());
+    }
+
     #[test]
     fn test_result_serialization() {
         let result = process_html(sample_html());
@@ -354,4 +731,152 @@ mod tests {
         assert_eq!(result.stats.entity_count, result.entities.total_count);
         assert_eq!(result.stats.chunk_count, result.chunks.len());
     }
+
+    #[test]
+    fn test_redaction_keeps_pii_out_of_record() {
+        let config = RefineryConfig {
+            redaction: Some(RedactionPolicy::default()),
+            ..Default::default()
+        };
+
+        let refinery = Refinery::with_config(config);
+        let result = refinery.process(sample_html());
+
+        // * Entities are still extracted from the raw text
+        assert!(result.entities.entities.contains_key("email"));
+
+        let redacted = result.redacted_text.as_deref().unwrap();
+        assert!(!redacted.contains("test@example.com"));
+        assert!(redacted.contains("[EMAIL]"));
+        assert!(result.chunks.iter().all(|c| !c.content.contains("test@example.com")));
+
+        let record = result.to_record("https://example.com/article".to_string(), 42);
+        assert!(!record.text_content.contains("test@example.com"));
+        assert!(record.text_content.contains("[EMAIL]"));
+        assert_eq!(record.title, result.metadata.title);
+    }
+
+    #[test]
+    fn test_record_uses_raw_text_without_redaction() {
+        let result = process_html(sample_html());
+
+        assert!(result.redacted_text.is_none());
+        let record = result.to_record("https://example.com/article".to_string(), 42);
+        assert_eq!(record.text_content, result.content.text);
+        assert_eq!(record.word_count as usize, result.stats.word_count);
+    }
+
+    #[test]
+    fn test_table_scorer_config_threaded() {
+        let mut config = RefineryConfig::default();
+        config.tables.scorer.threshold = 100;
+
+        let result = Refinery::with_config(config).process(sample_html());
+
+        assert!(result.tables.is_empty());
+        assert_eq!(result.stats.table_count, 0);
+    }
+
+    #[test]
+    fn test_dom_node_limit_cuts_document() {
+        let config = RefineryConfig {
+            max_dom_nodes: 3,
+            ..Default::default()
+        };
+        let kept = "The first paragraph survives the cut and carries enough words to count as content. ".repeat(3);
+        let html = format!("<html><body><p>{}</p><p>Second paragraph dropped.</p></body></html>", kept);
+
+        let result = Refinery::with_config(config).process(&html);
+        assert_eq!(result.truncated, Some(Truncated::DomNodes));
+        assert!(result.content.text.contains("The first paragraph survives"));
+        assert!(!result.content.text.contains("Second"));
+
+        assert_eq!(truncate_dom("<p>a < b</p><br>", 2), None);
+        assert!(process_html(sample_html()).truncated.is_none());
+    }
+
+    #[test]
+    fn test_parse_time_budget_skips_optional_stages() {
+        let config = RefineryConfig {
+            max_parse_time_ms: 1,
+            ..Default::default()
+        };
+        let html = format!(
+            "<html><body><article>{}</article></body></html>",
+            "<p>Contact sales@example.com about the quarterly report and its tables.</p>".repeat(5_000)
+        );
+
+        let result = Refinery::with_config(config).process(&html);
+        assert_eq!(result.truncated, Some(Truncated::ParseTime));
+        assert_eq!(result.entities.total_count, 0);
+        assert!(!result.content.text.is_empty());
+        assert!(!result.chunks.is_empty());
+    }
+
+    #[test]
+    fn test_site_rules_override_heuristics_for_matching_domain() {
+        let config = RefineryConfig {
+            site_rules: vec![SiteRule {
+                domain: "example.com".to_string(),
+                title: Some("h1".to_string()),
+                author: Some(".byline".to_string()),
+                pagination: Some("a.next".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let refinery = Refinery::with_config(config);
+        let html = format!(
+            "{}<div class=\"byline\">Ada Writer</div><a class=\"next\" href=\"/p/2\">Next</a>",
+            sample_html()
+        );
+
+        let ruled = refinery.process_for_domain(&html, "www.example.com");
+        assert_eq!(ruled.metadata.author.as_deref(), Some("Ada Writer"));
+        assert_eq!(ruled.metadata.next_page_url.as_deref(), Some("/p/2"));
+        assert_eq!(ruled.metadata.extraction_method, "site_rules");
+
+        let heuristic = refinery.process_for_domain(&html, "example.org");
+        assert_eq!(heuristic.metadata.next_page_url, None);
+        assert_ne!(heuristic.metadata.extraction_method, "site_rules");
+    }
+
+    #[test]
+    fn test_json_rules_map_api_responses() {
+        let refinery = Refinery::with_config(RefineryConfig {
+            json_rules: vec![JsonRule {
+                domain: "api.example.com".to_string(),
+                title: Some("$.headline".to_string()),
+                body: Some("$.sections[*].text".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        let body = r#"{"headline": "Tide Tables", "sections": [
+            {"text": "High tide arrives at dawn along the northern coast this week, bringing strong currents."},
+            {"text": "Low tide follows in the early afternoon, exposing the rock pools near the harbor wall."}
+        ]}"#;
+
+        let result = refinery.process_for_domain(body, "api.example.com");
+        assert_eq!(result.metadata.title.as_deref(), Some("Tide Tables"));
+        assert_eq!(result.metadata.extraction_method, "json_rules");
+        assert_eq!(result.content.paragraphs.len(), 2);
+        assert!(!result.chunks.is_empty());
+
+        assert!(refinery.process_json(body, "example.org").is_none());
+        assert!(refinery.process_json(sample_html(), "api.example.com").is_none());
+
+        let captured = |url: &str| CapturedResponse {
+            url: url.to_string(),
+            status: 200,
+            mime_type: "application/json".to_string(),
+            body: body.to_string(),
+        };
+        let mapped = refinery.process_captured(&[
+            captured("https://cdn.example.net/config.json"),
+            captured("https://api.example.com/v1/articles/7"),
+        ]);
+        assert_eq!(mapped.len(), 1);
+        assert_eq!(mapped[0].0, "https://api.example.com/v1/articles/7");
+    }
 }
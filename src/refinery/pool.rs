@@ -0,0 +1,142 @@
+// * Refinery Processing Pool
+// * HTML parsing is CPU-bound; running it inline stalls the tokio workers that drive fetches.
+// * The pool moves each document onto the blocking thread pool, with a semaphore bounding
+// * how many are parsed at once.
+
+use super::{Refinery, RefineryConfig, RefineryResult};
+use std::sync::Arc;
+use std::thread;
+use tokio::sync::Semaphore;
+
+/// Runs the refinery off the async runtime with bounded concurrency
+///
+/// # Example
+/// ```ignore
+/// use titan_flow::refinery::RefineryPool;
+///
+/// let pool = RefineryPool::new(RefineryConfig::default());
+/// let result = pool.process_async(html).await;
+/// ```
+#[derive(Clone)]
+pub struct RefineryPool {
+    refinery: Arc<Refinery>,
+    permits: Arc<Semaphore>,
+    concurrency: usize,
+}
+
+impl RefineryPool {
+    /// Creates a pool sized by `config.pool_concurrency`
+    pub fn new(config: RefineryConfig) -> Self {
+        let concurrency = config.pool_concurrency;
+        Self::with_concurrency(Refinery::with_config(config), concurrency)
+    }
+
+    /// Wraps an existing refinery, refining at most `concurrency` documents at once
+    ///
+    /// 0 uses the number of available CPU cores.
+    pub fn with_concurrency(refinery: Refinery, concurrency: usize) -> Self {
+        let concurrency = match concurrency {
+            0 => thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        };
+        Self {
+            refinery: Arc::new(refinery),
+            permits: Arc::new(Semaphore::new(concurrency)),
+            concurrency,
+        }
+    }
+
+    /// Processes HTML on the blocking pool (see [`Refinery::process`])
+    pub async fn process_async(&self, html: String) -> RefineryResult {
+        self.run(move |refinery| refinery.process(&html)).await
+    }
+
+    /// Processes HTML from `domain` on the blocking pool (see [`Refinery::process_for_domain`])
+    pub async fn process_for_domain_async(&self, html: String, domain: String) -> RefineryResult {
+        self.run(move |refinery| refinery.process_for_domain(&html, &domain)).await
+    }
+
+    /// Maximum number of documents refined at once
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    /// Documents currently being refined
+    pub fn in_flight(&self) -> usize {
+        self.concurrency - self.permits.available_permits()
+    }
+
+    /// Returns the underlying refinery for synchronous use
+    pub fn refinery(&self) -> &Refinery {
+        &self.refinery
+    }
+
+    async fn run<F>(&self, job: F) -> RefineryResult
+    where
+        F: FnOnce(&Refinery) -> RefineryResult + Send + 'static,
+    {
+        // ! The semaphore is never closed, so acquiring cannot fail
+        let permit = Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .expect("refinery pool semaphore closed");
+        let refinery = Arc::clone(&self.refinery);
+        let span = tracing::Span::current();
+
+        let handle = tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            span.in_scope(|| job(&refinery))
+        });
+        // * A panic inside the refinery surfaces in the caller, as it would inline
+        handle
+            .await
+            .unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article(topic: &str) -> String {
+        format!(
+            "<html><body><article><h1>{topic}</h1><p>{}</p></article></body></html>",
+            format!("This paragraph about {topic} has plenty of words to be kept as content. ").repeat(5)
+        )
+    }
+
+    #[tokio::test]
+    async fn test_process_async_matches_inline() {
+        let pool = RefineryPool::new(RefineryConfig::default());
+        let html = article("rivers");
+
+        let pooled = pool.process_async(html.clone()).await;
+        let inline = pool.refinery().process(&html);
+        assert_eq!(pooled.content.text, inline.content.text);
+        assert_eq!(pooled.stats.word_count, inline.stats.word_count);
+        assert!(pooled.content.text.contains("rivers"));
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_is_bounded() {
+        let pool = RefineryPool::with_concurrency(Refinery::new(), 2);
+        assert_eq!(pool.concurrency(), 2);
+
+        let tasks: Vec<_> = ["oak", "elm", "ash", "yew", "fir"]
+            .into_iter()
+            .map(|topic| {
+                let pool = pool.clone();
+                tokio::spawn(async move {
+                    assert!(pool.in_flight() <= 2);
+                    pool.process_for_domain_async(article(topic), "example.com".to_string()).await
+                })
+            })
+            .collect();
+
+        for (task, topic) in tasks.into_iter().zip(["oak", "elm", "ash", "yew", "fir"]) {
+            assert!(task.await.unwrap().content.text.contains(topic));
+        }
+        assert_eq!(pool.in_flight(), 0);
+        assert!(RefineryPool::with_concurrency(Refinery::new(), 0).concurrency() >= 1);
+    }
+}
@@ -0,0 +1,465 @@
+// * Product Page Extraction
+// * Recognizes e-commerce product pages and reads price, currency, availability, SKU, brand
+// * and ratings. Sources in priority order: JSON-LD Product, schema.org microdata, then DOM
+// * patterns (Open Graph product tags, price elements next to an add-to-cart control).
+
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::LazyLock;
+
+static SELECTOR_JSON_LD: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse(r#"script[type="application/ld+json"]"#).unwrap());
+static SELECTOR_ITEMSCOPE: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("[itemscope][itemtype]").unwrap());
+static SELECTOR_ITEMPROP: LazyLock<Selector> = LazyLock::new(|| Selector::parse("[itemprop]").unwrap());
+static SELECTOR_META: LazyLock<Selector> = LazyLock::new(|| Selector::parse("meta[content]").unwrap());
+static SELECTOR_H1: LazyLock<Selector> = LazyLock::new(|| Selector::parse("h1").unwrap());
+static SELECTOR_PRICE: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse(r#"[class*="price"], [id*="price"], [data-price]"#).unwrap());
+static SELECTOR_CART: LazyLock<Selector> = LazyLock::new(|| {
+    Selector::parse(r#"button, input[type="submit"], a[class*="cart"], form[action*="cart"], [class*="add-to-cart"]"#)
+        .unwrap()
+});
+
+// * First amount in a price string: grouped thousands and an optional 1-2 digit fraction
+static PRICE_NUMBER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\d{1,3}(?:[,.\u{a0}\u{202f} ]\d{3})+(?:[.,]\d{1,2})?|\d+(?:[.,]\d{1,2})?").unwrap()
+});
+static CART_TEXT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)add to (cart|bag|basket|trolley)|buy (it )?now").unwrap());
+
+// * Symbols checked longest first so "US$" is not read as "$"
+const CURRENCY_SYMBOLS: &[(&str, &str)] = &[
+    ("US$", "USD"),
+    ("CA$", "CAD"),
+    ("AU$", "AUD"),
+    ("C$", "CAD"),
+    ("A$", "AUD"),
+    ("R$", "BRL"),
+    ("$", "USD"),
+    ("€", "EUR"),
+    ("£", "GBP"),
+    ("¥", "JPY"),
+    ("₹", "INR"),
+    ("₩", "KRW"),
+    ("zł", "PLN"),
+];
+const CURRENCY_CODES: &[&str] = &[
+    "USD", "EUR", "GBP", "JPY", "CAD", "AUD", "CHF", "CNY", "INR", "SEK", "NOK", "DKK", "PLN", "BRL", "MXN", "KRW",
+];
+
+/// Stock status, following schema.org ItemAvailability
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Availability {
+    InStock,
+    OutOfStock,
+    PreOrder,
+    BackOrder,
+    LimitedAvailability,
+    Discontinued,
+}
+
+impl Availability {
+    /// Reads a schema.org URL ("https://schema.org/InStock") or a label ("in stock", "oos")
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.rsplit('/').next().unwrap_or(value);
+        let key: String = value.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase();
+        match key.as_str() {
+            "instock" | "available" | "onlineonly" | "instoreonly" => Some(Self::InStock),
+            "outofstock" | "soldout" | "oos" | "unavailable" => Some(Self::OutOfStock),
+            "preorder" | "presale" => Some(Self::PreOrder),
+            "backorder" => Some(Self::BackOrder),
+            "limitedavailability" => Some(Self::LimitedAvailability),
+            "discontinued" => Some(Self::Discontinued),
+            _ => None,
+        }
+    }
+}
+
+/// Where a product's data came from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProductSource {
+    #[default]
+    JsonLd,
+    Microdata,
+    Dom,
+}
+
+/// Product facts read from a page
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProductInfo {
+    pub name: Option<String>,
+    /// Current price (the lowest offer for aggregate offers)
+    pub price: Option<f64>,
+    /// ISO 4217 code
+    pub currency: Option<String>,
+    pub availability: Option<Availability>,
+    pub sku: Option<String>,
+    pub brand: Option<String>,
+    /// Average rating on the page's own scale (usually 0-5)
+    pub rating: Option<f32>,
+    pub review_count: Option<u32>,
+    /// Source that identified the page as a product; later sources only fill gaps
+    pub source: ProductSource,
+}
+
+impl ProductInfo {
+    /// Returns the product on a page, or None when nothing marks it as a product page
+    pub fn extract(html: &str) -> Option<Self> {
+        let document = Html::parse_document(html);
+        let candidates = [from_json_ld(&document), from_microdata(&document), from_dom(&document)];
+        let mut found = candidates.into_iter().flatten();
+        let mut product = found.next()?;
+        for other in found {
+            product.fill_from(other);
+        }
+        Some(product)
+    }
+
+    fn fill_from(&mut self, other: ProductInfo) {
+        self.name = self.name.take().or(other.name);
+        // * A currency belongs with its price
+        if self.price.is_none() && other.price.is_some() {
+            self.price = other.price;
+            self.currency = other.currency;
+        } else {
+            self.currency = self.currency.take().or(other.currency);
+        }
+        self.availability = self.availability.or(other.availability);
+        self.sku = self.sku.take().or(other.sku);
+        self.brand = self.brand.take().or(other.brand);
+        self.rating = self.rating.or(other.rating);
+        self.review_count = self.review_count.or(other.review_count);
+    }
+}
+
+/// Reads the first amount in a price string and the currency written next to it
+///
+/// "1.299,00 €" and "€1,299.00" both read as 1299.0 EUR; a single separator followed by
+/// exactly three digits is a thousands separator.
+pub fn parse_price(text: &str) -> Option<(f64, Option<String>)> {
+    let found = PRICE_NUMBER.find(text)?;
+    let digits = found.as_str();
+    let normalized = match digits.rfind(['.', ',']) {
+        Some(at) if digits.len() - at - 1 <= 2 => {
+            let (whole, fraction) = digits.split_at(at);
+            format!("{}.{}", whole.replace(|c: char| !c.is_ascii_digit(), ""), &fraction[1..])
+        }
+        _ => digits.replace(|c: char| !c.is_ascii_digit(), ""),
+    };
+    let price = normalized.parse().ok()?;
+    Some((price, currency_in(text)))
+}
+
+fn currency_in(text: &str) -> Option<String> {
+    let upper = text.to_uppercase();
+    if let Some(code) = CURRENCY_CODES.iter().find(|code| {
+        upper.match_indices(*code).any(|(at, _)| {
+            let before = upper[..at].chars().next_back();
+            let after = upper[at + code.len()..].chars().next();
+            !before.is_some_and(char::is_alphabetic) && !after.is_some_and(char::is_alphabetic)
+        })
+    }) {
+        return Some(code.to_string());
+    }
+    CURRENCY_SYMBOLS
+        .iter()
+        .find(|(symbol, _)| text.contains(symbol))
+        .map(|(_, code)| code.to_string())
+}
+
+fn is_product_type(value: &str) -> bool {
+    let name = value.rsplit(['/', ':', '#']).next().unwrap_or(value);
+    matches!(name, "Product" | "IndividualProduct" | "ProductModel" | "ProductGroup" | "Vehicle" | "Car")
+}
+
+fn from_json_ld(document: &Html) -> Option<ProductInfo> {
+    document.select(&SELECTOR_JSON_LD).find_map(|script| {
+        let json: Value = serde_json::from_str(script.text().collect::<String>().trim()).ok()?;
+        find_product_node(&json).map(product_from_node)
+    })
+}
+
+// * Products sit at the top level, in @graph, or under mainEntity / itemListElement
+fn find_product_node(value: &Value) -> Option<&Value> {
+    match value {
+        Value::Array(items) => items.iter().find_map(find_product_node),
+        Value::Object(map) => {
+            let is_product = match map.get("@type") {
+                Some(Value::String(t)) => is_product_type(t),
+                Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).any(is_product_type),
+                _ => false,
+            };
+            if is_product {
+                return Some(value);
+            }
+            map.iter()
+                .filter(|(key, _)| !matches!(key.as_str(), "offers" | "review" | "aggregateRating"))
+                .find_map(|(_, nested)| find_product_node(nested))
+        }
+        _ => None,
+    }
+}
+
+fn product_from_node(node: &Value) -> ProductInfo {
+    let mut product = ProductInfo {
+        name: json_text(node.get("name")),
+        sku: json_text(node.get("sku")).or_else(|| json_text(node.get("productID"))),
+        brand: node.get("brand").and_then(|brand| match brand {
+            Value::Array(brands) => brands.first().and_then(named),
+            other => named(other),
+        }),
+        source: ProductSource::JsonLd,
+        ..Default::default()
+    };
+
+    let offer = match node.get("offers").or_else(|| node.get("hasVariant").and_then(|v| v.get(0)?.get("offers"))) {
+        Some(Value::Array(offers)) => offers.first(),
+        other => other,
+    };
+    if let Some(offer) = offer {
+        let price = offer
+            .get("price")
+            .or_else(|| offer.get("lowPrice"))
+            .or_else(|| offer.get("priceSpecification").and_then(|spec| spec.get("price")));
+        if let Some((amount, currency)) = json_text(price).as_deref().and_then(parse_price) {
+            product.price = Some(amount);
+            product.currency = json_text(offer.get("priceCurrency"))
+                .or_else(|| json_text(offer.get("priceSpecification").and_then(|spec| spec.get("priceCurrency"))))
+                .or(currency);
+        }
+        product.availability = json_text(offer.get("availability")).as_deref().and_then(Availability::parse);
+    }
+
+    if let Some(rating) = node.get("aggregateRating") {
+        product.rating = json_text(rating.get("ratingValue")).and_then(|value| parse_number(&value));
+        product.review_count = json_text(rating.get("reviewCount"))
+            .or_else(|| json_text(rating.get("ratingCount")))
+            .and_then(|value| parse_number(&value))
+            .map(|count: f64| count as u32);
+    }
+    product
+}
+
+/// A string or number value as text
+fn json_text(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::String(text) => Some(text.trim().to_string()).filter(|text| !text.is_empty()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+// * Brands are a plain name or a Brand/Organization object
+fn named(value: &Value) -> Option<String> {
+    json_text(Some(value)).or_else(|| json_text(value.get("name")))
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Option<T> {
+    value.trim().replace(',', ".").parse().ok()
+}
+
+fn from_microdata(document: &Html) -> Option<ProductInfo> {
+    let scope = document.select(&SELECTOR_ITEMSCOPE).find(|scope| {
+        scope
+            .value()
+            .attr("itemtype")
+            .is_some_and(|types| types.split_whitespace().any(is_product_type))
+    })?;
+
+    let prop = |name: &str| -> Option<String> {
+        let mut matches = scope.select(&SELECTOR_ITEMPROP).filter(|element| {
+            element
+                .value()
+                .attr("itemprop")
+                .is_some_and(|props| props.split_whitespace().any(|prop| prop == name))
+        });
+        // * Prefer the product's own property over one of a nested item (a brand's name)
+        let own = matches.clone().find(|element| nearest_scope(*element).is_some_and(|s| s.id() == scope.id()));
+        own.or_else(|| matches.next()).and_then(microdata_value)
+    };
+
+    let (price, currency) = match prop("price").as_deref().and_then(parse_price) {
+        Some((amount, currency)) => (Some(amount), prop("priceCurrency").or(currency)),
+        None => (None, prop("priceCurrency")),
+    };
+    Some(ProductInfo {
+        name: prop("name"),
+        price,
+        currency,
+        availability: prop("availability").as_deref().and_then(Availability::parse),
+        sku: prop("sku").or_else(|| prop("productID")),
+        brand: prop("brand"),
+        rating: prop("ratingValue").and_then(|value| parse_number(&value)),
+        review_count: prop("reviewCount")
+            .or_else(|| prop("ratingCount"))
+            .and_then(|value| parse_number(&value))
+            .map(|count: f64| count as u32),
+        source: ProductSource::Microdata,
+    })
+}
+
+// * The closest itemscope above an itemprop element (not counting the element itself)
+fn nearest_scope(element: ElementRef) -> Option<ElementRef> {
+    element
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .find(|ancestor| ancestor.value().attr("itemscope").is_some())
+}
+
+fn microdata_value(element: ElementRef) -> Option<String> {
+    let value = element.value();
+    let raw = value
+        .attr("content")
+        .or_else(|| value.attr("href"))
+        .or_else(|| value.attr("datetime"))
+        .map(str::to_string)
+        .unwrap_or_else(|| element.text().collect::<Vec<_>>().join(" "));
+    let text = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+fn from_dom(document: &Html) -> Option<ProductInfo> {
+    let meta = |names: &[&str]| -> Option<String> {
+        document.select(&SELECTOR_META).find_map(|element| {
+            let key = element.value().attr("property").or_else(|| element.value().attr("name"))?;
+            names
+                .iter()
+                .any(|name| key.eq_ignore_ascii_case(name))
+                .then(|| element.value().attr("content").unwrap_or_default().trim().to_string())
+                .filter(|content| !content.is_empty())
+        })
+    };
+    let is_product_page = meta(&["og:type"]).is_some_and(|kind| kind.to_lowercase().contains("product"))
+        || document.select(&SELECTOR_CART).any(|element| {
+            let value = element.value();
+            value.name() == "form"
+                || value.attr("class").is_some_and(|class| class.contains("add-to-cart"))
+                || CART_TEXT.is_match(&element.text().collect::<String>())
+                || value.attr("value").is_some_and(|text| CART_TEXT.is_match(text))
+        });
+
+    let meta_price = meta(&["product:price:amount", "og:price:amount"]).and_then(|amount| parse_price(&amount));
+    let (price, currency) = match meta_price {
+        Some((amount, currency)) => (Some(amount), meta(&["product:price:currency", "og:price:currency"]).or(currency)),
+        None if is_product_page => match document
+            .select(&SELECTOR_PRICE)
+            .filter_map(|element| {
+                let text = element.value().attr("data-price").map(str::to_string).unwrap_or_else(|| element.text().collect());
+                parse_price(&text).filter(|(_, currency)| currency.is_some())
+            })
+            .next()
+        {
+            Some((amount, currency)) => (Some(amount), currency),
+            None => (None, None),
+        },
+        None => (None, None),
+    };
+    if price.is_none() && !is_product_page {
+        return None;
+    }
+
+    Some(ProductInfo {
+        name: meta(&["og:title"]).or_else(|| {
+            document.select(&SELECTOR_H1).next().map(|h1| h1.text().collect::<Vec<_>>().join(" ").trim().to_string())
+        }),
+        price,
+        currency,
+        availability: meta(&["product:availability", "og:availability"]).as_deref().and_then(Availability::parse),
+        sku: meta(&["product:retailer_item_id", "product:sku"]),
+        brand: meta(&["product:brand", "og:brand"]),
+        rating: None,
+        review_count: None,
+        source: ProductSource::Dom,
+    })
+    .filter(|product| product.price.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_ld_product_in_graph() {
+        let html = r#"<html><head><script type="application/ld+json">{"@context": "https://schema.org", "@graph": [
+            {"@type": "WebPage", "name": "Trail Shoe | Shop"},
+            {"@type": ["Product"], "name": "Trail Runner 3", "sku": 88412,
+             "brand": {"@type": "Brand", "name": "Ridgeline"},
+             "offers": [{"@type": "AggregateOffer", "lowPrice": "89.95", "priceCurrency": "EUR",
+                         "availability": "https://schema.org/InStock"}],
+             "aggregateRating": {"ratingValue": "4.6", "reviewCount": 213}}
+        ]}</script></head><body><h1>Trail Runner 3</h1></body></html>"#;
+
+        let product = ProductInfo::extract(html).unwrap();
+        assert_eq!(
+            product,
+            ProductInfo {
+                name: Some("Trail Runner 3".to_string()),
+                price: Some(89.95),
+                currency: Some("EUR".to_string()),
+                availability: Some(Availability::InStock),
+                sku: Some("88412".to_string()),
+                brand: Some("Ridgeline".to_string()),
+                rating: Some(4.6),
+                review_count: Some(213),
+                source: ProductSource::JsonLd,
+            }
+        );
+    }
+
+    #[test]
+    fn test_microdata_product_fills_json_ld_gaps() {
+        let html = r#"<html><head><script type="application/ld+json">{"@type": "Product", "name": "Desk Lamp"}</script></head>
+        <body><div itemscope itemtype="https://schema.org/Product">
+            <span itemprop="name">Desk Lamp LX</span>
+            <div itemprop="brand" itemscope itemtype="https://schema.org/Brand"><span itemprop="name">Lumo</span></div>
+            <div itemprop="offers" itemscope itemtype="https://schema.org/Offer">
+                <span itemprop="price" content="1299.00">1.299,00 kr</span>
+                <meta itemprop="priceCurrency" content="SEK">
+                <link itemprop="availability" href="https://schema.org/OutOfStock">
+            </div>
+        </div></body></html>"#;
+
+        let product = ProductInfo::extract(html).unwrap();
+        assert_eq!(product.source, ProductSource::JsonLd);
+        assert_eq!(product.name.as_deref(), Some("Desk Lamp"));
+        assert_eq!(product.brand.as_deref(), Some("Lumo"));
+        assert_eq!(product.price, Some(1299.0));
+        assert_eq!(product.currency.as_deref(), Some("SEK"));
+        assert_eq!(product.availability, Some(Availability::OutOfStock));
+    }
+
+    #[test]
+    fn test_dom_patterns_need_product_evidence() {
+        let shop = r#"<html><body><h1>Ceramic Mug</h1><p class="product-price">£12.50</p>
+            <button>Add to basket</button></body></html>"#;
+        let product = ProductInfo::extract(shop).unwrap();
+        assert_eq!(product.source, ProductSource::Dom);
+        assert_eq!(product.name.as_deref(), Some("Ceramic Mug"));
+        assert_eq!((product.price, product.currency.as_deref()), (Some(12.5), Some("GBP")));
+
+        let tagged = r#"<html><head><meta property="og:type" content="product">
+            <meta property="product:price:amount" content="24"><meta property="product:price:currency" content="USD">
+            <meta property="product:availability" content="preorder"></head><body></body></html>"#;
+        let product = ProductInfo::extract(tagged).unwrap();
+        assert_eq!((product.price, product.availability), (Some(24.0), Some(Availability::PreOrder)));
+
+        // * A price on an article is not a product
+        let article = r#"<html><body><h1>Coffee prices rise</h1><p class="price-note">Now $4.50 a cup.</p></body></html>"#;
+        assert_eq!(ProductInfo::extract(article), None);
+    }
+
+    #[test]
+    fn test_parse_price_formats() {
+        assert_eq!(parse_price("$1,299.99"), Some((1299.99, Some("USD".to_string()))));
+        assert_eq!(parse_price("1.299,00 €"), Some((1299.0, Some("EUR".to_string()))));
+        assert_eq!(parse_price("CHF 2 450"), Some((2450.0, Some("CHF".to_string()))));
+        assert_eq!(parse_price("US$ 15"), Some((15.0, Some("USD".to_string()))));
+        assert_eq!(parse_price("19.5"), Some((19.5, None)));
+        assert_eq!(parse_price("free"), None);
+    }
+}
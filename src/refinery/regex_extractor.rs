@@ -60,6 +60,41 @@ static PATTERN_CURRENCY: LazyLock<Regex> = LazyLock::new(|| {
         .expect("Invalid currency regex")
 });
 
+// * Investigation patterns (network identifiers, banking, crypto wallets)
+// * IPv6 alternatives are ordered so the longest compressed form wins under leftmost-first matching
+static PATTERN_IPV6: LazyLock<Regex> = LazyLock::new(|| {
+    const H: &str = "[0-9a-fA-F]{1,4}";
+    let pattern = [
+        format!(r"\b(?:{H}:){{7}}{H}\b"),
+        format!(r"\b{H}:(?::{H}){{1,6}}\b"),
+        format!(r"\b(?:{H}:){{1,2}}(?::{H}){{1,5}}\b"),
+        format!(r"\b(?:{H}:){{1,3}}(?::{H}){{1,4}}\b"),
+        format!(r"\b(?:{H}:){{1,4}}(?::{H}){{1,3}}\b"),
+        format!(r"\b(?:{H}:){{1,5}}(?::{H}){{1,2}}\b"),
+        format!(r"\b(?:{H}:){{1,6}}:{H}\b"),
+        format!(r"::(?:{H}:){{0,6}}{H}\b"),
+    ]
+    .join("|");
+    Regex::new(&pattern).expect("Invalid IPv6 regex")
+});
+
+static PATTERN_MAC: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?:[0-9A-Fa-f]{2}:){5}[0-9A-Fa-f]{2}\b|\b(?:[0-9A-Fa-f]{2}-){5}[0-9A-Fa-f]{2}\b")
+        .expect("Invalid MAC address regex")
+});
+
+static PATTERN_IBAN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b[A-Z]{2}[0-9]{2}(?: ?[A-Z0-9]{4}){2,7}(?: ?[A-Z0-9]{1,3})?\b").expect("Invalid IBAN regex")
+});
+
+static PATTERN_BTC_ADDRESS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?:[13][a-km-zA-HJ-NP-Z1-9]{25,34}|bc1[ac-hj-np-z02-9]{11,71})\b")
+        .expect("Invalid BTC address regex")
+});
+
+static PATTERN_ETH_ADDRESS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b0x[a-fA-F0-9]{40}\b").expect("Invalid ETH address regex"));
+
 /// Represents the type of entity extracted
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EntityType {
@@ -70,14 +105,40 @@ pub enum EntityType {
     PhoneUs,
     PhoneIntl,
     Ipv4,
+    Ipv6,
+    MacAddress,
     CreditCard,
     Ssn,
+    Iban,
     Hashtag,
     Mention,
     Currency,
+    BtcAddress,
+    EthAddress,
 }
 
 impl EntityType {
+    /// Every supported entity type, in extraction order
+    pub const ALL: [EntityType; 17] = [
+        EntityType::Email,
+        EntityType::Url,
+        EntityType::Uuid,
+        EntityType::DateIso,
+        EntityType::PhoneUs,
+        EntityType::PhoneIntl,
+        EntityType::Ipv4,
+        EntityType::Ipv6,
+        EntityType::MacAddress,
+        EntityType::CreditCard,
+        EntityType::Ssn,
+        EntityType::Iban,
+        EntityType::Hashtag,
+        EntityType::Mention,
+        EntityType::Currency,
+        EntityType::BtcAddress,
+        EntityType::EthAddress,
+    ];
+
     /// Returns the string representation for JSON output
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -88,17 +149,80 @@ impl EntityType {
             EntityType::PhoneUs => "phone_us",
             EntityType::PhoneIntl => "phone_intl",
             EntityType::Ipv4 => "ipv4",
+            EntityType::Ipv6 => "ipv6",
+            EntityType::MacAddress => "mac_address",
             EntityType::CreditCard => "credit_card",
             EntityType::Ssn => "ssn",
+            EntityType::Iban => "iban",
             EntityType::Hashtag => "hashtag",
             EntityType::Mention => "mention",
             EntityType::Currency => "currency",
+            EntityType::BtcAddress => "btc_address",
+            EntityType::EthAddress => "eth_address",
         }
     }
+
+    /// Returns the precompiled pattern for this entity type
+    fn pattern(&self) -> &'static Regex {
+        match self {
+            EntityType::Email => &PATTERN_EMAIL,
+            EntityType::Url => &PATTERN_URL,
+            EntityType::Uuid => &PATTERN_UUID,
+            EntityType::DateIso => &PATTERN_DATE_ISO,
+            EntityType::PhoneUs => &PATTERN_PHONE_US,
+            EntityType::PhoneIntl => &PATTERN_PHONE_INTL,
+            EntityType::Ipv4 => &PATTERN_IPV4,
+            EntityType::Ipv6 => &PATTERN_IPV6,
+            EntityType::MacAddress => &PATTERN_MAC,
+            EntityType::CreditCard => &PATTERN_CREDIT_CARD,
+            EntityType::Ssn => &PATTERN_SSN,
+            EntityType::Iban => &PATTERN_IBAN,
+            EntityType::Hashtag => &PATTERN_HASHTAG,
+            EntityType::Mention => &PATTERN_MENTION,
+            EntityType::Currency => &PATTERN_CURRENCY,
+            EntityType::BtcAddress => &PATTERN_BTC_ADDRESS,
+            EntityType::EthAddress => &PATTERN_ETH_ADDRESS,
+        }
+    }
+
+    /// Post-match validation for types whose regex alone is too permissive
+    fn is_valid_match(&self, candidate: &str) -> bool {
+        match self {
+            EntityType::Iban => is_valid_iban(candidate),
+            _ => true,
+        }
+    }
+}
+
+/// Validates an IBAN using the ISO 13616 mod-97 checksum
+fn is_valid_iban(candidate: &str) -> bool {
+    let compact: String = candidate.chars().filter(|c| !c.is_whitespace()).collect();
+    // * Byte lengths and slicing below are only character-accurate for ASCII
+    if !compact.is_ascii() || !(15..=34).contains(&compact.len()) {
+        return false;
+    }
+
+    // * Move country code + check digits to the end, map letters to 10..35
+    let rearranged = compact[4..].chars().chain(compact[..4].chars());
+    let mut remainder: u32 = 0;
+    for c in rearranged {
+        let value = match c.to_digit(36) {
+            Some(v) => v,
+            None => return false,
+        };
+        remainder = if value < 10 {
+            (remainder * 10 + value) % 97
+        } else {
+            (remainder * 100 + value) % 97
+        };
+    }
+
+    remainder == 1
 }
 
 /// Configuration for which entity types to extract
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ExtractorConfig {
     pub extract_emails: bool,
     pub extract_urls: bool,
@@ -109,6 +233,9 @@ pub struct ExtractorConfig {
     pub extract_pii: bool,
     pub extract_social: bool,
     pub extract_currency: bool,
+    pub extract_mac_addresses: bool,
+    pub extract_ibans: bool,
+    pub extract_crypto_wallets: bool,
 }
 
 impl Default for ExtractorConfig {
@@ -123,6 +250,64 @@ impl Default for ExtractorConfig {
             extract_pii: false, // ! Disabled by default for privacy
             extract_social: true,
             extract_currency: true,
+            extract_mac_addresses: false,
+            extract_ibans: false,
+            extract_crypto_wallets: false,
+        }
+    }
+}
+
+impl ExtractorConfig {
+    /// Returns whether the given entity type is enabled by this configuration
+    pub fn is_enabled(&self, entity_type: EntityType) -> bool {
+        match entity_type {
+            EntityType::Email => self.extract_emails,
+            EntityType::Url => self.extract_urls,
+            EntityType::Uuid => self.extract_uuids,
+            EntityType::DateIso => self.extract_dates,
+            EntityType::PhoneUs | EntityType::PhoneIntl => self.extract_phones,
+            EntityType::Ipv4 | EntityType::Ipv6 => self.extract_ips,
+            EntityType::MacAddress => self.extract_mac_addresses,
+            EntityType::CreditCard | EntityType::Ssn => self.extract_pii,
+            EntityType::Iban => self.extract_ibans,
+            EntityType::Hashtag | EntityType::Mention => self.extract_social,
+            EntityType::Currency => self.extract_currency,
+            EntityType::BtcAddress | EntityType::EthAddress => self.extract_crypto_wallets,
+        }
+    }
+}
+
+/// Policy controlling which PII entity types are replaced during redaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RedactionPolicy {
+    pub redact_emails: bool,
+    pub redact_phones: bool,
+    pub redact_ssns: bool,
+    pub redact_credit_cards: bool,
+}
+
+impl Default for RedactionPolicy {
+    fn default() -> Self {
+        // ! Redact everything by default - raw PII must never reach the vector store
+        Self {
+            redact_emails: true,
+            redact_phones: true,
+            redact_ssns: true,
+            redact_credit_cards: true,
+        }
+    }
+}
+
+impl RedactionPolicy {
+    /// Returns the placeholder substituted for a redacted entity type
+    pub fn placeholder(entity_type: EntityType) -> &'static str {
+        match entity_type {
+            EntityType::Email => "[EMAIL]",
+            EntityType::PhoneUs | EntityType::PhoneIntl => "[PHONE]",
+            EntityType::Ssn => "[SSN]",
+            EntityType::CreditCard => "[CREDIT_CARD]",
+            _ => "[REDACTED]",
         }
     }
 }
@@ -164,11 +349,14 @@ impl RegexExtractor {
         Self { config }
     }
 
-    /// Extracts all unique matches for a given pattern
-    fn extract_pattern(pattern: &Regex, text: &str) -> Vec<String> {
-        pattern
+    /// Extracts all unique, validated matches for a given entity type
+    fn extract_entity(entity_type: EntityType, text: &str) -> Vec<String> {
+        entity_type
+            .pattern()
             .find_iter(text)
-            .map(|m| m.as_str().to_string())
+            .map(|m| m.as_str())
+            .filter(|m| entity_type.is_valid_match(m))
+            .map(|m| m.to_string())
             .collect::<std::collections::HashSet<_>>()
             .into_iter()
             .collect()
@@ -176,109 +364,49 @@ impl RegexExtractor {
 
     /// Extracts all configured entity types from the given text
     pub fn extract(&self, text: &str) -> ExtractionResult {
-        let mut entities: HashMap<String, Vec<String>> = HashMap::new();
-        let mut total_count = 0;
-
-        // * Core entities (always extracted based on config)
-        if self.config.extract_emails {
-            let matches = Self::extract_pattern(&PATTERN_EMAIL, text);
-            if !matches.is_empty() {
-                total_count += matches.len();
-                entities.insert(EntityType::Email.as_str().to_string(), matches);
-            }
-        }
-
-        if self.config.extract_urls {
-            let matches = Self::extract_pattern(&PATTERN_URL, text);
-            if !matches.is_empty() {
-                total_count += matches.len();
-                entities.insert(EntityType::Url.as_str().to_string(), matches);
-            }
-        }
-
-        if self.config.extract_uuids {
-            let matches = Self::extract_pattern(&PATTERN_UUID, text);
-            if !matches.is_empty() {
-                total_count += matches.len();
-                entities.insert(EntityType::Uuid.as_str().to_string(), matches);
-            }
-        }
-
-        if self.config.extract_dates {
-            let matches = Self::extract_pattern(&PATTERN_DATE_ISO, text);
-            if !matches.is_empty() {
-                total_count += matches.len();
-                entities.insert(EntityType::DateIso.as_str().to_string(), matches);
-            }
-        }
-
-        // * Phone numbers
-        if self.config.extract_phones {
-            let us_phones = Self::extract_pattern(&PATTERN_PHONE_US, text);
-            if !us_phones.is_empty() {
-                total_count += us_phones.len();
-                entities.insert(EntityType::PhoneUs.as_str().to_string(), us_phones);
-            }
+        let enabled: Vec<EntityType> = EntityType::ALL
+            .into_iter()
+            .filter(|t| self.config.is_enabled(*t))
+            .collect();
 
-            let intl_phones = Self::extract_pattern(&PATTERN_PHONE_INTL, text);
-            if !intl_phones.is_empty() {
-                total_count += intl_phones.len();
-                entities.insert(EntityType::PhoneIntl.as_str().to_string(), intl_phones);
-            }
-        }
+        self.extract_specific(text, &enabled)
+    }
 
-        // * IP addresses
-        if self.config.extract_ips {
-            let matches = Self::extract_pattern(&PATTERN_IPV4, text);
-            if !matches.is_empty() {
-                total_count += matches.len();
-                entities.insert(EntityType::Ipv4.as_str().to_string(), matches);
-            }
+    /// Replaces detected PII in `text` with placeholders according to `policy`
+    ///
+    /// Credit cards and SSNs are replaced before phone numbers so that long
+    /// digit runs are not partially consumed by the looser phone patterns.
+    pub fn redact(text: &str, policy: RedactionPolicy) -> String {
+        let mut redacted = text.to_string();
+
+        if policy.redact_emails {
+            redacted = PATTERN_EMAIL
+                .replace_all(&redacted, RedactionPolicy::placeholder(EntityType::Email))
+                .into_owned();
         }
 
-        // ! PII extraction (disabled by default)
-        if self.config.extract_pii {
-            let cc_matches = Self::extract_pattern(&PATTERN_CREDIT_CARD, text);
-            if !cc_matches.is_empty() {
-                total_count += cc_matches.len();
-                entities.insert(EntityType::CreditCard.as_str().to_string(), cc_matches);
-            }
-
-            let ssn_matches = Self::extract_pattern(&PATTERN_SSN, text);
-            if !ssn_matches.is_empty() {
-                total_count += ssn_matches.len();
-                entities.insert(EntityType::Ssn.as_str().to_string(), ssn_matches);
-            }
+        if policy.redact_credit_cards {
+            redacted = PATTERN_CREDIT_CARD
+                .replace_all(&redacted, RedactionPolicy::placeholder(EntityType::CreditCard))
+                .into_owned();
         }
 
-        // * Social media entities
-        if self.config.extract_social {
-            let hashtags = Self::extract_pattern(&PATTERN_HASHTAG, text);
-            if !hashtags.is_empty() {
-                total_count += hashtags.len();
-                entities.insert(EntityType::Hashtag.as_str().to_string(), hashtags);
-            }
-
-            let mentions = Self::extract_pattern(&PATTERN_MENTION, text);
-            if !mentions.is_empty() {
-                total_count += mentions.len();
-                entities.insert(EntityType::Mention.as_str().to_string(), mentions);
-            }
+        if policy.redact_ssns {
+            redacted = PATTERN_SSN
+                .replace_all(&redacted, RedactionPolicy::placeholder(EntityType::Ssn))
+                .into_owned();
         }
 
-        // * Currency values
-        if self.config.extract_currency {
-            let matches = Self::extract_pattern(&PATTERN_CURRENCY, text);
-            if !matches.is_empty() {
-                total_count += matches.len();
-                entities.insert(EntityType::Currency.as_str().to_string(), matches);
-            }
+        if policy.redact_phones {
+            redacted = PATTERN_PHONE_US
+                .replace_all(&redacted, RedactionPolicy::placeholder(EntityType::PhoneUs))
+                .into_owned();
+            redacted = PATTERN_PHONE_INTL
+                .replace_all(&redacted, RedactionPolicy::placeholder(EntityType::PhoneIntl))
+                .into_owned();
         }
 
-        ExtractionResult {
-            entities,
-            total_count,
-        }
+        redacted
     }
 
     /// Extracts only the specified entity types
@@ -287,20 +415,7 @@ impl RegexExtractor {
         let mut total_count = 0;
 
         for entity_type in types {
-            let matches = match entity_type {
-                EntityType::Email => Self::extract_pattern(&PATTERN_EMAIL, text),
-                EntityType::Url => Self::extract_pattern(&PATTERN_URL, text),
-                EntityType::Uuid => Self::extract_pattern(&PATTERN_UUID, text),
-                EntityType::DateIso => Self::extract_pattern(&PATTERN_DATE_ISO, text),
-                EntityType::PhoneUs => Self::extract_pattern(&PATTERN_PHONE_US, text),
-                EntityType::PhoneIntl => Self::extract_pattern(&PATTERN_PHONE_INTL, text),
-                EntityType::Ipv4 => Self::extract_pattern(&PATTERN_IPV4, text),
-                EntityType::CreditCard => Self::extract_pattern(&PATTERN_CREDIT_CARD, text),
-                EntityType::Ssn => Self::extract_pattern(&PATTERN_SSN, text),
-                EntityType::Hashtag => Self::extract_pattern(&PATTERN_HASHTAG, text),
-                EntityType::Mention => Self::extract_pattern(&PATTERN_MENTION, text),
-                EntityType::Currency => Self::extract_pattern(&PATTERN_CURRENCY, text),
-            };
+            let matches = Self::extract_entity(*entity_type, text);
 
             if !matches.is_empty() {
                 total_count += matches.len();
@@ -464,4 +579,140 @@ mod tests {
         assert!(result.entities.contains_key("ssn"));
         assert!(result.entities.contains_key("credit_card"));
     }
+
+    #[test]
+    fn test_redact_all_pii() {
+        let text = "Mail jane@example.com, call (555) 123-4567, SSN 123-45-6789, card 4111111111111111.";
+
+        let redacted = RegexExtractor::redact(text, RedactionPolicy::default());
+
+        assert_eq!(
+            redacted,
+            "Mail [EMAIL], call [PHONE], SSN [SSN], card [CREDIT_CARD]."
+        );
+    }
+
+    #[test]
+    fn test_redact_respects_policy() {
+        let policy = RedactionPolicy {
+            redact_emails: false,
+            ..Default::default()
+        };
+        let text = "Reach jane@example.com or +447911123456";
+
+        let redacted = RegexExtractor::redact(text, policy);
+
+        assert!(redacted.contains("jane@example.com"));
+        assert!(redacted.contains("[PHONE]"));
+        assert!(!redacted.contains("447911123456"));
+    }
+
+    #[test]
+    fn test_redact_leaves_clean_text_untouched() {
+        let text = "Nothing sensitive here, just an article about Rust.";
+
+        assert_eq!(RegexExtractor::redact(text, RedactionPolicy::default()), text);
+    }
+
+    fn investigation_extractor() -> RegexExtractor {
+        RegexExtractor::with_config(ExtractorConfig {
+            extract_ips: true,
+            extract_mac_addresses: true,
+            extract_ibans: true,
+            extract_crypto_wallets: true,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_extract_ipv6() {
+        let extractor = investigation_extractor();
+        let text = "Hosts 2001:0db8:85a3:0000:0000:8a2e:0370:7334, fe80::1 and 2001:db8:85a3::8a2e:370:7334 plus ::1";
+
+        let result = extractor.extract(text);
+        let ips = result.entities.get("ipv6").unwrap();
+
+        assert!(ips.contains(&"2001:0db8:85a3:0000:0000:8a2e:0370:7334".to_string()));
+        assert!(ips.contains(&"fe80::1".to_string()));
+        assert!(ips.contains(&"2001:db8:85a3::8a2e:370:7334".to_string()));
+        assert!(ips.contains(&"::1".to_string()));
+    }
+
+    #[test]
+    fn test_ipv6_ignores_times_and_paths() {
+        let extractor = investigation_extractor();
+        let text = "Meeting at 10:30:45, see std::vec::Vec and Add::new";
+
+        let result = extractor.extract(text);
+
+        assert!(!result.entities.contains_key("ipv6"));
+    }
+
+    #[test]
+    fn test_extract_mac_addresses() {
+        let extractor = investigation_extractor();
+        let text = "Devices 00:1A:2B:3C:4D:5E and 00-1a-2b-3c-4d-5f were seen";
+
+        let result = extractor.extract(text);
+        let macs = result.entities.get("mac_address").unwrap();
+
+        assert_eq!(macs.len(), 2);
+        assert!(!result.entities.contains_key("ipv6"));
+    }
+
+    #[test]
+    fn test_extract_iban_with_checksum() {
+        let extractor = investigation_extractor();
+        let text = "Pay GB82 WEST 1234 5698 7654 32 or DE89370400440532013000, not GB00WEST12345698765432";
+
+        let result = extractor.extract(text);
+        let ibans = result.entities.get("iban").unwrap();
+
+        assert_eq!(ibans.len(), 2);
+        assert!(ibans.contains(&"GB82 WEST 1234 5698 7654 32".to_string()));
+        assert!(ibans.contains(&"DE89370400440532013000".to_string()));
+    }
+
+    #[test]
+    fn test_iban_rejects_non_ascii_digits() {
+        let extractor = investigation_extractor();
+
+        let result = extractor.extract("Konto DE४४ 1234 5678 9012 3456");
+
+        assert!(!result.entities.contains_key("iban"));
+        assert!(!is_valid_iban("DE४४ 1234 5678 9012 3456"));
+    }
+
+    #[test]
+    fn test_extract_crypto_wallets() {
+        let extractor = investigation_extractor();
+        let text = "Send to 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa, bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq or 0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+
+        let result = extractor.extract(text);
+
+        let btc = result.entities.get("btc_address").unwrap();
+        assert_eq!(btc.len(), 2);
+        let eth = result.entities.get("eth_address").unwrap();
+        assert_eq!(eth, &vec!["0x742d35Cc6634C0532925a3b844Bc454e4438f44e".to_string()]);
+    }
+
+    #[test]
+    fn test_investigation_types_disabled_by_default() {
+        let extractor = RegexExtractor::new();
+        let text = "fe80::1 00:1A:2B:3C:4D:5E DE89370400440532013000 0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+
+        let result = extractor.extract(text);
+
+        assert!(!result.entities.contains_key("ipv6"));
+        assert!(!result.entities.contains_key("mac_address"));
+        assert!(!result.entities.contains_key("iban"));
+        assert!(!result.entities.contains_key("eth_address"));
+    }
+
+    #[test]
+    fn test_all_entity_types_have_unique_names() {
+        let names: std::collections::HashSet<_> =
+            EntityType::ALL.iter().map(|t| t.as_str()).collect();
+        assert_eq!(names.len(), EntityType::ALL.len());
+    }
 }
@@ -0,0 +1,165 @@
+// * Sanitized HTML output for the content cleaner
+// * Keeps structural markup (paragraphs, headings, lists, tables, code, quotes, links, images)
+// * and drops everything else; attributes are allow-listed per tag and URLs must be safe.
+
+use scraper::node::Node;
+use scraper::{ElementRef, Html};
+
+// * Tags kept in the output; table parts are kept so tables stay tables
+const ALLOWED_TAGS: &[&str] = &[
+    "p", "h1", "h2", "h3", "h4", "h5", "h6", "ul", "ol", "li", "table", "thead", "tbody", "tfoot",
+    "tr", "th", "td", "caption", "pre", "blockquote", "a", "img",
+];
+
+// * Tags dropped together with their content (other disallowed tags are unwrapped)
+const DROPPED_TAGS: &[&str] = &[
+    "script", "style", "noscript", "template", "iframe", "object", "embed", "form", "svg", "math",
+    "nav", "header", "footer", "aside", "button", "select", "textarea",
+];
+
+const VOID_TAGS: &[&str] = &["img"];
+
+// * URL schemes allowed in href/src; relative URLs are always allowed
+const SAFE_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Returns the allowed attributes of a tag
+fn allowed_attributes(tag: &str) -> &'static [&'static str] {
+    match tag {
+        "a" => &["href", "title"],
+        "img" => &["src", "alt", "title", "width", "height"],
+        "th" | "td" => &["colspan", "rowspan"],
+        "ol" => &["start"],
+        _ => &[],
+    }
+}
+
+/// Sanitizes an HTML fragment, keeping only safe structural markup
+pub fn sanitize_html(html: &str) -> String {
+    let fragment = Html::parse_fragment(html);
+    sanitize_element(fragment.root_element(), &|_| false)
+}
+
+/// Sanitizes the children of `root`, dropping elements for which `skip` returns true
+pub fn sanitize_element(root: ElementRef, skip: &dyn Fn(&ElementRef) -> bool) -> String {
+    let mut out = String::new();
+    write_children(root, skip, &mut out);
+    out.trim().to_string()
+}
+
+fn write_children(element: ElementRef, skip: &dyn Fn(&ElementRef) -> bool, out: &mut String) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => escape_into(text, out),
+            Node::Element(_) => {
+                if let Some(child) = ElementRef::wrap(child) {
+                    write_element(child, skip, out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn write_element(element: ElementRef, skip: &dyn Fn(&ElementRef) -> bool, out: &mut String) {
+    let tag = element.value().name();
+    if DROPPED_TAGS.contains(&tag) || skip(&element) {
+        return;
+    }
+    if !ALLOWED_TAGS.contains(&tag) {
+        write_children(element, skip, out);
+        return;
+    }
+
+    out.push('<');
+    out.push_str(tag);
+    for &name in allowed_attributes(tag) {
+        let Some(value) = element.value().attr(name) else {
+            continue;
+        };
+        if matches!(name, "href" | "src") && !is_safe_url(value) {
+            continue;
+        }
+        out.push(' ');
+        out.push_str(name);
+        out.push_str("=\"");
+        escape_into(value, out);
+        out.push('"');
+    }
+    out.push('>');
+
+    if VOID_TAGS.contains(&tag) {
+        return;
+    }
+    write_children(element, skip, out);
+    out.push_str("</");
+    out.push_str(tag);
+    out.push('>');
+}
+
+/// Returns true for relative URLs and absolute URLs with a safe scheme
+fn is_safe_url(url: &str) -> bool {
+    // * Browsers ignore whitespace and control characters inside the scheme ("java\tscript:")
+    let compact: String = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect();
+    match compact.split_once(':') {
+        Some((scheme, _)) if !scheme.contains(['/', '?', '#']) => {
+            SAFE_SCHEMES.contains(&scheme.to_ascii_lowercase().as_str())
+        }
+        _ => true,
+    }
+}
+
+pub(crate) fn escape_into(text: &str, out: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_structure_and_drops_unsafe_markup() {
+        let html = r#"<div class="wrap" onclick="steal()">
+            <h2 id="t" style="color:red">Title</h2>
+            <p>Text with <b>bold</b> and <a href="/docs" onclick="x()" target="_blank">a link</a>.</p>
+            <script>alert(1)</script>
+            <img src="https://cdn.example.com/a.png" alt="Chart" onerror="x()">
+        </div>"#;
+
+        let clean = sanitize_html(html);
+        assert!(clean.contains("<h2>Title</h2>"));
+        assert!(clean.contains(r#"<p>Text with bold and <a href="/docs">a link</a>.</p>"#));
+        assert!(clean.contains(r#"<img src="https://cdn.example.com/a.png" alt="Chart">"#));
+        assert!(!clean.contains("script"));
+        assert!(!clean.contains("alert"));
+        assert!(!clean.contains("onclick"));
+        assert!(!clean.contains("<div"));
+    }
+
+    #[test]
+    fn test_unsafe_urls_are_stripped() {
+        let clean = sanitize_html(
+            r#"<a href="java	script:alert(1)">x</a><a href="mailto:a@b.c">m</a><img src="data:text/html;base64,AAAA">"#,
+        );
+        assert_eq!(clean, r#"<a>x</a><a href="mailto:a@b.c">m</a><img>"#);
+        assert!(is_safe_url("page?next=http://x"));
+        assert!(!is_safe_url("VBScript:msgbox"));
+    }
+
+    #[test]
+    fn test_text_is_escaped() {
+        let clean = sanitize_html("<p>1 &lt; 2 &amp; \"q\"</p><table><tr><td colspan=\"2\">x</td></tr></table>");
+        assert!(clean.starts_with("<p>1 &lt; 2 &amp; &quot;q&quot;</p>"));
+        assert!(clean.contains(r#"<td colspan="2">x</td>"#));
+    }
+}
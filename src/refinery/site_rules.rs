@@ -0,0 +1,519 @@
+// * Per-Site Extraction Rules
+// * Explicit selectors for title, body, author, date and pagination on high-value sources.
+// * When a page's host has a rule, every field the rule's selectors find replaces the
+// * heuristic result; fields a selector misses keep the heuristic value.
+
+use super::content_cleaner::CleanedContent;
+use super::language::count_words;
+use super::metadata::PageMetadata;
+use crate::ops;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use thiserror::Error;
+
+#[cfg(feature = "xpath")]
+use super::xpath::{self, XPathMatch, XPathQuery};
+
+// * Blocks inside a body match that become separate paragraphs
+static SELECTOR_BLOCKS: LazyLock<Selector> =
+    LazyLock::new(|| Selector::parse("p, li, h1, h2, h3, h4, h5, h6, pre, blockquote").unwrap());
+
+#[derive(Debug, Error)]
+pub enum SiteRulesError {
+    #[error("Failed to read site rules {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Invalid site rules {path}: {message}")]
+    Parse { path: PathBuf, message: String },
+
+    #[error("Unsupported site rules format '{0}' (expected .toml or .json)")]
+    UnsupportedFormat(String),
+
+    #[error("Invalid {field} selector '{selector}' for {domain}: {message}")]
+    InvalidSelector {
+        domain: String,
+        field: &'static str,
+        selector: String,
+        message: String,
+    },
+}
+
+/// Selectors for one site
+///
+/// `domain` matches the host and its subdomains; when several rules match, the most
+/// specific domain wins. Selectors are CSS; with the `xpath` feature, selectors starting
+/// with `/` or `(`, or prefixed `xpath:`, are XPath 1.0 (`//a[@rel='next']/@href`).
+///
+/// # Example
+/// ```toml
+/// [[site]]
+/// domain = "news.example.com"
+/// title = "h1.headline"
+/// body = "div.article-body"
+/// author = "a[rel=author]"
+/// date = "time[datetime]"
+/// pagination = "a.next-page"
+///
+/// [[site]]
+/// domain = "journal.example.org"
+/// title = "//meta[@property='og:title']/@content"
+/// body = "//div[@id='article']//p"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SiteRule {
+    pub domain: String,
+    pub title: Option<String>,
+    /// Main content; the text of every match is kept, in document order
+    pub body: Option<String>,
+    pub author: Option<String>,
+    /// Publication date (a `datetime` or `content` attribute is preferred over the text)
+    pub date: Option<String>,
+    /// Link to the next page of a paginated article
+    pub pagination: Option<String>,
+}
+
+/// A site rules file: `[[site]]` tables in TOML, or `{"site": [...]}` in JSON
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SiteRulesFile {
+    pub site: Vec<SiteRule>,
+}
+
+impl SiteRulesFile {
+    /// Reads a .toml or .json rules file
+    pub fn load(path: &Path) -> Result<Self, SiteRulesError> {
+        let text = std::fs::read_to_string(path).map_err(|source| SiteRulesError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+        let parse_error = |message: String| SiteRulesError::Parse {
+            path: path.to_path_buf(),
+            message,
+        };
+        match ext.as_str() {
+            "toml" => toml::from_str(&text).map_err(|e| parse_error(e.to_string())),
+            "json" => serde_json::from_str(&text).map_err(|e| parse_error(e.to_string())),
+            _ => Err(SiteRulesError::UnsupportedFormat(path.display().to_string())),
+        }
+    }
+}
+
+/// What a site rule found on a page
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SiteExtraction {
+    pub title: Option<String>,
+    /// Paragraphs of the body matches
+    pub body: Option<Vec<String>>,
+    pub author: Option<String>,
+    pub date: Option<String>,
+    /// `href` of the pagination link, as written in the page
+    pub next_page: Option<String>,
+}
+
+/// A compiled field selector: CSS, or XPath for expressions starting with `/` or `(`
+#[derive(Debug)]
+enum RuleSelector {
+    Css(Selector),
+    #[cfg(feature = "xpath")]
+    XPath(XPathQuery),
+}
+
+impl RuleSelector {
+    fn parse(selector: &str) -> Result<Self, String> {
+        let selector = selector.trim();
+        if let Some(expression) = xpath_expression(selector) {
+            #[cfg(feature = "xpath")]
+            return XPathQuery::new(expression).map(Self::XPath).map_err(|e| e.message);
+            #[cfg(not(feature = "xpath"))]
+            return Err(format!("XPath '{}' needs the `xpath` feature", expression));
+        }
+        Selector::parse(selector.strip_prefix("css:").unwrap_or(selector))
+            .map(Self::Css)
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Returns the XPath expression of an `xpath:`-prefixed or path-shaped selector
+fn xpath_expression(selector: &str) -> Option<&str> {
+    match selector.strip_prefix("xpath:") {
+        Some(expression) => Some(expression.trim()),
+        // * No CSS selector starts with a slash or a parenthesis
+        None => selector.starts_with(['/', '(']).then_some(selector),
+    }
+}
+
+/// One node a field selector matched
+enum RuleMatch<'a> {
+    Element(ElementRef<'a>),
+    /// An XPath attribute, text or string result
+    #[cfg(feature = "xpath")]
+    Value(String),
+}
+
+impl RuleMatch<'_> {
+    fn text(&self) -> Option<String> {
+        match self {
+            Self::Element(element) => element_text(*element),
+            #[cfg(feature = "xpath")]
+            Self::Value(value) => collapse(value),
+        }
+    }
+
+    /// Returns the first non-empty attribute of `names`; an XPath value is the attribute itself
+    fn attr(&self, names: &[&str]) -> Option<String> {
+        match self {
+            Self::Element(element) => names
+                .iter()
+                .find_map(|name| element.value().attr(name).and_then(collapse)),
+            #[cfg(feature = "xpath")]
+            Self::Value(value) => collapse(value),
+        }
+    }
+
+    fn paragraphs(&self) -> Vec<String> {
+        match self {
+            Self::Element(element) => paragraphs_of(*element),
+            #[cfg(feature = "xpath")]
+            Self::Value(value) => collapse(value).into_iter().collect(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CompiledRule {
+    domain: String,
+    title: Option<RuleSelector>,
+    body: Option<RuleSelector>,
+    author: Option<RuleSelector>,
+    date: Option<RuleSelector>,
+    pagination: Option<RuleSelector>,
+}
+
+impl CompiledRule {
+    /// Runs every field selector, with one DOM conversion shared by the XPath fields
+    fn select<'a>(&self, document: &'a Html) -> [Vec<RuleMatch<'a>>; 5] {
+        let fields = [&self.title, &self.body, &self.author, &self.date, &self.pagination];
+        let mut matches: [Vec<RuleMatch<'a>>; 5] = Default::default();
+        #[cfg(feature = "xpath")]
+        let mut queries = Vec::new();
+        for (index, field) in fields.into_iter().enumerate() {
+            match field {
+                Some(RuleSelector::Css(selector)) => {
+                    matches[index] = document.select(selector).map(RuleMatch::Element).collect();
+                }
+                #[cfg(feature = "xpath")]
+                Some(RuleSelector::XPath(query)) => queries.push((index, query)),
+                None => {}
+            }
+        }
+        #[cfg(feature = "xpath")]
+        if !queries.is_empty() {
+            let found = xpath::select_many(document, &queries.iter().map(|(_, query)| *query).collect::<Vec<_>>());
+            for ((index, _), found) in queries.into_iter().zip(found) {
+                matches[index] = found
+                    .into_iter()
+                    .map(|found| match found {
+                        XPathMatch::Element(element) => RuleMatch::Element(element),
+                        XPathMatch::Value(value) => RuleMatch::Value(value),
+                    })
+                    .collect();
+            }
+        }
+        matches
+    }
+}
+
+/// Compiled site rules, looked up by host
+#[derive(Debug, Default)]
+pub struct SiteRuleSet {
+    rules: Vec<CompiledRule>,
+}
+
+impl SiteRuleSet {
+    /// Compiles rules, failing on the first invalid selector
+    pub fn new(rules: &[SiteRule]) -> Result<Self, SiteRulesError> {
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                let domain = rule.domain.trim().trim_start_matches("*.").to_ascii_lowercase();
+                let compile = |field: &'static str, selector: &Option<String>| {
+                    selector
+                        .as_deref()
+                        .map(|selector| {
+                            RuleSelector::parse(selector).map_err(|message| SiteRulesError::InvalidSelector {
+                                domain: rule.domain.clone(),
+                                field,
+                                selector: selector.to_string(),
+                                message,
+                            })
+                        })
+                        .transpose()
+                };
+                Ok(CompiledRule {
+                    title: compile("title", &rule.title)?,
+                    body: compile("body", &rule.body)?,
+                    author: compile("author", &rule.author)?,
+                    date: compile("date", &rule.date)?,
+                    pagination: compile("pagination", &rule.pagination)?,
+                    domain,
+                })
+            })
+            .collect::<Result<_, SiteRulesError>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Returns the domain of the rule that applies to a host
+    pub fn domain_for(&self, host: &str) -> Option<&str> {
+        self.rule_for(host).map(|rule| rule.domain.as_str())
+    }
+
+    /// Runs the host's rule over a page; returns None when no rule applies
+    pub fn extract(&self, html: &str, host: &str) -> Option<SiteExtraction> {
+        let rule = self.rule_for(host)?;
+        let document = Html::parse_document(html);
+        let [title, body, author, date, pagination] = rule.select(&document);
+        let first = |field: &str, selector: &Option<RuleSelector>, matches: Vec<RuleMatch>, read: fn(&RuleMatch) -> Option<String>| {
+            selector.as_ref()?;
+            let value = matches.iter().find_map(read);
+            ops::record_site_rule_match(field, value.is_some());
+            value
+        };
+
+        let body = rule.body.as_ref().and_then(|_| {
+            let paragraphs: Vec<String> = body.iter().flat_map(RuleMatch::paragraphs).collect();
+            ops::record_site_rule_match("body", !paragraphs.is_empty());
+            (!paragraphs.is_empty()).then_some(paragraphs)
+        });
+        Some(SiteExtraction {
+            title: first("title", &rule.title, title, |found| found.text()),
+            body,
+            author: first("author", &rule.author, author, |found| found.text()),
+            date: first("date", &rule.date, date, |found| {
+                found.attr(&["datetime", "content"]).or_else(|| found.text())
+            }),
+            next_page: first("pagination", &rule.pagination, pagination, |found| found.attr(&["href"])),
+        })
+    }
+
+    fn rule_for(&self, host: &str) -> Option<&CompiledRule> {
+        let host = host.to_ascii_lowercase();
+        self.rules
+            .iter()
+            .filter(|rule| host == rule.domain || host.ends_with(&format!(".{}", rule.domain)))
+            .max_by_key(|rule| rule.domain.len())
+    }
+}
+
+impl SiteExtraction {
+    /// Overwrites the heuristic content and metadata with every field the rule found
+    pub fn apply(self, content: &mut CleanedContent, metadata: &mut PageMetadata) {
+        if let Some(paragraphs) = self.body {
+            content.text = paragraphs.join("\n\n");
+            content.word_count = count_words(&content.text);
+            content.paragraphs = paragraphs;
+            content.found_main_content = true;
+        }
+        let mut matched = false;
+        if let Some(title) = self.title {
+            metadata.title = Some(title);
+            matched = true;
+        }
+        if let Some(author) = self.author {
+            metadata.authors = vec![author.clone()];
+            metadata.author = Some(author);
+            matched = true;
+        }
+        if let Some(date) = self.date {
+            metadata.date_published = Some(date);
+            matched = true;
+        }
+        if let Some(next_page) = self.next_page {
+            metadata.next_page_url = Some(next_page);
+        }
+        if matched {
+            metadata.extraction_method = "site_rules".to_string();
+        }
+    }
+}
+
+/// Returns an element's whitespace-collapsed text, or None when it has none
+fn element_text(element: ElementRef) -> Option<String> {
+    collapse(&element.text().collect::<Vec<_>>().join(" "))
+}
+
+fn collapse(text: &str) -> Option<String> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+/// Splits a body match into its block-level paragraphs (the whole text when it has none)
+fn paragraphs_of(element: ElementRef) -> Vec<String> {
+    let blocks: Vec<String> = element
+        .select(&SELECTOR_BLOCKS)
+        // * Nested blocks (a <p> inside a <blockquote>) are read through their outermost block
+        .filter(|block| {
+            !block
+                .ancestors()
+                .take_while(|ancestor| ancestor.id() != element.id())
+                .filter_map(ElementRef::wrap)
+                .any(|ancestor| SELECTOR_BLOCKS.matches(&ancestor))
+        })
+        .filter_map(element_text)
+        .collect();
+    if blocks.is_empty() {
+        element_text(element).into_iter().collect()
+    } else {
+        blocks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><head><title>Ignored | Site</title></head><body>
+        <h1 class="headline">Council Approves New Bike Lanes</h1>
+        <span class="byline"><a rel="author" href="/staff/lee">Sam Lee</a></span>
+        <time datetime="2024-05-02T09:00:00Z">May 2</time>
+        <div class="article-body">
+            <p>The city council voted on Tuesday to add protected bike lanes downtown.</p>
+            <blockquote><p>This makes the commute safer for everyone.</p></blockquote>
+        </div>
+        <div class="article-body"><p>Construction starts in the autumn.</p></div>
+        <a class="next-page" href="/news/bike-lanes?page=2">Next</a>
+    </body></html>"#;
+
+    fn rules() -> SiteRuleSet {
+        SiteRuleSet::new(&[
+            SiteRule {
+                domain: "example.com".to_string(),
+                title: Some("h1.headline".to_string()),
+                ..Default::default()
+            },
+            SiteRule {
+                domain: "*.news.example.com".to_string(),
+                title: Some("h1.headline".to_string()),
+                body: Some("div.article-body".to_string()),
+                author: Some("a[rel=author]".to_string()),
+                date: Some("time".to_string()),
+                pagination: Some("a.next-page".to_string()),
+            },
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_most_specific_rule_extracts_fields() {
+        let rules = rules();
+        assert_eq!(rules.domain_for("www.news.example.com"), Some("news.example.com"));
+        assert_eq!(rules.domain_for("blog.example.com"), Some("example.com"));
+        assert_eq!(rules.domain_for("example.org"), None);
+
+        let found = rules.extract(PAGE, "news.example.com").unwrap();
+        assert_eq!(found.title.as_deref(), Some("Council Approves New Bike Lanes"));
+        assert_eq!(found.author.as_deref(), Some("Sam Lee"));
+        assert_eq!(found.date.as_deref(), Some("2024-05-02T09:00:00Z"));
+        assert_eq!(found.next_page.as_deref(), Some("/news/bike-lanes?page=2"));
+        assert_eq!(
+            found.body.unwrap(),
+            vec![
+                "The city council voted on Tuesday to add protected bike lanes downtown.",
+                "This makes the commute safer for everyone.",
+                "Construction starts in the autumn.",
+            ]
+        );
+        assert_eq!(rules.extract(PAGE, "example.org"), None);
+    }
+
+    #[test]
+    fn test_apply_keeps_heuristics_for_missed_fields() {
+        let mut content = CleanedContent {
+            text: "Heuristic text".to_string(),
+            ..Default::default()
+        };
+        let mut metadata = PageMetadata {
+            title: Some("Ignored".to_string()),
+            author: Some("Heuristic Author".to_string()),
+            ..Default::default()
+        };
+
+        let found = rules().extract(PAGE, "example.com").unwrap();
+        found.apply(&mut content, &mut metadata);
+
+        assert_eq!(metadata.title.as_deref(), Some("Council Approves New Bike Lanes"));
+        assert_eq!(metadata.author.as_deref(), Some("Heuristic Author"));
+        assert_eq!(metadata.extraction_method, "site_rules");
+        assert_eq!(content.text, "Heuristic text");
+    }
+
+    #[test]
+    fn test_load_rules_files() {
+        let dir = std::env::temp_dir().join(format!("titan-site-rules-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("rules.toml"), "[[site]]\ndomain = \"example.com\"\nbody = \"main\"\n").unwrap();
+        std::fs::write(dir.join("rules.json"), r#"{"site": [{"domain": "example.com", "body": "main"}]}"#).unwrap();
+
+        let toml = SiteRulesFile::load(&dir.join("rules.toml")).unwrap();
+        assert_eq!(toml, SiteRulesFile::load(&dir.join("rules.json")).unwrap());
+        assert_eq!(toml.site[0].body.as_deref(), Some("main"));
+
+        let invalid = SiteRuleSet::new(&[SiteRule {
+            domain: "example.com".to_string(),
+            title: Some("h1[".to_string()),
+            ..Default::default()
+        }]);
+        assert!(matches!(invalid, Err(SiteRulesError::InvalidSelector { field: "title", .. })));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "xpath")]
+    #[test]
+    fn test_xpath_selectors_mix_with_css() {
+        let rules = SiteRuleSet::new(&[SiteRule {
+            domain: "news.example.com".to_string(),
+            title: Some("css:h1.headline".to_string()),
+            body: Some("//div[@class='article-body']".to_string()),
+            author: Some("xpath: //a[@rel='author']/text()".to_string()),
+            date: Some("//time/@datetime".to_string()),
+            pagination: Some("(//a[contains(@class, 'next')])[1]".to_string()),
+        }])
+        .unwrap();
+
+        let found = rules.extract(PAGE, "news.example.com").unwrap();
+        assert_eq!(found.title.as_deref(), Some("Council Approves New Bike Lanes"));
+        assert_eq!(found.author.as_deref(), Some("Sam Lee"));
+        assert_eq!(found.date.as_deref(), Some("2024-05-02T09:00:00Z"));
+        assert_eq!(found.next_page.as_deref(), Some("/news/bike-lanes?page=2"));
+        assert_eq!(found.body.unwrap().len(), 3);
+
+        let invalid = SiteRuleSet::new(&[SiteRule {
+            domain: "example.com".to_string(),
+            body: Some("//div[@class=".to_string()),
+            ..Default::default()
+        }]);
+        assert!(matches!(invalid, Err(SiteRulesError::InvalidSelector { field: "body", .. })));
+    }
+
+    #[cfg(not(feature = "xpath"))]
+    #[test]
+    fn test_xpath_selectors_need_feature() {
+        let result = SiteRuleSet::new(&[SiteRule {
+            domain: "example.com".to_string(),
+            title: Some("//h1".to_string()),
+            ..Default::default()
+        }]);
+        assert!(matches!(result, Err(SiteRulesError::InvalidSelector { message, .. }) if message.contains("xpath")));
+    }
+}
@@ -2,6 +2,7 @@
 // * Heuristic-based table scoring to distinguish data tables from layout tables.
 // * Ported from crawl4ai/table_extraction.py
 
+use regex::Regex;
 use scraper::{ElementRef, Html, Selector};
 use serde::{Deserialize, Serialize};
 use std::sync::LazyLock;
@@ -17,19 +18,343 @@ static SELECTOR_TH: LazyLock<Selector> =
     LazyLock::new(|| Selector::parse("th").expect("Invalid th selector"));
 static SELECTOR_TR: LazyLock<Selector> =
     LazyLock::new(|| Selector::parse("tr").expect("Invalid tr selector"));
-static SELECTOR_TD: LazyLock<Selector> =
-    LazyLock::new(|| Selector::parse("td").expect("Invalid td selector"));
 
-// * Threshold for determining if a table is a data table (from config)
+// * Trailing unit annotation in a header, e.g. "Price ($)" or "Weight [kg]"
+static UNIT_SUFFIX_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"[(\[]\s*([^()\[\]]{1,12}?)\s*[)\]]\s*$").expect("Invalid unit suffix regex")
+});
+
+// * Upper bounds on span attributes (HTML spec limits) to cap grid expansion
+const MAX_COLSPAN: usize = 1000;
+const MAX_ROWSPAN: usize = 65534;
+// * Bounds on the expanded grid itself: row width and total cells across a table
+const MAX_GRID_COLUMNS: usize = 1000;
+const MAX_GRID_CELLS: usize = 100_000;
+
+// * Default threshold for determining if a table is a data table (see TableScorerConfig)
 // * Lowered from 7 to 4 to be more permissive (thead=2 + tbody=1 + th=2 = 5)
 const DATA_TABLE_THRESHOLD: i32 = 4;
 
+/// Cells left to expand for one table; set `truncated` once a bound is hit
+struct GridBudget {
+    cells_left: usize,
+    truncated: bool,
+}
+
+impl GridBudget {
+    fn new() -> Self {
+        Self {
+            cells_left: MAX_GRID_CELLS,
+            truncated: false,
+        }
+    }
+
+    /// Admits one more cell at `col`, or records the truncation
+    fn admit(&mut self, col: usize) -> bool {
+        if col >= MAX_GRID_COLUMNS || self.cells_left == 0 {
+            self.truncated = true;
+            return false;
+        }
+        self.cells_left -= 1;
+        true
+    }
+}
+
 /// Represents an extracted data table with headers and rows
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct ExtractedTable {
     pub headers: Vec<String>,
     pub rows: Vec<Vec<String>>,
     pub score: i32,
+    /// Whether every row had the same width after span expansion (before padding)
+    #[serde(default)]
+    pub is_rectangular: bool,
+    /// Text of the `<caption>` element, if any
+    #[serde(default)]
+    pub caption: Option<String>,
+    /// Rows from `<tfoot>` (totals, notes), kept out of `rows`
+    #[serde(default)]
+    pub footer_rows: Vec<Vec<String>>,
+    /// Best-effort unit per header column, e.g. `$` for "Price ($)"
+    #[serde(default)]
+    pub column_units: Vec<Option<String>>,
+}
+
+/// Configuration for table extraction behaviour
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TableExtractionConfig {
+    /// Join multi-row (grouped) headers into one name per column
+    pub flatten_multi_row_headers: bool,
+    /// Separator used when flattening grouped headers ("Q1 / Revenue")
+    pub header_separator: String,
+    /// Descend into layout tables to find inner data tables instead of rejecting them
+    pub unwrap_layout_tables: bool,
+    /// Data-table threshold and scoring weights
+    pub scorer: TableScorerConfig,
+}
+
+impl Default for TableExtractionConfig {
+    fn default() -> Self {
+        Self {
+            flatten_multi_row_headers: true,
+            header_separator: " / ".to_string(),
+            unwrap_layout_tables: true,
+            scorer: TableScorerConfig::default(),
+        }
+    }
+}
+
+/// Threshold, per-signal weights, and size limits for data-table detection
+///
+/// Defaults reproduce the crawl4ai scoring; raise `threshold` for precision,
+/// lower it for recall. Negative weights penalise layout signals.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TableScorerConfig {
+    /// Minimum score for a table to count as data
+    pub threshold: i32,
+    pub thead_weight: i32,
+    pub tbody_weight: i32,
+    pub th_weight: i32,
+    pub nested_table_weight: i32,
+    pub presentation_role_weight: i32,
+    pub high_density_weight: i32,
+    pub medium_density_weight: i32,
+    /// Text/tag ratio above which `high_density_weight` applies
+    pub high_density_ratio: f32,
+    /// Text/tag ratio above which `medium_density_weight` applies
+    pub medium_density_ratio: f32,
+    /// Minimum number of data rows (0 disables the check)
+    pub min_rows: usize,
+    /// Minimum number of columns (0 disables the check)
+    pub min_cols: usize,
+}
+
+impl Default for TableScorerConfig {
+    fn default() -> Self {
+        Self {
+            threshold: DATA_TABLE_THRESHOLD,
+            thead_weight: 2,
+            tbody_weight: 1,
+            th_weight: 2,
+            nested_table_weight: -3,
+            presentation_role_weight: -3,
+            high_density_weight: 3,
+            medium_density_weight: 2,
+            high_density_ratio: 20.0,
+            medium_density_ratio: 10.0,
+            min_rows: 0,
+            min_cols: 0,
+        }
+    }
+}
+
+impl TableScorerConfig {
+    /// Checks the extracted table against `min_rows` / `min_cols`
+    pub fn meets_minimum_size(&self, table: &ExtractedTable) -> bool {
+        table.rows.len() >= self.min_rows && table.column_count() >= self.min_cols
+    }
+}
+
+/// Column data type inferred from cell contents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnType {
+    /// Every non-empty cell parses as a number (currency/percent/thousands stripped)
+    Numeric,
+    /// Every non-empty cell is an ISO-8601 date (YYYY-MM-DD)
+    Date,
+    /// Anything else, including fully empty columns
+    String,
+}
+
+impl ExtractedTable {
+    /// Returns the number of columns (widest of headers and rows)
+    pub fn column_count(&self) -> usize {
+        self.rows
+            .iter()
+            .map(|r| r.len())
+            .chain(std::iter::once(self.headers.len()))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns column names with inferred fallbacks
+    ///
+    /// Missing or empty headers become `column_N` (1-based) and duplicates are
+    /// suffixed (`Name`, `Name_2`) so the result is usable as a schema.
+    pub fn column_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::with_capacity(self.column_count());
+        for i in 0..self.column_count() {
+            let base = match self.headers.get(i).map(|h| h.trim()) {
+                Some(h) if !h.is_empty() => h.to_string(),
+                _ => format!("column_{}", i + 1),
+            };
+
+            let mut name = base.clone();
+            let mut suffix = 2;
+            while names.contains(&name) {
+                name = format!("{}_{}", base, suffix);
+                suffix += 1;
+            }
+            names.push(name);
+        }
+        names
+    }
+
+    /// Sniffs the data type of every column
+    pub fn column_types(&self) -> Vec<ColumnType> {
+        (0..self.column_count())
+            .map(|col| {
+                let cells: Vec<&str> = self
+                    .rows
+                    .iter()
+                    .filter_map(|r| r.get(col))
+                    .map(|c| c.trim())
+                    .filter(|c| !c.is_empty())
+                    .collect();
+
+                if cells.is_empty() {
+                    ColumnType::String
+                } else if cells.iter().all(|c| parse_numeric(c).is_some()) {
+                    ColumnType::Numeric
+                } else if cells.iter().all(|c| parse_iso_date(c).is_some()) {
+                    ColumnType::Date
+                } else {
+                    ColumnType::String
+                }
+            })
+            .collect()
+    }
+
+    /// Serializes the table to RFC 4180 CSV with a header line
+    ///
+    /// Short rows are padded with empty fields so every record has the same width.
+    pub fn to_csv(&self) -> String {
+        let width = self.column_count();
+        let mut out = String::new();
+
+        let header_line: Vec<String> = self.column_names().iter().map(|h| csv_escape(h)).collect();
+        out.push_str(&header_line.join(","));
+        out.push_str("\r\n");
+
+        for row in &self.rows {
+            let fields: Vec<String> = (0..width)
+                .map(|i| csv_escape(row.get(i).map(String::as_str).unwrap_or("")))
+                .collect();
+            out.push_str(&fields.join(","));
+            out.push_str("\r\n");
+        }
+
+        out
+    }
+
+    /// Converts the table to an Arrow `RecordBatch` using the sniffed column types
+    ///
+    /// Numeric columns become `Float64`, date columns `Date32`, everything else
+    /// `Utf8`. Empty cells are stored as nulls.
+    #[cfg(feature = "arrow-export")]
+    pub fn to_arrow(&self) -> Result<arrow::record_batch::RecordBatch, arrow::error::ArrowError> {
+        use arrow::array::{ArrayRef, Date32Array, Float64Array, StringArray};
+        use arrow::datatypes::{DataType, Field, Schema};
+        use std::sync::Arc;
+
+        let names = self.column_names();
+        let types = self.column_types();
+
+        let mut fields = Vec::with_capacity(names.len());
+        let mut columns: Vec<ArrayRef> = Vec::with_capacity(names.len());
+
+        for (col, (name, column_type)) in names.iter().zip(types.iter()).enumerate() {
+            let cells = self.rows.iter().map(|r| {
+                r.get(col)
+                    .map(|c| c.trim())
+                    .filter(|c| !c.is_empty())
+            });
+
+            let (data_type, array): (DataType, ArrayRef) = match column_type {
+                ColumnType::Numeric => (
+                    DataType::Float64,
+                    Arc::new(cells.map(|c| c.and_then(parse_numeric)).collect::<Float64Array>()),
+                ),
+                ColumnType::Date => (
+                    DataType::Date32,
+                    Arc::new(cells.map(|c| c.and_then(parse_iso_date)).collect::<Date32Array>()),
+                ),
+                ColumnType::String => (
+                    DataType::Utf8,
+                    Arc::new(cells.collect::<StringArray>()),
+                ),
+            };
+
+            fields.push(Field::new(name, data_type, true));
+            columns.push(array);
+        }
+
+        arrow::record_batch::RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+    }
+}
+
+/// Quotes a CSV field when it contains separators, quotes, or line breaks
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Parses a numeric cell, tolerating currency symbols, thousands separators, and percents
+fn parse_numeric(cell: &str) -> Option<f64> {
+    let cleaned: String = cell
+        .trim()
+        .trim_start_matches(['$', '\u{20ac}', '\u{00a3}'])
+        .trim_end_matches('%')
+        .chars()
+        .filter(|c| *c != ',')
+        .collect();
+
+    if cleaned.is_empty() || !cleaned.chars().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    cleaned.trim().parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+/// Parses a YYYY-MM-DD cell into days since the Unix epoch
+fn parse_iso_date(cell: &str) -> Option<i32> {
+    let cell = cell.trim();
+    let bytes = cell.as_bytes();
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return None;
+    }
+
+    let year: i64 = cell[0..4].parse().ok()?;
+    let month: i64 = cell[5..7].parse().ok()?;
+    let day: i64 = cell[8..10].parse().ok()?;
+
+    let is_leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap => 29,
+        2 => 28,
+        _ => return None,
+    };
+    if day < 1 || day > days_in_month {
+        return None;
+    }
+
+    // * Days-from-civil (Howard Hinnant), proleptic Gregorian calendar
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    i32::try_from(era * 146_097 + doe - 719_468).ok()
 }
 
 /// Scores and extracts data tables from HTML content
@@ -46,21 +371,26 @@ impl TableScorer {
     /// - +3 if text/tag ratio > 20
     /// - +2 if text/tag ratio > 10
     pub fn calculate_score(table: &ElementRef) -> i32 {
+        Self::calculate_score_with_config(table, &TableScorerConfig::default())
+    }
+
+    /// Calculates the heuristic score using custom signal weights
+    pub fn calculate_score_with_config(table: &ElementRef, config: &TableScorerConfig) -> i32 {
         let mut score: i32 = 0;
 
         // * Structure checks (positive signals for data tables)
         if table.select(&SELECTOR_THEAD).next().is_some() {
-            score += 2;
+            score += config.thead_weight;
         }
 
         if table.select(&SELECTOR_TBODY).next().is_some() {
-            score += 1;
+            score += config.tbody_weight;
         }
 
         // * Header cells indicate structured data
         let th_count = table.select(&SELECTOR_TH).count();
         if th_count > 0 {
-            score += 2;
+            score += config.th_weight;
         }
 
         // * Nested tables are typically for layout (negative signal)
@@ -69,14 +399,14 @@ impl TableScorer {
             .skip(1) // ? Skip self - only count nested
             .count();
         if nested_table_count > 0 {
-            score -= 3;
+            score += config.nested_table_weight;
         }
 
         // * Role attribute check (presentation tables are layout)
         if let Some(role) = table.value().attr("role") {
             let role_lower = role.to_lowercase();
             if role_lower == "presentation" || role_lower == "none" {
-                score -= 3;
+                score += config.presentation_role_weight;
             }
         }
 
@@ -86,10 +416,10 @@ impl TableScorer {
         let tag_count = table.descendants().count().max(1);
         let ratio = text_len as f32 / tag_count as f32;
 
-        if ratio > 20.0 {
-            score += 3;
-        } else if ratio > 10.0 {
-            score += 2;
+        if ratio > config.high_density_ratio {
+            score += config.high_density_weight;
+        } else if ratio > config.medium_density_ratio {
+            score += config.medium_density_weight;
         }
 
         score
@@ -97,107 +427,326 @@ impl TableScorer {
 
     /// Determines if a table is a data table based on heuristic score
     pub fn is_data_table(table: &ElementRef) -> bool {
-        Self::calculate_score(table) >= DATA_TABLE_THRESHOLD
+        Self::is_data_table_with_config(table, &TableScorerConfig::default())
     }
 
-    /// Extracts headers from a table element
-    fn extract_headers(table: &ElementRef) -> Vec<String> {
-        let mut headers = Vec::new();
+    /// Determines if a table is a data table using a custom threshold and weights
+    pub fn is_data_table_with_config(table: &ElementRef, config: &TableScorerConfig) -> bool {
+        Self::calculate_score_with_config(table, config) >= config.threshold
+    }
 
-        // * Try thead > tr > th first
-        if let Some(thead) = table.select(&SELECTOR_THEAD).next() {
-            for th in thead.select(&SELECTOR_TH) {
-                let text: String = th.text().collect();
-                headers.push(text.trim().to_string());
-            }
-        }
+    /// Returns the trimmed text content of a cell
+    fn cell_text(cell: &ElementRef) -> String {
+        let text: String = cell.text().collect();
+        text.trim().to_string()
+    }
 
-        // * Fallback: first row th elements
-        if headers.is_empty() {
-            if let Some(first_row) = table.select(&SELECTOR_TR).next() {
-                for th in first_row.select(&SELECTOR_TH) {
-                    let text: String = th.text().collect();
-                    headers.push(text.trim().to_string());
+    /// Reads a span attribute, clamped to `1..=max` (missing/invalid/0 count as 1)
+    fn span_attr(cell: &ElementRef, name: &str, max: usize) -> usize {
+        cell.value()
+            .attr(name)
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .unwrap_or(1)
+            .clamp(1, max)
+    }
+
+    /// Returns the direct `th`/`td` children of a row (ignores nested tables)
+    fn row_cells<'a>(row: &ElementRef<'a>) -> Vec<ElementRef<'a>> {
+        row.children()
+            .filter_map(ElementRef::wrap)
+            .filter(|e| matches!(e.value().name(), "td" | "th"))
+            .collect()
+    }
+
+    /// Expands `colspan`/`rowspan` into a grid, duplicating merged cell text
+    ///
+    /// Rows spanned from above are filled before the row's own cells; a gap
+    /// left of a carried cell is padded with an empty string. Rows are cut at
+    /// `MAX_GRID_COLUMNS` and expansion stops once the budget runs out.
+    fn expand_rows(rows: &[ElementRef], budget: &mut GridBudget) -> Vec<Vec<String>> {
+        // * Per-column carry: (rows remaining, text) from a rowspan above
+        let mut carry: Vec<Option<(usize, String)>> = Vec::new();
+        let mut grid = Vec::with_capacity(rows.len());
+
+        for row in rows {
+            let mut out: Vec<String> = Vec::new();
+            let mut col = 0;
+
+            'cells: for cell in Self::row_cells(row) {
+                // * Fill columns occupied by rowspans from previous rows
+                while let Some(text) = Self::take_carry(&mut carry, col) {
+                    if !budget.admit(col) {
+                        break 'cells;
+                    }
+                    out.push(text);
+                    col += 1;
+                }
+
+                let text = Self::cell_text(&cell);
+                let colspan = Self::span_attr(&cell, "colspan", MAX_COLSPAN);
+                let rowspan = Self::span_attr(&cell, "rowspan", MAX_ROWSPAN);
+
+                for _ in 0..colspan {
+                    if !budget.admit(col) {
+                        break 'cells;
+                    }
+                    if rowspan > 1 {
+                        if carry.len() <= col {
+                            carry.resize(col + 1, None);
+                        }
+                        carry[col] = Some((rowspan - 1, text.clone()));
+                    }
+                    out.push(text.clone());
+                    col += 1;
                 }
             }
-        }
 
-        // * Fallback: first row td elements if no th found
-        if headers.is_empty() {
-            if let Some(first_row) = table.select(&SELECTOR_TR).next() {
-                for td in first_row.select(&SELECTOR_TD) {
-                    let text: String = td.text().collect();
-                    headers.push(text.trim().to_string());
+            // * Trailing rowspans after the row's last cell
+            while let Some(last_active) = carry.iter().rposition(Option::is_some) {
+                if col > last_active || !budget.admit(col) {
+                    break;
                 }
+                out.push(Self::take_carry(&mut carry, col).unwrap_or_default());
+                col += 1;
+            }
+
+            grid.push(out);
+            if budget.cells_left == 0 {
+                break;
             }
         }
 
-        headers
+        grid
+    }
+
+    /// Consumes one row of a carried rowspan at `col`, if active
+    fn take_carry(carry: &mut [Option<(usize, String)>], col: usize) -> Option<String> {
+        let slot = carry.get_mut(col)?;
+        let (remaining, text) = slot.as_mut()?;
+        let value = text.clone();
+        *remaining -= 1;
+        if *remaining == 0 {
+            *slot = None;
+        }
+        Some(value)
     }
 
-    /// Extracts data rows from a table element
-    fn extract_rows(table: &ElementRef, skip_first: bool) -> Vec<Vec<String>> {
-        let mut rows = Vec::new();
-        let row_iter = table.select(&SELECTOR_TR);
+    /// Extracts the table's own `<caption>` text (ignores nested tables)
+    fn extract_caption(table: &ElementRef) -> Option<String> {
+        table
+            .children()
+            .filter_map(ElementRef::wrap)
+            .find(|e| e.value().name() == "caption")
+            .map(|c| Self::cell_text(&c))
+            .filter(|t| !t.is_empty())
+    }
 
-        // * Skip header row if headers were extracted from first row
-        let rows_to_process: Vec<ElementRef> = if skip_first {
-            row_iter.skip(1).collect()
-        } else {
-            // * If we have thead, skip rows inside thead
-            let tbody_selector = &SELECTOR_TBODY;
-            if let Some(tbody) = table.select(tbody_selector).next() {
-                tbody.select(&SELECTOR_TR).collect()
-            } else {
-                row_iter.skip(1).collect()
+    /// Detects a unit annotation at the end of a header ("Weight (kg)" -> "kg")
+    ///
+    /// Purely numeric annotations such as "Revenue (2024)" are not units.
+    pub fn detect_unit(header: &str) -> Option<String> {
+        let unit = UNIT_SUFFIX_REGEX.captures(header)?.get(1)?.as_str().trim();
+        let is_numeric = unit.chars().all(|c| c.is_ascii_digit() || c == '-' || c == ' ');
+        if unit.is_empty() || is_numeric {
+            return None;
+        }
+        Some(unit.to_string())
+    }
+
+    /// Returns the nearest enclosing `<table>` section of a row (`thead`/`tbody`/`tfoot`)
+    /// and whether the row belongs to `table` itself rather than a nested table
+    fn row_owner(row: &ElementRef, table: &ElementRef) -> (bool, Option<&'static str>) {
+        let mut section = None;
+        for ancestor in row.ancestors().filter_map(ElementRef::wrap) {
+            match ancestor.value().name() {
+                "thead" if section.is_none() => section = Some("thead"),
+                "tbody" if section.is_none() => section = Some("tbody"),
+                "tfoot" if section.is_none() => section = Some("tfoot"),
+                "table" => return (ancestor.id() == table.id(), section),
+                _ => {}
             }
-        };
+        }
+        (false, section)
+    }
 
-        for row in rows_to_process {
-            let cells: Vec<String> = row
-                .select(&SELECTOR_TD)
-                .map(|td| {
-                    let text: String = td.text().collect();
-                    text.trim().to_string()
-                })
-                .collect();
+    /// Splits the table's own rows into (thead, body, tfoot) in document order
+    fn partition_rows<'a>(
+        table: &ElementRef<'a>,
+    ) -> (Vec<ElementRef<'a>>, Vec<ElementRef<'a>>, Vec<ElementRef<'a>>) {
+        let mut head = Vec::new();
+        let mut body = Vec::new();
+        let mut foot = Vec::new();
 
-            // * Only add non-empty rows
-            if !cells.is_empty() && cells.iter().any(|c| !c.is_empty()) {
-                rows.push(cells);
+        for row in table.select(&SELECTOR_TR) {
+            match Self::row_owner(&row, table) {
+                (false, _) => {}
+                (true, Some("thead")) => head.push(row),
+                (true, Some("tfoot")) => foot.push(row),
+                (true, _) => body.push(row),
             }
         }
 
-        rows
+        (head, body, foot)
+    }
+
+    /// Flattens a (span-expanded) header grid into one name per column
+    ///
+    /// With flattening enabled, distinct non-empty labels are joined top to
+    /// bottom ("Q1 / Revenue"); otherwise only the bottom row is used.
+    fn flatten_headers(grid: &[Vec<String>], config: &TableExtractionConfig) -> Vec<String> {
+        let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+
+        if !config.flatten_multi_row_headers {
+            let mut bottom = grid.last().cloned().unwrap_or_default();
+            bottom.resize(width, String::new());
+            return bottom;
+        }
+
+        (0..width)
+            .map(|col| {
+                let mut parts: Vec<&str> = Vec::new();
+                for row in grid {
+                    let label = row.get(col).map(|c| c.as_str()).unwrap_or("");
+                    if !label.is_empty() && parts.last() != Some(&label) {
+                        parts.push(label);
+                    }
+                }
+                parts.join(&config.header_separator)
+            })
+            .collect()
+    }
+
+    /// Expands rows and drops those that are entirely empty
+    fn non_empty_rows(rows: &[ElementRef], budget: &mut GridBudget) -> Vec<Vec<String>> {
+        // * Filter after expansion so rowspans stay aligned
+        Self::expand_rows(rows, budget)
+            .into_iter()
+            .filter(|cells| cells.iter().any(|c| !c.is_empty()))
+            .collect()
     }
 
     /// Extracts a single table element into structured form
     pub fn extract_table(table: &ElementRef) -> ExtractedTable {
-        let score = Self::calculate_score(table);
-        let headers = Self::extract_headers(table);
+        Self::extract_table_with_config(table, &TableExtractionConfig::default())
+    }
 
-        // * Determine if we need to skip first row (when headers came from first tr)
-        let has_thead = table.select(&SELECTOR_THEAD).next().is_some();
-        let skip_first = !has_thead && !headers.is_empty();
+    /// Extracts a single table element using the given extraction settings
+    pub fn extract_table_with_config(
+        table: &ElementRef,
+        config: &TableExtractionConfig,
+    ) -> ExtractedTable {
+        let score = Self::calculate_score_with_config(table, &config.scorer);
+        let (head_rows, mut body_rows, foot_rows) = Self::partition_rows(table);
 
-        let rows = Self::extract_rows(table, skip_first);
+        // * Header rows: <thead>, else leading all-<th> rows, else the first row
+        let header_rows: Vec<ElementRef> = if !head_rows.is_empty() {
+            head_rows
+        } else {
+            let leading_th = body_rows
+                .iter()
+                .take_while(|row| {
+                    let cells = Self::row_cells(row);
+                    !cells.is_empty() && cells.iter().all(|c| c.value().name() == "th")
+                })
+                .count();
+            let take = if leading_th > 0 { leading_th } else { body_rows.len().min(1) };
+            body_rows.drain(..take).collect()
+        };
+
+        // * One budget for head, body and foot so the whole table stays bounded
+        let mut budget = GridBudget::new();
+        let headers = Self::flatten_headers(&Self::expand_rows(&header_rows, &mut budget), config);
+        let mut rows = Self::non_empty_rows(&body_rows, &mut budget);
+        let footer_rows = Self::non_empty_rows(&foot_rows, &mut budget);
+
+        // * Pad ragged rows so consumers get rectangular data, unless padding
+        // * would itself blow the cell budget or the grid was truncated
+        let width = rows
+            .iter()
+            .map(Vec::len)
+            .chain(std::iter::once(headers.len()))
+            .max()
+            .unwrap_or(0);
+        let is_rectangular = !budget.truncated
+            && rows.iter().all(|r| r.len() == width)
+            && (headers.is_empty() || headers.len() == width);
+        if !budget.truncated && rows.len().saturating_mul(width) <= MAX_GRID_CELLS {
+            for row in &mut rows {
+                row.resize(width, String::new());
+            }
+        }
+
+        let column_units = headers.iter().map(|h| Self::detect_unit(h)).collect();
 
         ExtractedTable {
             headers,
             rows,
             score,
+            is_rectangular,
+            caption: Self::extract_caption(table),
+            footer_rows,
+            column_units,
+        }
+    }
+
+    /// Returns tables nested directly inside `table` (not inside a deeper table)
+    fn direct_nested_tables<'a>(table: &ElementRef<'a>) -> Vec<ElementRef<'a>> {
+        table
+            .select(&SELECTOR_TABLE)
+            .filter(|inner| {
+                inner
+                    .ancestors()
+                    .filter_map(ElementRef::wrap)
+                    .find(|e| e.value().name() == "table")
+                    .is_some_and(|parent| parent.id() == table.id())
+            })
+            .collect()
+    }
+
+    /// Walks a table tree, collecting data tables and descending into layout wrappers
+    fn collect_tables(
+        table: &ElementRef,
+        config: &TableExtractionConfig,
+        out: &mut Vec<ExtractedTable>,
+    ) {
+        let extracted = Self::is_data_table_with_config(table, &config.scorer)
+            .then(|| Self::extract_table_with_config(table, config))
+            .filter(|t| config.scorer.meets_minimum_size(t));
+
+        if let Some(extracted) = extracted {
+            out.push(extracted);
+        } else if !config.unwrap_layout_tables {
+            // * Reject the whole structure, including any inner tables
+            return;
+        }
+
+        for inner in Self::direct_nested_tables(table) {
+            Self::collect_tables(&inner, config, out);
         }
     }
 
     /// Extracts all data tables from HTML content
     /// Only returns tables that meet the data table threshold
     pub fn extract_all_tables(html: &str) -> Vec<ExtractedTable> {
+        Self::extract_all_tables_with_config(html, &TableExtractionConfig::default())
+    }
+
+    /// Extracts all data tables using the given extraction settings
+    pub fn extract_all_tables_with_config(
+        html: &str,
+        config: &TableExtractionConfig,
+    ) -> Vec<ExtractedTable> {
         let document = Html::parse_document(html);
         let mut tables = Vec::new();
 
+        // * Start from top-level tables; nested ones are reached through collect_tables
         for table in document.select(&SELECTOR_TABLE) {
-            if Self::is_data_table(&table) {
-                tables.push(Self::extract_table(&table));
+            let is_top_level = !table
+                .ancestors()
+                .filter_map(ElementRef::wrap)
+                .any(|e| e.value().name() == "table");
+            if is_top_level {
+                Self::collect_tables(&table, config, &mut tables);
             }
         }
 
@@ -331,4 +880,383 @@ mod tests {
         assert_eq!(table.headers, vec!["Column A", "Column B"]);
         assert_eq!(table.rows.len(), 2);
     }
+
+    fn typed_table() -> ExtractedTable {
+        ExtractedTable {
+            headers: vec!["Product".into(), "Price".into(), "Released".into()],
+            rows: vec![
+                vec!["Widget, Large".into(), "$1,200.50".into(), "2024-01-15".into()],
+                vec!["Gadget \"Pro\"".into(), "15".into(), "2023-12-31".into()],
+                vec!["Gizmo".into(), "".into()],
+            ],
+            score: 5,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_column_type_sniffing() {
+        let table = typed_table();
+        assert_eq!(
+            table.column_types(),
+            vec![ColumnType::String, ColumnType::Numeric, ColumnType::Date]
+        );
+    }
+
+    #[test]
+    fn test_column_name_inference() {
+        let table = ExtractedTable {
+            headers: vec!["Name".into(), "".into(), "Name".into()],
+            rows: vec![vec!["a".into(), "b".into(), "c".into(), "d".into()]],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            table.column_names(),
+            vec!["Name", "column_2", "Name_2", "column_4"]
+        );
+    }
+
+    #[test]
+    fn test_to_csv_escapes_and_pads() {
+        let csv = typed_table().to_csv();
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+
+        assert_eq!(lines[0], "Product,Price,Released");
+        assert_eq!(lines[1], "\"Widget, Large\",\"$1,200.50\",2024-01-15");
+        assert_eq!(lines[2], "\"Gadget \"\"Pro\"\"\",15,2023-12-31");
+        assert_eq!(lines[3], "Gizmo,,");
+    }
+
+    #[test]
+    fn test_parse_iso_date_epoch_offsets() {
+        assert_eq!(parse_iso_date("1970-01-01"), Some(0));
+        assert_eq!(parse_iso_date("2000-03-01"), Some(11_017));
+        assert_eq!(parse_iso_date("2024-02-30"), None);
+        assert_eq!(parse_iso_date("15/01/2024"), None);
+    }
+
+    #[cfg(feature = "arrow-export")]
+    #[test]
+    fn test_to_arrow_record_batch() {
+        use arrow::array::{Array, Float64Array};
+        use arrow::datatypes::DataType;
+
+        let batch = typed_table().to_arrow().unwrap();
+
+        assert_eq!(batch.num_rows(), 3);
+        assert_eq!(batch.num_columns(), 3);
+        assert_eq!(batch.schema().field(1).data_type(), &DataType::Float64);
+        assert_eq!(batch.schema().field(2).data_type(), &DataType::Date32);
+
+        let prices = batch.column(1).as_any().downcast_ref::<Float64Array>().unwrap();
+        assert!((prices.value(0) - 1200.5).abs() < f64::EPSILON);
+        assert!(prices.is_null(2));
+    }
+
+    #[test]
+    fn test_colspan_expansion() {
+        let html = r#"
+            <table>
+                <thead><tr><th>Region</th><th colspan="2">Sales</th></tr></thead>
+                <tbody>
+                    <tr><td>North</td><td>10</td><td>20</td></tr>
+                    <tr><td colspan="3">Totals pending</td></tr>
+                </tbody>
+            </table>
+        "#;
+
+        let tables = TableScorer::extract_all_tables_unfiltered(html);
+        let table = &tables[0];
+
+        assert_eq!(table.headers, vec!["Region", "Sales", "Sales"]);
+        assert_eq!(table.rows[1], vec!["Totals pending"; 3]);
+        assert!(table.is_rectangular);
+    }
+
+    #[test]
+    fn test_rowspan_expansion() {
+        let html = r#"
+            <table>
+                <thead><tr><th>Group</th><th>Item</th><th>Qty</th></tr></thead>
+                <tbody>
+                    <tr><td rowspan="2">Fruit</td><td>Apple</td><td>3</td></tr>
+                    <tr><td>Pear</td><td>5</td></tr>
+                    <tr><td>Veg</td><td>Leek</td><td rowspan="2">1</td></tr>
+                    <tr><td>Veg</td><td>Kale</td></tr>
+                </tbody>
+            </table>
+        "#;
+
+        let tables = TableScorer::extract_all_tables_unfiltered(html);
+        let table = &tables[0];
+
+        assert_eq!(table.rows[0], vec!["Fruit", "Apple", "3"]);
+        assert_eq!(table.rows[1], vec!["Fruit", "Pear", "5"]);
+        assert_eq!(table.rows[3], vec!["Veg", "Kale", "1"]);
+        assert!(table.is_rectangular);
+    }
+
+    #[test]
+    fn test_ragged_rows_are_padded() {
+        let html = r#"
+            <table>
+                <thead><tr><th>A</th><th>B</th><th>C</th></tr></thead>
+                <tbody>
+                    <tr><td>1</td><td>2</td><td>3</td></tr>
+                    <tr><td>4</td></tr>
+                </tbody>
+            </table>
+        "#;
+
+        let tables = TableScorer::extract_all_tables_unfiltered(html);
+        let table = &tables[0];
+
+        assert!(!table.is_rectangular);
+        assert_eq!(table.rows[1], vec!["4", "", ""]);
+    }
+
+    #[test]
+    fn test_span_attributes_are_clamped() {
+        let html = r#"
+            <table>
+                <tr><th>A</th></tr>
+                <tr><td colspan="0">x</td><td colspan="abc">y</td><td colspan="99999">z</td></tr>
+            </table>
+        "#;
+
+        let tables = TableScorer::extract_all_tables_unfiltered(html);
+
+        // * 2 + MAX_COLSPAN columns, cut at the row width cap
+        assert_eq!(tables[0].rows[0].len(), MAX_GRID_COLUMNS);
+        assert!(!tables[0].is_rectangular);
+    }
+
+    #[test]
+    fn test_grid_expansion_is_bounded() {
+        // * Every span is within its own clamp, but the grid is not
+        let wide = r#"<td colspan="1000">x</td><td colspan="1000">y</td>"#;
+        let body = format!("<tr>{wide}</tr>").repeat(300);
+        let html = format!("<table><tr><th>A</th></tr>{body}</table>");
+
+        let tables = TableScorer::extract_all_tables_unfiltered(&html);
+        let table = &tables[0];
+
+        assert!(!table.is_rectangular);
+        assert!(table.rows.iter().all(|row| row.len() <= MAX_GRID_COLUMNS));
+        let cells: usize = table.rows.iter().map(Vec::len).sum::<usize>() + table.headers.len();
+        assert!(cells <= MAX_GRID_CELLS);
+        assert!(table.rows.len() < 300);
+    }
+
+    #[test]
+    fn test_caption_footer_and_units() {
+        let html = r#"
+            <table>
+                <caption> Quarterly shipments </caption>
+                <thead><tr><th>Item</th><th>Price ($)</th><th>Weight [kg]</th><th>Year (2024)</th></tr></thead>
+                <tbody>
+                    <tr><td>Crate</td><td>12.50</td><td>4.2</td><td>Q1</td></tr>
+                </tbody>
+                <tfoot><tr><td colspan="4">Prices exclude VAT</td></tr></tfoot>
+            </table>
+        "#;
+
+        let tables = TableScorer::extract_all_tables_unfiltered(html);
+        let table = &tables[0];
+
+        assert_eq!(table.caption.as_deref(), Some("Quarterly shipments"));
+        assert_eq!(table.rows.len(), 1);
+        assert_eq!(table.footer_rows, vec![vec!["Prices exclude VAT"; 4]]);
+        assert_eq!(
+            table.column_units,
+            vec![None, Some("$".to_string()), Some("kg".to_string()), None]
+        );
+    }
+
+    #[test]
+    fn test_footer_excluded_without_thead() {
+        let html = r#"
+            <table>
+                <tr><th>Name</th><th>Total</th></tr>
+                <tr><td>A</td><td>1</td></tr>
+                <tfoot><tr><td>Sum</td><td>1</td></tr></tfoot>
+            </table>
+        "#;
+
+        let tables = TableScorer::extract_all_tables_unfiltered(html);
+        let table = &tables[0];
+
+        assert_eq!(table.rows, vec![vec!["A", "1"]]);
+        assert_eq!(table.footer_rows, vec![vec!["Sum", "1"]]);
+        assert!(table.caption.is_none());
+    }
+
+    #[test]
+    fn test_detect_unit() {
+        assert_eq!(TableScorer::detect_unit("Speed (km/h)"), Some("km/h".to_string()));
+        assert_eq!(TableScorer::detect_unit("Share (%)"), Some("%".to_string()));
+        assert_eq!(TableScorer::detect_unit("Price"), None);
+        assert_eq!(TableScorer::detect_unit("Revenue (2023-2024)"), None);
+    }
+
+    #[test]
+    fn test_multi_row_header_flattening() {
+        let html = r#"
+            <table>
+                <thead>
+                    <tr><th rowspan="2">Region</th><th colspan="2">Q1</th><th colspan="2">Q2</th></tr>
+                    <tr><th>Revenue</th><th>Cost</th><th>Revenue</th><th>Cost</th></tr>
+                </thead>
+                <tbody>
+                    <tr><td>North</td><td>10</td><td>4</td><td>12</td><td>5</td></tr>
+                </tbody>
+            </table>
+        "#;
+
+        let tables = TableScorer::extract_all_tables_unfiltered(html);
+        let table = &tables[0];
+
+        assert_eq!(
+            table.headers,
+            vec!["Region", "Q1 / Revenue", "Q1 / Cost", "Q2 / Revenue", "Q2 / Cost"]
+        );
+        assert_eq!(table.rows, vec![vec!["North", "10", "4", "12", "5"]]);
+        assert!(table.is_rectangular);
+    }
+
+    #[test]
+    fn test_multi_row_header_without_thead() {
+        let html = r#"
+            <table>
+                <tr><th colspan="2">Size</th></tr>
+                <tr><th>Width</th><th>Height</th></tr>
+                <tr><td>3</td><td>4</td></tr>
+            </table>
+        "#;
+
+        let config = TableExtractionConfig {
+            flatten_multi_row_headers: false,
+            ..Default::default()
+        };
+        let document = Html::parse_document(html);
+        let table = document.select(&SELECTOR_TABLE).next().unwrap();
+        let extracted = TableScorer::extract_table_with_config(&table, &config);
+
+        assert_eq!(extracted.headers, vec!["Width", "Height"]);
+        assert_eq!(extracted.rows, vec![vec!["3", "4"]]);
+
+        let flattened = TableScorer::extract_table(&table);
+        assert_eq!(flattened.headers, vec!["Size / Width", "Size / Height"]);
+    }
+
+    fn layout_wrapped_html() -> &'static str {
+        r#"
+            <table role="presentation">
+                <tr>
+                    <td>Sidebar</td>
+                    <td>
+                        <table>
+                            <thead><tr><th>Name</th><th>Score</th></tr></thead>
+                            <tbody>
+                                <tr><td>Alice</td><td>91</td></tr>
+                                <tr><td>Bob</td><td>78</td></tr>
+                            </tbody>
+                        </table>
+                    </td>
+                </tr>
+            </table>
+        "#
+    }
+
+    #[test]
+    fn test_inner_table_extracted_from_layout_wrapper() {
+        let tables = TableScorer::extract_all_tables(layout_wrapped_html());
+
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].headers, vec!["Name", "Score"]);
+        assert_eq!(tables[0].rows.len(), 2);
+    }
+
+    #[test]
+    fn test_layout_wrapper_rejected_when_unwrapping_disabled() {
+        let config = TableExtractionConfig {
+            unwrap_layout_tables: false,
+            ..Default::default()
+        };
+
+        let tables = TableScorer::extract_all_tables_with_config(layout_wrapped_html(), &config);
+
+        assert!(tables.is_empty());
+    }
+
+    #[test]
+    fn test_outer_table_ignores_nested_rows() {
+        let document = Html::parse_document(layout_wrapped_html());
+        let outer = document.select(&SELECTOR_TABLE).next().unwrap();
+        let extracted = TableScorer::extract_table(&outer);
+
+        // * Only the wrapper's own single row is read; it becomes the header row
+        assert_eq!(extracted.headers.len(), 2);
+        assert!(extracted.rows.is_empty());
+    }
+
+    fn small_data_table_html() -> &'static str {
+        r#"
+            <table>
+                <thead><tr><th>Key</th><th>Value</th></tr></thead>
+                <tbody><tr><td>a</td><td>1</td></tr></tbody>
+            </table>
+        "#
+    }
+
+    #[test]
+    fn test_custom_threshold_controls_precision() {
+        let strict = TableExtractionConfig {
+            scorer: TableScorerConfig {
+                threshold: 50,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(TableScorer::extract_all_tables(small_data_table_html()).len(), 1);
+        assert!(TableScorer::extract_all_tables_with_config(small_data_table_html(), &strict).is_empty());
+    }
+
+    #[test]
+    fn test_custom_weights_change_score() {
+        let document = Html::parse_document(small_data_table_html());
+        let table = document.select(&SELECTOR_TABLE).next().unwrap();
+
+        let heavy_thead = TableScorerConfig {
+            thead_weight: 10,
+            ..Default::default()
+        };
+
+        let default_score = TableScorer::calculate_score(&table);
+        let custom_score = TableScorer::calculate_score_with_config(&table, &heavy_thead);
+        assert_eq!(custom_score - default_score, 8);
+    }
+
+    #[test]
+    fn test_min_rows_and_cols() {
+        let config = TableExtractionConfig {
+            scorer: TableScorerConfig {
+                min_rows: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(TableScorer::extract_all_tables_with_config(small_data_table_html(), &config).is_empty());
+
+        let config = TableExtractionConfig {
+            scorer: TableScorerConfig {
+                min_cols: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(TableScorer::extract_all_tables_with_config(small_data_table_html(), &config).len(), 1);
+    }
 }
@@ -0,0 +1,210 @@
+// * Site Template Learning
+// * Sites repeat the same blocks on every page: promos, newsletter pitches, author bios.
+// * The learner counts the text blocks the cleaner sees per domain and hands the ones that
+// * recur on most pages back to the cleaner as extra boilerplate.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use xxhash_rust::xxh64::xxh64;
+
+/// Template learning settings (`[refinery.templates]`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TemplateConfig {
+    /// Learn repeated blocks per domain and drop them from extracted content
+    pub enabled: bool,
+    /// Pages of a domain seen before any block is treated as template
+    pub min_pages: usize,
+    /// Share of the domain's pages a block must appear on (0.0 - 1.0)
+    pub min_share: f32,
+    /// Distinct blocks tracked per domain; blocks seen on one page only are evicted first
+    pub max_blocks_per_domain: usize,
+}
+
+impl Default for TemplateConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_pages: 5,
+            min_share: 0.6,
+            max_blocks_per_domain: 20_000,
+        }
+    }
+}
+
+/// Hashes a text block the way the learner counts it (case and whitespace insensitive)
+pub fn block_hash(text: &str) -> u64 {
+    let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    xxh64(normalized.as_bytes(), 0)
+}
+
+/// Blocks learned as template for one domain at one point in time
+#[derive(Debug, Clone, Default)]
+pub struct LearnedTemplate {
+    blocks: Arc<HashSet<u64>>,
+}
+
+impl LearnedTemplate {
+    /// Whether a text block is part of the template
+    pub fn contains(&self, text: &str) -> bool {
+        !self.blocks.is_empty() && self.contains_hash(block_hash(text))
+    }
+
+    pub fn contains_hash(&self, hash: u64) -> bool {
+        self.blocks.contains(&hash)
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+}
+
+#[derive(Debug, Default)]
+struct DomainBlocks {
+    pages: usize,
+    // * Block hash -> pages it appeared on
+    counts: HashMap<u64, usize>,
+    learned: LearnedTemplate,
+}
+
+/// Per-domain counts of the text blocks seen across pages
+///
+/// Shared by every document a `Refinery` processes; lookups return a snapshot, so the
+/// lock is only held while counting.
+///
+/// # Example
+/// ```ignore
+/// let learner = TemplateLearner::new(TemplateConfig { enabled: true, ..Default::default() });
+/// let template = learner.template("news.example.com");
+/// let (content, blocks) = cleaner.clean_with_template(html, Some("news.example.com"), &template);
+/// learner.observe("news.example.com", &blocks);
+/// ```
+#[derive(Debug, Default)]
+pub struct TemplateLearner {
+    config: TemplateConfig,
+    domains: Mutex<HashMap<String, DomainBlocks>>,
+}
+
+impl TemplateLearner {
+    pub fn new(config: TemplateConfig) -> Self {
+        Self {
+            config,
+            domains: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn config(&self) -> &TemplateConfig {
+        &self.config
+    }
+
+    /// Returns the blocks currently learned for `domain`
+    pub fn template(&self, domain: &str) -> LearnedTemplate {
+        self.domains
+            .lock()
+            .unwrap()
+            .get(domain)
+            .map(|blocks| blocks.learned.clone())
+            .unwrap_or_default()
+    }
+
+    /// Counts the block hashes of one page of `domain` and relearns its template
+    pub fn observe(&self, domain: &str, hashes: &[u64]) {
+        let mut domains = self.domains.lock().unwrap();
+        let blocks = domains.entry(domain.to_string()).or_default();
+        blocks.pages += 1;
+        // * A block repeated within one page still counts once
+        let distinct: HashSet<u64> = hashes.iter().copied().collect();
+        for hash in distinct {
+            *blocks.counts.entry(hash).or_default() += 1;
+        }
+        if blocks.counts.len() > self.config.max_blocks_per_domain {
+            blocks.counts.retain(|_, pages| *pages > 1);
+        }
+        blocks.learned = self.learn(blocks);
+    }
+
+    fn learn(&self, blocks: &DomainBlocks) -> LearnedTemplate {
+        if blocks.pages < self.config.min_pages.max(2) {
+            return LearnedTemplate::default();
+        }
+        let needed = (blocks.pages as f32 * self.config.min_share).ceil().max(2.0) as usize;
+        let learned: HashSet<u64> = blocks
+            .counts
+            .iter()
+            .filter(|(_, pages)| **pages >= needed)
+            .map(|(hash, _)| *hash)
+            .collect();
+        LearnedTemplate {
+            blocks: Arc::new(learned),
+        }
+    }
+
+    /// Pages counted for `domain`
+    pub fn pages(&self, domain: &str) -> usize {
+        self.domains.lock().unwrap().get(domain).map_or(0, |blocks| blocks.pages)
+    }
+
+    /// Forgets everything learned for `domain` (after a site redesign, say)
+    pub fn reset(&self, domain: &str) {
+        self.domains.lock().unwrap().remove(domain);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn learner(min_pages: usize) -> TemplateLearner {
+        TemplateLearner::new(TemplateConfig {
+            enabled: true,
+            min_pages,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_blocks_on_most_pages_are_learned() {
+        let learner = learner(3);
+        let promo = block_hash("Subscribe to our   weekly digest");
+        for page in 0..4u64 {
+            let mut hashes = vec![promo, 1_000 + page];
+            if page == 0 {
+                hashes.push(promo);
+            }
+            learner.observe("news.example.com", &hashes);
+            // * Nothing is learned before `min_pages`
+            assert_eq!(learner.template("news.example.com").is_empty(), page < 2);
+        }
+
+        let template = learner.template("news.example.com");
+        assert!(template.contains("subscribe to our weekly DIGEST"));
+        assert!(!template.contains_hash(1_001));
+        assert_eq!(template.len(), 1);
+        assert_eq!(learner.pages("news.example.com"), 4);
+        assert!(learner.template("other.example.com").is_empty());
+
+        learner.reset("news.example.com");
+        assert!(learner.template("news.example.com").is_empty());
+    }
+
+    #[test]
+    fn test_rare_blocks_are_evicted_first() {
+        let learner = TemplateLearner::new(TemplateConfig {
+            enabled: true,
+            min_pages: 2,
+            max_blocks_per_domain: 4,
+            ..Default::default()
+        });
+        learner.observe("a.example", &[7, 1, 2]);
+        learner.observe("a.example", &[7, 3, 4]);
+        // * Over the cap: the one-page blocks go, the shared one stays learned
+        let domains = learner.domains.lock().unwrap();
+        assert_eq!(domains["a.example"].counts.len(), 1);
+        assert!(domains["a.example"].learned.contains_hash(7));
+    }
+}
@@ -0,0 +1,209 @@
+// * XPath Queries over HTML
+// * Evaluates XPath 1.0 against scraper's lenient HTML parse by mirroring the DOM into an
+// * sxd document, so rule sets written for lxml or Scrapy port over without rewriting to CSS.
+// * Element results map back to scraper elements; everything else comes back as text.
+
+use scraper::{ElementRef, Html, Node};
+use std::collections::HashMap;
+use sxd_document::dom::{Document, Element};
+use sxd_document::Package;
+use sxd_xpath::nodeset::Node as XNode;
+use sxd_xpath::{Context, Factory, Value};
+use thiserror::Error;
+use tracing::debug;
+
+#[derive(Debug, Clone, Error)]
+#[error("Invalid XPath '{expression}': {message}")]
+pub struct XPathError {
+    pub expression: String,
+    pub message: String,
+}
+
+/// A value an XPath query selected
+#[derive(Debug, Clone, PartialEq)]
+pub enum XPathMatch<'a> {
+    /// An element node
+    Element(ElementRef<'a>),
+    /// An attribute or text node's value, or a string, number or boolean result
+    Value(String),
+}
+
+/// A validated XPath 1.0 expression
+///
+/// Compiled sxd expressions are neither `Send` nor `Sync`, so the query keeps its source
+/// and compiles it again per evaluation.
+///
+/// # Example
+/// ```ignore
+/// let query = XPathQuery::new("//div[@class='byline']/a/@href")?;
+/// let document = Html::parse_document(html);
+/// for found in query.select(&document) { ... }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XPathQuery {
+    expression: String,
+}
+
+impl XPathQuery {
+    pub fn new(expression: &str) -> Result<Self, XPathError> {
+        let error = |message: String| XPathError {
+            expression: expression.to_string(),
+            message,
+        };
+        match Factory::new().build(expression) {
+            Ok(Some(_)) => Ok(Self {
+                expression: expression.to_string(),
+            }),
+            Ok(None) => Err(error("empty expression".to_string())),
+            Err(e) => Err(error(e.to_string())),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.expression
+    }
+
+    /// Returns the query's matches in document order
+    pub fn select<'a>(&self, document: &'a Html) -> Vec<XPathMatch<'a>> {
+        select_many(document, &[self]).pop().unwrap_or_default()
+    }
+}
+
+/// Runs several queries over one conversion of the document
+///
+/// A query that fails at evaluation time (e.g. an unknown function) selects nothing.
+// * sxd nodes hash by node pointer, so their interior mutability never moves a map key
+#[allow(clippy::mutable_key_type)]
+pub fn select_many<'a>(document: &'a Html, queries: &[&XPathQuery]) -> Vec<Vec<XPathMatch<'a>>> {
+    let package = Package::new();
+    let mirror = package.as_document();
+    let elements = mirror_dom(document, &mirror);
+    let factory = Factory::new();
+    let context = Context::new();
+
+    queries
+        .iter()
+        .map(|query| {
+            let value = factory
+                .build(&query.expression)
+                .ok()
+                .flatten()
+                .map(|xpath| xpath.evaluate(&context, mirror.root()));
+            match value {
+                Some(Ok(value)) => matches_of(value, &elements),
+                Some(Err(e)) => {
+                    debug!(xpath = %query.expression, error = %e, "XPath evaluation failed");
+                    Vec::new()
+                }
+                None => Vec::new(),
+            }
+        })
+        .collect()
+}
+
+#[allow(clippy::mutable_key_type)]
+fn matches_of<'a>(
+    value: Value<'_>,
+    elements: &HashMap<Element<'_>, ElementRef<'a>>,
+) -> Vec<XPathMatch<'a>> {
+    match value {
+        Value::Nodeset(nodes) => nodes
+            .document_order()
+            .into_iter()
+            .map(|node| match node {
+                XNode::Element(element) => match elements.get(&element) {
+                    Some(original) => XPathMatch::Element(*original),
+                    None => XPathMatch::Value(node.string_value()),
+                },
+                other => XPathMatch::Value(other.string_value()),
+            })
+            .collect(),
+        Value::String(text) => vec![XPathMatch::Value(text)],
+        Value::Number(number) => vec![XPathMatch::Value(number.to_string())],
+        Value::Boolean(flag) => vec![XPathMatch::Value(flag.to_string())],
+    }
+}
+
+/// Copies elements, attributes and text into `mirror`, returning each copy's original element
+#[allow(clippy::mutable_key_type)]
+fn mirror_dom<'a, 'd>(document: &'a Html, mirror: &Document<'d>) -> HashMap<Element<'d>, ElementRef<'a>> {
+    let mut elements = HashMap::new();
+    // * Iterative so deeply nested pages cannot overflow the stack
+    let mut pending: Vec<_> = document.tree.root().children().rev().map(|node| (node, None::<Element>)).collect();
+    while let Some((node, parent)) = pending.pop() {
+        match node.value() {
+            Node::Element(element) => {
+                let Some(original) = ElementRef::wrap(node) else {
+                    continue;
+                };
+                let copy = mirror.create_element(element.name());
+                for (name, value) in element.attrs() {
+                    copy.set_attribute_value(name, value);
+                }
+                match parent {
+                    Some(parent) => parent.append_child(copy),
+                    None => mirror.root().append_child(copy),
+                }
+                elements.insert(copy, original);
+                pending.extend(node.children().rev().map(|child| (child, Some(copy))));
+            }
+            Node::Text(text) => {
+                if let Some(parent) = parent {
+                    parent.append_child(mirror.create_text(text));
+                }
+            }
+            // * Comments, doctypes and processing instructions are not queryable content
+            _ => {}
+        }
+    }
+    elements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html><body>
+        <div class="story">
+            <h1>Harbor Reopens</h1>
+            <p class="byline">By <a href="/staff/kim">Jo Kim</a></p>
+            <p>Ships returned to the harbor on Monday.</p>
+        </div>
+        <a class="next" href="?page=2">Next</a>
+    </body></html>"#;
+
+    #[test]
+    fn test_select_elements_attributes_and_values() {
+        let document = Html::parse_document(PAGE);
+
+        let heading = XPathQuery::new("//div[@class='story']/h1").unwrap().select(&document);
+        match heading.as_slice() {
+            [XPathMatch::Element(element)] => assert_eq!(element.text().collect::<String>(), "Harbor Reopens"),
+            other => panic!("expected one element, got {:?}", other),
+        }
+
+        let results = select_many(
+            &document,
+            &[
+                &XPathQuery::new("//a[@class='next']/@href").unwrap(),
+                &XPathQuery::new("string(//p[@class='byline']/a)").unwrap(),
+                &XPathQuery::new("count(//p)").unwrap(),
+                &XPathQuery::new("//table").unwrap(),
+            ],
+        );
+        assert_eq!(results[0], vec![XPathMatch::Value("?page=2".to_string())]);
+        assert_eq!(results[1], vec![XPathMatch::Value("Jo Kim".to_string())]);
+        assert_eq!(results[2], vec![XPathMatch::Value("2".to_string())]);
+        assert!(results[3].is_empty());
+    }
+
+    #[test]
+    fn test_invalid_expressions_are_rejected() {
+        assert!(XPathQuery::new("//div[@class=").is_err());
+        assert!(XPathQuery::new("").is_err());
+
+        // * Unknown functions only fail at evaluation time
+        let query = XPathQuery::new("//p[no-such-fn()]").unwrap();
+        assert!(query.select(&Html::parse_document(PAGE)).is_empty());
+    }
+}
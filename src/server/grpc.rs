@@ -0,0 +1,456 @@
+// * gRPC API
+// * Implements the TitanFlow service from proto/titan.proto: crawl submission, status and control,
+// * record lookup, and embedding similarity search against the orchestrator's store
+
+use crate::orchestrator::{CrawlConfig, CrawlJob, CrawlStore, JobError, JobManager, JobState, JobStatus, Orchestrator};
+use crate::persistence::{compute_embedding, MultimodalRecord, RecordFilter, RecordSearch, SimilarRecord};
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use tracing::info;
+
+/// Types and client/server stubs generated from proto/titan.proto
+pub mod proto {
+    tonic::include_proto!("titan.v1");
+}
+
+use proto::get_record_request::Key;
+use proto::titan_flow_server::{TitanFlow, TitanFlowServer};
+
+// * Matches returned by SearchSimilar when the request leaves limit at 0
+const DEFAULT_SEARCH_LIMIT: usize = 10;
+
+// * Upper bound on SearchSimilar matches
+const MAX_SEARCH_LIMIT: usize = 1000;
+
+// * Finished crawls kept for GetCrawlStatus; older ones are forgotten
+const RETAINED_CRAWLS: usize = 1000;
+
+/// gRPC service backed by an orchestrator and its record store
+///
+/// Each SubmitUrls call becomes a job on a `JobManager` with the service's base crawl
+/// config, so crawls get their own control handles and can be paused or cancelled; all
+/// crawls persist into the orchestrator's store.
+///
+/// # Example
+/// ```ignore
+/// let service = TitanFlowService::new(Orchestrator::new(), titan.crawl_config());
+/// serve(service, "0.0.0.0:50051".parse()?, shutdown).await?;
+/// ```
+pub struct TitanFlowService<S> {
+    orchestrator: Arc<Orchestrator<S>>,
+    config: CrawlConfig,
+    jobs: JobManager<S>,
+    next_job: AtomicU64,
+}
+
+impl<S> TitanFlowService<S>
+where
+    S: CrawlStore + RecordSearch + Send + Sync,
+{
+    /// Creates a service that crawls with `config` unless a request overrides its limits
+    pub fn new(orchestrator: Orchestrator<S>, config: CrawlConfig) -> Self {
+        let orchestrator = Arc::new(orchestrator);
+        Self {
+            jobs: JobManager::new(Arc::clone(&orchestrator)).with_retained_finished(RETAINED_CRAWLS),
+            orchestrator,
+            config,
+            next_job: AtomicU64::new(1),
+        }
+    }
+
+    /// Returns the jobs running the submitted crawls
+    pub fn jobs(&self) -> &JobManager<S> {
+        &self.jobs
+    }
+
+    /// Starts a crawl as a background job and returns its id
+    fn start_crawl(&self, seeds: Vec<String>, config: CrawlConfig) -> Result<String, JobError> {
+        let id = format!("crawl-{}", self.next_job.fetch_add(1, Ordering::Relaxed));
+        self.jobs.submit(CrawlJob::new(id.clone(), seeds, config))?;
+        Ok(id)
+    }
+
+    /// Applies a control action to a crawl and returns its updated status
+    fn control_crawl(
+        &self,
+        request: Request<proto::CrawlControlRequest>,
+        action: impl FnOnce(&JobManager<S>, &str) -> Result<(), JobError>,
+    ) -> Result<JobStatus, JobError> {
+        let crawl_id = request.into_inner().crawl_id;
+        action(&self.jobs, &crawl_id)?;
+        self.jobs.status(&crawl_id)
+    }
+}
+
+#[tonic::async_trait]
+impl<S> TitanFlow for TitanFlowService<S>
+where
+    S: CrawlStore + RecordSearch + Send + Sync,
+{
+    async fn submit_urls(
+        &self,
+        request: Request<proto::SubmitUrlsRequest>,
+    ) -> Result<Response<proto::SubmitUrlsResponse>, Status> {
+        let request = request.into_inner();
+        if request.urls.is_empty() {
+            return Err(Status::invalid_argument("urls must not be empty"));
+        }
+        if let Some(url) = request
+            .urls
+            .iter()
+            .find(|u| !(u.starts_with("http://") || u.starts_with("https://")))
+        {
+            return Err(Status::invalid_argument(format!("'{}' is not an http(s) URL", url)));
+        }
+
+        let mut config = self.config.clone();
+        if let Some(max_pages) = request.max_pages {
+            config.max_pages = (max_pages as usize).max(1);
+        }
+        if let Some(max_depth) = request.max_depth {
+            config.max_depth = max_depth as usize;
+        }
+
+        let crawl_id = self.start_crawl(request.urls, config).map_err(job_status)?;
+        Ok(Response::new(proto::SubmitUrlsResponse { crawl_id }))
+    }
+
+    async fn get_record(
+        &self,
+        request: Request<proto::GetRecordRequest>,
+    ) -> Result<Response<proto::GetRecordResponse>, Status> {
+        let store = self.orchestrator.store();
+        let record = match request.into_inner().key {
+            Some(Key::Id(id)) => store.get_record(&id).await,
+            Some(Key::Url(url)) => store.get_record_by_url(&url).await,
+            None => return Err(Status::invalid_argument("id or url is required")),
+        }
+        .map_err(|e| Status::internal(e.to_string()))?
+        .ok_or_else(|| Status::not_found("record not found"))?;
+
+        Ok(Response::new(proto::GetRecordResponse {
+            record: Some(record.into()),
+        }))
+    }
+
+    async fn search_similar(
+        &self,
+        request: Request<proto::SearchSimilarRequest>,
+    ) -> Result<Response<proto::SearchSimilarResponse>, Status> {
+        let request = request.into_inner();
+        let embedding = if !request.embedding.is_empty() {
+            request.embedding
+        } else if !request.text.trim().is_empty() {
+            compute_embedding(&request.text)
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?
+        } else {
+            return Err(Status::invalid_argument("embedding or text is required"));
+        };
+        let limit = match request.limit as usize {
+            0 => DEFAULT_SEARCH_LIMIT,
+            limit => limit.min(MAX_SEARCH_LIMIT),
+        };
+
+        let matches = self
+            .orchestrator
+            .store()
+            .search_similar(embedding, limit, RecordFilter::new())
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(proto::SearchSimilarResponse {
+            matches: matches.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn get_crawl_status(
+        &self,
+        request: Request<proto::GetCrawlStatusRequest>,
+    ) -> Result<Response<proto::GetCrawlStatusResponse>, Status> {
+        let status = self.jobs.status(&request.into_inner().crawl_id).map_err(job_status)?;
+        Ok(Response::new(status.into()))
+    }
+
+    async fn pause_crawl(
+        &self,
+        request: Request<proto::CrawlControlRequest>,
+    ) -> Result<Response<proto::GetCrawlStatusResponse>, Status> {
+        let status = self
+            .control_crawl(request, |jobs, id| jobs.pause(id).map(drop))
+            .map_err(job_status)?;
+        Ok(Response::new(status.into()))
+    }
+
+    async fn resume_crawl(
+        &self,
+        request: Request<proto::CrawlControlRequest>,
+    ) -> Result<Response<proto::GetCrawlStatusResponse>, Status> {
+        let status = self
+            .control_crawl(request, |jobs, id| jobs.resume(id).map(drop))
+            .map_err(job_status)?;
+        Ok(Response::new(status.into()))
+    }
+
+    async fn cancel_crawl(
+        &self,
+        request: Request<proto::CrawlControlRequest>,
+    ) -> Result<Response<proto::GetCrawlStatusResponse>, Status> {
+        let status = self
+            .control_crawl(request, |jobs, id| jobs.cancel(id))
+            .map_err(job_status)?;
+        Ok(Response::new(status.into()))
+    }
+}
+
+/// Maps a job error to the gRPC status returned to the client
+fn job_status(error: JobError) -> Status {
+    match error {
+        JobError::NotFound(id) => Status::not_found(format!("unknown crawl '{}'", id)),
+        JobError::Duplicate(_) => Status::already_exists(error.to_string()),
+        JobError::Finished(_) | JobError::Running(_) => Status::failed_precondition(error.to_string()),
+        JobError::Failed(..) => Status::internal(error.to_string()),
+    }
+}
+
+/// Serves the API on `addr` until `shutdown` completes
+pub async fn serve<S>(
+    service: TitanFlowService<S>,
+    addr: SocketAddr,
+    shutdown: impl Future<Output = ()>,
+) -> Result<(), tonic::transport::Error>
+where
+    S: CrawlStore + RecordSearch + Send + Sync,
+{
+    info!(%addr, "Serving gRPC API");
+    tonic::transport::Server::builder()
+        .add_service(TitanFlowServer::new(service))
+        .serve_with_shutdown(addr, shutdown)
+        .await
+}
+
+impl From<MultimodalRecord> for proto::Record {
+    fn from(record: MultimodalRecord) -> Self {
+        Self {
+            id: record.id.into(),
+            url: record.url,
+            content_hash: record.content_hash,
+            title: record.title,
+            text_content: record.text_content,
+            media_json: record.media_json,
+            embedding: record.embedding.unwrap_or_default(),
+            sentiment_score: record.sentiment_score,
+            word_count: record.word_count,
+            chunk_count: record.chunk_count,
+            quality_score: record.quality_score,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+            revision: record.revision,
+        }
+    }
+}
+
+impl From<SimilarRecord> for proto::SimilarRecord {
+    fn from(similar: SimilarRecord) -> Self {
+        Self {
+            record: Some(similar.record.into()),
+            score: similar.score,
+        }
+    }
+}
+
+impl From<JobStatus> for proto::GetCrawlStatusResponse {
+    fn from(status: JobStatus) -> Self {
+        let report = status.progress.report;
+        let mut response = Self {
+            crawl_id: status.id,
+            error: status.error.unwrap_or_default(),
+            started_at: status.started_at,
+            finished_at: status.finished_at.unwrap_or_default(),
+            pages_fetched: report.pages_fetched as u64,
+            pages_persisted: report.pages_persisted as u64,
+            duplicates_skipped: report.duplicates_skipped as u64,
+            fetch_errors: report.fetch_errors as u64,
+            links_enqueued: report.links_enqueued as u64,
+            interrupted: report.interrupted,
+            ..Default::default()
+        };
+        response.set_state(match status.state {
+            JobState::Running => proto::CrawlState::Running,
+            JobState::Paused => proto::CrawlState::Paused,
+            JobState::Completed => proto::CrawlState::Completed,
+            JobState::Cancelled => proto::CrawlState::Cancelled,
+            JobState::Failed => proto::CrawlState::Failed,
+        });
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::rate_limiter::CrawlDelayConfig;
+    use crate::orchestrator::test_support::{article, chain_article, MockFetcher};
+    use crate::persistence::InMemoryRecordStore;
+    use std::time::Duration;
+
+    fn service(fetcher: MockFetcher) -> TitanFlowService<Arc<InMemoryRecordStore>> {
+        let config = CrawlConfig {
+            max_pages: 1,
+            enable_slow_path: false,
+            enable_enrichment: false,
+            crawl_delay: CrawlDelayConfig {
+                standard_delay_ms: 0,
+                slow_path_delay_ms: 0,
+            },
+            ..Default::default()
+        };
+        TitanFlowService::new(Orchestrator::new().with_fetcher(Arc::new(fetcher)), config)
+    }
+
+    async fn crawl_status(
+        service: &TitanFlowService<Arc<InMemoryRecordStore>>,
+        crawl_id: &str,
+    ) -> proto::GetCrawlStatusResponse {
+        service
+            .get_crawl_status(Request::new(proto::GetCrawlStatusRequest {
+                crawl_id: crawl_id.to_string(),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+    }
+
+    #[tokio::test]
+    async fn test_submit_and_poll_crawl() {
+        let service = service(MockFetcher::default().page("https://example.com/", article("gardening", &[])));
+        let response = service
+            .submit_urls(Request::new(proto::SubmitUrlsRequest {
+                urls: vec!["https://example.com/".to_string()],
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let status = loop {
+            let status = crawl_status(&service, &response.crawl_id).await;
+            if status.state() != proto::CrawlState::Running {
+                break status;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+        assert_eq!(status.state(), proto::CrawlState::Completed);
+        assert_eq!(status.pages_persisted, 1);
+
+        let record = service
+            .get_record(Request::new(proto::GetRecordRequest {
+                key: Some(Key::Url("https://example.com/".to_string())),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .record
+            .unwrap();
+        assert!(record.text_content.contains("Compost heaps"));
+    }
+
+    #[tokio::test]
+    async fn test_pause_resume_and_cancel_crawl() {
+        let service = service(MockFetcher::default().generated(chain_article));
+        let crawl_id = service
+            .submit_urls(Request::new(proto::SubmitUrlsRequest {
+                urls: vec!["https://example.com/".to_string()],
+                max_pages: Some(10_000),
+                max_depth: Some(10_000),
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .crawl_id;
+        let control = |crawl_id: &str| {
+            Request::new(proto::CrawlControlRequest {
+                crawl_id: crawl_id.to_string(),
+            })
+        };
+
+        let paused = service.pause_crawl(control(&crawl_id)).await.unwrap().into_inner();
+        assert_eq!(paused.state(), proto::CrawlState::Paused);
+        let resumed = service.resume_crawl(control(&crawl_id)).await.unwrap().into_inner();
+        assert_eq!(resumed.state(), proto::CrawlState::Running);
+
+        service.cancel_crawl(control(&crawl_id)).await.unwrap();
+        service.jobs().wait(&crawl_id).await.unwrap();
+        let status = crawl_status(&service, &crawl_id).await;
+        assert_eq!(status.state(), proto::CrawlState::Cancelled);
+        assert!(status.interrupted);
+        assert!(status.finished_at >= status.started_at);
+
+        let err = service.cancel_crawl(control(&crawl_id)).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+        let err = service.pause_crawl(control("crawl-404")).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_requests() {
+        let service = service(MockFetcher::default());
+
+        let status = service
+            .submit_urls(Request::new(proto::SubmitUrlsRequest {
+                urls: vec!["ftp://example.com/".to_string()],
+                ..Default::default()
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+        let status = service
+            .get_crawl_status(Request::new(proto::GetCrawlStatusRequest {
+                crawl_id: "crawl-404".to_string(),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+
+        let status = service
+            .get_record(Request::new(proto::GetRecordRequest {
+                key: Some(Key::Id("missing".to_string())),
+            }))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+
+        let status = service
+            .search_similar(Request::new(proto::SearchSimilarRequest::default()))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_search_similar_by_text() {
+        let service = service(MockFetcher::default());
+        let store = service.orchestrator.store();
+        for text in ["rust async runtimes", "gardening in spring"] {
+            let mut record = MultimodalRecord::new(format!("https://example.com/{}", text.len()), 1, text.to_string());
+            record.embedding = Some(compute_embedding(text).await.unwrap());
+            store.add(record);
+        }
+
+        let matches = service
+            .search_similar(Request::new(proto::SearchSimilarRequest {
+                text: "gardening in spring".to_string(),
+                limit: 1,
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner()
+            .matches;
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].record.as_ref().unwrap().text_content, "gardening in spring");
+    }
+}
@@ -0,0 +1,631 @@
+// * REST Control Plane
+// * JSON API for steering a running crawl through its CrawlControl handle and for
+// * reading stored records
+//
+// * Routes:
+// *   GET  /status                      progress and full crawl report
+// *   GET  /frontier                    frontier depth, in-flight and parked pages
+// *   POST /pause                       stops dispatching pages for every domain
+// *   POST /resume                      resumes a paused crawl
+// *   POST /seeds                       {"urls": [...]} adds seeds to the running crawl
+// *   GET  /domains                     paused and quarantined domains and per-domain statistics
+// *   GET  /domains/:domain             one domain's statistics
+// *   POST /domains/:domain/pause       stops dispatching the domain's pages
+// *   POST /domains/:domain/quarantine  {"hours": N} holds the domain's pages for N hours
+// *   POST /domains/:domain/resume      ends a pause or quarantine and releases parked pages
+// *   GET  /records                 pages through records (offset, limit, filters)
+// *   GET  /records/:id             one record
+// *   POST /extract                 {"url": ..., "options": {...}} refines one page (extract_router)
+// *   GET  /domain-stats            fetch, ban and latency stats (sort, limit, min_fetches) (domain_stats_router)
+// *   GET  /domain-stats/:domain    one domain's fetch, ban and latency stats (domain_stats_router)
+
+use crate::ops::{DomainStats, DomainStatsQuery, DomainStatsRegistry};
+use crate::orchestrator::{
+    CrawlControl, CrawlProgress, DomainCrawlStats, ExtractError, ExtractOptions, Extractor,
+};
+use crate::persistence::{EnrichmentError, MultimodalRecord, RecordFilter, RecordProvider, RecordSearch};
+use crate::refinery::RefineryResult;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::info;
+
+// * Records returned by GET /records when no limit is given
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+// * Upper bound on the GET /records limit
+const MAX_PAGE_SIZE: usize = 1000;
+
+/// Errors returned as `{"error": "..."}` with a matching status code
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("{0}")]
+    BadRequest(String),
+
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] EnrichmentError),
+
+    #[error("{0}")]
+    Extract(#[from] ExtractError),
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Storage(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::Extract(ExtractError::InvalidUrl(_)) => StatusCode::BAD_REQUEST,
+            ApiError::Extract(ExtractError::Timeout(_)) => StatusCode::GATEWAY_TIMEOUT,
+            ApiError::Extract(_) => StatusCode::BAD_GATEWAY,
+        };
+        (status, Json(ErrorBody { error: self.to_string() })).into_response()
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+/// Shared handler state
+struct ApiState<S> {
+    control: Arc<CrawlControl>,
+    store: S,
+}
+
+impl<S: Clone> Clone for ApiState<S> {
+    fn clone(&self) -> Self {
+        Self {
+            control: Arc::clone(&self.control),
+            store: self.store.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct FrontierView {
+    running: bool,
+    paused: bool,
+    frontier_depth: usize,
+    in_flight: usize,
+    parked: usize,
+    dispatched: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct SeedsRequest {
+    urls: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct SeedsResponse {
+    accepted: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct CrawlPauseView {
+    paused: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct QuarantineRequest {
+    hours: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct DomainsView {
+    paused: Vec<String>,
+    /// Domain -> end of its quarantine (Unix seconds)
+    quarantined: BTreeMap<String, u64>,
+    domains: BTreeMap<String, DomainCrawlStats>,
+}
+
+#[derive(Debug, Serialize)]
+struct DomainView {
+    domain: String,
+    paused: bool,
+    /// End of the domain's quarantine (Unix seconds)
+    quarantined_until: Option<u64>,
+    stats: Option<DomainCrawlStats>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExtractRequest {
+    url: String,
+    #[serde(default)]
+    options: ExtractOptions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RecordsQuery {
+    offset: usize,
+    limit: Option<usize>,
+    include_deleted: bool,
+    enriched_only: bool,
+    updated_since: Option<u64>,
+}
+
+/// Builds the control plane routes for a crawl control handle and its record store
+///
+/// # Example
+/// ```ignore
+/// let control = Arc::new(CrawlControl::new());
+/// let orchestrator = Orchestrator::new().with_control(Arc::clone(&control));
+/// let app = router(control, orchestrator.store().clone());
+/// tokio::spawn(serve(app, "127.0.0.1:8080".parse()?, shutdown));
+/// ```
+pub fn router<S>(control: Arc<CrawlControl>, store: S) -> Router
+where
+    S: RecordProvider + RecordSearch + Clone + 'static,
+{
+    Router::new()
+        .route("/status", get(status::<S>))
+        .route("/frontier", get(frontier::<S>))
+        .route("/pause", post(pause_crawl::<S>))
+        .route("/resume", post(resume_crawl::<S>))
+        .route("/seeds", post(enqueue_seeds::<S>))
+        .route("/domains", get(domains::<S>))
+        .route("/domains/:domain", get(domain::<S>))
+        .route("/domains/:domain/pause", post(pause_domain::<S>))
+        .route("/domains/:domain/quarantine", post(quarantine_domain::<S>))
+        .route("/domains/:domain/resume", post(resume_domain::<S>))
+        .route("/records", get(list_records::<S>))
+        .route("/records/:id", get(get_record::<S>))
+        .with_state(ApiState { control, store })
+}
+
+/// Builds the on-demand extraction route (POST /extract)
+///
+/// Merge it into `router` or serve it alone as an extraction microservice.
+pub fn extract_router(extractor: Arc<Extractor>) -> Router {
+    Router::new()
+        .route("/extract", post(extract))
+        .with_state(extractor)
+}
+
+/// Builds the per-domain fetch statistics routes (GET /domain-stats)
+///
+/// # Example
+/// ```ignore
+/// let app = router(control, store).merge(domain_stats_router(alerts.domain_stats()));
+/// ```
+pub fn domain_stats_router(registry: Arc<DomainStatsRegistry>) -> Router {
+    Router::new()
+        .route("/domain-stats", get(query_domain_stats))
+        .route("/domain-stats/:domain", get(get_domain_stats))
+        .with_state(registry)
+}
+
+/// Serves a router on `addr` until `shutdown` completes
+pub async fn serve(
+    router: Router,
+    addr: SocketAddr,
+    shutdown: impl Future<Output = ()>,
+) -> Result<(), hyper::Error> {
+    let server = axum::Server::try_bind(&addr)?.serve(router.into_make_service());
+    info!(addr = %server.local_addr(), "Serving control plane API");
+    server.with_graceful_shutdown(shutdown).await
+}
+
+// * Pause state is read from the control handle, so it shows before the next dispatch round
+async fn status<S>(State(state): State<ApiState<S>>) -> Json<CrawlProgress> {
+    let mut progress = state.control.progress();
+    progress.paused = state.control.is_crawl_paused();
+    Json(progress)
+}
+
+async fn frontier<S>(State(state): State<ApiState<S>>) -> Json<FrontierView> {
+    let progress = state.control.progress();
+    Json(FrontierView {
+        running: progress.running,
+        paused: state.control.is_crawl_paused(),
+        frontier_depth: progress.frontier_depth,
+        in_flight: progress.in_flight,
+        parked: progress.parked,
+        dispatched: progress.dispatched,
+    })
+}
+
+async fn pause_crawl<S>(State(state): State<ApiState<S>>) -> Json<CrawlPauseView> {
+    state.control.pause_crawl();
+    Json(CrawlPauseView { paused: true })
+}
+
+async fn resume_crawl<S>(State(state): State<ApiState<S>>) -> Json<CrawlPauseView> {
+    state.control.resume_crawl();
+    Json(CrawlPauseView { paused: false })
+}
+
+async fn enqueue_seeds<S>(
+    State(state): State<ApiState<S>>,
+    Json(request): Json<SeedsRequest>,
+) -> Result<(StatusCode, Json<SeedsResponse>), ApiError> {
+    let accepted = state.control.enqueue_seeds(&request.urls);
+    if accepted == 0 {
+        return Err(ApiError::BadRequest("no http(s) URLs in request".to_string()));
+    }
+    Ok((StatusCode::ACCEPTED, Json(SeedsResponse { accepted })))
+}
+
+async fn domains<S>(State(state): State<ApiState<S>>) -> Json<DomainsView> {
+    Json(DomainsView {
+        paused: state.control.paused_domains(),
+        quarantined: state.control.quarantined_domains(),
+        domains: state.control.progress().report.domains,
+    })
+}
+
+async fn domain<S>(
+    State(state): State<ApiState<S>>,
+    Path(domain): Path<String>,
+) -> Result<Json<DomainView>, ApiError> {
+    let view = domain_view(&state.control, domain);
+    if view.stats.is_none() && !view.paused && view.quarantined_until.is_none() {
+        return Err(ApiError::NotFound(format!("no crawl activity for '{}'", view.domain)));
+    }
+    Ok(Json(view))
+}
+
+async fn pause_domain<S>(State(state): State<ApiState<S>>, Path(domain): Path<String>) -> Json<DomainView> {
+    state.control.pause_domain(&domain);
+    Json(domain_view(&state.control, domain))
+}
+
+async fn quarantine_domain<S>(
+    State(state): State<ApiState<S>>,
+    Path(domain): Path<String>,
+    Json(request): Json<QuarantineRequest>,
+) -> Result<Json<DomainView>, ApiError> {
+    let duration = Duration::try_from_secs_f64(request.hours * 3600.0)
+        .ok()
+        .filter(|duration| !duration.is_zero())
+        .ok_or_else(|| ApiError::BadRequest("hours must be a positive number".to_string()))?;
+    state.control.quarantine_domain(&domain, duration);
+    Ok(Json(domain_view(&state.control, domain)))
+}
+
+async fn resume_domain<S>(State(state): State<ApiState<S>>, Path(domain): Path<String>) -> Json<DomainView> {
+    state.control.resume_domain(&domain);
+    state.control.release_quarantine(&domain);
+    Json(domain_view(&state.control, domain))
+}
+
+async fn list_records<S>(
+    State(state): State<ApiState<S>>,
+    Query(query): Query<RecordsQuery>,
+) -> Result<Json<Vec<MultimodalRecord>>, ApiError>
+where
+    S: RecordProvider,
+{
+    let mut filter = RecordFilter::new()
+        .include_deleted(query.include_deleted)
+        .enriched_only(query.enriched_only);
+    if let Some(since) = query.updated_since {
+        filter = filter.updated_since(since);
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+    Ok(Json(state.store.fetch_records(filter, query.offset, limit).await?))
+}
+
+async fn get_record<S>(
+    State(state): State<ApiState<S>>,
+    Path(id): Path<String>,
+) -> Result<Json<MultimodalRecord>, ApiError>
+where
+    S: RecordSearch,
+{
+    state
+        .store
+        .get_record(&id)
+        .await?
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("record '{}' not found", id)))
+}
+
+async fn extract(
+    State(extractor): State<Arc<Extractor>>,
+    Json(request): Json<ExtractRequest>,
+) -> Result<Json<RefineryResult>, ApiError> {
+    Ok(Json(extractor.extract(&request.url, &request.options).await?))
+}
+
+async fn query_domain_stats(
+    State(registry): State<Arc<DomainStatsRegistry>>,
+    Query(query): Query<DomainStatsQuery>,
+) -> Json<Vec<DomainStats>> {
+    Json(registry.query(&query))
+}
+
+async fn get_domain_stats(
+    State(registry): State<Arc<DomainStatsRegistry>>,
+    Path(domain): Path<String>,
+) -> Result<Json<DomainStats>, ApiError> {
+    registry
+        .get(&domain)
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("no fetches recorded for '{}'", domain)))
+}
+
+fn domain_view(control: &CrawlControl, domain: String) -> DomainView {
+    let domain = domain.to_lowercase();
+    DomainView {
+        paused: control.is_paused(&domain),
+        quarantined_until: control.quarantined_until(&domain),
+        stats: control.domain_stats(&domain),
+        domain,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orchestrator::test_support::MockFetcher;
+    use crate::orchestrator::CrawlReport;
+    use crate::persistence::InMemoryRecordStore;
+    use serde_json::{json, Value};
+
+    // * Served by the extraction tests; every other URL fails
+    const TIDES: &str = r#"<html><head><title>Tides</title></head><body><article>
+        <p>Spring tides follow the new and full moon, when solar and lunar pulls align
+        and the tidal range grows noticeably wider along every coastline.</p>
+        </article></body></html>"#;
+
+    /// Serves the API on an ephemeral port and returns its base URL
+    fn start(control: Arc<CrawlControl>, store: Arc<InMemoryRecordStore>) -> String {
+        serve_router(router(control, store))
+    }
+
+    fn serve_router(router: Router) -> String {
+        let server = axum::Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0)))
+            .serve(router.into_make_service());
+        let base = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+        base
+    }
+
+    #[tokio::test]
+    async fn test_seed_and_domain_controls() {
+        let control = Arc::new(CrawlControl::new());
+        let base = start(Arc::clone(&control), Arc::new(InMemoryRecordStore::new()));
+        let client = reqwest::Client::new();
+
+        let response = client
+            .post(format!("{}/seeds", base))
+            .json(&json!({"urls": ["https://example.com/", "mailto:x@example.com"]}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::ACCEPTED);
+        assert_eq!(response.json::<Value>().await.unwrap()["accepted"], 1);
+        assert_eq!(control.take_seeds(), vec!["https://example.com/"]);
+
+        let response = client
+            .post(format!("{}/seeds", base))
+            .json(&json!({"urls": ["ftp://example.com/"]}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+
+        let view: Value = client
+            .post(format!("{}/domains/Example.com/pause", base))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(
+            view,
+            json!({"domain": "example.com", "paused": true, "quarantined_until": null, "stats": null})
+        );
+        assert!(control.is_paused("example.com"));
+
+        let domains: Value = reqwest::get(format!("{}/domains", base)).await.unwrap().json().await.unwrap();
+        assert_eq!(domains["paused"], json!(["example.com"]));
+
+        client
+            .post(format!("{}/domains/example.com/resume", base))
+            .send()
+            .await
+            .unwrap();
+        assert!(!control.is_paused("example.com"));
+        let response = reqwest::get(format!("{}/domains/example.com", base)).await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_crawl_pause_and_quarantine_controls() {
+        let control = Arc::new(CrawlControl::new());
+        let base = start(Arc::clone(&control), Arc::new(InMemoryRecordStore::new()));
+        let client = reqwest::Client::new();
+
+        let paused: Value = client.post(format!("{}/pause", base)).send().await.unwrap().json().await.unwrap();
+        assert_eq!(paused, json!({"paused": true}));
+        assert!(control.is_crawl_paused());
+        let status: Value = reqwest::get(format!("{}/status", base)).await.unwrap().json().await.unwrap();
+        assert_eq!(status["paused"], true);
+        client.post(format!("{}/resume", base)).send().await.unwrap();
+        assert!(!control.is_crawl_paused());
+
+        let view: Value = client
+            .post(format!("{}/domains/Slow.com/quarantine", base))
+            .json(&json!({"hours": 6}))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let until = control.quarantined_until("slow.com").unwrap();
+        assert_eq!(view["quarantined_until"], until);
+        assert!(control.is_held("slow.com"));
+        let domains: Value = reqwest::get(format!("{}/domains", base)).await.unwrap().json().await.unwrap();
+        assert_eq!(domains["quarantined"], json!({"slow.com": until}));
+
+        let response = client
+            .post(format!("{}/domains/slow.com/quarantine", base))
+            .json(&json!({"hours": -1}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+
+        client.post(format!("{}/domains/slow.com/resume", base)).send().await.unwrap();
+        assert!(!control.is_held("slow.com"));
+    }
+
+    #[tokio::test]
+    async fn test_progress_endpoints() {
+        let control = Arc::new(CrawlControl::new());
+        let mut report = CrawlReport::default();
+        report.domains.insert(
+            "example.com".to_string(),
+            DomainCrawlStats {
+                pages_dispatched: 3,
+                ..Default::default()
+            },
+        );
+        control.publish(CrawlProgress {
+            running: true,
+            frontier_depth: 7,
+            in_flight: 2,
+            dispatched: 3,
+            report,
+            ..Default::default()
+        });
+        let base = start(Arc::clone(&control), Arc::new(InMemoryRecordStore::new()));
+
+        let frontier: Value = reqwest::get(format!("{}/frontier", base)).await.unwrap().json().await.unwrap();
+        assert_eq!(
+            frontier,
+            json!({"running": true, "paused": false, "frontier_depth": 7, "in_flight": 2, "parked": 0, "dispatched": 3})
+        );
+
+        let domain: Value = reqwest::get(format!("{}/domains/example.com", base))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(domain["stats"]["pages_dispatched"], 3);
+
+        let status: Value = reqwest::get(format!("{}/status", base)).await.unwrap().json().await.unwrap();
+        assert_eq!(status["report"]["domains"]["example.com"]["pages_dispatched"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_record_endpoints() {
+        let store = Arc::new(InMemoryRecordStore::new());
+        for i in 0..3 {
+            store.add(MultimodalRecord::new(format!("https://example.com/{}", i), i, format!("Page {}", i)));
+        }
+        let id = store.records()[1].id.clone();
+        let base = start(Arc::new(CrawlControl::new()), store);
+
+        let records: Vec<MultimodalRecord> = reqwest::get(format!("{}/records?offset=1&limit=5", base))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].url, "https://example.com/1");
+
+        let record: MultimodalRecord = reqwest::get(format!("{}/records/{}", base, id))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(record.id, id);
+
+        let response = reqwest::get(format!("{}/records/missing", base)).await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+        let body: Value = response.json().await.unwrap();
+        assert!(body["error"].as_str().unwrap().contains("missing"));
+    }
+
+    #[tokio::test]
+    async fn test_domain_stats_endpoints() {
+        let registry = Arc::new(DomainStatsRegistry::new());
+        registry.record_outcome("example.com", true, false);
+        registry.record_outcome("banned.com", false, true);
+        registry.record_outcome("banned.com", false, true);
+        registry.record_latency("example.com", std::time::Duration::from_millis(250));
+        let base = serve_router(domain_stats_router(registry));
+
+        let stats: Vec<DomainStats> = reqwest::get(format!("{}/domain-stats?sort=fetches&limit=1", base))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].domain, "banned.com");
+        assert_eq!(stats[0].ban_count, 2);
+
+        let stats: DomainStats = reqwest::get(format!("{}/domain-stats/Example.com", base))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(stats.mean_latency_ms, Some(250));
+
+        let response = reqwest::get(format!("{}/domain-stats/missing.com", base)).await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+        let response = reqwest::get(format!("{}/domain-stats?sort=bogus", base)).await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_extract_endpoint() {
+        let fetcher = MockFetcher::default().page("https://example.com/article", TIDES);
+        let base = serve_router(extract_router(Arc::new(Extractor::with_fetcher(Arc::new(fetcher)))));
+        let client = reqwest::Client::new();
+
+        let response = client
+            .post(format!("{}/extract", base))
+            .json(&json!({"url": "https://example.com/article", "options": {"render": "never"}}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        let result: RefineryResult = response.json().await.unwrap();
+        assert_eq!(result.metadata.title.as_deref(), Some("Tides"));
+        assert!(result.content.text.contains("Spring tides"));
+
+        let response = client
+            .post(format!("{}/extract", base))
+            .json(&json!({"url": "not a url"}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+
+        let response = client
+            .post(format!("{}/extract", base))
+            .json(&json!({"url": "https://example.com/missing"}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::BAD_GATEWAY);
+    }
+}
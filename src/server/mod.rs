@@ -0,0 +1,14 @@
+// * Network Services
+// * Goal: Let other services drive Titan-Flow without linking the crate
+// * This module provides the gRPC API (`grpc` feature) and the REST control plane (`http-api` feature)
+
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "http-api")]
+pub mod http;
+
+// * Re-exports for convenient access
+#[cfg(feature = "grpc")]
+pub use grpc::{serve, TitanFlowService};
+#[cfg(feature = "http-api")]
+pub use http::{domain_stats_router, extract_router, router, ApiError};
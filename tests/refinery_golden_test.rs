@@ -0,0 +1,13 @@
+use titan_flow::refinery::GoldenHarness;
+
+// * Golden Extraction Regression Suite
+// * Fails with field-level diffs when refinery output drifts from tests/fixtures/golden;
+// * accept intended changes with `titan-flow golden --fixtures tests/fixtures/golden --update`
+
+#[test]
+fn test_refinery_matches_goldens() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/golden");
+    let report = GoldenHarness::new(dir).run().unwrap();
+    assert_eq!(report.fixtures.len(), 2);
+    assert!(report.is_clean(), "{}", report.render());
+}